@@ -0,0 +1,181 @@
+//! Throughput of each ingestion stage on synthetic productions, plus the end-to-end
+//! million-record case the app is expected to load and analyze in well under 30 seconds.
+//!
+//! Run with `cargo bench`, or `cargo bench -- analytics` for a single group.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use esubpoena_tolls_tool::{
+    AnalyticsEngine, Annotations, AppSettings, CallRecord, CallerNames, CarrierDirectory, CsvExporter, KnownNumbers, Pipeline,
+    PipelineConfig, ProcessedCallRecord, XmlParser,
+};
+use std::fmt::Write;
+use std::io::Cursor;
+use std::time::Duration;
+
+const SIZES: [usize; 2] = [10_000, 100_000];
+const MILLION: usize = 1_000_000;
+
+/// An LDS-101 production with `records` calls spread over 20,000 remote numbers and three targets.
+fn synthetic_production(records: usize) -> Vec<u8> {
+    let mut xml = String::with_capacity(records * 240);
+    xml.push_str("<dataProduct>");
+    for target in 0..3 {
+        let _ = write!(xml, "<xmlResult><targetType>MSISDN</targetType><targetValue>205555010{}</targetValue>\
+            <fromDate>2024-01-01T00:00:00Z</fromDate><toDate>2024-12-31T23:59:59Z</toDate>", target);
+        for i in (target..records).step_by(3) {
+            let start = 1_704_067_200 + i as i64 * 31;
+            let length = (i * 37) % 1800;
+            let _ = write!(xml, "<results><messageDirection>{}</messageDirection><remoteNumber>+1 (256) 555-{:04}</remoteNumber>\
+                <startTime>{}</startTime><endTime>{}</endTime><lengthOfCall>{}</lengthOfCall></results>",
+                if i % 2 == 0 { "Incoming" } else { "Outgoing" },
+                (i * 7919) % 20_000,
+                rfc3339(start),
+                rfc3339(start + length as i64),
+                length);
+        }
+        xml.push_str("</xmlResult>");
+    }
+    xml.push_str("</dataProduct>");
+    xml.into_bytes()
+}
+
+fn rfc3339(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .expect("synthetic timestamps are in range")
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+fn raw_records(xml: &[u8]) -> Vec<(String, CallRecord)> {
+    let mut records = Vec::new();
+    XmlParser::stream_call_records(xml, |header, record| {
+        records.push((header.target_value.clone(), record));
+        Ok(())
+    }).expect("synthetic production parses");
+    records
+}
+
+fn processed_records(xml: &[u8]) -> Vec<ProcessedCallRecord> {
+    let mut records = Vec::new();
+    Pipeline::run(Cursor::new(xml.to_vec()), "synthetic.xml".to_string(), &mut records, PipelineConfig::default())
+        .expect("synthetic production ingests");
+    records
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for size in SIZES {
+        let xml = synthetic_production(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &xml, |b, xml| {
+            b.iter(|| {
+                let mut count = 0;
+                XmlParser::stream_call_records(xml.as_slice(), |_, _| {
+                    count += 1;
+                    Ok(())
+                }).unwrap();
+                count
+            })
+        });
+    }
+    group.finish();
+}
+
+fn normalize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize");
+    for size in SIZES {
+        let raw = raw_records(&synthetic_production(size));
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &raw, |b, raw| {
+            b.iter(|| {
+                raw.iter()
+                    .filter_map(|(target, record)| ProcessedCallRecord::from_call_record(record, target, "synthetic.xml").ok())
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline");
+    group.sample_size(10);
+    for size in SIZES {
+        let xml = synthetic_production(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &xml, |b, xml| {
+            b.iter_batched(
+                || Cursor::new(xml.clone()),
+                |reader| {
+                    let mut records = Vec::new();
+                    Pipeline::run(reader, "synthetic.xml".to_string(), &mut records, PipelineConfig::default()).unwrap();
+                    records
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn analytics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analytics");
+    for size in SIZES {
+        let records = processed_records(&synthetic_production(size));
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &records, |b, records| {
+            b.iter(|| AnalyticsEngine::generate_analytics(records))
+        });
+    }
+    group.finish();
+}
+
+fn export(c: &mut Criterion) {
+    let settings = AppSettings::default();
+    let mut group = c.benchmark_group("export");
+    group.sample_size(10);
+    for size in SIZES {
+        let records = processed_records(&synthetic_production(size));
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("csv", size), &records, |b, records| {
+            b.iter(|| CsvExporter::write_call_records(std::io::sink(), records, &Annotations::default(), &CallerNames::default(), &settings).unwrap())
+        });
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let analytics = AnalyticsEngine::generate_analytics(&records);
+            let output_path = std::env::temp_dir().join(format!("esubpoena-bench-{}.xlsx", size));
+            group.bench_with_input(BenchmarkId::new("excel", size), &records, |b, records| {
+                b.iter(|| {
+                    esubpoena_tolls_tool::ExcelExporter::export_data(records, &[], &analytics, &Annotations::default(), &CarrierDirectory::default(), &CallerNames::default(), &KnownNumbers::default(), &settings, &[], &output_path).unwrap()
+                })
+            });
+            let _ = std::fs::remove_file(&output_path);
+        }
+    }
+    group.finish();
+}
+
+/// Parse, normalize, and analyze a million-record production, as loading a file in the app does.
+fn million_record_case(c: &mut Criterion) {
+    let xml = synthetic_production(MILLION);
+    let mut group = c.benchmark_group("case");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(60));
+    group.throughput(Throughput::Elements(MILLION as u64));
+    group.bench_function("load_and_analyze_1m", |b| {
+        b.iter_batched(
+            || Cursor::new(xml.clone()),
+            |reader| {
+                let mut records = Vec::new();
+                Pipeline::run(reader, "synthetic.xml".to_string(), &mut records, PipelineConfig::default()).unwrap();
+                AnalyticsEngine::generate_analytics(&records)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, normalize, pipeline, analytics, export, million_record_case);
+criterion_main!(benches);
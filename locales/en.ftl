@@ -0,0 +1,1203 @@
+### English UI and report text. Message ids are shared with es.ftl; keep both files in step.
+
+## Tabs
+
+tab-overview = Overview
+tab-call-records = Call Records
+tab-analytics = Analytics
+tab-bookmarks = Bookmarks
+tab-summary = Summary
+tab-data-quality = Data Quality
+tab-subscribers = Subscribers
+tab-scripts = Scripts
+tab-jobs = Jobs
+tab-logs = Logs
+tab-settings = Settings
+workspace-label = Cases:
+workspace-name = Case { $number }
+workspace-targets = { $target } +{ $count }
+workspace-new = Open another case in a new workspace
+workspace-close-hint = Close this workspace; unsaved review work in it is discarded
+workspace-compare = Compare with…
+
+## Shared buttons and labels
+
+button-export-excel = Export to Excel
+button-more-exports = More Exports
+button-undo-hint = Undo: { $change }
+button-undo-none = Nothing to undo
+button-redo-hint = Redo: { $change }
+button-redo-none = Nothing to redo
+button-export-grouped = Export Excel Grouped by Contact
+button-export-grouped-hint = Writes a workbook with each contact's calls grouped under an outline and followed by a subtotal of calls and duration, with a grand total at the bottom
+button-export-network = Export Network CSV
+button-export-network-hint = Writes nodes.csv (number, alias, calls, minutes) and edges.csv (source, target, weight) for Gephi or any other graph tool
+button-export-cellebrite = Export Cellebrite Call Log
+button-export-cellebrite-hint = Writes the records as a UFED call log CSV, with E.164 numbers and UTC times, for import into Physical Analyzer or Pathfinder next to a device extraction
+button-export-penlink = Export for PenLink
+button-export-penlink-hint = Writes the records in the layout of PenLink's generic call import, with digit-only numbers and times in the case timezone
+button-export-elastic = Export for Elasticsearch
+button-export-elastic-hint = Writes bulk-ingest NDJSON with normalized fields and case metadata, for indexing into an Elastic/Kibana stack
+button-export-parse-errors = Export Parse Errors
+button-export-parse-errors-hint = Writes a CSV of the malformed XML blocks and unreadable records in every production, with where and why each was skipped
+button-export-exhibits = Court Exhibit Package (PDF)
+button-export-exhibits-hint = Writes one PDF with a cover index and numbered, paginated exhibits: the summary, calls by hour and by day charts, and a call list for each top contact
+button-push-google-sheets = Push to Google Sheets
+button-push-google-sheets-hint = Replaces the Call Records, Analytics, and Summary Report tabs of the shared Google Sheet set on the Settings tab
+button-cancel = Cancel
+label-numbers = Numbers:
+value-minutes = { $minutes } min
+banner-skipped-records = { $skipped } of { $total } records could not be parsed
+banner-discrepancies =
+    { $files ->
+        [one] 1 production does not match its declared coverage and may be truncated
+       *[other] { $files } productions do not match their declared coverage and may be truncated
+    }
+banner-view-details = View details
+banner-dismiss = Dismiss
+
+## Table and sheet headers
+
+header-direction = Direction
+header-target = Target
+header-target-number = Target Number
+header-target-numbers = Target Numbers
+header-remote-number = Remote Number
+header-normalized-number = Normalized Number
+header-phone-number = Phone Number
+header-date = Date
+header-date-time = Date & Time
+header-duration-sec = Duration (sec)
+header-duration-min = Duration (min)
+header-duration = Duration
+header-start-time-zone = Start Time ({ $timezone })
+header-end-time-zone = End Time ({ $timezone })
+header-day-of-week = Day of Week
+header-disposition = Disposition
+header-source-file = Source File
+header-tags = Tags
+header-notes = Notes
+header-parser = Parser
+header-confidence = Confidence
+header-keywords = Keywords
+header-tagged = Tagged
+header-message = Message
+value-yes = Yes
+header-call-count = Call Count
+header-count = Count
+header-rank = Rank
+header-metric = Metric
+header-value = Value
+header-record-position = Record #
+header-raw-start = Start Time (as produced)
+header-reason = Reason
+header-line = Line
+header-element = Element
+header-problem = Problem
+problem-malformed-xml = Malformed XML
+problem-unreadable-record = Unreadable record
+header-counted-as = Counted As
+header-subscriber = Subscriber
+header-address = Address
+header-carrier = Carrier
+header-lrn = LRN
+header-cell-site-id = Cell Site ID
+header-sector = Sector
+header-latitude = Latitude
+header-longitude = Longitude
+header-distance-km = Distance (km)
+header-busiest-tower = Busiest Tower
+header-towers = Towers
+header-tower-changes = Tower Changes
+header-from-tower = From Tower
+header-to-tower = To Tower
+header-left-zone = Left ({ $timezone })
+header-arrived-zone = Arrived ({ $timezone })
+header-speed-kmh = Speed (km/h)
+header-implausible = Implausible
+header-first-call-zone = First Call ({ $timezone })
+header-last-call-zone = Last Call ({ $timezone })
+header-caller-name = Caller Name
+header-alias = Alias
+header-entity = Entity
+header-first-contact = First Contact
+header-last-contact = Last Contact
+header-first-contact-zone = First Contact ({ $timezone })
+header-last-contact-zone = Last Contact ({ $timezone })
+header-span-days = Span (days)
+header-ongoing = Ongoing
+header-device-subject = Number or Device
+header-device-id-kind = Identifier
+header-device-used-with = Used With
+header-first-seen = First Seen
+header-last-seen = Last Seen
+header-total-calls = Total Calls
+header-incoming = Incoming
+header-outgoing = Outgoing
+header-total-duration = Total Duration
+header-average-duration = Average Duration
+header-night-calls = Night Calls
+header-watchlist = Watchlist
+header-night-share = Night Share
+header-odd-hours-score = Odd-Hours Score
+header-messages-sent = Sent
+header-messages-received = Received
+header-category = Category
+header-prefix = NPA-NXX
+header-region = Region
+header-area-code = Area Code
+header-share = Share
+header-attempts = Answered / Calls Placed
+header-answer-rate = Answer Rate
+header-longest-unanswered = Longest Unanswered Run
+header-unanswered-since = Unanswered Since Last Answer
+header-missed-incoming = Missed Incoming
+header-numbers = Numbers
+
+heading-most-frequent = Most Frequent Numbers
+heading-exchange-clusters = Shared Exchanges
+heading-calls-by-region = Calls by Region
+heading-area-codes = Area Codes
+heading-answer-rates = Answer Rates
+heading-odd-hours = Odd-Hours Contacts
+heading-messages-per-contact = Messages per Contact
+heading-contact-spans = First and Last Contact
+heading-device-changes = Phone and SIM Changes
+heading-number-warnings = Suspicious Numbers
+heading-record-warnings = Suspicious Records
+heading-production-formats = Production Formats
+heading-known-numbers = Known Number Categories
+heading-entities = Entities
+heading-message-keywords = Message Keywords
+keywords-hint = Some productions include the text of messages. Search it for comma-separated keywords (letters match regardless of case); hits are highlighted in the Message column.
+keywords-input-hint = e.g. money, meet, package
+keywords-apply = Apply
+keywords-show-hits = Show Keyword Hits
+keywords-summary = { $count } messages mention a keyword; running totals per keyword by day ({ $timezone })
+heading-location-analysis = Location Analysis
+heading-tower-usage = Tower Usage by Day ({ $timezone })
+heading-dwell-periods = Dwell Periods
+heading-tower-changes = Tower Changes
+heading-implausible-moves = Implausible Travel
+heading-geofence = Geofence
+location-hint = Where each target's calls were served from. A dwell period is a run of calls on one tower with no call on another tower in between and no gap over { $hours } hours; a tower change is a move to another tower between consecutive calls. Sectors of one tower count as the same place.
+location-summary = { $towers } towers, { $dwells } dwell periods, and { $transitions } tower changes over { $days } target-days
+location-days-caption = Most recent days first ({ $timezone })
+location-show-movement = Show Dwell Periods and Tower Changes
+location-implausible = { $count } moves between towers imply travel faster than { $speed } km/h; they are listed with the tower changes and on the Data Quality tab
+implausible-moves-hint = Consecutive calls whose towers are too far apart for the time between them: even allowing { $range } km between the phone and each tower, it would have had to travel faster than { $speed } km/h. Usually a data error, or the number in use on more than one phone.
+geofence-hint = List the calls whose serving tower stood inside an area during a window of time. A tower is often a few kilometers from the phone, farther in rural areas. Times are in { $timezone }; a date alone takes in the whole day, and a blank time leaves that end open.
+geofence-radius = Radius
+geofence-box = Bounding box
+geofence-latitude = Latitude:
+geofence-longitude = Longitude:
+geofence-radius-km = Radius (km):
+geofence-south = South:
+geofence-west = West:
+geofence-north = North:
+geofence-east = East:
+geofence-from = From:
+geofence-to = To:
+geofence-apply = Apply
+geofence-clear = Clear
+geofence-show-calls = Show Calls
+geofence-applied = Searching { $geofence }
+geofence-summary = { $count } calls on towers { $geofence }
+geofence-radius-label = within { $radius } km of { $center }
+geofence-box-label = between { $southwest } and { $northeast }
+geofence-window-label = { $area }, { $from } to { $to } ({ $timezone })
+geofence-window-open = open
+geofence-invalid-coordinates = Enter latitude and longitude in decimal degrees, e.g. 34.7304 and -86.5861
+geofence-invalid-box = The south edge must be below the north edge
+geofence-invalid-radius = Enter a radius in kilometers greater than zero
+geofence-invalid-time = Cannot read "{ $time }"; use 2024-03-01 18:30 or 2024-03-01
+geofence-invalid-window = The window ends before it starts
+heading-calls-by-day = Calls by Day ({ $timezone })
+heading-calls-by-hour = Calls by Hour ({ $timezone })
+
+## Call records table
+
+column-direction = Direction
+column-remote-number = Remote Number
+column-normalized = Normalized
+column-date = Date (UTC)
+column-time = Time (UTC)
+column-local-time = Local Time
+column-duration = Duration (min)
+column-subscriber = Subscriber
+column-carrier = Carrier
+column-region = Region
+column-caller-name = Caller Name
+column-alias = Alias
+column-category = Category
+column-message = Message
+column-cell-site = Cell Site
+column-tower-location = Tower Location
+column-device = Device (IMEI / IMSI)
+column-tags = Tags
+
+chip-incoming-only = Incoming only
+chip-outgoing-only = Outgoing only
+chip-long-calls = > 5 min
+chip-night-hours = Night hours
+chip-watchlist-hits = Watchlist hits
+chip-tagged = Tagged
+chip-keyword-hits = Keyword hits
+chip-chart-day = Day: { $date }
+chip-chart-hour = Hour: { $hour }
+chip-chart-clear-hint = Picked on a chart; click to remove
+
+phone-format-raw = Raw digits
+region-north-america = United States / Canada
+region-mexico = Mexico
+region-guatemala = Guatemala
+region-el-salvador = El Salvador
+region-honduras = Honduras
+region-colombia = Colombia
+region-brazil = Brazil
+region-united-kingdom = United Kingdom
+region-spain = Spain
+region-france = France
+region-germany = Germany
+region-india = India
+region-china = China
+country-canada = Canada
+country-antigua = Antigua and Barbuda
+country-anguilla = Anguilla
+country-barbados = Barbados
+country-bermuda = Bermuda
+country-bahamas = Bahamas
+country-dominica = Dominica
+country-dominican-republic = Dominican Republic
+country-grenada = Grenada
+country-jamaica = Jamaica
+country-st-kitts = Saint Kitts and Nevis
+country-cayman-islands = Cayman Islands
+country-st-lucia = Saint Lucia
+country-montserrat = Montserrat
+country-sint-maarten = Sint Maarten
+country-turks-caicos = Turks and Caicos Islands
+country-trinidad = Trinidad and Tobago
+country-st-vincent = Saint Vincent and the Grenadines
+country-british-virgin-islands = British Virgin Islands
+
+records-empty = No call records loaded. Please process an XML file first.
+records-showing = Showing { $total } call records
+records-showing-filtered = Showing { $shown } of { $total } call records
+records-more = ... and { $count } more records
+records-show-local-time = Show local time
+records-reset-columns = Reset columns
+records-reset-columns-hint = Restore the default column order and widths
+records-select-shown-hint = Select or deselect the records shown
+selection-count = { $count } selected
+selection-tag = Tag:
+selection-tag-hint = Custom tag
+selection-add-tag = Add Tag
+selection-remove-tag = Remove Tag
+selection-note = Note:
+selection-note-hint = Blank removes the note
+selection-set-note = Set Note
+selection-clear = Clear Selection
+tag-relevant = Relevant
+tag-alibi-window = Alibi window
+
+menu-add-watchlist = Add to watchlist
+menu-remove-watchlist = Remove from watchlist
+menu-tag-record = Tag record
+menu-remove-tag = Remove tag
+menu-record-details = Record details
+menu-contact-drilldown = Contact drilldown
+
+bookmark-add = Bookmark this record
+bookmark-remove = Remove bookmark
+
+filter-label = Filter:
+filter-hint-text = number, date, direction...
+filter-hint-regex = regex, e.g. ^256|Sunday
+filter-regex = Regex
+filter-regex-hint = Match the filter as a regular expression against every column
+filter-from = From:
+filter-to = To:
+filter-clear = Clear
+filter-tag = Tag:
+filter-any-tag = Any
+filter-invalid-regex = Invalid regex: { $error }
+
+## Overview
+
+overview-welcome = Welcome to eSubpoena Tolls Tool
+overview-intro = This tool processes telecommunication XML data and provides comprehensive analytics.
+overview-new-case = New case…
+overview-new-case-hint = Set up a case step by step: its details, timezone and night hours, the productions to load, and how to handle duplicate records
+overview-add-productions = Add productions…
+overview-add-productions-hint = Add more productions, e.g. another target's records, to the loaded case and re-run the analytics over everything
+overview-case-number = Case No. { $number }
+overview-case-analyst = Analyst: { $analyst }
+overview-quick-stats = Quick Statistics
+overview-case-file = Case File
+overview-case-file-hint = Path to a case file or exported workbook, e.g. cases/2024-118.case.json
+overview-save-case = Save Case
+overview-open-case = Open
+overview-open-case-tooltip = Open a saved case file, or rebuild a session from an .xlsx workbook this tool exported
+overview-compare-case = Compare with Case
+overview-show-comparison = Show Comparison
+overview-verify-evidence = Verify Evidence
+overview-verify-evidence-tooltip = Re-hash the productions this case was built from and confirm they still match the SHA-256 recorded at import
+overview-subscribers = Subscriber results: { $count } numbers resolved
+overview-subscribers-hint = CSV with Number, Name, and Address columns
+overview-import-subscribers = Import Subscribers
+overview-contacts = Aliases from contact lists: { $count }
+overview-contacts-hint = Path to a contact export (.vcf) or CSV of known associates
+overview-import-contacts = Import Contacts
+overview-show-contact-matches = Show Matches
+
+drop-zone-idle =
+    Drag and drop XML files here
+    or click to browse
+drop-zone-hover = Drop XML files here
+drop-zone-processing = Processing...
+dialog-open-productions = Open productions
+dialog-add-productions = Add productions to the case
+filter-productions = Productions (XML, JSON, CSV, TSV, PDF, Excel, ZIP)
+filter-xml = XML productions
+
+status-idle = Ready to process files
+status-processing = Processing...
+status-completed = Processing completed
+status-error = Error: { $error }
+
+stat-total-calls = Total Calls
+stat-incoming = Incoming
+stat-outgoing = Outgoing
+stat-unique-numbers = Unique Numbers
+stat-unknown-numbers = Unknown Caller ID
+stat-short-codes = Short Codes and Service Numbers
+stat-short-codes-hint = Calls with short codes, feature codes, and voicemail, counted in the totals but not ranked as contacts (Settings → Short codes)
+stat-sms = SMS
+stat-mms = MMS
+stat-total-duration = Total Duration
+stat-avg-duration = Avg Duration
+
+## Analytics and drilldowns
+
+analytics-heading = Analytics Dashboard
+analytics-timeline = Timeline
+analytics-exchange-clusters-hint = Exchanges with 3 or more contacted numbers, often prepaid phones bought together. Hover a count for the numbers.
+analytics-calls-by-region-hint = Calls with North American numbers by the state, province, or country of the area code. Numbers keep their area code when ported or moved, so this is where a number was issued.
+analytics-area-codes-hint = Contacted North American numbers grouped by area code, with the share of their calls.
+analytics-contact-spans-hint = When the target was first and last in touch with each contact. Contact is ongoing when the last call with a target was within { $days } days of the end of that target's records. Click a number for its calls.
+analytics-sort-hint = Click to sort; click again to reverse
+area-code-concentration = Of { $located } calls with North American numbers from a known area, { $region_share }% are with { $region } numbers, { $area_code_share }% with area code { $area_code } alone.
+area-code-non-geographic = Non-geographic (toll-free, ...)
+analytics-answer-rates-hint = Contacts the target called { $attempts } or more times. Calls of { $seconds } seconds or less count as unanswered; ⚠ marks contacts that picked up before but left the last { $streak } or more calls unanswered.
+analytics-stopped-answering-hint = Stopped picking up the target's calls. Click for the contact's calls.
+analytics-odd-hours-hint = Contacts ranked by how much of their calls fall between { $from } and { $to } ({ $timezone }): the night share weighted by the number of night calls, so steady late-night contact outranks a single call at 2 AM.
+analytics-device-changes-hint = Target numbers seen on more than one handset (IMEI) or SIM (IMSI), and handsets or SIMs seen with more than one target number, from the device identifiers in the productions.
+analytics-answered-of = { $answered } / { $attempts }
+analytics-unanswered-since = { $count } since { $date }
+analytics-never-answered = never answered
+analytics-cnam-lookup = 🔎 Look up caller names
+analytics-cnam-lookup-hint = Sends up to { $limit } of the busiest contacts without a name to the lookup service set in Settings
+analytics-unidentified-hint = Unidentified: not in the contact lists, subscriber results, or caller names
+analytics-call-durations = Call Durations
+analytics-cumulative = Cumulative
+analytics-drilldown-hint = Click for contact drilldown
+analytics-targets = Targets included:
+analytics-targets-hint = Uncheck to leave this target out of the totals and common contacts; its records stay loaded.
+analytics-empty = No analytics available. Please process an XML file first.
+analytics-entities-hint = Group numbers that belong to one person, such as an old and a new phone. Records keep their own numbers; the table combines their calls.
+entity-name = Entity
+entity-numbers = Numbers
+entity-numbers-hint = Comma-separated, e.g. 256-555-0100, 256-555-0199
+entity-merge = Merge
+entity-remove-number-hint = Take this number out of the entity
+entity-dissolve = Dissolve
+entity-dissolve-hint = Remove the entity; its numbers stay in the records
+
+detail-record-title = Record Detail
+detail-contact-title = Contact: { $number }
+detail-timeline-title = Call Timeline
+detail-call-durations-title = Call Durations
+detail-cumulative-title = Cumulative Calls
+detail-skipped-title = Import Issues
+detail-comparison-title = Cross-Case Comparison
+detail-diff-title = Production Diff
+detail-evidence-title = Evidence Verification
+detail-keyword-hits-title = Keyword Hits
+detail-geofence-title = Geofence Calls
+detail-tower-movement-title = Tower Movement
+detail-contact-matches-title = Known Contacts
+detail-contact-matches-empty = Load records to match them against the contact lists.
+contact-matches-matched = Loaded numbers in the contact lists: { $count }
+contact-matches-unidentified = Frequent numbers still unidentified: { $count }
+contact-matches-unidentified-hint = Most frequent numbers with no alias, subscriber result, or caller name.
+detail-dock = Dock in main window
+detail-pop-out = Pop out
+detail-pop-out-hint = Open in a separate window
+detail-record-missing = This record is no longer loaded.
+detail-start-utc = Start (UTC)
+detail-end-utc = End (UTC)
+detail-start-local = Start (local)
+detail-duration = Duration
+detail-duration-value = { $seconds } sec ({ $minutes } min)
+detail-contact-missing = No calls with this number are loaded.
+detail-contact-calls = Total calls: { $total } ({ $incoming } incoming, { $outgoing } outgoing)
+detail-contact-duration = Total duration: { $minutes } min
+detail-contact-subscriber = Subscriber: { $name }, { $address }
+detail-contact-carrier = Carrier: { $carrier }
+detail-contact-carrier-ported = Carrier: { $carrier } (ported, LRN { $lrn })
+detail-contact-region = Region: { $region }
+detail-contact-caller-name = Caller ID name: { $name }
+detail-contact-name = Name:
+detail-contact-name-hint = e.g. Pat Doe (supplier)
+detail-contact-name-save = Save Name
+detail-contact-entity = Entity: { $entity }
+detail-contact-categories = Known number: { $categories }
+detail-first-contact = First contact: { $time }
+detail-last-contact = Last contact: { $time }
+detail-timeline-empty = No analytics available.
+detail-timeline-caption = Calls per day ({ $timezone })
+chart-interaction-hint = Drag across to zoom, scroll sideways (or Shift+scroll) to pan, right-click to zoom out. Click to show those records.
+detail-call-durations-caption = Calls with the most frequent numbers, from { $from } to { $to } UTC. Orange bars overlap another call on the same target ({ $overlapping } calls).
+detail-call-durations-call = { $start } UTC, { $minutes } min
+detail-call-durations-overlap = Overlaps another call on the same target
+detail-cumulative-total = All calls, running total per day ({ $timezone })
+detail-cumulative-contacts = Top { $count } contacts
+detail-skipped-summary = { $skipped } of { $total } records could not be parsed and are not in the totals.
+detail-malformed-summary = { $count } <results> blocks were malformed XML and were skipped:
+detail-skipped-truncated = Showing the first { $shown }.
+detail-comparison-empty = No comparison has been run.
+detail-diff-empty = No productions have been compared.
+diff-heading = Compare Productions
+diff-hint = When a carrier re-produces records for the same period (a corrected or supplemental return), compare the two files to document what was added, removed, or corrected.
+diff-original-label = Original:
+diff-corrected-label = Corrected:
+diff-compare = Compare
+diff-show = Show Diff
+diff-save-report = Save Diff Report
+diff-copy-report = Copy Report
+diff-title = PRODUCTION DIFF
+diff-original = Original production: { $name }
+diff-corrected = Corrected production: { $name }
+diff-counts = { $unchanged } unchanged, { $added } added, { $removed } removed, { $changed } corrected
+diff-identical = The two productions contain the same records.
+diff-added-heading = Added in the corrected production ({ $count })
+diff-removed-heading = Removed from the corrected production ({ $count })
+diff-changed-heading = Time or duration corrected ({ $count })
+diff-record = { $time } { $direction } { $number } (target { $target }), { $seconds } sec
+diff-correction = now { $record } (start moved { $shift } sec, duration changed { $duration } sec)
+diff-header-original-time = Original Time
+diff-header-corrected-time = Corrected Time
+diff-header-original-duration = Original (sec)
+diff-header-corrected-duration = Corrected (sec)
+detail-evidence-empty = No evidence verification has been run.
+evidence-title = EVIDENCE VERIFICATION
+evidence-case-file = Case file: { $path }
+evidence-verified-at = Verified at: { $time }
+evidence-counts = { $verified } of { $total } source files match their recorded hash
+evidence-no-sources = The case file records no source files.
+evidence-recorded-hash = Recorded SHA-256: { $hash }
+evidence-current-hash = Current SHA-256: { $hash }
+evidence-found-at = Found at: { $path }
+evidence-status-verified = Verified
+evidence-status-altered = ALTERED since import
+evidence-status-missing = Not found
+evidence-status-unreadable = Could not be read: { $error }
+evidence-header-file = Source File
+evidence-header-status = Status
+evidence-header-sha256 = Recorded SHA-256
+evidence-header-found-at = Found At
+detail-keyword-hits-empty = No keywords have been set for this case.
+detail-geofence-empty = No geofence has been set for this case.
+detail-tower-movement-empty = No records have a tower location or cell site ID.
+comparison-current-range = This case: { $range }
+comparison-other-range = { $name }: { $range }
+comparison-overlap = Both cases cover: { $range }
+comparison-no-overlap = no overlap
+comparison-shared-common-contacts = Common contacts in both cases ({ $count }):
+comparison-shared-numbers = Numbers in both cases ({ $count }):
+comparison-header-current = This Case
+comparison-header-both-active = Active in Both
+comparison-target = target
+discrepancy-outside-range = { $count } records fall outside the declared range { $from } – { $to }.
+discrepancy-fewer-records = The production declares { $declared } records but contains { $found }.
+
+## Bookmarks
+
+bookmarks-empty = No bookmarked records. Click the ☆ next to a call record to bookmark it.
+bookmarks-count =
+    { $count ->
+        [one] 1 bookmarked record (included in the summary report and Excel export)
+       *[other] { $count } bookmarked records (included in the summary report and Excel export)
+    }
+bookmarks-show-details = Show record details
+
+## Summary
+
+summary-heading = Summary Report
+summary-copy = Copy to Clipboard
+summary-copy-digest = Copy Findings for Email
+summary-copy-digest-hint = Copies a short digest of the top contacts, key dates, calls between targets, and the files to attach, for an update email to the case agent
+summary-empty = No summary available. Please process an XML file first.
+summary-save-as = Save as { $format }
+summary-sections = Report Sections
+summary-sections-hint = Checked sections appear in the report in this order, in every format and in the Excel Summary Report sheet.
+summary-sections-save = Save Layout
+summary-sections-reset = Reset to Default
+summary-template = Narrative Template
+summary-template-hint = Your standard narrative or affidavit language, with placeholders the report fills in from this case. It appears as the Narrative section.
+summary-template-placeholders = Placeholders: { $placeholders } (top contacts go up to the ranking size)
+summary-template-example = Analysis of the toll records for { "{{targets}}" } covering { "{{date_range}}" } shows { "{{total_calls}}" } calls with { "{{unique_numbers}}" } unique numbers. The most frequent contact was { "{{top_contact_1}}" } with { "{{top_contact_1_calls}}" } calls, and { "{{night_call_pct}}" } of calls took place at night.
+summary-template-unknown = Unknown placeholders, left as written: { $placeholders }
+summary-template-save = Save Template
+summary-template-insert-example = Insert Example
+summary-template-insert-findings = Insert Key Findings
+summary-template-insert-findings-hint = Appends the generated key findings to the template as plain text, to edit into the narrative
+report-format-text = Text
+report-format-pdf = PDF
+report-format-word = Word
+section-summary-stats = Summary statistics
+section-narrative = Narrative
+section-key-findings = Key findings
+section-top-contacts = Top contacts
+section-targets = Target numbers
+section-common-contacts = Common contacts
+section-exchange-clusters = Shared exchanges
+section-night-activity = Night activity
+section-bookmarks = Bookmarked records
+section-calls-by-day = Calls by day
+section-calls-by-hour = Calls by hour
+section-data-quality = Data quality
+quality-empty = No data loaded. Process an XML file to see its completeness.
+quality-heading = Completeness of each production, as a share of the records it contains:
+quality-title = Data Quality
+quality-overall = All productions
+quality-records = Records
+quality-loaded = Loaded
+quality-parse-failures = Parse Failures
+quality-valid-timestamps = Valid Timestamps
+quality-with-direction = With Direction
+quality-zero-duration = Zero Duration
+quality-unusable-numbers = Unknown or Invalid Numbers
+quality-duplicates = Duplicates
+quality-collapsed-duplicates = In Another Production
+quality-collapsed-duplicates-hint = Calls left out because an earlier production of the case already had them (Settings → Cross-file duplicates)
+number-warnings-hint = Remote values that look like junk rather than a party: padded or placeholder digits, too few digits to dial, or codes such as voicemail access. Exclude them from the analytics, or change how they are counted.
+number-warnings-exclude = Exclude
+number-warnings-exclude-hint = Add to the excluded numbers in settings, leaving its calls out of the analytics for every case
+number-warnings-excluded = Excluded
+record-warnings-hint = Records that read but look wrong: ending before they start, a duration that disagrees with the start and end times by more than a minute, no remote number at all, or a start in the future. They count in the analytics unless left out below.
+record-warnings-exclude = Leave the { $count } flagged records out of the analytics
+record-warnings-excluded = { $count } records left out of the current analytics
+record-warnings-truncated = Showing the first { $shown } flagged records
+record-issue-end-before-start = Ends before it starts
+record-issue-duration-mismatch = Duration disagrees with start and end
+record-issue-missing-remote = No remote number
+record-issue-future-date = In the future
+number-issue-not-a-number = Not a number
+number-issue-padded = Padded or placeholder digits
+number-issue-too-short = Too few digits
+production-formats-hint = The parser each production was read with, and how sure detection was that it fits. If records look wrong, for example with day and month swapped, read the production again with another parser.
+button-reparse-as = Re-parse as…
+button-map-columns = Map columns…
+parser-lds101-xml = LDS-101 XML
+parser-json = LDS-101 JSON
+parser-lds102-xml = LDS-102 Subscriber XML
+parser-delimited = Delimited text ({ $profile })
+parser-att = AT&T call detail
+parser-verizon = Verizon compliance report
+parser-tmobile = T-Mobile call detail
+parser-pdf = PDF toll records (row patterns)
+parser-csv-mapped = Delimited text (columns mapped by hand)
+parser-spreadsheet = Excel workbook (columns mapped)
+mapping-generic = generic columns, month first
+mapping-generic-day-first = generic columns, day first
+mapping-penlink = PenLink export
+mapped-field-target = Target number
+mapped-field-direction = Direction
+mapped-field-remote = Remote number
+mapped-field-originating = Calling number
+mapped-field-terminating = Called number
+mapped-field-start = Start date and time
+mapped-field-date = Start date
+mapped-field-time = Start time
+mapped-field-end = End date and time
+mapped-field-duration = Duration
+mapped-field-content = Message content
+mapped-field-disposition = Disposition or feature code
+confidence-low = Low
+confidence-medium = Medium
+confidence-high = High
+confidence-chosen = Chosen manually
+number-kind-nanp = North American number
+number-kind-international = International number
+number-kind-short-code = Short code or service number
+number-kind-unknown = Withheld caller ID
+number-kind-invalid = Not a dialable number
+device-imei = IMEI
+device-imsi = IMSI
+record-type-voice = Voice call
+record-type-sms = Text message (SMS)
+record-type-mms = Multimedia message (MMS)
+call-disposition-completed = Completed
+call-disposition-missed = Missed
+call-disposition-busy = Busy
+call-disposition-voicemail = Voicemail
+call-disposition-forwarded = Forwarded
+call-disposition-roaming = Roaming
+quality-line-records = { $total } records, { $loaded } loaded, { $failed } could not be parsed
+quality-line-flagged = { $count } records look wrong:
+quality-line-implausible-moves = { $count } moves between towers imply travel faster than { $speed } km/h:
+quality-line-implausible-move = { $target }: { $from } at { $left } to { $to } at { $arrived }, { $distance } km
+
+subscribers-empty = No subscriber records loaded. Process an LDS-102 subscriber XML delivered with a production to see who its numbers belong to.
+subscribers-heading = { $count ->
+    [one] 1 subscriber record delivered with the productions:
+   *[other] { $count } subscriber records delivered with the productions:
+}
+subscribers-as-target = As Target
+subscribers-as-contact = As Contact
+subscribers-name = Name
+subscribers-address = Address
+subscribers-account = Account Number
+subscribers-email = Email
+subscribers-account-start = Account Start
+subscribers-account-end = Account End
+
+## Scripts
+
+scripts-intro = Rhai scripts run over the loaded records. Rows they emit are shown below and exported as extra sheets.
+scripts-none = No scripts
+scripts-new = New script
+scripts-delete = Delete
+scripts-save = Save scripts
+scripts-save-hint = Scripts are stored with the settings
+scripts-name = Name:
+scripts-run = Run
+scripts-running = Running…
+scripts-empty = No scripts yet. Create one to get started.
+scripts-metrics = Metrics
+scripts-rows = Rows ({ $count })
+scripts-rows-truncated = Showing the first { $count } rows; the export contains all of them.
+scripts-log = Log
+
+## Jobs
+
+jobs-heading = Background Jobs
+jobs-clear-finished = Clear finished
+jobs-empty = No background jobs have run yet.
+jobs-kind = Kind
+jobs-job = Job
+jobs-status = Status
+jobs-elapsed = Elapsed
+jobs-completed = Completed
+jobs-cancelled = Cancelled
+jobs-eta = about { $time } left
+eta-seconds = { $count } s
+eta-minutes = { $count } min
+eta-hours = { $hours } h { $minutes } min
+
+job-kind-parse = Parse
+job-kind-analytics = Analytics
+job-kind-export = Export
+job-kind-script = Script
+job-kind-compare = Compare
+job-kind-lookup = Lookup
+job-parse = Parse { $name }
+job-preview = Preview { $name }
+job-sample-columns = Read the columns of { $name }
+job-extract-pdf = Read the text of { $name }
+job-analyze = Analyze { $count } records
+job-restore-session = Restore autosaved session
+job-load-sample = Load sample dataset
+job-export = Export { $path }
+job-google-sheets = Push to Google Sheets
+job-auto-export = Auto-export to { $path }
+job-save-case = Save case to { $path }
+job-open-case = Open { $path }
+job-compare-case = Compare with { $path }
+job-verify-evidence = Verify evidence of { $path }
+job-compare-workspace = Compare with { $name }
+job-diff-productions = Compare { $original } with { $corrected }
+job-cnam-lookup = Look up caller names of { $count } numbers
+job-script = Script { $name }
+
+progress-records-parsed = { $count } records parsed
+progress-sampling-file = Sampling the start of the file
+progress-extracting-pdf = Reading the text of the PDF
+progress-computing-analytics = Computing analytics…
+progress-loading-cached-analytics = Loading cached analytics…
+progress-writing-records = Writing { $count } records
+progress-finishing-workbook = Writing the remaining sheets
+progress-writing-exhibits = Laying out exhibits
+progress-signing = Signing export
+progress-google-sign-in = Signing in to Google
+progress-loading-case = Loading case file
+progress-comparing-cases = Comparing cases
+progress-hashing-sources = Hashing { $count } source files
+progress-diffing-productions = Comparing productions
+progress-script-running = Running over { $count } records
+progress-cnam-lookup = Looked up { $done } of { $total } numbers
+
+## Logs
+
+logs-level = Level:
+logs-search = Search:
+logs-copy = Copy shown entries
+logs-copy-hint = Copy as JSON lines, e.g. to paste into a bug report
+logs-file = Full log: { $path }
+logs-no-file = File logging is unavailable on this platform.
+logs-time = Time (UTC)
+logs-level-header = Level
+logs-module = Module
+logs-case = Case
+logs-message = Message
+
+## Settings
+
+settings-stored-in = Stored in { $path }
+settings-no-config-dir = No configuration directory is available; settings last for this session only.
+settings-language = Language
+settings-timezone = Case timezone
+settings-number-format = Number format
+settings-default-region = Default country
+settings-default-region-hint = Numbers without a country code are read as this country's, e.g. 55 1234 5678 as +52 55 1234 5678 for Mexico. Numbers with a + or an international prefix keep their own country. Applies to productions loaded afterwards.
+settings-night-hours = Night hours
+settings-night-from = from
+settings-night-to = to
+settings-top-n = Top numbers to rank
+settings-unanswered-seconds = Unanswered call length
+settings-unanswered-seconds-hint = Calls this many seconds long or shorter count as unanswered in the answer rates; zero-length calls always do
+settings-short-codes = Short codes
+settings-short-codes-include = Rank short codes and service numbers as contacts
+settings-short-codes-hint = Bank alerts, two-factor codes, *86 and other voicemail access are left out of the rankings, answer rates, and other contact analytics unless this is ticked; their calls still count in the totals
+settings-export-name = Export file name
+settings-export-name-hint = Placeholders: {"{date}"}, {"{time}"}, {"{target}"}. Example: { $example }
+settings-export-locale = Export formatting
+settings-export-locale-language = Follow the language
+settings-export-locale-hint = Dates, times, and decimals in Excel, CSV, and PDF output. Example: { $example }
+date-order-ymd = YYYY-MM-DD
+date-order-mdy = MM/DD/YYYY
+date-order-dmy = DD/MM/YYYY
+decimal-separator-point = Decimal point (1.5)
+decimal-separator-comma = Decimal comma (1,5)
+clock-24-hour = 24-hour clock
+clock-12-hour = 12-hour clock (AM/PM)
+settings-excel-formats = Excel formats
+settings-excel-datetime = Date & time:
+settings-excel-duration = Duration:
+settings-excel-formats-hint = Excel number formats for times (in the case timezone) and call durations. Leave blank to follow the export formatting.
+settings-watch-folder = Watch folder
+settings-watch-folder-hint = Folder monitored for new productions. Leave empty to disable.
+settings-preview-threshold = Preview files from
+settings-preview-threshold-hint = Opened productions at least this large are sampled first, so the target, period, and size can be checked and the timezone and duplicate handling set before the full parse. 0 turns the preview off.
+settings-auto-export = Auto-export
+settings-auto-export-enabled = Export Excel and CSV for each new watched production
+settings-auto-export-dir = Auto-export folder
+settings-auto-export-dir-hint = Where auto-exports are written. Leave empty to use an "exports" folder inside the watch folder.
+settings-signing = Export signing
+settings-sign-exports = Write a detached .sig signature next to every export
+settings-signing-key = Agency key file
+settings-signing-key-hint = Secret file of at least 32 bytes shared with recipients who verify exports.
+settings-verify-file = Verify export
+settings-verify = Verify
+settings-carriers = Carrier Lookup
+settings-carrier-assignments = NPA-NXX assignments:
+settings-carrier-assignments-hint = CSV with NPA, NXX, and Carrier columns
+settings-ported-numbers = Ported numbers:
+settings-ported-numbers-hint = CSV with Number, LRN, and optional Carrier columns; overrides block assignments
+settings-cnam = Caller Names (CNAM)
+settings-cnam-file = CNAM file
+settings-cnam-file-hint = CSV with Number and Name columns
+settings-cnam-lookup-enabled = Allow online caller-name lookups
+settings-cnam-lookup-warning = Every number looked up is sent to the lookup service. Lookups only run when started from the Analytics tab.
+settings-cnam-lookup-url = Lookup URL
+settings-cnam-forget = Forget looked-up names
+settings-cnam-forget-hint = Clears the cached lookup answers; names from the CNAM file are kept
+settings-google-sheets = Google Sheets
+settings-google-sheets-credentials = Service-account key
+settings-google-sheets-credentials-hint = JSON key file of a Google Cloud service account with the Sheets API enabled
+settings-google-sheet-id = Sheet ID
+settings-google-sheet-id-hint = The ID in the sheet URL (docs.google.com/spreadsheets/d/<ID>/edit); share the sheet with the service account's email address
+settings-known-numbers = Known-number lists
+settings-known-numbers-hint = CSVs with a Number column and an optional Category column; without one, the file name is the category
+settings-excluded = Excluded numbers
+settings-excluded-hint = Left out of analytics, e.g. voicemail or carrier test lines.
+settings-xml-recovery = Malformed XML
+settings-xml-recovery-enabled = Skip malformed <results> blocks
+settings-xml-recovery-hint = Keeps reading an LDS production past <results> blocks that are not well-formed XML, and lists each one skipped, with its line, element, and reason, under Import Issues. Off, a malformed block stops the file.
+settings-cross-file-duplicates = Cross-file duplicates
+settings-cross-file-duplicates-enabled = Count calls repeated across productions once
+settings-cross-file-duplicates-hint = When productions overlap, e.g. a supplemental return covering some of the original's dates, a call with the same target, number, start, and duration as one in an earlier production is left out, so it is not counted twice. The Data Quality tab shows how many each production lost. Applies to productions loaded afterwards.
+settings-pdf-patterns = PDF row patterns
+settings-pdf-patterns-hint = Regular expressions tried on each line of a PDF's text, in order. Named groups: remote, and start or date and time, are required; direction, duration, end, target, and type are read when present.
+settings-pdf-patterns-add = Add pattern
+settings-pdf-patterns-restore = Restore default patterns
+settings-remove = Remove
+settings-add = Add
+settings-save = Save settings
+settings-reload = Reload from disk
+settings-restore-defaults = Restore defaults
+settings-clear-cache = Clear analytics cache
+settings-clear-cache-hint = Delete analytics results saved on this computer for reopening cases quickly
+
+## Dialogs
+
+recovery-title = Recover previous session?
+recovery-body = The tool did not shut down cleanly last time. An autosaved copy of the loaded records and your watchlist, tags, and bookmarks is available.
+recovery-restore = Restore session
+recovery-discard = Discard
+preview-title = Preview: { $name }
+preview-size = File size:
+preview-format = Format:
+preview-target = Target:
+preview-declared-range = Declared period:
+preview-range = { $from } to { $to }
+preview-not-declared = not declared
+preview-records = Records:
+preview-estimated-records = about { $count } (estimated from { $sampled } in the sample)
+preview-declared-records = The production declares { $count } records.
+preview-timezone = Case timezone:
+preview-duplicates = Duplicate records:
+preview-no-records = No call records were found at the start of this file. It may not be an LDS-101 production.
+preview-load = Load
+column-mapping-title = Map columns: { $name }
+column-mapping-hint = Choose the column that holds each field. Every call needs the remote number (or both the calling and called numbers), a start date and time, and a duration or end time; the rest can be left unmapped.
+column-mapping-sheet = Sheet:
+column-mapping-header-row = Column names in row:
+column-mapping-preset = Start from:
+column-mapping-custom = Custom
+column-mapping-none = (not mapped)
+column-mapping-dates = Dates:
+column-mapping-day-first = Day before month, e.g. 14/03/2024
+column-mapping-sample = First rows as they will be read
+column-mapping-row-skipped = Would be skipped: { $reason }
+column-mapping-incomplete = Map the remote number, the start, and the duration or end time to load this production.
+pdf-review-title = Review PDF rows: { $name }
+pdf-review-hint = These lines matched no pattern or read doubtfully. Correct the fields, tick the rows to load, and leave the rest out.
+pdf-review-summary = { $rows } rows read, { $review } to review
+pdf-review-show-all = Show all rows
+pdf-review-keep = Load
+pdf-review-line = Line
+pdf-review-problems = Problems
+pdf-review-unmatched = No pattern matched
+pdf-problem-time = start time does not read
+pdf-problem-number = remote number is not valid
+pdf-problem-duration = no duration or end time
+pdf-problem-direction = direction is not incoming or outgoing
+format-data-product = eSubpoena data product (LDS-101)
+format-lds101 = LDS-101 results
+format-unrecognized = Unrecognized
+duplicates-keep = Keep all records
+duplicates-drop-exact = Drop exact duplicates
+
+palette-title = Command Palette
+palette-hint = Type a command…
+palette-no-matches = No matching commands
+
+cmd-open-file = Open file…
+cmd-new-case = New case (setup wizard)
+cmd-add-productions = Add productions to the case…
+cmd-export-excel = Export to Excel
+cmd-export-grouped = Export Excel grouped by contact with subtotals
+cmd-export-network = Export network CSV (nodes and edges)
+cmd-export-cellebrite = Export Cellebrite/UFED call log
+cmd-export-penlink = Export for PenLink
+cmd-export-parse-errors = Export Parse Errors
+cmd-export-elastic = Export Elasticsearch bulk NDJSON
+cmd-export-exhibits = Export court exhibit package
+cmd-undo = Undo: { $change }
+cmd-redo = Redo: { $change }
+cmd-go-to-tab = Go to tab: { $tab }
+cmd-new-workspace = New workspace
+cmd-switch-workspace = Switch to workspace: { $name }
+cmd-compare-workspace = Compare with workspace: { $name }
+cmd-apply-chip = Filter: Apply { $chip }
+cmd-remove-chip = Filter: Remove { $chip }
+cmd-enable-regex = Filter: Enable regex mode
+cmd-disable-regex = Filter: Disable regex mode
+cmd-clear-filters = Filter: Clear all filters
+cmd-number-format = Settings: Number format { $format }
+cmd-show-local-time = Settings: Show local time column
+cmd-hide-local-time = Settings: Hide local time column
+cmd-language = Settings: Language { $language }
+cmd-start-tutorial = Help: Start tutorial
+
+## Tutorial
+
+tutorial-window-title = Tutorial: { $step }
+tutorial-start = Start tour
+tutorial-skip = Skip
+tutorial-next = Next
+tutorial-end = End tour
+tutorial-done = Done
+
+wizard-window-title = New Case — Step { $step } of { $steps }: { $title }
+wizard-details-title = Case Details
+wizard-details-body = Name the case so it can be told apart from others; the rest is optional and only shown on the Overview.
+wizard-defaults-title = Timezone and Night Hours
+wizard-defaults-body = Times are shown and exported in the case timezone, and calls starting within the night hours count as night activity. Use the timezone the investigation works in.
+wizard-productions-title = Productions
+wizard-productions-body = Add the carrier's XML productions by path or by dropping them onto the window. You can also load them later from the Overview.
+wizard-duplicates-title = Duplicate Records
+wizard-duplicates-body = Carriers sometimes repeat a record within a production. Keeping them matches the production exactly; dropping exact repeats keeps them from inflating call counts.
+wizard-case-name = Case name
+wizard-case-number = Case number
+wizard-agency = Agency
+wizard-analyst = Analyst
+wizard-production-hint = Path to a production XML file
+wizard-add-production = Add
+wizard-no-productions = No productions added yet
+wizard-summary = { $name } will use { $timezone } with night hours { $from }–{ $to } and load { $count ->
+        [one] { $count } production
+       *[other] { $count } productions
+    }.
+wizard-back = Back
+wizard-next = Next
+wizard-finish = Create Case
+tutorial-welcome-title = Welcome
+tutorial-welcome-body = This walkthrough loads a small synthetic production so you can try the tool without real case data. Every number in it is fictional.
+tutorial-drop-zone-title = Loading productions
+tutorial-drop-zone-body = Drop carrier XML returns here, or click to browse. The sample dataset has already been loaded for you.
+tutorial-tabs-title = Navigating the results
+tutorial-tabs-body = Use these tabs to move between the overview, the raw call records, the analytics dashboard, and the written summary.
+tutorial-filters-title = Filtering records
+tutorial-filters-body = Type part of a number, pick a date range, or click a chip to narrow the table. Chips combine with each other and with the text filter.
+tutorial-export-title = Exporting
+tutorial-export-body = When you're done reviewing, export everything to an Excel workbook with one sheet per analysis.
+tutorial-finished-title = You're ready
+tutorial-finished-body = That's the tour. The sample data stays loaded until you process a real file. You can restart the tutorial from the command palette (Ctrl+Shift+P).
+
+## Messages
+
+msg-processing-file = Processing file: { $name }
+msg-duplicates-dropped = Dropped { $count } duplicate records
+msg-cross-file-duplicates-collapsed = Counted { $count } calls repeated across productions once
+msg-cross-file-duplicates-found = { $count } calls appear in more than one production and are counted each time; turn on Settings → Cross-file duplicates to count them once
+msg-productions-queued = { $count ->
+        [one] Queued 1 production; it will be added to the case once the current load finishes
+       *[other] Queued { $count } productions; they will be added to the case once the current load finishes
+    }
+msg-productions-added = { $count ->
+        [one] Adding 1 production ({ $records } records) to the case
+       *[other] Adding { $count } productions ({ $records } records) to the case
+    }
+msg-production-already-loaded = { $name } is already in the case and was not added again
+msg-processed = Successfully processed { $count } call records
+msg-records-flagged = { $count } records look wrong (end before start, future dates, ...); see Suspicious Records on the Data Quality tab
+msg-session-restored = Restored session autosaved at { $saved_at }
+msg-script-finished = Script "{ $name }" emitted { $count } rows
+msg-exported = Successfully exported to: { $path }
+msg-auto-exported = Auto-exported { $paths }
+msg-network-exported = Network exported to { $paths }
+msg-google-sheets-pushed = Pushed to Google Sheets: { $url }
+msg-google-sheets-not-configured = Set a service-account key file and a sheet ID on the Settings tab first
+msg-case-saved = Case saved to { $path }
+msg-case-opened = Opened { $path } with { $count } records
+msg-evidence-verified = All { $count } source files match the hashes recorded at import
+msg-evidence-discrepancies = { $count } of { $total } source files are altered, missing, or unreadable
+msg-subscribers-imported = Imported { $count } subscribers
+msg-subscribers-import-failed = Subscriber import failed: { $error }
+msg-contacts-imported = Imported { $count } contact numbers; { $added } new aliases
+msg-entity-merged = Added { $count } numbers to { $name }
+msg-number-excluded = Excluded { $number } from the analytics
+msg-entity-no-numbers = No new phone numbers to add to { $name }
+msg-undone = Undid: { $change }
+msg-redone = Redid: { $change }
+msg-nothing-to-undo = Nothing to undo
+msg-nothing-to-redo = Nothing to redo
+change-watchlist = Watchlist change for { $number }
+change-alias = Name change for { $number }
+change-tag = Record tag
+change-note = Record note
+change-bookmark = Bookmark
+change-import-subscribers = Subscriber results import
+change-import-contacts = Contact list import
+change-merge-entity = Merge into { $name }
+change-remove-from-entity = Remove { $number } from its entity
+change-dissolve-entity = Dissolve { $name }
+change-keywords = Message keywords
+change-geofence = Geofence
+change-reclassify-number = Count { $number } as { $kind }
+msg-contacts-import-failed = Failed to import contacts: { $error }
+msg-case-compared = Compared with { $name }: { $count } numbers in common
+msg-compare-needs-records = Load a case before comparing it with another.
+msg-productions-diffed = Productions compared: { $added } added, { $removed } removed, { $changed } corrected
+msg-workspace-busy = Wait for the current file to finish loading before switching cases.
+msg-case-created = Case "{ $name }" created
+msg-wizard-file-missing = No file at { $path }
+msg-format-detected = { $name }: read as { $parser }
+msg-format-uncertain = { $name }: read as { $parser } (confidence: { $confidence }); check the records, or re-parse it from Data Quality
+msg-format-chosen = { $name }: read again as { $parser }
+msg-reparse-unavailable = Cannot re-parse { $name }: its production is no longer on disk
+msg-workspace-closed = Closed workspace { $name }
+msg-signature-valid = { $name } matches its signature from { $signed_at }
+msg-signature-invalid = Signature check failed: { $error }
+msg-job-failed = { $kind } failed: { $error }
+msg-settings-saved = Settings saved to { $path }
+msg-settings-save-failed = Failed to save settings: { $error }
+msg-carriers-loaded = Carrier data loaded
+msg-carriers-load-failed = Failed to load carrier data: { $error }
+msg-caller-names-loaded = Caller names loaded
+msg-caller-names-load-failed = Failed to load caller names: { $error }
+msg-known-numbers-loaded = Loaded { $count } known numbers
+msg-known-numbers-load-failed = Failed to load known-number lists: { $error }
+msg-cnam-lookup-failed = Caller-name lookup failed: { $error }
+msg-cnam-nothing-to-look-up = Every contact already has a caller name or was looked up before
+msg-cnam-lookup-finished = Looked up { $count } numbers; { $named } had a caller name
+msg-cnam-lookup-stopped = Caller-name lookup stopped after { $count } numbers: { $error }
+msg-cnam-cache-cleared = Forgot { $count } looked-up caller names
+msg-cache-cleared = Removed { $count } cached analytics results
+msg-cache-clear-failed = Failed to clear the analytics cache: { $error }
+msg-scripts-saved = Scripts saved to { $path }
+msg-scripts-save-failed = Failed to save scripts: { $error }
+msg-script-needs-records = Load records before running a script
+msg-no-data-to-export = No data to export
+msg-no-parse-errors = No production had records skipped
+msg-export-running = An export is already running
+msg-downloaded = Downloaded { $stem }.csv and { $stem }_summary.txt
+msg-network-downloaded = Downloaded { $stem }_nodes.csv and { $stem }_edges.csv
+msg-file-downloaded = Downloaded { $name }
+msg-export-failed = Export failed: { $error }
+msg-xml-only = Please drop XML, JSON, CSV, TSV, PDF, or Excel productions, or ZIP archives of them, only
+msg-report-copied = Report copied to clipboard
+msg-digest-copied = Findings digest copied to clipboard
+msg-report-saved = Report saved to { $path }
+msg-report-save-failed = Failed to save report: { $error }
+
+## Summary report
+
+report-title = TELECOMMUNICATION DATA ANALYSIS
+report-summary-stats = SUMMARY STATISTICS
+report-total-calls = Total Calls: { $count }
+report-incoming-calls = Incoming Calls: { $count }
+report-outgoing-calls = Outgoing Calls: { $count }
+report-unique-numbers = Unique Phone Numbers: { $count }
+report-unknown-numbers = Calls with Unknown Caller ID: { $count }
+report-short-code-calls = Calls with Short Codes and Service Numbers (not ranked as contacts): { $count }
+report-disposition-calls = Calls Marked { $disposition }: { $count }
+report-target-numbers = Target Numbers: { $count }
+report-files-processed = Files Processed: { $count }
+report-total-duration = Total Duration: { $minutes } minutes
+report-average-duration = Average Call Duration: { $minutes } minutes
+report-longest-call = Longest Call: { $seconds } seconds ({ $minutes } minutes) to { $number } on { $date }
+report-shortest-call = Shortest Call: { $seconds } seconds to { $number } on { $date }
+report-date-range = Date Range: { $from } to { $to }
+report-narrative = NARRATIVE
+report-key-findings = KEY FINDINGS
+finding-overview = { report-calls } with { $numbers } unique numbers from { $from } to { $to }.
+finding-night-share = { $pct }% of all calls were between { $from } and { $to }.
+finding-busiest-day = The busiest day was { $date }, with { report-calls }.
+finding-gap = No calls for { $days } days, between { $from } and { $to }.
+finding-direct-contact = Targets { $a } and { $b } were in direct contact: { report-calls } from { $first } to { $last }.
+finding-common-contact = { $number } was in contact with { $count } target numbers.
+finding-number-on-devices = { $number } was used on { $count } devices by { $kind }: { $spans }.
+finding-device-on-numbers = { $kind } { $device } was used with { $count } target numbers: { $spans }.
+finding-device-span = { $value } from { $from } to { $to } ({ report-calls })
+finding-stopped-answering = { $number } stopped answering: the last { $count } calls to it went unanswered after { $date }.
+finding-contact = { $targets } had { report-calls } with { $number } ({ $outgoing } outgoing, { $incoming } incoming)
+finding-contact-night = { $pct }% between { $from } and { $to }
+finding-contact-began = contact began on { $date }
+finding-contact-ceased = contact ceased after { $date }
+finding-named-number = { $number } ({ $name })
+template-date-range = { $from } through { $to }
+exhibit-label = EXHIBIT { $number }
+exhibit-footer = Exhibit { $number } — Page { $page } of { $pages }
+exhibit-index-title = Exhibit Index
+exhibit-index-targets = Target numbers: { $targets }
+exhibit-index-period = Records from { $from } to { $to } ({ $timezone })
+exhibit-index-prepared = Prepared { $date }
+exhibit-index-exhibit = Exhibit
+exhibit-index-description = Description
+exhibit-index-page = Page
+exhibit-summary = Toll analysis summary
+exhibit-hour-chart = Calls by Hour ({ $timezone })
+exhibit-day-chart = Calls by Day ({ $timezone })
+exhibit-chart-caption = { $calls } calls in total
+exhibit-contact = Calls with { $number }
+exhibit-contact-name = Known as: { $name }
+exhibit-contact-entity = Entity: { $entity }
+exhibit-contact-totals = { $total } calls ({ $incoming } incoming, { $outgoing } outgoing), { $minutes } minutes; times in { $timezone }
+exhibit-column-time = Date/Time
+exhibit-column-direction = Direction
+exhibit-column-target = Target
+exhibit-column-duration = Duration
+exhibit-column-source = Source File
+report-most-frequent = MOST FREQUENT NUMBERS
+report-targets = TARGET NUMBERS
+report-excluded-targets = Excluded from analytics: { $targets }
+report-common-contacts = COMMON CONTACTS ACROSS TARGET NUMBERS
+report-common-contact = { $number }: appears in { $count } target numbers ({ $targets })
+report-exchange-clusters = SHARED EXCHANGES
+report-exchange-cluster = { $prefix }: { $count } numbers, { $calls } calls ({ $numbers })
+report-bookmarks = BOOKMARKED RECORDS
+report-night-activity = NIGHT ACTIVITY ({ $timezone })
+report-night-calls = { report-calls } between { $from } and { $to }
+report-calls-by-day = CALLS BY DAY ({ $timezone })
+report-calls-by-hour = CALLS BY HOUR ({ $timezone })
+report-calls =
+    { $count ->
+        [one] 1 call
+       *[other] { $count } calls
+    }
+report-number-calls = { $number } ({ report-calls })
+report-seconds = { $seconds } seconds
+digest-overview = Toll records: { $calls } calls across { $targets } target numbers, { $from } to { $to } ({ $timezone })
+digest-top-contacts = Top contacts:
+digest-named-contact = { $number }, { $name } ({ report-calls })
+digest-key-dates = Key dates:
+digest-first-call = First call: { $date }
+digest-last-call = Last call: { $date }
+digest-busiest-day = Busiest day: { $date } ({ report-calls })
+digest-direct-contacts = Direct contacts between targets:
+digest-direct-contact = { $a } ↔ { $b }: { report-calls }, { $first } to { $last }
+digest-no-direct-contacts = None found
+digest-attachments = Attachments:
+digest-sources = Source productions: { $files }
+
+## Excel export
+
+sheet-call-records = Call Records
+sheet-analytics = Analytics
+sheet-summary-report = Summary Report
+sheet-common-contacts = Common Contacts
+sheet-bookmarks = Bookmarks
+sheet-subscribers = Subscriber Resolution
+sheet-subscriber-info = Subscriber Info
+sheet-known-numbers = Known Numbers
+sheet-call-durations = Call Durations
+sheet-exchange-clusters = Shared Exchanges
+sheet-area-codes = Area Codes
+sheet-contact-summary = Contact Summary
+sheet-contact-spans = Contact Spans
+sheet-calls-by-contact = Calls by Contact
+grouped-subtotal = { $number } Total
+grouped-grand-total = Grand Total
+sheet-no-common-contacts = No common contacts found across target numbers
+sheet-no-exchange-clusters = No exchange has 3 or more contacted numbers
+sheet-no-area-codes = No North American numbers were contacted
+sheet-no-bookmarks = No records were bookmarked
+sheet-keyword-hits = Keyword Hits
+sheet-no-keyword-hits = No messages mention the keywords
+sheet-geofence-hits = Geofence Calls
+sheet-location-analysis = Location Analysis
+sheet-no-geofence-hits = No calls were on towers inside the geofence
+sheet-no-tower-changes = No tower changes
+sheet-no-calls = No calls with the most frequent numbers
+sheet-no-known-numbers = No loaded contacts are on the known-number lists
+sheet-no-contact-spans = No contacts were loaded
+sheet-contact-spans-note = Ongoing: last contact with a target within { $days } days of the end of that target's records
+sheet-call-durations-note = Each column covers { $minutes } minutes (UTC); cells give minutes on a call. Orange cells hold calls that overlap another call on the same target ({ $overlapping } calls).
+sheet-unresolved = Unresolved
+carrier-ported = { $carrier } (ported)
+carrier-lrn = LRN { $lrn }
+sheet-script = Script - { $name }
+sheet-script-no-output = The script produced no output
+
+metric-total-calls = Total Calls
+metric-incoming-calls = Incoming Calls
+metric-outgoing-calls = Outgoing Calls
+metric-unique-numbers = Unique Phone Numbers
+metric-unknown-numbers = Calls with Unknown Caller ID
+metric-total-duration = Total Duration (minutes)
+metric-average-duration = Average Call Duration (minutes)
@@ -0,0 +1,1203 @@
+### Textos de la interfaz y de los informes en español. Los identificadores son los mismos que en en.ftl.
+
+## Pestañas
+
+tab-overview = Resumen general
+tab-call-records = Registros de llamadas
+tab-analytics = Análisis
+tab-bookmarks = Marcadores
+tab-summary = Informe
+tab-data-quality = Calidad de datos
+tab-subscribers = Abonados
+tab-scripts = Scripts
+tab-jobs = Tareas
+tab-logs = Registros del sistema
+tab-settings = Configuración
+workspace-label = Casos:
+workspace-name = Caso { $number }
+workspace-targets = { $target } +{ $count }
+workspace-new = Abrir otro caso en un nuevo espacio de trabajo
+workspace-close-hint = Cerrar este espacio de trabajo; se descarta el trabajo de revisión sin guardar
+workspace-compare = Comparar con…
+
+## Botones y etiquetas comunes
+
+button-export-excel = Exportar a Excel
+button-more-exports = Más exportaciones
+button-undo-hint = Deshacer: { $change }
+button-undo-none = Nada que deshacer
+button-redo-hint = Rehacer: { $change }
+button-redo-none = Nada que rehacer
+button-export-grouped = Exportar Excel agrupado por contacto
+button-export-grouped-hint = Genera un libro con las llamadas de cada contacto agrupadas en un esquema y seguidas de un subtotal de llamadas y duración, con un total general al final
+button-export-network = Exportar red CSV
+button-export-network-hint = Genera nodes.csv (número, alias, llamadas, minutos) y edges.csv (origen, destino, peso) para Gephi u otra herramienta de grafos
+button-export-cellebrite = Exportar registro de llamadas Cellebrite
+button-export-cellebrite-hint = Genera los registros como un CSV de registro de llamadas UFED, con números E.164 y horas UTC, para importarlo en Physical Analyzer o Pathfinder junto a una extracción del dispositivo
+button-export-penlink = Exportar para PenLink
+button-export-penlink-hint = Genera los registros con el formato de importación genérica de llamadas de PenLink, con números solo de dígitos y horas en la zona horaria del caso
+button-export-elastic = Exportar para Elasticsearch
+button-export-elastic-hint = Genera NDJSON de ingesta masiva con campos normalizados y metadatos del caso, para indexarlo en Elastic/Kibana
+button-export-parse-errors = Exportar errores de lectura
+button-export-parse-errors-hint = Genera un CSV con los bloques XML mal formados y los registros ilegibles de cada producción, con dónde y por qué se omitió cada uno
+button-export-exhibits = Paquete de pruebas para el tribunal (PDF)
+button-export-exhibits-hint = Genera un PDF con un índice y pruebas numeradas y paginadas: el resumen, los gráficos de llamadas por hora y por día, y una lista de llamadas por cada contacto principal
+button-push-google-sheets = Enviar a Google Sheets
+button-push-google-sheets-hint = Reemplaza las pestañas Registros de llamadas, Análisis e Informe resumido de la hoja de Google compartida configurada en Configuración
+button-cancel = Cancelar
+label-numbers = Números:
+value-minutes = { $minutes } min
+banner-skipped-records = { $skipped } de { $total } registros no se pudieron leer
+banner-discrepancies =
+    { $files ->
+        [one] 1 producción no coincide con su cobertura declarada y puede estar truncada
+       *[other] { $files } producciones no coinciden con su cobertura declarada y pueden estar truncadas
+    }
+banner-view-details = Ver detalles
+banner-dismiss = Descartar
+
+## Encabezados de tablas y hojas
+
+header-direction = Dirección
+header-target = Objetivo
+header-target-number = Número objetivo
+header-target-numbers = Números objetivo
+header-remote-number = Número remoto
+header-normalized-number = Número normalizado
+header-phone-number = Número de teléfono
+header-date = Fecha
+header-date-time = Fecha y hora
+header-duration-sec = Duración (s)
+header-duration-min = Duración (min)
+header-duration = Duración
+header-start-time-zone = Hora de inicio ({ $timezone })
+header-end-time-zone = Hora de fin ({ $timezone })
+header-day-of-week = Día de la semana
+header-disposition = Disposición
+header-source-file = Archivo de origen
+header-tags = Etiquetas
+header-notes = Notas
+header-parser = Analizador
+header-confidence = Confianza
+header-keywords = Palabras clave
+header-tagged = Etiquetado
+header-message = Mensaje
+value-yes = Sí
+header-call-count = Llamadas
+header-count = Cantidad
+header-rank = Posición
+header-metric = Métrica
+header-value = Valor
+header-record-position = Registro n.º
+header-raw-start = Hora de inicio (original)
+header-reason = Motivo
+header-line = Línea
+header-element = Elemento
+header-problem = Problema
+problem-malformed-xml = XML mal formado
+problem-unreadable-record = Registro ilegible
+header-counted-as = Contado como
+header-subscriber = Abonado
+header-address = Dirección
+header-carrier = Operador
+header-lrn = LRN
+header-cell-site-id = ID de celda
+header-sector = Sector
+header-latitude = Latitud
+header-longitude = Longitud
+header-distance-km = Distancia (km)
+header-busiest-tower = Antena más usada
+header-towers = Antenas
+header-tower-changes = Cambios de antena
+header-from-tower = Antena de origen
+header-to-tower = Antena de destino
+header-left-zone = Salida ({ $timezone })
+header-arrived-zone = Llegada ({ $timezone })
+header-speed-kmh = Velocidad (km/h)
+header-implausible = Inverosímil
+header-first-call-zone = Primera llamada ({ $timezone })
+header-last-call-zone = Última llamada ({ $timezone })
+header-caller-name = Nombre del llamante
+header-alias = Alias
+header-entity = Entidad
+header-first-contact = Primer contacto
+header-last-contact = Último contacto
+header-first-contact-zone = Primer contacto ({ $timezone })
+header-last-contact-zone = Último contacto ({ $timezone })
+header-span-days = Duración (días)
+header-ongoing = En curso
+header-device-subject = Número o dispositivo
+header-device-id-kind = Identificador
+header-device-used-with = Usado con
+header-first-seen = Primera vez
+header-last-seen = Última vez
+header-total-calls = Llamadas totales
+header-incoming = Entrantes
+header-outgoing = Salientes
+header-total-duration = Duración total
+header-average-duration = Duración promedio
+header-night-calls = Llamadas nocturnas
+header-watchlist = Lista de vigilancia
+header-night-share = Proporción nocturna
+header-odd-hours-score = Puntuación de horas inusuales
+header-messages-sent = Enviados
+header-messages-received = Recibidos
+header-category = Categoría
+header-prefix = NPA-NXX
+header-region = Región
+header-area-code = Código de área
+header-share = Proporción
+header-attempts = Contestadas / Realizadas
+header-answer-rate = Tasa de respuesta
+header-longest-unanswered = Racha más larga sin respuesta
+header-unanswered-since = Sin respuesta desde la última contestada
+header-missed-incoming = Entrantes perdidas
+header-numbers = Números
+
+heading-most-frequent = Números más frecuentes
+heading-exchange-clusters = Centrales compartidas
+heading-calls-by-region = Llamadas por región
+heading-area-codes = Códigos de área
+heading-answer-rates = Tasas de respuesta
+heading-odd-hours = Contactos en horas inusuales
+heading-messages-per-contact = Mensajes por contacto
+heading-contact-spans = Primer y último contacto
+heading-device-changes = Cambios de teléfono y SIM
+heading-number-warnings = Números sospechosos
+heading-record-warnings = Registros sospechosos
+heading-production-formats = Formatos de producción
+heading-known-numbers = Categorías de números conocidos
+heading-entities = Entidades
+heading-message-keywords = Palabras clave en mensajes
+keywords-hint = Algunas producciones incluyen el texto de los mensajes. Busque en él palabras clave separadas por comas (sin distinguir mayúsculas); las coincidencias se resaltan en la columna Mensaje.
+keywords-input-hint = p. ej. dinero, reunión, paquete
+keywords-apply = Aplicar
+keywords-show-hits = Ver coincidencias
+keywords-summary = { $count } mensajes mencionan una palabra clave; totales acumulados por palabra clave y día ({ $timezone })
+heading-location-analysis = Análisis de ubicación
+heading-tower-usage = Uso de antenas por día ({ $timezone })
+heading-dwell-periods = Períodos de permanencia
+heading-tower-changes = Cambios de antena
+heading-implausible-moves = Desplazamientos inverosímiles
+heading-geofence = Geocerca
+location-hint = Desde dónde se atendieron las llamadas de cada objetivo. Un período de permanencia es una serie de llamadas en una misma antena sin llamadas en otra antena entre ellas y sin pausas de más de { $hours } horas; un cambio de antena es el paso a otra antena entre llamadas consecutivas. Los sectores de una antena cuentan como el mismo lugar.
+location-summary = { $towers } antenas, { $dwells } períodos de permanencia y { $transitions } cambios de antena en { $days } días-objetivo
+location-days-caption = Días más recientes primero ({ $timezone })
+location-show-movement = Ver permanencias y cambios de antena
+location-implausible = { $count } cambios de antena implican viajar a más de { $speed } km/h; se listan con los cambios de antena y en la pestaña Calidad de datos
+implausible-moves-hint = Llamadas consecutivas cuyas antenas están demasiado lejos para el tiempo transcurrido: aun admitiendo { $range } km entre el teléfono y cada antena, habría tenido que viajar a más de { $speed } km/h. Suele ser un error en los datos, o el número en uso en más de un teléfono.
+geofence-hint = Lista las llamadas cuya antena estaba dentro de un área durante un intervalo de tiempo. La antena suele estar a unos kilómetros del teléfono, más lejos en zonas rurales. Las horas están en { $timezone }; una fecha sola abarca todo el día, y una hora en blanco deja ese extremo abierto.
+geofence-radius = Radio
+geofence-box = Rectángulo
+geofence-latitude = Latitud:
+geofence-longitude = Longitud:
+geofence-radius-km = Radio (km):
+geofence-south = Sur:
+geofence-west = Oeste:
+geofence-north = Norte:
+geofence-east = Este:
+geofence-from = Desde:
+geofence-to = Hasta:
+geofence-apply = Aplicar
+geofence-clear = Borrar
+geofence-show-calls = Ver llamadas
+geofence-applied = Buscando { $geofence }
+geofence-summary = { $count } llamadas en antenas { $geofence }
+geofence-radius-label = a menos de { $radius } km de { $center }
+geofence-box-label = entre { $southwest } y { $northeast }
+geofence-window-label = { $area }, de { $from } a { $to } ({ $timezone })
+geofence-window-open = abierto
+geofence-invalid-coordinates = Introduzca la latitud y la longitud en grados decimales, p. ej. 34.7304 y -86.5861
+geofence-invalid-box = El borde sur debe estar por debajo del borde norte
+geofence-invalid-radius = Introduzca un radio en kilómetros mayor que cero
+geofence-invalid-time = No se puede leer "{ $time }"; use 2024-03-01 18:30 o 2024-03-01
+geofence-invalid-window = El intervalo termina antes de empezar
+heading-calls-by-day = Llamadas por día ({ $timezone })
+heading-calls-by-hour = Llamadas por hora ({ $timezone })
+
+## Tabla de registros de llamadas
+
+column-direction = Dirección
+column-remote-number = Número remoto
+column-normalized = Normalizado
+column-date = Fecha (UTC)
+column-time = Hora (UTC)
+column-local-time = Hora local
+column-duration = Duración (min)
+column-subscriber = Abonado
+column-carrier = Operador
+column-region = Región
+column-caller-name = Nombre del llamante
+column-alias = Alias
+column-category = Categoría
+column-message = Mensaje
+column-cell-site = Celda
+column-tower-location = Ubicación de la torre
+column-device = Dispositivo (IMEI / IMSI)
+column-tags = Etiquetas
+
+chip-incoming-only = Solo entrantes
+chip-outgoing-only = Solo salientes
+chip-long-calls = > 5 min
+chip-night-hours = Horario nocturno
+chip-watchlist-hits = En lista de vigilancia
+chip-tagged = Etiquetados
+chip-keyword-hits = Con palabras clave
+chip-chart-day = Día: { $date }
+chip-chart-hour = Hora: { $hour }
+chip-chart-clear-hint = Elegido en un gráfico; haga clic para quitarlo
+
+phone-format-raw = Solo dígitos
+region-north-america = Estados Unidos / Canadá
+region-mexico = México
+region-guatemala = Guatemala
+region-el-salvador = El Salvador
+region-honduras = Honduras
+region-colombia = Colombia
+region-brazil = Brasil
+region-united-kingdom = Reino Unido
+region-spain = España
+region-france = Francia
+region-germany = Alemania
+region-india = India
+region-china = China
+country-canada = Canadá
+country-antigua = Antigua y Barbuda
+country-anguilla = Anguila
+country-barbados = Barbados
+country-bermuda = Bermudas
+country-bahamas = Bahamas
+country-dominica = Dominica
+country-dominican-republic = República Dominicana
+country-grenada = Granada
+country-jamaica = Jamaica
+country-st-kitts = San Cristóbal y Nieves
+country-cayman-islands = Islas Caimán
+country-st-lucia = Santa Lucía
+country-montserrat = Montserrat
+country-sint-maarten = Sint Maarten
+country-turks-caicos = Islas Turcas y Caicos
+country-trinidad = Trinidad y Tobago
+country-st-vincent = San Vicente y las Granadinas
+country-british-virgin-islands = Islas Vírgenes Británicas
+
+records-empty = No hay registros de llamadas cargados. Procese primero un archivo XML.
+records-showing = Mostrando { $total } registros de llamadas
+records-showing-filtered = Mostrando { $shown } de { $total } registros de llamadas
+records-more = ... y { $count } registros más
+records-show-local-time = Mostrar hora local
+records-reset-columns = Restablecer columnas
+records-reset-columns-hint = Restaurar el orden y el ancho predeterminados de las columnas
+records-select-shown-hint = Seleccionar o deseleccionar los registros mostrados
+selection-count = { $count } seleccionados
+selection-tag = Etiqueta:
+selection-tag-hint = Etiqueta personalizada
+selection-add-tag = Añadir etiqueta
+selection-remove-tag = Quitar etiqueta
+selection-note = Nota:
+selection-note-hint = En blanco elimina la nota
+selection-set-note = Guardar nota
+selection-clear = Borrar selección
+tag-relevant = Relevante
+tag-alibi-window = Ventana de coartada
+
+menu-add-watchlist = Añadir a la lista de vigilancia
+menu-remove-watchlist = Quitar de la lista de vigilancia
+menu-tag-record = Etiquetar registro
+menu-remove-tag = Quitar etiqueta
+menu-record-details = Detalles del registro
+menu-contact-drilldown = Detalle del contacto
+
+bookmark-add = Marcar este registro
+bookmark-remove = Quitar marcador
+
+filter-label = Filtro:
+filter-hint-text = número, fecha, dirección...
+filter-hint-regex = expresión regular, p. ej. ^256|Sunday
+filter-regex = Expresión regular
+filter-regex-hint = Aplicar el filtro como expresión regular a todas las columnas
+filter-from = Desde:
+filter-to = Hasta:
+filter-clear = Limpiar
+filter-tag = Etiqueta:
+filter-any-tag = Cualquiera
+filter-invalid-regex = Expresión regular no válida: { $error }
+
+## Resumen general
+
+overview-welcome = Bienvenido a eSubpoena Tolls Tool
+overview-intro = Esta herramienta procesa datos XML de telecomunicaciones y ofrece análisis completos.
+overview-new-case = Nuevo caso…
+overview-new-case-hint = Configure un caso paso a paso: sus datos, la zona horaria y el horario nocturno, las producciones a cargar y cómo tratar los registros duplicados
+overview-add-productions = Añadir producciones…
+overview-add-productions-hint = Añada más producciones, p. ej. los registros de otro objetivo, al caso cargado y vuelva a calcular los análisis sobre todo
+overview-case-number = Caso n.º { $number }
+overview-case-analyst = Analista: { $analyst }
+overview-quick-stats = Estadísticas rápidas
+overview-case-file = Archivo de caso
+overview-case-file-hint = Ruta a un archivo de caso o libro exportado, p. ej. casos/2024-118.case.json
+overview-save-case = Guardar caso
+overview-open-case = Abrir
+overview-open-case-tooltip = Abrir un archivo de caso guardado o reconstruir una sesión a partir de un libro .xlsx exportado por esta herramienta
+overview-compare-case = Comparar con caso
+overview-show-comparison = Ver comparación
+overview-verify-evidence = Verificar evidencia
+overview-verify-evidence-tooltip = Vuelve a calcular el hash de las producciones de este caso y confirma que coinciden con el SHA-256 registrado al importarlas
+overview-subscribers = Resultados de abonados: { $count } números resueltos
+overview-subscribers-hint = CSV con columnas Number, Name y Address
+overview-import-subscribers = Importar abonados
+overview-contacts = Alias de listas de contactos: { $count }
+overview-contacts-hint = Ruta a una exportación de contactos (.vcf) o CSV de asociados conocidos
+overview-import-contacts = Importar contactos
+overview-show-contact-matches = Ver coincidencias
+
+drop-zone-idle =
+    Arrastre y suelte archivos XML aquí
+    o haga clic para buscarlos
+drop-zone-hover = Suelte los archivos XML aquí
+drop-zone-processing = Procesando...
+dialog-open-productions = Abrir producciones
+dialog-add-productions = Añadir producciones al caso
+filter-productions = Producciones (XML, JSON, CSV, TSV, PDF, Excel, ZIP)
+filter-xml = Producciones XML
+
+status-idle = Listo para procesar archivos
+status-processing = Procesando...
+status-completed = Procesamiento completado
+status-error = Error: { $error }
+
+stat-total-calls = Total de llamadas
+stat-incoming = Entrantes
+stat-outgoing = Salientes
+stat-unique-numbers = Números únicos
+stat-unknown-numbers = Identificador oculto
+stat-short-codes = Códigos cortos y números de servicio
+stat-short-codes-hint = Llamadas con códigos cortos, códigos de función y buzón de voz, contadas en los totales pero no clasificadas como contactos (Configuración → Códigos cortos)
+stat-sms = SMS
+stat-mms = MMS
+stat-total-duration = Duración total
+stat-avg-duration = Duración media
+
+## Análisis y detalles
+
+analytics-heading = Panel de análisis
+analytics-timeline = Cronología
+analytics-exchange-clusters-hint = Centrales con 3 o más números contactados, a menudo teléfonos prepagados comprados juntos. Pase el cursor sobre un recuento para ver los números.
+analytics-calls-by-region-hint = Llamadas con números norteamericanos por el estado, provincia o país del código de área. Los números conservan su código de área al portarse o mudarse, así que indica dónde se asignó el número.
+analytics-area-codes-hint = Números norteamericanos contactados agrupados por código de área, con su proporción de las llamadas.
+analytics-contact-spans-hint = Cuándo el objetivo tuvo su primer y último contacto con cada número. El contacto sigue en curso si la última llamada con un objetivo fue dentro de los { $days } días previos al final de los registros de ese objetivo. Haga clic en un número para ver sus llamadas.
+analytics-sort-hint = Haga clic para ordenar; otra vez para invertir el orden
+area-code-concentration = De { $located } llamadas con números norteamericanos de una zona conocida, el { $region_share }% son con números de { $region } y el { $area_code_share }% solo con el código de área { $area_code }.
+area-code-non-geographic = No geográfico (gratuito, ...)
+analytics-answer-rates-hint = Contactos a los que el objetivo llamó { $attempts } o más veces. Las llamadas de { $seconds } segundos o menos cuentan como no contestadas; ⚠ marca los contactos que antes contestaban pero dejaron sin respuesta las últimas { $streak } llamadas o más.
+analytics-stopped-answering-hint = Dejó de contestar las llamadas del objetivo. Haga clic para ver las llamadas del contacto.
+analytics-odd-hours-hint = Contactos ordenados según cuántas de sus llamadas ocurren entre las { $from } y las { $to } ({ $timezone }): la proporción nocturna ponderada por el número de llamadas nocturnas, de modo que el contacto nocturno constante supera a una sola llamada a las 2 AM.
+analytics-device-changes-hint = Números objetivo vistos en más de un teléfono (IMEI) o SIM (IMSI), y teléfonos o SIM vistos con más de un número objetivo, según los identificadores de dispositivo de las producciones.
+analytics-answered-of = { $answered } / { $attempts }
+analytics-unanswered-since = { $count } desde { $date }
+analytics-never-answered = nunca contestó
+analytics-cnam-lookup = 🔎 Consultar nombres
+analytics-cnam-lookup-hint = Envía hasta { $limit } de los contactos más frecuentes sin nombre al servicio de consulta configurado en Configuración
+analytics-unidentified-hint = Sin identificar: no figura en las listas de contactos, los resultados de abonados ni los nombres de llamantes
+analytics-call-durations = Duración de llamadas
+analytics-cumulative = Acumulado
+analytics-drilldown-hint = Haga clic para ver el detalle del contacto
+analytics-targets = Objetivos incluidos:
+analytics-targets-hint = Desmárquelo para dejar este objetivo fuera de los totales y los contactos comunes; sus registros siguen cargados.
+analytics-empty = No hay análisis disponibles. Procese primero un archivo XML.
+analytics-entities-hint = Agrupe números que pertenecen a una misma persona, como un teléfono antiguo y uno nuevo. Los registros conservan sus propios números; la tabla combina sus llamadas.
+entity-name = Entidad
+entity-numbers = Números
+entity-numbers-hint = Separados por comas, p. ej. 256-555-0100, 256-555-0199
+entity-merge = Fusionar
+entity-remove-number-hint = Quitar este número de la entidad
+entity-dissolve = Disolver
+entity-dissolve-hint = Eliminar la entidad; sus números permanecen en los registros
+
+detail-record-title = Detalle del registro
+detail-contact-title = Contacto: { $number }
+detail-timeline-title = Cronología de llamadas
+detail-call-durations-title = Duración de llamadas
+detail-cumulative-title = Llamadas acumuladas
+detail-skipped-title = Problemas de importación
+detail-comparison-title = Comparación entre casos
+detail-diff-title = Diferencias entre producciones
+detail-evidence-title = Verificación de evidencia
+detail-keyword-hits-title = Coincidencias de palabras clave
+detail-geofence-title = Llamadas en la geocerca
+detail-tower-movement-title = Movimiento entre antenas
+detail-contact-matches-title = Contactos conocidos
+detail-contact-matches-empty = Cargue registros para compararlos con las listas de contactos.
+contact-matches-matched = Números cargados en las listas de contactos: { $count }
+contact-matches-unidentified = Números frecuentes aún sin identificar: { $count }
+contact-matches-unidentified-hint = Números más frecuentes sin alias, resultado de abonado ni nombre de llamante.
+detail-dock = Acoplar en la ventana principal
+detail-pop-out = Separar
+detail-pop-out-hint = Abrir en una ventana aparte
+detail-record-missing = Este registro ya no está cargado.
+detail-start-utc = Inicio (UTC)
+detail-end-utc = Fin (UTC)
+detail-start-local = Inicio (hora local)
+detail-duration = Duración
+detail-duration-value = { $seconds } s ({ $minutes } min)
+detail-contact-missing = No hay llamadas cargadas con este número.
+detail-contact-calls = Total de llamadas: { $total } ({ $incoming } entrantes, { $outgoing } salientes)
+detail-contact-duration = Duración total: { $minutes } min
+detail-contact-subscriber = Abonado: { $name }, { $address }
+detail-contact-carrier = Operador: { $carrier }
+detail-contact-carrier-ported = Operador: { $carrier } (portado, LRN { $lrn })
+detail-contact-region = Región: { $region }
+detail-contact-caller-name = Nombre de identificador de llamadas: { $name }
+detail-contact-name = Nombre:
+detail-contact-name-hint = p. ej. Pat Doe (proveedor)
+detail-contact-name-save = Guardar nombre
+detail-contact-entity = Entidad: { $entity }
+detail-contact-categories = Número conocido: { $categories }
+detail-first-contact = Primer contacto: { $time }
+detail-last-contact = Último contacto: { $time }
+detail-timeline-empty = No hay análisis disponibles.
+detail-timeline-caption = Llamadas por día ({ $timezone })
+chart-interaction-hint = Arrastre para ampliar, desplácese de lado (o Mayús+rueda) para moverse y haga clic derecho para alejar. Haga clic para ver esos registros.
+detail-call-durations-caption = Llamadas con los números más frecuentes, del { $from } al { $to } UTC. Las barras naranjas se superponen con otra llamada del mismo objetivo ({ $overlapping } llamadas).
+detail-call-durations-call = { $start } UTC, { $minutes } min
+detail-call-durations-overlap = Se superpone con otra llamada del mismo objetivo
+detail-cumulative-total = Todas las llamadas, total acumulado por día ({ $timezone })
+detail-cumulative-contacts = Los { $count } contactos principales
+detail-skipped-summary = { $skipped } de { $total } registros no se pudieron leer y no figuran en los totales.
+detail-malformed-summary = { $count } bloques <results> tenían XML mal formado y se omitieron:
+detail-skipped-truncated = Se muestran los primeros { $shown }.
+detail-comparison-empty = No se ha realizado ninguna comparación.
+detail-diff-empty = No se han comparado producciones.
+diff-heading = Comparar producciones
+diff-hint = Cuando un operador vuelve a entregar registros del mismo periodo (una respuesta corregida o complementaria), compare los dos archivos para documentar lo que se añadió, eliminó o corrigió.
+diff-original-label = Original:
+diff-corrected-label = Corregida:
+diff-compare = Comparar
+diff-show = Ver diferencias
+diff-save-report = Guardar informe de diferencias
+diff-copy-report = Copiar informe
+diff-title = DIFERENCIAS ENTRE PRODUCCIONES
+diff-original = Producción original: { $name }
+diff-corrected = Producción corregida: { $name }
+diff-counts = { $unchanged } sin cambios, { $added } añadidos, { $removed } eliminados, { $changed } corregidos
+diff-identical = Las dos producciones contienen los mismos registros.
+diff-added-heading = Añadidos en la producción corregida ({ $count })
+diff-removed-heading = Eliminados de la producción corregida ({ $count })
+diff-changed-heading = Hora o duración corregida ({ $count })
+diff-record = { $time } { $direction } { $number } (objetivo { $target }), { $seconds } s
+diff-correction = ahora { $record } (inicio movido { $shift } s, duración cambiada { $duration } s)
+diff-header-original-time = Hora original
+diff-header-corrected-time = Hora corregida
+diff-header-original-duration = Original (s)
+diff-header-corrected-duration = Corregida (s)
+detail-evidence-empty = No se ha verificado la evidencia.
+evidence-title = VERIFICACIÓN DE EVIDENCIA
+evidence-case-file = Archivo de caso: { $path }
+evidence-verified-at = Verificado el: { $time }
+evidence-counts = { $verified } de { $total } archivos de origen coinciden con su hash registrado
+evidence-no-sources = El archivo de caso no registra archivos de origen.
+evidence-recorded-hash = SHA-256 registrado: { $hash }
+evidence-current-hash = SHA-256 actual: { $hash }
+evidence-found-at = Encontrado en: { $path }
+evidence-status-verified = Verificado
+evidence-status-altered = ALTERADO desde la importación
+evidence-status-missing = No encontrado
+evidence-status-unreadable = No se pudo leer: { $error }
+evidence-header-file = Archivo de origen
+evidence-header-status = Estado
+evidence-header-sha256 = SHA-256 registrado
+evidence-header-found-at = Encontrado en
+detail-keyword-hits-empty = No se han definido palabras clave para este caso.
+detail-geofence-empty = No se ha definido una geocerca para este caso.
+detail-tower-movement-empty = Ningún registro tiene ubicación de antena ni ID de celda.
+comparison-current-range = Este caso: { $range }
+comparison-other-range = { $name }: { $range }
+comparison-overlap = Ambos casos cubren: { $range }
+comparison-no-overlap = sin coincidencia
+comparison-shared-common-contacts = Contactos comunes en ambos casos ({ $count }):
+comparison-shared-numbers = Números en ambos casos ({ $count }):
+comparison-header-current = Este caso
+comparison-header-both-active = Activo en ambos
+comparison-target = objetivo
+discrepancy-outside-range = { $count } registros quedan fuera del rango declarado { $from } – { $to }.
+discrepancy-fewer-records = La producción declara { $declared } registros pero contiene { $found }.
+
+## Marcadores
+
+bookmarks-empty = No hay registros marcados. Haga clic en la ☆ junto a un registro de llamada para marcarlo.
+bookmarks-count =
+    { $count ->
+        [one] 1 registro marcado (incluido en el informe y en la exportación a Excel)
+       *[other] { $count } registros marcados (incluidos en el informe y en la exportación a Excel)
+    }
+bookmarks-show-details = Ver detalles del registro
+
+## Informe
+
+summary-heading = Informe resumido
+summary-copy = Copiar al portapapeles
+summary-copy-digest = Copiar hallazgos para correo
+summary-copy-digest-hint = Copia un resumen breve de los contactos principales, fechas clave, llamadas entre objetivos y los archivos a adjuntar, para un correo de actualización al agente del caso
+summary-empty = No hay informe disponible. Procese primero un archivo XML.
+summary-save-as = Guardar como { $format }
+summary-sections = Secciones del informe
+summary-sections-hint = Las secciones marcadas aparecen en el informe en este orden, en todos los formatos y en la hoja Informe resumido de Excel.
+summary-sections-save = Guardar diseño
+summary-sections-reset = Restablecer
+summary-template = Plantilla narrativa
+summary-template-hint = Su texto narrativo o de declaración jurada habitual, con marcadores que el informe completa con los datos de este caso. Aparece como la sección Narrativa.
+summary-template-placeholders = Marcadores: { $placeholders } (los contactos principales llegan hasta el tamaño de la clasificación)
+summary-template-example = El análisis de los registros de llamadas de { "{{targets}}" } del periodo { "{{date_range}}" } muestra { "{{total_calls}}" } llamadas con { "{{unique_numbers}}" } números únicos. El contacto más frecuente fue { "{{top_contact_1}}" } con { "{{top_contact_1_calls}}" } llamadas, y el { "{{night_call_pct}}" } de las llamadas ocurrió de noche.
+summary-template-unknown = Marcadores desconocidos, se dejan tal cual: { $placeholders }
+summary-template-save = Guardar plantilla
+summary-template-insert-example = Insertar ejemplo
+summary-template-insert-findings = Insertar hallazgos clave
+summary-template-insert-findings-hint = Añade los hallazgos clave generados a la plantilla como texto, para editarlos en la narrativa
+report-format-text = Texto
+report-format-pdf = PDF
+report-format-word = Word
+section-summary-stats = Estadísticas resumidas
+section-narrative = Narrativa
+section-key-findings = Hallazgos clave
+section-top-contacts = Contactos principales
+section-targets = Números objetivo
+section-common-contacts = Contactos comunes
+section-exchange-clusters = Centrales compartidas
+section-night-activity = Actividad nocturna
+section-bookmarks = Registros marcados
+section-calls-by-day = Llamadas por día
+section-calls-by-hour = Llamadas por hora
+section-data-quality = Calidad de datos
+quality-empty = No hay datos cargados. Procese un archivo XML para ver su integridad.
+quality-heading = Integridad de cada producción, como parte de los registros que contiene:
+quality-title = Calidad de datos
+quality-overall = Todas las producciones
+quality-records = Registros
+quality-loaded = Cargados
+quality-parse-failures = Errores de lectura
+quality-valid-timestamps = Marcas de tiempo válidas
+quality-with-direction = Con dirección
+quality-zero-duration = Duración cero
+quality-unusable-numbers = Números desconocidos o inválidos
+quality-duplicates = Duplicados
+quality-collapsed-duplicates = En otra producción
+quality-collapsed-duplicates-hint = Llamadas omitidas porque una producción anterior del caso ya las tenía (Configuración → Duplicados entre archivos)
+number-warnings-hint = Valores remotos que parecen basura en lugar de una parte: dígitos de relleno o de marcador, muy pocos dígitos para marcar, o códigos como el acceso al buzón de voz. Exclúyalos de los análisis o cambie cómo se cuentan.
+number-warnings-exclude = Excluir
+number-warnings-exclude-hint = Añadir a los números excluidos en la configuración, dejando sus llamadas fuera de los análisis de todos los casos
+number-warnings-excluded = Excluido
+record-warnings-hint = Registros que se leyeron pero parecen erróneos: terminan antes de empezar, su duración difiere en más de un minuto de las horas de inicio y fin, no tienen número remoto o empiezan en el futuro. Cuentan en los análisis salvo que se omitan abajo.
+record-warnings-exclude = Omitir de los análisis los { $count } registros señalados
+record-warnings-excluded = { $count } registros omitidos de los análisis actuales
+record-warnings-truncated = Se muestran los primeros { $shown } registros señalados
+record-issue-end-before-start = Termina antes de empezar
+record-issue-duration-mismatch = La duración no coincide con inicio y fin
+record-issue-missing-remote = Sin número remoto
+record-issue-future-date = En el futuro
+number-issue-not-a-number = No es un número
+number-issue-padded = Dígitos de relleno o de marcador
+number-issue-too-short = Muy pocos dígitos
+production-formats-hint = El analizador con que se leyó cada producción y la seguridad de la detección. Si los registros parecen incorrectos, por ejemplo con el día y el mes invertidos, vuelva a leer la producción con otro analizador.
+button-reparse-as = Volver a analizar como…
+button-map-columns = Asignar columnas…
+parser-lds101-xml = XML LDS-101
+parser-json = JSON LDS-101
+parser-lds102-xml = XML de abonados LDS-102
+parser-delimited = Texto delimitado ({ $profile })
+parser-att = Detalle de llamadas de AT&T
+parser-verizon = Informe de cumplimiento de Verizon
+parser-tmobile = Detalle de llamadas de T-Mobile
+parser-pdf = Registros de llamadas en PDF (patrones de fila)
+parser-csv-mapped = Texto delimitado (columnas asignadas a mano)
+parser-spreadsheet = Libro de Excel (columnas asignadas)
+mapping-generic = columnas genéricas, mes primero
+mapping-generic-day-first = columnas genéricas, día primero
+mapping-penlink = exportación de PenLink
+mapped-field-target = Número objetivo
+mapped-field-direction = Dirección
+mapped-field-remote = Número remoto
+mapped-field-originating = Número que llama
+mapped-field-terminating = Número llamado
+mapped-field-start = Fecha y hora de inicio
+mapped-field-date = Fecha de inicio
+mapped-field-time = Hora de inicio
+mapped-field-end = Fecha y hora de fin
+mapped-field-duration = Duración
+mapped-field-content = Contenido del mensaje
+mapped-field-disposition = Disposición o código de función
+confidence-low = Baja
+confidence-medium = Media
+confidence-high = Alta
+confidence-chosen = Elegido manualmente
+number-kind-nanp = Número norteamericano
+number-kind-international = Número internacional
+number-kind-short-code = Código corto o número de servicio
+number-kind-unknown = Identificador de llamada oculto
+number-kind-invalid = Número no marcable
+device-imei = IMEI
+device-imsi = IMSI
+record-type-voice = Llamada de voz
+record-type-sms = Mensaje de texto (SMS)
+record-type-mms = Mensaje multimedia (MMS)
+call-disposition-completed = Completada
+call-disposition-missed = Perdida
+call-disposition-busy = Ocupado
+call-disposition-voicemail = Buzón de voz
+call-disposition-forwarded = Desviada
+call-disposition-roaming = En itinerancia
+quality-line-records = { $total } registros, { $loaded } cargados, { $failed } no se pudieron leer
+quality-line-flagged = { $count } registros parecen erróneos:
+quality-line-implausible-moves = { $count } cambios de antena implican viajar a más de { $speed } km/h:
+quality-line-implausible-move = { $target }: { $from } a las { $left } a { $to } a las { $arrived }, { $distance } km
+
+subscribers-empty = No hay registros de abonados cargados. Procese un XML de abonados LDS-102 entregado con una producción para ver a quién pertenecen sus números.
+subscribers-heading = { $count ->
+    [one] 1 registro de abonado entregado con las producciones:
+   *[other] { $count } registros de abonados entregados con las producciones:
+}
+subscribers-as-target = Como objetivo
+subscribers-as-contact = Como contacto
+subscribers-name = Nombre
+subscribers-address = Dirección
+subscribers-account = Número de cuenta
+subscribers-email = Correo electrónico
+subscribers-account-start = Inicio de la cuenta
+subscribers-account-end = Fin de la cuenta
+
+## Scripts
+
+scripts-intro = Los scripts de Rhai se ejecutan sobre los registros cargados. Las filas que emiten se muestran abajo y se exportan como hojas adicionales.
+scripts-none = Sin scripts
+scripts-new = Nuevo script
+scripts-delete = Eliminar
+scripts-save = Guardar scripts
+scripts-save-hint = Los scripts se guardan junto con la configuración
+scripts-name = Nombre:
+scripts-run = Ejecutar
+scripts-running = Ejecutando…
+scripts-empty = Todavía no hay scripts. Cree uno para empezar.
+scripts-metrics = Métricas
+scripts-rows = Filas ({ $count })
+scripts-rows-truncated = Se muestran las primeras { $count } filas; la exportación las incluye todas.
+scripts-log = Salida
+
+## Tareas
+
+jobs-heading = Tareas en segundo plano
+jobs-clear-finished = Borrar finalizadas
+jobs-empty = Todavía no se ha ejecutado ninguna tarea en segundo plano.
+jobs-kind = Tipo
+jobs-job = Tarea
+jobs-status = Estado
+jobs-elapsed = Tiempo
+jobs-completed = Completada
+jobs-cancelled = Cancelada
+jobs-eta = quedan unos { $time }
+eta-seconds = { $count } s
+eta-minutes = { $count } min
+eta-hours = { $hours } h { $minutes } min
+
+job-kind-parse = Lectura
+job-kind-analytics = Análisis
+job-kind-export = Exportación
+job-kind-script = Script
+job-kind-compare = Comparación
+job-kind-lookup = Consulta
+job-parse = Leer { $name }
+job-preview = Vista previa de { $name }
+job-sample-columns = Leer las columnas de { $name }
+job-extract-pdf = Leer el texto de { $name }
+job-analyze = Analizar { $count } registros
+job-restore-session = Restaurar la sesión guardada automáticamente
+job-load-sample = Cargar datos de ejemplo
+job-export = Exportar { $path }
+job-google-sheets = Enviar a Google Sheets
+job-auto-export = Exportación automática a { $path }
+job-save-case = Guardar caso en { $path }
+job-open-case = Abrir { $path }
+job-compare-case = Comparar con { $path }
+job-verify-evidence = Verificar evidencia de { $path }
+job-compare-workspace = Comparar con { $name }
+job-diff-productions = Comparar { $original } con { $corrected }
+job-cnam-lookup = Consultar nombres de { $count } números
+job-script = Script { $name }
+
+progress-records-parsed = { $count } registros leídos
+progress-sampling-file = Leyendo una muestra del inicio del archivo
+progress-extracting-pdf = Leyendo el texto del PDF
+progress-computing-analytics = Calculando análisis…
+progress-loading-cached-analytics = Cargando análisis guardados…
+progress-writing-records = Escribiendo { $count } registros
+progress-finishing-workbook = Escribiendo las hojas restantes
+progress-writing-exhibits = Maquetando las pruebas
+progress-signing = Firmando la exportación
+progress-google-sign-in = Iniciando sesión en Google
+progress-loading-case = Cargando archivo de caso
+progress-comparing-cases = Comparando casos
+progress-hashing-sources = Calculando el hash de { $count } archivos de origen
+progress-diffing-productions = Comparando producciones
+progress-script-running = Ejecutando sobre { $count } registros
+progress-cnam-lookup = Consultados { $done } de { $total } números
+
+## Registros del sistema
+
+logs-level = Nivel:
+logs-search = Buscar:
+logs-copy = Copiar entradas mostradas
+logs-copy-hint = Copiar como líneas JSON, p. ej. para adjuntarlas a un informe de error
+logs-file = Registro completo: { $path }
+logs-no-file = El registro en archivo no está disponible en esta plataforma.
+logs-time = Hora (UTC)
+logs-level-header = Nivel
+logs-module = Módulo
+logs-case = Caso
+logs-message = Mensaje
+
+## Configuración
+
+settings-stored-in = Guardada en { $path }
+settings-no-config-dir = No hay un directorio de configuración disponible; la configuración solo dura esta sesión.
+settings-language = Idioma
+settings-timezone = Zona horaria del caso
+settings-number-format = Formato de números
+settings-default-region = País predeterminado
+settings-default-region-hint = Los números sin código de país se leen como de este país, p. ej. 55 1234 5678 como +52 55 1234 5678 para México. Los números con + o un prefijo internacional conservan su propio país. Se aplica a las producciones cargadas después.
+settings-night-hours = Horario nocturno
+settings-night-from = de
+settings-night-to = a
+settings-top-n = Números a clasificar
+settings-unanswered-seconds = Duración de llamada no contestada
+settings-unanswered-seconds-hint = Las llamadas de esta duración en segundos o menos cuentan como no contestadas en las tasas de respuesta; las de duración cero siempre cuentan
+settings-short-codes = Códigos cortos
+settings-short-codes-include = Clasificar códigos cortos y números de servicio como contactos
+settings-short-codes-hint = Las alertas bancarias, los códigos de verificación, *86 y otros accesos al buzón de voz quedan fuera de las clasificaciones, tasas de respuesta y demás análisis de contactos salvo que se marque esta opción; sus llamadas siguen contando en los totales
+settings-export-name = Nombre del archivo exportado
+settings-export-name-hint = Comodines: {"{date}"}, {"{time}"}, {"{target}"}. Ejemplo: { $example }
+settings-export-locale = Formato de exportación
+settings-export-locale-language = Seguir el idioma
+settings-export-locale-hint = Fechas, horas y decimales en los archivos Excel, CSV y PDF. Ejemplo: { $example }
+date-order-ymd = AAAA-MM-DD
+date-order-mdy = MM/DD/AAAA
+date-order-dmy = DD/MM/AAAA
+decimal-separator-point = Punto decimal (1.5)
+decimal-separator-comma = Coma decimal (1,5)
+clock-24-hour = Reloj de 24 horas
+clock-12-hour = Reloj de 12 horas (AM/PM)
+settings-excel-formats = Formatos de Excel
+settings-excel-datetime = Fecha y hora:
+settings-excel-duration = Duración:
+settings-excel-formats-hint = Formatos numéricos de Excel para las horas (en la zona horaria del caso) y la duración de las llamadas. Déjelo en blanco para seguir el formato de exportación.
+settings-watch-folder = Carpeta vigilada
+settings-watch-folder-hint = Carpeta en la que se buscan nuevas producciones. Déjela vacía para desactivarla.
+settings-preview-threshold = Vista previa a partir de
+settings-preview-threshold-hint = Las producciones abiertas de al menos este tamaño se muestrean primero, para comprobar el objetivo, el periodo y el tamaño y elegir la zona horaria y el tratamiento de duplicados antes de leerlas por completo. 0 desactiva la vista previa.
+settings-auto-export = Exportación automática
+settings-auto-export-enabled = Exportar Excel y CSV de cada nueva producción vigilada
+settings-auto-export-dir = Carpeta de exportación automática
+settings-auto-export-dir-hint = Dónde se escriben las exportaciones automáticas. Déjelo vacío para usar una carpeta "exports" dentro de la carpeta vigilada.
+settings-signing = Firma de exportaciones
+settings-sign-exports = Escribir una firma .sig separada junto a cada exportación
+settings-signing-key = Archivo de clave de la agencia
+settings-signing-key-hint = Archivo secreto de al menos 32 bytes compartido con los destinatarios que verifican las exportaciones.
+settings-verify-file = Verificar exportación
+settings-verify = Verificar
+settings-carriers = Búsqueda de operador
+settings-carrier-assignments = Asignaciones NPA-NXX:
+settings-carrier-assignments-hint = CSV con columnas NPA, NXX y Carrier
+settings-ported-numbers = Números portados:
+settings-ported-numbers-hint = CSV con columnas Number, LRN y Carrier opcional; tiene prioridad sobre las asignaciones de bloque
+settings-cnam = Nombres de llamantes (CNAM)
+settings-cnam-file = Archivo CNAM
+settings-cnam-file-hint = CSV con columnas Number y Name
+settings-cnam-lookup-enabled = Permitir consultas de nombres en línea
+settings-cnam-lookup-warning = Cada número consultado se envía al servicio de consulta. Las consultas solo se ejecutan al iniciarlas desde la pestaña Análisis.
+settings-cnam-lookup-url = URL de consulta
+settings-cnam-forget = Olvidar nombres consultados
+settings-cnam-forget-hint = Borra las respuestas guardadas; se conservan los nombres del archivo CNAM
+settings-google-sheets = Google Sheets
+settings-google-sheets-credentials = Clave de cuenta de servicio
+settings-google-sheets-credentials-hint = Archivo de clave JSON de una cuenta de servicio de Google Cloud con la API de Sheets habilitada
+settings-google-sheet-id = ID de hoja
+settings-google-sheet-id-hint = El ID en la URL de la hoja (docs.google.com/spreadsheets/d/<ID>/edit); comparta la hoja con el correo de la cuenta de servicio
+settings-known-numbers = Listas de números conocidos
+settings-known-numbers-hint = CSV con una columna Number y una columna Category opcional; sin ella, el nombre del archivo es la categoría
+settings-excluded = Números excluidos
+settings-excluded-hint = Se omiten del análisis, p. ej. buzón de voz o líneas de prueba del operador.
+settings-xml-recovery = XML mal formado
+settings-xml-recovery-enabled = Omitir bloques <results> mal formados
+settings-xml-recovery-hint = Sigue leyendo una producción LDS después de bloques <results> que no son XML bien formado, y enumera cada uno omitido, con su línea, elemento y motivo, en Problemas de importación. Desactivado, un bloque mal formado detiene el archivo.
+settings-cross-file-duplicates = Duplicados entre archivos
+settings-cross-file-duplicates-enabled = Contar una sola vez las llamadas repetidas entre producciones
+settings-cross-file-duplicates-hint = Cuando las producciones se superponen, p. ej. una respuesta complementaria que cubre parte de las fechas de la original, se omite la llamada con el mismo objetivo, número, inicio y duración que una de una producción anterior, para no contarla dos veces. La pestaña Calidad de datos muestra cuántas perdió cada producción. Se aplica a las producciones cargadas después.
+settings-pdf-patterns = Patrones de fila de PDF
+settings-pdf-patterns-hint = Expresiones regulares que se prueban, en orden, en cada línea del texto de un PDF. Grupos con nombre: remote, y start o date y time, son obligatorios; direction, duration, end, target y type se leen cuando existen.
+settings-pdf-patterns-add = Agregar patrón
+settings-pdf-patterns-restore = Restaurar patrones predeterminados
+settings-remove = Quitar
+settings-add = Añadir
+settings-save = Guardar configuración
+settings-reload = Volver a cargar del disco
+settings-restore-defaults = Restaurar valores predeterminados
+settings-clear-cache = Vaciar caché de análisis
+settings-clear-cache-hint = Eliminar los resultados de análisis guardados en este equipo para reabrir casos rápidamente
+
+## Diálogos
+
+recovery-title = ¿Recuperar la sesión anterior?
+recovery-body = La herramienta no se cerró correctamente la última vez. Hay una copia guardada automáticamente de los registros cargados y de su lista de vigilancia, etiquetas y marcadores.
+recovery-restore = Restaurar sesión
+recovery-discard = Descartar
+preview-title = Vista previa: { $name }
+preview-size = Tamaño del archivo:
+preview-format = Formato:
+preview-target = Objetivo:
+preview-declared-range = Periodo declarado:
+preview-range = del { $from } al { $to }
+preview-not-declared = no declarado
+preview-records = Registros:
+preview-estimated-records = unos { $count } (estimado a partir de { $sampled } en la muestra)
+preview-declared-records = La producción declara { $count } registros.
+preview-timezone = Zona horaria del caso:
+preview-duplicates = Registros duplicados:
+preview-no-records = No se encontraron registros de llamadas al inicio de este archivo. Puede que no sea una producción LDS-101.
+preview-load = Cargar
+column-mapping-title = Asignar columnas: { $name }
+column-mapping-hint = Elija la columna que contiene cada campo. Cada llamada necesita el número remoto (o los números que llama y llamado), una fecha y hora de inicio, y una duración u hora de fin; el resto puede quedar sin asignar.
+column-mapping-sheet = Hoja:
+column-mapping-header-row = Nombres de columna en la fila:
+column-mapping-preset = Partir de:
+column-mapping-custom = Personalizada
+column-mapping-none = (sin asignar)
+column-mapping-dates = Fechas:
+column-mapping-day-first = Día antes del mes, p. ej. 14/03/2024
+column-mapping-sample = Primeras filas tal como se leerán
+column-mapping-row-skipped = Se omitiría: { $reason }
+column-mapping-incomplete = Asigne el número remoto, el inicio y la duración u hora de fin para cargar esta producción.
+pdf-review-title = Revisar filas del PDF: { $name }
+pdf-review-hint = Estas líneas no coincidieron con ningún patrón o se leyeron con dudas. Corrija los campos, marque las filas que desea cargar y deje fuera el resto.
+pdf-review-summary = { $rows } filas leídas, { $review } por revisar
+pdf-review-show-all = Mostrar todas las filas
+pdf-review-keep = Cargar
+pdf-review-line = Línea
+pdf-review-problems = Problemas
+pdf-review-unmatched = Ningún patrón coincidió
+pdf-problem-time = la hora de inicio no se puede leer
+pdf-problem-number = el número remoto no es válido
+pdf-problem-duration = no hay duración ni hora de fin
+pdf-problem-direction = la dirección no es entrante ni saliente
+format-data-product = Producto de datos eSubpoena (LDS-101)
+format-lds101 = Resultados LDS-101
+format-unrecognized = No reconocido
+duplicates-keep = Conservar todos los registros
+duplicates-drop-exact = Descartar duplicados exactos
+
+palette-title = Paleta de comandos
+palette-hint = Escriba un comando…
+palette-no-matches = Ningún comando coincide
+
+cmd-open-file = Abrir archivo…
+cmd-new-case = Nuevo caso (asistente de configuración)
+cmd-add-productions = Añadir producciones al caso…
+cmd-export-excel = Exportar a Excel
+cmd-export-grouped = Exportar Excel agrupado por contacto con subtotales
+cmd-export-network = Exportar red CSV (nodos y aristas)
+cmd-export-cellebrite = Exportar registro de llamadas Cellebrite/UFED
+cmd-export-penlink = Exportar para PenLink
+cmd-export-parse-errors = Exportar errores de lectura
+cmd-export-elastic = Exportar NDJSON masivo para Elasticsearch
+cmd-export-exhibits = Exportar paquete de pruebas para el tribunal
+cmd-undo = Deshacer: { $change }
+cmd-redo = Rehacer: { $change }
+cmd-go-to-tab = Ir a la pestaña: { $tab }
+cmd-new-workspace = Nuevo espacio de trabajo
+cmd-switch-workspace = Cambiar al espacio de trabajo: { $name }
+cmd-compare-workspace = Comparar con el espacio de trabajo: { $name }
+cmd-apply-chip = Filtro: Aplicar { $chip }
+cmd-remove-chip = Filtro: Quitar { $chip }
+cmd-enable-regex = Filtro: Activar expresiones regulares
+cmd-disable-regex = Filtro: Desactivar expresiones regulares
+cmd-clear-filters = Filtro: Quitar todos los filtros
+cmd-number-format = Configuración: Formato de números { $format }
+cmd-show-local-time = Configuración: Mostrar columna de hora local
+cmd-hide-local-time = Configuración: Ocultar columna de hora local
+cmd-language = Configuración: Idioma { $language }
+cmd-start-tutorial = Ayuda: Iniciar tutorial
+
+## Tutorial
+
+tutorial-window-title = Tutorial: { $step }
+tutorial-start = Iniciar recorrido
+tutorial-skip = Omitir
+tutorial-next = Siguiente
+tutorial-end = Terminar recorrido
+tutorial-done = Listo
+
+wizard-window-title = Nuevo caso — Paso { $step } de { $steps }: { $title }
+wizard-details-title = Datos del caso
+wizard-details-body = Dé un nombre al caso para distinguirlo de otros; lo demás es opcional y solo se muestra en la Resumen general.
+wizard-defaults-title = Zona horaria y horario nocturno
+wizard-defaults-body = Las horas se muestran y exportan en la zona horaria del caso, y las llamadas que empiezan dentro del horario nocturno cuentan como actividad nocturna. Use la zona horaria en la que trabaja la investigación.
+wizard-productions-title = Producciones
+wizard-productions-body = Añada las producciones XML del operador por ruta o arrastrándolas a la ventana. También puede cargarlas más tarde desde la Resumen general.
+wizard-duplicates-title = Registros duplicados
+wizard-duplicates-body = A veces los operadores repiten un registro dentro de una producción. Conservarlos reproduce la producción exactamente; descartar las repeticiones exactas evita que inflen el número de llamadas.
+wizard-case-name = Nombre del caso
+wizard-case-number = Número de caso
+wizard-agency = Agencia
+wizard-analyst = Analista
+wizard-production-hint = Ruta de un archivo XML de producción
+wizard-add-production = Añadir
+wizard-no-productions = Aún no se han añadido producciones
+wizard-summary = { $name } usará { $timezone } con horario nocturno { $from }–{ $to } y cargará { $count ->
+        [one] { $count } producción
+       *[other] { $count } producciones
+    }.
+wizard-back = Atrás
+wizard-next = Siguiente
+wizard-finish = Crear caso
+tutorial-welcome-title = Bienvenida
+tutorial-welcome-body = Este recorrido carga una pequeña producción sintética para que pueda probar la herramienta sin datos reales de un caso. Todos los números son ficticios.
+tutorial-drop-zone-title = Cargar producciones
+tutorial-drop-zone-body = Suelte aquí las respuestas XML del operador o haga clic para buscarlas. Los datos de ejemplo ya están cargados.
+tutorial-tabs-title = Navegar por los resultados
+tutorial-tabs-body = Use estas pestañas para pasar del resumen general a los registros de llamadas, el panel de análisis y el informe escrito.
+tutorial-filters-title = Filtrar registros
+tutorial-filters-body = Escriba parte de un número, elija un rango de fechas o haga clic en un filtro rápido para acotar la tabla. Los filtros rápidos se combinan entre sí y con el filtro de texto.
+tutorial-export-title = Exportar
+tutorial-export-body = Cuando termine la revisión, exporte todo a un libro de Excel con una hoja por análisis.
+tutorial-finished-title = Todo listo
+tutorial-finished-body = Ese es el recorrido. Los datos de ejemplo siguen cargados hasta que procese un archivo real. Puede reiniciar el tutorial desde la paleta de comandos (Ctrl+Shift+P).
+
+## Mensajes
+
+msg-processing-file = Procesando archivo: { $name }
+msg-duplicates-dropped = Se descartaron { $count } registros duplicados
+msg-cross-file-duplicates-collapsed = Se contaron una sola vez { $count } llamadas repetidas entre producciones
+msg-cross-file-duplicates-found = { $count } llamadas aparecen en más de una producción y se cuentan cada vez; active Configuración → Duplicados entre archivos para contarlas una sola vez
+msg-productions-queued = { $count ->
+        [one] 1 producción en cola; se añadirá al caso cuando termine la carga actual
+       *[other] { $count } producciones en cola; se añadirán al caso cuando termine la carga actual
+    }
+msg-productions-added = { $count ->
+        [one] Añadiendo 1 producción ({ $records } registros) al caso
+       *[other] Añadiendo { $count } producciones ({ $records } registros) al caso
+    }
+msg-production-already-loaded = { $name } ya está en el caso y no se añadió de nuevo
+msg-processed = Se procesaron correctamente { $count } registros de llamadas
+msg-records-flagged = { $count } registros parecen erróneos (fin antes del inicio, fechas futuras, ...); vea Registros sospechosos en la pestaña Calidad de datos
+msg-session-restored = Sesión restaurada, guardada automáticamente el { $saved_at }
+msg-script-finished = El script "{ $name }" emitió { $count } filas
+msg-exported = Exportado correctamente a: { $path }
+msg-auto-exported = Exportado automáticamente: { $paths }
+msg-network-exported = Red exportada a { $paths }
+msg-google-sheets-pushed = Enviado a Google Sheets: { $url }
+msg-google-sheets-not-configured = Primero configure un archivo de clave de cuenta de servicio y un ID de hoja en Configuración
+msg-case-saved = Caso guardado en { $path }
+msg-case-opened = Se abrió { $path } con { $count } registros
+msg-evidence-verified = Los { $count } archivos de origen coinciden con los hashes registrados al importarlos
+msg-evidence-discrepancies = { $count } de { $total } archivos de origen están alterados, faltan o no se pueden leer
+msg-subscribers-imported = Se importaron { $count } abonados
+msg-subscribers-import-failed = Error al importar abonados: { $error }
+msg-contacts-imported = Se importaron { $count } números de contactos; { $added } alias nuevos
+msg-entity-merged = Se agregaron { $count } números a { $name }
+msg-number-excluded = { $number } excluido de los análisis
+msg-entity-no-numbers = No hay números de teléfono nuevos para agregar a { $name }
+msg-undone = Deshecho: { $change }
+msg-redone = Rehecho: { $change }
+msg-nothing-to-undo = Nada que deshacer
+msg-nothing-to-redo = Nada que rehacer
+change-watchlist = Cambio en la lista de vigilancia para { $number }
+change-alias = Cambio de nombre para { $number }
+change-tag = Etiqueta de registro
+change-note = Nota del registro
+change-bookmark = Marcador
+change-import-subscribers = Importación de resultados de abonados
+change-import-contacts = Importación de lista de contactos
+change-merge-entity = Fusionar en { $name }
+change-remove-from-entity = Quitar { $number } de su entidad
+change-dissolve-entity = Disolver { $name }
+change-keywords = Palabras clave de mensajes
+change-geofence = Geocerca
+change-reclassify-number = Contar { $number } como { $kind }
+msg-contacts-import-failed = Error al importar los contactos: { $error }
+msg-case-compared = Comparado con { $name }: { $count } números en común
+msg-compare-needs-records = Cargue un caso antes de compararlo con otro.
+msg-productions-diffed = Producciones comparadas: { $added } añadidos, { $removed } eliminados, { $changed } corregidos
+msg-workspace-busy = Espere a que termine de cargarse el archivo actual antes de cambiar de caso.
+msg-case-created = Caso "{ $name }" creado
+msg-wizard-file-missing = No hay ningún archivo en { $path }
+msg-format-detected = { $name }: leído como { $parser }
+msg-format-uncertain = { $name }: leído como { $parser } (confianza: { $confidence }); revise los registros o vuelva a analizarlo desde Calidad de datos
+msg-format-chosen = { $name }: leído de nuevo como { $parser }
+msg-reparse-unavailable = No se puede volver a analizar { $name }: su producción ya no está en el disco
+msg-workspace-closed = Se cerró el espacio de trabajo { $name }
+msg-signature-valid = { $name } coincide con su firma del { $signed_at }
+msg-signature-invalid = La verificación de la firma falló: { $error }
+msg-job-failed = Falló la tarea de { $kind }: { $error }
+msg-settings-saved = Configuración guardada en { $path }
+msg-settings-save-failed = No se pudo guardar la configuración: { $error }
+msg-carriers-loaded = Datos de operadores cargados
+msg-carriers-load-failed = Error al cargar los datos de operadores: { $error }
+msg-caller-names-loaded = Nombres de llamantes cargados
+msg-caller-names-load-failed = Error al cargar los nombres de llamantes: { $error }
+msg-known-numbers-loaded = Se cargaron { $count } números conocidos
+msg-known-numbers-load-failed = Error al cargar las listas de números conocidos: { $error }
+msg-cnam-lookup-failed = Error en la consulta de nombres: { $error }
+msg-cnam-nothing-to-look-up = Todos los contactos ya tienen nombre o ya se consultaron
+msg-cnam-lookup-finished = Se consultaron { $count } números; { $named } tenían nombre
+msg-cnam-lookup-stopped = La consulta de nombres se detuvo tras { $count } números: { $error }
+msg-cnam-cache-cleared = Se olvidaron { $count } nombres consultados
+msg-cache-cleared = Se eliminaron { $count } resultados de análisis guardados
+msg-cache-clear-failed = No se pudo vaciar la caché de análisis: { $error }
+msg-scripts-saved = Scripts guardados en { $path }
+msg-scripts-save-failed = No se pudieron guardar los scripts: { $error }
+msg-script-needs-records = Cargue registros antes de ejecutar un script
+msg-no-data-to-export = No hay datos para exportar
+msg-no-parse-errors = Ninguna producción tuvo registros omitidos
+msg-export-running = Ya hay una exportación en curso
+msg-downloaded = Se descargaron { $stem }.csv y { $stem }_summary.txt
+msg-network-downloaded = Se descargaron { $stem }_nodes.csv y { $stem }_edges.csv
+msg-file-downloaded = Se descargó { $name }
+msg-export-failed = Error al exportar: { $error }
+msg-xml-only = Suelte solo producciones XML, JSON, CSV, TSV, PDF o Excel, o archivos ZIP que las contengan
+msg-report-copied = Informe copiado al portapapeles
+msg-digest-copied = Resumen de hallazgos copiado al portapapeles
+msg-report-saved = Informe guardado en { $path }
+msg-report-save-failed = Error al guardar el informe: { $error }
+
+## Informe resumido
+
+report-title = ANÁLISIS DE DATOS DE TELECOMUNICACIONES
+report-summary-stats = ESTADÍSTICAS RESUMIDAS
+report-total-calls = Total de llamadas: { $count }
+report-incoming-calls = Llamadas entrantes: { $count }
+report-outgoing-calls = Llamadas salientes: { $count }
+report-unique-numbers = Números de teléfono únicos: { $count }
+report-unknown-numbers = Llamadas con identificador oculto: { $count }
+report-short-code-calls = Llamadas con códigos cortos y números de servicio (no clasificados como contactos): { $count }
+report-disposition-calls = Llamadas marcadas como { $disposition }: { $count }
+report-target-numbers = Números objetivo: { $count }
+report-files-processed = Archivos procesados: { $count }
+report-total-duration = Duración total: { $minutes } minutos
+report-average-duration = Duración media de las llamadas: { $minutes } minutos
+report-longest-call = Llamada más larga: { $seconds } segundos ({ $minutes } minutos) con { $number } el { $date }
+report-shortest-call = Llamada más corta: { $seconds } segundos con { $number } el { $date }
+report-date-range = Periodo: del { $from } al { $to }
+report-narrative = NARRATIVA
+report-key-findings = HALLAZGOS CLAVE
+finding-overview = { report-calls } con { $numbers } números únicos del { $from } al { $to }.
+finding-night-share = El { $pct }% de todas las llamadas fue entre las { $from } y las { $to }.
+finding-busiest-day = El día de más actividad fue el { $date }, con { report-calls }.
+finding-gap = Sin llamadas durante { $days } días, entre el { $from } y el { $to }.
+finding-direct-contact = Los objetivos { $a } y { $b } estuvieron en contacto directo: { report-calls } del { $first } al { $last }.
+finding-common-contact = { $number } estuvo en contacto con { $count } números objetivo.
+finding-number-on-devices = { $number } se usó en { $count } dispositivos según el { $kind }: { $spans }.
+finding-device-on-numbers = El { $kind } { $device } se usó con { $count } números objetivo: { $spans }.
+finding-device-span = { $value } del { $from } al { $to } ({ report-calls })
+finding-stopped-answering = { $number } dejó de contestar: las últimas { $count } llamadas quedaron sin respuesta después del { $date }.
+finding-contact = { $targets } tuvo { report-calls } con { $number } ({ $outgoing } salientes, { $incoming } entrantes)
+finding-contact-night = el { $pct }% entre las { $from } y las { $to }
+finding-contact-began = el contacto comenzó el { $date }
+finding-contact-ceased = el contacto cesó después del { $date }
+finding-named-number = { $number } ({ $name })
+template-date-range = del { $from } al { $to }
+exhibit-label = PRUEBA { $number }
+exhibit-footer = Prueba { $number } — Página { $page } de { $pages }
+exhibit-index-title = Índice de pruebas
+exhibit-index-targets = Números objetivo: { $targets }
+exhibit-index-period = Registros del { $from } al { $to } ({ $timezone })
+exhibit-index-prepared = Preparado el { $date }
+exhibit-index-exhibit = Prueba
+exhibit-index-description = Descripción
+exhibit-index-page = Página
+exhibit-summary = Resumen del análisis de llamadas
+exhibit-hour-chart = Llamadas por hora ({ $timezone })
+exhibit-day-chart = Llamadas por día ({ $timezone })
+exhibit-chart-caption = { $calls } llamadas en total
+exhibit-contact = Llamadas con { $number }
+exhibit-contact-name = Conocido como: { $name }
+exhibit-contact-entity = Entidad: { $entity }
+exhibit-contact-totals = { $total } llamadas ({ $incoming } entrantes, { $outgoing } salientes), { $minutes } minutos; horas en { $timezone }
+exhibit-column-time = Fecha/hora
+exhibit-column-direction = Dirección
+exhibit-column-target = Objetivo
+exhibit-column-duration = Duración
+exhibit-column-source = Archivo de origen
+report-most-frequent = NÚMEROS MÁS FRECUENTES
+report-targets = NÚMEROS OBJETIVO
+report-excluded-targets = Excluidos del análisis: { $targets }
+report-common-contacts = CONTACTOS COMUNES ENTRE NÚMEROS OBJETIVO
+report-common-contact = { $number }: aparece en { $count } números objetivo ({ $targets })
+report-exchange-clusters = CENTRALES COMPARTIDAS
+report-exchange-cluster = { $prefix }: { $count } números, { $calls } llamadas ({ $numbers })
+report-bookmarks = REGISTROS MARCADOS
+report-night-activity = ACTIVIDAD NOCTURNA ({ $timezone })
+report-night-calls = { report-calls } entre las { $from } y las { $to }
+report-calls-by-day = LLAMADAS POR DÍA ({ $timezone })
+report-calls-by-hour = LLAMADAS POR HORA ({ $timezone })
+report-calls =
+    { $count ->
+        [one] 1 llamada
+       *[other] { $count } llamadas
+    }
+report-number-calls = { $number } ({ report-calls })
+report-seconds = { $seconds } segundos
+digest-overview = Registros de llamadas: { $calls } llamadas de { $targets } números objetivo, del { $from } al { $to } ({ $timezone })
+digest-top-contacts = Contactos principales:
+digest-named-contact = { $number }, { $name } ({ report-calls })
+digest-key-dates = Fechas clave:
+digest-first-call = Primera llamada: { $date }
+digest-last-call = Última llamada: { $date }
+digest-busiest-day = Día con más actividad: { $date } ({ report-calls })
+digest-direct-contacts = Contactos directos entre objetivos:
+digest-direct-contact = { $a } ↔ { $b }: { report-calls }, del { $first } al { $last }
+digest-no-direct-contacts = No se encontraron
+digest-attachments = Adjuntos:
+digest-sources = Producciones de origen: { $files }
+
+## Exportación a Excel
+
+sheet-call-records = Registros de llamadas
+sheet-analytics = Análisis
+sheet-summary-report = Informe resumido
+sheet-common-contacts = Contactos comunes
+sheet-bookmarks = Marcadores
+sheet-subscribers = Resolución de abonados
+sheet-subscriber-info = Información de abonados
+sheet-known-numbers = Números conocidos
+sheet-call-durations = Duración de llamadas
+sheet-exchange-clusters = Centrales compartidas
+sheet-area-codes = Códigos de área
+sheet-contact-summary = Resumen de contactos
+sheet-contact-spans = Períodos de contacto
+sheet-calls-by-contact = Llamadas por contacto
+grouped-subtotal = Total { $number }
+grouped-grand-total = Total general
+sheet-no-common-contacts = No se encontraron contactos comunes entre los números objetivo
+sheet-no-exchange-clusters = Ninguna central tiene 3 o más números contactados
+sheet-no-area-codes = No se contactaron números norteamericanos
+sheet-no-bookmarks = No se marcó ningún registro
+sheet-keyword-hits = Palabras clave
+sheet-no-keyword-hits = Ningún mensaje menciona las palabras clave
+sheet-geofence-hits = Llamadas en la geocerca
+sheet-location-analysis = Análisis de ubicación
+sheet-no-geofence-hits = Ninguna llamada fue en antenas dentro de la geocerca
+sheet-no-tower-changes = No hay cambios de antena
+sheet-no-calls = No hay llamadas con los números más frecuentes
+sheet-no-known-numbers = Ningún contacto cargado figura en las listas de números conocidos
+sheet-no-contact-spans = No se cargó ningún contacto
+sheet-contact-spans-note = En curso: último contacto con un objetivo dentro de los { $days } días previos al final de los registros de ese objetivo
+sheet-call-durations-note = Cada columna abarca { $minutes } minutos (UTC); las celdas indican los minutos en llamada. Las celdas naranjas contienen llamadas que se superponen con otra llamada del mismo objetivo ({ $overlapping } llamadas).
+sheet-unresolved = Sin resolver
+carrier-ported = { $carrier } (portado)
+carrier-lrn = LRN { $lrn }
+sheet-script = Script - { $name }
+sheet-script-no-output = El script no produjo resultados
+
+metric-total-calls = Total de llamadas
+metric-incoming-calls = Llamadas entrantes
+metric-outgoing-calls = Llamadas salientes
+metric-unique-numbers = Números de teléfono únicos
+metric-unknown-numbers = Llamadas con identificador oculto
+metric-total-duration = Duración total (minutos)
+metric-average-duration = Duración media de las llamadas (minutos)
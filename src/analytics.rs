@@ -1,237 +1,804 @@
-use crate::data_models::{Analytics, ProcessedCallRecord};
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use crate::annotations::Annotations;
+use crate::area_codes::{area_code_region, region_label};
+use crate::data_models::{
+    Analytics, AreaCodeActivity, CallDisposition, ContactAnswerRate, ContactMessages, ContactNightActivity, ContactSpan,
+    DeviceChange, DeviceChangeKind, DeviceIdKind, DeviceSpan, ExchangeCluster, NumberKind, ProcessedCallRecord, RecordType,
+    ONGOING_CONTACT_DAYS,
+};
+use crate::filters::{is_night_hour, DEFAULT_NIGHT_HOURS};
+use crate::record_warnings::RecordIssue;
+use crate::report::{Report, ReportInput};
+use crate::settings::AppSettings;
+use crate::tr;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use log::info;
 
+/// Length of the most-frequent-numbers ranking unless configured otherwise.
+pub const DEFAULT_TOP_N: usize = 10;
+
+/// Fewest distinct contacted numbers from one NPA-NXX reported as a shared exchange.
+pub const MIN_EXCHANGE_CLUSTER: usize = 3;
+
+/// Calls this many seconds long or shorter count as unanswered unless configured otherwise.
+pub const DEFAULT_UNANSWERED_SECONDS: u32 = 3;
+
+/// Fewest calls placed to a contact before its answer rate is reported.
+pub const MIN_ANSWER_RATE_ATTEMPTS: usize = 5;
+
+/// Each target number's calls on each device, keyed by the kind of identifier, the number,
+/// and the device, as `(calls, first, last)`.
+type DeviceUses = HashMap<(DeviceIdKind, String, String), (usize, DateTime<Utc>, DateTime<Utc>)>;
+
+/// Each target's last record, with each contact's last contact with the target.
+type TargetContacts = HashMap<String, (DateTime<Utc>, HashMap<String, DateTime<Utc>>)>;
+
+/// Each contact's calls as `(start, outgoing, answered)`, for the answer rates.
+type ContactAnswers = HashMap<String, Vec<(DateTime<Utc>, bool, bool)>>;
+
+/// Fewest records handed to each analytics thread; smaller inputs stay on the calling thread.
+const MIN_RECORDS_PER_TASK: usize = 16 * 1024;
+
+/// Knobs for an analytics pass, normally taken from [`AppSettings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsOptions {
+    pub top_n: usize,
+    /// Normalized numbers whose records are skipped entirely.
+    pub excluded_numbers: HashSet<String>,
+    /// Targets left out of the combined totals and common-contact detection. Their records
+    /// stay loaded; this is a per-case choice, not a setting.
+    pub excluded_targets: HashSet<String>,
+    /// Zone the by-day and by-hour buckets are counted in, so "3 AM" means 3 AM where the case is.
+    pub timezone: Tz,
+    /// Calls no longer than this count as unanswered in the answer rates.
+    pub unanswered_max_seconds: u32,
+    /// Night window (start hour inclusive, end hour exclusive) for the odd-hours scores.
+    pub night_hours: (u32, u32),
+    /// Per-case overrides of the parser's number classification, by normalized number.
+    pub reclassified_numbers: BTreeMap<String, NumberKind>,
+    /// Skip records the validation pass flags (end before start, future dates, ...).
+    pub exclude_flagged_records: bool,
+    /// Rank short codes and service numbers as contacts instead of only counting their calls.
+    pub include_short_codes: bool,
+}
+
+impl Default for AnalyticsOptions {
+    fn default() -> Self {
+        Self {
+            top_n: DEFAULT_TOP_N,
+            excluded_numbers: HashSet::new(),
+            excluded_targets: HashSet::new(),
+            timezone: Tz::UTC,
+            unanswered_max_seconds: DEFAULT_UNANSWERED_SECONDS,
+            night_hours: DEFAULT_NIGHT_HOURS,
+            reclassified_numbers: BTreeMap::new(),
+            exclude_flagged_records: false,
+            include_short_codes: false,
+        }
+    }
+}
+
+impl AnalyticsOptions {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            top_n: settings.top_n,
+            excluded_numbers: settings.excluded_numbers.iter().cloned().collect(),
+            excluded_targets: HashSet::new(),
+            timezone: settings.case_timezone,
+            unanswered_max_seconds: settings.unanswered_max_seconds,
+            night_hours: settings.night_hours,
+            reclassified_numbers: BTreeMap::new(),
+            exclude_flagged_records: settings.exclude_flagged_records,
+            include_short_codes: settings.include_short_codes,
+        }
+    }
+    
+    pub fn with_excluded_targets(mut self, excluded_targets: HashSet<String>) -> Self {
+        self.excluded_targets = excluded_targets;
+        self
+    }
+    
+    pub fn with_reclassified_numbers(mut self, reclassified_numbers: BTreeMap<String, NumberKind>) -> Self {
+        self.reclassified_numbers = reclassified_numbers;
+        self
+    }
+    
+    /// The record's number kind, unless the analyst reclassified the number.
+    pub fn number_kind(&self, record: &ProcessedCallRecord) -> NumberKind {
+        self.reclassified_numbers.get(&record.normalized_number).copied().unwrap_or(record.number_kind)
+    }
+}
+
 pub struct AnalyticsEngine;
 
 impl AnalyticsEngine {
     pub fn generate_analytics(records: &[ProcessedCallRecord]) -> Analytics {
+        Self::generate_analytics_with(records, &AnalyticsOptions::default())
+    }
+    
+    pub fn generate_analytics_with(records: &[ProcessedCallRecord], options: &AnalyticsOptions) -> Analytics {
         info!("Generating analytics for {} records", records.len());
         
-        if records.is_empty() {
-            return Analytics {
-                total_calls: 0,
-                incoming_calls: 0,
-                outgoing_calls: 0,
-                total_duration_minutes: 0.0,
-                average_call_duration: 0.0,
-                unique_numbers: 0,
-                most_frequent_numbers: Vec::new(),
-                calls_by_day: HashMap::new(),
-                calls_by_hour: HashMap::new(),
-                longest_call: None,
-                shortest_call: None,
-                target_numbers: std::collections::HashSet::new(),
-                common_contacts: Vec::new(),
-                files_processed: std::collections::HashSet::new(),
-                date_range: (Utc::now(), Utc::now()),
-            };
+        let mut accumulator = AnalyticsAccumulator::new(options.clone());
+        accumulator.add_all(records);
+        accumulator.finish()
+    }
+    
+    /// The summary report in the layout chosen in settings. Callers that have the parse
+    /// reports should compose a [`Report`] themselves so the data-quality section sees them.
+    pub fn generate_summary_report(analytics: &Analytics, records: &[ProcessedCallRecord], annotations: &Annotations, settings: &AppSettings) -> String {
+        let input = ReportInput { analytics, records, annotations, settings, parse_reports: &[] };
+        Report::compose(&settings.report_sections, &input).to_text()
+    }
+    
+    fn find_common_contacts(target_groups: &HashMap<String, HashSet<String>>) -> Vec<crate::data_models::CommonContact> {
+        // Find numbers that appear in multiple target groups
+        if target_groups.len() <= 1 {
+            return Vec::new();
         }
         
-        let total_calls = records.len();
-        let incoming_calls = records.iter()
-            .filter(|r| r.message_direction.eq_ignore_ascii_case("incoming"))
-            .count();
-        let outgoing_calls = total_calls - incoming_calls;
-        
-        let total_duration_minutes: f64 = records.iter()
-            .map(|r| r.duration_minutes)
-            .sum();
-        
-        let average_call_duration = if total_calls > 0 {
-            total_duration_minutes / total_calls as f64
-        } else {
-            0.0
-        };
-        
-        let unique_numbers = records.iter()
-            .map(|r| &r.normalized_number)
-            .collect::<std::collections::HashSet<_>>()
-            .len();
-        
-        let mut number_counts: HashMap<String, usize> = HashMap::new();
-        for record in records {
-            *number_counts.entry(record.normalized_number.clone()).or_insert(0) += 1;
+        let mut all_numbers = HashSet::new();
+        for numbers in target_groups.values() {
+            all_numbers.extend(numbers.iter());
         }
         
-        let mut most_frequent_numbers: Vec<(String, usize)> = number_counts.into_iter().collect();
-        most_frequent_numbers.sort_by(|a, b| b.1.cmp(&a.1));
-        most_frequent_numbers.truncate(10); // Top 10
+        let mut common_contacts: Vec<_> = all_numbers.into_par_iter()
+            .filter_map(|number| {
+                let target_numbers_with_contact: Vec<String> = target_groups.iter()
+                    .filter(|(_, numbers)| numbers.contains(number))
+                    .map(|(target, _)| target.clone())
+                    .collect();
+                
+                (target_numbers_with_contact.len() > 1).then(|| crate::data_models::CommonContact {
+                    number: number.clone(),
+                    count: target_numbers_with_contact.len(),
+                    target_numbers: target_numbers_with_contact,
+                })
+            })
+            .collect();
         
-        let mut calls_by_day: HashMap<String, usize> = HashMap::new();
-        for record in records {
-            *calls_by_day.entry(record.date.clone()).or_insert(0) += 1;
+        // Sort by number of target numbers they appear in
+        common_contacts.sort_by(|a, b| b.count.cmp(&a.count));
+        common_contacts
+    }
+    
+    /// Answer rates and unanswered streaks per contact, folding each contact's calls in time order.
+    fn find_answer_rates(contact_answers: ContactAnswers) -> Vec<ContactAnswerRate> {
+        let mut rates: Vec<ContactAnswerRate> = contact_answers.into_par_iter()
+            .filter_map(|(number, mut calls)| {
+                calls.sort_unstable();
+                let run = calls.into_iter()
+                    .map(|(start, outgoing, answered)| AnswerRun::call(start, outgoing, answered))
+                    .reduce(AnswerRun::then)?;
+                if run.attempts < MIN_ANSWER_RATE_ATTEMPTS {
+                    return None;
+                }
+                Some(ContactAnswerRate {
+                    number,
+                    attempts: run.attempts,
+                    answered: run.answered,
+                    longest_unanswered_streak: run.longest_unanswered,
+                    trailing_unanswered: run.trailing_unanswered,
+                    last_answered: run.last_answered,
+                    incoming: run.incoming,
+                    incoming_unanswered: run.incoming_unanswered,
+                })
+            })
+            .collect();
+        rates.sort_by(|a, b| b.stopped_answering().cmp(&a.stopped_answering())
+            .then_with(|| b.attempts.cmp(&a.attempts))
+            .then_with(|| a.number.cmp(&b.number)));
+        rates
+    }
+    
+    /// The `top_n` contacts with the most messages.
+    fn find_messages_per_contact(contact_messages: HashMap<String, ContactMessages>, top_n: usize) -> Vec<ContactMessages> {
+        let mut contacts: Vec<ContactMessages> = contact_messages.into_values().collect();
+        contacts.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.number.cmp(&b.number)));
+        contacts.truncate(top_n);
+        contacts
+    }
+    
+    /// Night activity per contact with any night calls, ranked by odd-hours score.
+    fn find_odd_hours_contacts(number_counts: &HashMap<String, usize>, night_counts: HashMap<String, usize>) -> Vec<ContactNightActivity> {
+        let mut contacts: Vec<ContactNightActivity> = night_counts.into_iter()
+            .map(|(number, night_calls)| ContactNightActivity {
+                calls: number_counts.get(&number).copied().unwrap_or(night_calls),
+                number,
+                night_calls,
+            })
+            .collect();
+        contacts.sort_by(|a, b| b.odd_hours_score().total_cmp(&a.odd_hours_score())
+            .then_with(|| b.night_calls.cmp(&a.night_calls))
+            .then_with(|| a.number.cmp(&b.number)));
+        contacts
+    }
+    
+    /// Target numbers on more than one device and devices with more than one target number,
+    /// from each number's calls on each device as `(calls, first, last)`. IMEIs and IMSIs are
+    /// compared separately, so a phone swap and a SIM swap are told apart.
+    fn find_device_changes(device_uses: &DeviceUses) -> Vec<DeviceChange> {
+        let mut by_number: BTreeMap<(DeviceIdKind, &str), Vec<DeviceSpan>> = BTreeMap::new();
+        let mut by_device: BTreeMap<(DeviceIdKind, &str), Vec<DeviceSpan>> = BTreeMap::new();
+        for ((kind, number, device), &(calls, first, last)) in device_uses {
+            by_number.entry((*kind, number)).or_default().push(DeviceSpan { value: device.clone(), calls, first, last });
+            by_device.entry((*kind, device)).or_default().push(DeviceSpan { value: number.clone(), calls, first, last });
         }
         
-        let mut calls_by_hour: HashMap<u32, usize> = HashMap::new();
-        for record in records {
-            let hour = record.start_time.hour();
-            *calls_by_hour.entry(hour).or_insert(0) += 1;
+        let mut changes = Vec::new();
+        for (change, groups) in [(DeviceChangeKind::NumberOnDevices, by_number), (DeviceChangeKind::DeviceOnNumbers, by_device)] {
+            for ((id_kind, subject), mut spans) in groups.into_iter().filter(|(_, spans)| spans.len() > 1) {
+                spans.sort_by(|a, b| a.first.cmp(&b.first).then_with(|| a.value.cmp(&b.value)));
+                changes.push(DeviceChange { change, id_kind, subject: subject.to_string(), spans });
+            }
         }
-        
-        let longest_call = records.iter()
-            .max_by(|a, b| a.length_of_call.cmp(&b.length_of_call))
-            .cloned();
-        
-        let shortest_call = records.iter()
-            .filter(|r| r.length_of_call > 0) // Exclude 0-second calls
-            .min_by(|a, b| a.length_of_call.cmp(&b.length_of_call))
-            .cloned();
-        
-        let min_date = records.iter()
-            .map(|r| r.start_time)
-            .min()
-            .unwrap_or_else(Utc::now);
-        
-        let max_date = records.iter()
-            .map(|r| r.start_time)
-            .max()
-            .unwrap_or_else(Utc::now);
-        
-        let date_range = (min_date, max_date);
-        
-        // Collect target numbers and source files
-        let target_numbers: std::collections::HashSet<String> = records.iter()
-            .map(|r| r.target_number.clone())
-            .filter(|t| !t.is_empty())
+        changes
+    }
+    
+    fn find_area_code_activity(number_counts: &HashMap<String, usize>) -> Vec<AreaCodeActivity> {
+        let mut by_area_code: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (number, count) in number_counts {
+            if number.len() == 10 && number.bytes().all(|b| b.is_ascii_digit()) {
+                let activity = by_area_code.entry(&number[..3]).or_insert((0, 0));
+                *activity = (activity.0 + 1, activity.1 + count);
+            }
+        }
+        let mut activity: Vec<AreaCodeActivity> = by_area_code.into_iter()
+            .map(|(area_code, (numbers, calls))| AreaCodeActivity {
+                area_code: area_code.to_string(),
+                region: area_code_region(area_code).map(str::to_string),
+                numbers,
+                calls,
+            })
             .collect();
-        
-        let files_processed: std::collections::HashSet<String> = records.iter()
-            .map(|r| r.source_file.clone())
-            .filter(|f| !f.is_empty())
+        activity.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.area_code.cmp(&b.area_code)));
+        activity
+    }
+    
+    /// Each contact's span from `contact_times`, ongoing when its last contact with some
+    /// target is within [`ONGOING_CONTACT_DAYS`] of that target's last record, so a target
+    /// whose production ends early is judged by its own end.
+    fn find_contact_spans(
+        number_counts: &HashMap<String, usize>,
+        contact_times: HashMap<String, (DateTime<Utc>, DateTime<Utc>)>,
+        target_contacts: &TargetContacts,
+    ) -> Vec<ContactSpan> {
+        let mut ongoing: HashSet<&str> = HashSet::new();
+        for (target_end, last_contacts) in target_contacts.values() {
+            let ongoing_from = *target_end - chrono::Duration::days(ONGOING_CONTACT_DAYS);
+            ongoing.extend(last_contacts.iter()
+                .filter(|(_, last_contact)| **last_contact >= ongoing_from)
+                .map(|(number, _)| number.as_str()));
+        }
+        let mut spans: Vec<ContactSpan> = contact_times.into_iter()
+            .map(|(number, (first_contact, last_contact))| ContactSpan {
+                calls: number_counts.get(&number).copied().unwrap_or(0),
+                ongoing: ongoing.contains(number.as_str()),
+                number,
+                first_contact,
+                last_contact,
+            })
             .collect();
-        
-        // Find common contacts across target numbers
-        let common_contacts = Self::find_common_contacts(records);
-        
-        Analytics {
-            total_calls,
-            incoming_calls,
-            outgoing_calls,
-            total_duration_minutes,
-            average_call_duration,
-            unique_numbers,
-            most_frequent_numbers,
-            calls_by_day,
-            calls_by_hour,
-            longest_call,
-            shortest_call,
-            target_numbers,
-            common_contacts,
-            files_processed,
-            date_range,
+        spans.sort_by(|a, b| a.first_contact.cmp(&b.first_contact).then_with(|| a.number.cmp(&b.number)));
+        spans
+    }
+    
+    fn find_calls_by_region(area_code_activity: &[AreaCodeActivity]) -> Vec<(String, usize)> {
+        let mut by_region: HashMap<&str, usize> = HashMap::new();
+        for activity in area_code_activity {
+            if let Some(region) = &activity.region {
+                *by_region.entry(region).or_insert(0) += activity.calls;
+            }
         }
+        let mut calls_by_region: Vec<(String, usize)> = by_region.into_iter()
+            .map(|(region, count)| (region.to_string(), count))
+            .collect();
+        calls_by_region.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        calls_by_region
     }
     
-    pub fn generate_summary_report(analytics: &Analytics, records: &[ProcessedCallRecord]) -> String {
-        let mut report = String::new();
-        
-        report.push_str("=== TELECOMMUNICATION DATA ANALYSIS ===\n\n");
-        
-        report.push_str(&format!("Total Calls: {}\n", analytics.total_calls));
-        report.push_str(&format!("Incoming Calls: {}\n", analytics.incoming_calls));
-        report.push_str(&format!("Outgoing Calls: {}\n", analytics.outgoing_calls));
-        report.push_str(&format!("Unique Phone Numbers: {}\n", analytics.unique_numbers));
-        report.push_str(&format!("Target Numbers: {}\n", analytics.target_numbers.len()));
-        report.push_str(&format!("Files Processed: {}\n", analytics.files_processed.len()));
-        report.push_str(&format!("Total Duration: {:.2} minutes\n", analytics.total_duration_minutes));
-        report.push_str(&format!("Average Call Duration: {:.2} minutes\n", analytics.average_call_duration));
-        
-        if let Some(longest) = &analytics.longest_call {
-            report.push_str(&format!("Longest Call: {} seconds ({:.2} minutes) to {} on {}\n", 
-                longest.length_of_call, longest.duration_minutes, longest.remote_number, longest.date));
+    /// Groups ten-digit NANP numbers by NPA-NXX and keeps exchanges with at least
+    /// [`MIN_EXCHANGE_CLUSTER`] distinct numbers.
+    fn find_exchange_clusters(number_counts: &HashMap<String, usize>) -> Vec<ExchangeCluster> {
+        let mut by_prefix: HashMap<&str, Vec<(String, usize)>> = HashMap::new();
+        for (number, count) in number_counts {
+            if number.len() == 10 && number.bytes().all(|b| b.is_ascii_digit()) {
+                by_prefix.entry(&number[..6]).or_default().push((number.clone(), *count));
+            }
         }
         
-        if let Some(shortest) = &analytics.shortest_call {
-            report.push_str(&format!("Shortest Call: {} seconds to {} on {}\n", 
-                shortest.length_of_call, shortest.remote_number, shortest.date));
+        let mut clusters: Vec<ExchangeCluster> = by_prefix.into_iter()
+            .filter(|(_, numbers)| numbers.len() >= MIN_EXCHANGE_CLUSTER)
+            .map(|(prefix, mut numbers)| {
+                numbers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                ExchangeCluster {
+                    prefix: prefix.to_string(),
+                    total_calls: numbers.iter().map(|(_, count)| count).sum(),
+                    numbers,
+                }
+            })
+            .collect();
+        clusters.sort_by(|a, b| b.numbers.len().cmp(&a.numbers.len())
+            .then_with(|| b.total_calls.cmp(&a.total_calls))
+            .then_with(|| a.prefix.cmp(&b.prefix)));
+        clusters
+    }
+}
+
+/// Running totals behind [`Analytics`], fed one record at a time.
+///
+/// Memory grows with the number of distinct remote numbers and days, not with the
+/// number of records, so the ingestion pipeline can compute analytics for a production
+/// without ever holding all of its records.
+///
+/// Accumulators over consecutive slices of records can be [merged](Self::merge), which is
+/// how [`add_all`](Self::add_all) spreads a large slice across threads.
+#[derive(Debug, Default)]
+pub struct AnalyticsAccumulator {
+    options: AnalyticsOptions,
+    total_calls: usize,
+    incoming_calls: usize,
+    total_duration_minutes: f64,
+    unknown_number_calls: usize,
+    short_code_calls: usize,
+    sms_count: usize,
+    mms_count: usize,
+    /// Records with each disposition, in [`CallDisposition::ALL`] order.
+    disposition_counts: [usize; 6],
+    /// Length of the voice calls only, for the average.
+    voice_duration_minutes: f64,
+    number_counts: HashMap<String, usize>,
+    calls_by_day: HashMap<String, usize>,
+    calls_by_hour: HashMap<u32, usize>,
+    longest_call: Option<ProcessedCallRecord>,
+    shortest_call: Option<ProcessedCallRecord>,
+    date_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    target_groups: HashMap<String, HashSet<String>>,
+    /// Each contact's calls, for the answer rates.
+    contact_answers: ContactAnswers,
+    /// Each contact's calls starting within the night hours.
+    night_counts: HashMap<String, usize>,
+    contact_messages: HashMap<String, ContactMessages>,
+    /// Each contact's first and last start time.
+    contact_times: HashMap<String, (DateTime<Utc>, DateTime<Utc>)>,
+    /// Whether contact is ongoing is judged per target, against the target's own end.
+    target_contacts: TargetContacts,
+    device_uses: DeviceUses,
+    files_processed: HashSet<String>,
+    /// When records are judged to be in the future.
+    validated_at: DateTime<Utc>,
+    flagged_records_excluded: usize,
+}
+
+impl AnalyticsAccumulator {
+    pub fn new(options: AnalyticsOptions) -> Self {
+        Self {
+            options,
+            validated_at: Utc::now(),
+            ..Self::default()
+        }
+    }
+    
+    pub fn add(&mut self, record: &ProcessedCallRecord) {
+        if self.options.excluded_numbers.contains(&record.normalized_number)
+            || self.options.excluded_targets.contains(&record.target_number)
+        {
+            return;
+        }
+        if self.options.exclude_flagged_records && RecordIssue::any(record, self.validated_at) {
+            self.flagged_records_excluded += 1;
+            return;
         }
         
-        report.push_str(&format!("Date Range: {} to {}\n", 
-            analytics.date_range.0.format("%Y-%m-%d"), 
-            analytics.date_range.1.format("%Y-%m-%d")));
-        
-        report.push_str("\n=== MOST FREQUENT NUMBERS ===\n");
-        for (i, (number, count)) in analytics.most_frequent_numbers.iter().enumerate() {
-            report.push_str(&format!("{}. {} ({} calls)\n", i + 1, number, count));
+        self.total_calls += 1;
+        if record.message_direction.eq_ignore_ascii_case("incoming") {
+            self.incoming_calls += 1;
+        }
+        self.total_duration_minutes += record.duration_minutes;
+        let message = record.record_type.is_message();
+        match record.record_type {
+            RecordType::Voice => self.voice_duration_minutes += record.duration_minutes,
+            RecordType::Sms => self.sms_count += 1,
+            RecordType::Mms => self.mms_count += 1,
+        }
+        self.disposition_counts[record.disposition as usize] += 1;
+        
+        // Withheld and garbage values are not one shared contact, so they stay out of the
+        // rankings, as do short codes unless asked for
+        let number_kind = self.options.number_kind(record);
+        let valid_number = number_kind.is_contact(self.options.include_short_codes);
+        if valid_number {
+            increment(&mut self.number_counts, &record.normalized_number);
+            let time = record.start_time;
+            match self.contact_times.get_mut(&record.normalized_number) {
+                Some((first, last)) => (*first, *last) = ((*first).min(time), (*last).max(time)),
+                None => {
+                    self.contact_times.insert(record.normalized_number.clone(), (time, time));
+                }
+            }
+            let (_, last_contacts) = match self.target_contacts.get_mut(&record.target_number) {
+                Some(target) => target,
+                None => self.target_contacts.entry(record.target_number.clone()).or_insert((time, HashMap::new())),
+            };
+            match last_contacts.get_mut(&record.normalized_number) {
+                Some(last) => *last = (*last).max(time),
+                None => {
+                    last_contacts.insert(record.normalized_number.clone(), time);
+                }
+            }
+            if message {
+                self.add_message(record);
+            } else {
+                // Messages are never answered, so only calls count toward the answer rates
+                let call = (
+                    record.start_time,
+                    !record.message_direction.eq_ignore_ascii_case("incoming"),
+                    record.length_of_call > self.options.unanswered_max_seconds,
+                );
+                match self.contact_answers.get_mut(&record.normalized_number) {
+                    Some(calls) => calls.push(call),
+                    None => {
+                        self.contact_answers.insert(record.normalized_number.clone(), vec![call]);
+                    }
+                }
+            }
+        } else if number_kind == NumberKind::Unknown {
+            self.unknown_number_calls += 1;
+        } else if number_kind == NumberKind::ShortCode {
+            self.short_code_calls += 1;
+        }
+        // Records already carry their UTC date, so only other zones need converting
+        let hour = if self.options.timezone == Tz::UTC {
+            increment(&mut self.calls_by_day, &record.date);
+            record.start_time.hour()
+        } else {
+            let local = record.start_time.with_timezone(&self.options.timezone);
+            let date = format!("{:04}-{:02}-{:02}", local.year(), local.month(), local.day());
+            increment(&mut self.calls_by_day, &date);
+            local.hour()
+        };
+        *self.calls_by_hour.entry(hour).or_insert(0) += 1;
+        if valid_number && is_night_hour(hour, self.options.night_hours) {
+            increment(&mut self.night_counts, &record.normalized_number);
         }
         
-        report.push_str("\n=== TARGET NUMBERS ===\n");
-        for target_num in &analytics.target_numbers {
-            let target_records: Vec<_> = records.iter()
-                .filter(|r| r.target_number == *target_num)
-                .collect();
-            report.push_str(&format!("• {}: {} calls\n", target_num, target_records.len()));
+        // Ties keep the latest longest call and the earliest shortest call
+        if !message && self.longest_call.as_ref().is_none_or(|c| record.length_of_call >= c.length_of_call) {
+            self.longest_call = Some(record.clone());
+        }
+        if !message
+            && record.length_of_call > 0 // Exclude 0-second calls
+            && self.shortest_call.as_ref().is_none_or(|c| record.length_of_call < c.length_of_call)
+        {
+            self.shortest_call = Some(record.clone());
         }
         
-        if !analytics.common_contacts.is_empty() {
-            report.push_str("\n=== COMMON CONTACTS ACROSS TARGET NUMBERS ===\n");
-            for contact in &analytics.common_contacts {
-                let target_nums = contact.target_numbers.join(", ");
-                report.push_str(&format!("• {}: appears in {} target numbers ({})\n", 
-                    contact.number, contact.count, target_nums));
+        self.date_range = Some(match self.date_range {
+            Some((min, max)) => (min.min(record.start_time), max.max(record.start_time)),
+            None => (record.start_time, record.start_time),
+        });
+        // Every record counts toward where its target's records end, contacts or not
+        match self.target_contacts.get_mut(&record.target_number) {
+            Some((end, _)) => *end = (*end).max(record.start_time),
+            None => {
+                self.target_contacts.insert(record.target_number.clone(), (record.start_time, HashMap::new()));
             }
         }
         
-        report.push_str("\n=== CALLS BY DAY ===\n");
-        let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
-        sorted_days.sort_by(|a, b| a.0.cmp(b.0));
-        for (day, count) in sorted_days {
-            report.push_str(&format!("{}: {} calls\n", day, count));
+        if !record.target_number.is_empty() {
+            let numbers = match self.target_groups.get_mut(&record.target_number) {
+                Some(numbers) => numbers,
+                None => self.target_groups.entry(record.target_number.clone()).or_default(),
+            };
+            if valid_number && !numbers.contains(&record.normalized_number) {
+                numbers.insert(record.normalized_number.clone());
+            }
         }
-        
-        report.push_str("\n=== CALLS BY HOUR ===\n");
-        for hour in 0..24 {
-            if let Some(count) = analytics.calls_by_hour.get(&hour) {
-                report.push_str(&format!("{:02}:00: {} calls\n", hour, count));
+        if let (false, Some(device)) = (record.target_number.is_empty(), &record.device) {
+            for kind in DeviceIdKind::ALL {
+                if let Some(id) = device.id(kind) {
+                    let time = record.start_time;
+                    let key = (kind, record.target_number.clone(), id.to_string());
+                    let used = self.device_uses.entry(key).or_insert((0, time, time));
+                    *used = (used.0 + 1, used.1.min(time), used.2.max(time));
+                }
             }
         }
+        if !record.source_file.is_empty() && !self.files_processed.contains(&record.source_file) {
+            self.files_processed.insert(record.source_file.clone());
+        }
+    }
+    
+    fn add_message(&mut self, record: &ProcessedCallRecord) {
+        let messages = match self.contact_messages.get_mut(&record.normalized_number) {
+            Some(messages) => messages,
+            None => self.contact_messages.entry(record.normalized_number.clone()).or_insert_with(|| ContactMessages {
+                number: record.normalized_number.clone(),
+                ..ContactMessages::default()
+            }),
+        };
+        match record.record_type {
+            RecordType::Mms => messages.mms += 1,
+            _ => messages.sms += 1,
+        }
+        if record.message_direction.eq_ignore_ascii_case("incoming") {
+            messages.received += 1;
+        } else {
+            messages.sent += 1;
+        }
+    }
+    
+    /// Adds a slice of records, folding chunks of it on the rayon thread pool and merging the
+    /// partial results. Gives the same totals as calling [`add`](Self::add) for each record in
+    /// order.
+    pub fn add_all(&mut self, records: &[ProcessedCallRecord]) {
+        if records.len() < MIN_RECORDS_PER_TASK {
+            records.iter().for_each(|record| self.add(record));
+            return;
+        }
         
-        report
+        // One chunk per thread: every partial accumulator holds its own map of numbers, so
+        // merging costs more than the per-record work finer splits would balance
+        let chunk_size = records.len().div_ceil(rayon::current_num_threads()).max(MIN_RECORDS_PER_TASK);
+        let options = &self.options;
+        let partial = records.par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut accumulator = AnalyticsAccumulator::new(options.clone());
+                chunk.iter().for_each(|record| accumulator.add(record));
+                accumulator
+            })
+            .reduce(|| AnalyticsAccumulator::new(options.clone()), |mut left, right| {
+                left.merge(right);
+                left
+            });
+        self.merge(partial);
     }
     
-    fn find_common_contacts(records: &[ProcessedCallRecord]) -> Vec<crate::data_models::CommonContact> {
-        use std::collections::HashMap;
+    /// Folds in totals gathered over records that come after this accumulator's, keeping the
+    /// same tie-breaking as if they had been added one at a time.
+    pub fn merge(&mut self, other: AnalyticsAccumulator) {
+        self.total_calls += other.total_calls;
+        self.incoming_calls += other.incoming_calls;
+        self.total_duration_minutes += other.total_duration_minutes;
+        self.unknown_number_calls += other.unknown_number_calls;
+        self.short_code_calls += other.short_code_calls;
+        self.sms_count += other.sms_count;
+        self.mms_count += other.mms_count;
+        for (count, other_count) in self.disposition_counts.iter_mut().zip(other.disposition_counts) {
+            *count += other_count;
+        }
+        self.voice_duration_minutes += other.voice_duration_minutes;
+        self.flagged_records_excluded += other.flagged_records_excluded;
+        
+        merge_counts(&mut self.number_counts, other.number_counts);
+        merge_counts(&mut self.calls_by_day, other.calls_by_day);
+        merge_counts(&mut self.night_counts, other.night_counts);
+        for (hour, count) in other.calls_by_hour {
+            *self.calls_by_hour.entry(hour).or_insert(0) += count;
+        }
         
-        // Group records by target number
-        let mut target_groups: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
-        for record in records {
-            if !record.target_number.is_empty() {
-                target_groups.entry(record.target_number.clone())
-                    .or_insert_with(std::collections::HashSet::new)
-                    .insert(record.normalized_number.clone());
+        if let Some(call) = other.longest_call {
+            if self.longest_call.as_ref().is_none_or(|c| call.length_of_call >= c.length_of_call) {
+                self.longest_call = Some(call);
+            }
+        }
+        if let Some(call) = other.shortest_call {
+            if self.shortest_call.as_ref().is_none_or(|c| call.length_of_call < c.length_of_call) {
+                self.shortest_call = Some(call);
             }
         }
         
-        // Find numbers that appear in multiple target groups
-        if target_groups.len() <= 1 {
-            return Vec::new();
+        self.date_range = match (self.date_range, other.date_range) {
+            (Some((min, max)), Some((other_min, other_max))) => Some((min.min(other_min), max.max(other_max))),
+            (range, None) | (None, range) => range,
+        };
+        
+        for (number, other_calls) in other.contact_answers {
+            self.contact_answers.entry(number).or_default().extend(other_calls);
         }
         
-        let mut all_numbers = std::collections::HashSet::new();
-        for numbers in target_groups.values() {
-            all_numbers.extend(numbers.iter().cloned());
-        }
-        
-        let mut common_contacts = Vec::new();
-        for number in all_numbers {
-            let target_numbers_with_contact: Vec<String> = target_groups.iter()
-                .filter(|(_, numbers)| numbers.contains(&number))
-                .map(|(target, _)| target.clone())
-                .collect();
-            
-            if target_numbers_with_contact.len() > 1 {
-                common_contacts.push(crate::data_models::CommonContact {
-                    number,
-                    target_numbers: target_numbers_with_contact,
-                    count: target_numbers_with_contact.len(),
-                });
+        for (number, (first, last)) in other.contact_times {
+            let times = self.contact_times.entry(number).or_insert((first, last));
+            *times = (times.0.min(first), times.1.max(last));
+        }
+        
+        for (target, (other_end, other_contacts)) in other.target_contacts {
+            let (end, last_contacts) = self.target_contacts.entry(target).or_insert((other_end, HashMap::new()));
+            *end = (*end).max(other_end);
+            for (number, last) in other_contacts {
+                let existing = last_contacts.entry(number).or_insert(last);
+                *existing = (*existing).max(last);
             }
         }
         
-        // Sort by number of target numbers they appear in
-        common_contacts.sort_by(|a, b| b.count.cmp(&a.count));
-        common_contacts
+        for (number, messages) in other.contact_messages {
+            match self.contact_messages.get_mut(&number) {
+                Some(existing) => {
+                    existing.sms += messages.sms;
+                    existing.mms += messages.mms;
+                    existing.sent += messages.sent;
+                    existing.received += messages.received;
+                }
+                None => {
+                    self.contact_messages.insert(number, messages);
+                }
+            }
+        }
+        
+        for (target, numbers) in other.target_groups {
+            match self.target_groups.get_mut(&target) {
+                Some(existing) => existing.extend(numbers),
+                None => {
+                    self.target_groups.insert(target, numbers);
+                }
+            }
+        }
+        for (key, (calls, first, last)) in other.device_uses {
+            let used = self.device_uses.entry(key).or_insert((0, first, last));
+            *used = (used.0 + calls, used.1.min(first), used.2.max(last));
+        }
+        self.files_processed.extend(other.files_processed);
+    }
+    
+    pub fn finish(self) -> Analytics {
+        let voice_calls = self.total_calls - self.sms_count - self.mms_count;
+        let average_call_duration = if voice_calls > 0 {
+            self.voice_duration_minutes / voice_calls as f64
+        } else {
+            0.0
+        };
+        
+        let unique_numbers = self.number_counts.len();
+        let calls_by_disposition = CallDisposition::ALL.into_iter()
+            .zip(self.disposition_counts)
+            .filter(|(disposition, count)| !disposition.is_completed() && *count > 0)
+            .collect();
+        let exchange_clusters = AnalyticsEngine::find_exchange_clusters(&self.number_counts);
+        let area_code_activity = AnalyticsEngine::find_area_code_activity(&self.number_counts);
+        let calls_by_region = AnalyticsEngine::find_calls_by_region(&area_code_activity);
+        let answer_rates = AnalyticsEngine::find_answer_rates(self.contact_answers);
+        let odd_hours_contacts = AnalyticsEngine::find_odd_hours_contacts(&self.number_counts, self.night_counts);
+        let device_changes = AnalyticsEngine::find_device_changes(&self.device_uses);
+        let top_n = self.options.top_n;
+        let messages_per_contact = AnalyticsEngine::find_messages_per_contact(self.contact_messages, top_n);
+        let contact_spans = AnalyticsEngine::find_contact_spans(&self.number_counts, self.contact_times, &self.target_contacts);
+        let number_counts = self.number_counts;
+        let target_groups = &self.target_groups;
+        
+        // The ranking and the common-contacts search are independent, so run them side by side
+        let (most_frequent_numbers, common_contacts) = rayon::join(
+            || {
+                let mut most_frequent_numbers: Vec<(String, usize)> = number_counts.into_iter().collect();
+                most_frequent_numbers.sort_by(|a, b| b.1.cmp(&a.1));
+                most_frequent_numbers.truncate(top_n);
+                most_frequent_numbers
+            },
+            || AnalyticsEngine::find_common_contacts(target_groups),
+        );
+        let target_numbers = self.target_groups.into_keys().collect();
+        
+        Analytics {
+            total_calls: self.total_calls,
+            incoming_calls: self.incoming_calls,
+            outgoing_calls: self.total_calls - self.incoming_calls,
+            total_duration_minutes: self.total_duration_minutes,
+            average_call_duration,
+            unique_numbers,
+            unknown_number_calls: self.unknown_number_calls,
+            short_code_calls: self.short_code_calls,
+            sms_count: self.sms_count,
+            mms_count: self.mms_count,
+            calls_by_disposition,
+            most_frequent_numbers,
+            calls_by_day: self.calls_by_day,
+            calls_by_hour: self.calls_by_hour,
+            longest_call: self.longest_call,
+            shortest_call: self.shortest_call,
+            target_numbers,
+            common_contacts,
+            exchange_clusters,
+            calls_by_region,
+            area_code_activity,
+            answer_rates,
+            odd_hours_contacts,
+            messages_per_contact,
+            contact_spans,
+            device_changes,
+            files_processed: self.files_processed,
+            date_range: self.date_range.unwrap_or_else(|| (Utc::now(), Utc::now())),
+            excluded_targets: self.options.excluded_targets,
+            flagged_records_excluded: self.flagged_records_excluded,
+            timezone: self.options.timezone,
+        }
+    }
+} 
+
+/// Where the calls with located North American numbers are concentrated, in a sentence:
+/// the busiest region and the busiest area code in it, with their shares of those calls.
+pub fn geographic_concentration(analytics: &Analytics) -> Option<String> {
+    let located: usize = analytics.calls_by_region.iter().map(|(_, calls)| calls).sum();
+    let (region, region_calls) = analytics.calls_by_region.first().filter(|_| located > 0)?;
+    let area_code = analytics.area_code_activity.iter()
+        .find(|activity| activity.region.as_ref() == Some(region))?;
+    let share = |calls: usize| format!("{:.1}", calls as f64 * 100.0 / located as f64);
+    Some(tr!("area-code-concentration",
+        located = located,
+        region = region_label(region),
+        region_share = share(*region_calls),
+        area_code = area_code.area_code.as_str(),
+        area_code_share = share(area_code.calls)))
+}
+
+/// Writes a six-digit NPA-NXX as `256-555`.
+pub fn format_prefix(prefix: &str) -> String {
+    match (prefix.get(..3), prefix.get(3..)) {
+        (Some(npa), Some(nxx)) if !nxx.is_empty() => format!("{}-{}", npa, nxx),
+        _ => prefix.to_string(),
+    }
+}
+
+/// A contact's calls over a stretch of time, reduced to what the answer rates need, so
+/// consecutive stretches can be joined in time order.
+#[derive(Debug, Clone, Copy)]
+struct AnswerRun {
+    /// Calls the target placed, and how many were answered.
+    attempts: usize,
+    answered: usize,
+    /// Unanswered attempts before the first answered one; all of them when none was.
+    leading_unanswered: usize,
+    longest_unanswered: usize,
+    trailing_unanswered: usize,
+    last_answered: Option<DateTime<Utc>>,
+    incoming: usize,
+    incoming_unanswered: usize,
+}
+
+impl AnswerRun {
+    fn call(start: DateTime<Utc>, outgoing: bool, answered: bool) -> Self {
+        let unanswered_attempt = usize::from(outgoing && !answered);
+        Self {
+            attempts: usize::from(outgoing),
+            answered: usize::from(outgoing && answered),
+            leading_unanswered: unanswered_attempt,
+            longest_unanswered: unanswered_attempt,
+            trailing_unanswered: unanswered_attempt,
+            last_answered: (outgoing && answered).then_some(start),
+            incoming: usize::from(!outgoing),
+            incoming_unanswered: usize::from(!outgoing && !answered),
+        }
+    }
+
+    /// This run followed by `later`.
+    fn then(self, later: Self) -> Self {
+        Self {
+            attempts: self.attempts + later.attempts,
+            answered: self.answered + later.answered,
+            leading_unanswered: if self.answered == 0 { self.attempts + later.leading_unanswered } else { self.leading_unanswered },
+            longest_unanswered: self.longest_unanswered
+                .max(later.longest_unanswered)
+                .max(self.trailing_unanswered + later.leading_unanswered),
+            trailing_unanswered: if later.answered == 0 { self.trailing_unanswered + later.attempts } else { later.trailing_unanswered },
+            last_answered: later.last_answered.or(self.last_answered),
+            incoming: self.incoming + later.incoming,
+            incoming_unanswered: self.incoming_unanswered + later.incoming_unanswered,
+        }
+    }
+}
+
+/// Counts one more `key`, allocating the key only the first time it is seen.
+fn increment(counts: &mut HashMap<String, usize>, key: &str) {
+    match counts.get_mut(key) {
+        Some(count) => *count += 1,
+        None => {
+            counts.insert(key.to_string(), 1);
+        }
+    }
+}
+
+fn merge_counts(counts: &mut HashMap<String, usize>, other: HashMap<String, usize>) {
+    for (key, count) in other {
+        *counts.entry(key).or_insert(0) += count;
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
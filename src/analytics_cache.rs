@@ -0,0 +1,175 @@
+use crate::analytics::{AnalyticsOptions, DEFAULT_UNANSWERED_SECONDS};
+use crate::filters::DEFAULT_NIGHT_HOURS;
+use crate::data_models::{hex_digest, Analytics, SourceFile};
+use anyhow::{Context, Result};
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+const CACHE_DIR_NAME: &str = "esubpoena-tolls-tool";
+
+/// Bump when [`Analytics`] or the way it is computed changes, so stale entries are never reused.
+const CACHE_FORMAT_VERSION: u32 = 14;
+
+/// Entries kept on disk; the least recently used are removed beyond this.
+pub const MAX_CACHE_ENTRIES: usize = 32;
+
+/// Identifies one analytics pass: the exact productions it read and how each was read, how
+/// many of their records were kept, and the options it ran with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsCacheKey(String);
+
+impl AnalyticsCacheKey {
+    /// Returns `None` when there are no known sources, e.g. a session saved before hashing existed.
+    /// The same productions can load as different records, with duplicates dropped or not,
+    /// so the record count is part of the key.
+    pub fn new(sources: &[SourceFile], record_count: usize, options: &AnalyticsOptions) -> Option<Self> {
+        if sources.is_empty() {
+            return None;
+        }
+
+        // Sorted so neither load order nor set iteration order changes the key
+        let mut sources: Vec<_> = sources.iter()
+            .map(|source| format!("{}:{}:{}", source.name, source.sha256, source.parse_config.as_deref().unwrap_or_default()))
+            .collect();
+        sources.sort();
+        let mut excluded: Vec<_> = options.excluded_numbers.iter().map(String::as_str).collect();
+        excluded.sort();
+
+        let mut hasher = Sha256::new();
+        hasher.update(format!("v{}\n", CACHE_FORMAT_VERSION));
+        hasher.update(sources.join("\n"));
+        hasher.update(format!("\nrecords={}", record_count));
+        hasher.update(format!("\ntop_n={}\nexcluded={}", options.top_n, excluded.join(",")));
+        // Only hashed when set, so entries cached before these options existed stay valid
+        if !options.excluded_targets.is_empty() {
+            let mut targets: Vec<_> = options.excluded_targets.iter().map(String::as_str).collect();
+            targets.sort();
+            hasher.update(format!("\nexcluded_targets={}", targets.join(",")));
+        }
+        if options.timezone != chrono_tz::Tz::UTC {
+            hasher.update(format!("\ntimezone={}", options.timezone.name()));
+        }
+        if options.unanswered_max_seconds != DEFAULT_UNANSWERED_SECONDS {
+            hasher.update(format!("\nunanswered_max_seconds={}", options.unanswered_max_seconds));
+        }
+        if options.night_hours != DEFAULT_NIGHT_HOURS {
+            hasher.update(format!("\nnight_hours={}-{}", options.night_hours.0, options.night_hours.1));
+        }
+        if !options.reclassified_numbers.is_empty() {
+            let reclassified: Vec<_> = options.reclassified_numbers.iter()
+                .map(|(number, kind)| format!("{}:{:?}", number, kind))
+                .collect();
+            hasher.update(format!("\nreclassified={}", reclassified.join(",")));
+        }
+        if options.exclude_flagged_records {
+            hasher.update("\nexclude_flagged_records");
+        }
+        if options.include_short_codes {
+            hasher.update("\ninclude_short_codes");
+        }
+        Some(Self(hex_digest(&hasher.finalize())))
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}.json", self.0)
+    }
+}
+
+/// Analytics results stored on disk so reopening a case, or returning to options already
+/// analyzed, skips the recomputation.
+#[derive(Debug, Clone)]
+pub struct AnalyticsCache {
+    dir: PathBuf,
+}
+
+impl AnalyticsCache {
+    /// The cache lives under the platform local data directory, next to the recovery files.
+    pub fn locate() -> Option<Self> {
+        dirs::data_local_dir().map(|dir| Self::in_dir(dir.join(CACHE_DIR_NAME).join("analytics-cache")))
+    }
+
+    pub fn in_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// A missing or unreadable entry is a cache miss, never an error.
+    pub fn load(&self, key: &AnalyticsCacheKey) -> Option<Analytics> {
+        let path = self.dir.join(key.file_name());
+        let file = File::open(&path).ok()?;
+        match serde_json::from_reader(BufReader::new(file)) {
+            Ok(analytics) => {
+                info!("Loaded cached analytics from {:?}", path);
+                // Refresh the modification time so pruning treats this entry as recently used
+                if let Err(e) = File::options().append(true).open(&path).and_then(|f| f.set_modified(std::time::SystemTime::now())) {
+                    warn!("Failed to touch cache entry {:?}: {}", path, e);
+                }
+                Some(analytics)
+            }
+            Err(e) => {
+                warn!("Ignoring unreadable cache entry {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Writes to a temporary file first so a crash mid-write never leaves a truncated entry.
+    pub fn store(&self, key: &AnalyticsCacheKey, analytics: &Analytics) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory: {:?}", self.dir))?;
+        let path = self.dir.join(key.file_name());
+        let tmp_path = path.with_extension("json.tmp");
+        let file = File::create(&tmp_path)
+            .with_context(|| format!("Failed to create cache entry: {:?}", tmp_path))?;
+        serde_json::to_writer(BufWriter::new(file), analytics).context("Failed to write cache entry")?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to move cache entry into place: {:?}", path))?;
+
+        self.prune();
+        Ok(())
+    }
+
+    /// Removes every entry, e.g. before handing the machine to another case.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = self.entries();
+        for (path, _) in &entries {
+            fs::remove_file(path).with_context(|| format!("Failed to remove cache entry: {:?}", path))?;
+        }
+        info!("Cleared {} cached analytics entries", entries.len());
+        Ok(entries.len())
+    }
+
+    fn prune(&self) {
+        let mut entries = self.entries();
+        if entries.len() <= MAX_CACHE_ENTRIES {
+            return;
+        }
+        entries.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        for (path, _) in &entries[MAX_CACHE_ENTRIES..] {
+            if let Err(e) = fs::remove_file(path) {
+                warn!("Failed to prune cache entry {:?}: {}", path, e);
+            }
+        }
+    }
+
+    fn entries(&self) -> Vec<(PathBuf, std::time::SystemTime)> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        read_dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect()
+    }
+}
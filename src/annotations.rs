@@ -0,0 +1,267 @@
+use crate::contacts::ContactList;
+use crate::data_models::{NumberKind, ProcessedCallRecord};
+use crate::entities::Entities;
+use crate::location::Geofence;
+use crate::subscribers::SubscriberDirectory;
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Changes kept for undo; the oldest are dropped beyond this.
+const HISTORY_LIMIT: usize = 100;
+
+/// Tags offered for records alongside the ones the case already uses.
+pub fn suggested_tags() -> [String; 2] {
+    [tr!("tag-relevant"), tr!("tag-alibi-window")]
+}
+
+/// Analyst review state layered over the parsed records. Records are referenced by
+/// `ProcessedCallRecord::record_key` and contacts by normalized number.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Annotations {
+    pub watchlist: HashSet<String>,
+    /// Records tagged, with or without a label in `record_tags`.
+    pub tagged_records: HashSet<String>,
+    /// Labels on tagged records ("Relevant", "Alibi window", ...), by record key.
+    #[serde(default)]
+    pub record_tags: HashMap<String, BTreeSet<String>>,
+    /// Free-text notes on records, by record key.
+    #[serde(default)]
+    pub record_notes: HashMap<String, String>,
+    pub bookmarks: HashSet<String>,
+    /// Imported subscriber results, joined to contacts by normalized number.
+    #[serde(default)]
+    pub subscribers: SubscriberDirectory,
+    /// Names for contacts by normalized number, e.g. from an imported contact list.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Numbers the analyst has grouped as one person.
+    #[serde(default)]
+    pub entities: Entities,
+    /// Words searched for in message content, where the production includes it.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// The area and window of time searched for calls on nearby towers, where the
+    /// production has tower locations.
+    #[serde(default)]
+    pub geofence: Option<Geofence>,
+    /// Numbers the analyst has classified differently from the parser, e.g. a padded
+    /// placeholder marked as not a number, by normalized number.
+    #[serde(default)]
+    pub reclassified_numbers: BTreeMap<String, NumberKind>,
+    /// What the case is, as entered when it was set up.
+    #[serde(default)]
+    pub case: CaseDetails,
+}
+
+/// Identifying details of a case, entered in the new-case wizard.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseDetails {
+    pub name: String,
+    /// The agency's case or report number.
+    pub number: String,
+    pub agency: String,
+    pub analyst: String,
+}
+
+impl CaseDetails {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.number.is_empty() && self.agency.is_empty() && self.analyst.is_empty()
+    }
+
+    /// Trims what was typed, so stray spaces never end up in labels.
+    pub fn trimmed(&self) -> Self {
+        Self {
+            name: self.name.trim().to_string(),
+            number: self.number.trim().to_string(),
+            agency: self.agency.trim().to_string(),
+            analyst: self.analyst.trim().to_string(),
+        }
+    }
+}
+
+impl Annotations {
+    pub fn toggle_watchlist(&mut self, number: &str) {
+        toggle(&mut self.watchlist, number);
+    }
+
+    /// Tags or untags a record; untagging drops its labels too.
+    pub fn toggle_tagged(&mut self, record_key: &str) {
+        toggle(&mut self.tagged_records, record_key);
+        if !self.tagged_records.contains(record_key) {
+            self.record_tags.remove(record_key);
+        }
+    }
+
+    /// Tags a record with `label`; a blank label just tags it.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::annotations::Annotations;
+    ///
+    /// let mut annotations = Annotations::default();
+    /// annotations.add_tag("call-1", " Alibi window ");
+    /// annotations.add_tag("call-1", "Relevant");
+    /// assert_eq!(annotations.tags("call-1").collect::<Vec<_>>(), ["Alibi window", "Relevant"]);
+    /// annotations.remove_tag("call-1", "Alibi window");
+    /// annotations.remove_tag("call-1", "Relevant");
+    /// assert!(!annotations.tagged_records.contains("call-1"));
+    /// ```
+    pub fn add_tag(&mut self, record_key: &str, label: &str) {
+        self.tagged_records.insert(record_key.to_string());
+        let label = label.trim();
+        if !label.is_empty() {
+            self.record_tags.entry(record_key.to_string()).or_default().insert(label.to_string());
+        }
+    }
+
+    /// Takes `label` off a record, untagging it when that was its last label.
+    pub fn remove_tag(&mut self, record_key: &str, label: &str) {
+        let Some(labels) = self.record_tags.get_mut(record_key) else {
+            return;
+        };
+        labels.remove(label);
+        if labels.is_empty() {
+            self.record_tags.remove(record_key);
+            self.tagged_records.remove(record_key);
+        }
+    }
+
+    /// A record's tag labels, in alphabetical order.
+    pub fn tags(&self, record_key: &str) -> impl Iterator<Item = &str> {
+        self.record_tags.get(record_key).into_iter().flatten().map(String::as_str)
+    }
+
+    /// Whether a record carries `label`.
+    pub fn has_tag(&self, record_key: &str, label: &str) -> bool {
+        self.record_tags.get(record_key).is_some_and(|labels| labels.contains(label))
+    }
+
+    /// Every label in use on the case's records, in alphabetical order.
+    pub fn tag_labels(&self) -> BTreeSet<&str> {
+        self.record_tags.values().flatten().map(String::as_str).collect()
+    }
+
+    /// Sets a record's note; a blank note removes it.
+    pub fn set_note(&mut self, record_key: &str, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.record_notes.remove(record_key);
+        } else {
+            self.record_notes.insert(record_key.to_string(), note.to_string());
+        }
+    }
+
+    pub fn note(&self, record_key: &str) -> Option<&str> {
+        self.record_notes.get(record_key).map(String::as_str)
+    }
+
+    /// Whether any record is tagged or has a note.
+    pub fn has_record_annotations(&self) -> bool {
+        !self.tagged_records.is_empty() || !self.record_notes.is_empty()
+    }
+
+    pub fn toggle_bookmark(&mut self, record_key: &str) {
+        toggle(&mut self.bookmarks, record_key);
+    }
+
+    /// Adds the list's names as aliases. Numbers that already have one keep it, so a later
+    /// import never overrides an earlier name. Returns how many aliases were added.
+    pub fn add_aliases(&mut self, contacts: &ContactList) -> usize {
+        let mut added = 0;
+        for (number, name) in contacts.names() {
+            if !self.aliases.contains_key(number) {
+                self.aliases.insert(number.to_string(), name.to_string());
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Names a number by hand, replacing any imported name; an empty name removes it.
+    pub fn set_alias(&mut self, normalized_number: &str, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.aliases.remove(normalized_number);
+        } else {
+            self.aliases.insert(normalized_number.to_string(), name.to_string());
+        }
+    }
+
+    pub fn alias(&self, normalized_number: &str) -> Option<&str> {
+        self.aliases.get(normalized_number).map(String::as_str)
+    }
+
+    pub fn is_bookmarked(&self, record: &ProcessedCallRecord) -> bool {
+        self.bookmarks.contains(&record.record_key())
+    }
+
+    /// Bookmarked records in chronological order.
+    pub fn bookmarked_records<'a>(&self, records: &'a [ProcessedCallRecord]) -> Vec<&'a ProcessedCallRecord> {
+        // Skips building a key for every record in the common no-bookmarks case
+        if self.bookmarks.is_empty() {
+            return Vec::new();
+        }
+        let mut bookmarked: Vec<&ProcessedCallRecord> = records.iter()
+            .filter(|r| self.is_bookmarked(r))
+            .collect();
+        bookmarked.sort_by_key(|r| r.start_time);
+        bookmarked
+    }
+}
+
+/// Undo and redo stacks of whole [`Annotations`] states, each with a description of the
+/// change that left it, so any review edit (aliases, tags, watchlist, entities, ...) can be
+/// stepped back and forth without every edit needing its own inverse.
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationHistory {
+    undo: VecDeque<(String, Annotations)>,
+    redo: Vec<(String, Annotations)>,
+}
+
+impl AnnotationHistory {
+    /// Records a change described by `label`, given the state from before it. Clears the
+    /// redo stack, since the undone changes no longer follow from the current state.
+    pub fn record(&mut self, label: String, before: Annotations) {
+        self.undo.push_back((label, before));
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    /// Restores the state from before the last change and returns that change's description.
+    pub fn undo(&mut self, current: &mut Annotations) -> Option<String> {
+        let (label, before) = self.undo.pop_back()?;
+        let after = std::mem::replace(current, before);
+        self.redo.push((label.clone(), after));
+        Some(label)
+    }
+
+    /// Reapplies the last undone change and returns its description.
+    pub fn redo(&mut self, current: &mut Annotations) -> Option<String> {
+        let (label, after) = self.redo.pop()?;
+        let before = std::mem::replace(current, after);
+        self.undo.push_back((label.clone(), before));
+        Some(label)
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.back().map(|(label, _)| label.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|(label, _)| label.as_str())
+    }
+
+    /// Forgets every change, e.g. when another case's annotations are loaded.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+fn toggle(set: &mut HashSet<String>, value: &str) {
+    if !set.remove(value) {
+        set.insert(value.to_string());
+    }
+}
@@ -1,30 +1,257 @@
-use crate::analytics::AnalyticsEngine;
-use crate::data_models::{Analytics, ProcessedCallRecord};
-use crate::excel_exporter::ExcelExporter;
-use crate::xml_parser::XmlParser;
+use esubpoena_tolls_tool::analytics::{format_prefix, geographic_concentration, AnalyticsEngine, AnalyticsOptions, MIN_ANSWER_RATE_ATTEMPTS};
+use esubpoena_tolls_tool::analytics_cache::{AnalyticsCache, AnalyticsCacheKey};
+use esubpoena_tolls_tool::annotations::{suggested_tags, AnnotationHistory, Annotations};
+use esubpoena_tolls_tool::call_timeline::CallTimeline;
+use esubpoena_tolls_tool::area_codes::region_label;
+use esubpoena_tolls_tool::carriers::{CarrierDirectory, CarrierSource};
+use esubpoena_tolls_tool::case_comparison::CaseComparison;
+use esubpoena_tolls_tool::cnam::CallerNames;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::cnam::CNAM_LOOKUP_LIMIT;
+use esubpoena_tolls_tool::columns::{ColumnLayout, RecordColumn};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::contacts::ContactList;
+use esubpoena_tolls_tool::contacts::ContactMatches;
+use esubpoena_tolls_tool::cumulative::{CumulativeCalls, CUMULATIVE_TOP_CONTACTS};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::case_wizard::{NewCaseWizard, WizardStep};
+use crate::command_palette::CommandPalette;
+use crate::jobs::{Job, JobContext, JobEvent, JobId, JobKind, JobManager, JobStatus};
+use crate::logging::{self, LogEntry};
+use esubpoena_tolls_tool::data_models::{has_cell_sites, has_device_ids, Analytics, CallDisposition, ContactSpan, DeviceChangeKind, Discrepancy, NumberKind, ParseReport, ProcessedCallRecord, SourceFile, ONGOING_CONTACT_DAYS, STOPPED_ANSWERING_STREAK};
+use esubpoena_tolls_tool::att_parser::AttParser;
+use esubpoena_tolls_tool::csv_parser::CsvParser;
+use esubpoena_tolls_tool::tmobile_parser::TMobileParser;
+use esubpoena_tolls_tool::json_parser::JsonParser;
+use esubpoena_tolls_tool::subscriber_parser::{match_subscribers, SubscriberParser};
+use esubpoena_tolls_tool::verizon_parser::VerizonParser;
+use esubpoena_tolls_tool::pdf_parser::{PdfParser, PdfPattern};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::pdf_parser::PdfExtraction;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::csv_parser::{CsvMapping, CsvSample, SheetMapping};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::spreadsheet_parser::{is_blank, SpreadsheetParser, WorkbookSample, HEADER_SEARCH_ROWS};
+use esubpoena_tolls_tool::delimited_parser::DelimitedParser;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::delimited_parser::{MappedField, MappingProfile};
+use esubpoena_tolls_tool::data_quality::{DataQualityReport, FileQuality};
+use esubpoena_tolls_tool::dedup;
+use esubpoena_tolls_tool::record_warnings::RecordIssue;
+use esubpoena_tolls_tool::cellebrite_export::CellebriteExporter;
+use esubpoena_tolls_tool::csv_exporter::CsvExporter;
+use esubpoena_tolls_tool::elastic_export::ElasticExporter;
+use esubpoena_tolls_tool::evidence::{EvidenceStatus, EvidenceVerification};
+use esubpoena_tolls_tool::exhibits::ExhibitPackage;
+use esubpoena_tolls_tool::export_locale::{ClockFormat, DateOrder, DecimalSeparator};
+use esubpoena_tolls_tool::network_export::NetworkExporter;
+use esubpoena_tolls_tool::penlink_export::PenLinkExporter;
+#[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+use esubpoena_tolls_tool::google_sheets::GoogleSheetsExporter;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::excel_exporter::ExcelExporter;
+use esubpoena_tolls_tool::data_models::PhoneFormat;
+use esubpoena_tolls_tool::phone_region::{self, PhoneRegion};
+use esubpoena_tolls_tool::pipeline::{Pipeline, PipelineConfig, RecordSink};
+use esubpoena_tolls_tool::pipeline::DuplicatePolicy;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::preview::{ProductionFormat, ProductionPreview};
+use esubpoena_tolls_tool::production_diff::ProductionDiff;
+use esubpoena_tolls_tool::report::{self, Report, ReportFormat, ReportInput, ReportSection};
+use esubpoena_tolls_tool::scripting::{ScriptOutput, ScriptRunner, UserScript};
+use esubpoena_tolls_tool::filters::{digit_match_ranges, ChartSelection, FilterContext, QuickFilter, RecordFilter};
+use esubpoena_tolls_tool::findings;
+use esubpoena_tolls_tool::archive::ProductionArchive;
+use esubpoena_tolls_tool::format_detection::{is_archive_file, is_production_file, is_spreadsheet_file, DetectionConfidence, FormatDetection, ParserKind, ReadFormat};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::format_detection::PRODUCTION_EXTENSIONS;
+use esubpoena_tolls_tool::i18n::{self, Language};
+use esubpoena_tolls_tool::keywords::{has_message_content, keyword_ranges, parse_keywords, KeywordHits};
+use esubpoena_tolls_tool::known_numbers::KnownNumbers;
+use esubpoena_tolls_tool::location::{parse_local_time, Geofence, GeofenceArea, LocationSummary, TowerTransition, DWELL_BREAK_HOURS, IMPLAUSIBLE_SPEED_KMH, TOWER_RANGE_KM};
+use esubpoena_tolls_tool::session::{RecoveryFiles, SessionSnapshot};
+use esubpoena_tolls_tool::settings::{AppSettings, COMMON_TIMEZONES};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::signing::{ExportSignature, SigningKey};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::subscribers::SubscriberDirectory;
+use crate::tutorial::{self, TutorialAnchors, TutorialStep};
+#[cfg(target_arch = "wasm32")]
+use crate::web::{self, PickedFile};
+use esubpoena_tolls_tool::tr;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::watch_folder::{AutoExporter, FolderWatcher};
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::workbook_import;
+use esubpoena_tolls_tool::xml_parser::XmlParser;
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
 use eframe::egui;
-use log::{error, info};
+use log::{error, info, warn, Level, LevelFilter};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
+use web_time::Instant;
+
+/// How often unsaved changes are snapshotted for crash recovery.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// Script output rows rendered in the Scripts tab; the export always has all of them.
+const SCRIPT_ROWS_SHOWN: usize = 500;
+/// Contacts listed under Answer Rates on the Analytics tab, those that stopped answering first.
+const ANSWER_RATES_SHOWN: usize = 25;
+
+/// Contacts listed in the Analytics tab's odd-hours ranking.
+const ODD_HOURS_SHOWN: usize = 25;
+/// Keyword hits listed in the detail view; the export always has all of them.
+const KEYWORD_HITS_SHOWN: usize = 500;
+/// Calls listed in the geofence detail view; the export always has all of them.
+const GEOFENCE_HITS_SHOWN: usize = 500;
+/// Target-days listed in the Analytics tab's tower usage table, latest first.
+const TOWER_DAYS_SHOWN: usize = 14;
+/// Dwell periods and tower changes listed in the tower movement detail view; the export
+/// always has all of them.
+const TOWER_MOVEMENT_SHOWN: usize = 500;
+/// Line colors for per-contact chart series.
+const SERIES_COLORS: [egui::Color32; 5] = [
+    egui::Color32::from_rgb(100, 150, 255),
+    egui::Color32::from_rgb(255, 150, 50),
+    egui::Color32::from_rgb(90, 200, 120),
+    egui::Color32::from_rgb(220, 90, 200),
+    egui::Color32::from_rgb(230, 210, 80),
+];
+/// Known-number categories, wherever a contact is tagged with one.
+const CATEGORY_COLOR: egui::Color32 = egui::Color32::from_rgb(180, 100, 220);
+/// How often the watch folder is checked for new productions.
+#[cfg(not(target_arch = "wasm32"))]
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct EsubpoenaApp {
     // Data
-    call_records: Vec<ProcessedCallRecord>,
+    /// Shared with background jobs (autosave, scripts, export) instead of copied into them.
+    call_records: Arc<Vec<ProcessedCallRecord>>,
+    /// Productions `call_records` were read from.
+    sources: Vec<SourceFile>,
+    /// Records each production had to skip; non-empty ones raise the warning banner.
+    parse_reports: Vec<ParseReport>,
+    skipped_banner_dismissed: bool,
+    /// Completeness of `call_records`, rebuilt whenever a new record set is loaded.
+    data_quality: DataQualityReport,
     analytics: Option<Analytics>,
+    /// On-disk analytics results, keyed by sources and options; unavailable in the browser.
+    analytics_cache: Option<AnalyticsCache>,
+    
+    settings: AppSettings,
+    settings_form: SettingsForm,
+    log_view: LogViewState,
+    scripts_view: ScriptsViewState,
+    /// Options the current analytics were computed with.
+    analytics_options: AnalyticsOptions,
+    /// Every target in `call_records`, sorted, whether or not it is included in analytics.
+    loaded_targets: Vec<String>,
+    /// Targets unchecked in the Analytics tab; cleared when a new production is loaded.
+    excluded_targets: HashSet<String>,
+    
+    // Review state
+    annotations: Annotations,
+    /// Earlier and undone states of `annotations`, for undo and redo.
+    annotation_history: AnnotationHistory,
+    /// Carrier datasets named in settings; reloaded when their paths change.
+    carriers: Arc<CarrierDirectory>,
+    /// Offline CNAM file named in settings plus cached lookup answers.
+    caller_names: Arc<CallerNames>,
+    /// Known-number lists named in settings; reloaded when the list of files changes.
+    known_numbers: Arc<KnownNumbers>,
+    /// Entity name and comma-separated numbers typed on the Analytics tab to merge.
+    entity_name: String,
+    entity_numbers: String,
     
     // UI State
     drag_state: DragState,
     processing_state: ProcessingState,
     selected_tab: Tab,
+    record_filter: RecordFilter,
+    record_selection: RecordSelection,
+    /// Column the first-and-last-contact table is sorted by, and whether descending.
+    contact_span_sort: (ContactSpanSort, bool),
+    command_palette: CommandPalette,
+    tutorial_step: Option<TutorialStep>,
+    tutorial_anchors: TutorialAnchors,
+    detail_views: Vec<OpenDetailView>,
+    /// The geofence being typed on the Analytics tab, until applied to the case's annotations.
+    geofence_input: Option<GeofenceInput>,
     
     // Messages
     messages: Vec<Message>,
     
     // Background processing
-    processing_sender: Option<mpsc::Sender<ProcessingMessage>>,
-    processing_receiver: Option<mpsc::Receiver<ProcessingMessage>>,
+    jobs: JobManager<JobOutput>,
+    
+    // Crash recovery
+    recovery: Option<RecoveryFiles>,
+    /// Snapshot left by a crashed session, until the user restores or discards it.
+    pending_recovery: Option<PathBuf>,
+    /// A large production previewed but not yet parsed, until the user loads or cancels it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_preview: Option<PendingPreview>,
+    /// A delimited production whose columns are being mapped, until the user loads or cancels it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_mapping: Option<PendingMapping>,
+    /// A PDF whose doubtful rows are being reviewed, until the user loads or cancels it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_pdf_review: Option<PendingPdfReview>,
+    /// The new-case wizard while it is open.
+    #[cfg(not(target_arch = "wasm32"))]
+    new_case_wizard: Option<NewCaseWizard>,
+    /// Bumped whenever records or annotations change; compared against the last autosave.
+    session_revision: u64,
+    autosaved_revision: u64,
+    last_autosave: Instant,
+    autosave_running: Arc<AtomicBool>,
+    
+    // Case files
+    /// Case file to save to or compare against, typed on the Overview tab.
+    #[cfg(not(target_arch = "wasm32"))]
+    case_file_path: String,
+    case_comparison: Option<CaseComparison>,
+    /// Differences between two of the loaded productions, from the Data Quality tab.
+    production_diff: Option<ProductionDiff>,
+    /// The last re-hash of the case file's source productions, from the Overview tab.
+    evidence_verification: Option<EvidenceVerification>,
+    /// Source files picked on the Data Quality tab as the original and corrected productions.
+    diff_original: String,
+    diff_corrected: String,
+    /// Keywords being typed on the Analytics tab, until applied to the case's annotations.
+    keyword_input: Option<String>,
+    /// Open cases, in tab order; the active one's data is in the fields above.
+    workspaces: Vec<Workspace>,
+    active_workspace: usize,
+    /// CSV of subscriber results to import, typed on the Overview tab.
+    #[cfg(not(target_arch = "wasm32"))]
+    subscriber_import_path: String,
+    /// Contact list (CSV or vCard) to import aliases from, typed on the Overview tab.
+    #[cfg(not(target_arch = "wasm32"))]
+    contact_import_path: String,
+    
+    // Watch folder
+    #[cfg(not(target_arch = "wasm32"))]
+    watcher: Option<FolderWatcher>,
+    #[cfg(not(target_arch = "wasm32"))]
+    last_watch_poll: Instant,
+    /// File names of watched productions still being ingested that should be auto-exported.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_auto_exports: HashSet<String>,
+    /// Productions opened while another load was running, added to its case once it finishes.
+    #[cfg(not(target_arch = "wasm32"))]
+    queued_productions: Vec<PathBuf>,
+    
+    // Browser file picker results
+    #[cfg(target_arch = "wasm32")]
+    picked_files: (std::sync::mpsc::Sender<Vec<PickedFile>>, std::sync::mpsc::Receiver<Vec<PickedFile>>),
+    #[cfg(target_arch = "wasm32")]
+    file_picker_open: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -38,16 +265,24 @@ enum DragState {
 enum ProcessingState {
     Idle,
     Processing,
+    ComputingAnalytics,
     Completed,
     Error(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Tab {
     Overview,
     CallRecords,
     Analytics,
+    Bookmarks,
     Summary,
+    DataQuality,
+    Subscribers,
+    Scripts,
+    Jobs,
+    Logs,
+    Settings,
 }
 
 #[derive(Debug, Clone)]
@@ -58,24 +293,589 @@ enum Message {
     Error(String),
 }
 
-#[derive(Debug)]
-enum ProcessingMessage {
-    Progress(String),
-    Completed(Vec<ProcessedCallRecord>),
-    Error(String),
+/// Text fields on the Settings tab that are applied to [`AppSettings`] on save.
+#[derive(Debug, Clone, Default)]
+struct SettingsForm {
+    new_excluded_number: String,
+    watch_folder: String,
+    auto_export_dir: String,
+    signing_key_path: String,
+    carrier_assignments_path: String,
+    ported_numbers_path: String,
+    cnam_path: String,
+    cnam_lookup_url: String,
+    new_known_number_list: String,
+    google_sheets_credentials: String,
+    google_sheet_id: String,
+    /// Excel number formats; empty means the language's default.
+    excel_datetime_format: String,
+    excel_duration_format: String,
+    /// Export to check against its signature; not saved.
+    verify_path: String,
+}
+
+impl SettingsForm {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            new_excluded_number: String::new(),
+            watch_folder: settings.watch_folder.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            auto_export_dir: settings.auto_export_dir.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            signing_key_path: settings.signing_key_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            carrier_assignments_path: settings.carrier_assignments_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            ported_numbers_path: settings.ported_numbers_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            cnam_path: settings.cnam_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            cnam_lookup_url: settings.cnam_lookup_url.clone().unwrap_or_default(),
+            new_known_number_list: String::new(),
+            google_sheets_credentials: settings.google_sheets_credentials.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            google_sheet_id: settings.google_sheet_id.clone().unwrap_or_default(),
+            excel_datetime_format: settings.excel_datetime_format.clone().unwrap_or_default(),
+            excel_duration_format: settings.excel_duration_format.clone().unwrap_or_default(),
+            verify_path: String::new(),
+        }
+    }
+}
+
+/// Filters applied in the Logs tab.
+#[derive(Debug, Clone)]
+struct LogViewState {
+    min_level: LevelFilter,
+    search: String,
+}
+
+impl Default for LogViewState {
+    fn default() -> Self {
+        Self {
+            min_level: LevelFilter::Info,
+            search: String::new(),
+        }
+    }
+}
+
+/// Scripts tab selection and the latest output of each script run this session.
+#[derive(Debug, Clone, Default)]
+struct ScriptsViewState {
+    selected: usize,
+    /// At most one output per script name; exported as extra sheets.
+    outputs: Vec<ScriptOutput>,
+    last_error: Option<String>,
+}
+
+impl ScriptsViewState {
+    fn output_for(&self, script_name: &str) -> Option<&ScriptOutput> {
+        self.outputs.iter().find(|o| o.script_name == script_name)
+    }
+    
+    fn store(&mut self, output: ScriptOutput) {
+        self.outputs.retain(|o| o.script_name != output.script_name);
+        self.outputs.push(output);
+    }
+}
+
+/// A case open in a workspace tab. The active case lives in the app's own fields, leaving
+/// its workspace an empty shell; switching swaps the fields with the chosen workspace's.
+struct Workspace {
+    name: String,
+    call_records: Arc<Vec<ProcessedCallRecord>>,
+    sources: Vec<SourceFile>,
+    parse_reports: Vec<ParseReport>,
+    skipped_banner_dismissed: bool,
+    data_quality: DataQualityReport,
+    analytics: Option<Analytics>,
+    analytics_options: AnalyticsOptions,
+    loaded_targets: Vec<String>,
+    excluded_targets: HashSet<String>,
+    annotations: Annotations,
+    annotation_history: AnnotationHistory,
+    processing_state: ProcessingState,
+    record_filter: RecordFilter,
+    record_selection: RecordSelection,
+    detail_views: Vec<OpenDetailView>,
+    case_comparison: Option<CaseComparison>,
+    production_diff: Option<ProductionDiff>,
+    evidence_verification: Option<EvidenceVerification>,
+    script_outputs: Vec<ScriptOutput>,
+    #[cfg(not(target_arch = "wasm32"))]
+    case_file_path: String,
+}
+
+impl Workspace {
+    fn new(name: String, settings: &AppSettings) -> Self {
+        Self {
+            name,
+            call_records: Arc::default(),
+            sources: Vec::new(),
+            parse_reports: Vec::new(),
+            skipped_banner_dismissed: false,
+            data_quality: DataQualityReport::default(),
+            analytics: None,
+            analytics_options: AnalyticsOptions::from_settings(settings),
+            loaded_targets: Vec::new(),
+            excluded_targets: HashSet::new(),
+            annotations: Annotations::default(),
+            annotation_history: AnnotationHistory::default(),
+            processing_state: ProcessingState::Idle,
+            record_filter: RecordFilter::default(),
+            record_selection: RecordSelection::default(),
+            detail_views: Vec::new(),
+            case_comparison: None,
+            production_diff: None,
+            evidence_verification: None,
+            script_outputs: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            case_file_path: String::new(),
+        }
+    }
+}
+
+/// A previewed production and the options it will be parsed with.
+#[cfg(not(target_arch = "wasm32"))]
+struct PendingPreview {
+    path: PathBuf,
+    preview: ProductionPreview,
+    timezone: chrono_tz::Tz,
+    duplicates: DuplicatePolicy,
+}
+
+/// A delimited production sampled so its columns can be mapped, and the options it will be
+/// parsed with.
+#[cfg(not(target_arch = "wasm32"))]
+struct PendingMapping {
+    path: PathBuf,
+    sample: CsvSample,
+    mapping: CsvMapping,
+    /// The preset the mapping started from, until a column is changed by hand.
+    preset: Option<MappingProfile>,
+    timezone: chrono_tz::Tz,
+    duplicates: DuplicatePolicy,
+    /// Whether the production is already in the case and is being read again.
+    reparse: bool,
+    /// For an Excel production, its sheets and the one the columns are mapped from.
+    sheet: Option<SheetChoice>,
+}
+
+/// The worksheets of an Excel production, and the sheet and header row its columns are
+/// mapped from.
+#[cfg(not(target_arch = "wasm32"))]
+struct SheetChoice {
+    workbook: WorkbookSample,
+    sheet: usize,
+    header_row: usize,
+}
+
+/// The rows read from a PDF, some of which need a look before they are loaded.
+#[cfg(not(target_arch = "wasm32"))]
+struct PendingPdfReview {
+    path: PathBuf,
+    extraction: PdfExtraction,
+    timezone: chrono_tz::Tz,
+    duplicates: DuplicatePolicy,
+    /// Show every row rather than only those that need review.
+    show_all: bool,
+}
+
+/// What a finished background job hands back to the UI thread.
+enum JobOutput {
+    Parsed(Vec<ProcessedCallRecord>, Vec<SourceFile>, Vec<ParseReport>),
+    /// Productions parsed to add to the loaded case rather than replace it.
+    Added(Vec<ProcessedCallRecord>, Vec<SourceFile>, Vec<ParseReport>),
+    Analyzed(Arc<Vec<ProcessedCallRecord>>, Vec<SourceFile>, Box<Analytics>),
+    Restored(Box<SessionSnapshot>),
+    Script(ScriptOutput),
+    ProductionDiffed(Box<ProductionDiff>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Exported(PathBuf),
+    #[cfg(not(target_arch = "wasm32"))]
+    AutoExported(Vec<PathBuf>),
+    /// The nodes and edges files of a network export.
+    #[cfg(not(target_arch = "wasm32"))]
+    NetworkExported(Vec<PathBuf>),
+    /// URL of the Google Sheet pushed to.
+    #[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+    SheetsPushed(String),
+    #[cfg(not(target_arch = "wasm32"))]
+    CaseSaved(PathBuf),
+    /// The start of a large production, sampled before parsing it.
+    #[cfg(not(target_arch = "wasm32"))]
+    Previewed(PathBuf, Box<ProductionPreview>),
+    /// The first rows of a delimited production, sampled to map its columns; true when it
+    /// is already in the case.
+    #[cfg(not(target_arch = "wasm32"))]
+    ColumnsSampled(PathBuf, Box<CsvSample>, bool),
+    /// The sheets of an Excel production, with the one its columns are first mapped from;
+    /// true when it is already in the case.
+    #[cfg(not(target_arch = "wasm32"))]
+    WorkbookSampled(PathBuf, Box<SheetChoice>, Box<CsvSample>, bool),
+    /// The rows read from a PDF's text, before they are loaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    PdfExtracted(PathBuf, Box<PdfExtraction>),
+    /// A case file or exported workbook opened from the Overview tab.
+    #[cfg(not(target_arch = "wasm32"))]
+    CaseOpened(PathBuf, Box<SessionSnapshot>),
+    /// The case file's source productions, re-hashed.
+    #[cfg(not(target_arch = "wasm32"))]
+    EvidenceVerified(Box<EvidenceVerification>),
+    /// Caller-name lookup answers, and the error that stopped the run early, if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    CallerNames(HashMap<String, Option<String>>, Option<String>),
+    Compared(Box<CaseComparison>),
+}
+
+impl Tab {
+    const ALL: [Tab; 11] = [
+        Tab::Overview, Tab::CallRecords, Tab::Analytics, Tab::Bookmarks, Tab::Summary, Tab::DataQuality,
+        Tab::Subscribers, Tab::Scripts, Tab::Jobs, Tab::Logs, Tab::Settings,
+    ];
+    
+    fn label(&self) -> String {
+        match self {
+            Tab::Overview => tr!("tab-overview"),
+            Tab::CallRecords => tr!("tab-call-records"),
+            Tab::Analytics => tr!("tab-analytics"),
+            Tab::Bookmarks => tr!("tab-bookmarks"),
+            Tab::Summary => tr!("tab-summary"),
+            Tab::DataQuality => tr!("tab-data-quality"),
+            Tab::Subscribers => tr!("tab-subscribers"),
+            Tab::Scripts => tr!("tab-scripts"),
+            Tab::Jobs => tr!("tab-jobs"),
+            Tab::Logs => tr!("tab-logs"),
+            Tab::Settings => tr!("tab-settings"),
+        }
+    }
+}
+
+/// Actions reachable from the command palette.
+#[derive(Debug, Clone, Copy)]
+enum AppCommand {
+    OpenFile,
+    #[cfg(not(target_arch = "wasm32"))]
+    NewCase,
+    #[cfg(not(target_arch = "wasm32"))]
+    AddProductions,
+    ExportExcel,
+    #[cfg(not(target_arch = "wasm32"))]
+    ExportGrouped,
+    ExportNetwork,
+    ExportCellebrite,
+    ExportPenLink,
+    ExportParseErrors,
+    ExportElastic,
+    ExportExhibits,
+    Undo,
+    Redo,
+    NewWorkspace,
+    SwitchWorkspace(usize),
+    CompareWorkspace(usize),
+    GoToTab(Tab),
+    ToggleChip(QuickFilter),
+    ToggleRegex,
+    ClearFilters,
+    SetPhoneFormat(PhoneFormat),
+    ToggleLocalTime,
+    SetLanguage(Language),
+    StartTutorial,
+}
+
+/// A drilldown that can be shown docked in the main window or popped out into its own OS window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DetailView {
+    Record(String),
+    Contact(String),
+    Timeline,
+    /// Gantt-style bars of the top contacts' calls.
+    CallDurations,
+    CumulativeCalls,
+    SkippedRecords,
+    CaseComparison,
+    /// Added, removed, and corrected records between two loaded productions.
+    ProductionDiff,
+    /// Whether the case file's source productions still match their recorded hashes.
+    EvidenceVerification,
+    /// Messages mentioning the case's keywords, and how often each came up over time.
+    KeywordHits,
+    /// Calls on towers inside the case's geofence.
+    GeofenceHits,
+    /// Each target's dwell periods at a tower and moves between towers.
+    TowerMovement,
+    /// Loaded numbers found in the contact lists, and frequent numbers still unidentified.
+    ContactMatches,
+}
+
+impl DetailView {
+    fn title(&self, settings: &AppSettings) -> String {
+        match self {
+            DetailView::Record(_) => tr!("detail-record-title"),
+            DetailView::Contact(number) => tr!("detail-contact-title", number = settings.format_number(number)),
+            DetailView::Timeline => tr!("detail-timeline-title"),
+            DetailView::CallDurations => tr!("detail-call-durations-title"),
+            DetailView::CumulativeCalls => tr!("detail-cumulative-title"),
+            DetailView::SkippedRecords => tr!("detail-skipped-title"),
+            DetailView::CaseComparison => tr!("detail-comparison-title"),
+            DetailView::ProductionDiff => tr!("detail-diff-title"),
+            DetailView::EvidenceVerification => tr!("detail-evidence-title"),
+            DetailView::KeywordHits => tr!("detail-keyword-hits-title"),
+            DetailView::GeofenceHits => tr!("detail-geofence-title"),
+            DetailView::TowerMovement => tr!("detail-tower-movement-title"),
+            DetailView::ContactMatches => tr!("detail-contact-matches-title"),
+        }
+    }
+}
+
+struct OpenDetailView {
+    view: DetailView,
+    popped_out: bool,
+}
+
+/// A geofence query as typed, with times in the case timezone.
+#[derive(Debug, Clone, Default)]
+struct GeofenceInput {
+    bounding_box: bool,
+    latitude: String,
+    longitude: String,
+    radius_km: String,
+    south: String,
+    west: String,
+    north: String,
+    east: String,
+    from: String,
+    to: String,
+    /// Why the last attempt to apply it failed.
+    error: Option<String>,
+}
+
+impl GeofenceInput {
+    fn from_geofence(geofence: Option<&Geofence>, timezone: chrono_tz::Tz) -> Self {
+        let Some(geofence) = geofence else {
+            return Self::default();
+        };
+        let time = |time: Option<chrono::DateTime<Utc>>| {
+            time.map(|time| time.with_timezone(&timezone).format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()
+        };
+        let mut input = Self { from: time(geofence.from), to: time(geofence.to), ..Self::default() };
+        match geofence.area {
+            GeofenceArea::Radius { latitude, longitude, radius_km } => {
+                input.latitude = latitude.to_string();
+                input.longitude = longitude.to_string();
+                input.radius_km = radius_km.to_string();
+            }
+            GeofenceArea::BoundingBox { south, west, north, east } => {
+                input.bounding_box = true;
+                input.south = south.to_string();
+                input.west = west.to_string();
+                input.north = north.to_string();
+                input.east = east.to_string();
+            }
+        }
+        input
+    }
+
+    /// The query typed, or what is wrong with it.
+    fn parse(&self, timezone: chrono_tz::Tz) -> Result<Geofence, String> {
+        let degrees = |text: &str, limit: f64| {
+            text.trim().parse::<f64>().ok()
+                .filter(|degrees| degrees.is_finite() && degrees.abs() <= limit)
+                .ok_or_else(|| tr!("geofence-invalid-coordinates"))
+        };
+        let area = if self.bounding_box {
+            let (south, north) = (degrees(&self.south, 90.0)?, degrees(&self.north, 90.0)?);
+            if south > north {
+                return Err(tr!("geofence-invalid-box"));
+            }
+            GeofenceArea::BoundingBox { south, west: degrees(&self.west, 180.0)?, north, east: degrees(&self.east, 180.0)? }
+        } else {
+            let radius_km = self.radius_km.trim().parse::<f64>().ok()
+                .filter(|radius| radius.is_finite() && *radius > 0.0)
+                .ok_or_else(|| tr!("geofence-invalid-radius"))?;
+            GeofenceArea::Radius { latitude: degrees(&self.latitude, 90.0)?, longitude: degrees(&self.longitude, 180.0)?, radius_km }
+        };
+        let time = |text: &str, end_of_day: bool| {
+            if text.trim().is_empty() {
+                return Ok(None);
+            }
+            parse_local_time(text, timezone, end_of_day).map(Some).ok_or_else(|| tr!("geofence-invalid-time", time = text.trim()))
+        };
+        let (from, to) = (time(&self.from, false)?, time(&self.to, true)?);
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(tr!("geofence-invalid-window"));
+            }
+        }
+        Ok(Geofence { area, from, to })
+    }
+}
+
+enum ColumnLayoutChange {
+    Move { column: RecordColumn, target: RecordColumn },
+    Resize { column: RecordColumn, delta: f32 },
+    /// A resize drag ended; the layout is saved then rather than on every frame of the drag.
+    ResizeDone,
+}
+
+/// Collects parsed records for the UI while reporting progress to the owning job, and stops
+/// the pipeline once the job is cancelled.
+struct ProgressSink<'a> {
+    job: &'a JobContext,
+    records: Vec<ProcessedCallRecord>,
+    /// Bytes of the file parsed so far and its size, when reading from a file.
+    bytes: Option<(Arc<AtomicU64>, u64)>,
+}
+
+impl RecordSink for ProgressSink<'_> {
+    fn accept(&mut self, batch: Vec<ProcessedCallRecord>) -> anyhow::Result<()> {
+        self.job.check_cancelled()?;
+        self.records.extend(batch);
+        let fraction = self.bytes.as_ref()
+            .filter(|(_, size)| *size > 0)
+            .map(|(read, size)| read.load(Ordering::Relaxed) as f32 / *size as f32);
+        self.job.set_progress(fraction, tr!("progress-records-parsed", count = self.records.len()));
+        Ok(())
+    }
+}
+
+/// Records picked in the call records table to tag or annotate together, with the tag and
+/// note being typed for them.
+#[derive(Default)]
+struct RecordSelection {
+    keys: HashSet<String>,
+    tag: String,
+    note: String,
+}
+
+/// Columns the first-and-last-contact table on the Analytics tab can be sorted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ContactSpanSort {
+    Number,
+    #[default]
+    FirstContact,
+    LastContact,
+    Span,
+    Calls,
+}
+
+enum RecordAction {
+    ToggleWatchlist(String),
+    ToggleTagged(String),
+    /// Record keys and the label to tag them with or take off them.
+    AddTag(Vec<String>, String),
+    RemoveTag(Vec<String>, String),
+    /// Record keys and the note typed for them, empty to remove it.
+    SetNote(Vec<String>, String),
+    ToggleBookmark(String),
+    /// A number and the name typed for it, empty to remove it.
+    SetAlias(String, String),
+    OpenDetail(DetailView),
 }
 
 impl EsubpoenaApp {
     pub fn new() -> Self {
+        let settings = AppSettings::load();
+        i18n::set_language(settings.language);
+        phone_region::set_default_region(settings.default_region);
+        let tutorial_step = (!settings.tutorial_completed).then_some(TutorialStep::Welcome);
+        let recovery = RecoveryFiles::locate();
+        let pending_recovery = recovery.as_ref().and_then(RecoveryFiles::begin_session);
+        let carriers = settings.carrier_directory().unwrap_or_else(|e| {
+            warn!("Carrier lookups disabled: {:#}", e);
+            CarrierDirectory::default()
+        });
+        let caller_names = settings.caller_names().unwrap_or_else(|e| {
+            warn!("Caller names from the CNAM file disabled: {:#}", e);
+            CallerNames::default()
+        });
+        let known_numbers = settings.known_numbers().unwrap_or_else(|e| {
+            warn!("Known-number lists disabled: {:#}", e);
+            KnownNumbers::default()
+        });
+        let workspaces = vec![Workspace::new(tr!("workspace-name", number = 1), &settings)];
+        
         Self {
-            call_records: Vec::new(),
+            call_records: Arc::default(),
+            sources: Vec::new(),
+            parse_reports: Vec::new(),
+            skipped_banner_dismissed: false,
+            data_quality: DataQualityReport::default(),
             analytics: None,
+            analytics_cache: AnalyticsCache::locate(),
+            settings_form: SettingsForm::from_settings(&settings),
+            log_view: LogViewState::default(),
+            scripts_view: ScriptsViewState::default(),
+            analytics_options: AnalyticsOptions::from_settings(&settings),
+            loaded_targets: Vec::new(),
+            excluded_targets: HashSet::new(),
+            settings,
+            annotations: Annotations::default(),
+            annotation_history: AnnotationHistory::default(),
+            carriers: Arc::new(carriers),
+            caller_names: Arc::new(caller_names),
+            known_numbers: Arc::new(known_numbers),
             drag_state: DragState::None,
             processing_state: ProcessingState::Idle,
             selected_tab: Tab::Overview,
+            record_filter: RecordFilter::default(),
+            record_selection: RecordSelection::default(),
+            contact_span_sort: (ContactSpanSort::default(), false),
+            command_palette: CommandPalette::default(),
+            tutorial_step,
+            tutorial_anchors: TutorialAnchors::default(),
+            detail_views: Vec::new(),
+            geofence_input: None,
             messages: Vec::new(),
-            processing_sender: None,
-            processing_receiver: None,
+            jobs: JobManager::default(),
+            recovery,
+            pending_recovery,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_preview: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_mapping: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_pdf_review: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            new_case_wizard: None,
+            session_revision: 0,
+            autosaved_revision: 0,
+            last_autosave: Instant::now(),
+            autosave_running: Arc::new(AtomicBool::new(false)),
+            #[cfg(not(target_arch = "wasm32"))]
+            case_file_path: String::new(),
+            case_comparison: None,
+            production_diff: None,
+            evidence_verification: None,
+            diff_original: String::new(),
+            diff_corrected: String::new(),
+            keyword_input: None,
+            entity_name: String::new(),
+            entity_numbers: String::new(),
+            workspaces,
+            active_workspace: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            subscriber_import_path: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            contact_import_path: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_watch_poll: Instant::now(),
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_auto_exports: HashSet::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            queued_productions: Vec::new(),
+            #[cfg(target_arch = "wasm32")]
+            picked_files: std::sync::mpsc::channel(),
+            #[cfg(target_arch = "wasm32")]
+            file_picker_open: false,
         }
     }
     
@@ -86,350 +886,6526 @@ impl EsubpoenaApp {
         }
     }
     
-    fn process_file(&mut self, file_path: PathBuf) {
-        info!("Processing file: {:?}", file_path);
-        self.processing_state = ProcessingState::Processing;
-        self.add_message(Message::Info(format!("Processing file: {}", file_path.display())));
+    /// Opens a production picked by the user, previewing it first if it is large. Anything
+    /// but XML has its columns mapped first; a ZIP archive's productions are read as detected.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_production(&mut self, file_path: PathBuf) {
+        if is_archive_file(&file_path.to_string_lossy()) {
+            self.process_file(file_path);
+            return;
+        }
+        if is_spreadsheet_file(&file_path.to_string_lossy()) {
+            self.sample_workbook(file_path, false);
+            return;
+        }
+        let xml = file_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+        if !xml {
+            self.sample_columns(file_path, false);
+            return;
+        }
+        let threshold = self.settings.preview_threshold_mb.saturating_mul(1024 * 1024);
+        let large = std::fs::metadata(&file_path).is_ok_and(|metadata| metadata.len() >= threshold);
+        if threshold == 0 || !large {
+            self.process_file(file_path);
+            return;
+        }
         
-        let (sender, receiver) = mpsc::channel();
-        self.processing_sender = Some(sender);
-        self.processing_receiver = Some(receiver);
+        self.jobs.spawn(JobKind::Parse, tr!("job-preview", name = file_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-sampling-file"));
+            let preview = ProductionPreview::read_file(&file_path)?;
+            Ok(JobOutput::Previewed(file_path, Box::new(preview)))
+        });
+    }
+    
+    /// Samples a delimited production's header and first rows so the user can map its
+    /// columns, either before it is loaded or, with `reparse`, to read it again in the case.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sample_columns(&mut self, file_path: PathBuf, reparse: bool) {
+        self.jobs.spawn(JobKind::Parse, tr!("job-sample-columns", name = file_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-sampling-file"));
+            let sample = CsvSample::read_file(&file_path)?;
+            Ok(JobOutput::ColumnsSampled(file_path, Box::new(sample), reparse))
+        });
+    }
+    
+    /// Samples every sheet of an Excel production so the sheet, header row, and columns can
+    /// be picked, starting from the first sheet a preset fits.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sample_workbook(&mut self, file_path: PathBuf, reparse: bool) {
+        self.jobs.spawn(JobKind::Parse, tr!("job-sample-columns", name = file_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-sampling-file"));
+            let workbook = WorkbookSample::read_file(&file_path)?;
+            let (sheet, header_row) = workbook.suggested()
+                .map_or((0, 0), |(mapping, _)| (mapping.sheet, mapping.header_row));
+            let sample = workbook.csv_sample(sheet, header_row)?;
+            let choice = SheetChoice { workbook, sheet, header_row };
+            Ok(JobOutput::WorkbookSampled(file_path, Box::new(choice), Box::new(sample), reparse))
+        });
+    }
+    
+    /// Reads the rows of a PDF so the doubtful ones can be reviewed before it is loaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn extract_pdf(&mut self, file_path: PathBuf) {
+        let patterns = self.settings.pdf_patterns.clone();
+        self.jobs.spawn(JobKind::Parse, tr!("job-extract-pdf", name = file_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-extracting-pdf"));
+            let extraction = PdfExtraction::read_file(&file_path, &patterns)?;
+            Ok(JobOutput::PdfExtracted(file_path, Box::new(extraction)))
+        });
+    }
+    
+    /// Loads the rows read from a PDF as a case. Reviewed rows count as chosen by hand;
+    /// otherwise the confidence is how cleanly the rows matched.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_pdf_extraction(&mut self, file_path: PathBuf, extraction: PdfExtraction, duplicates: DuplicatePolicy, reviewed: bool) {
+        let name = file_path.display().to_string();
+        self.add_message(Message::Info(tr!("msg-processing-file", name = name.as_str())));
+        self.processing_state = ProcessingState::Processing;
+        let timezone = self.settings.case_timezone;
+        let pdf_patterns = self.settings.pdf_patterns.clone();
         
-        thread::spawn(move || {
-            match XmlParser::parse_file(&file_path) {
-                Ok(records) => {
-                    let _ = sender.send(ProcessingMessage::Completed(records));
-                }
-                Err(e) => {
-                    let _ = sender.send(ProcessingMessage::Error(e.to_string()));
-                }
-            }
+        self.jobs.spawn(JobKind::Parse, tr!("job-parse", name = name.as_str()), move |_| {
+            let source = SourceFile::from_path(&file_path)
+                .with_context(|| format!("Failed to hash file: {:?}", file_path))?;
+            let confidence = (!reviewed).then(|| extraction.confidence());
+            let (records, mut report) = extraction.into_records(timezone, duplicates);
+            report.format = Some(ReadFormat { parser: ParserKind::Pdf, confidence, duplicates });
+            Ok(JobOutput::Parsed(records, vec![source.read_with(report.format, &pdf_patterns)], vec![report]))
         });
     }
     
-    fn export_to_excel(&mut self) {
-        if self.call_records.is_empty() {
-            self.add_message(Message::Warning("No data to export".to_string()));
+    fn process_file(&mut self, file_path: PathBuf) {
+        self.process_file_with(file_path, PipelineConfig::default());
+    }
+    
+    fn process_file_with(&mut self, file_path: PathBuf, config: PipelineConfig) {
+        self.process_files_with(vec![(file_path, None)], config, false);
+    }
+    
+    /// Opens productions chosen together, e.g. one per target, as a single case. A lone
+    /// production may be previewed first; several are parsed in one job without previews. While
+    /// another load is running they are queued and added to its case once it finishes.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_productions(&mut self, mut paths: Vec<PathBuf>) {
+        if paths.is_empty() {
             return;
         }
-        
-        if let Some(analytics) = &self.analytics {
-            let output_path = PathBuf::from("telecommunication_analysis.xlsx");
-            
-            match ExcelExporter::export_data(&self.call_records, analytics, &output_path) {
-                Ok(_) => {
-                    self.add_message(Message::Success(format!(
-                        "Successfully exported to: {}",
-                        output_path.display()
-                    )));
-                }
-                Err(e) => {
-                    self.add_message(Message::Error(format!("Export failed: {}", e)));
-                }
-            }
+        if self.loading() {
+            self.add_message(Message::Info(tr!("msg-productions-queued", count = paths.len())));
+            self.queued_productions.append(&mut paths);
+            return;
+        }
+        if paths.len() == 1 {
+            self.open_production(paths.remove(0));
+        } else {
+            self.process_files_with(paths.into_iter().map(|path| (path, None)).collect(), PipelineConfig::default(), false);
         }
     }
-}
-
-impl eframe::App for EsubpoenaApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Check for background processing messages
-        if let Some(receiver) = &self.processing_receiver {
-            if let Ok(message) = receiver.try_recv() {
-                match message {
-                    ProcessingMessage::Progress(msg) => {
-                        self.add_message(Message::Info(msg));
-                    }
-                    ProcessingMessage::Completed(records) => {
-                        self.call_records = records;
-                        self.analytics = Some(AnalyticsEngine::generate_analytics(&self.call_records));
-                        self.processing_state = ProcessingState::Completed;
-                        self.add_message(Message::Success(format!(
-                            "Successfully processed {} call records",
-                            self.call_records.len()
-                        )));
-                    }
-                    ProcessingMessage::Error(error_msg) => {
-                        self.processing_state = ProcessingState::Error(error_msg.clone());
-                        self.add_message(Message::Error(error_msg));
+    
+    /// Whether productions are being read or previewed, so newly opened ones should wait.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn loading(&self) -> bool {
+        self.jobs.is_running(JobKind::Parse)
+            || self.jobs.is_running(JobKind::Analytics)
+            || self.pending_preview.is_some()
+            || self.pending_mapping.is_some()
+            || self.pending_pdf_review.is_some()
+    }
+    
+    /// Starts on the productions queued during the last load, adding them to its case or,
+    /// when it failed and left nothing loaded, making a case of them.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_queued_productions(&mut self) {
+        if self.queued_productions.is_empty() || self.loading() {
+            return;
+        }
+        let files = std::mem::take(&mut self.queued_productions).into_iter().map(|path| (path, None)).collect();
+        let add_to_case = !self.call_records.is_empty();
+        self.process_files_with(files, PipelineConfig::default(), add_to_case);
+    }
+    
+    /// Asks for productions to add to the loaded case, e.g. a target's records that came in later.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn add_productions_dialog(&mut self) {
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return;
+        }
+        let Some(paths) = rfd::FileDialog::new()
+            .set_title(tr!("dialog-add-productions"))
+            .add_filter(tr!("filter-productions"), &PRODUCTION_EXTENSIONS)
+            .add_filter(tr!("filter-xml"), &["xml"])
+            .pick_files()
+        else {
+            return;
+        };
+        let add_to_case = !self.call_records.is_empty();
+        self.process_files_with(paths.into_iter().map(|path| (path, None)).collect(), PipelineConfig::default(), add_to_case);
+    }
+    
+    /// Parses productions one after another in a single job, so they load together as one
+    /// case rather than each replacing the last, or are added to the loaded case when
+    /// `add_to_case` is set. Each is read with the parser given for it, or the one detected
+    /// from its first bytes; each production inside a ZIP archive is read as detected.
+    fn process_files_with(&mut self, files: Vec<(PathBuf, Option<ParserKind>)>, config: PipelineConfig, add_to_case: bool) {
+        let names = files.iter()
+            .map(|(path, _)| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        info!("Processing files: {:?}", files);
+        self.add_message(Message::Info(tr!("msg-processing-file", name = names.as_str())));
+        self.processing_state = ProcessingState::Processing;
+        let timezone = self.settings.case_timezone;
+        let pdf_patterns = self.settings.pdf_patterns.clone();
+        let config = PipelineConfig { recover_malformed: self.settings.recover_malformed_xml, ..config };
+        
+        let label = tr!("job-parse", name = names.as_str());
+        self.jobs.spawn(JobKind::Parse, label, move |job| {
+            let mut sink = ProgressSink { job, records: Vec::new(), bytes: None };
+            let mut sources = Vec::with_capacity(files.len());
+            let mut reports = Vec::with_capacity(files.len());
+            for (file_path, chosen) in &files {
+                if is_archive_file(&file_path.to_string_lossy()) {
+                    sink.bytes = None;
+                    for entry in ProductionArchive::read_file(file_path)?.entries {
+                        let source = SourceFile::from_bytes(&entry.name, &entry.bytes);
+                        let mut report = match entry.parser {
+                            ParserKind::Lds101Xml => {
+                                Pipeline::run(std::io::Cursor::new(entry.bytes), entry.name, &mut sink, config)?.report
+                            }
+                            parser => {
+                                let (records, report) = parse_production_bytes(
+                                    &entry.bytes, &entry.name, parser, timezone, config, &pdf_patterns,
+                                )?;
+                                sink.accept(records)?;
+                                report
+                            }
+                        };
+                        report.format = Some(ReadFormat {
+                            parser: entry.parser,
+                            confidence: Some(entry.confidence),
+                            duplicates: config.duplicates,
+                        });
+                        sources.push(source.read_with(report.format, &pdf_patterns));
+                        reports.push(report);
                     }
+                    continue;
                 }
+                let source = SourceFile::from_path(file_path)
+                    .with_context(|| format!("Failed to hash file: {:?}", file_path))?;
+                let detection = FormatDetection::detect_file(file_path)?;
+                let parser = chosen.or(detection.parser)
+                    .with_context(|| format!("Unrecognized production format: {:?}", file_path))?;
+                let mut report = match parser {
+                    ParserKind::Lds101Xml => {
+                        let size = std::fs::metadata(file_path).map(|metadata| metadata.len()).unwrap_or(0);
+                        let bytes_read = Arc::new(AtomicU64::new(0));
+                        sink.bytes = Some((Arc::clone(&bytes_read), size));
+                        Pipeline::run_file_counting(file_path, &mut sink, config, bytes_read)?.report
+                    }
+                    ParserKind::Json => {
+                        let (records, report) = JsonParser::parse_file(file_path, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::Lds102Xml => {
+                        let (records, report) = SubscriberParser::parse_file(file_path)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::Delimited(profile) => {
+                        let (records, report) = DelimitedParser::parse_file(file_path, profile, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::Att => {
+                        let (records, report) = AttParser::parse_file(file_path, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::Verizon => {
+                        let (records, report) = VerizonParser::parse_file(file_path, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::TMobile => {
+                        let (records, report) = TMobileParser::parse_file(file_path, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    ParserKind::Csv(mapping) => {
+                        let (records, report) = CsvParser::parse_file(file_path, &mapping, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ParserKind::Spreadsheet(sheet) => {
+                        let (records, report) = SpreadsheetParser::parse_file(file_path, &sheet, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    ParserKind::Spreadsheet(_) => anyhow::bail!("Excel workbooks can only be read in the desktop app"),
+                    ParserKind::Pdf => {
+                        let (records, report) = PdfParser::parse_file(file_path, &pdf_patterns, timezone, config.duplicates)?;
+                        sink.bytes = None;
+                        sink.accept(records)?;
+                        report
+                    }
+                };
+                report.format = Some(ReadFormat {
+                    parser,
+                    confidence: chosen.is_none().then_some(detection.confidence),
+                    duplicates: config.duplicates,
+                });
+                sources.push(source.read_with(report.format, &pdf_patterns));
+                reports.push(report);
+            }
+            if add_to_case {
+                Ok(JobOutput::Added(sink.records, sources, reports))
+            } else {
+                Ok(JobOutput::Parsed(sink.records, sources, reports))
             }
+        });
+    }
+    
+    /// Parses the case's productions again with `parser` for `source_file`; the others keep
+    /// any parser chosen for them before. Every production must still be on disk.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reparse_production(&mut self, source_file: &str, parser: ParserKind) {
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return;
+        }
+        let format_of = |name: &str| self.parse_reports.iter()
+            .find(|report| report.source_file == name)
+            .and_then(|report| report.format);
+        let mut files = Vec::with_capacity(self.sources.len());
+        for source in &self.sources {
+            let Some(path) = source.path.clone().filter(|path| path.is_file()) else {
+                self.add_message(Message::Warning(tr!("msg-reparse-unavailable", name = source.name.as_str())));
+                return;
+            };
+            let chosen = if source.name == source_file {
+                Some(parser)
+            } else {
+                format_of(&source.name).filter(ReadFormat::overridden).map(|format| format.parser)
+            };
+            files.push((path, chosen));
+        }
+        let duplicates = format_of(source_file).map(|format| format.duplicates).unwrap_or_default();
+        self.process_files_with(files, PipelineConfig { duplicates, ..PipelineConfig::default() }, false);
+    }
+    
+    /// Samples a production in the case so its columns can be mapped by hand before it is
+    /// read again.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn map_production_columns(&mut self, source_file: &str) {
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return;
+        }
+        let path = self.sources.iter()
+            .find(|source| source.name == source_file)
+            .and_then(|source| source.path.clone())
+            .filter(|path| path.is_file());
+        match path {
+            Some(path) if is_spreadsheet_file(&path.to_string_lossy()) => self.sample_workbook(path, true),
+            Some(path) => self.sample_columns(path, true),
+            None => self.add_message(Message::Warning(tr!("msg-reparse-unavailable", name = source_file))),
+        }
+    }
+    
+    /// Parses productions that only exist in memory, e.g. files dropped onto the browser
+    /// build, into one case. ZIP archives among them are unpacked into their productions.
+    fn process_bytes(&mut self, files: Vec<(String, Vec<u8>)>) {
+        if files.is_empty() {
+            return;
         }
+        let names = files.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ");
+        info!("Processing {} ({} bytes)", names, files.iter().map(|(_, bytes)| bytes.len()).sum::<usize>());
+        self.add_message(Message::Info(tr!("msg-processing-file", name = names.as_str())));
+        self.processing_state = ProcessingState::Processing;
+        let timezone = self.settings.case_timezone;
+        let pdf_patterns = self.settings.pdf_patterns.clone();
+        let config = PipelineConfig {
+            duplicates: DuplicatePolicy::Keep,
+            recover_malformed: self.settings.recover_malformed_xml,
+            ..PipelineConfig::default()
+        };
         
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.render_header(ui);
-            ui.add_space(10.0);
+        self.jobs.spawn(JobKind::Parse, tr!("job-parse", name = names.as_str()), move |_| {
+            let mut productions = Vec::with_capacity(files.len());
+            for (name, bytes) in files {
+                if is_archive_file(&name) {
+                    let archive = ProductionArchive::from_bytes(&bytes, &name)?;
+                    productions.extend(archive.entries.into_iter().map(|entry| (entry.name, entry.bytes)));
+                } else {
+                    productions.push((name, bytes));
+                }
+            }
             
-            match self.selected_tab {
-                Tab::Overview => self.render_overview(ui),
-                Tab::CallRecords => self.render_call_records(ui),
-                Tab::Analytics => self.render_analytics(ui),
-                Tab::Summary => self.render_summary(ui),
+            let mut records = Vec::new();
+            let mut sources = Vec::with_capacity(productions.len());
+            let mut reports = Vec::with_capacity(productions.len());
+            for (name, bytes) in &productions {
+                let detection = FormatDetection::detect_named(name, bytes);
+                let parser = detection.parser
+                    .with_context(|| format!("Unrecognized production format: {}", name))?;
+                let (parsed, mut report) = parse_production_bytes(bytes, name, parser, timezone, config, &pdf_patterns)?;
+                report.format = Some(ReadFormat { parser, confidence: Some(detection.confidence), duplicates: DuplicatePolicy::Keep });
+                records.extend(parsed);
+                sources.push(SourceFile::from_bytes(name, bytes).read_with(report.format, &pdf_patterns));
+                reports.push(report);
             }
+            Ok(JobOutput::Parsed(records, sources, reports))
         });
     }
-}
-
-impl EsubpoenaApp {
-    fn render_header(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.heading("📞 eSubpoena Tolls Tool");
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button("Export to Excel").clicked() {
-                    self.export_to_excel();
+    
+    /// Hands freshly parsed records to an analytics job; they come back with the results.
+    /// Results already cached for the same sources and options are reused instead.
+    fn spawn_analytics(&mut self, records: Arc<Vec<ProcessedCallRecord>>, sources: Vec<SourceFile>) {
+        self.processing_state = ProcessingState::ComputingAnalytics;
+        self.analytics_options = self.current_analytics_options();
+        
+        let options = self.analytics_options.clone();
+        let cache = self.analytics_cache.clone();
+        let label = tr!("job-analyze", count = records.len());
+        self.jobs.spawn(JobKind::Analytics, label, move |job| {
+            let key = AnalyticsCacheKey::new(&sources, records.len(), &options);
+            if let (Some(cache), Some(key)) = (&cache, &key) {
+                job.set_progress(None, tr!("progress-loading-cached-analytics"));
+                if let Some(analytics) = cache.load(key) {
+                    return Ok(JobOutput::Analyzed(records, sources, Box::new(analytics)));
                 }
-            });
+            }
+            
+            job.set_progress(None, tr!("progress-computing-analytics"));
+            let analytics = AnalyticsEngine::generate_analytics_with(&records, &options);
+            if let (Some(cache), Some(key)) = (&cache, &key) {
+                if let Err(e) = cache.store(key, &analytics) {
+                    warn!("Failed to cache analytics: {:#}", e);
+                }
+            }
+            Ok(JobOutput::Analyzed(records, sources, Box::new(analytics)))
         });
+    }
+    
+    /// Appends newly parsed productions to the loaded records and re-analyzes them together.
+    /// A production already in the case, by its hash, is left out rather than counted twice.
+    fn add_to_case(&mut self, mut records: Vec<ProcessedCallRecord>, sources: Vec<SourceFile>, reports: Vec<ParseReport>) {
+        let (sources, repeated): (Vec<SourceFile>, Vec<SourceFile>) = sources.into_iter()
+            .partition(|source| !self.sources.iter().any(|loaded| loaded.sha256 == source.sha256));
+        for source in &repeated {
+            self.add_message(Message::Warning(tr!("msg-production-already-loaded", name = source.name.as_str())));
+        }
+        records.retain(|record| !repeated.iter().any(|source| source.name == record.source_file));
+        let reports: Vec<ParseReport> = reports.into_iter()
+            .filter(|report| !repeated.iter().any(|source| source.name == report.source_file))
+            .collect();
+        if sources.is_empty() {
+            self.processing_state = ProcessingState::Completed;
+            return;
+        }
         
-        ui.separator();
-        
-        // Tab bar
-        ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.selected_tab, Tab::Overview, "Overview");
-            ui.selectable_value(&mut self.selected_tab, Tab::CallRecords, "Call Records");
-            ui.selectable_value(&mut self.selected_tab, Tab::Analytics, "Analytics");
-            ui.selectable_value(&mut self.selected_tab, Tab::Summary, "Summary");
-        });
+        let dropped: usize = reports.iter().map(|r| r.duplicates_dropped).sum();
+        if dropped > 0 {
+            self.add_message(Message::Info(tr!("msg-duplicates-dropped", count = dropped)));
+        }
+        self.announce_formats(&reports);
+        self.add_message(Message::Info(tr!("msg-productions-added", count = sources.len(), records = records.len())));
+        let mut all_reports = self.parse_reports.clone();
+        all_reports.extend(reports);
+        let mut all_records = Vec::with_capacity(self.call_records.len() + records.len());
+        all_records.extend(self.call_records.iter().cloned());
+        all_records.extend(records);
+        self.collapse_cross_file_duplicates(&mut all_records, &mut all_reports);
+        self.set_parse_reports(all_reports);
+        let mut all_sources = self.sources.clone();
+        all_sources.extend(sources);
+        self.spawn_analytics(Arc::new(all_records), all_sources);
     }
     
-    fn render_overview(&mut self, ui: &mut egui::Ui) {
-        ui.vertical(|ui| {
-            ui.heading("Welcome to eSubpoena Tolls Tool");
-            ui.label("This tool processes telecommunication XML data and provides comprehensive analytics.");
-            
-            ui.add_space(20.0);
-            
-            // Drag and drop area
-            let (response, painter) = ui.allocate_painter(
-                egui::vec2(ui.available_width(), 200.0),
-                egui::Sense::drag(),
-            );
-            
-            let rect = response.rect;
-            let is_hovered = response.hovered();
-            let is_dragged = response.dragged();
-            
-            // Update drag state
-            self.drag_state = if is_dragged {
-                DragState::Dropping
-            } else if is_hovered {
-                DragState::Hovering
-            } else {
-                DragState::None
-            };
-            
-            // Draw drop zone
-            let color = match self.drag_state {
-                DragState::None => egui::Color32::from_gray(50),
+    /// Drops calls an earlier production of the case already had when the setting is on, or
+    /// says how many there are when it is off.
+    fn collapse_cross_file_duplicates(&mut self, records: &mut Vec<ProcessedCallRecord>, reports: &mut [ParseReport]) {
+        if self.settings.collapse_cross_file_duplicates {
+            let collapsed = dedup::collapse_cross_file_duplicates(records, reports);
+            if collapsed > 0 {
+                self.add_message(Message::Info(tr!("msg-cross-file-duplicates-collapsed", count = collapsed)));
+            }
+        } else {
+            let repeated = dedup::find_cross_file_duplicates(records).len();
+            if repeated > 0 {
+                self.add_message(Message::Warning(tr!("msg-cross-file-duplicates-found", count = repeated)));
+            }
+        }
+    }
+    
+    /// Says which parser read each production, warning when detection was unsure.
+    fn announce_formats(&mut self, reports: &[ParseReport]) {
+        for report in reports {
+            let Some(format) = report.format else {
+                continue;
+            };
+            let name = report.source_file.as_str();
+            let message = match format.confidence {
+                None => Message::Info(tr!("msg-format-chosen", name = name, parser = format.parser.label())),
+                Some(DetectionConfidence::High) => Message::Info(tr!("msg-format-detected", name = name, parser = format.parser.label())),
+                Some(confidence) => Message::Warning(tr!("msg-format-uncertain", name = name, parser = format.parser.label(), confidence = confidence.label())),
+            };
+            self.add_message(message);
+        }
+    }
+    
+    fn set_parse_reports(&mut self, reports: Vec<ParseReport>) {
+        for report in reports.iter().filter(|r| r.skipped_count > 0) {
+            warn!("{}: skipped {} of {} records", report.source_file, report.skipped_count, report.total_records);
+        }
+        for report in reports.iter().filter(|r| !r.discrepancies().is_empty()) {
+            warn!("{}: contents do not match its declared coverage", report.source_file);
+        }
+        self.parse_reports = reports;
+        self.skipped_banner_dismissed = false;
+        self.detail_views.retain(|open| open.view != DetailView::SkippedRecords);
+    }
+    
+    fn current_analytics_options(&self) -> AnalyticsOptions {
+        AnalyticsOptions::from_settings(&self.settings)
+            .with_excluded_targets(self.excluded_targets.clone())
+            .with_reclassified_numbers(self.annotations.reclassified_numbers.clone())
+    }
+    
+    /// Recomputes analytics over the loaded records, e.g. after the options changed.
+    fn reanalyze(&mut self) {
+        if self.call_records.is_empty() || self.jobs.is_running(JobKind::Analytics) {
+            return;
+        }
+        self.spawn_analytics(Arc::clone(&self.call_records), self.sources.clone());
+    }
+    
+    /// Re-analyzes when the settings or the case's review choices no longer match the options
+    /// the current analytics ran with.
+    fn reanalyze_if_stale(&mut self) {
+        if self.current_analytics_options() != self.analytics_options {
+            self.reanalyze();
+        }
+    }
+    
+    fn toggle_target(&mut self, target: &str) {
+        if !self.excluded_targets.remove(target) {
+            self.excluded_targets.insert(target.to_string());
+        }
+        self.reanalyze();
+    }
+    
+    fn handle_job_results(&mut self) {
+        for event in self.jobs.poll() {
+            match event {
+                JobEvent::Completed(output) => match output {
+                    JobOutput::Parsed(mut records, sources, mut reports) => {
+                        let dropped: usize = reports.iter().map(|r| r.duplicates_dropped).sum();
+                        if dropped > 0 {
+                            self.add_message(Message::Info(tr!("msg-duplicates-dropped", count = dropped)));
+                        }
+                        self.excluded_targets.clear();
+                        self.announce_formats(&reports);
+                        self.collapse_cross_file_duplicates(&mut records, &mut reports);
+                        self.set_parse_reports(reports);
+                        self.spawn_analytics(Arc::new(records), sources);
+                    }
+                    JobOutput::Added(records, sources, reports) => {
+                        self.add_to_case(records, sources, reports);
+                    }
+                    JobOutput::Analyzed(records, sources, analytics) => {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        let auto_export = self.take_pending_auto_export(&sources);
+                        self.load_records(records, sources, *analytics);
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if auto_export {
+                            self.spawn_auto_export();
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        self.start_queued_productions();
+                    }
+                    JobOutput::Restored(snapshot) => {
+                        self.add_message(Message::Info(tr!(
+                            "msg-session-restored",
+                            saved_at = self.settings.format_local_time(&snapshot.saved_at)
+                        )));
+                        self.load_snapshot(*snapshot);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::EvidenceVerified(verification) => {
+                        let verified = verification.verified_count();
+                        let total = verification.checks.len();
+                        if verification.all_verified() {
+                            self.add_message(Message::Success(tr!("msg-evidence-verified", count = total)));
+                        } else {
+                            self.add_message(Message::Warning(tr!("msg-evidence-discrepancies", count = total - verified, total = total)));
+                        }
+                        self.evidence_verification = Some(*verification);
+                        self.open_detail_view(DetailView::EvidenceVerification);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::Previewed(path, preview) => {
+                        self.pending_preview = Some(PendingPreview {
+                            path,
+                            preview: *preview,
+                            timezone: self.settings.case_timezone,
+                            duplicates: DuplicatePolicy::default(),
+                        });
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::ColumnsSampled(path, sample, reparse) => {
+                        if !reparse && sample.fixed_parser() == Some(ParserKind::Pdf) {
+                            self.extract_pdf(path);
+                        } else if let Some(parser) = sample.fixed_parser() {
+                            // XML, JSON, or a layout with a parser of its own, has no columns to map
+                            if reparse {
+                                self.reparse_production(&sample.source_file, parser);
+                            } else {
+                                self.process_file(path);
+                            }
+                        } else {
+                            let (preset, mapping) = sample.suggested_mapping();
+                            self.pending_mapping = Some(PendingMapping {
+                                path,
+                                sample: *sample,
+                                mapping,
+                                preset: Some(preset),
+                                timezone: self.settings.case_timezone,
+                                duplicates: DuplicatePolicy::default(),
+                                reparse,
+                                sheet: None,
+                            });
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::WorkbookSampled(path, choice, sample, reparse) => {
+                        let (preset, mapping) = sample.suggested_mapping();
+                        self.pending_mapping = Some(PendingMapping {
+                            path,
+                            sample: *sample,
+                            mapping,
+                            preset: Some(preset),
+                            timezone: self.settings.case_timezone,
+                            duplicates: DuplicatePolicy::default(),
+                            reparse,
+                            sheet: Some(*choice),
+                        });
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::PdfExtracted(path, extraction) => {
+                        if extraction.review_count() > 0 {
+                            self.pending_pdf_review = Some(PendingPdfReview {
+                                path,
+                                extraction: *extraction,
+                                timezone: self.settings.case_timezone,
+                                duplicates: DuplicatePolicy::default(),
+                                show_all: false,
+                            });
+                        } else {
+                            self.load_pdf_extraction(path, *extraction, DuplicatePolicy::default(), false);
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::CaseOpened(path, snapshot) => {
+                        self.add_message(Message::Success(tr!(
+                            "msg-case-opened",
+                            path = path.display().to_string(),
+                            count = snapshot.records.len()
+                        )));
+                        self.load_snapshot(*snapshot);
+                    }
+                    JobOutput::Script(output) => {
+                        self.add_message(Message::Success(tr!(
+                            "msg-script-finished",
+                            name = &output.script_name,
+                            count = output.rows.len()
+                        )));
+                        self.scripts_view.last_error = None;
+                        self.scripts_view.store(output);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::Exported(path) => {
+                        self.add_message(Message::Success(tr!(
+                            "msg-exported",
+                            path = path.display().to_string()
+                        )));
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::AutoExported(paths) => {
+                        let paths: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+                        self.add_message(Message::Success(tr!("msg-auto-exported", paths = paths.join(", "))));
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::NetworkExported(paths) => {
+                        let paths: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+                        self.add_message(Message::Success(tr!("msg-network-exported", paths = paths.join(", "))));
+                    }
+                    #[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+                    JobOutput::SheetsPushed(url) => {
+                        self.add_message(Message::Success(tr!("msg-google-sheets-pushed", url = url)));
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::CaseSaved(path) => {
+                        self.add_message(Message::Success(tr!("msg-case-saved", path = path.display().to_string())));
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    JobOutput::CallerNames(answers, stopped) => {
+                        let named = answers.values().filter(|name| name.is_some()).count();
+                        let looked_up = answers.len();
+                        Arc::make_mut(&mut self.caller_names).add_lookups(answers);
+                        if let Some(path) = CallerNames::cache_path() {
+                            if let Err(e) = self.caller_names.save_cache(&path) {
+                                warn!("Failed to save the CNAM cache: {:#}", e);
+                            }
+                        }
+                        match stopped {
+                            Some(error) => self.add_message(Message::Warning(tr!("msg-cnam-lookup-stopped", count = looked_up, error = error))),
+                            None => self.add_message(Message::Success(tr!("msg-cnam-lookup-finished", count = looked_up, named = named))),
+                        }
+                    }
+                    JobOutput::Compared(comparison) => {
+                        self.add_message(Message::Success(tr!(
+                            "msg-case-compared",
+                            name = &comparison.other_label,
+                            count = comparison.shared_numbers.len()
+                        )));
+                        self.case_comparison = Some(*comparison);
+                        self.open_detail_view(DetailView::CaseComparison);
+                    }
+                    JobOutput::ProductionDiffed(diff) => {
+                        self.add_message(Message::Success(tr!(
+                            "msg-productions-diffed",
+                            added = diff.added.len(),
+                            removed = diff.removed.len(),
+                            changed = diff.changed.len()
+                        )));
+                        self.production_diff = Some(*diff);
+                        self.open_detail_view(DetailView::ProductionDiff);
+                    }
+                },
+                JobEvent::Failed { kind, error: error_msg } => {
+                    error!("{:?} job failed: {}", kind, error_msg);
+                    if kind == JobKind::Script {
+                        self.scripts_view.last_error = Some(error_msg.clone());
+                    } else if kind != JobKind::Export && kind != JobKind::Compare && kind != JobKind::Lookup {
+                        self.processing_state = ProcessingState::Error(error_msg.clone());
+                    }
+                    self.add_message(Message::Error(tr!("msg-job-failed", kind = kind.label(), error = &error_msg)));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.start_queued_productions();
+                }
+            }
+        }
+    }
+    
+    fn cancel_job(&mut self, id: JobId) {
+        self.jobs.cancel(id);
+        let ingesting = self.jobs.is_running(JobKind::Parse) || self.jobs.is_running(JobKind::Analytics);
+        if !ingesting && matches!(self.processing_state, ProcessingState::Processing | ProcessingState::ComputingAnalytics) {
+            self.processing_state = ProcessingState::Idle;
+        }
+    }
+    
+    fn load_records(&mut self, records: Arc<Vec<ProcessedCallRecord>>, sources: Vec<SourceFile>, analytics: Analytics) {
+        let mut targets: Vec<String> = records.iter()
+            .map(|r| r.target_number.as_str())
+            .filter(|t| !t.is_empty())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        targets.sort();
+        logging::set_case_id((!targets.is_empty()).then(|| targets.join("+")));
+        
+        self.loaded_targets = targets;
+        // Re-analyzing the same records (e.g. toggling a target) leaves completeness unchanged
+        if !Arc::ptr_eq(&records, &self.call_records) {
+            self.data_quality = DataQualityReport::build(&records, &self.parse_reports);
+            let flagged = self.data_quality.record_validation.flagged;
+            if flagged > 0 {
+                self.add_message(Message::Warning(tr!("msg-records-flagged", count = flagged)));
+            }
+            // A comparison describes the records it was run against
+            self.case_comparison = None;
+            self.production_diff = None;
+            self.detail_views.retain(|open| !matches!(open.view, DetailView::CaseComparison | DetailView::ProductionDiff));
+        }
+        self.call_records = records;
+        self.sources = sources;
+        self.analytics = Some(analytics);
+        // Script output describes the previous record set
+        self.scripts_view.outputs.clear();
+        self.processing_state = ProcessingState::Completed;
+        self.session_revision += 1;
+        self.add_message(Message::Success(tr!(
+            "msg-processed",
+            count = self.call_records.len()
+        )));
+    }
+    
+    /// Swaps the active case's fields with workspace `index`'s.
+    fn swap_workspace(&mut self, index: usize) {
+        let workspace = &mut self.workspaces[index];
+        std::mem::swap(&mut self.call_records, &mut workspace.call_records);
+        std::mem::swap(&mut self.sources, &mut workspace.sources);
+        std::mem::swap(&mut self.parse_reports, &mut workspace.parse_reports);
+        std::mem::swap(&mut self.skipped_banner_dismissed, &mut workspace.skipped_banner_dismissed);
+        std::mem::swap(&mut self.data_quality, &mut workspace.data_quality);
+        std::mem::swap(&mut self.analytics, &mut workspace.analytics);
+        std::mem::swap(&mut self.analytics_options, &mut workspace.analytics_options);
+        std::mem::swap(&mut self.loaded_targets, &mut workspace.loaded_targets);
+        std::mem::swap(&mut self.excluded_targets, &mut workspace.excluded_targets);
+        std::mem::swap(&mut self.annotations, &mut workspace.annotations);
+        std::mem::swap(&mut self.annotation_history, &mut workspace.annotation_history);
+        std::mem::swap(&mut self.processing_state, &mut workspace.processing_state);
+        std::mem::swap(&mut self.record_filter, &mut workspace.record_filter);
+        std::mem::swap(&mut self.record_selection, &mut workspace.record_selection);
+        std::mem::swap(&mut self.detail_views, &mut workspace.detail_views);
+        std::mem::swap(&mut self.case_comparison, &mut workspace.case_comparison);
+        std::mem::swap(&mut self.production_diff, &mut workspace.production_diff);
+        std::mem::swap(&mut self.evidence_verification, &mut workspace.evidence_verification);
+        std::mem::swap(&mut self.scripts_view.outputs, &mut workspace.script_outputs);
+        #[cfg(not(target_arch = "wasm32"))]
+        std::mem::swap(&mut self.case_file_path, &mut workspace.case_file_path);
+    }
+    
+    /// Records being parsed or analyzed, and comparisons, land in whichever case is active
+    /// when they finish, so the active case cannot change meanwhile.
+    fn workspace_busy(&self) -> bool {
+        [JobKind::Parse, JobKind::Analytics, JobKind::Compare].into_iter().any(|kind| self.jobs.is_running(kind))
+    }
+    
+    fn switch_workspace(&mut self, index: usize) {
+        if index == self.active_workspace || index >= self.workspaces.len() {
+            return;
+        }
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return;
+        }
+        self.swap_workspace(self.active_workspace);
+        self.swap_workspace(index);
+        self.active_workspace = index;
+        logging::set_case_id((!self.loaded_targets.is_empty()).then(|| self.loaded_targets.join("+")));
+        // The autosave snapshot follows the active case
+        self.session_revision += 1;
+    }
+    
+    fn new_workspace(&mut self) {
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return;
+        }
+        let mut number = self.workspaces.len() + 1;
+        while self.workspaces.iter().any(|w| w.name == tr!("workspace-name", number = number)) {
+            number += 1;
+        }
+        self.workspaces.push(Workspace::new(tr!("workspace-name", number = number), &self.settings));
+        self.switch_workspace(self.workspaces.len() - 1);
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_new_case_wizard(&mut self) {
+        self.new_case_wizard = Some(NewCaseWizard::new(&self.settings));
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    fn wizard_open(&self) -> bool {
+        self.new_case_wizard.is_some()
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn wizard_open(&self) -> bool {
+        false
+    }
+    
+    /// Sets up the wizard's case: in the active workspace if it is still empty, otherwise in
+    /// a new one, with the chosen defaults saved and its productions parsed. Returns the
+    /// wizard if the case cannot be set up yet.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn finish_new_case_wizard(&mut self, wizard: NewCaseWizard) -> Option<NewCaseWizard> {
+        if self.workspace_busy() {
+            self.add_message(Message::Warning(tr!("msg-workspace-busy")));
+            return Some(wizard);
+        }
+        if !self.call_records.is_empty() || !self.annotations.case.is_empty() {
+            self.new_workspace();
+        }
+        let case = wizard.case.trimmed();
+        self.add_message(Message::Success(tr!("msg-case-created", name = case.name.as_str())));
+        self.workspaces[self.active_workspace].name = case.name.clone();
+        self.annotations.case = case;
+        self.settings.case_timezone = wizard.timezone;
+        self.settings.night_hours = wizard.night_hours;
+        if let Err(e) = self.settings.save() {
+            error!("Failed to save settings: {:#}", e);
+        }
+        if !wizard.productions.is_empty() {
+            let config = PipelineConfig { duplicates: wizard.duplicates, ..PipelineConfig::default() };
+            self.process_files_with(wizard.productions.into_iter().map(|path| (path, None)).collect(), config, false);
+        }
+        self.selected_tab = Tab::Overview;
+        self.session_revision += 1;
+        None
+    }
+    
+    /// Closes a workspace, discarding its case; the last one cannot be closed.
+    fn close_workspace(&mut self, index: usize) {
+        if self.workspaces.len() < 2 || index >= self.workspaces.len() {
+            return;
+        }
+        if index == self.active_workspace {
+            self.switch_workspace(if index == 0 { 1 } else { index - 1 });
+            if index == self.active_workspace {
+                return;
+            }
+        }
+        let label = self.workspace_label(index);
+        self.workspaces.remove(index);
+        if index < self.active_workspace {
+            self.active_workspace -= 1;
+        }
+        self.add_message(Message::Info(tr!("msg-workspace-closed", name = label)));
+    }
+    
+    /// The case name given in the new-case wizard, else the workspace's targets once records
+    /// are loaded, else its name.
+    fn workspace_label(&self, index: usize) -> String {
+        let (targets, case) = if index == self.active_workspace {
+            (&self.loaded_targets, &self.annotations.case)
+        } else {
+            (&self.workspaces[index].loaded_targets, &self.workspaces[index].annotations.case)
+        };
+        if !case.name.is_empty() {
+            return case.name.clone();
+        }
+        match targets.as_slice() {
+            [] => self.workspaces[index].name.clone(),
+            [target] => self.settings.format_number(target),
+            [target, rest @ ..] => tr!("workspace-targets", target = self.settings.format_number(target), count = rest.len()),
+        }
+    }
+    
+    /// Compares the active case with another open workspace, like comparing with a case file.
+    fn compare_with_workspace(&mut self, index: usize) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-compare-needs-records")));
+            return;
+        }
+        if index == self.active_workspace || index >= self.workspaces.len() {
+            return;
+        }
+        let label = self.workspace_label(index);
+        let records = Arc::clone(&self.call_records);
+        let other = Arc::clone(&self.workspaces[index].call_records);
+        self.jobs.spawn(JobKind::Compare, tr!("job-compare-workspace", name = label.clone()), move |job| {
+            job.set_progress(None, tr!("progress-comparing-cases"));
+            let comparison = CaseComparison::compare(&records, &other, &label);
+            Ok(JobOutput::Compared(Box::new(comparison)))
+        });
+    }
+    
+    fn render_workspace_bar(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut close = None;
+        let mut compare = None;
+        let mut add = false;
+        
+        ui.horizontal(|ui| {
+            ui.label(tr!("workspace-label"));
+            for index in 0..self.workspaces.len() {
+                if ui.selectable_label(index == self.active_workspace, self.workspace_label(index)).clicked() {
+                    switch_to = Some(index);
+                }
+                if self.workspaces.len() > 1 && ui.small_button("✖").on_hover_text(tr!("workspace-close-hint")).clicked() {
+                    close = Some(index);
+                }
+            }
+            if ui.small_button("➕").on_hover_text(tr!("workspace-new")).clicked() {
+                add = true;
+            }
+            if self.workspaces.len() > 1 {
+                ui.add_enabled_ui(!self.call_records.is_empty(), |ui| {
+                    ui.menu_button(tr!("workspace-compare"), |ui| {
+                        for index in (0..self.workspaces.len()).filter(|&i| i != self.active_workspace) {
+                            if ui.button(self.workspace_label(index)).clicked() {
+                                ui.close_menu();
+                                compare = Some(index);
+                            }
+                        }
+                    });
+                });
+            }
+        });
+        
+        if let Some(index) = switch_to {
+            self.switch_workspace(index);
+        }
+        if let Some(index) = close {
+            self.close_workspace(index);
+        }
+        if let Some(index) = compare {
+            self.compare_with_workspace(index);
+        }
+        if add {
+            self.new_workspace();
+        }
+    }
+    
+    /// Replaces the loaded records and review state with a saved session's.
+    fn load_snapshot(&mut self, snapshot: SessionSnapshot) {
+        self.annotations = snapshot.annotations;
+        self.annotation_history.clear();
+        self.excluded_targets.clear();
+        self.set_parse_reports(snapshot.parse_reports);
+        self.spawn_analytics(snapshot.records, snapshot.sources);
+    }
+    
+    fn restore_session(&mut self) {
+        let Some(path) = self.pending_recovery.take() else {
+            return;
+        };
+        self.processing_state = ProcessingState::Processing;
+        self.jobs.spawn(JobKind::Parse, tr!("job-restore-session"), move |_| {
+            let snapshot = SessionSnapshot::load_from(&path)?;
+            Ok(JobOutput::Restored(Box::new(snapshot)))
+        });
+    }
+    
+    fn discard_recovery(&mut self) {
+        self.pending_recovery = None;
+        if let Some(recovery) = &self.recovery {
+            recovery.discard_snapshot();
+        }
+    }
+    
+    /// Snapshots records and annotations on a background thread once per [`AUTOSAVE_INTERVAL`]
+    /// while there are unsaved changes.
+    fn autosave(&mut self, ctx: &egui::Context) {
+        if self.session_revision == self.autosaved_revision || self.pending_recovery.is_some() {
+            return;
+        }
+        // Records are handed to the analytics job while it runs, so there is nothing to snapshot
+        if self.jobs.is_running(JobKind::Analytics) {
+            return;
+        }
+        let Some(recovery) = &self.recovery else {
+            return;
+        };
+        let remaining = AUTOSAVE_INTERVAL.saturating_sub(self.last_autosave.elapsed());
+        if !remaining.is_zero() {
+            ctx.request_repaint_after(remaining);
+            return;
+        }
+        if self.autosave_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        
+        let path = recovery.snapshot_path();
+        let snapshot = SessionSnapshot::new(
+            Arc::clone(&self.call_records),
+            self.sources.clone(),
+            self.parse_reports.clone(),
+            self.annotations.clone(),
+        );
+        let running = Arc::clone(&self.autosave_running);
+        thread::spawn(move || {
+            match snapshot.save_to(&path) {
+                Ok(()) => info!("Autosaved {} records to {:?}", snapshot.records.len(), path),
+                Err(e) => warn!("Autosave failed: {:#}", e),
+            }
+            running.store(false, Ordering::SeqCst);
+        });
+        
+        self.autosaved_revision = self.session_revision;
+        self.last_autosave = Instant::now();
+    }
+    
+    fn load_sample_dataset(&mut self) {
+        self.processing_state = ProcessingState::Processing;
+        self.jobs.spawn(JobKind::Parse, tr!("job-load-sample"), |_| {
+            let (records, report) = XmlParser::parse_content_with_report(tutorial::SAMPLE_DATASET, tutorial::SAMPLE_DATASET_NAME)?;
+            let source = SourceFile::from_bytes(tutorial::SAMPLE_DATASET_NAME, tutorial::SAMPLE_DATASET.as_bytes())
+                .read_with(report.format, &[]);
+            Ok(JobOutput::Parsed(records, vec![source], vec![report]))
+        });
+    }
+    
+    fn advance_tutorial(&mut self, step: TutorialStep) {
+        match step {
+            TutorialStep::DropZone => {
+                self.load_sample_dataset();
+                self.selected_tab = Tab::Overview;
+            }
+            TutorialStep::Filters => self.selected_tab = Tab::CallRecords,
+            _ => {}
+        }
+        self.tutorial_step = Some(step);
+    }
+    
+    fn end_tutorial(&mut self) {
+        self.tutorial_step = None;
+        self.settings.tutorial_completed = true;
+        if let Err(e) = self.settings.save() {
+            error!("Failed to save settings: {:#}", e);
+        }
+    }
+    
+    fn save_settings(&mut self) {
+        let watch_folder = self.settings_form.watch_folder.trim();
+        self.settings.watch_folder = (!watch_folder.is_empty()).then(|| PathBuf::from(watch_folder));
+        let auto_export_dir = self.settings_form.auto_export_dir.trim();
+        self.settings.auto_export_dir = (!auto_export_dir.is_empty()).then(|| PathBuf::from(auto_export_dir));
+        let signing_key_path = self.settings_form.signing_key_path.trim();
+        self.settings.signing_key_path = (!signing_key_path.is_empty()).then(|| PathBuf::from(signing_key_path));
+        let carrier_paths = (self.settings.carrier_assignments_path.clone(), self.settings.ported_numbers_path.clone());
+        let carrier_assignments_path = self.settings_form.carrier_assignments_path.trim();
+        self.settings.carrier_assignments_path = (!carrier_assignments_path.is_empty()).then(|| PathBuf::from(carrier_assignments_path));
+        let ported_numbers_path = self.settings_form.ported_numbers_path.trim();
+        self.settings.ported_numbers_path = (!ported_numbers_path.is_empty()).then(|| PathBuf::from(ported_numbers_path));
+        if carrier_paths != (self.settings.carrier_assignments_path.clone(), self.settings.ported_numbers_path.clone()) {
+            self.reload_carriers();
+        }
+        let cnam_path = self.settings_form.cnam_path.trim();
+        let cnam_path = (!cnam_path.is_empty()).then(|| PathBuf::from(cnam_path));
+        if cnam_path != self.settings.cnam_path {
+            self.settings.cnam_path = cnam_path;
+            self.reload_caller_names();
+        }
+        let cnam_lookup_url = self.settings_form.cnam_lookup_url.trim();
+        self.settings.cnam_lookup_url = (!cnam_lookup_url.is_empty()).then(|| cnam_lookup_url.to_string());
+        let google_sheets_credentials = self.settings_form.google_sheets_credentials.trim();
+        self.settings.google_sheets_credentials = (!google_sheets_credentials.is_empty()).then(|| PathBuf::from(google_sheets_credentials));
+        let google_sheet_id = self.settings_form.google_sheet_id.trim();
+        self.settings.google_sheet_id = (!google_sheet_id.is_empty()).then(|| google_sheet_id.to_string());
+        let excel_datetime_format = self.settings_form.excel_datetime_format.trim();
+        self.settings.excel_datetime_format = (!excel_datetime_format.is_empty()).then(|| excel_datetime_format.to_string());
+        let excel_duration_format = self.settings_form.excel_duration_format.trim();
+        self.settings.excel_duration_format = (!excel_duration_format.is_empty()).then(|| excel_duration_format.to_string());
+        
+        match self.settings.save() {
+            Ok(path) => self.add_message(Message::Success(tr!("msg-settings-saved", path = path.display().to_string()))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-settings-save-failed", error = format!("{:#}", e)))),
+        }
+        
+        // Top-N, exclusions, and the case timezone only take effect on a fresh analytics pass
+        self.reanalyze_if_stale();
+    }
+    
+    fn reload_carriers(&mut self) {
+        match self.settings.carrier_directory() {
+            Ok(carriers) => {
+                if !carriers.is_empty() {
+                    self.add_message(Message::Success(tr!("msg-carriers-loaded")));
+                }
+                self.carriers = Arc::new(carriers);
+            }
+            Err(e) => {
+                self.carriers = Arc::default();
+                self.add_message(Message::Error(tr!("msg-carriers-load-failed", error = format!("{:#}", e))));
+            }
+        }
+    }
+    
+    fn reload_known_numbers(&mut self) {
+        match self.settings.known_numbers() {
+            Ok(known_numbers) => {
+                if !known_numbers.is_empty() {
+                    self.add_message(Message::Success(tr!("msg-known-numbers-loaded", count = known_numbers.len())));
+                }
+                self.known_numbers = Arc::new(known_numbers);
+            }
+            Err(e) => {
+                self.known_numbers = Arc::default();
+                self.add_message(Message::Error(tr!("msg-known-numbers-load-failed", error = format!("{:#}", e))));
+            }
+        }
+    }
+    
+    fn reload_caller_names(&mut self) {
+        match self.settings.caller_names() {
+            Ok(names) => {
+                if self.settings.cnam_path.is_some() {
+                    self.add_message(Message::Success(tr!("msg-caller-names-loaded")));
+                }
+                self.caller_names = Arc::new(names);
+            }
+            Err(e) => {
+                self.caller_names = Arc::default();
+                self.add_message(Message::Error(tr!("msg-caller-names-load-failed", error = format!("{:#}", e))));
+            }
+        }
+    }
+    
+    /// Sends the busiest contacts that still have no name to the lookup service. Only runs
+    /// when asked for, with lookups turned on in settings.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_cnam_lookup(&mut self) {
+        if self.jobs.is_running(JobKind::Lookup) {
+            return;
+        }
+        let lookup = match self.settings.cnam_lookup() {
+            Ok(Some(lookup)) => lookup,
+            Ok(None) => return,
+            Err(e) => {
+                self.add_message(Message::Error(tr!("msg-cnam-lookup-failed", error = format!("{:#}", e))));
+                return;
+            }
+        };
+        let pending: Vec<String> = self.annotations.subscribers.resolve(&self.call_records).into_iter()
+            .map(|resolution| resolution.number)
+            .filter(|number| self.caller_names.needs_lookup(number))
+            .take(CNAM_LOOKUP_LIMIT)
+            .map(str::to_string)
+            .collect();
+        if pending.is_empty() {
+            self.add_message(Message::Info(tr!("msg-cnam-nothing-to-look-up")));
+            return;
+        }
+        
+        self.jobs.spawn(JobKind::Lookup, tr!("job-cnam-lookup", count = pending.len()), move |job| {
+            let mut answers = HashMap::new();
+            for (i, number) in pending.iter().enumerate() {
+                job.check_cancelled()?;
+                job.set_progress(Some(i as f32 / pending.len() as f32), tr!("progress-cnam-lookup", done = i, total = pending.len()));
+                match lookup.fetch(number) {
+                    Ok(name) => {
+                        answers.insert(number.clone(), name);
+                    }
+                    // Keep what was answered so far; a dead service would fail every number anyway
+                    Err(e) => return Ok(JobOutput::CallerNames(answers, Some(format!("{:#}", e)))),
+                }
+            }
+            Ok(JobOutput::CallerNames(answers, None))
+        });
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    fn forget_cnam_lookups(&mut self) {
+        let count = Arc::make_mut(&mut self.caller_names).clear_lookups();
+        if let Some(path) = CallerNames::cache_path() {
+            if let Err(e) = self.caller_names.save_cache(&path) {
+                warn!("Failed to clear the CNAM cache: {:#}", e);
+            }
+        }
+        self.add_message(Message::Success(tr!("msg-cnam-cache-cleared", count = count)));
+    }
+    
+    fn clear_analytics_cache(&mut self) {
+        let Some(cache) = &self.analytics_cache else {
+            return;
+        };
+        match cache.clear() {
+            Ok(count) => self.add_message(Message::Success(tr!("msg-cache-cleared", count = count))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-cache-clear-failed", error = format!("{:#}", e)))),
+        }
+    }
+    
+    fn run_script(&mut self, script: UserScript) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-script-needs-records")));
+            return;
+        }
+        
+        let records = Arc::clone(&self.call_records);
+        self.jobs.spawn(JobKind::Script, tr!("job-script", name = &script.name), move |job| {
+            job.set_progress(None, tr!("progress-script-running", count = records.len()));
+            let job = job.clone();
+            let output = ScriptRunner::run(&script, &records, move || job.is_cancelled())?;
+            Ok(JobOutput::Script(output))
+        });
+    }
+    
+    /// Opens the native file picker; the productions chosen are opened as if dropped together.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_file_dialog(&mut self) {
+        let Some(paths) = rfd::FileDialog::new()
+            .set_title(tr!("dialog-open-productions"))
+            .add_filter(tr!("filter-productions"), &PRODUCTION_EXTENSIONS)
+            .add_filter(tr!("filter-xml"), &["xml"])
+            .pick_files()
+        else {
+            return;
+        };
+        self.open_productions(paths);
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn open_file_dialog(&mut self) {
+        if !self.file_picker_open {
+            self.file_picker_open = true;
+            web::pick_files(self.picked_files.0.clone());
+        }
+    }
+    
+    /// Ingests productions that appear in the watch folder. Follows the saved setting, so
+    /// changing the folder starts watching the new one from its current contents.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_watch_folder(&mut self, ctx: &egui::Context) {
+        let Some(folder) = &self.settings.watch_folder else {
+            self.watcher = None;
+            return;
+        };
+        if self.watcher.as_ref().map(FolderWatcher::folder) != Some(folder.as_path()) {
+            self.watcher = Some(FolderWatcher::new(folder.clone()));
+            self.last_watch_poll = Instant::now();
+        }
+        
+        let remaining = WATCH_POLL_INTERVAL.saturating_sub(self.last_watch_poll.elapsed());
+        if !remaining.is_zero() {
+            ctx.request_repaint_after(remaining);
+            return;
+        }
+        self.last_watch_poll = Instant::now();
+        
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+        let new_files = match watcher.poll() {
+            Ok(files) => files,
+            Err(e) => {
+                warn!("Watch folder poll failed: {:#}", e);
+                Vec::new()
+            }
+        };
+        for path in new_files {
+            info!("New production in watch folder: {:?}", path);
+            if self.settings.auto_export {
+                if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                    self.pending_auto_exports.insert(name.to_string());
+                }
+            }
+            self.process_file(path);
+        }
+        ctx.request_repaint_after(WATCH_POLL_INTERVAL);
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn poll_file_picker(&mut self, ctx: &egui::Context) {
+        if !self.file_picker_open {
+            return;
+        }
+        match self.picked_files.1.try_recv() {
+            Ok(files) => {
+                self.file_picker_open = false;
+                self.process_bytes(files.into_iter().map(|file| (file.name, file.bytes)).collect());
+            }
+            // The picker resolves outside egui's event loop, so keep checking while it is open
+            Err(_) => ctx.request_repaint_after(Duration::from_millis(200)),
+        }
+    }
+    
+    fn available_commands(&self) -> Vec<(String, AppCommand)> {
+        let mut commands = vec![
+            (tr!("cmd-open-file"), AppCommand::OpenFile),
+            (tr!("cmd-export-excel"), AppCommand::ExportExcel),
+            (tr!("cmd-export-network"), AppCommand::ExportNetwork),
+            (tr!("cmd-export-cellebrite"), AppCommand::ExportCellebrite),
+            (tr!("cmd-export-penlink"), AppCommand::ExportPenLink),
+            (tr!("cmd-export-parse-errors"), AppCommand::ExportParseErrors),
+            (tr!("cmd-export-elastic"), AppCommand::ExportElastic),
+            (tr!("cmd-export-exhibits"), AppCommand::ExportExhibits),
+        ];
+        #[cfg(not(target_arch = "wasm32"))]
+        commands.insert(2, (tr!("cmd-export-grouped"), AppCommand::ExportGrouped));
+        #[cfg(not(target_arch = "wasm32"))]
+        commands.insert(1, (tr!("cmd-new-case"), AppCommand::NewCase));
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.call_records.is_empty() {
+            commands.insert(2, (tr!("cmd-add-productions"), AppCommand::AddProductions));
+        }
+        if let Some(label) = self.annotation_history.undo_label() {
+            commands.push((tr!("cmd-undo", change = label), AppCommand::Undo));
+        }
+        if let Some(label) = self.annotation_history.redo_label() {
+            commands.push((tr!("cmd-redo", change = label), AppCommand::Redo));
+        }
+        commands.push((tr!("cmd-new-workspace"), AppCommand::NewWorkspace));
+        for index in (0..self.workspaces.len()).filter(|&i| i != self.active_workspace) {
+            let name = self.workspace_label(index);
+            commands.push((tr!("cmd-switch-workspace", name = name.clone()), AppCommand::SwitchWorkspace(index)));
+            if !self.call_records.is_empty() {
+                commands.push((tr!("cmd-compare-workspace", name = name), AppCommand::CompareWorkspace(index)));
+            }
+        }
+        for tab in Tab::ALL {
+            commands.push((tr!("cmd-go-to-tab", tab = tab.label()), AppCommand::GoToTab(tab)));
+        }
+        for chip in QuickFilter::ALL {
+            let label = if self.record_filter.chips.contains(&chip) {
+                tr!("cmd-remove-chip", chip = chip.label())
+            } else {
+                tr!("cmd-apply-chip", chip = chip.label())
+            };
+            commands.push((label, AppCommand::ToggleChip(chip)));
+        }
+        let regex_label = if self.record_filter.regex_mode { tr!("cmd-disable-regex") } else { tr!("cmd-enable-regex") };
+        commands.push((regex_label, AppCommand::ToggleRegex));
+        commands.push((tr!("cmd-clear-filters"), AppCommand::ClearFilters));
+        for format in PhoneFormat::ALL {
+            commands.push((tr!("cmd-number-format", format = format.label()), AppCommand::SetPhoneFormat(format)));
+        }
+        let local_label = if self.settings.show_local_time { tr!("cmd-hide-local-time") } else { tr!("cmd-show-local-time") };
+        commands.push((local_label, AppCommand::ToggleLocalTime));
+        for language in Language::ALL {
+            commands.push((tr!("cmd-language", language = language.label()), AppCommand::SetLanguage(language)));
+        }
+        commands.push((tr!("cmd-start-tutorial"), AppCommand::StartTutorial));
+        commands
+    }
+    
+    fn run_command(&mut self, command: AppCommand) {
+        match command {
+            AppCommand::OpenFile => self.open_file_dialog(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppCommand::NewCase => self.start_new_case_wizard(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppCommand::AddProductions => self.add_productions_dialog(),
+            AppCommand::ExportExcel => self.export_to_excel(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppCommand::ExportGrouped => self.export_grouped_excel(),
+            AppCommand::ExportNetwork => self.export_network(),
+            AppCommand::ExportCellebrite => self.export_cellebrite(),
+            AppCommand::ExportPenLink => self.export_penlink(),
+            AppCommand::ExportParseErrors => self.export_parse_errors(),
+            AppCommand::ExportElastic => self.export_elastic(),
+            AppCommand::ExportExhibits => self.export_exhibits(),
+            AppCommand::Undo => self.undo_annotations(),
+            AppCommand::Redo => self.redo_annotations(),
+            AppCommand::NewWorkspace => self.new_workspace(),
+            AppCommand::SwitchWorkspace(index) => self.switch_workspace(index),
+            AppCommand::CompareWorkspace(index) => self.compare_with_workspace(index),
+            AppCommand::GoToTab(tab) => self.selected_tab = tab,
+            AppCommand::ToggleChip(chip) => {
+                self.record_filter.toggle_chip(chip);
+                self.selected_tab = Tab::CallRecords;
+            }
+            AppCommand::ToggleRegex => {
+                self.record_filter.regex_mode = !self.record_filter.regex_mode;
+                self.record_filter.refresh_regex();
+            }
+            AppCommand::ClearFilters => self.record_filter.clear(),
+            AppCommand::SetPhoneFormat(format) => self.settings.phone_format = format,
+            AppCommand::ToggleLocalTime => self.settings.show_local_time = !self.settings.show_local_time,
+            AppCommand::SetLanguage(language) => self.settings.language = language,
+            AppCommand::StartTutorial => self.tutorial_step = Some(TutorialStep::Welcome),
+        }
+    }
+    
+    fn export_file_name(&self, analytics: &Analytics) -> String {
+        self.settings.case_export_file_name(&Utc::now(), analytics)
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_to_excel(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        if self.jobs.is_running(JobKind::Export) {
+            self.add_message(Message::Warning(tr!("msg-export-running")));
+            return;
+        }
+        
+        if let Some(analytics) = &self.analytics {
+            let output_path = PathBuf::from(self.export_file_name(analytics));
+            let records = Arc::clone(&self.call_records);
+            let analytics = analytics.clone();
+            let annotations = self.annotations.clone();
+            let carriers = Arc::clone(&self.carriers);
+            let caller_names = Arc::clone(&self.caller_names);
+            let known_numbers = Arc::clone(&self.known_numbers);
+            let parse_reports = self.parse_reports.clone();
+            let settings = self.settings.clone();
+            let script_outputs = self.scripts_view.outputs.clone();
+            
+            self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+                let signing_key = settings.signing_key()?;
+                let progress = |fraction: Option<f32>| match fraction {
+                    Some(fraction) => job.set_progress(Some(fraction), tr!("progress-writing-records", count = records.len())),
+                    None => job.set_progress(None, tr!("progress-finishing-workbook")),
+                };
+                ExcelExporter::export_data_with_progress(&records, &parse_reports, &analytics, &annotations, &carriers, &caller_names, &known_numbers, &settings, &script_outputs, &output_path, &progress)?;
+                if let Some(key) = &signing_key {
+                    job.set_progress(None, tr!("progress-signing"));
+                    ExportSignature::sign_file(&output_path, key)?;
+                }
+                Ok(JobOutput::Exported(output_path))
+            });
+        }
+    }
+    
+    /// Writes `<export name>_by_contact.xlsx`: the records grouped by contact with subtotals.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_grouped_excel(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        if self.jobs.is_running(JobKind::Export) {
+            self.add_message(Message::Warning(tr!("msg-export-running")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_by_contact.xlsx", stem));
+        let records = Arc::clone(&self.call_records);
+        let annotations = self.annotations.clone();
+        let settings = self.settings.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+            let signing_key = settings.signing_key()?;
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            ExcelExporter::export_grouped(&records, &annotations, &settings, &output_path)?;
+            if let Some(key) = &signing_key {
+                job.set_progress(None, tr!("progress-signing"));
+                ExportSignature::sign_file(&output_path, key)?;
+            }
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    /// Writes `<export name>_nodes.csv` and `<export name>_edges.csv` for graph tools.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_network(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let nodes_path = PathBuf::from(format!("{}_nodes.csv", stem));
+        let edges_path = PathBuf::from(format!("{}_edges.csv", stem));
+        let records = Arc::clone(&self.call_records);
+        let annotations = self.annotations.clone();
+        let settings = self.settings.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = nodes_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            NetworkExporter::export(&records, &annotations, &settings, &nodes_path, &edges_path)?;
+            Ok(JobOutput::NetworkExported(vec![nodes_path, edges_path]))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_network(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name).to_string();
+        let result = NetworkExporter::to_bytes(&self.call_records, &self.annotations, &self.settings)
+            .and_then(|(nodes, edges)| {
+                web::download(&format!("{}_nodes.csv", stem), "text/csv", &nodes)
+                    .and_then(|_| web::download(&format!("{}_edges.csv", stem), "text/csv", &edges))
+                    .map_err(|e| anyhow::anyhow!("{:?}", e))
+            });
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-network-downloaded", stem = &stem))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+    
+    /// Writes `<export name>_ufed_calls.csv` for import into Cellebrite Physical Analyzer or Pathfinder.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_cellebrite(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_ufed_calls.csv", stem));
+        let records = Arc::clone(&self.call_records);
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            CellebriteExporter::export(&records, &output_path)?;
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_cellebrite(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let name = format!("{}_ufed_calls.csv", stem);
+        let result = CellebriteExporter::to_bytes(&self.call_records)
+            .and_then(|csv| web::download(&name, "text/csv", &csv).map_err(|e| anyhow::anyhow!("{:?}", e)));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-file-downloaded", name = name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+    
+    /// Writes `<export name>_penlink.csv` for PenLink's generic call import.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_penlink(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_penlink.csv", stem));
+        let records = Arc::clone(&self.call_records);
+        let timezone = self.settings.case_timezone;
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            PenLinkExporter::export(&records, timezone, &output_path)?;
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_penlink(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let name = format!("{}_penlink.csv", stem);
+        let result = PenLinkExporter::to_bytes(&self.call_records, self.settings.case_timezone)
+            .and_then(|csv| web::download(&name, "text/csv", &csv).map_err(|e| anyhow::anyhow!("{:?}", e)));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-file-downloaded", name = name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+    
+    /// Writes `<export name>_parse_errors.csv`, the malformed blocks and unreadable records of
+    /// every production in the case.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_parse_errors(&mut self) {
+        if self.parse_reports.iter().all(|report| report.skipped_count == 0) {
+            self.add_message(Message::Info(tr!("msg-no-parse-errors")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_parse_errors.csv", stem));
+        let reports = self.parse_reports.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |_| {
+            CsvExporter::export_parse_errors(&reports, &output_path)?;
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_parse_errors(&mut self) {
+        if self.parse_reports.iter().all(|report| report.skipped_count == 0) {
+            self.add_message(Message::Info(tr!("msg-no-parse-errors")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let name = format!("{}_parse_errors.csv", stem);
+        let result = CsvExporter::parse_errors_to_bytes(&self.parse_reports)
+            .and_then(|csv| web::download(&name, "text/csv", &csv).map_err(|e| anyhow::anyhow!("{:?}", e)));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-file-downloaded", name = name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+    
+    /// Writes `<export name>_elastic.ndjson` for the Elasticsearch bulk API.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_elastic(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_elastic.ndjson", stem));
+        let records = Arc::clone(&self.call_records);
+        let sources = self.sources.clone();
+        let annotations = self.annotations.clone();
+        let caller_names = Arc::clone(&self.caller_names);
+        let settings = self.settings.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            ElasticExporter::export(&records, &sources, &annotations, &caller_names, &settings, &output_path)?;
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_elastic(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let name = format!("{}_elastic.ndjson", stem);
+        let result = ElasticExporter::to_bytes(&self.call_records, &self.sources, &self.annotations, &self.caller_names, &self.settings)
+            .and_then(|ndjson| web::download(&name, "application/x-ndjson", &ndjson).map_err(|e| anyhow::anyhow!("{:?}", e)));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-file-downloaded", name = name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+    
+    /// Writes the court-exhibit PDF, with the summary exhibit in the report layout arranged on
+    /// the Summary tab.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_exhibits(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let output_path = PathBuf::from(format!("{}_exhibits.pdf", stem));
+        let report = self.compose_report(analytics);
+        let analytics = analytics.clone();
+        let records = Arc::clone(&self.call_records);
+        let annotations = self.annotations.clone();
+        let settings = self.settings.clone();
+        let parse_reports = self.parse_reports.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-export", path = output_path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-exhibits"));
+            let input = ReportInput {
+                analytics: &analytics,
+                records: &records,
+                annotations: &annotations,
+                settings: &settings,
+                parse_reports: &parse_reports,
+            };
+            ExhibitPackage::export(&report, &input, &output_path)?;
+            Ok(JobOutput::Exported(output_path))
+        });
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    fn export_exhibits(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let name = format!("{}_exhibits.pdf", stem);
+        let report = self.compose_report(analytics);
+        let input = ReportInput {
+            analytics,
+            records: &self.call_records,
+            annotations: &self.annotations,
+            settings: &self.settings,
+            parse_reports: &self.parse_reports,
+        };
+        let pdf = ExhibitPackage::to_bytes(&report, &input);
+        match web::download(&name, "application/pdf", &pdf) {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-file-downloaded", name = name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = format!("{:?}", e)))),
+        }
+    }
+    
+    /// Replaces the Call Records, Analytics, and Summary Report tabs of the configured Google Sheet.
+    #[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+    fn push_to_google_sheets(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let (Some(credentials), Some(sheet_id)) = (self.settings.google_sheets_credentials.clone(), self.settings.google_sheet_id.clone()) else {
+            self.add_message(Message::Warning(tr!("msg-google-sheets-not-configured")));
+            return;
+        };
+        let report = self.compose_report(analytics);
+        let records = Arc::clone(&self.call_records);
+        let analytics = analytics.clone();
+        let annotations = self.annotations.clone();
+        let caller_names = Arc::clone(&self.caller_names);
+        let settings = self.settings.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-google-sheets"), move |job| {
+            job.set_progress(None, tr!("progress-google-sign-in"));
+            let exporter = GoogleSheetsExporter::connect(&credentials, &sheet_id)?;
+            job.check_cancelled()?;
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            let url = exporter.push(&records, &analytics, &report, &annotations, &caller_names, &settings)?;
+            Ok(JobOutput::SheetsPushed(url))
+        });
+    }
+    
+    /// Writes the loaded records, their sources, and annotations to the case file named on the
+    /// Overview tab, in the same format as the autosave snapshot.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_case(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let path = PathBuf::from(self.case_file_path.trim());
+        let snapshot = SessionSnapshot::new(
+            Arc::clone(&self.call_records),
+            self.sources.clone(),
+            self.parse_reports.clone(),
+            self.annotations.clone(),
+        );
+        self.jobs.spawn(JobKind::Export, tr!("job-save-case", path = path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = snapshot.records.len()));
+            snapshot.save_to(&path)?;
+            Ok(JobOutput::CaseSaved(path))
+        });
+    }
+    
+    /// Adds the subscriber results CSV named on the Overview tab to the case's annotations.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import_subscribers(&mut self) {
+        let path = PathBuf::from(self.subscriber_import_path.trim());
+        match SubscriberDirectory::import_csv(&path) {
+            Ok(directory) => {
+                let count = directory.len();
+                let before = self.annotations.clone();
+                self.annotations.subscribers.merge(directory);
+                self.annotations_changed(tr!("change-import-subscribers"), before);
+                self.add_message(Message::Success(tr!("msg-subscribers-imported", count = count)));
+            }
+            Err(e) => self.add_message(Message::Error(tr!("msg-subscribers-import-failed", error = format!("{:#}", e)))),
+        }
+    }
+    
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import_contacts(&mut self) {
+        let path = PathBuf::from(self.contact_import_path.trim());
+        match ContactList::import(&path) {
+            Ok(contacts) => {
+                let before = self.annotations.clone();
+                let added = self.annotations.add_aliases(&contacts);
+                self.annotations_changed(tr!("change-import-contacts"), before);
+                self.add_message(Message::Success(tr!("msg-contacts-imported", count = contacts.len(), added = added)));
+                self.open_detail_view(DetailView::ContactMatches);
+            }
+            Err(e) => self.add_message(Message::Error(tr!("msg-contacts-import-failed", error = format!("{:#}", e)))),
+        }
+    }
+    
+    /// Records an edit of `annotations` for undo, given the state from before it, and marks
+    /// the session for autosave.
+    fn annotations_changed(&mut self, label: String, before: Annotations) {
+        self.annotation_history.record(label, before);
+        self.session_revision += 1;
+        // Reclassified numbers change what the analytics count
+        self.reanalyze_if_stale();
+    }
+    
+    fn undo_annotations(&mut self) {
+        match self.annotation_history.undo(&mut self.annotations) {
+            Some(label) => {
+                self.session_revision += 1;
+                self.reanalyze_if_stale();
+                self.add_message(Message::Info(tr!("msg-undone", change = label)));
+            }
+            None => self.add_message(Message::Info(tr!("msg-nothing-to-undo"))),
+        }
+    }
+    
+    fn redo_annotations(&mut self) {
+        match self.annotation_history.redo(&mut self.annotations) {
+            Some(label) => {
+                self.session_revision += 1;
+                self.reanalyze_if_stale();
+                self.add_message(Message::Info(tr!("msg-redone", change = label)));
+            }
+            None => self.add_message(Message::Info(tr!("msg-nothing-to-redo"))),
+        }
+    }
+    
+    /// Whether a contact has a name from any source: alias, subscriber results, or caller ID.
+    fn is_identified(&self, number: &str) -> bool {
+        self.annotations.alias(number).is_some()
+            || self.annotations.subscribers.lookup(number).is_some()
+            || self.caller_names.lookup(number).is_some()
+    }
+    
+    fn contact_matches(&self, analytics: &Analytics) -> ContactMatches {
+        ContactMatches::build(&self.call_records, &self.annotations.aliases, &analytics.most_frequent_numbers, |number| {
+            self.annotations.subscribers.lookup(number).is_some() || self.caller_names.lookup(number).is_some()
+        })
+    }
+    
+    /// Opens the case file named on the Overview tab, or rebuilds a session from a workbook
+    /// this tool exported when the case file was lost.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_case(&mut self) {
+        let path = PathBuf::from(self.case_file_path.trim());
+        let is_workbook = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"));
+        self.processing_state = ProcessingState::Processing;
+        self.jobs.spawn(JobKind::Parse, tr!("job-open-case", path = path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-loading-case"));
+            let snapshot = if is_workbook {
+                workbook_import::import_workbook(&path)?
+            } else {
+                SessionSnapshot::load_from(&path)?
+            };
+            Ok(JobOutput::CaseOpened(path, Box::new(snapshot)))
+        });
+    }
+    
+    /// Opens the case file named on the Overview tab and compares it with the loaded records.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn compare_with_case(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-compare-needs-records")));
+            return;
+        }
+        let path = PathBuf::from(self.case_file_path.trim());
+        let records = Arc::clone(&self.call_records);
+        self.jobs.spawn(JobKind::Compare, tr!("job-compare-case", path = path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-loading-case"));
+            let other = SessionSnapshot::load_from(&path)?;
+            job.set_progress(None, tr!("progress-comparing-cases"));
+            let label = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let comparison = CaseComparison::compare(&records, &other.records, &label);
+            Ok(JobOutput::Compared(Box::new(comparison)))
+        });
+    }
+    
+    /// Re-hashes the productions the case file named on the Overview tab was built from and
+    /// checks them against the hashes recorded when they were imported.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn verify_evidence(&mut self) {
+        let path = PathBuf::from(self.case_file_path.trim());
+        self.jobs.spawn(JobKind::Compare, tr!("job-verify-evidence", path = path.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-loading-case"));
+            let snapshot = SessionSnapshot::load_from(&path)?;
+            job.set_progress(None, tr!("progress-hashing-sources", count = snapshot.sources.len()));
+            let verification = EvidenceVerification::verify(&path, &snapshot.sources);
+            Ok(JobOutput::EvidenceVerified(Box::new(verification)))
+        });
+    }
+    
+    /// Checks the file named on the Settings tab against its detached signature.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn verify_signature(&mut self) {
+        let path = PathBuf::from(self.settings_form.verify_path.trim());
+        let key_path = self.settings_form.signing_key_path.trim();
+        let result = SigningKey::load(std::path::Path::new(key_path))
+            .and_then(|key| ExportSignature::verify_file(&path, &key));
+        match result {
+            Ok(signature) => self.add_message(Message::Success(tr!(
+                "msg-signature-valid",
+                name = &signature.file_name,
+                signed_at = self.settings.format_local_time(&signature.signed_at)
+            ))),
+            Err(e) => {
+                warn!("Signature check failed for {:?}: {:#}", path, e);
+                self.add_message(Message::Error(tr!("msg-signature-invalid", error = format!("{:#}", e))));
+            }
+        }
+    }
+    
+    /// Whether any of `sources` came from the watch folder while auto-export was on.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn take_pending_auto_export(&mut self, sources: &[SourceFile]) -> bool {
+        let before = self.pending_auto_exports.len();
+        // A watched archive's productions are named after it, e.g. `return.zip/calls.xml`
+        self.pending_auto_exports.retain(|name| !sources.iter().any(|source| {
+            source.name == *name || source.name.strip_prefix(name.as_str()).is_some_and(|inner| inner.starts_with('/'))
+        }));
+        self.pending_auto_exports.len() != before
+    }
+    
+    /// Writes Excel and CSV for a freshly ingested watched production, without user interaction.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn_auto_export(&mut self) {
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let Some(output_dir) = AutoExporter::output_dir(&self.settings) else {
+            return;
+        };
+        let records = Arc::clone(&self.call_records);
+        let analytics = analytics.clone();
+        let annotations = self.annotations.clone();
+        let carriers = Arc::clone(&self.carriers);
+        let caller_names = Arc::clone(&self.caller_names);
+        let known_numbers = Arc::clone(&self.known_numbers);
+        let parse_reports = self.parse_reports.clone();
+        let settings = self.settings.clone();
+        let script_outputs = self.scripts_view.outputs.clone();
+        
+        self.jobs.spawn(JobKind::Export, tr!("job-auto-export", path = output_dir.display().to_string()), move |job| {
+            job.set_progress(None, tr!("progress-writing-records", count = records.len()));
+            let paths = AutoExporter::export(&records, &parse_reports, &analytics, &annotations, &carriers, &caller_names, &known_numbers, &settings, &script_outputs, &output_dir)?;
+            Ok(JobOutput::AutoExported(paths))
+        });
+    }
+    
+    /// The Excel writer is a C library that cannot run in the browser, so the web build
+    /// downloads the call records as CSV and the summary report as text instead.
+    #[cfg(target_arch = "wasm32")]
+    fn export_to_excel(&mut self) {
+        if self.call_records.is_empty() {
+            self.add_message(Message::Warning(tr!("msg-no-data-to-export")));
+            return;
+        }
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name).to_string();
+        let report = self.compose_report(analytics).to_text();
+        let result = CsvExporter::call_records_to_bytes(&self.call_records, &self.annotations, &self.caller_names, &self.settings)
+            .and_then(|csv| {
+                web::download(&format!("{}.csv", stem), "text/csv", &csv)
+                    .and_then(|_| web::download(&format!("{}_summary.txt", stem), "text/plain", report.as_bytes()))
+                    .map_err(|e| anyhow::anyhow!("{:?}", e))
+            });
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-downloaded", stem = &stem))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-export-failed", error = e.to_string()))),
+        }
+    }
+}
+
+impl eframe::App for EsubpoenaApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        i18n::set_language(self.settings.language);
+        phone_region::set_default_region(self.settings.default_region);
+        self.handle_job_results();
+        self.autosave(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_watch_folder(ctx);
+        #[cfg(target_arch = "wasm32")]
+        self.poll_file_picker(ctx);
+        
+        let palette_shortcut = egui::KeyboardShortcut::new(
+            egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+            egui::Key::P,
+        );
+        if ctx.input_mut(|i| i.consume_shortcut(&palette_shortcut)) {
+            self.command_palette.toggle();
+        }
+        // Text fields keep these for their own undo
+        if !ctx.wants_keyboard_input() {
+            let redo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z);
+            let undo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+            // Checked first, since the undo shortcut also matches with Shift held
+            if ctx.input_mut(|i| i.consume_shortcut(&redo_shortcut)) {
+                self.redo_annotations();
+            } else if ctx.input_mut(|i| i.consume_shortcut(&undo_shortcut)) {
+                self.undo_annotations();
+            }
+        }
+        if self.command_palette.open {
+            self.render_command_palette(ctx);
+        }
+        
+        // Keep polling the worker while it runs, even without user input
+        if self.jobs.has_running() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        
+        self.tutorial_anchors = TutorialAnchors::default();
+        
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.render_header(ui);
+            ui.add_space(10.0);
+            
+            match self.selected_tab {
+                Tab::Overview => self.render_overview(ui),
+                Tab::CallRecords => self.render_call_records(ui),
+                Tab::Analytics => self.render_analytics(ui),
+                Tab::Bookmarks => self.render_bookmarks(ui),
+                Tab::Summary => self.render_summary(ui),
+                Tab::DataQuality => self.render_data_quality(ui),
+                Tab::Subscribers => self.render_subscribers(ui),
+                Tab::Scripts => self.render_scripts(ui),
+                Tab::Jobs => self.render_jobs(ui),
+                Tab::Logs => self.render_logs(ui),
+                Tab::Settings => self.render_settings(ui),
+            }
+        });
+        
+        self.render_detail_views(ctx);
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.pending_preview.is_some() {
+            self.render_production_preview(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.pending_mapping.is_some() {
+            self.render_column_mapping(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.pending_pdf_review.is_some() {
+            self.render_pdf_review(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.new_case_wizard.is_some() {
+            self.render_new_case_wizard(ctx);
+        }
+        if self.pending_recovery.is_some() {
+            self.render_recovery_prompt(ctx);
+        } else if let Some(step) = self.tutorial_step {
+            self.render_tutorial(ctx, step);
+        }
+    }
+}
+
+impl EsubpoenaApp {
+    fn render_header(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("📞 eSubpoena Tolls Tool");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let export_button = ui.button(tr!("button-export-excel"));
+                self.tutorial_anchors.export_button = Some(export_button.rect);
+                if export_button.clicked() {
+                    self.export_to_excel();
+                }
+                let redo_label = self.annotation_history.redo_label().map(str::to_string);
+                let redo = ui.add_enabled(redo_label.is_some(), egui::Button::new("⟳"))
+                    .on_hover_text(tr!("button-redo-hint", change = redo_label.unwrap_or_default()))
+                    .on_disabled_hover_text(tr!("button-redo-none"));
+                if redo.clicked() {
+                    self.redo_annotations();
+                }
+                let undo_label = self.annotation_history.undo_label().map(str::to_string);
+                let undo = ui.add_enabled(undo_label.is_some(), egui::Button::new("⟲"))
+                    .on_hover_text(tr!("button-undo-hint", change = undo_label.unwrap_or_default()))
+                    .on_disabled_hover_text(tr!("button-undo-none"));
+                if undo.clicked() {
+                    self.undo_annotations();
+                }
+                ui.menu_button(tr!("button-more-exports"), |ui| {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button(tr!("button-export-grouped")).on_hover_text(tr!("button-export-grouped-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_grouped_excel();
+                    }
+                    if ui.button(tr!("button-export-network")).on_hover_text(tr!("button-export-network-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_network();
+                    }
+                    if ui.button(tr!("button-export-cellebrite")).on_hover_text(tr!("button-export-cellebrite-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_cellebrite();
+                    }
+                    if ui.button(tr!("button-export-penlink")).on_hover_text(tr!("button-export-penlink-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_penlink();
+                    }
+                    if ui.button(tr!("button-export-elastic")).on_hover_text(tr!("button-export-elastic-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_elastic();
+                    }
+                    if ui.button(tr!("button-export-parse-errors")).on_hover_text(tr!("button-export-parse-errors-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_parse_errors();
+                    }
+                    if ui.button(tr!("button-export-exhibits")).on_hover_text(tr!("button-export-exhibits-hint")).clicked() {
+                        ui.close_menu();
+                        self.export_exhibits();
+                    }
+                    #[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+                    if ui.button(tr!("button-push-google-sheets")).on_hover_text(tr!("button-push-google-sheets-hint")).clicked() {
+                        ui.close_menu();
+                        self.push_to_google_sheets();
+                    }
+                });
+                egui::ComboBox::from_id_source("phone_format")
+                    .selected_text(self.settings.phone_format.label())
+                    .show_ui(ui, |ui| {
+                        for format in PhoneFormat::ALL {
+                            ui.selectable_value(&mut self.settings.phone_format, format, format.label());
+                        }
+                    });
+                ui.label(tr!("label-numbers"));
+            });
+        });
+        
+        ui.separator();
+        self.render_workspace_bar(ui);
+        
+        // Tab bar
+        let tab_bar = ui.horizontal(|ui| {
+            for tab in Tab::ALL {
+                ui.selectable_value(&mut self.selected_tab, tab, tab.label());
+            }
+        });
+        self.tutorial_anchors.tab_bar = Some(tab_bar.response.rect);
+        
+        self.render_skipped_banner(ui);
+    }
+    
+    /// Warns, above every tab, that the loaded totals leave out records that failed to parse
+    /// or that a production does not match its own declared coverage.
+    fn render_skipped_banner(&mut self, ui: &mut egui::Ui) {
+        let skipped: usize = self.parse_reports.iter().map(|r| r.skipped_count).sum();
+        let mismatched = self.parse_reports.iter().filter(|r| !r.discrepancies().is_empty()).count();
+        if (skipped == 0 && mismatched == 0) || self.skipped_banner_dismissed {
+            return;
+        }
+        let total: usize = self.parse_reports.iter().map(|r| r.total_records).sum();
+        let message = match (skipped, mismatched) {
+            (0, _) => tr!("banner-discrepancies", files = mismatched),
+            (_, 0) => tr!("banner-skipped-records", skipped = i18n::format_count(skipped), total = i18n::format_count(total)),
+            _ => format!(
+                "{} · {}",
+                tr!("banner-skipped-records", skipped = i18n::format_count(skipped), total = i18n::format_count(total)),
+                tr!("banner-discrepancies", files = mismatched)
+            ),
+        };
+        
+        let mut view_details = false;
+        ui.add_space(5.0);
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(120, 80, 0))
+            .rounding(4.0)
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::WHITE, format!("⚠ {}", message));
+                    view_details = ui.button(tr!("banner-view-details")).clicked();
+                    if ui.button(tr!("banner-dismiss")).clicked() {
+                        self.skipped_banner_dismissed = true;
+                    }
+                });
+            });
+        if view_details {
+            self.open_detail_view(DetailView::SkippedRecords);
+        }
+    }
+    
+    fn render_recovery_prompt(&mut self, ctx: &egui::Context) {
+        let mut restore = false;
+        let mut discard = false;
+        egui::Window::new(tr!("recovery-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(tr!("recovery-body"));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    restore = ui.button(tr!("recovery-restore")).clicked();
+                    discard = ui.button(tr!("recovery-discard")).clicked();
+                });
+            });
+        if restore {
+            self.restore_session();
+        } else if discard {
+            self.discard_recovery();
+        }
+    }
+    
+    /// Shows what the start of a large production holds and lets the user set the parse
+    /// options before committing to the full parse.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_production_preview(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.pending_preview else {
+            return;
+        };
+        let mut load = false;
+        let mut cancel = false;
+        let preview = &pending.preview;
+        let date = |time: &Option<chrono::DateTime<Utc>>| {
+            time.map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_else(|| tr!("preview-not-declared"))
+        };
+        egui::Window::new(tr!("preview-title", name = preview.source_file.as_str()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                egui::Grid::new("production_preview").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("preview-size"));
+                    ui.label(format!("{:.1} MB", preview.file_size as f64 / (1024.0 * 1024.0)));
+                    ui.end_row();
+                    ui.label(tr!("preview-format"));
+                    ui.label(preview.format.label());
+                    ui.end_row();
+                    ui.label(tr!("preview-target"));
+                    ui.label(if preview.header.target_value.is_empty() {
+                        tr!("preview-not-declared")
+                    } else {
+                        self.settings.format_number(&preview.header.target_value)
+                    });
+                    ui.end_row();
+                    ui.label(tr!("preview-declared-range"));
+                    ui.label(tr!("preview-range", from = date(&preview.header.from_date), to = date(&preview.header.to_date)));
+                    ui.end_row();
+                    ui.label(tr!("preview-records"));
+                    ui.vertical(|ui| {
+                        ui.label(tr!("preview-estimated-records", count = preview.estimated_records, sampled = preview.sampled_records));
+                        if let Some(declared) = preview.header.expected_records {
+                            ui.small(tr!("preview-declared-records", count = declared));
+                        }
+                    });
+                    ui.end_row();
+                    
+                    ui.label(tr!("preview-timezone"));
+                    egui::ComboBox::from_id_source("preview_timezone")
+                        .selected_text(pending.timezone.name())
+                        .show_ui(ui, |ui| {
+                            for tz in COMMON_TIMEZONES {
+                                ui.selectable_value(&mut pending.timezone, tz, tz.name());
+                            }
+                        });
+                    ui.end_row();
+                    ui.label(tr!("preview-duplicates"));
+                    egui::ComboBox::from_id_source("preview_duplicates")
+                        .selected_text(pending.duplicates.label())
+                        .show_ui(ui, |ui| {
+                            for policy in DuplicatePolicy::ALL {
+                                ui.selectable_value(&mut pending.duplicates, policy, policy.label());
+                            }
+                        });
+                    ui.end_row();
+                });
+                if preview.format == ProductionFormat::Unrecognized {
+                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), tr!("preview-no-records"));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    load = ui.button(tr!("preview-load")).clicked();
+                    cancel = ui.button(tr!("button-cancel")).clicked();
+                });
+            });
+        
+        if load {
+            if let Some(pending) = self.pending_preview.take() {
+                self.settings.case_timezone = pending.timezone;
+                let config = PipelineConfig { duplicates: pending.duplicates, ..PipelineConfig::default() };
+                self.process_file_with(pending.path, config);
+            }
+        } else if cancel {
+            self.pending_preview = None;
+            self.start_queued_productions();
+        }
+    }
+    
+    /// Lets the user say which column holds each call record field of a delimited production,
+    /// starting from a preset, with the first rows shown as they would be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_column_mapping(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.pending_mapping else {
+            return;
+        };
+        let mut load = false;
+        let mut cancel = false;
+        egui::Window::new(tr!("column-mapping-title", name = pending.sample.source_file.as_str()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.small(tr!("column-mapping-hint"));
+                ui.add_space(5.0);
+                if let Some(choice) = &mut pending.sheet {
+                    let mut sheet_changed = false;
+                    let mut header_changed = false;
+                    egui::Grid::new("column_mapping_sheet").num_columns(2).show(ui, |ui| {
+                        ui.label(tr!("column-mapping-sheet"));
+                        let selected = choice.workbook.sheets.get(choice.sheet).map_or("", |(name, _)| name.as_str());
+                        egui::ComboBox::from_id_source("column_mapping_sheet")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for (index, (name, _)) in choice.workbook.sheets.iter().enumerate() {
+                                    sheet_changed |= ui.selectable_value(&mut choice.sheet, index, name).changed();
+                                }
+                            });
+                        ui.end_row();
+                        
+                        ui.label(tr!("column-mapping-header-row"));
+                        let rows = choice.workbook.sheets.get(choice.sheet).map_or(&[][..], |(_, rows)| rows.as_slice());
+                        // The row number as the sheet shows it, then its first few cells
+                        let row_label = |index: usize| {
+                            let cells = rows.get(index)
+                                .map(|row| row.iter().filter(|cell| !cell.is_empty()).take(4).collect::<Vec<_>>().join(", "))
+                                .unwrap_or_default();
+                            format!("{}. {}", index + 1, cells)
+                        };
+                        egui::ComboBox::from_id_source("column_mapping_header_row")
+                            .selected_text(row_label(choice.header_row))
+                            .width(300.0)
+                            .show_ui(ui, |ui| {
+                                for (index, row) in rows.iter().enumerate().take(HEADER_SEARCH_ROWS) {
+                                    if !is_blank(row) {
+                                        header_changed |= ui.selectable_value(&mut choice.header_row, index, row_label(index)).changed();
+                                    }
+                                }
+                            });
+                        ui.end_row();
+                    });
+                    if sheet_changed {
+                        choice.header_row = choice.workbook.header_row(choice.sheet).unwrap_or(0);
+                    }
+                    if sheet_changed || header_changed {
+                        if let Ok(sample) = choice.workbook.csv_sample(choice.sheet, choice.header_row) {
+                            let (preset, mapping) = sample.suggested_mapping();
+                            pending.sample = sample;
+                            pending.preset = Some(preset);
+                            pending.mapping = mapping;
+                        }
+                    }
+                    ui.add_space(5.0);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr!("column-mapping-preset"));
+                    let selected = pending.preset.map_or_else(|| tr!("column-mapping-custom"), |preset| preset.label());
+                    egui::ComboBox::from_id_source("column_mapping_preset")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for profile in MappingProfile::ALL {
+                                if ui.selectable_label(pending.preset == Some(profile), profile.label()).clicked() {
+                                    pending.preset = Some(profile);
+                                    pending.mapping = CsvMapping::from_profile(&pending.sample.headers, profile);
+                                }
+                            }
+                        });
+                });
+                ui.add_space(5.0);
+                
+                let headers = &pending.sample.headers;
+                let mut edited = false;
+                egui::Grid::new("column_mapping").num_columns(2).striped(true).show(ui, |ui| {
+                    for field in MappedField::ALL {
+                        ui.label(field.label());
+                        let column = pending.mapping.columns.column_mut(field);
+                        let selected = column
+                            .and_then(|index| headers.get(index))
+                            .map_or_else(|| tr!("column-mapping-none"), str::to_string);
+                        egui::ComboBox::from_id_source(("mapped_column", field))
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                edited |= ui.selectable_value(column, None, tr!("column-mapping-none")).changed();
+                                for (index, header) in headers.iter().enumerate() {
+                                    edited |= ui.selectable_value(column, Some(index), format!("{}. {}", index + 1, header)).changed();
+                                }
+                            });
+                        ui.end_row();
+                    }
+                    ui.label(tr!("column-mapping-dates"));
+                    edited |= ui.checkbox(&mut pending.mapping.day_first, tr!("column-mapping-day-first")).changed();
+                    ui.end_row();
+                    if !pending.reparse {
+                        ui.label(tr!("preview-timezone"));
+                        egui::ComboBox::from_id_source("column_mapping_timezone")
+                            .selected_text(pending.timezone.name())
+                            .show_ui(ui, |ui| {
+                                for tz in COMMON_TIMEZONES {
+                                    ui.selectable_value(&mut pending.timezone, tz, tz.name());
+                                }
+                            });
+                        ui.end_row();
+                        ui.label(tr!("preview-duplicates"));
+                        egui::ComboBox::from_id_source("column_mapping_duplicates")
+                            .selected_text(pending.duplicates.label())
+                            .show_ui(ui, |ui| {
+                                for policy in DuplicatePolicy::ALL {
+                                    ui.selectable_value(&mut pending.duplicates, policy, policy.label());
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+                if edited {
+                    pending.preset = None;
+                }
+                
+                ui.add_space(10.0);
+                ui.strong(tr!("column-mapping-sample"));
+                let complete = pending.mapping.is_complete();
+                if complete {
+                    egui::Grid::new("column_mapping_sample").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-target"));
+                        ui.strong(tr!("header-direction"));
+                        ui.strong(tr!("header-remote-number"));
+                        ui.strong(tr!("header-date-time"));
+                        ui.strong(tr!("header-duration-sec"));
+                        ui.end_row();
+                        for row in pending.sample.preview(&pending.mapping, pending.timezone) {
+                            match row {
+                                Ok(record) => {
+                                    ui.label(self.settings.format_number(&record.target_number));
+                                    ui.label(&record.message_direction);
+                                    ui.label(self.settings.format_number(&record.remote_number));
+                                    ui.label(self.settings.format_local_time(&record.start_time));
+                                    ui.label(record.length_of_call.to_string());
+                                }
+                                Err(e) => {
+                                    ui.colored_label(egui::Color32::from_rgb(200, 0, 0), tr!("column-mapping-row-skipped", reason = e));
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                } else {
+                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), tr!("column-mapping-incomplete"));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    load = ui.add_enabled(complete, egui::Button::new(tr!("preview-load"))).clicked();
+                    cancel = ui.button(tr!("button-cancel")).clicked();
+                });
+            });
+        
+        if load {
+            if let Some(pending) = self.pending_mapping.take() {
+                let parser = match &pending.sheet {
+                    Some(choice) => ParserKind::Spreadsheet(SheetMapping {
+                        sheet: choice.sheet,
+                        header_row: choice.header_row,
+                        mapping: pending.mapping,
+                    }),
+                    None => ParserKind::Csv(pending.mapping),
+                };
+                if pending.reparse {
+                    self.reparse_production(&pending.sample.source_file, parser);
+                } else {
+                    self.settings.case_timezone = pending.timezone;
+                    let config = PipelineConfig { duplicates: pending.duplicates, ..PipelineConfig::default() };
+                    self.process_files_with(vec![(pending.path, Some(parser))], config, false);
+                }
+            }
+        } else if cancel {
+            self.pending_mapping = None;
+            self.start_queued_productions();
+        }
+    }
+    
+    /// Shows the rows of a PDF that matched no pattern or read doubtfully, each next to the
+    /// line it came from, so they can be corrected, kept, or left out before loading.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_pdf_review(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &mut self.pending_pdf_review else {
+            return;
+        };
+        let mut load = false;
+        let mut cancel = false;
+        egui::Window::new(tr!("pdf-review-title", name = pending.extraction.source_file.as_str()))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(900.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.small(tr!("pdf-review-hint"));
+                ui.label(tr!(
+                    "pdf-review-summary",
+                    rows = pending.extraction.rows.len(),
+                    review = pending.extraction.review_count()
+                ));
+                if let Some(target) = &pending.extraction.target {
+                    ui.label(format!("{}: {}", tr!("header-target"), self.settings.format_number(target)));
+                }
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr!("preview-timezone"));
+                    egui::ComboBox::from_id_source("pdf_review_timezone")
+                        .selected_text(pending.timezone.name())
+                        .show_ui(ui, |ui| {
+                            for tz in COMMON_TIMEZONES {
+                                ui.selectable_value(&mut pending.timezone, tz, tz.name());
+                            }
+                        });
+                    ui.label(tr!("preview-duplicates"));
+                    egui::ComboBox::from_id_source("pdf_review_duplicates")
+                        .selected_text(pending.duplicates.label())
+                        .show_ui(ui, |ui| {
+                            for policy in DuplicatePolicy::ALL {
+                                ui.selectable_value(&mut pending.duplicates, policy, policy.label());
+                            }
+                        });
+                    ui.checkbox(&mut pending.show_all, tr!("pdf-review-show-all"));
+                });
+                ui.add_space(5.0);
+                
+                let show_all = pending.show_all;
+                egui::ScrollArea::both().max_height(420.0).show(ui, |ui| {
+                    egui::Grid::new("pdf_review").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("pdf-review-keep"));
+                        ui.strong(tr!("pdf-review-line"));
+                        ui.strong(tr!("header-direction"));
+                        ui.strong(tr!("header-remote-number"));
+                        ui.strong(tr!("header-date-time"));
+                        ui.strong(tr!("header-duration-sec"));
+                        ui.strong(tr!("pdf-review-problems"));
+                        ui.end_row();
+                        
+                        for row in pending.extraction.rows.iter_mut().filter(|row| show_all || row.needs_review()) {
+                            ui.checkbox(&mut row.keep, "");
+                            ui.label(egui::RichText::new(format!("{}: {}", row.line_number, row.line)).monospace().small());
+                            let mut edited = false;
+                            edited |= ui.add(egui::TextEdit::singleline(&mut row.direction).desired_width(80.0)).changed();
+                            edited |= ui.add(egui::TextEdit::singleline(&mut row.remote_number).desired_width(120.0)).changed();
+                            edited |= ui.add(egui::TextEdit::singleline(&mut row.start).desired_width(150.0)).changed();
+                            edited |= ui.add(egui::TextEdit::singleline(&mut row.duration).desired_width(60.0)).changed();
+                            if edited {
+                                row.check();
+                            }
+                            if row.pattern.is_none() {
+                                ui.weak(tr!("pdf-review-unmatched"));
+                            } else if row.problems.is_empty() {
+                                ui.colored_label(egui::Color32::from_rgb(0, 150, 0), "✔");
+                            } else {
+                                let problems = row.problems.iter().map(|problem| problem.label()).collect::<Vec<_>>().join("; ");
+                                ui.colored_label(egui::Color32::from_rgb(200, 120, 0), problems);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+                
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    load = ui.button(tr!("preview-load")).clicked();
+                    cancel = ui.button(tr!("button-cancel")).clicked();
+                });
+            });
+        
+        if load {
+            if let Some(pending) = self.pending_pdf_review.take() {
+                self.settings.case_timezone = pending.timezone;
+                self.load_pdf_extraction(pending.path, pending.extraction, pending.duplicates, true);
+            }
+        } else if cancel {
+            self.pending_pdf_review = None;
+            self.start_queued_productions();
+        }
+    }
+    
+    /// Walks an occasional user through starting a case one step at a time, so nothing is
+    /// parsed with the wrong timezone or duplicate handling.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_new_case_wizard(&mut self, ctx: &egui::Context) {
+        let Some(wizard) = &mut self.new_case_wizard else {
+            return;
+        };
+        let mut warnings = Vec::new();
+        let mut finish = false;
+        let mut cancel = false;
+        let step = wizard.step;
+        
+        // Productions dropped anywhere on the window while this step is shown are added here
+        if step == WizardStep::Productions {
+            let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+            for path in dropped.into_iter().filter_map(|file| file.path) {
+                if let Err(warning) = wizard.add_production(path) {
+                    warnings.push(warning);
+                }
+            }
+        }
+        
+        egui::Window::new(tr!("wizard-window-title", step = step.number(), steps = WizardStep::ALL.len(), title = step.title()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label(step.body());
+                ui.add_space(10.0);
+                match step {
+                    WizardStep::Details => {
+                        egui::Grid::new("wizard_details").num_columns(2).show(ui, |ui| {
+                            ui.label(tr!("wizard-case-name"));
+                            ui.text_edit_singleline(&mut wizard.case.name);
+                            ui.end_row();
+                            ui.label(tr!("wizard-case-number"));
+                            ui.text_edit_singleline(&mut wizard.case.number);
+                            ui.end_row();
+                            ui.label(tr!("wizard-agency"));
+                            ui.text_edit_singleline(&mut wizard.case.agency);
+                            ui.end_row();
+                            ui.label(tr!("wizard-analyst"));
+                            ui.text_edit_singleline(&mut wizard.case.analyst);
+                            ui.end_row();
+                        });
+                    }
+                    WizardStep::Defaults => {
+                        egui::Grid::new("wizard_defaults").num_columns(2).show(ui, |ui| {
+                            ui.label(tr!("settings-timezone"));
+                            egui::ComboBox::from_id_source("wizard_timezone")
+                                .selected_text(wizard.timezone.name())
+                                .show_ui(ui, |ui| {
+                                    for tz in COMMON_TIMEZONES {
+                                        ui.selectable_value(&mut wizard.timezone, tz, tz.name());
+                                    }
+                                });
+                            ui.end_row();
+                            ui.label(tr!("settings-night-hours"));
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("settings-night-from"));
+                                ui.add(egui::DragValue::new(&mut wizard.night_hours.0).clamp_range(0..=23).suffix(":00"));
+                                ui.label(tr!("settings-night-to"));
+                                ui.add(egui::DragValue::new(&mut wizard.night_hours.1).clamp_range(0..=23).suffix(":00"));
+                            });
+                            ui.end_row();
+                        });
+                    }
+                    WizardStep::Productions => {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut wizard.new_production)
+                                .hint_text(tr!("wizard-production-hint"))
+                                .desired_width(300.0));
+                            let has_path = !wizard.new_production.trim().is_empty();
+                            if ui.add_enabled(has_path, egui::Button::new(tr!("wizard-add-production"))).clicked() {
+                                let path = PathBuf::from(wizard.new_production.trim());
+                                match wizard.add_production(path) {
+                                    Ok(()) => wizard.new_production.clear(),
+                                    Err(warning) => warnings.push(warning),
+                                }
+                            }
+                        });
+                        let mut remove = None;
+                        for (index, path) in wizard.productions.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                if ui.small_button("✖").clicked() {
+                                    remove = Some(index);
+                                }
+                                ui.label(path.display().to_string());
+                            });
+                        }
+                        if let Some(index) = remove {
+                            wizard.productions.remove(index);
+                        }
+                        if wizard.productions.is_empty() {
+                            ui.weak(tr!("wizard-no-productions"));
+                        }
+                    }
+                    WizardStep::Duplicates => {
+                        for policy in DuplicatePolicy::ALL {
+                            ui.radio_value(&mut wizard.duplicates, policy, policy.label());
+                        }
+                        ui.add_space(10.0);
+                        ui.label(tr!("wizard-summary",
+                            name = wizard.case.name.trim(),
+                            timezone = wizard.timezone.name(),
+                            from = format!("{:02}:00", wizard.night_hours.0),
+                            to = format!("{:02}:00", wizard.night_hours.1),
+                            count = wizard.productions.len()));
+                    }
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if let Some(previous) = step.previous() {
+                        if ui.button(tr!("wizard-back")).clicked() {
+                            wizard.step = previous;
+                        }
+                    }
+                    let ready = wizard.can_advance();
+                    match step.next() {
+                        Some(next) => {
+                            if ui.add_enabled(ready, egui::Button::new(tr!("wizard-next"))).clicked() {
+                                wizard.step = next;
+                            }
+                        }
+                        None => finish = ui.add_enabled(ready, egui::Button::new(tr!("wizard-finish"))).clicked(),
+                    }
+                    cancel = ui.button(tr!("button-cancel")).clicked();
+                });
+            });
+        
+        for warning in warnings {
+            self.add_message(Message::Warning(warning));
+        }
+        if cancel {
+            self.new_case_wizard = None;
+        } else if finish {
+            if let Some(wizard) = self.new_case_wizard.take() {
+                self.new_case_wizard = self.finish_new_case_wizard(wizard);
+            }
+        }
+    }
+    
+    fn render_tutorial(&mut self, ctx: &egui::Context, step: TutorialStep) {
+        if let Some(rect) = step.target(&self.tutorial_anchors) {
+            tutorial::paint_highlight(ctx, rect);
+        }
+        
+        let mut next = None;
+        let mut finish = false;
+        egui::Window::new(tr!("tutorial-window-title", step = step.title()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-20.0, -20.0))
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                ui.label(step.body());
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    match step {
+                        TutorialStep::Welcome => {
+                            if ui.button(tr!("tutorial-start")).clicked() {
+                                next = Some(step.next());
+                            }
+                            if ui.button(tr!("tutorial-skip")).clicked() {
+                                finish = true;
+                            }
+                        }
+                        TutorialStep::Finished => {
+                            if ui.button(tr!("tutorial-done")).clicked() {
+                                finish = true;
+                            }
+                        }
+                        _ => {
+                            if ui.button(tr!("tutorial-next")).clicked() {
+                                next = Some(step.next());
+                            }
+                            if ui.button(tr!("tutorial-end")).clicked() {
+                                finish = true;
+                            }
+                        }
+                    }
+                });
+            });
+        
+        if finish {
+            self.end_tutorial();
+        } else if let Some(step) = next {
+            self.advance_tutorial(step);
+        }
+    }
+    
+    fn render_command_palette(&mut self, ctx: &egui::Context) {
+        let commands = self.available_commands();
+        let labels: Vec<&str> = commands.iter().map(|(label, _)| label.as_str()).collect();
+        let matches = self.command_palette.matches(&labels);
+        
+        let (up, down, enter, escape) = ctx.input(|i| (
+            i.key_pressed(egui::Key::ArrowUp),
+            i.key_pressed(egui::Key::ArrowDown),
+            i.key_pressed(egui::Key::Enter),
+            i.key_pressed(egui::Key::Escape),
+        ));
+        if escape {
+            self.command_palette.close();
+            return;
+        }
+        if down && self.command_palette.selected + 1 < matches.len() {
+            self.command_palette.selected += 1;
+        }
+        if up && self.command_palette.selected > 0 {
+            self.command_palette.selected -= 1;
+        }
+        self.command_palette.selected = self.command_palette.selected.min(matches.len().saturating_sub(1));
+        
+        let mut chosen = None;
+        if enter {
+            chosen = matches.get(self.command_palette.selected).map(|&i| commands[i].1);
+        }
+        
+        egui::Window::new(tr!("palette-title"))
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .fixed_size(egui::vec2(420.0, 320.0))
+            .show(ctx, |ui| {
+                let response = ui.add(egui::TextEdit::singleline(&mut self.command_palette.query)
+                    .hint_text(tr!("palette-hint"))
+                    .desired_width(f32::INFINITY));
+                response.request_focus();
+                if response.changed() {
+                    self.command_palette.selected = 0;
+                }
+                
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    for (row, &index) in matches.iter().enumerate() {
+                        let selected = row == self.command_palette.selected;
+                        let item = ui.selectable_label(selected, labels[index]);
+                        if selected && (up || down) {
+                            item.scroll_to_me(None);
+                        }
+                        if item.clicked() {
+                            chosen = Some(commands[index].1);
+                        }
+                    }
+                    if matches.is_empty() {
+                        ui.weak(tr!("palette-no-matches"));
+                    }
+                });
+            });
+        
+        if let Some(command) = chosen {
+            self.command_palette.close();
+            self.run_command(command);
+        }
+    }
+    
+    fn render_overview(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.heading(tr!("overview-welcome"));
+            ui.label(tr!("overview-intro"));
+            
+            #[cfg(not(target_arch = "wasm32"))]
+            ui.horizontal(|ui| {
+                if ui.button(format!("🧭 {}", tr!("overview-new-case")))
+                    .on_hover_text(tr!("overview-new-case-hint"))
+                    .clicked()
+                {
+                    self.start_new_case_wizard();
+                }
+                if ui.add_enabled(!self.call_records.is_empty(), egui::Button::new(format!("➕ {}", tr!("overview-add-productions"))))
+                    .on_hover_text(tr!("overview-add-productions-hint"))
+                    .clicked()
+                {
+                    self.add_productions_dialog();
+                }
+            });
+            let case = &self.annotations.case;
+            if !case.is_empty() {
+                let mut details = vec![case.name.clone()];
+                if !case.number.is_empty() {
+                    details.push(tr!("overview-case-number", number = case.number.as_str()));
+                }
+                if !case.agency.is_empty() {
+                    details.push(case.agency.clone());
+                }
+                if !case.analyst.is_empty() {
+                    details.push(tr!("overview-case-analyst", analyst = case.analyst.as_str()));
+                }
+                details.retain(|detail| !detail.is_empty());
+                ui.strong(details.join(" · "));
+            }
+            
+            ui.add_space(20.0);
+            
+            // Drag and drop area
+            let (response, painter) = ui.allocate_painter(
+                egui::vec2(ui.available_width(), 200.0),
+                egui::Sense::drag(),
+            );
+            
+            let rect = response.rect;
+            let is_hovered = response.hovered();
+            let is_dragged = response.dragged();
+            
+            // Update drag state
+            self.drag_state = if is_dragged {
+                DragState::Dropping
+            } else if is_hovered {
+                DragState::Hovering
+            } else {
+                DragState::None
+            };
+            
+            // Draw drop zone
+            let color = match self.drag_state {
+                DragState::None => egui::Color32::from_gray(50),
                 DragState::Hovering => egui::Color32::from_gray(70),
                 DragState::Dropping => egui::Color32::from_rgb(100, 150, 255),
             };
             
-            painter.rect_filled(rect, 5.0, color);
-            painter.rect_stroke(rect, 2.0, (2.0, egui::Color32::from_gray(100)));
+            painter.rect_filled(rect, 5.0, color);
+            painter.rect_stroke(rect, 2.0, (2.0, egui::Color32::from_gray(100)));
+            self.tutorial_anchors.drop_zone = Some(rect);
+            
+            // Draw text
+            let text = match self.drag_state {
+                DragState::None => format!("📁 {}", tr!("drop-zone-idle")),
+                DragState::Hovering => format!("📁 {}", tr!("drop-zone-hover")),
+                DragState::Dropping => format!("📁 {}", tr!("drop-zone-processing")),
+            };
+            
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                text,
+                egui::FontId::proportional(18.0),
+                egui::Color32::WHITE,
+            );
+            
+            // Handle file drops
+            let dropped_files = if response.contains_pointer() && !self.wizard_open() {
+                ui.ctx().input(|i| i.raw.dropped_files.clone())
+            } else {
+                Vec::new()
+            };
+            if !dropped_files.is_empty() {
+                let mut paths = Vec::new();
+                let mut contents = Vec::new();
+                for dropped_file in &dropped_files {
+                    if let Some(path) = &dropped_file.path {
+                        if is_production_file(&path.to_string_lossy()) {
+                            paths.push(path.clone());
+                        } else {
+                            self.add_message(Message::Warning(tr!("msg-xml-only")));
+                        }
+                    } else if let Some(bytes) = &dropped_file.bytes {
+                        // Browsers hand over file contents instead of a path
+                        if is_production_file(&dropped_file.name) {
+                            contents.push((dropped_file.name.clone(), bytes.to_vec()));
+                        } else {
+                            self.add_message(Message::Warning(tr!("msg-xml-only")));
+                        }
+                    }
+                }
+                // Files dropped together are loaded together, as one case
+                #[cfg(not(target_arch = "wasm32"))]
+                self.open_productions(paths);
+                self.process_bytes(contents);
+            }
+            
+            // Handle click to browse
+            if response.clicked() {
+                self.open_file_dialog();
+            }
+            
+            ui.add_space(20.0);
+            
+            // Processing status
+            match &self.processing_state {
+                ProcessingState::Idle => {
+                    ui.label(tr!("status-idle"));
+                }
+                ProcessingState::Processing => {
+                    ui.label(format!("⏳ {}", tr!("status-processing")));
+                    if let Some(job) = self.jobs.jobs().iter().find(|job| job.kind == JobKind::Parse && job.is_running()) {
+                        render_job_progress(ui, job);
+                    }
+                }
+                ProcessingState::ComputingAnalytics => {
+                    ui.label(format!("⏳ {}", tr!("progress-computing-analytics")));
+                }
+                ProcessingState::Completed => {
+                    ui.label(format!("✅ {}", tr!("status-completed")));
+                }
+                ProcessingState::Error(error) => {
+                    ui.label(format!("❌ {}", tr!("status-error", error = error)));
+                }
+            }
+            
+            // Statistics
+            if let Some(analytics) = &self.analytics {
+                ui.add_space(20.0);
+                ui.heading(tr!("overview-quick-stats"));
+                
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label(format!("{}: {}", tr!("stat-total-calls"), analytics.total_calls));
+                        ui.label(format!("{}: {}", tr!("stat-incoming"), analytics.incoming_calls));
+                        ui.label(format!("{}: {}", tr!("stat-outgoing"), analytics.outgoing_calls));
+                    });
+                    ui.vertical(|ui| {
+                        ui.label(format!("{}: {}", tr!("stat-unique-numbers"), analytics.unique_numbers));
+                        ui.label(format!("{}: {}", tr!("stat-unknown-numbers"), analytics.unknown_number_calls));
+                        if analytics.short_code_calls > 0 {
+                            ui.label(format!("{}: {}", tr!("stat-short-codes"), analytics.short_code_calls))
+                                .on_hover_text(tr!("stat-short-codes-hint"));
+                        }
+                        if analytics.sms_count + analytics.mms_count > 0 {
+                            ui.label(format!("{}: {}", tr!("stat-sms"), analytics.sms_count));
+                            ui.label(format!("{}: {}", tr!("stat-mms"), analytics.mms_count));
+                        }
+                        ui.label(format!("{}: {}", tr!("stat-total-duration"), tr!("value-minutes", minutes = format!("{:.1}", analytics.total_duration_minutes))));
+                        ui.label(format!("{}: {}", tr!("stat-avg-duration"), tr!("value-minutes", minutes = format!("{:.1}", analytics.average_call_duration))));
+                    });
+                });
+            }
+            
+            // Opening a case or workbook needs nothing loaded, so the row is always shown
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.add_space(20.0);
+                ui.heading(tr!("overview-case-file"));
+                let idle = !matches!(self.processing_state, ProcessingState::Processing);
+                let has_records = !self.call_records.is_empty();
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.case_file_path)
+                        .hint_text(tr!("overview-case-file-hint"))
+                        .desired_width(350.0));
+                    let has_path = !self.case_file_path.trim().is_empty();
+                    if ui.add_enabled(has_path && idle, egui::Button::new(tr!("overview-open-case")))
+                        .on_hover_text(tr!("overview-open-case-tooltip"))
+                        .clicked()
+                    {
+                        self.open_case();
+                    }
+                    if ui.add_enabled(has_path && has_records, egui::Button::new(tr!("overview-save-case"))).clicked() {
+                        self.save_case();
+                    }
+                    if ui.add_enabled(has_path && has_records, egui::Button::new(tr!("overview-compare-case"))).clicked() {
+                        self.compare_with_case();
+                    }
+                    if self.case_comparison.is_some() && ui.button(tr!("overview-show-comparison")).clicked() {
+                        self.open_detail_view(DetailView::CaseComparison);
+                    }
+                    if ui.add_enabled(has_path, egui::Button::new(tr!("overview-verify-evidence")))
+                        .on_hover_text(tr!("overview-verify-evidence-tooltip"))
+                        .clicked()
+                    {
+                        self.verify_evidence();
+                    }
+                });
+            }
+            
+            #[cfg(not(target_arch = "wasm32"))]
+            if !self.call_records.is_empty() {
+                ui.add_space(10.0);
+                ui.label(tr!("overview-subscribers", count = self.annotations.subscribers.len()));
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.subscriber_import_path)
+                        .hint_text(tr!("overview-subscribers-hint"))
+                        .desired_width(350.0));
+                    let has_path = !self.subscriber_import_path.trim().is_empty();
+                    if ui.add_enabled(has_path, egui::Button::new(tr!("overview-import-subscribers"))).clicked() {
+                        self.import_subscribers();
+                    }
+                });
+                
+                ui.add_space(10.0);
+                ui.label(tr!("overview-contacts", count = self.annotations.aliases.len()));
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.contact_import_path)
+                        .hint_text(tr!("overview-contacts-hint"))
+                        .desired_width(350.0));
+                    let has_path = !self.contact_import_path.trim().is_empty();
+                    if ui.add_enabled(has_path, egui::Button::new(tr!("overview-import-contacts"))).clicked() {
+                        self.import_contacts();
+                    }
+                    if !self.annotations.aliases.is_empty() && ui.button(tr!("overview-show-contact-matches")).clicked() {
+                        self.open_detail_view(DetailView::ContactMatches);
+                    }
+                });
+            }
+        });
+    }
+    
+    fn render_call_records(&mut self, ui: &mut egui::Ui) {
+        if self.call_records.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(tr!("records-empty"));
+            });
+            return;
+        }
+        
+        let filter_bar = ui.vertical(|ui| self.render_filter_bar(ui));
+        self.tutorial_anchors.filter_bar = Some(filter_bar.response.rect);
+        if !self.record_selection.keys.is_empty() {
+            self.render_selection_bar(ui);
+        }
+        ui.add_space(5.0);
+        
+        let filter_ctx = FilterContext {
+            annotations: &self.annotations,
+            night_hours: self.settings.night_hours,
+            timezone: self.settings.case_timezone,
+        };
+        let filtered: Vec<&ProcessedCallRecord> = self.call_records.iter()
+            .filter(|r| self.record_filter.matches(r, &filter_ctx))
+            .collect();
+        let filtered_count = filtered.len();
+        let fragment = self.record_filter.number_fragment();
+        
+        let mut export_clicked = false;
+        let mut reset_columns = false;
+        ui.horizontal(|ui| {
+            if self.record_filter.is_active() {
+                ui.label(tr!("records-showing-filtered", shown = filtered_count, total = self.call_records.len()));
+            } else {
+                ui.label(tr!("records-showing", total = self.call_records.len()));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr!("button-export-excel")).clicked() {
+                    export_clicked = true;
+                }
+                egui::ComboBox::from_id_source("case_timezone")
+                    .selected_text(self.settings.case_timezone.name())
+                    .show_ui(ui, |ui| {
+                        for tz in COMMON_TIMEZONES {
+                            ui.selectable_value(&mut self.settings.case_timezone, tz, tz.name());
+                        }
+                    });
+                ui.checkbox(&mut self.settings.show_local_time, tr!("records-show-local-time"));
+                if ui.button(tr!("records-reset-columns")).on_hover_text(tr!("records-reset-columns-hint")).clicked() {
+                    reset_columns = true;
+                }
+            });
+        });
+        if reset_columns {
+            self.settings.column_layout = ColumnLayout::default();
+        }
+        
+        let columns: Vec<RecordColumn> = self.settings.column_layout.ordered()
+            .into_iter()
+            .filter(|c| *c != RecordColumn::LocalTime || self.settings.show_local_time)
+            .filter(|c| *c != RecordColumn::Subscriber || !self.annotations.subscribers.is_empty())
+            .filter(|c| *c != RecordColumn::Carrier || !self.carriers.is_empty())
+            .filter(|c| *c != RecordColumn::CallerName || !self.caller_names.is_empty())
+            .filter(|c| *c != RecordColumn::Alias || !self.annotations.aliases.is_empty())
+            .filter(|c| *c != RecordColumn::Category || !self.known_numbers.is_empty())
+            .filter(|c| *c != RecordColumn::Message || has_message_content(&self.call_records))
+            .filter(|c| !matches!(c, RecordColumn::CellSite | RecordColumn::TowerLocation) || has_cell_sites(&self.call_records))
+            .filter(|c| *c != RecordColumn::Device || has_device_ids(&self.call_records))
+            .filter(|c| *c != RecordColumn::Tags || self.annotations.has_record_annotations())
+            .collect();
+        
+        let mut actions = Vec::new();
+        let mut layout_changes = Vec::new();
+        let mut selection_changes: Vec<(String, bool)> = Vec::new();
+        egui::ScrollArea::both().max_height(600.0).show(ui, |ui| {
+            egui::Grid::new("call_records").striped(true).show(ui, |ui| {
+                // Headers: drag to reorder, drag the right edge to resize
+                let shown: Vec<String> = filtered.iter().take(100).map(|r| r.record_key()).collect();
+                let mut all_selected = !shown.is_empty() && shown.iter().all(|key| self.record_selection.keys.contains(key));
+                if ui.checkbox(&mut all_selected, "").on_hover_text(tr!("records-select-shown-hint")).changed() {
+                    selection_changes.extend(shown.into_iter().map(|key| (key, all_selected)));
+                }
+                ui.label("");
+                for &column in &columns {
+                    let title = if column == RecordColumn::LocalTime {
+                        format!("{} ({})", column.label(), self.settings.case_timezone.name())
+                    } else {
+                        column.label()
+                    };
+                    let width = self.settings.column_layout.width(column);
+                    if let Some(change) = render_column_header(ui, column, &title, width) {
+                        layout_changes.push(change);
+                    }
+                }
+                ui.end_row();
+                
+                // Data (show first 100 matching records)
+                for record in filtered.iter().take(100) {
+                    let record_key = record.record_key();
+                    let on_watchlist = self.annotations.watchlist.contains(&record.normalized_number);
+                    let is_tagged = self.annotations.tagged_records.contains(&record_key);
+                    let is_bookmarked = self.annotations.bookmarks.contains(&record_key);
+                    
+                    let mut selected = self.record_selection.keys.contains(&record_key);
+                    if ui.checkbox(&mut selected, "").changed() {
+                        selection_changes.push((record_key.clone(), selected));
+                    }
+                    if bookmark_toggle(ui, is_bookmarked).clicked() {
+                        actions.push(RecordAction::ToggleBookmark(record_key.clone()));
+                    }
+                    
+                    for &column in &columns {
+                        let width = self.settings.column_layout.width(column);
+                        fixed_width_cell(ui, width, |ui| match column {
+                            RecordColumn::Direction => {
+                                ui.label(&record.message_direction);
+                            }
+                            RecordColumn::RemoteNumber => {
+                                let number_label = if on_watchlist {
+                                    format!("👁 {}", record.remote_number)
+                                } else {
+                                    record.remote_number.clone()
+                                };
+                                ui.label(highlight_number(ui, &number_label, fragment.as_deref())).context_menu(|ui| {
+                                    let watch_text = if on_watchlist { tr!("menu-remove-watchlist") } else { tr!("menu-add-watchlist") };
+                                    if ui.button(watch_text).clicked() {
+                                        actions.push(RecordAction::ToggleWatchlist(record.normalized_number.clone()));
+                                        ui.close_menu();
+                                    }
+                                    let tag_text = if is_tagged { tr!("menu-remove-tag") } else { tr!("menu-tag-record") };
+                                    if ui.button(tag_text).clicked() {
+                                        actions.push(RecordAction::ToggleTagged(record_key.clone()));
+                                        ui.close_menu();
+                                    }
+                                    ui.separator();
+                                    if ui.button(tr!("menu-record-details")).clicked() {
+                                        actions.push(RecordAction::OpenDetail(DetailView::Record(record_key.clone())));
+                                        ui.close_menu();
+                                    }
+                                    if ui.button(tr!("menu-contact-drilldown")).clicked() {
+                                        actions.push(RecordAction::OpenDetail(DetailView::Contact(record.normalized_number.clone())));
+                                        ui.close_menu();
+                                    }
+                                });
+                            }
+                            RecordColumn::NormalizedNumber => {
+                                ui.label(highlight_number(ui, &self.settings.format_number(&record.normalized_number), fragment.as_deref()));
+                            }
+                            RecordColumn::Date => {
+                                ui.label(&record.date);
+                            }
+                            RecordColumn::Time => {
+                                ui.label(&record.time);
+                            }
+                            RecordColumn::LocalTime => {
+                                ui.label(self.settings.format_local_time(&record.start_time));
+                            }
+                            RecordColumn::Duration => {
+                                ui.label(format!("{:.2}", record.duration_minutes));
+                            }
+                            RecordColumn::Subscriber => {
+                                if let Some(subscriber) = self.annotations.subscribers.lookup(&record.normalized_number) {
+                                    ui.label(&subscriber.name).on_hover_text(&subscriber.address);
+                                }
+                            }
+                            RecordColumn::Carrier => {
+                                if let Some(info) = self.carriers.lookup(&record.normalized_number) {
+                                    match info.lrn {
+                                        Some(lrn) => {
+                                            ui.label(tr!("carrier-ported", carrier = info.carrier))
+                                                .on_hover_text(tr!("carrier-lrn", lrn = self.settings.format_number(lrn)));
+                                        }
+                                        None => {
+                                            ui.label(info.carrier);
+                                        }
+                                    }
+                                }
+                            }
+                            RecordColumn::Region => {
+                                if let Some(region) = self.carriers.region(&record.normalized_number) {
+                                    ui.label(region);
+                                }
+                            }
+                            RecordColumn::CallerName => {
+                                if let Some(name) = self.caller_names.lookup(&record.normalized_number) {
+                                    ui.label(name);
+                                }
+                            }
+                            RecordColumn::Alias => {
+                                if let Some(alias) = self.annotations.alias(&record.normalized_number) {
+                                    ui.label(alias);
+                                }
+                            }
+                            RecordColumn::Category => {
+                                if let Some(categories) = self.known_numbers.label(&record.normalized_number) {
+                                    ui.colored_label(CATEGORY_COLOR, categories);
+                                }
+                            }
+                            RecordColumn::Message => {
+                                if let Some(content) = &record.message_content {
+                                    let ranges = keyword_ranges(content, &self.annotations.keywords);
+                                    ui.label(highlight_ranges(ui, content, ranges)).on_hover_text(content);
+                                }
+                            }
+                            RecordColumn::CellSite => {
+                                if let Some(site) = &record.cell_site {
+                                    ui.label(site.label());
+                                }
+                            }
+                            RecordColumn::TowerLocation => {
+                                if let Some(site) = &record.cell_site {
+                                    ui.label(site.location_label());
+                                }
+                            }
+                            RecordColumn::Device => {
+                                if let Some(device) = &record.device {
+                                    ui.label(device.label());
+                                }
+                            }
+                            RecordColumn::Tags => {
+                                let tags: Vec<&str> = self.annotations.tags(&record_key).collect();
+                                let mut text = if tags.is_empty() && is_tagged { "🏷".to_string() } else { tags.join(", ") };
+                                let note = self.annotations.note(&record_key);
+                                if note.is_some() {
+                                    text = format!("{} 📝", text).trim_start().to_string();
+                                }
+                                let response = ui.label(text);
+                                if let Some(note) = note {
+                                    response.on_hover_text(note);
+                                }
+                            }
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+            
+            if filtered_count > 100 {
+                ui.label(tr!("records-more", count = filtered_count - 100));
+            }
+        });
+        
+        let mut save_layout = reset_columns;
+        for change in layout_changes {
+            match change {
+                ColumnLayoutChange::Move { column, target } => {
+                    self.settings.column_layout.move_column(column, target);
+                    save_layout = true;
+                }
+                ColumnLayoutChange::Resize { column, delta } => self.settings.column_layout.resize(column, delta),
+                ColumnLayoutChange::ResizeDone => save_layout = true,
+            }
+        }
+        if save_layout {
+            if let Err(e) = self.settings.save() {
+                error!("Failed to save settings: {:#}", e);
+            }
+        }
+        for (record_key, selected) in selection_changes {
+            if selected {
+                self.record_selection.keys.insert(record_key);
+            } else {
+                self.record_selection.keys.remove(&record_key);
+            }
+        }
+        
+        for action in actions {
+            self.apply_record_action(action);
+        }
+        
+        if export_clicked {
+            self.export_to_excel();
+        }
+    }
+    
+    /// Tags and notes for the records ticked in the call records table.
+    fn render_selection_bar(&mut self, ui: &mut egui::Ui) {
+        let keys: Vec<String> = self.record_selection.keys.iter().cloned().collect();
+        let mut action = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.strong(tr!("selection-count", count = keys.len()));
+            ui.label(tr!("selection-tag"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.record_selection.tag)
+                .hint_text(tr!("selection-tag-hint"))
+                .desired_width(140.0));
+            let typed = self.record_selection.tag.trim().to_string();
+            let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.add_enabled(!typed.is_empty(), egui::Button::new(tr!("selection-add-tag"))).clicked() || (entered && !typed.is_empty()) {
+                action = Some(RecordAction::AddTag(keys.clone(), typed));
+                self.record_selection.tag.clear();
+            }
+            // One click for the usual tags and the ones the case already uses
+            let mut quick_tags: Vec<String> = suggested_tags().into();
+            for label in self.annotations.tag_labels() {
+                if !quick_tags.iter().any(|tag| tag == label) {
+                    quick_tags.push(label.to_string());
+                }
+            }
+            for label in quick_tags {
+                if ui.small_button(format!("🏷 {}", label)).clicked() {
+                    action = Some(RecordAction::AddTag(keys.clone(), label));
+                }
+            }
+            let on_selection: BTreeSet<&str> = keys.iter().flat_map(|key| self.annotations.tags(key)).collect();
+            if !on_selection.is_empty() {
+                ui.menu_button(tr!("selection-remove-tag"), |ui| {
+                    for label in on_selection {
+                        if ui.button(label).clicked() {
+                            action = Some(RecordAction::RemoveTag(keys.clone(), label.to_string()));
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("selection-note"));
+            ui.add(egui::TextEdit::singleline(&mut self.record_selection.note)
+                .hint_text(tr!("selection-note-hint"))
+                .desired_width(320.0));
+            if ui.button(tr!("selection-set-note")).clicked() {
+                action = Some(RecordAction::SetNote(keys.clone(), std::mem::take(&mut self.record_selection.note)));
+            }
+            if ui.button(tr!("selection-clear")).clicked() {
+                self.record_selection = RecordSelection::default();
+            }
+        });
+        if let Some(action) = action {
+            self.apply_record_action(action);
+        }
+    }
+    
+    fn render_filter_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("filter-label"));
+            let hint = if self.record_filter.regex_mode { tr!("filter-hint-regex") } else { tr!("filter-hint-text") };
+            let text_changed = ui.add(egui::TextEdit::singleline(&mut self.record_filter.text)
+                .hint_text(hint)
+                .desired_width(200.0))
+                .changed();
+            let mode_changed = ui.checkbox(&mut self.record_filter.regex_mode, tr!("filter-regex"))
+                .on_hover_text(tr!("filter-regex-hint"))
+                .changed();
+            if text_changed || mode_changed {
+                self.record_filter.refresh_regex();
+            }
+            ui.label(tr!("filter-from"));
+            ui.add(egui::TextEdit::singleline(&mut self.record_filter.date_from)
+                .hint_text("YYYY-MM-DD")
+                .desired_width(90.0));
+            ui.label(tr!("filter-to"));
+            ui.add(egui::TextEdit::singleline(&mut self.record_filter.date_to)
+                .hint_text("YYYY-MM-DD")
+                .desired_width(90.0));
+            if ui.add_enabled(self.record_filter.is_active(), egui::Button::new(tr!("filter-clear"))).clicked() {
+                self.record_filter.clear();
+            }
+        });
+        
+        if let Some(error) = self.record_filter.regex_error() {
+            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), tr!("filter-invalid-regex", error = error.to_string()));
+        }
+        
+        ui.horizontal_wrapped(|ui| {
+            for chip in QuickFilter::ALL {
+                let selected = self.record_filter.chips.contains(&chip);
+                // Keyword hits mean nothing until the case has keywords
+                if chip == QuickFilter::KeywordHits && self.annotations.keywords.is_empty() && !selected {
+                    continue;
+                }
+                if ui.selectable_label(selected, chip.label()).clicked() {
+                    self.record_filter.toggle_chip(chip);
+                }
+            }
+            if let Some(selection) = self.record_filter.chart_selection {
+                if ui.selectable_label(true, format!("{} ✖", selection.label())).on_hover_text(tr!("chip-chart-clear-hint")).clicked() {
+                    self.record_filter.chart_selection = None;
+                }
+            }
+            let labels = self.annotations.tag_labels();
+            if !labels.is_empty() || self.record_filter.tag.is_some() {
+                ui.label(tr!("filter-tag"));
+                egui::ComboBox::from_id_source("filter_tag")
+                    .selected_text(self.record_filter.tag.clone().unwrap_or_else(|| tr!("filter-any-tag")))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.record_filter.tag, None, tr!("filter-any-tag"));
+                        for label in labels {
+                            ui.selectable_value(&mut self.record_filter.tag, Some(label.to_string()), label);
+                        }
+                    });
+            }
+        });
+    }
+    
+    fn apply_record_action(&mut self, action: RecordAction) {
+        let before = self.annotations.clone();
+        let label = match action {
+            RecordAction::ToggleWatchlist(number) => {
+                self.annotations.toggle_watchlist(&number);
+                tr!("change-watchlist", number = self.settings.format_number(&number))
+            }
+            RecordAction::ToggleTagged(record_key) => {
+                self.annotations.toggle_tagged(&record_key);
+                tr!("change-tag")
+            }
+            RecordAction::AddTag(record_keys, label) => {
+                for record_key in &record_keys {
+                    self.annotations.add_tag(record_key, &label);
+                }
+                tr!("change-tag")
+            }
+            RecordAction::RemoveTag(record_keys, label) => {
+                for record_key in &record_keys {
+                    self.annotations.remove_tag(record_key, &label);
+                }
+                tr!("change-tag")
+            }
+            RecordAction::SetNote(record_keys, note) => {
+                for record_key in &record_keys {
+                    self.annotations.set_note(record_key, &note);
+                }
+                tr!("change-note")
+            }
+            RecordAction::ToggleBookmark(record_key) => {
+                self.annotations.toggle_bookmark(&record_key);
+                tr!("change-bookmark")
+            }
+            RecordAction::SetAlias(number, name) => {
+                if self.annotations.alias(&number) == Some(name.trim()) {
+                    return;
+                }
+                self.annotations.set_alias(&number, &name);
+                tr!("change-alias", number = self.settings.format_number(&number))
+            }
+            RecordAction::OpenDetail(view) => {
+                self.open_detail_view(view);
+                return;
+            }
+        };
+        self.annotations_changed(label, before);
+    }
+    
+    fn render_analytics(&mut self, ui: &mut egui::Ui) {
+        let mut drilldown = None;
+        let mut toggled_target = None;
+        let mut span_sort = self.contact_span_sort;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut lookup_names = false;
+        if let Some(analytics) = &self.analytics {
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(tr!("analytics-heading"));
+                    if ui.button(format!("📈 {}", tr!("analytics-timeline"))).clicked() {
+                        drilldown = Some(DetailView::Timeline);
+                    }
+                    if ui.button(format!("📊 {}", tr!("analytics-call-durations"))).clicked() {
+                        drilldown = Some(DetailView::CallDurations);
+                    }
+                    if ui.button(format!("📈 {}", tr!("analytics-cumulative"))).clicked() {
+                        drilldown = Some(DetailView::CumulativeCalls);
+                    }
+                });
+                
+                // Summary cards
+                ui.horizontal(|ui| {
+                    self.render_stat_card(ui, &tr!("stat-total-calls"), &analytics.total_calls.to_string(), "📞");
+                    self.render_stat_card(ui, &tr!("stat-incoming"), &analytics.incoming_calls.to_string(), "📥");
+                    self.render_stat_card(ui, &tr!("stat-outgoing"), &analytics.outgoing_calls.to_string(), "📤");
+                    self.render_stat_card(ui, &tr!("stat-unique-numbers"), &analytics.unique_numbers.to_string(), "👥");
+                });
+                
+                ui.horizontal(|ui| {
+                    self.render_stat_card(ui, &tr!("stat-total-duration"), &tr!("value-minutes", minutes = format!("{:.1}", analytics.total_duration_minutes)), "⏱️");
+                    self.render_stat_card(ui, &tr!("stat-avg-duration"), &tr!("value-minutes", minutes = format!("{:.1}", analytics.average_call_duration)), "📊");
+                    self.render_stat_card(ui, &tr!("stat-unknown-numbers"), &analytics.unknown_number_calls.to_string(), "🚫");
+                    if analytics.short_code_calls > 0 {
+                        self.render_stat_card(ui, &tr!("stat-short-codes"), &analytics.short_code_calls.to_string(), "🔢");
+                    }
+                });
+                
+                if analytics.sms_count + analytics.mms_count > 0 {
+                    ui.horizontal(|ui| {
+                        self.render_stat_card(ui, &tr!("stat-sms"), &analytics.sms_count.to_string(), "💬");
+                        self.render_stat_card(ui, &tr!("stat-mms"), &analytics.mms_count.to_string(), "🖼");
+                    });
+                }
+                
+                if !analytics.calls_by_disposition.is_empty() {
+                    ui.horizontal(|ui| {
+                        for (disposition, count) in &analytics.calls_by_disposition {
+                            let icon = match disposition {
+                                CallDisposition::Missed => "📵",
+                                CallDisposition::Busy => "⛔",
+                                CallDisposition::Voicemail => "📼",
+                                CallDisposition::Forwarded => "↪",
+                                CallDisposition::Roaming => "🌐",
+                                CallDisposition::Completed => "📞",
+                            };
+                            self.render_stat_card(ui, &disposition.label(), &count.to_string(), icon);
+                        }
+                    });
+                }
+                
+                if self.loaded_targets.len() > 1 {
+                    ui.add_space(10.0);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(tr!("analytics-targets"));
+                        let recomputing = self.jobs.is_running(JobKind::Analytics);
+                        for target in &self.loaded_targets {
+                            let mut included = !self.excluded_targets.contains(target);
+                            let checkbox = ui.add_enabled(!recomputing, egui::Checkbox::new(&mut included, self.settings.format_number(target)))
+                                .on_hover_text(tr!("analytics-targets-hint"));
+                            if checkbox.changed() {
+                                toggled_target = Some(target.clone());
+                            }
+                        }
+                    });
+                }
+                
+                ui.add_space(20.0);
+                
+                // Most frequent numbers
+                let fragment = self.record_filter.number_fragment();
+                ui.horizontal(|ui| {
+                    ui.heading(tr!("heading-most-frequent"));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if self.settings.cnam_lookup_enabled {
+                        let running = self.jobs.is_running(JobKind::Lookup);
+                        if ui.add_enabled(!running, egui::Button::new(tr!("analytics-cnam-lookup")))
+                            .on_hover_text(tr!("analytics-cnam-lookup-hint", limit = CNAM_LOOKUP_LIMIT))
+                            .clicked()
+                        {
+                            lookup_names = true;
+                        }
+                    }
+                });
+                let show_names = !self.caller_names.is_empty();
+                let show_aliases = !self.annotations.aliases.is_empty();
+                let show_categories = !self.known_numbers.is_empty();
+                let show_entities = !self.annotations.entities.is_empty();
+                egui::Grid::new("frequent_numbers").striped(true).show(ui, |ui| {
+                    ui.strong(tr!("header-rank"));
+                    ui.strong(tr!("header-phone-number"));
+                    if show_names {
+                        ui.strong(tr!("header-caller-name"));
+                    }
+                    if show_aliases {
+                        ui.strong(tr!("header-alias"));
+                    }
+                    if show_categories {
+                        ui.strong(tr!("header-category"));
+                    }
+                    if show_entities {
+                        ui.strong(tr!("header-entity"));
+                    }
+                    ui.strong(tr!("header-call-count"));
+                    ui.end_row();
+                    
+                    for (i, (number, count)) in analytics.most_frequent_numbers.iter().enumerate() {
+                        ui.label(format!("{}", i + 1));
+                        let number_label = ui.add(egui::Label::new(highlight_number(ui, &self.settings.format_number(number), fragment.as_deref()))
+                            .sense(egui::Sense::click()))
+                            .on_hover_text(tr!("analytics-drilldown-hint"));
+                        if number_label.clicked() {
+                            drilldown = Some(DetailView::Contact(number.clone()));
+                        }
+                        if show_names {
+                            ui.label(self.caller_names.lookup(number).unwrap_or_default());
+                        }
+                        if show_aliases {
+                            match self.annotations.alias(number) {
+                                Some(alias) => ui.label(alias),
+                                // Flag the busy numbers no source could name yet
+                                None if !self.is_identified(number) => ui.colored_label(egui::Color32::from_rgb(200, 120, 0), "❓")
+                                    .on_hover_text(tr!("analytics-unidentified-hint")),
+                                None => ui.label(""),
+                            };
+                        }
+                        if show_categories {
+                            ui.colored_label(CATEGORY_COLOR, self.known_numbers.label(number).unwrap_or_default());
+                        }
+                        if show_entities {
+                            ui.label(self.annotations.entities.entity_of(number).unwrap_or_default());
+                        }
+                        ui.label(count.to_string());
+                        ui.end_row();
+                    }
+                });
+                
+                let category_counts = self.known_numbers.category_counts(&self.call_records);
+                if !category_counts.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-known-numbers"));
+                    egui::Grid::new("known_number_categories").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-category"));
+                        ui.strong(tr!("header-numbers"));
+                        ui.strong(tr!("header-call-count"));
+                        ui.end_row();
+                        
+                        for count in &category_counts {
+                            ui.colored_label(CATEGORY_COLOR, &count.category);
+                            let numbers = count.numbers.iter()
+                                .map(|(number, calls)| format!("{} ({})", self.settings.format_number(number), calls))
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            ui.label(count.numbers.len().to_string()).on_hover_text(numbers);
+                            ui.label(count.calls.to_string());
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.exchange_clusters.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-exchange-clusters"));
+                    ui.small(tr!("analytics-exchange-clusters-hint"));
+                    egui::Grid::new("exchange_clusters").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-prefix"));
+                        ui.strong(tr!("header-numbers"));
+                        ui.strong(tr!("header-call-count"));
+                        ui.end_row();
+                        
+                        for cluster in &analytics.exchange_clusters {
+                            ui.label(format_prefix(&cluster.prefix));
+                            let numbers = cluster.numbers.iter()
+                                .map(|(number, _)| self.settings.format_number(number))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            ui.label(cluster.numbers.len().to_string()).on_hover_text(numbers);
+                            ui.label(cluster.total_calls.to_string());
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.calls_by_region.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-calls-by-region"));
+                    ui.small(tr!("analytics-calls-by-region-hint"));
+                    if let Some(concentration) = geographic_concentration(analytics) {
+                        ui.label(concentration);
+                    }
+                    let located: usize = analytics.calls_by_region.iter().map(|(_, calls)| calls).sum();
+                    egui::Grid::new("calls_by_region").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-region"));
+                        ui.strong(tr!("header-call-count"));
+                        ui.strong(tr!("header-share"));
+                        ui.end_row();
+                        
+                        for (region, calls) in &analytics.calls_by_region {
+                            ui.label(region_label(region));
+                            ui.label(calls.to_string());
+                            ui.label(format!("{:.1}%", *calls as f64 * 100.0 / located as f64));
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.area_code_activity.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-area-codes"));
+                    ui.small(tr!("analytics-area-codes-hint"));
+                    let nanp_calls: usize = analytics.area_code_activity.iter().map(|activity| activity.calls).sum();
+                    egui::ScrollArea::vertical().id_source("area_codes").max_height(300.0).show(ui, |ui| {
+                        egui::Grid::new("area_codes").striped(true).show(ui, |ui| {
+                            ui.strong(tr!("header-area-code"));
+                            ui.strong(tr!("header-region"));
+                            ui.strong(tr!("header-numbers"));
+                            ui.strong(tr!("header-call-count"));
+                            ui.strong(tr!("header-share"));
+                            ui.end_row();
+                            
+                            for activity in &analytics.area_code_activity {
+                                ui.label(&activity.area_code);
+                                ui.label(activity.region.as_deref().map_or_else(|| tr!("area-code-non-geographic"), region_label));
+                                ui.label(activity.numbers.to_string());
+                                ui.label(activity.calls.to_string());
+                                ui.label(format!("{:.1}%", activity.calls as f64 * 100.0 / nanp_calls as f64));
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+                
+                if !analytics.answer_rates.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-answer-rates"));
+                    ui.small(tr!("analytics-answer-rates-hint",
+                        attempts = MIN_ANSWER_RATE_ATTEMPTS,
+                        seconds = self.settings.unanswered_max_seconds,
+                        streak = STOPPED_ANSWERING_STREAK));
+                    let timezone = analytics.timezone;
+                    egui::Grid::new("answer_rates").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-phone-number"));
+                        ui.strong(tr!("header-attempts"));
+                        ui.strong(tr!("header-answer-rate"));
+                        ui.strong(tr!("header-longest-unanswered"));
+                        ui.strong(tr!("header-unanswered-since"));
+                        ui.strong(tr!("header-missed-incoming"));
+                        ui.end_row();
+                        
+                        for rate in analytics.answer_rates.iter().take(ANSWER_RATES_SHOWN) {
+                            let number = self.settings.format_number(&rate.number);
+                            let text = if rate.stopped_answering() {
+                                egui::RichText::new(format!("⚠ {}", number)).color(egui::Color32::from_rgb(200, 120, 0))
+                            } else {
+                                egui::RichText::new(number)
+                            };
+                            let hint = if rate.stopped_answering() { tr!("analytics-stopped-answering-hint") } else { tr!("analytics-drilldown-hint") };
+                            if ui.add(egui::Label::new(text).sense(egui::Sense::click())).on_hover_text(hint).clicked() {
+                                drilldown = Some(DetailView::Contact(rate.number.clone()));
+                            }
+                            ui.label(tr!("analytics-answered-of", answered = rate.answered, attempts = rate.attempts));
+                            ui.label(format!("{:.0}%", rate.answer_rate()));
+                            ui.label(rate.longest_unanswered_streak.to_string());
+                            let last_answered = rate.last_answered
+                                .map(|time| time.with_timezone(&timezone).format("%Y-%m-%d").to_string())
+                                .unwrap_or_else(|| tr!("analytics-never-answered"));
+                            ui.label(tr!("analytics-unanswered-since", count = rate.trailing_unanswered, date = last_answered));
+                            ui.label(format!("{} / {}", rate.incoming_unanswered, rate.incoming));
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.odd_hours_contacts.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-odd-hours"));
+                    ui.small(tr!("analytics-odd-hours-hint",
+                        from = format!("{:02}:00", self.settings.night_hours.0),
+                        to = format!("{:02}:00", self.settings.night_hours.1),
+                        timezone = analytics.timezone.name()));
+                    egui::Grid::new("odd_hours").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-phone-number"));
+                        ui.strong(tr!("header-call-count"));
+                        ui.strong(tr!("header-night-calls"));
+                        ui.strong(tr!("header-night-share"));
+                        ui.strong(tr!("header-odd-hours-score"));
+                        ui.end_row();
+                        
+                        for contact in analytics.odd_hours_contacts.iter().take(ODD_HOURS_SHOWN) {
+                            let label = ui.add(egui::Label::new(self.settings.format_number(&contact.number)).sense(egui::Sense::click()))
+                                .on_hover_text(tr!("analytics-drilldown-hint"));
+                            if label.clicked() {
+                                drilldown = Some(DetailView::Contact(contact.number.clone()));
+                            }
+                            ui.label(contact.calls.to_string());
+                            ui.label(contact.night_calls.to_string());
+                            ui.label(format!("{:.0}%", contact.night_share()));
+                            ui.label(format!("{:.2}", contact.odd_hours_score()));
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.contact_spans.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-contact-spans"));
+                    ui.small(tr!("analytics-contact-spans-hint", days = ONGOING_CONTACT_DAYS));
+                    let (sort, descending) = span_sort;
+                    let mut spans: Vec<&ContactSpan> = analytics.contact_spans.iter().collect();
+                    spans.sort_by(|a, b| {
+                        let order = match sort {
+                            ContactSpanSort::Number => a.number.cmp(&b.number),
+                            ContactSpanSort::FirstContact => a.first_contact.cmp(&b.first_contact),
+                            ContactSpanSort::LastContact => a.last_contact.cmp(&b.last_contact),
+                            ContactSpanSort::Span => a.span().cmp(&b.span()),
+                            ContactSpanSort::Calls => a.calls.cmp(&b.calls),
+                        };
+                        if descending { order.reverse() } else { order }
+                    });
+                    egui::ScrollArea::vertical().id_source("contact_spans").max_height(300.0).show(ui, |ui| {
+                        egui::Grid::new("contact_spans").striped(true).show(ui, |ui| {
+                            // Click a header to sort by it, again to reverse
+                            for (column, title) in [
+                                (ContactSpanSort::Number, tr!("header-phone-number")),
+                                (ContactSpanSort::FirstContact, tr!("header-first-contact")),
+                                (ContactSpanSort::LastContact, tr!("header-last-contact")),
+                                (ContactSpanSort::Span, tr!("header-span-days")),
+                                (ContactSpanSort::Calls, tr!("header-call-count")),
+                            ] {
+                                let arrow = match (column == sort, descending) {
+                                    (true, false) => " ⬆",
+                                    (true, true) => " ⬇",
+                                    (false, _) => "",
+                                };
+                                let header = egui::Label::new(egui::RichText::new(format!("{}{}", title, arrow)).strong())
+                                    .sense(egui::Sense::click());
+                                if ui.add(header).on_hover_text(tr!("analytics-sort-hint")).clicked() {
+                                    span_sort = (column, column == sort && !descending);
+                                }
+                            }
+                            ui.strong(tr!("header-ongoing"));
+                            ui.end_row();
+                            
+                            for span in spans {
+                                let label = ui.add(egui::Label::new(self.settings.format_number(&span.number)).sense(egui::Sense::click()))
+                                    .on_hover_text(tr!("analytics-drilldown-hint"));
+                                if label.clicked() {
+                                    drilldown = Some(DetailView::Contact(span.number.clone()));
+                                }
+                                ui.label(self.settings.format_local_time(&span.first_contact));
+                                ui.label(self.settings.format_local_time(&span.last_contact));
+                                ui.label(format!("{:.1}", span.span_days()));
+                                ui.label(span.calls.to_string());
+                                ui.label(if span.ongoing { tr!("value-yes") } else { String::new() });
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+                
+                if !analytics.messages_per_contact.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-messages-per-contact"));
+                    egui::Grid::new("messages_per_contact").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-phone-number"));
+                        ui.strong(tr!("stat-sms"));
+                        ui.strong(tr!("stat-mms"));
+                        ui.strong(tr!("header-messages-sent"));
+                        ui.strong(tr!("header-messages-received"));
+                        ui.end_row();
+                        
+                        for contact in &analytics.messages_per_contact {
+                            let label = ui.add(egui::Label::new(self.settings.format_number(&contact.number)).sense(egui::Sense::click()))
+                                .on_hover_text(tr!("analytics-drilldown-hint"));
+                            if label.clicked() {
+                                drilldown = Some(DetailView::Contact(contact.number.clone()));
+                            }
+                            ui.label(contact.sms.to_string());
+                            ui.label(contact.mms.to_string());
+                            ui.label(contact.sent.to_string());
+                            ui.label(contact.received.to_string());
+                            ui.end_row();
+                        }
+                    });
+                }
+                
+                if !analytics.device_changes.is_empty() {
+                    ui.add_space(20.0);
+                    ui.heading(tr!("heading-device-changes"));
+                    ui.small(tr!("analytics-device-changes-hint"));
+                    let day = |time: &chrono::DateTime<chrono::Utc>| time.with_timezone(&analytics.timezone).format("%Y-%m-%d").to_string();
+                    egui::Grid::new("device_changes").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-device-subject"));
+                        ui.strong(tr!("header-device-id-kind"));
+                        ui.strong(tr!("header-device-used-with"));
+                        ui.strong(tr!("header-call-count"));
+                        ui.strong(tr!("header-first-seen"));
+                        ui.strong(tr!("header-last-seen"));
+                        ui.end_row();
+                        
+                        for change in &analytics.device_changes {
+                            for (index, span) in change.spans.iter().enumerate() {
+                                let (subject, used_with) = match change.change {
+                                    DeviceChangeKind::NumberOnDevices => (self.settings.format_number(&change.subject), span.value.clone()),
+                                    DeviceChangeKind::DeviceOnNumbers => (change.subject.clone(), self.settings.format_number(&span.value)),
+                                };
+                                if index == 0 {
+                                    ui.label(subject);
+                                    ui.label(change.id_kind.label());
+                                } else {
+                                    ui.label("");
+                                    ui.label("");
+                                }
+                                ui.label(used_with);
+                                ui.label(span.calls.to_string());
+                                ui.label(day(&span.first));
+                                ui.label(day(&span.last));
+                                ui.end_row();
+                            }
+                        }
+                    });
+                }
+                
+                ui.add_space(20.0);
+                
+                // Calls by day
+                ui.heading(tr!("heading-calls-by-day", timezone = analytics.timezone.name()));
+                let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
+                sorted_days.sort_by(|a, b| a.0.cmp(b.0));
+                
+                egui::Grid::new("calls_by_day").striped(true).show(ui, |ui| {
+                    ui.strong(tr!("header-date"));
+                    ui.strong(tr!("header-call-count"));
+                    ui.end_row();
+                    
+                    for (day, count) in sorted_days {
+                        ui.label(day);
+                        ui.label(count.to_string());
+                        ui.end_row();
+                    }
+                });
+            });
+        } else {
+            ui.centered_and_justified(|ui| {
+                if matches!(self.processing_state, ProcessingState::ComputingAnalytics) {
+                    ui.label(format!("⏳ {}", tr!("progress-computing-analytics")));
+                } else {
+                    ui.label(tr!("analytics-empty"));
+                }
+            });
+        }
+        
+        if self.analytics.is_some() {
+            ui.add_space(20.0);
+            self.render_entities(ui);
+        }
+        
+        if self.analytics.is_some() && has_message_content(&self.call_records) {
+            ui.add_space(20.0);
+            self.render_message_keywords(ui);
+        }
+        
+        if self.analytics.is_some() && has_cell_sites(&self.call_records) {
+            ui.add_space(20.0);
+            self.render_location_analysis(ui);
+            ui.add_space(10.0);
+            self.render_geofence(ui);
+        }
+        
+        self.contact_span_sort = span_sort;
+        if let Some(view) = drilldown {
+            self.open_detail_view(view);
+        }
+        if let Some(target) = toggled_target {
+            self.toggle_target(&target);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if lookup_names {
+            self.start_cnam_lookup();
+        }
+    }
+    
+    /// Keywords searched for in message content, for productions that include it.
+    fn render_message_keywords(&mut self, ui: &mut egui::Ui) {
+        let mut apply = false;
+        ui.heading(tr!("heading-message-keywords"));
+        ui.small(tr!("keywords-hint"));
+        ui.horizontal(|ui| {
+            let mut text = self.keyword_input.clone().unwrap_or_else(|| self.annotations.keywords.join(", "));
+            let response = ui.add(egui::TextEdit::singleline(&mut text)
+                .hint_text(tr!("keywords-input-hint"))
+                .desired_width(350.0));
+            if response.changed() {
+                self.keyword_input = Some(text);
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                apply = true;
+            }
+            if ui.add_enabled(self.keyword_input.is_some(), egui::Button::new(tr!("keywords-apply"))).clicked() {
+                apply = true;
+            }
+            if ui.add_enabled(!self.annotations.keywords.is_empty(), egui::Button::new(tr!("keywords-show-hits"))).clicked() {
+                self.open_detail_view(DetailView::KeywordHits);
+            }
+        });
+        
+        if apply {
+            if let Some(text) = self.keyword_input.take() {
+                let keywords = parse_keywords(&text);
+                if keywords != self.annotations.keywords {
+                    let before = self.annotations.clone();
+                    self.annotations.keywords = keywords;
+                    self.annotations_changed(tr!("change-keywords"), before);
+                }
+            }
+        }
+    }
+    
+    /// The towers that served each target's calls by day, for productions with tower
+    /// locations.
+    fn render_location_analysis(&mut self, ui: &mut egui::Ui) {
+        let timezone = self.settings.case_timezone;
+        let summary = LocationSummary::build(&self.call_records, timezone);
+        
+        ui.heading(tr!("heading-location-analysis"));
+        ui.small(tr!("location-hint", hours = DWELL_BREAK_HOURS));
+        ui.label(tr!(
+            "location-summary",
+            towers = summary.tower_count(),
+            dwells = summary.dwells.len(),
+            transitions = summary.transitions.len(),
+            days = summary.days.len()
+        ));
+        if !summary.implausible.is_empty() {
+            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), tr!(
+                "location-implausible",
+                count = summary.implausible.len(),
+                speed = IMPLAUSIBLE_SPEED_KMH
+            ));
+        }
+        if summary.days.is_empty() {
+            return;
+        }
+        
+        ui.label(tr!("location-days-caption", timezone = timezone.name()));
+        egui::Grid::new("tower_days").striped(true).show(ui, |ui| {
+            ui.strong(tr!("header-date"));
+            ui.strong(tr!("header-target-number"));
+            ui.strong(tr!("header-busiest-tower"));
+            ui.strong(tr!("header-cell-site-id"));
+            ui.strong(tr!("header-call-count"));
+            ui.strong(tr!("header-towers"));
+            ui.strong(tr!("header-tower-changes"));
+            ui.end_row();
+            let mut days: Vec<_> = summary.days.iter().collect();
+            days.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.target_number.cmp(&b.target_number)));
+            for day in days.into_iter().take(TOWER_DAYS_SHOWN) {
+                let (tower, calls) = &day.towers[0];
+                ui.label(day.date.format("%Y-%m-%d").to_string());
+                ui.label(self.settings.format_number(&day.target_number));
+                ui.label(tower.label());
+                ui.label(tower.cell_id.as_deref().unwrap_or_default());
+                ui.label(calls.to_string());
+                ui.label(day.towers.len().to_string());
+                ui.label(day.transitions.to_string());
+                ui.end_row();
+            }
+        });
+        if summary.days.len() > TOWER_DAYS_SHOWN {
+            ui.label(tr!("records-more", count = summary.days.len() - TOWER_DAYS_SHOWN));
+        }
+        if ui.button(tr!("location-show-movement")).clicked() {
+            self.open_detail_view(DetailView::TowerMovement);
+        }
+    }
+    
+    /// A radius or box, and a window of time, to list the calls on towers inside, for
+    /// productions with tower locations.
+    fn render_geofence(&mut self, ui: &mut egui::Ui) {
+        let timezone = self.settings.case_timezone;
+        let mut input = self.geofence_input.clone()
+            .unwrap_or_else(|| GeofenceInput::from_geofence(self.annotations.geofence.as_ref(), timezone));
+        let mut changed = false;
+        let mut apply = false;
+        let mut clear = false;
+        let field = |ui: &mut egui::Ui, value: &mut String, hint: String| {
+            ui.add(egui::TextEdit::singleline(value).hint_text(hint).desired_width(110.0)).changed()
+        };
+        
+        ui.heading(tr!("heading-geofence"));
+        ui.small(tr!("geofence-hint", timezone = timezone.name()));
+        ui.horizontal(|ui| {
+            changed |= ui.radio_value(&mut input.bounding_box, false, tr!("geofence-radius")).changed();
+            changed |= ui.radio_value(&mut input.bounding_box, true, tr!("geofence-box")).changed();
+        });
+        egui::Grid::new("geofence_input").show(ui, |ui| {
+            if input.bounding_box {
+                ui.label(tr!("geofence-south"));
+                changed |= field(ui, &mut input.south, "34.70".to_string());
+                ui.label(tr!("geofence-west"));
+                changed |= field(ui, &mut input.west, "-86.62".to_string());
+                ui.end_row();
+                ui.label(tr!("geofence-north"));
+                changed |= field(ui, &mut input.north, "34.76".to_string());
+                ui.label(tr!("geofence-east"));
+                changed |= field(ui, &mut input.east, "-86.55".to_string());
+                ui.end_row();
+            } else {
+                ui.label(tr!("geofence-latitude"));
+                changed |= field(ui, &mut input.latitude, "34.7304".to_string());
+                ui.label(tr!("geofence-longitude"));
+                changed |= field(ui, &mut input.longitude, "-86.5861".to_string());
+                ui.label(tr!("geofence-radius-km"));
+                changed |= field(ui, &mut input.radius_km, "2".to_string());
+                ui.end_row();
+            }
+            ui.label(tr!("geofence-from"));
+            changed |= field(ui, &mut input.from, "2024-03-01 18:00".to_string());
+            ui.label(tr!("geofence-to"));
+            changed |= field(ui, &mut input.to, "2024-03-01".to_string());
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.geofence_input.is_some() || changed, egui::Button::new(tr!("geofence-apply"))).clicked() {
+                apply = true;
+            }
+            if ui.add_enabled(self.annotations.geofence.is_some(), egui::Button::new(tr!("geofence-clear"))).clicked() {
+                clear = true;
+            }
+            if ui.add_enabled(self.annotations.geofence.is_some(), egui::Button::new(tr!("geofence-show-calls"))).clicked() {
+                self.open_detail_view(DetailView::GeofenceHits);
+            }
+        });
+        if let Some(error) = &input.error {
+            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), error);
+        } else if let Some(geofence) = &self.annotations.geofence {
+            ui.small(tr!("geofence-applied", geofence = geofence.label(timezone)));
+        }
+        
+        if changed {
+            input.error = None;
+            self.geofence_input = Some(input.clone());
+        }
+        if apply {
+            match input.parse(timezone) {
+                Ok(geofence) => {
+                    self.geofence_input = None;
+                    if self.annotations.geofence != Some(geofence) {
+                        let before = self.annotations.clone();
+                        self.annotations.geofence = Some(geofence);
+                        self.annotations_changed(tr!("change-geofence"), before);
+                    }
+                }
+                Err(error) => {
+                    input.error = Some(error);
+                    self.geofence_input = Some(input);
+                }
+            }
+        } else if clear {
+            self.geofence_input = None;
+            let before = self.annotations.clone();
+            self.annotations.geofence = None;
+            self.annotations_changed(tr!("change-geofence"), before);
+        }
+    }
+    
+    /// Numbers grouped as one person, with their combined calls; each number's own count
+    /// stays visible next to it.
+    fn render_entities(&mut self, ui: &mut egui::Ui) {
+        let mut merge = false;
+        let mut removed_number = None;
+        let mut dissolved = None;
+        let mut drilldown = None;
+        
+        ui.heading(tr!("heading-entities"));
+        ui.small(tr!("analytics-entities-hint"));
+        ui.horizontal(|ui| {
+            ui.label(tr!("entity-name"));
+            ui.add(egui::TextEdit::singleline(&mut self.entity_name).desired_width(160.0));
+            ui.label(tr!("entity-numbers"));
+            ui.add(egui::TextEdit::singleline(&mut self.entity_numbers)
+                .hint_text(tr!("entity-numbers-hint"))
+                .desired_width(260.0));
+            let ready = !self.entity_name.trim().is_empty() && !self.entity_numbers.trim().is_empty();
+            if ui.add_enabled(ready, egui::Button::new(tr!("entity-merge"))).clicked() {
+                merge = true;
+            }
+        });
+        
+        let summaries = self.annotations.entities.summaries(&self.call_records);
+        if !summaries.is_empty() {
+            egui::Grid::new("entities").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-entity"));
+                ui.strong(tr!("header-numbers"));
+                ui.strong(tr!("header-call-count"));
+                ui.strong(tr!("header-duration-min"));
+                ui.strong(tr!("header-first-contact"));
+                ui.strong(tr!("header-last-contact"));
+                ui.label("");
+                ui.end_row();
+                
+                for summary in &summaries {
+                    ui.strong(&summary.name);
+                    ui.vertical(|ui| {
+                        for (number, calls) in &summary.numbers {
+                            ui.horizontal(|ui| {
+                                let label = ui.add(egui::Label::new(format!("{} ({})", self.settings.format_number(number), calls))
+                                    .sense(egui::Sense::click()))
+                                    .on_hover_text(tr!("analytics-drilldown-hint"));
+                                if label.clicked() {
+                                    drilldown = Some(DetailView::Contact(number.clone()));
+                                }
+                                if ui.small_button("✖").on_hover_text(tr!("entity-remove-number-hint")).clicked() {
+                                    removed_number = Some(number.clone());
+                                }
+                            });
+                        }
+                    });
+                    ui.label(summary.calls.to_string());
+                    ui.label(format!("{:.1}", summary.duration_minutes));
+                    let format_time = |time: Option<chrono::DateTime<Utc>>| time.map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string()).unwrap_or_default();
+                    ui.label(format_time(summary.first_contact));
+                    ui.label(format_time(summary.last_contact));
+                    if ui.button(tr!("entity-dissolve")).on_hover_text(tr!("entity-dissolve-hint")).clicked() {
+                        dissolved = Some(summary.name.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+        }
+        
+        if merge {
+            let name = self.entity_name.trim().to_string();
+            let before = self.annotations.clone();
+            let added = self.annotations.entities.merge(&name, self.entity_numbers.split(','));
+            if added > 0 {
+                self.annotations_changed(tr!("change-merge-entity", name = name.clone()), before);
+                self.entity_numbers.clear();
+                self.add_message(Message::Success(tr!("msg-entity-merged", count = added, name = name)));
+            } else {
+                self.add_message(Message::Warning(tr!("msg-entity-no-numbers", name = name)));
+            }
+        }
+        if let Some(number) = removed_number {
+            let before = self.annotations.clone();
+            self.annotations.entities.remove_number(&number);
+            self.annotations_changed(tr!("change-remove-from-entity", number = self.settings.format_number(&number)), before);
+        }
+        if let Some(name) = dissolved {
+            let before = self.annotations.clone();
+            self.annotations.entities.dissolve(&name);
+            self.annotations_changed(tr!("change-dissolve-entity", name = name), before);
+        }
+        if let Some(view) = drilldown {
+            self.open_detail_view(view);
+        }
+    }
+    
+    fn open_detail_view(&mut self, view: DetailView) {
+        if !self.detail_views.iter().any(|open| open.view == view) {
+            self.detail_views.push(OpenDetailView { view, popped_out: false });
+        }
+    }
+    
+    fn render_detail_views(&mut self, ctx: &egui::Context) {
+        let mut closed = Vec::new();
+        let mut toggled = Vec::new();
+        let mut selection = None;
+        let mut actions = Vec::new();
+        
+        for (index, open) in self.detail_views.iter().enumerate() {
+            let title = open.view.title(&self.settings);
             
-            // Draw text
-            let text = match self.drag_state {
-                DragState::None => "📁 Drag and drop XML files here\nor click to browse",
-                DragState::Hovering => "📁 Drop XML files here",
-                DragState::Dropping => "📁 Processing...",
+            if open.popped_out {
+                let viewport_id = egui::ViewportId::from_hash_of(&open.view);
+                let builder = egui::ViewportBuilder::default()
+                    .with_title(format!("{} - eSubpoena Tolls Tool", title))
+                    .with_inner_size([640.0, 480.0]);
+                ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                    if class == egui::ViewportClass::Embedded {
+                        // The backend can't open more OS windows; fall back to a docked window
+                        egui::Window::new(&title).id(egui::Id::new(&open.view)).show(ctx, |ui| {
+                            selection = self.render_detail_view(ui, &open.view, &mut actions).or(selection);
+                        });
+                        return;
+                    }
+                    egui::TopBottomPanel::top("detail_toolbar").show(ctx, |ui| {
+                        if ui.button(format!("⬅ {}", tr!("detail-dock"))).clicked() {
+                            toggled.push(index);
+                        }
+                    });
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        selection = self.render_detail_view(ui, &open.view, &mut actions).or(selection);
+                    });
+                    if ctx.input(|i| i.viewport().close_requested()) {
+                        closed.push(index);
+                    }
+                });
+            } else {
+                let mut is_open = true;
+                egui::Window::new(&title)
+                    .id(egui::Id::new(&open.view))
+                    .open(&mut is_open)
+                    .default_size([520.0, 400.0])
+                    .show(ctx, |ui| {
+                        if ui.button(format!("⧉ {}", tr!("detail-pop-out"))).on_hover_text(tr!("detail-pop-out-hint")).clicked() {
+                            toggled.push(index);
+                        }
+                        ui.separator();
+                        selection = self.render_detail_view(ui, &open.view, &mut actions).or(selection);
+                    });
+                if !is_open {
+                    closed.push(index);
+                }
+            }
+        }
+        
+        for index in toggled {
+            self.detail_views[index].popped_out = !self.detail_views[index].popped_out;
+        }
+        for index in closed.into_iter().rev() {
+            self.detail_views.remove(index);
+        }
+        for action in actions {
+            self.apply_record_action(action);
+        }
+        if let Some(selection) = selection {
+            self.record_filter.chart_selection = Some(selection);
+            self.selected_tab = Tab::CallRecords;
+        }
+    }
+    
+    /// Returns the day or hour clicked on a chart, if any; edits made in the view are added
+    /// to `actions`.
+    fn render_detail_view(&self, ui: &mut egui::Ui, view: &DetailView, actions: &mut Vec<RecordAction>) -> Option<ChartSelection> {
+        match view {
+            DetailView::Timeline => return self.render_timeline(ui),
+            DetailView::CallDurations => return self.render_call_durations(ui),
+            DetailView::CumulativeCalls => return self.render_cumulative_calls(ui),
+            DetailView::KeywordHits => return self.render_keyword_hits(ui),
+            DetailView::Record(record_key) => self.render_record_detail(ui, record_key),
+            DetailView::Contact(number) => self.render_contact_detail(ui, number, actions),
+            DetailView::SkippedRecords => self.render_skipped_records(ui),
+            DetailView::CaseComparison => self.render_case_comparison(ui),
+            DetailView::ProductionDiff => self.render_production_diff(ui),
+            DetailView::EvidenceVerification => self.render_evidence_verification(ui),
+            DetailView::ContactMatches => self.render_contact_matches(ui),
+            DetailView::GeofenceHits => self.render_geofence_hits(ui),
+            DetailView::TowerMovement => self.render_tower_movement(ui),
+        }
+        None
+    }
+    
+    fn render_skipped_records(&self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for report in self.parse_reports.iter().filter(|r| r.has_issues()) {
+                ui.heading(&report.source_file);
+                for discrepancy in report.discrepancies() {
+                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), self.describe_discrepancy(&discrepancy));
+                }
+                if report.skipped_count == 0 {
+                    ui.add_space(10.0);
+                    continue;
+                }
+                ui.label(tr!(
+                    "detail-skipped-summary",
+                    skipped = i18n::format_count(report.skipped_count),
+                    total = i18n::format_count(report.total_records)
+                ));
+                if report.skipped.len() < report.skipped_count {
+                    ui.small(tr!("detail-skipped-truncated", shown = i18n::format_count(report.skipped.len())));
+                }
+                if !report.malformed.is_empty() {
+                    ui.label(tr!("detail-malformed-summary", count = i18n::format_count(report.malformed_count)));
+                    if report.malformed.len() < report.malformed_count {
+                        ui.small(tr!("detail-skipped-truncated", shown = i18n::format_count(report.malformed.len())));
+                    }
+                    egui::Grid::new(("malformed_blocks", &report.source_file)).striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-record-position"));
+                        ui.strong(tr!("header-line"));
+                        ui.strong(tr!("header-element"));
+                        ui.strong(tr!("header-reason"));
+                        ui.end_row();
+                        for block in &report.malformed {
+                            ui.label(block.position.to_string());
+                            ui.label(block.line.to_string());
+                            ui.monospace(&block.element);
+                            ui.label(&block.reason);
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(5.0);
+                }
+                if report.skipped.is_empty() {
+                    ui.add_space(10.0);
+                    continue;
+                }
+                egui::Grid::new(("skipped_records", &report.source_file)).striped(true).show(ui, |ui| {
+                    ui.strong(tr!("header-record-position"));
+                    ui.strong(tr!("header-target"));
+                    ui.strong(tr!("header-remote-number"));
+                    ui.strong(tr!("header-raw-start"));
+                    ui.strong(tr!("header-reason"));
+                    ui.end_row();
+                    for skipped in &report.skipped {
+                        ui.label(skipped.position.to_string());
+                        ui.label(self.settings.format_number(&skipped.target_number));
+                        ui.label(&skipped.remote_number);
+                        ui.label(&skipped.start_time);
+                        ui.label(&skipped.reason);
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(10.0);
+            }
+        });
+    }
+    
+    fn describe_discrepancy(&self, discrepancy: &Discrepancy) -> String {
+        let format_bound = |bound: Option<chrono::DateTime<chrono::Utc>>| {
+            bound.map(|time| self.settings.format_local_time(&time)).unwrap_or_else(|| "…".to_string())
+        };
+        match discrepancy {
+            Discrepancy::OutsideDeclaredRange { count, from, to } => tr!(
+                "discrepancy-outside-range",
+                count = i18n::format_count(*count),
+                from = format_bound(*from),
+                to = format_bound(*to)
+            ),
+            Discrepancy::FewerThanDeclared { declared, found } => tr!(
+                "discrepancy-fewer-records",
+                declared = i18n::format_count(*declared),
+                found = i18n::format_count(*found)
+            ),
+        }
+    }
+    
+    fn render_record_detail(&self, ui: &mut egui::Ui, record_key: &str) {
+        let Some(record) = self.call_records.iter().find(|r| r.record_key() == record_key) else {
+            ui.label(tr!("detail-record-missing"));
+            return;
+        };
+        
+        egui::Grid::new("record_detail").num_columns(2).striped(true).show(ui, |ui| {
+            let rows = [
+                (tr!("header-direction"), record.message_direction.clone()),
+                (tr!("header-target-number"), self.settings.format_number(&record.target_number)),
+                (tr!("header-remote-number"), record.remote_number.clone()),
+                (tr!("header-normalized-number"), self.settings.format_number(&record.normalized_number)),
+                (tr!("detail-start-utc"), record.start_time.format("%Y-%m-%d %H:%M:%S").to_string()),
+                (tr!("detail-end-utc"), record.end_time.format("%Y-%m-%d %H:%M:%S").to_string()),
+                (tr!("detail-start-local"), self.settings.format_local_time(&record.start_time)),
+                (tr!("detail-duration"), tr!("detail-duration-value",
+                    seconds = record.length_of_call,
+                    minutes = format!("{:.2}", record.duration_minutes))),
+                (tr!("header-disposition"), record.disposition.label()),
+                (tr!("header-day-of-week"), record.local_day_of_week(self.settings.case_timezone).to_string()),
+                (tr!("header-source-file"), record.source_file.clone()),
+                (tr!("header-tags"), self.annotations.tags(record_key).collect::<Vec<_>>().join(", ")),
+                (tr!("header-notes"), self.annotations.note(record_key).unwrap_or_default().to_string()),
+            ];
+            for (field, value) in rows {
+                ui.strong(field);
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    }
+    
+    fn render_contact_detail(&self, ui: &mut egui::Ui, number: &str, actions: &mut Vec<RecordAction>) {
+        let calls: Vec<&ProcessedCallRecord> = self.call_records.iter()
+            .filter(|r| r.normalized_number == number)
+            .collect();
+        if calls.is_empty() {
+            ui.label(tr!("detail-contact-missing"));
+            return;
+        }
+        
+        let incoming = calls.iter().filter(|r| r.message_direction.eq_ignore_ascii_case("incoming")).count();
+        let total_minutes: f64 = calls.iter().map(|r| r.duration_minutes).sum();
+        let first = calls.iter().map(|r| r.start_time).min();
+        let last = calls.iter().map(|r| r.start_time).max();
+        
+        ui.label(tr!("detail-contact-calls", total = calls.len(), incoming = incoming, outgoing = calls.len() - incoming));
+        ui.label(tr!("detail-contact-duration", minutes = format!("{:.1}", total_minutes)));
+        if let Some(subscriber) = self.annotations.subscribers.lookup(number) {
+            ui.label(tr!("detail-contact-subscriber", name = &subscriber.name, address = &subscriber.address));
+        }
+        if let Some(info) = self.carriers.lookup(number) {
+            match (info.source, info.lrn) {
+                (CarrierSource::Ported, Some(lrn)) => ui.label(tr!("detail-contact-carrier-ported",
+                    carrier = info.carrier, lrn = self.settings.format_number(lrn))),
+                _ => ui.label(tr!("detail-contact-carrier", carrier = info.carrier)),
             };
+        }
+        if let Some(region) = self.carriers.region(number) {
+            ui.label(tr!("detail-contact-region", region = region));
+        }
+        if let Some(name) = self.caller_names.lookup(number) {
+            ui.label(tr!("detail-contact-caller-name", name = name));
+        }
+        let alias = self.annotations.alias(number).unwrap_or_default();
+        let edit_id = ui.id().with(("alias_edit", number));
+        let mut name: String = ui.data(|d| d.get_temp(edit_id)).unwrap_or_else(|| alias.to_string());
+        ui.horizontal(|ui| {
+            ui.label(tr!("detail-contact-name"));
+            let response = ui.add(egui::TextEdit::singleline(&mut name)
+                .hint_text(tr!("detail-contact-name-hint"))
+                .desired_width(220.0));
+            let changed = name.trim() != alias;
+            let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.add_enabled(changed, egui::Button::new(tr!("detail-contact-name-save"))).clicked() || (changed && entered) {
+                actions.push(RecordAction::SetAlias(number.to_string(), name.clone()));
+            }
+        });
+        // Only unsaved text is kept, so the field follows imports, saves, and undo otherwise
+        if name.trim() == alias {
+            ui.data_mut(|d| d.remove::<String>(edit_id));
+        } else {
+            ui.data_mut(|d| d.insert_temp(edit_id, name));
+        }
+        if let Some(entity) = self.annotations.entities.entity_of(number) {
+            ui.label(tr!("detail-contact-entity", entity = entity));
+        }
+        if let Some(categories) = self.known_numbers.label(number) {
+            ui.colored_label(CATEGORY_COLOR, tr!("detail-contact-categories", categories = categories));
+        }
+        if let (Some(first), Some(last)) = (first, last) {
+            ui.label(tr!("detail-first-contact", time = self.settings.format_local_time(&first)));
+            ui.label(tr!("detail-last-contact", time = self.settings.format_local_time(&last)));
+        }
+        ui.add_space(10.0);
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("contact_detail_calls").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-date-time"));
+                ui.strong(tr!("header-direction"));
+                ui.strong(tr!("header-target"));
+                ui.strong(tr!("header-duration-min"));
+                ui.end_row();
+                for record in calls {
+                    ui.label(self.settings.format_local_time(&record.start_time));
+                    ui.label(&record.message_direction);
+                    ui.label(self.settings.format_number(&record.target_number));
+                    ui.label(format!("{:.2}", record.duration_minutes));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    
+    fn render_case_comparison(&self, ui: &mut egui::Ui) {
+        let Some(comparison) = &self.case_comparison else {
+            ui.label(tr!("detail-comparison-empty"));
+            return;
+        };
+        let timezone = self.settings.case_timezone;
+        let format_range = |range: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)>| match range {
+            Some((first, last)) => format!(
+                "{} – {}",
+                first.with_timezone(&timezone).format("%Y-%m-%d"),
+                last.with_timezone(&timezone).format("%Y-%m-%d")
+            ),
+            None => tr!("comparison-no-overlap"),
+        };
+        
+        ui.label(tr!("comparison-current-range", range = format_range(comparison.current_range)));
+        ui.label(tr!("comparison-other-range", name = &comparison.other_label, range = format_range(comparison.other_range)));
+        ui.label(tr!("comparison-overlap", range = format_range(comparison.overlapping_period())));
+        ui.add_space(10.0);
+        
+        let common: Vec<_> = comparison.shared_common_contacts().collect();
+        if !common.is_empty() {
+            ui.strong(tr!("comparison-shared-common-contacts", count = common.len()));
+            let numbers: Vec<_> = common.iter().map(|shared| self.settings.format_number(&shared.number)).collect();
+            ui.label(numbers.join(", "));
+            ui.add_space(10.0);
+        }
+        
+        ui.strong(tr!("comparison-shared-numbers", count = comparison.shared_numbers.len()));
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("case_comparison").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("comparison-header-current"));
+                ui.strong(&comparison.other_label);
+                ui.strong(tr!("comparison-header-both-active"));
+                ui.end_row();
+                for shared in &comparison.shared_numbers {
+                    let role = |is_target: bool| if is_target { format!(" ({})", tr!("comparison-target")) } else { String::new() };
+                    ui.label(self.settings.format_number(&shared.number));
+                    ui.label(format!("{}{}", tr!("report-calls", count = shared.current.calls), role(shared.current.is_target)));
+                    ui.label(format!("{}{}", tr!("report-calls", count = shared.other.calls), role(shared.other.is_target)));
+                    ui.label(format_range(shared.overlapping_period()));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    
+    fn render_geofence_hits(&self, ui: &mut egui::Ui) {
+        let Some(geofence) = &self.annotations.geofence else {
+            ui.label(tr!("detail-geofence-empty"));
+            return;
+        };
+        let timezone = self.settings.case_timezone;
+        let hits = geofence.hits(&self.call_records);
+        ui.label(tr!("geofence-summary", count = hits.len(), geofence = geofence.label(timezone)));
+        if hits.is_empty() {
+            return;
+        }
+        let radius = matches!(geofence.area, GeofenceArea::Radius { .. });
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("geofence_hits").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-start-time-zone", timezone = timezone.name()));
+                ui.strong(tr!("header-direction"));
+                ui.strong(tr!("header-target-number"));
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("column-cell-site"));
+                ui.strong(tr!("column-tower-location"));
+                if radius {
+                    ui.strong(tr!("header-distance-km"));
+                }
+                ui.end_row();
+                for record in hits.iter().take(GEOFENCE_HITS_SHOWN) {
+                    let site = record.cell_site.as_ref();
+                    ui.label(record.start_time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string());
+                    ui.label(&record.message_direction);
+                    ui.label(self.settings.format_number(&record.target_number));
+                    ui.label(self.settings.format_number(&record.normalized_number));
+                    ui.label(site.map(|site| site.label()).unwrap_or_default());
+                    ui.label(site.map(|site| site.location_label()).unwrap_or_default());
+                    if let Some(distance) = geofence.distance_km(record) {
+                        ui.label(format!("{:.2}", distance));
+                    }
+                    ui.end_row();
+                }
+            });
+            if hits.len() > GEOFENCE_HITS_SHOWN {
+                ui.label(tr!("records-more", count = hits.len() - GEOFENCE_HITS_SHOWN));
+            }
+        });
+    }
+    
+    fn render_tower_movement(&self, ui: &mut egui::Ui) {
+        let timezone = self.settings.case_timezone;
+        let summary = LocationSummary::build(&self.call_records, timezone);
+        if summary.dwells.is_empty() {
+            ui.label(tr!("detail-tower-movement-empty"));
+            return;
+        }
+        let time = |time: chrono::DateTime<Utc>| time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string();
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading(tr!("heading-dwell-periods"));
+            ui.small(tr!("location-hint", hours = DWELL_BREAK_HOURS));
+            egui::Grid::new("dwell_periods").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-target-number"));
+                ui.strong(tr!("column-tower-location"));
+                ui.strong(tr!("header-cell-site-id"));
+                ui.strong(tr!("header-first-call-zone", timezone = timezone.name()));
+                ui.strong(tr!("header-last-call-zone", timezone = timezone.name()));
+                ui.strong(tr!("header-duration-min"));
+                ui.strong(tr!("header-call-count"));
+                ui.end_row();
+                for dwell in summary.dwells.iter().take(TOWER_MOVEMENT_SHOWN) {
+                    ui.label(self.settings.format_number(&dwell.target_number));
+                    ui.label(dwell.tower.label());
+                    ui.label(dwell.tower.cell_id.as_deref().unwrap_or_default());
+                    ui.label(time(dwell.first));
+                    ui.label(time(dwell.last));
+                    ui.label(dwell.duration().num_minutes().to_string());
+                    ui.label(dwell.calls.to_string());
+                    ui.end_row();
+                }
+            });
+            if summary.dwells.len() > TOWER_MOVEMENT_SHOWN {
+                ui.label(tr!("records-more", count = summary.dwells.len() - TOWER_MOVEMENT_SHOWN));
+            }
             
-            painter.text(
-                rect.center(),
-                egui::Align2::CENTER_CENTER,
-                text,
-                egui::FontId::proportional(18.0),
-                egui::Color32::WHITE,
-            );
+            ui.add_space(20.0);
+            ui.heading(tr!("heading-tower-changes"));
+            if summary.transitions.is_empty() {
+                ui.label(tr!("sheet-no-tower-changes"));
+                return;
+            }
+            egui::Grid::new("tower_changes").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-target-number"));
+                ui.strong(tr!("header-left-zone", timezone = timezone.name()));
+                ui.strong(tr!("header-from-tower"));
+                ui.strong(tr!("header-arrived-zone", timezone = timezone.name()));
+                ui.strong(tr!("header-to-tower"));
+                ui.strong(tr!("header-distance-km"));
+                ui.strong(tr!("header-speed-kmh"));
+                ui.end_row();
+                for transition in summary.transitions.iter().take(TOWER_MOVEMENT_SHOWN) {
+                    ui.label(self.settings.format_number(&transition.target_number));
+                    ui.label(time(transition.left));
+                    ui.label(transition.from.label());
+                    ui.label(time(transition.arrived));
+                    ui.label(transition.to.label());
+                    ui.label(transition.distance_km.map(|distance| format!("{:.2}", distance)).unwrap_or_default());
+                    let speed = transition.speed_kmh().map(|speed| format!("{:.0}", speed)).unwrap_or_default();
+                    if transition.implausible() {
+                        ui.colored_label(egui::Color32::from_rgb(220, 80, 80), speed);
+                    } else {
+                        ui.label(speed);
+                    }
+                    ui.end_row();
+                }
+            });
+            if summary.transitions.len() > TOWER_MOVEMENT_SHOWN {
+                ui.label(tr!("records-more", count = summary.transitions.len() - TOWER_MOVEMENT_SHOWN));
+            }
             
-            // Handle file drops
-            if !response.dropped_files().is_empty() {
-                for dropped_file in response.dropped_files() {
-                    if let Some(path) = &dropped_file.path {
-                        if path.extension().map_or(false, |ext| ext == "xml") {
-                            self.process_file(path.clone());
+            if !summary.implausible.is_empty() {
+                ui.add_space(20.0);
+                ui.heading(tr!("heading-implausible-moves"));
+                self.implausible_moves_grid(ui, "tower_movement_implausible", &summary.implausible);
+            }
+        });
+    }
+    
+    /// Moves no phone could have made, with the speed each implies.
+    fn implausible_moves_grid(&self, ui: &mut egui::Ui, id: &str, moves: &[TowerTransition]) {
+        let timezone = self.settings.case_timezone;
+        let time = |time: chrono::DateTime<Utc>| time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string();
+        ui.small(tr!("implausible-moves-hint", speed = IMPLAUSIBLE_SPEED_KMH, range = TOWER_RANGE_KM));
+        egui::Grid::new(id).striped(true).show(ui, |ui| {
+            ui.strong(tr!("header-target-number"));
+            ui.strong(tr!("header-left-zone", timezone = timezone.name()));
+            ui.strong(tr!("header-from-tower"));
+            ui.strong(tr!("header-arrived-zone", timezone = timezone.name()));
+            ui.strong(tr!("header-to-tower"));
+            ui.strong(tr!("header-distance-km"));
+            ui.strong(tr!("header-speed-kmh"));
+            ui.end_row();
+            for transition in moves.iter().take(TOWER_MOVEMENT_SHOWN) {
+                ui.label(self.settings.format_number(&transition.target_number));
+                ui.label(time(transition.left));
+                ui.label(transition.from.label());
+                ui.label(time(transition.arrived));
+                ui.label(transition.to.label());
+                ui.label(transition.distance_km.map(|distance| format!("{:.2}", distance)).unwrap_or_default());
+                ui.label(transition.speed_kmh().map_or_else(|| "∞".to_string(), |speed| format!("{:.0}", speed)));
+                ui.end_row();
+            }
+        });
+        if moves.len() > TOWER_MOVEMENT_SHOWN {
+            ui.label(tr!("records-more", count = moves.len() - TOWER_MOVEMENT_SHOWN));
+        }
+    }
+    
+    fn render_keyword_hits(&self, ui: &mut egui::Ui) -> Option<ChartSelection> {
+        if self.annotations.keywords.is_empty() {
+            ui.label(tr!("detail-keyword-hits-empty"));
+            return None;
+        }
+        let timezone = self.settings.case_timezone;
+        let hits = KeywordHits::search(&self.call_records, &self.annotations.keywords, timezone);
+        ui.label(tr!("keywords-summary", count = hits.hits.len(), timezone = timezone.name()));
+        ui.horizontal_wrapped(|ui| {
+            for (i, keyword) in hits.keywords.iter().enumerate() {
+                ui.colored_label(SERIES_COLORS[i % SERIES_COLORS.len()], format!("■ {}: {}", keyword, hits.counts[i]));
+            }
+        });
+        if hits.hits.is_empty() {
+            return None;
+        }
+        
+        let series: Vec<(egui::Color32, &[usize])> = hits.cumulative.iter().enumerate()
+            .map(|(i, counts)| (SERIES_COLORS[i % SERIES_COLORS.len()], counts.as_slice()))
+            .collect();
+        let mut selection = None;
+        let (response, hovered_day) = render_cumulative_chart(ui, egui::Id::new("keyword_hits_zoom"), &series, 120.0);
+        if let Some(day) = hovered_day {
+            if response.clicked() {
+                selection = Some(ChartSelection::Day(hits.days[day]));
+            }
+            response.on_hover_ui(|ui| {
+                ui.label(hits.days[day].to_string());
+                for (i, keyword) in hits.keywords.iter().enumerate() {
+                    ui.colored_label(SERIES_COLORS[i % SERIES_COLORS.len()], format!("{}: {}", keyword, hits.cumulative[i][day]));
+                }
+            });
+        }
+        ui.add_space(10.0);
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("keyword_hits").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-date-time"));
+                ui.strong(tr!("header-direction"));
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("header-keywords"));
+                ui.strong(tr!("header-message"));
+                ui.end_row();
+                for hit in hits.hits.iter().take(KEYWORD_HITS_SHOWN) {
+                    let record = hit.record;
+                    let tagged = self.annotations.tagged_records.contains(&record.record_key());
+                    let time = record.start_time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string();
+                    ui.label(if tagged { format!("🏷 {}", time) } else { time });
+                    ui.label(&record.message_direction);
+                    ui.label(self.settings.format_number(&record.normalized_number));
+                    ui.label(hits.matched_text(hit));
+                    let content = record.message_content.as_deref().unwrap_or_default();
+                    ui.label(highlight_ranges(ui, content, keyword_ranges(content, &hits.keywords)));
+                    ui.end_row();
+                }
+            });
+            if hits.hits.len() > KEYWORD_HITS_SHOWN {
+                ui.label(tr!("records-more", count = hits.hits.len() - KEYWORD_HITS_SHOWN));
+            }
+        });
+        selection
+    }
+    
+    fn render_evidence_verification(&self, ui: &mut egui::Ui) {
+        let Some(verification) = &self.evidence_verification else {
+            ui.label(tr!("detail-evidence-empty"));
+            return;
+        };
+        ui.label(tr!("evidence-case-file", path = verification.case_file.display().to_string()));
+        ui.label(tr!("evidence-verified-at", time = verification.verified_at.format("%Y-%m-%d %H:%M:%S UTC").to_string()));
+        ui.label(tr!("evidence-counts", verified = verification.verified_count(), total = verification.checks.len()));
+        if verification.checks.is_empty() {
+            ui.label(tr!("evidence-no-sources"));
+        }
+        if ui.button(tr!("diff-copy-report")).clicked() {
+            ui.output_mut(|o| o.copied_text = verification.to_text());
+        }
+        ui.add_space(10.0);
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("evidence_verification").striped(true).show(ui, |ui| {
+                ui.strong(tr!("evidence-header-file"));
+                ui.strong(tr!("evidence-header-status"));
+                ui.strong(tr!("evidence-header-sha256"));
+                ui.strong(tr!("evidence-header-found-at"));
+                ui.end_row();
+                for check in &verification.checks {
+                    ui.label(&check.source.name);
+                    let color = match check.status {
+                        EvidenceStatus::Verified => egui::Color32::from_rgb(90, 200, 120),
+                        _ => egui::Color32::from_rgb(220, 80, 80),
+                    };
+                    ui.colored_label(color, check.status_label());
+                    ui.monospace(&check.source.sha256).on_hover_text(match &check.status {
+                        EvidenceStatus::Altered(actual) => tr!("evidence-current-hash", hash = actual.as_str()),
+                        _ => tr!("evidence-recorded-hash", hash = check.source.sha256.as_str()),
+                    });
+                    ui.label(check.found_at.as_ref().map(|path| path.display().to_string()).unwrap_or_default());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    
+    fn render_production_diff(&self, ui: &mut egui::Ui) {
+        let Some(diff) = &self.production_diff else {
+            ui.label(tr!("detail-diff-empty"));
+            return;
+        };
+        ui.label(tr!("diff-original", name = diff.original.as_str()));
+        ui.label(tr!("diff-corrected", name = diff.corrected.as_str()));
+        ui.label(tr!("diff-counts", unchanged = diff.unchanged, added = diff.added.len(), removed = diff.removed.len(), changed = diff.changed.len()));
+        if ui.button(tr!("diff-copy-report")).clicked() {
+            ui.output_mut(|o| o.copied_text = diff.to_text(&self.settings));
+        }
+        if diff.is_identical() {
+            ui.label(tr!("diff-identical"));
+            return;
+        }
+        ui.add_space(10.0);
+        
+        let timezone = self.settings.case_timezone;
+        let time = |record: &ProcessedCallRecord| record.start_time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S").to_string();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (id, heading, records) in [
+                ("diff_added", tr!("diff-added-heading", count = diff.added.len()), &diff.added),
+                ("diff_removed", tr!("diff-removed-heading", count = diff.removed.len()), &diff.removed),
+            ] {
+                if records.is_empty() {
+                    continue;
+                }
+                egui::CollapsingHeader::new(heading).id_source(id).default_open(true).show(ui, |ui| {
+                    egui::Grid::new(id).striped(true).show(ui, |ui| {
+                        ui.strong(tr!("header-date-time"));
+                        ui.strong(tr!("header-direction"));
+                        ui.strong(tr!("header-phone-number"));
+                        ui.strong(tr!("header-target"));
+                        ui.strong(tr!("header-duration-sec"));
+                        ui.end_row();
+                        for record in records {
+                            ui.label(time(record));
+                            ui.label(&record.message_direction);
+                            ui.label(self.settings.format_number(&record.normalized_number));
+                            ui.label(self.settings.format_number(&record.target_number));
+                            ui.label(record.length_of_call.to_string());
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+            if !diff.changed.is_empty() {
+                egui::CollapsingHeader::new(tr!("diff-changed-heading", count = diff.changed.len())).default_open(true).show(ui, |ui| {
+                    egui::Grid::new("diff_changed").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("diff-header-original-time"));
+                        ui.strong(tr!("diff-header-corrected-time"));
+                        ui.strong(tr!("header-phone-number"));
+                        ui.strong(tr!("diff-header-original-duration"));
+                        ui.strong(tr!("diff-header-corrected-duration"));
+                        ui.end_row();
+                        for change in &diff.changed {
+                            let highlight = |ui: &mut egui::Ui, text: String, changed: bool| {
+                                if changed {
+                                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), text);
+                                } else {
+                                    ui.label(text);
+                                }
+                            };
+                            ui.label(time(&change.original));
+                            highlight(ui, time(&change.corrected), change.time_shift_secs() != 0);
+                            ui.label(self.settings.format_number(&change.original.normalized_number));
+                            ui.label(change.original.length_of_call.to_string());
+                            highlight(ui, change.corrected.length_of_call.to_string(), change.duration_change_secs() != 0);
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+        });
+    }
+    
+    fn render_contact_matches(&self, ui: &mut egui::Ui) {
+        let Some(analytics) = &self.analytics else {
+            ui.label(tr!("detail-contact-matches-empty"));
+            return;
+        };
+        let matches = self.contact_matches(analytics);
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.strong(tr!("contact-matches-matched", count = matches.matched.len()));
+            egui::Grid::new("contact_matches").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("header-alias"));
+                ui.strong(tr!("header-call-count"));
+                ui.end_row();
+                for (number, alias, calls) in &matches.matched {
+                    ui.label(self.settings.format_number(number));
+                    ui.label(alias);
+                    ui.label(calls.to_string());
+                    ui.end_row();
+                }
+            });
+            
+            ui.add_space(10.0);
+            ui.strong(tr!("contact-matches-unidentified", count = matches.unidentified.len()));
+            ui.small(tr!("contact-matches-unidentified-hint"));
+            egui::Grid::new("contact_unidentified").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("header-call-count"));
+                ui.end_row();
+                for (number, calls) in &matches.unidentified {
+                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), self.settings.format_number(number));
+                    ui.label(calls.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    
+    /// Calls per day and per hour of the day; clicking a bar filters the records to it.
+    fn render_timeline(&self, ui: &mut egui::Ui) -> Option<ChartSelection> {
+        let Some(analytics) = &self.analytics else {
+            ui.label(tr!("detail-timeline-empty"));
+            return None;
+        };
+        let mut days: Vec<_> = analytics.calls_by_day.iter().collect();
+        days.sort_by(|a, b| a.0.cmp(b.0));
+        let day_counts: Vec<usize> = days.iter().map(|(_, count)| **count).collect();
+        let hour_counts: Vec<usize> = (0..24).map(|hour| analytics.calls_by_hour.get(&hour).copied().unwrap_or(0)).collect();
+        let mut selection = None;
+        
+        ui.label(tr!("detail-timeline-caption", timezone = analytics.timezone.name()));
+        ui.small(tr!("chart-interaction-hint"));
+        let height = ui.available_height().max(220.0) - 40.0;
+        let (response, hovered) = render_bar_chart(ui, egui::Id::new("timeline_days"), &day_counts, height * 0.65);
+        if let Some(i) = hovered {
+            let (day, count) = days[i];
+            if response.clicked() {
+                selection = NaiveDate::parse_from_str(day, "%Y-%m-%d").ok().map(ChartSelection::Day);
+            }
+            response.on_hover_text(format!("{}: {}", day, tr!("report-calls", count = *count)));
+        }
+        
+        ui.label(tr!("heading-calls-by-hour", timezone = analytics.timezone.name()));
+        let (response, hovered) = render_bar_chart(ui, egui::Id::new("timeline_hours"), &hour_counts, height * 0.35);
+        if let Some(hour) = hovered {
+            if response.clicked() {
+                selection = Some(ChartSelection::Hour(hour as u32));
+            }
+            response.on_hover_text(format!("{:02}:00: {}", hour, tr!("report-calls", count = hour_counts[hour])));
+        }
+        selection
+    }
+    
+    fn render_call_durations(&self, ui: &mut egui::Ui) -> Option<ChartSelection> {
+        let Some(analytics) = &self.analytics else {
+            ui.label(tr!("detail-timeline-empty"));
+            return None;
+        };
+        let contacts: Vec<String> = analytics.most_frequent_numbers.iter().map(|(number, _)| number.clone()).collect();
+        let timeline = CallTimeline::build(&self.call_records, &contacts);
+        let Some((first, last)) = timeline.range else {
+            ui.label(tr!("sheet-no-calls"));
+            return None;
+        };
+        
+        ui.label(tr!(
+            "detail-call-durations-caption",
+            from = first.format("%Y-%m-%d %H:%M").to_string(),
+            to = last.format("%Y-%m-%d %H:%M").to_string(),
+            overlapping = timeline.overlapping_calls()
+        ));
+        ui.small(tr!("chart-interaction-hint"));
+        let total_seconds = (last - first).num_seconds().max(1) as f32;
+        let label_width = 110.0;
+        let row_height = 18.0;
+        // Every row shows the same span, so they zoom and pan together
+        let zoom_id = egui::Id::new("call_durations_zoom");
+        let mut selection = None;
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for row in &timeline.rows {
+                ui.horizontal(|ui| {
+                    ui.add_sized([label_width, row_height], egui::Label::new(self.settings.format_number(&row.number)));
+                    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), row_height), egui::Sense::click_and_drag());
+                    let painter = ui.painter_at(rect);
+                    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+                    let zoom = chart_zoom(ui, zoom_id, rect, &response);
+                    
+                    let x_at = |time: chrono::DateTime<Utc>| zoom.x_at(rect, (time - first).num_seconds() as f32 / total_seconds);
+                    let mut hovered = None;
+                    for call in &row.calls {
+                        let left = x_at(call.start);
+                        // Keep short calls visible at any zoom
+                        let right = x_at(call.end).max(left + 1.0);
+                        if right < rect.left() || left > rect.right() {
+                            continue;
+                        }
+                        let bar = egui::Rect::from_min_max(egui::pos2(left, rect.top() + 2.0), egui::pos2(right, rect.bottom() - 2.0));
+                        let color = if call.overlaps {
+                            egui::Color32::from_rgb(255, 150, 50)
                         } else {
-                            self.add_message(Message::Warning("Please drop XML files only".to_string()));
+                            egui::Color32::from_rgb(100, 150, 255)
+                        };
+                        painter.rect_filled(bar, 1.0, color);
+                        if response.hover_pos().is_some_and(|pos| pos.x >= bar.left() - 1.0 && pos.x <= bar.right() + 1.0) {
+                            hovered = Some(call);
                         }
                     }
-                }
+                    if let Some(pos) = response.hover_pos() {
+                        painter.vline(pos.x, rect.y_range(), ui.visuals().widgets.noninteractive.fg_stroke);
+                    }
+                    if let Some(call) = hovered {
+                        if response.clicked() {
+                            selection = Some(ChartSelection::Day(call.start.with_timezone(&self.settings.case_timezone).date_naive()));
+                        }
+                        let mut text = tr!(
+                            "detail-call-durations-call",
+                            start = call.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+                            minutes = format!("{:.1}", (call.end - call.start).num_seconds() as f64 / 60.0)
+                        );
+                        if call.overlaps {
+                            text = format!("{}\n{}", text, tr!("detail-call-durations-overlap"));
+                        }
+                        response.on_hover_text(text);
+                    }
+                });
             }
-            
-            // Handle click to browse
+        });
+        selection
+    }
+    
+    fn render_cumulative_calls(&self, ui: &mut egui::Ui) -> Option<ChartSelection> {
+        let Some(analytics) = &self.analytics else {
+            ui.label(tr!("detail-timeline-empty"));
+            return None;
+        };
+        let contacts: Vec<String> = analytics.most_frequent_numbers.iter()
+            .take(CUMULATIVE_TOP_CONTACTS)
+            .map(|(number, _)| number.clone())
+            .collect();
+        let cumulative = CumulativeCalls::build(&self.call_records, &contacts, analytics.timezone);
+        if cumulative.days.is_empty() {
+            return None;
+        }
+        
+        let chart_height = ((ui.available_height() - 100.0) / 2.0).max(100.0);
+        // Both charts share the day axis, so they zoom and pan together
+        let zoom_id = egui::Id::new("cumulative_calls_zoom");
+        let mut selection = None;
+        ui.label(tr!("detail-cumulative-total", timezone = analytics.timezone.name()));
+        ui.small(tr!("chart-interaction-hint"));
+        let total_color = ui.visuals().widgets.active.fg_stroke.color;
+        let (response, hovered_day) = render_cumulative_chart(ui, zoom_id, &[(total_color, cumulative.total.as_slice())], chart_height);
+        if let Some(day) = hovered_day {
             if response.clicked() {
-                // In a real implementation, you'd open a file dialog here
-                self.add_message(Message::Info("File browser not implemented in this demo".to_string()));
+                selection = Some(ChartSelection::Day(cumulative.days[day]));
             }
-            
-            ui.add_space(20.0);
-            
-            // Processing status
-            match &self.processing_state {
-                ProcessingState::Idle => {
-                    ui.label("Ready to process files");
+            response.on_hover_text(format!("{}: {}", cumulative.days[day], tr!("report-calls", count = cumulative.total[day])));
+        }
+        
+        ui.add_space(6.0);
+        ui.label(tr!("detail-cumulative-contacts", count = cumulative.contacts.len()));
+        let series: Vec<(egui::Color32, &[usize])> = cumulative.contacts.iter().enumerate()
+            .map(|(i, (_, counts))| (SERIES_COLORS[i % SERIES_COLORS.len()], counts.as_slice()))
+            .collect();
+        let (response, hovered_day) = render_cumulative_chart(ui, zoom_id, &series, chart_height);
+        if let Some(day) = hovered_day {
+            if response.clicked() {
+                selection = Some(ChartSelection::Day(cumulative.days[day]));
+            }
+            response.on_hover_ui(|ui| {
+                ui.label(cumulative.days[day].to_string());
+                for (i, (number, counts)) in cumulative.contacts.iter().enumerate() {
+                    ui.colored_label(SERIES_COLORS[i % SERIES_COLORS.len()],
+                        format!("{}: {}", self.settings.format_number(number), counts[day]));
                 }
-                ProcessingState::Processing => {
-                    ui.label("⏳ Processing...");
+            });
+        }
+        ui.horizontal_wrapped(|ui| {
+            for (i, (number, _)) in cumulative.contacts.iter().enumerate() {
+                ui.colored_label(SERIES_COLORS[i % SERIES_COLORS.len()], format!("■ {}", self.settings.format_number(number)));
+            }
+        });
+        selection
+    }
+    
+    fn render_bookmarks(&mut self, ui: &mut egui::Ui) {
+        let bookmarked = self.annotations.bookmarked_records(&self.call_records);
+        if bookmarked.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(tr!("bookmarks-empty"));
+            });
+            return;
+        }
+        
+        ui.label(tr!("bookmarks-count", count = bookmarked.len()));
+        ui.add_space(5.0);
+        
+        let mut actions = Vec::new();
+        egui::ScrollArea::vertical().max_height(600.0).show(ui, |ui| {
+            egui::Grid::new("bookmarks").striped(true).show(ui, |ui| {
+                ui.label("");
+                ui.strong(tr!("header-date-time"));
+                ui.strong(tr!("header-direction"));
+                ui.strong(tr!("header-target"));
+                ui.strong(tr!("header-remote-number"));
+                ui.strong(tr!("header-duration-min"));
+                ui.strong(tr!("header-source-file"));
+                ui.end_row();
+                
+                for record in bookmarked {
+                    let record_key = record.record_key();
+                    if bookmark_toggle(ui, true).clicked() {
+                        actions.push(RecordAction::ToggleBookmark(record_key.clone()));
+                    }
+                    if ui.link(self.settings.format_local_time(&record.start_time)).on_hover_text(tr!("bookmarks-show-details")).clicked() {
+                        actions.push(RecordAction::OpenDetail(DetailView::Record(record_key)));
+                    }
+                    ui.label(&record.message_direction);
+                    ui.label(self.settings.format_number(&record.target_number));
+                    ui.label(self.settings.format_number(&record.normalized_number));
+                    ui.label(format!("{:.2}", record.duration_minutes));
+                    ui.label(&record.source_file);
+                    ui.end_row();
                 }
-                ProcessingState::Completed => {
-                    ui.label("✅ Processing completed");
+            });
+        });
+        
+        for action in actions {
+            self.apply_record_action(action);
+        }
+    }
+    
+    fn render_scripts(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("tab-scripts"));
+        ui.label(tr!("scripts-intro"));
+        ui.add_space(5.0);
+        
+        let mut save = false;
+        let mut delete = false;
+        let mut run = None;
+        let mut cancel = None;
+        
+        ui.horizontal(|ui| {
+            let selected_name = self.settings.scripts.get(self.scripts_view.selected)
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| tr!("scripts-none"));
+            egui::ComboBox::from_id_source("script_select")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (i, script) in self.settings.scripts.iter().enumerate() {
+                        ui.selectable_value(&mut self.scripts_view.selected, i, &script.name);
+                    }
+                });
+            if ui.button(format!("➕ {}", tr!("scripts-new"))).clicked() {
+                self.settings.scripts.push(UserScript::example());
+                self.scripts_view.selected = self.settings.scripts.len() - 1;
+            }
+            delete = !self.settings.scripts.is_empty() && ui.button(format!("🗑 {}", tr!("scripts-delete"))).clicked();
+            save = ui.button(format!("💾 {}", tr!("scripts-save"))).on_hover_text(tr!("scripts-save-hint")).clicked();
+        });
+        
+        let running = self.jobs.jobs().iter()
+            .find(|job| job.kind == JobKind::Script && job.is_running())
+            .map(|job| job.id);
+        
+        if let Some(script) = self.settings.scripts.get_mut(self.scripts_view.selected) {
+            ui.horizontal(|ui| {
+                ui.label(tr!("scripts-name"));
+                ui.text_edit_singleline(&mut script.name);
+            });
+            egui::ScrollArea::vertical().id_source("script_source").max_height(300.0).show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut script.source)
+                    .code_editor()
+                    .desired_rows(16)
+                    .desired_width(f32::INFINITY));
+            });
+            ui.horizontal(|ui| match running {
+                Some(id) => {
+                    ui.spinner();
+                    ui.label(tr!("scripts-running"));
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        cancel = Some(id);
+                    }
                 }
-                ProcessingState::Error(error) => {
-                    ui.label(format!("❌ Error: {}", error));
+                None => {
+                    if ui.add_enabled(!self.call_records.is_empty(), egui::Button::new(format!("▶ {}", tr!("scripts-run")))).clicked() {
+                        run = Some(script.clone());
+                    }
                 }
-            }
-            
-            // Statistics
-            if let Some(analytics) = &self.analytics {
-                ui.add_space(20.0);
-                ui.heading("Quick Statistics");
-                
-                ui.horizontal(|ui| {
-                    ui.vertical(|ui| {
-                        ui.label(format!("Total Calls: {}", analytics.total_calls));
-                        ui.label(format!("Incoming: {}", analytics.incoming_calls));
-                        ui.label(format!("Outgoing: {}", analytics.outgoing_calls));
+            });
+        } else {
+            ui.label(tr!("scripts-empty"));
+        }
+        
+        if let Some(error) = &self.scripts_view.last_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+        
+        let output = self.settings.scripts.get(self.scripts_view.selected)
+            .and_then(|script| self.scripts_view.output_for(&script.name));
+        if let Some(output) = output {
+            ui.separator();
+            egui::ScrollArea::vertical().id_source("script_output").show(ui, |ui| {
+                if !output.metrics.is_empty() {
+                    ui.strong(tr!("scripts-metrics"));
+                    egui::Grid::new("script_metrics").striped(true).num_columns(2).show(ui, |ui| {
+                        for (name, value) in &output.metrics {
+                            ui.label(name);
+                            ui.label(value);
+                            ui.end_row();
+                        }
                     });
-                    ui.vertical(|ui| {
-                        ui.label(format!("Unique Numbers: {}", analytics.unique_numbers));
-                        ui.label(format!("Total Duration: {:.1} min", analytics.total_duration_minutes));
-                        ui.label(format!("Avg Duration: {:.1} min", analytics.average_call_duration));
+                    ui.add_space(5.0);
+                }
+                
+                if !output.columns.is_empty() {
+                    ui.strong(tr!("scripts-rows", count = output.rows.len()));
+                    egui::Grid::new("script_rows").striped(true).num_columns(output.columns.len()).show(ui, |ui| {
+                        for column in &output.columns {
+                            ui.strong(column);
+                        }
+                        ui.end_row();
+                        for row in output.padded_rows().take(SCRIPT_ROWS_SHOWN) {
+                            for value in &row {
+                                ui.label(value);
+                            }
+                            ui.end_row();
+                        }
                     });
-                });
+                    if output.rows.len() > SCRIPT_ROWS_SHOWN {
+                        ui.small(tr!("scripts-rows-truncated", count = SCRIPT_ROWS_SHOWN));
+                    }
+                    ui.add_space(5.0);
+                }
+                
+                if !output.log.is_empty() {
+                    ui.strong(tr!("scripts-log"));
+                    for line in &output.log {
+                        ui.monospace(line);
+                    }
+                }
+            });
+        }
+        
+        if delete {
+            self.settings.scripts.remove(self.scripts_view.selected);
+            self.scripts_view.selected = self.scripts_view.selected.saturating_sub(1);
+        }
+        if save {
+            match self.settings.save() {
+                Ok(path) => self.add_message(Message::Success(tr!("msg-scripts-saved", path = path.display().to_string()))),
+                Err(e) => self.add_message(Message::Error(tr!("msg-scripts-save-failed", error = format!("{:#}", e)))),
+            }
+        }
+        if let Some(id) = cancel {
+            self.cancel_job(id);
+        }
+        if let Some(script) = run {
+            self.scripts_view.last_error = None;
+            self.run_script(script);
+        }
+    }
+    
+    fn render_jobs(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(tr!("jobs-heading"));
+            if ui.button(tr!("jobs-clear-finished")).clicked() {
+                self.jobs.clear_finished();
             }
         });
+        ui.add_space(5.0);
+        
+        if self.jobs.jobs().is_empty() {
+            ui.label(tr!("jobs-empty"));
+            return;
+        }
+        
+        let mut cancel = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("jobs").striped(true).num_columns(6).show(ui, |ui| {
+                ui.strong("#");
+                ui.strong(tr!("jobs-kind"));
+                ui.strong(tr!("jobs-job"));
+                ui.strong(tr!("jobs-status"));
+                ui.strong(tr!("jobs-elapsed"));
+                ui.label("");
+                ui.end_row();
+                
+                for job in self.jobs.jobs().iter().rev() {
+                    ui.label(job.id.to_string());
+                    ui.label(job.kind.label());
+                    ui.label(&job.label);
+                    match &job.status {
+                        JobStatus::Running => render_job_progress(ui, job),
+                        JobStatus::Completed => {
+                            ui.label(format!("✅ {}", tr!("jobs-completed")));
+                        }
+                        JobStatus::Failed(error) => {
+                            ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                        }
+                        JobStatus::Cancelled => {
+                            ui.label(format!("⛔ {}", tr!("jobs-cancelled")));
+                        }
+                    }
+                    ui.label(format!("{:.1}s", job.elapsed().as_secs_f32()));
+                    if job.is_running() && ui.button(tr!("button-cancel")).clicked() {
+                        cancel = Some(job.id);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        
+        if let Some(id) = cancel {
+            self.cancel_job(id);
+        }
     }
     
-    fn render_call_records(&mut self, ui: &mut egui::Ui) {
-        if self.call_records.is_empty() {
+    /// The subscriber records delivered with the productions, and how many of the case's calls
+    /// each number takes part in, as the target or the other party.
+    fn render_subscribers(&mut self, ui: &mut egui::Ui) {
+        let matches = match_subscribers(&self.parse_reports, &self.call_records);
+        if matches.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(tr!("subscribers-empty"));
+            });
+            return;
+        }
+        
+        ui.label(tr!("subscribers-heading", count = matches.len()));
+        ui.add_space(10.0);
+        
+        let mut open = None;
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("subscriber_info").striped(true).spacing([15.0, 4.0]).show(ui, |ui| {
+                ui.strong(tr!("header-phone-number"));
+                ui.strong(tr!("subscribers-as-target"));
+                ui.strong(tr!("subscribers-as-contact"));
+                ui.strong(tr!("subscribers-name"));
+                ui.strong(tr!("subscribers-address"));
+                ui.strong(tr!("subscribers-account"));
+                ui.strong(tr!("subscribers-email"));
+                ui.strong(tr!("subscribers-account-start"));
+                ui.strong(tr!("subscribers-account-end"));
+                ui.strong(tr!("header-source-file"));
+                ui.end_row();
+                for subscriber in &matches {
+                    let info = subscriber.info;
+                    if ui.link(&info.number).clicked() {
+                        open = Some(info.number.clone());
+                    }
+                    ui.label(i18n::format_count(subscriber.as_target));
+                    ui.label(i18n::format_count(subscriber.as_contact));
+                    ui.label(&info.name);
+                    ui.label(&info.address);
+                    ui.label(&info.account_number);
+                    ui.label(&info.email);
+                    ui.label(&info.account_start);
+                    ui.label(&info.account_end);
+                    ui.label(subscriber.source_file);
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(number) = open {
+            self.open_detail_view(DetailView::Contact(number));
+        }
+    }
+    
+    fn render_data_quality(&mut self, ui: &mut egui::Ui) {
+        if self.data_quality.files.is_empty() {
             ui.centered_and_justified(|ui| {
-                ui.label("No call records loaded. Please process an XML file first.");
+                ui.label(tr!("quality-empty"));
+            });
+            return;
+        }
+        
+        ui.horizontal(|ui| {
+            ui.label(tr!("quality-heading"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr!("summary-copy")).clicked() {
+                    ui.output_mut(|o| o.copied_text = self.data_quality.to_text());
+                    self.add_message(Message::Success(tr!("msg-report-copied")));
+                }
+            });
+        });
+        ui.add_space(10.0);
+        
+        let quality_row = |ui: &mut egui::Ui, file: &FileQuality| {
+            let percent = |count: usize| format!("{} ({:.1}%)", i18n::format_count(count), file.percent(count));
+            ui.label(&file.source_file);
+            ui.label(i18n::format_count(file.total_records));
+            ui.label(i18n::format_count(file.loaded_records()));
+            ui.label(percent(file.parse_failures));
+            ui.label(percent(file.valid_timestamps));
+            ui.label(percent(file.with_direction));
+            ui.label(percent(file.zero_duration));
+            ui.label(percent(file.unusable_numbers));
+            ui.label(percent(file.duplicates));
+            ui.label(percent(file.collapsed_duplicates));
+            ui.end_row();
+        };
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("data_quality").striped(true).spacing([15.0, 4.0]).show(ui, |ui| {
+                ui.strong(tr!("header-source-file"));
+                ui.strong(tr!("quality-records"));
+                ui.strong(tr!("quality-loaded"));
+                ui.strong(tr!("quality-parse-failures"));
+                ui.strong(tr!("quality-valid-timestamps"));
+                ui.strong(tr!("quality-with-direction"));
+                ui.strong(tr!("quality-zero-duration"));
+                ui.strong(tr!("quality-unusable-numbers"));
+                ui.strong(tr!("quality-duplicates"));
+                ui.strong(tr!("quality-collapsed-duplicates")).on_hover_text(tr!("quality-collapsed-duplicates-hint"));
+                ui.end_row();
+                for file in &self.data_quality.files {
+                    quality_row(ui, file);
+                }
+                if self.data_quality.files.len() > 1 {
+                    quality_row(ui, &self.data_quality.overall);
+                }
+            });
+        });
+        
+        if self.parse_reports.iter().any(|report| report.format.is_some()) {
+            ui.add_space(20.0);
+            self.render_production_formats(ui);
+        }
+        
+        if !self.data_quality.number_warnings.is_empty() {
+            ui.add_space(20.0);
+            self.render_number_warnings(ui);
+        }
+        
+        if !self.data_quality.record_validation.is_empty() {
+            ui.add_space(20.0);
+            self.render_record_warnings(ui);
+        }
+        
+        if !self.data_quality.implausible_moves.is_empty() {
+            ui.add_space(20.0);
+            ui.heading(tr!("heading-implausible-moves"));
+            egui::ScrollArea::vertical().id_source("implausible_moves").max_height(300.0).show(ui, |ui| {
+                self.implausible_moves_grid(ui, "quality_implausible_moves", &self.data_quality.implausible_moves);
+            });
+        }
+        
+        if self.sources.len() > 1 {
+            ui.add_space(20.0);
+            self.render_production_diff_picker(ui);
+        }
+    }
+    
+    /// The parser each production was read with and how sure detection was, with a way to
+    /// read one again with another parser.
+    fn render_production_formats(&mut self, ui: &mut egui::Ui) {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut reparse: Option<(String, ParserKind)> = None;
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut map_columns: Option<String> = None;
+        
+        ui.heading(tr!("heading-production-formats"));
+        ui.small(tr!("production-formats-hint"));
+        egui::Grid::new("production_formats").striped(true).spacing([15.0, 4.0]).show(ui, |ui| {
+            ui.strong(tr!("header-source-file"));
+            ui.strong(tr!("header-parser"));
+            ui.strong(tr!("header-confidence"));
+            #[cfg(not(target_arch = "wasm32"))]
+            ui.label("");
+            ui.end_row();
+            for report in &self.parse_reports {
+                let Some(format) = report.format else {
+                    continue;
+                };
+                ui.label(&report.source_file);
+                ui.label(format.parser.label());
+                match format.confidence {
+                    None => {
+                        ui.weak(tr!("confidence-chosen"));
+                    }
+                    Some(confidence) => {
+                        let color = match confidence {
+                            DetectionConfidence::High => egui::Color32::from_rgb(0, 160, 0),
+                            DetectionConfidence::Medium => egui::Color32::from_rgb(200, 120, 0),
+                            DetectionConfidence::Low => egui::Color32::from_rgb(200, 0, 0),
+                        };
+                        ui.colored_label(color, format!("● {}", confidence.label()));
+                    }
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                egui::ComboBox::from_id_source(("reparse", &report.source_file))
+                    .selected_text(tr!("button-reparse-as"))
+                    .show_ui(ui, |ui| {
+                        for parser in ParserKind::all() {
+                            if ui.selectable_label(parser == format.parser, parser.label()).clicked() {
+                                reparse = Some((report.source_file.clone(), parser));
+                            }
+                        }
+                        if matches!(format.parser, ParserKind::Delimited(_) | ParserKind::Csv(_)) {
+                            ui.separator();
+                            let mapped = matches!(format.parser, ParserKind::Csv(_));
+                            if ui.selectable_label(mapped, tr!("button-map-columns")).clicked() {
+                                map_columns = Some(report.source_file.clone());
+                            }
+                        }
+                    });
+                ui.end_row();
+            }
+        });
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((source_file, parser)) = reparse {
+            self.reparse_production(&source_file, parser);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(source_file) = map_columns {
+            self.map_production_columns(&source_file);
+        }
+    }
+    
+    /// Records that read but look wrong, with the choice of leaving them out of the analytics.
+    fn render_record_warnings(&mut self, ui: &mut egui::Ui) {
+        let mut detail = None;
+        let validation = &self.data_quality.record_validation;
+        
+        ui.heading(tr!("heading-record-warnings"));
+        ui.small(tr!("record-warnings-hint"));
+        ui.horizontal_wrapped(|ui| {
+            for issue in RecordIssue::ALL.into_iter().filter(|&issue| validation.count(issue) > 0) {
+                ui.label(format!("{}: {}", issue.label(), i18n::format_count(validation.count(issue))));
+                ui.separator();
+            }
+        });
+        let excluded = self.analytics.as_ref().map_or(0, |analytics| analytics.flagged_records_excluded);
+        let toggled = ui.checkbox(&mut self.settings.exclude_flagged_records, tr!("record-warnings-exclude",
+            count = i18n::format_count(validation.flagged))).changed();
+        if self.settings.exclude_flagged_records && excluded > 0 {
+            ui.small(tr!("record-warnings-excluded", count = i18n::format_count(excluded)));
+        }
+        if validation.records.len() < validation.flagged {
+            ui.small(tr!("record-warnings-truncated", shown = i18n::format_count(validation.records.len())));
+        }
+        egui::ScrollArea::vertical().id_source("record_warnings").max_height(300.0).show(ui, |ui| {
+            egui::Grid::new("record_warnings").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-date-time"));
+                ui.strong(tr!("header-target"));
+                ui.strong(tr!("header-remote-number"));
+                ui.strong(tr!("header-duration-sec"));
+                ui.strong(tr!("header-source-file"));
+                ui.strong(tr!("header-reason"));
+                ui.end_row();
+                
+                for (record, issues) in &validation.records {
+                    if ui.link(self.settings.format_local_time(&record.start_time)).on_hover_text(tr!("bookmarks-show-details")).clicked() {
+                        detail = Some(DetailView::Record(record.record_key()));
+                    }
+                    ui.label(self.settings.format_number(&record.target_number));
+                    ui.label(&record.remote_number);
+                    ui.label(record.length_of_call.to_string());
+                    ui.label(&record.source_file);
+                    ui.label(issues.iter().map(RecordIssue::label).collect::<Vec<_>>().join(", "));
+                    ui.end_row();
+                }
+            });
+        });
+        
+        if toggled {
+            if let Err(e) = self.settings.save() {
+                error!("Failed to save settings: {:#}", e);
+            }
+            self.reanalyze_if_stale();
+        }
+        if let Some(view) = detail {
+            self.open_detail_view(view);
+        }
+    }
+    
+    /// Remote numbers that look like junk, each with a way to leave it out of the analytics
+    /// or to count it as another kind of number.
+    fn render_number_warnings(&mut self, ui: &mut egui::Ui) {
+        let mut excluded = None;
+        let mut reclassified = None;
+        
+        ui.heading(tr!("heading-number-warnings"));
+        ui.small(tr!("number-warnings-hint"));
+        egui::ScrollArea::vertical().id_source("number_warnings").max_height(300.0).show(ui, |ui| {
+            egui::Grid::new("number_warnings").striped(true).show(ui, |ui| {
+                ui.strong(tr!("header-remote-number"));
+                ui.strong(tr!("header-reason"));
+                ui.strong(tr!("header-call-count"));
+                ui.strong(tr!("header-counted-as"));
+                ui.label("");
+                ui.end_row();
+                
+                for warning in &self.data_quality.number_warnings {
+                    ui.label(&warning.example);
+                    ui.label(warning.issue.label());
+                    ui.label(i18n::format_count(warning.calls));
+                    let current = self.annotations.reclassified_numbers.get(&warning.number).copied().unwrap_or(warning.kind);
+                    egui::ComboBox::from_id_source(("number_kind", &warning.number))
+                        .selected_text(current.label())
+                        .show_ui(ui, |ui| {
+                            for kind in NumberKind::ALL {
+                                if ui.selectable_label(kind == current, kind.label()).clicked() && kind != current {
+                                    reclassified = Some((warning.number.clone(), warning.kind, kind));
+                                }
+                            }
+                        });
+                    if self.settings.excluded_numbers.contains(&warning.number) {
+                        ui.label(tr!("number-warnings-excluded"));
+                    } else if ui.button(tr!("number-warnings-exclude")).on_hover_text(tr!("number-warnings-exclude-hint")).clicked() {
+                        excluded = Some(warning.number.clone());
+                    }
+                    ui.end_row();
+                }
             });
+        });
+        
+        if let Some((number, parsed_kind, kind)) = reclassified {
+            let before = self.annotations.clone();
+            if kind == parsed_kind {
+                self.annotations.reclassified_numbers.remove(&number);
+            } else {
+                self.annotations.reclassified_numbers.insert(number.clone(), kind);
+            }
+            self.annotations_changed(tr!("change-reclassify-number", number = number, kind = kind.label()), before);
+        }
+        if let Some(number) = excluded {
+            self.settings.excluded_numbers.push(number.clone());
+            if let Err(e) = self.settings.save() {
+                error!("Failed to save settings: {:#}", e);
+            }
+            self.add_message(Message::Info(tr!("msg-number-excluded", number = number)));
+            self.reanalyze_if_stale();
+        }
+    }
+    
+    /// Picks an original production and its re-production to diff.
+    fn render_production_diff_picker(&mut self, ui: &mut egui::Ui) {
+        let mut compare = false;
+        let mut save = false;
+        ui.heading(tr!("diff-heading"));
+        ui.small(tr!("diff-hint"));
+        ui.horizontal(|ui| {
+            let names: Vec<String> = self.sources.iter().map(|s| s.name.clone()).collect();
+            for (label, id, selected) in [
+                (tr!("diff-original-label"), "diff_original", &mut self.diff_original),
+                (tr!("diff-corrected-label"), "diff_corrected", &mut self.diff_corrected),
+            ] {
+                ui.label(label);
+                egui::ComboBox::from_id_source(id)
+                    .selected_text(selected.as_str())
+                    .show_ui(ui, |ui| {
+                        for name in &names {
+                            ui.selectable_value(selected, name.clone(), name);
+                        }
+                    });
+            }
+            let loaded = |name: &str| self.sources.iter().any(|s| s.name == name);
+            let ready = self.diff_original != self.diff_corrected && loaded(&self.diff_original) && loaded(&self.diff_corrected);
+            if ui.add_enabled(ready && !self.workspace_busy(), egui::Button::new(tr!("diff-compare"))).clicked() {
+                compare = true;
+            }
+            if self.production_diff.is_some() {
+                if ui.button(tr!("diff-show")).clicked() {
+                    self.open_detail_view(DetailView::ProductionDiff);
+                }
+                if ui.button(tr!("diff-save-report")).clicked() {
+                    save = true;
+                }
+            }
+        });
+        if compare {
+            self.diff_productions();
+        }
+        if save {
+            self.save_diff_report();
+        }
+    }
+    
+    fn diff_productions(&mut self) {
+        let records = Arc::clone(&self.call_records);
+        let original = self.diff_original.clone();
+        let corrected = self.diff_corrected.clone();
+        self.jobs.spawn(JobKind::Compare, tr!("job-diff-productions", original = original.clone(), corrected = corrected.clone()), move |job| {
+            job.set_progress(None, tr!("progress-diffing-productions"));
+            let diff = ProductionDiff::compare(&records, &original, &corrected);
+            Ok(JobOutput::ProductionDiffed(Box::new(diff)))
+        });
+    }
+    
+    /// Writes the diff report next to where the Excel export would go, or downloads it in the browser.
+    fn save_diff_report(&mut self) {
+        let (Some(diff), Some(analytics)) = (&self.production_diff, &self.analytics) else {
             return;
+        };
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        let report_name = format!("{}_diff.txt", stem);
+        let text = diff.to_text(&self.settings);
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = std::fs::write(&report_name, text).map_err(anyhow::Error::from);
+        #[cfg(target_arch = "wasm32")]
+        let result = web::download(&report_name, "text/plain", text.as_bytes()).map_err(|e| anyhow::anyhow!("{:?}", e));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-report-saved", path = report_name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-report-save-failed", error = format!("{:#}", e)))),
         }
+    }
+    
+    fn render_logs(&mut self, ui: &mut egui::Ui) {
+        let search = self.log_view.search.to_lowercase();
+        let entries: Vec<LogEntry> = logging::recent_entries().into_iter()
+            .filter(|e| e.level <= self.log_view.min_level)
+            .filter(|e| search.is_empty()
+                || e.message.to_lowercase().contains(&search)
+                || e.module.to_lowercase().contains(&search))
+            .collect();
         
         ui.horizontal(|ui| {
-            ui.label(format!("Showing {} call records", self.call_records.len()));
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button("Export to Excel").clicked() {
-                    self.export_to_excel();
-                }
-            });
+            ui.label(tr!("logs-level"));
+            egui::ComboBox::from_id_source("log_level")
+                .selected_text(self.log_view.min_level.as_str())
+                .show_ui(ui, |ui| {
+                    for level in [LevelFilter::Error, LevelFilter::Warn, LevelFilter::Info, LevelFilter::Debug, LevelFilter::Trace] {
+                        ui.selectable_value(&mut self.log_view.min_level, level, level.as_str());
+                    }
+                });
+            ui.label(tr!("logs-search"));
+            ui.text_edit_singleline(&mut self.log_view.search);
+            if ui.button(format!("📋 {}", tr!("logs-copy"))).on_hover_text(tr!("logs-copy-hint")).clicked() {
+                let text = entries.iter()
+                    .filter_map(|e| serde_json::to_string(e).ok())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ui.output_mut(|o| o.copied_text = text);
+            }
         });
+        match logging::log_file_path() {
+            Some(path) => ui.small(tr!("logs-file", path = path.display().to_string())),
+            None => ui.small(tr!("logs-no-file")),
+        };
+        ui.add_space(5.0);
         
-        egui::ScrollArea::vertical().max_height(600.0).show(ui, |ui| {
-            egui::Grid::new("call_records").striped(true).show(ui, |ui| {
-                // Headers
-                ui.strong("Direction");
-                ui.strong("Remote Number");
-                ui.strong("Normalized");
-                ui.strong("Date");
-                ui.strong("Time");
-                ui.strong("Duration (min)");
+        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+            egui::Grid::new("log_entries").striped(true).num_columns(5).show(ui, |ui| {
+                ui.strong(tr!("logs-time"));
+                ui.strong(tr!("logs-level-header"));
+                ui.strong(tr!("logs-module"));
+                ui.strong(tr!("logs-case"));
+                ui.strong(tr!("logs-message"));
                 ui.end_row();
                 
-                // Data (show first 100 records)
-                for record in self.call_records.iter().take(100) {
-                    ui.label(&record.message_direction);
-                    ui.label(&record.remote_number);
-                    ui.label(&record.normalized_number);
-                    ui.label(&record.date);
-                    ui.label(&record.time);
-                    ui.label(format!("{:.2}", record.duration_minutes));
+                for entry in &entries {
+                    ui.label(entry.timestamp.format("%H:%M:%S%.3f").to_string());
+                    let color = match entry.level {
+                        Level::Error => egui::Color32::RED,
+                        Level::Warn => egui::Color32::from_rgb(230, 160, 0),
+                        _ => ui.visuals().text_color(),
+                    };
+                    ui.colored_label(color, entry.level.as_str());
+                    ui.label(&entry.module);
+                    ui.label(entry.case_id.as_deref().unwrap_or("—"));
+                    ui.label(&entry.message);
                     ui.end_row();
                 }
             });
-            
-            if self.call_records.len() > 100 {
-                ui.label(format!("... and {} more records", self.call_records.len() - 100));
-            }
         });
     }
     
-    fn render_analytics(&mut self, ui: &mut egui::Ui) {
-        if let Some(analytics) = &self.analytics {
-            ui.vertical(|ui| {
-                ui.heading("Analytics Dashboard");
+    fn render_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("tab-settings"));
+        match AppSettings::config_path() {
+            Some(path) => ui.label(tr!("settings-stored-in", path = path.display().to_string())),
+            None => ui.label(tr!("settings-no-config-dir")),
+        };
+        ui.add_space(10.0);
+        
+        let mut save = false;
+        let mut reload = false;
+        let mut restore_defaults = false;
+        let mut clear_cache = false;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut verify = false;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut forget_lookups = false;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut remove_known_list = None;
+        let mut remove_excluded = None;
+        let mut remove_pdf_pattern = None;
+        let known_number_lists = self.settings.known_number_lists.clone();
+        
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("settings").num_columns(2).spacing([20.0, 8.0]).show(ui, |ui| {
+                ui.label(tr!("settings-language"));
+                egui::ComboBox::from_id_source("settings_language")
+                    .selected_text(self.settings.language.label())
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(&mut self.settings.language, language, language.label());
+                        }
+                    });
+                ui.end_row();
                 
-                // Summary cards
+                ui.label(tr!("settings-timezone"));
+                egui::ComboBox::from_id_source("settings_timezone")
+                    .selected_text(self.settings.case_timezone.name())
+                    .show_ui(ui, |ui| {
+                        for tz in COMMON_TIMEZONES {
+                            ui.selectable_value(&mut self.settings.case_timezone, tz, tz.name());
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(tr!("settings-number-format"));
+                egui::ComboBox::from_id_source("settings_phone_format")
+                    .selected_text(self.settings.phone_format.label())
+                    .show_ui(ui, |ui| {
+                        for format in PhoneFormat::ALL {
+                            ui.selectable_value(&mut self.settings.phone_format, format, format.label());
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(tr!("settings-default-region"));
+                egui::ComboBox::from_id_source("settings_default_region")
+                    .selected_text(self.settings.default_region.label())
+                    .show_ui(ui, |ui| {
+                        for region in PhoneRegion::ALL {
+                            ui.selectable_value(&mut self.settings.default_region, region, region.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("settings-default-region-hint"));
+                ui.end_row();
+                
+                ui.label(tr!("settings-night-hours"));
                 ui.horizontal(|ui| {
-                    self.render_stat_card(ui, "Total Calls", &analytics.total_calls.to_string(), "📞");
-                    self.render_stat_card(ui, "Incoming", &analytics.incoming_calls.to_string(), "📥");
-                    self.render_stat_card(ui, "Outgoing", &analytics.outgoing_calls.to_string(), "📤");
-                    self.render_stat_card(ui, "Unique Numbers", &analytics.unique_numbers.to_string(), "👥");
+                    ui.label(tr!("settings-night-from"));
+                    ui.add(egui::DragValue::new(&mut self.settings.night_hours.0).clamp_range(0..=23).suffix(":00"));
+                    ui.label(tr!("settings-night-to"));
+                    ui.add(egui::DragValue::new(&mut self.settings.night_hours.1).clamp_range(0..=23).suffix(":00"));
                 });
+                ui.end_row();
                 
-                ui.horizontal(|ui| {
-                    self.render_stat_card(ui, "Total Duration", &format!("{:.1} min", analytics.total_duration_minutes), "⏱️");
-                    self.render_stat_card(ui, "Avg Duration", &format!("{:.1} min", analytics.average_call_duration), "📊");
+                ui.label(tr!("settings-top-n"));
+                ui.add(egui::DragValue::new(&mut self.settings.top_n).clamp_range(1..=100));
+                ui.end_row();
+                
+                ui.label(tr!("settings-unanswered-seconds"));
+                ui.add(egui::DragValue::new(&mut self.settings.unanswered_max_seconds).clamp_range(0..=60).suffix(" s"))
+                    .on_hover_text(tr!("settings-unanswered-seconds-hint"));
+                ui.end_row();
+                
+                ui.label(tr!("settings-short-codes"));
+                ui.checkbox(&mut self.settings.include_short_codes, tr!("settings-short-codes-include"))
+                    .on_hover_text(tr!("settings-short-codes-hint"));
+                ui.end_row();
+                
+                ui.label(tr!("settings-export-name"));
+                ui.vertical(|ui| {
+                    ui.text_edit_singleline(&mut self.settings.export_template);
+                    ui.small(tr!(
+                        "settings-export-name-hint",
+                        example = self.settings.export_file_name(&Utc::now(), "2565550100")
+                    ));
                 });
+                ui.end_row();
                 
-                ui.add_space(20.0);
+                ui.label(tr!("settings-watch-folder"));
+                ui.text_edit_singleline(&mut self.settings_form.watch_folder)
+                    .on_hover_text(tr!("settings-watch-folder-hint"));
+                ui.end_row();
                 
-                // Most frequent numbers
-                ui.heading("Most Frequent Numbers");
-                egui::Grid::new("frequent_numbers").striped(true).show(ui, |ui| {
-                    ui.strong("Rank");
-                    ui.strong("Phone Number");
-                    ui.strong("Call Count");
+                ui.label(tr!("settings-export-locale"));
+                ui.vertical(|ui| {
+                    let mut follow_language = self.settings.export_locale.is_none();
+                    if ui.checkbox(&mut follow_language, tr!("settings-export-locale-language")).changed() {
+                        self.settings.export_locale = (!follow_language).then(|| self.settings.language.export_locale());
+                    }
+                    if let Some(locale) = &mut self.settings.export_locale {
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source("settings_date_order")
+                                .selected_text(locale.date_order.label())
+                                .show_ui(ui, |ui| {
+                                    for order in DateOrder::ALL {
+                                        ui.selectable_value(&mut locale.date_order, order, order.label());
+                                    }
+                                });
+                            egui::ComboBox::from_id_source("settings_decimal_separator")
+                                .selected_text(locale.decimal_separator.label())
+                                .show_ui(ui, |ui| {
+                                    for separator in DecimalSeparator::ALL {
+                                        ui.selectable_value(&mut locale.decimal_separator, separator, separator.label());
+                                    }
+                                });
+                            egui::ComboBox::from_id_source("settings_clock")
+                                .selected_text(locale.clock.label())
+                                .show_ui(ui, |ui| {
+                                    for clock in ClockFormat::ALL {
+                                        ui.selectable_value(&mut locale.clock, clock, clock.label());
+                                    }
+                                });
+                        });
+                    }
+                    let locale = self.settings.export_locale();
+                    ui.small(tr!("settings-export-locale-hint",
+                        example = format!("{} · {}", locale.format_date_time(&Utc::now()), locale.format_decimal(12.5, 2))));
+                });
+                ui.end_row();
+                
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.label(tr!("settings-excel-formats"));
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-excel-datetime"));
+                            ui.add(egui::TextEdit::singleline(&mut self.settings_form.excel_datetime_format)
+                                .hint_text(self.settings.export_locale().excel_datetime_format()));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-excel-duration"));
+                            ui.add(egui::TextEdit::singleline(&mut self.settings_form.excel_duration_format)
+                                .hint_text(self.settings.language.excel_duration_format()));
+                        });
+                        ui.small(tr!("settings-excel-formats-hint"));
+                    });
                     ui.end_row();
                     
-                    for (i, (number, count)) in analytics.most_frequent_numbers.iter().enumerate() {
-                        ui.label(format!("{}", i + 1));
-                        ui.label(number);
-                        ui.label(count.to_string());
+                    ui.label(tr!("settings-preview-threshold"));
+                    ui.add(egui::DragValue::new(&mut self.settings.preview_threshold_mb).clamp_range(0..=100_000).suffix(" MB"))
+                        .on_hover_text(tr!("settings-preview-threshold-hint"));
+                    ui.end_row();
+                    
+                    ui.label(tr!("settings-auto-export"));
+                    ui.checkbox(&mut self.settings.auto_export, tr!("settings-auto-export-enabled"));
+                    ui.end_row();
+                    
+                    ui.label(tr!("settings-auto-export-dir"));
+                    ui.add_enabled(self.settings.auto_export, egui::TextEdit::singleline(&mut self.settings_form.auto_export_dir))
+                        .on_hover_text(tr!("settings-auto-export-dir-hint"));
+                    ui.end_row();
+                    
+                    ui.label(tr!("settings-signing"));
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut self.settings.sign_exports, tr!("settings-sign-exports"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-signing-key"));
+                            ui.text_edit_singleline(&mut self.settings_form.signing_key_path)
+                                .on_hover_text(tr!("settings-signing-key-hint"));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-verify-file"));
+                            ui.text_edit_singleline(&mut self.settings_form.verify_path);
+                            let can_verify = !self.settings_form.verify_path.trim().is_empty()
+                                && !self.settings_form.signing_key_path.trim().is_empty();
+                            verify = ui.add_enabled(can_verify, egui::Button::new(tr!("settings-verify"))).clicked();
+                        });
+                    });
+                    ui.end_row();
+                    
+                    ui.label(tr!("settings-carriers"));
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-carrier-assignments"));
+                            ui.text_edit_singleline(&mut self.settings_form.carrier_assignments_path)
+                                .on_hover_text(tr!("settings-carrier-assignments-hint"));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-ported-numbers"));
+                            ui.text_edit_singleline(&mut self.settings_form.ported_numbers_path)
+                                .on_hover_text(tr!("settings-ported-numbers-hint"));
+                        });
+                    });
+                    ui.end_row();
+                    
+                    ui.label(tr!("settings-cnam"));
+                    ui.vertical(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-cnam-file"));
+                            ui.text_edit_singleline(&mut self.settings_form.cnam_path)
+                                .on_hover_text(tr!("settings-cnam-file-hint"));
+                        });
+                        ui.checkbox(&mut self.settings.cnam_lookup_enabled, tr!("settings-cnam-lookup-enabled"))
+                            .on_hover_text(tr!("settings-cnam-lookup-warning"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("settings-cnam-lookup-url"));
+                            ui.add_enabled(self.settings.cnam_lookup_enabled, egui::TextEdit::singleline(&mut self.settings_form.cnam_lookup_url)
+                                .hint_text("https://cnam.example.com/lookup?number={number}"));
+                        });
+                        if ui.button(tr!("settings-cnam-forget")).on_hover_text(tr!("settings-cnam-forget-hint")).clicked() {
+                            forget_lookups = true;
+                        }
+                    });
+                    ui.end_row();
+                    
+                    #[cfg(feature = "google-sheets")]
+                    {
+                        ui.label(tr!("settings-google-sheets"));
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("settings-google-sheets-credentials"));
+                                ui.text_edit_singleline(&mut self.settings_form.google_sheets_credentials)
+                                    .on_hover_text(tr!("settings-google-sheets-credentials-hint"));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("settings-google-sheet-id"));
+                                ui.text_edit_singleline(&mut self.settings_form.google_sheet_id)
+                                    .on_hover_text(tr!("settings-google-sheet-id-hint"));
+                            });
+                        });
                         ui.end_row();
                     }
+                    
+                    ui.label(tr!("settings-known-numbers"));
+                    ui.vertical(|ui| {
+                        ui.small(tr!("settings-known-numbers-hint"));
+                        for (i, path) in self.settings.known_number_lists.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(path.display().to_string());
+                                if ui.small_button("✖").on_hover_text(tr!("settings-remove")).clicked() {
+                                    remove_known_list = Some(i);
+                                }
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut self.settings_form.new_known_number_list);
+                            let path = self.settings_form.new_known_number_list.trim();
+                            if ui.add_enabled(!path.is_empty(), egui::Button::new(tr!("settings-add"))).clicked() {
+                                let path = PathBuf::from(path);
+                                if !self.settings.known_number_lists.contains(&path) {
+                                    self.settings.known_number_lists.push(path);
+                                }
+                                self.settings_form.new_known_number_list.clear();
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+                
+                ui.label(tr!("settings-excluded"));
+                ui.vertical(|ui| {
+                    ui.small(tr!("settings-excluded-hint"));
+                    for (i, number) in self.settings.excluded_numbers.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(self.settings.format_number(number));
+                            if ui.small_button("✖").on_hover_text(tr!("settings-remove")).clicked() {
+                                remove_excluded = Some(i);
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        let input = ui.text_edit_singleline(&mut self.settings_form.new_excluded_number);
+                        let submitted = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button(tr!("settings-add")).clicked() || submitted {
+                            let number = std::mem::take(&mut self.settings_form.new_excluded_number);
+                            self.settings.exclude_number(&number);
+                        }
+                    });
                 });
+                ui.end_row();
                 
-                ui.add_space(20.0);
+                ui.label(tr!("settings-xml-recovery"));
+                ui.checkbox(&mut self.settings.recover_malformed_xml, tr!("settings-xml-recovery-enabled"))
+                    .on_hover_text(tr!("settings-xml-recovery-hint"));
+                ui.end_row();
                 
-                // Calls by day
-                ui.heading("Calls by Day");
-                let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
-                sorted_days.sort_by(|a, b| a.0.cmp(b.0));
+                ui.label(tr!("settings-cross-file-duplicates"));
+                ui.checkbox(&mut self.settings.collapse_cross_file_duplicates, tr!("settings-cross-file-duplicates-enabled"))
+                    .on_hover_text(tr!("settings-cross-file-duplicates-hint"));
+                ui.end_row();
                 
-                egui::Grid::new("calls_by_day").striped(true).show(ui, |ui| {
-                    ui.strong("Date");
-                    ui.strong("Call Count");
-                    ui.end_row();
-                    
-                    for (day, count) in sorted_days {
-                        ui.label(day);
-                        ui.label(count.to_string());
-                        ui.end_row();
+                ui.label(tr!("settings-pdf-patterns"));
+                ui.vertical(|ui| {
+                    ui.small(tr!("settings-pdf-patterns-hint"));
+                    for (i, pattern) in self.settings.pdf_patterns.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut pattern.name).desired_width(180.0));
+                            ui.checkbox(&mut pattern.day_first, tr!("column-mapping-day-first"));
+                            if ui.small_button("✖").on_hover_text(tr!("settings-remove")).clicked() {
+                                remove_pdf_pattern = Some(i);
+                            }
+                        });
+                        ui.add(egui::TextEdit::singleline(&mut pattern.pattern).code_editor().desired_width(f32::INFINITY));
+                        if let Err(e) = pattern.compile() {
+                            ui.colored_label(egui::Color32::from_rgb(200, 0, 0), e.to_string());
+                        }
+                        ui.add_space(4.0);
                     }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("settings-pdf-patterns-add")).clicked() {
+                            self.settings.pdf_patterns.push(PdfPattern::default());
+                        }
+                        if ui.button(tr!("settings-pdf-patterns-restore")).clicked() {
+                            self.settings.pdf_patterns = PdfPattern::defaults();
+                        }
+                    });
                 });
+                ui.end_row();
             });
-        } else {
-            ui.centered_and_justified(|ui| {
-                ui.label("No analytics available. Please process an XML file first.");
+            
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                save = ui.button(format!("💾 {}", tr!("settings-save"))).clicked();
+                reload = ui.button(tr!("settings-reload")).clicked();
+                restore_defaults = ui.button(tr!("settings-restore-defaults")).clicked();
+                if self.analytics_cache.is_some() {
+                    clear_cache = ui.button(tr!("settings-clear-cache"))
+                        .on_hover_text(tr!("settings-clear-cache-hint"))
+                        .clicked();
+                }
+            });
+        });
+        
+        if let Some(i) = remove_excluded {
+            self.settings.excluded_numbers.remove(i);
+        }
+        if let Some(i) = remove_pdf_pattern {
+            self.settings.pdf_patterns.remove(i);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(i) = remove_known_list {
+            self.settings.known_number_lists.remove(i);
+        }
+        if reload {
+            self.settings = AppSettings::load();
+            self.settings_form = SettingsForm::from_settings(&self.settings);
+        }
+        if restore_defaults {
+            // Keep the walkthrough from reappearing just because the defaults were restored
+            // Keep the user's scripts too; they are not preferences
+            let tutorial_completed = self.settings.tutorial_completed;
+            let scripts = std::mem::take(&mut self.settings.scripts);
+            self.settings = AppSettings { tutorial_completed, scripts, ..AppSettings::default() };
+            self.settings_form = SettingsForm::from_settings(&self.settings);
+        }
+        if save {
+            self.save_settings();
+        }
+        if clear_cache {
+            self.clear_analytics_cache();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if verify {
+            self.verify_signature();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if forget_lookups {
+            self.forget_cnam_lookups();
+        }
+        // Unlike other settings, list changes apply at once so the tags can be checked before saving
+        if self.settings.known_number_lists != known_number_lists {
+            self.reload_known_numbers();
+        }
+    }
+    
+    /// The summary report in the layout arranged on the Summary tab.
+    fn compose_report(&self, analytics: &Analytics) -> Report {
+        let input = ReportInput {
+            analytics,
+            records: &self.call_records,
+            annotations: &self.annotations,
+            settings: &self.settings,
+            parse_reports: &self.parse_reports,
+        };
+        Report::compose(&self.settings.report_sections, &input)
+    }
+    
+    /// The report's file name, next to where the Excel export would go.
+    fn report_file_name(&self, analytics: &Analytics, format: ReportFormat) -> String {
+        let file_name = self.export_file_name(analytics);
+        let stem = file_name.strip_suffix(".xlsx").unwrap_or(&file_name);
+        format!("{}_report.{}", stem, format.extension())
+    }
+    
+    /// The email digest, listing the Excel export and the PDF report as attachments.
+    fn email_digest(&self, analytics: &Analytics) -> String {
+        let input = ReportInput {
+            analytics,
+            records: &self.call_records,
+            annotations: &self.annotations,
+            settings: &self.settings,
+            parse_reports: &self.parse_reports,
+        };
+        let attachments = [self.export_file_name(analytics), self.report_file_name(analytics, ReportFormat::Pdf)];
+        report::email_digest(&input, &attachments)
+    }
+    
+    /// Writes the report next to where the Excel export would go, or downloads it in the browser.
+    fn save_report(&mut self, report: &Report, format: ReportFormat) {
+        let Some(analytics) = &self.analytics else {
+            return;
+        };
+        let report_name = self.report_file_name(analytics, format);
+        let bytes = report.render(format);
+        
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = std::fs::write(&report_name, bytes).map_err(anyhow::Error::from);
+        #[cfg(target_arch = "wasm32")]
+        let result = web::download(&report_name, format.mime_type(), &bytes).map_err(|e| anyhow::anyhow!("{:?}", e));
+        match result {
+            Ok(()) => self.add_message(Message::Success(tr!("msg-report-saved", path = report_name))),
+            Err(e) => self.add_message(Message::Error(tr!("msg-report-save-failed", error = format!("{:#}", e)))),
+        }
+    }
+    
+    fn render_report_sections(&mut self, ui: &mut egui::Ui) {
+        enum Change {
+            Remove(usize),
+            Add(ReportSection),
+            Swap(usize, usize),
+        }
+        let mut change = None;
+        let sections = &self.settings.report_sections;
+        
+        ui.small(tr!("summary-sections-hint"));
+        for (i, section) in sections.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut true, section.label()).changed() {
+                    change = Some(Change::Remove(i));
+                }
+                if ui.add_enabled(i > 0, egui::Button::new("⬆").small()).clicked() {
+                    change = Some(Change::Swap(i, i - 1));
+                }
+                if ui.add_enabled(i + 1 < sections.len(), egui::Button::new("⬇").small()).clicked() {
+                    change = Some(Change::Swap(i, i + 1));
+                }
             });
         }
+        for section in ReportSection::ALL.iter().filter(|s| !sections.contains(s)) {
+            if ui.checkbox(&mut false, section.label()).changed() {
+                change = Some(Change::Add(*section));
+            }
+        }
+        
+        ui.horizontal(|ui| {
+            if ui.button(tr!("summary-sections-save")).clicked() {
+                match self.settings.save() {
+                    Ok(path) => self.add_message(Message::Success(tr!("msg-settings-saved", path = path.display().to_string()))),
+                    Err(e) => self.add_message(Message::Error(tr!("msg-settings-save-failed", error = format!("{:#}", e)))),
+                }
+            }
+            if ui.button(tr!("summary-sections-reset")).clicked() {
+                self.settings.report_sections = ReportSection::DEFAULT.to_vec();
+            }
+        });
+        
+        match change {
+            Some(Change::Remove(i)) => {
+                self.settings.report_sections.remove(i);
+            }
+            Some(Change::Add(section)) => self.settings.report_sections.push(section),
+            Some(Change::Swap(a, b)) => self.settings.report_sections.swap(a, b),
+            None => {}
+        }
+    }
+    
+    /// Editor for the narrative template; the report above follows it as it is typed.
+    fn render_report_template(&mut self, ui: &mut egui::Ui) {
+        let (unknown, findings) = self.analytics.as_ref()
+            .map(|analytics| {
+                let input = ReportInput {
+                    analytics,
+                    records: &self.call_records,
+                    annotations: &self.annotations,
+                    settings: &self.settings,
+                    parse_reports: &self.parse_reports,
+                };
+                (report::unknown_placeholders(&self.settings.report_template, &input), findings::key_findings_text(&input))
+            })
+            .unwrap_or_default();
+        let placeholders = report::TEMPLATE_PLACEHOLDERS.iter()
+            .map(|name| format!("{{{{{}}}}}", name))
+            .collect::<Vec<_>>()
+            .join(" ");
+        
+        ui.small(tr!("summary-template-hint"));
+        ui.small(tr!("summary-template-placeholders", placeholders = placeholders));
+        let was_empty = self.settings.report_template.trim().is_empty();
+        let editor = ui.add(egui::TextEdit::multiline(&mut self.settings.report_template)
+            .hint_text(tr!("summary-template-example"))
+            .desired_rows(6)
+            .desired_width(f32::INFINITY));
+        // A first template is put at the top of the report so typing it shows it at once
+        if editor.changed() && was_empty && !self.settings.report_template.trim().is_empty()
+            && !self.settings.report_sections.contains(&ReportSection::Narrative)
+        {
+            self.settings.report_sections.insert(0, ReportSection::Narrative);
+        }
+        if !unknown.is_empty() {
+            ui.colored_label(egui::Color32::from_rgb(200, 120, 0), tr!("summary-template-unknown", placeholders = unknown.join(", ")));
+        }
+        
+        ui.horizontal(|ui| {
+            if ui.button(tr!("summary-template-save")).clicked() {
+                match self.settings.save() {
+                    Ok(path) => self.add_message(Message::Success(tr!("msg-settings-saved", path = path.display().to_string()))),
+                    Err(e) => self.add_message(Message::Error(tr!("msg-settings-save-failed", error = format!("{:#}", e)))),
+                }
+            }
+            if ui.button(tr!("summary-template-insert-example")).clicked() {
+                self.settings.report_template = tr!("summary-template-example");
+                if !self.settings.report_sections.contains(&ReportSection::Narrative) {
+                    self.settings.report_sections.insert(0, ReportSection::Narrative);
+                }
+            }
+            // Findings go in as written text, not placeholders, so the analyst can edit them
+            if ui.add_enabled(!findings.is_empty(), egui::Button::new(tr!("summary-template-insert-findings")))
+                .on_hover_text(tr!("summary-template-insert-findings-hint"))
+                .clicked()
+            {
+                let template = &mut self.settings.report_template;
+                if !template.trim().is_empty() && !template.ends_with('\n') {
+                    template.push('\n');
+                }
+                template.push_str(&findings);
+                if !self.settings.report_sections.contains(&ReportSection::Narrative) {
+                    self.settings.report_sections.insert(0, ReportSection::Narrative);
+                }
+            }
+        });
     }
     
     fn render_summary(&mut self, ui: &mut egui::Ui) {
         if let Some(analytics) = &self.analytics {
-            let report = AnalyticsEngine::generate_summary_report(analytics);
+            let report = self.compose_report(analytics);
+            let text = report.to_text();
+            let mut save_as = None;
+            let mut copy_digest = false;
             
             ui.horizontal(|ui| {
-                ui.label("Summary Report");
+                ui.label(tr!("summary-heading"));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("Copy to Clipboard").clicked() {
-                        ui.output_mut(|o| o.copied_text = report.clone());
-                        self.add_message(Message::Success("Report copied to clipboard".to_string()));
+                    if ui.button(tr!("summary-copy")).clicked() {
+                        ui.output_mut(|o| o.copied_text = text.clone());
+                        self.add_message(Message::Success(tr!("msg-report-copied")));
+                    }
+                    if ui.button(tr!("summary-copy-digest")).on_hover_text(tr!("summary-copy-digest-hint")).clicked() {
+                        copy_digest = true;
+                    }
+                    for format in ReportFormat::ALL.iter().rev() {
+                        if ui.button(tr!("summary-save-as", format = format.label())).clicked() {
+                            save_as = Some(*format);
+                        }
                     }
                 });
             });
             
+            egui::CollapsingHeader::new(tr!("summary-sections")).show(ui, |ui| {
+                self.render_report_sections(ui);
+            });
+            egui::CollapsingHeader::new(tr!("summary-template")).show(ui, |ui| {
+                self.render_report_template(ui);
+            });
+            
+            if let Some(format) = save_as {
+                self.save_report(&report, format);
+            }
+            if copy_digest {
+                if let Some(analytics) = &self.analytics {
+                    let digest = self.email_digest(analytics);
+                    ui.output_mut(|o| o.copied_text = digest);
+                }
+                self.add_message(Message::Success(tr!("msg-digest-copied")));
+            }
+            
             egui::ScrollArea::vertical().max_height(600.0).show(ui, |ui| {
-                ui.add(egui::TextEdit::multiline(&mut report.as_str()).desired_width(f32::INFINITY));
+                ui.add(egui::TextEdit::multiline(&mut text.as_str()).desired_width(f32::INFINITY));
             });
         } else {
             ui.centered_and_justified(|ui| {
-                ui.label("No summary available. Please process an XML file first.");
+                ui.label(tr!("summary-empty"));
             });
         }
     }
@@ -445,4 +7421,307 @@ impl EsubpoenaApp {
             ui.add_space(10.0);
         });
     }
-} 
\ No newline at end of file
+}
+
+/// Reads a production held in memory with `parser`, e.g. a file dropped onto the browser build
+/// or an entry of a ZIP archive, with the duplicate and recovery options of `config`. LDS-101
+/// XML is read whole, keeping any duplicates.
+fn parse_production_bytes(
+    bytes: &[u8],
+    name: &str,
+    parser: ParserKind,
+    timezone: chrono_tz::Tz,
+    config: PipelineConfig,
+    pdf_patterns: &[PdfPattern],
+) -> anyhow::Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+    let duplicates = config.duplicates;
+    match parser {
+        ParserKind::Lds101Xml if config.recover_malformed => XmlParser::parse_content_recovering(&String::from_utf8_lossy(bytes), name),
+        ParserKind::Lds101Xml => XmlParser::parse_content_with_report(&String::from_utf8_lossy(bytes), name),
+        ParserKind::Json => JsonParser::parse_bytes(bytes, name, duplicates),
+        ParserKind::Lds102Xml => SubscriberParser::parse_bytes(bytes, name),
+        ParserKind::Delimited(profile) => DelimitedParser::parse_bytes(bytes, name, profile, timezone, duplicates),
+        ParserKind::Att => AttParser::parse_bytes(bytes, name, timezone, duplicates),
+        ParserKind::Verizon => VerizonParser::parse_bytes(bytes, name, timezone, duplicates),
+        ParserKind::TMobile => TMobileParser::parse_bytes(bytes, name, duplicates),
+        ParserKind::Csv(mapping) => CsvParser::parse_bytes(bytes, name, &mapping, timezone, duplicates),
+        #[cfg(not(target_arch = "wasm32"))]
+        ParserKind::Spreadsheet(sheet) => SpreadsheetParser::parse_bytes(bytes, name, &sheet, timezone, duplicates),
+        #[cfg(target_arch = "wasm32")]
+        ParserKind::Spreadsheet(_) => anyhow::bail!("Excel workbooks can only be read in the desktop app"),
+        ParserKind::Pdf => PdfParser::parse_bytes(bytes, name, pdf_patterns, timezone, duplicates),
+    }
+}
+
+/// A running job's progress bar with the estimated time left, or a spinner when the amount
+/// of work is unknown.
+fn render_job_progress(ui: &mut egui::Ui, job: &Job) {
+    let (fraction, message) = job.progress();
+    ui.horizontal(|ui| match fraction {
+        Some(fraction) => {
+            ui.add(egui::ProgressBar::new(fraction).text(message).desired_width(200.0));
+            if let Some(eta) = job.eta() {
+                ui.label(tr!("jobs-eta", time = format_eta(eta)));
+            }
+        }
+        None => {
+            ui.spinner();
+            ui.label(message);
+        }
+    });
+}
+
+/// Rounds a time left to what is worth showing: seconds under a minute, then minutes, then
+/// hours and minutes.
+fn format_eta(eta: Duration) -> String {
+    let seconds = eta.as_secs();
+    if seconds < 60 {
+        tr!("eta-seconds", count = seconds.max(1))
+    } else if seconds < 3600 {
+        tr!("eta-minutes", count = (seconds + 30) / 60)
+    } else {
+        tr!("eta-hours", hours = seconds / 3600, minutes = seconds % 3600 / 60)
+    }
+}
+
+/// Lays out `text` with any digits matching the search fragment highlighted.
+fn highlight_number(ui: &egui::Ui, text: &str, fragment: Option<&str>) -> egui::text::LayoutJob {
+    let ranges = fragment.map(|digits| digit_match_ranges(text, digits)).unwrap_or_default();
+    highlight_ranges(ui, text, ranges)
+}
+
+/// Lays out `text` with the given sorted, non-overlapping byte ranges highlighted.
+fn highlight_ranges(ui: &egui::Ui, text: &str, ranges: Vec<std::ops::Range<usize>>) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let normal = egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color());
+    let highlighted = egui::TextFormat {
+        background: ui.visuals().selection.bg_fill,
+        color: ui.visuals().strong_text_color(),
+        ..egui::TextFormat::simple(font_id, ui.visuals().text_color())
+    };
+    
+    let mut job = egui::text::LayoutJob::default();
+    let mut cursor = 0;
+    for range in ranges {
+        job.append(&text[cursor..range.start], 0.0, normal.clone());
+        job.append(&text[range.clone()], 0.0, highlighted.clone());
+        cursor = range.end;
+    }
+    job.append(&text[cursor..], 0.0, normal);
+    job
+}
+
+/// Narrowest slice of an axis, as a fraction of the whole, that a chart zooms into.
+const MIN_CHART_ZOOM: f32 = 0.002;
+
+/// Visible part of a chart's x axis as fractions of the whole axis, kept in egui's memory
+/// under the chart's id so the zoom lasts between frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ChartZoom {
+    start: f32,
+    end: f32,
+}
+
+impl Default for ChartZoom {
+    fn default() -> Self {
+        Self { start: 0.0, end: 1.0 }
+    }
+}
+
+impl ChartZoom {
+    /// Screen x of a position along the whole axis.
+    fn x_at(&self, rect: egui::Rect, fraction: f32) -> f32 {
+        rect.left() + rect.width() * (fraction - self.start) / (self.end - self.start)
+    }
+    
+    /// Position along the whole axis under screen x.
+    fn fraction_at(&self, rect: egui::Rect, x: f32) -> f32 {
+        self.start + (x - rect.left()) / rect.width() * (self.end - self.start)
+    }
+}
+
+/// Zoom and pan shared by the charts: drag across a range to zoom into it, scroll sideways
+/// (or Shift+scroll) to pan, right-click to zoom back out. Charts that zoom together, such as
+/// the rows of the call-durations chart, pass the same `id`.
+fn chart_zoom(ui: &egui::Ui, id: egui::Id, rect: egui::Rect, response: &egui::Response) -> ChartZoom {
+    let mut zoom: ChartZoom = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+    if response.secondary_clicked() {
+        zoom = ChartZoom::default();
+    }
+    
+    // The range being dragged across, as (anchor, pointer) along the whole axis
+    let drag_id = id.with("drag");
+    if let Some(pos) = response.interact_pointer_pos().filter(|_| response.drag_started() || response.dragged()) {
+        let current = zoom.fraction_at(rect, pos.x.clamp(rect.left(), rect.right()));
+        let anchor = if response.drag_started() {
+            current
+        } else {
+            ui.data(|d| d.get_temp::<(f32, f32)>(drag_id)).map_or(current, |(anchor, _)| anchor)
+        };
+        ui.data_mut(|d| d.insert_temp(drag_id, (anchor, current)));
+    }
+    if let Some((anchor, current)) = ui.data(|d| d.get_temp::<(f32, f32)>(drag_id)) {
+        let (from, to) = (anchor.min(current), anchor.max(current));
+        if response.drag_released() {
+            ui.data_mut(|d| d.remove::<(f32, f32)>(drag_id));
+            if to - from >= MIN_CHART_ZOOM {
+                zoom = ChartZoom { start: from.max(0.0), end: to.min(1.0) };
+            }
+        } else if response.dragged() {
+            let selection = egui::Rect::from_x_y_ranges(zoom.x_at(rect, from)..=zoom.x_at(rect, to), rect.y_range());
+            ui.painter_at(rect).rect_filled(selection, 0.0, ui.visuals().selection.bg_fill.gamma_multiply(0.4));
+        }
+    }
+    
+    if response.hovered() {
+        let scroll = ui.input(|i| if i.modifiers.shift { i.smooth_scroll_delta.x + i.smooth_scroll_delta.y } else { i.smooth_scroll_delta.x });
+        let span = zoom.end - zoom.start;
+        if scroll != 0.0 && span < 1.0 {
+            let shift = (-scroll / rect.width() * span).clamp(-zoom.start, 1.0 - zoom.end);
+            zoom.start += shift;
+            zoom.end += shift;
+        }
+    }
+    ui.data_mut(|d| d.insert_temp(id, zoom));
+    zoom
+}
+
+/// Draws a crosshair through `pos` across the chart.
+fn chart_crosshair(ui: &egui::Ui, rect: egui::Rect, pos: egui::Pos2) {
+    let painter = ui.painter_at(rect);
+    let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+    painter.vline(pos.x, rect.y_range(), stroke);
+    painter.hline(rect.x_range(), pos.y, stroke);
+}
+
+/// Draws one bar per bucket, scaled to the largest bar in view, with the shared zoom and a
+/// crosshair on the bar under the pointer. Returns the chart's response and that bucket.
+fn render_bar_chart(ui: &mut egui::Ui, id: egui::Id, counts: &[usize], height: f32) -> (egui::Response, Option<usize>) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::click_and_drag());
+    ui.painter_at(rect).rect_filled(rect, 3.0, ui.visuals().extreme_bg_color);
+    if counts.is_empty() {
+        return (response, None);
+    }
+    
+    let zoom = chart_zoom(ui, id, rect, &response);
+    let buckets = counts.len();
+    let first = ((zoom.start * buckets as f32).floor() as usize).min(buckets - 1);
+    let last = ((zoom.end * buckets as f32).ceil() as usize).clamp(first + 1, buckets);
+    let max_count = counts[first..last].iter().copied().max().unwrap_or(0).max(1);
+    let bar_top = |count: usize| rect.bottom() - rect.height() * (count as f32 / max_count as f32);
+    
+    let painter = ui.painter_at(rect);
+    for (i, count) in counts.iter().enumerate().take(last).skip(first) {
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(zoom.x_at(rect, i as f32 / buckets as f32) + 1.0, bar_top(*count)),
+            egui::pos2(zoom.x_at(rect, (i + 1) as f32 / buckets as f32) - 1.0, rect.bottom()),
+        );
+        painter.rect_filled(bar, 1.0, egui::Color32::from_rgb(100, 150, 255));
+    }
+    
+    let hovered = response.hover_pos()
+        .map(|pos| ((zoom.fraction_at(rect, pos.x) * buckets as f32).floor().max(0.0) as usize).min(buckets - 1));
+    if let (Some(bucket), Some(pos)) = (hovered, response.hover_pos()) {
+        chart_crosshair(ui, rect, egui::pos2(pos.x, bar_top(counts[bucket])));
+    }
+    (response, hovered)
+}
+
+/// Draws running totals as lines over a shared day axis, scaled to the largest total in view,
+/// with the shared zoom and a crosshair on the day under the pointer. Returns the chart's
+/// response and that day.
+fn render_cumulative_chart(ui: &mut egui::Ui, id: egui::Id, series: &[(egui::Color32, &[usize])], height: f32) -> (egui::Response, Option<usize>) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::click_and_drag());
+    ui.painter_at(rect).rect_filled(rect, 3.0, ui.visuals().extreme_bg_color);
+    let days = series.first().map_or(0, |(_, counts)| counts.len());
+    if days == 0 {
+        return (response, None);
+    }
+    
+    let zoom = chart_zoom(ui, id, rect, &response);
+    let last_day = (days.max(2) - 1) as f32;
+    let first = ((zoom.start * last_day).floor() as usize).min(days - 1);
+    let last = ((zoom.end * last_day).ceil() as usize).min(days - 1);
+    // Running totals only grow, so the last day in view holds the largest
+    let max_count = series.iter().map(|(_, counts)| counts[last]).max().unwrap_or(0).max(1);
+    let x_of = |day: usize| zoom.x_at(rect, day as f32 / last_day);
+    let y_of = |count: usize| rect.bottom() - rect.height() * (count as f32 / max_count as f32);
+    
+    let painter = ui.painter_at(rect);
+    for (color, counts) in series {
+        let points: Vec<egui::Pos2> = (first..=last).map(|day| egui::pos2(x_of(day), y_of(counts[day]))).collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(2.0, *color)));
+    }
+    
+    let hovered = response.hover_pos()
+        .map(|pos| ((zoom.fraction_at(rect, pos.x) * last_day).round().max(0.0) as usize).min(days - 1));
+    if let (Some(day), Some(pos)) = (hovered, response.hover_pos()) {
+        chart_crosshair(ui, rect, egui::pos2(x_of(day), pos.y));
+        for (color, counts) in series {
+            painter.circle_filled(egui::pos2(x_of(day), y_of(counts[day])), 3.0, *color);
+        }
+    }
+    (response, hovered)
+}
+
+/// Renders a header cell that can be dragged onto another header to reorder columns
+/// and has a grab handle on its right edge for resizing.
+fn render_column_header(ui: &mut egui::Ui, column: RecordColumn, title: &str, width: f32) -> Option<ColumnLayoutChange> {
+    const HANDLE_WIDTH: f32 = 6.0;
+    let mut change = None;
+    
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        let height = ui.spacing().interact_size.y;
+        
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(width - HANDLE_WIDTH, height), egui::Sense::drag());
+        response.dnd_set_drag_payload(column);
+        if response.dnd_hover_payload::<RecordColumn>().is_some_and(|dragged| *dragged != column) {
+            ui.painter().rect_filled(rect, 2.0, ui.visuals().selection.bg_fill);
+        }
+        if let Some(dragged) = response.dnd_release_payload::<RecordColumn>() {
+            if *dragged != column {
+                change = Some(ColumnLayoutChange::Move { column: *dragged, target: column });
+            }
+        }
+        ui.painter().text(
+            rect.left_center(),
+            egui::Align2::LEFT_CENTER,
+            title,
+            egui::TextStyle::Body.resolve(ui.style()),
+            ui.visuals().strong_text_color(),
+        );
+        
+        let (handle_rect, handle) = ui.allocate_exact_size(egui::vec2(HANDLE_WIDTH, height), egui::Sense::drag());
+        if handle.hovered() || handle.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+            ui.painter().vline(handle_rect.center().x, handle_rect.y_range(), ui.visuals().widgets.active.fg_stroke);
+        } else {
+            ui.painter().vline(handle_rect.center().x, handle_rect.y_range(), ui.visuals().widgets.noninteractive.bg_stroke);
+        }
+        if handle.dragged() {
+            change = Some(ColumnLayoutChange::Resize { column, delta: handle.drag_delta().x });
+        }
+        if handle.drag_released() {
+            change = Some(ColumnLayoutChange::ResizeDone);
+        }
+    });
+    
+    change
+}
+
+/// Lays out a table cell at exactly `width`, truncating anything wider.
+fn fixed_width_cell(ui: &mut egui::Ui, width: f32, add_contents: impl FnOnce(&mut egui::Ui)) {
+    let size = egui::vec2(width, ui.spacing().interact_size.y);
+    ui.allocate_ui_with_layout(size, egui::Layout::left_to_right(egui::Align::Center), |ui| {
+        ui.set_width(width);
+        ui.set_clip_rect(ui.max_rect().intersect(ui.clip_rect()));
+        add_contents(ui);
+    });
+}
+
+/// Star button used to bookmark a record.
+fn bookmark_toggle(ui: &mut egui::Ui, bookmarked: bool) -> egui::Response {
+    let (icon, hover) = if bookmarked { ("★", tr!("bookmark-remove")) } else { ("☆", tr!("bookmark-add")) };
+    ui.add(egui::Button::new(icon).frame(false)).on_hover_text(hover)
+}
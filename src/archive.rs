@@ -0,0 +1,84 @@
+use crate::format_detection::{is_archive_file, is_production_file, DetectionConfidence, FormatDetection, ParserKind};
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+/// A production unpacked from an archive, with the parser its first bytes call for.
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// The archive's file name and the entry's path inside it, e.g. `return.zip/calls.xml`,
+    /// as kept in [`crate::ProcessedCallRecord::source_file`].
+    pub name: String,
+    pub bytes: Vec<u8>,
+    pub parser: ParserKind,
+    pub confidence: DetectionConfidence,
+}
+
+/// The productions inside a ZIP archive, as carriers often deliver a return: several XML or
+/// CSV files, e.g. one per target or per record type.
+#[derive(Debug, Clone)]
+pub struct ProductionArchive {
+    pub name: String,
+    /// Entries a parser recognizes, in the order they are stored.
+    pub entries: Vec<ArchiveEntry>,
+    /// Entries with a production's extension that no parser recognizes, e.g. a cover letter
+    /// saved as text.
+    pub skipped: Vec<String>,
+}
+
+impl ProductionArchive {
+    pub fn read_file(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open file: {:?}", path))?;
+        let name = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::read(file, name)
+    }
+
+    pub fn from_bytes(bytes: &[u8], name: &str) -> Result<Self> {
+        Self::read(Cursor::new(bytes), name)
+    }
+
+    /// Folders, macOS resource forks, and files without a production's extension, nested
+    /// archives included, are passed over. Fails when nothing inside is a production a parser
+    /// recognizes.
+    fn read<R: Read + Seek>(reader: R, name: &str) -> Result<Self> {
+        let mut zip = zip::ZipArchive::new(reader)
+            .with_context(|| format!("{} is not a readable ZIP archive", name))?;
+        let mut archive = Self { name: name.to_string(), entries: Vec::new(), skipped: Vec::new() };
+        for index in 0..zip.len() {
+            let mut file = zip.by_index(index)
+                .with_context(|| format!("Failed to read entry {} of {}", index + 1, name))?;
+            let path = file.name().to_string();
+            if file.is_dir() || path.starts_with("__MACOSX/") || !is_production_file(&path) || is_archive_file(&path) {
+                continue;
+            }
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)
+                .with_context(|| format!("Failed to extract {} from {}", path, name))?;
+
+            let entry_name = format!("{}/{}", name, path);
+            let detection = FormatDetection::detect_named(&path, &bytes);
+            match detection.parser {
+                Some(parser) => archive.entries.push(ArchiveEntry {
+                    name: entry_name,
+                    bytes,
+                    parser,
+                    confidence: detection.confidence,
+                }),
+                None => {
+                    warn!("Skipping unrecognized archive entry {}", entry_name);
+                    archive.skipped.push(entry_name);
+                }
+            }
+        }
+        if archive.entries.is_empty() {
+            bail!("{} holds no productions a parser recognizes", name);
+        }
+        info!("Found {} productions in {} ({} skipped)", archive.entries.len(), name, archive.skipped.len());
+        Ok(archive)
+    }
+}
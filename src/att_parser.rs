@@ -0,0 +1,185 @@
+use crate::data_models::{normalize_phone_number, CallRecord, ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{
+    column_key, normalize_direction, parse_duration, parse_time, preamble_target, sniff_delimiter, DelimitedParser,
+};
+use crate::pipeline::DuplicatePolicy;
+use anyhow::{Context, Result};
+use chrono::Duration;
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::info;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Lines searched for the column header; AT&T puts a block of case details above it.
+const HEADER_SEARCH_LINES: usize = 40;
+
+/// Column names in AT&T call detail returns, compared as by [`column_key`].
+const START_COLUMNS: [&str; 4] = ["conn date time utc", "conn date time", "connection date time utc", "connection date time"];
+const ELAPSED_COLUMNS: [&str; 3] = ["et", "elapsed time", "et hh mm ss"];
+const ORIGINATING_COLUMNS: [&str; 2] = ["originating number", "orig number"];
+const TERMINATING_COLUMNS: [&str; 2] = ["terminating number", "term number"];
+const CALL_TYPE_COLUMNS: [&str; 2] = ["ct", "call type"];
+const FEATURE_COLUMNS: [&str; 3] = ["feature", "features", "feature code"];
+
+/// Where the fields of an AT&T return are, found from its header row.
+#[derive(Debug, Clone)]
+struct AttLayout {
+    /// Byte offset of the header row, after the case details.
+    header_offset: usize,
+    /// The target named in the case details, if any.
+    target: Option<String>,
+    start: usize,
+    /// Whether the start column says its times are UTC, as AT&T's usually are.
+    utc: bool,
+    elapsed: Option<usize>,
+    originating: usize,
+    terminating: usize,
+    call_type: Option<usize>,
+    /// Call features such as `[CFO]` for a forwarded call.
+    feature: Option<usize>,
+}
+
+impl AttLayout {
+    /// Looks for the header row in the first [`HEADER_SEARCH_LINES`] lines.
+    fn find(bytes: &[u8]) -> Option<Self> {
+        let mut offset = 0;
+        let mut target = None;
+        for line in bytes.split_inclusive(|&b| b == b'\n').take(HEADER_SEARCH_LINES) {
+            let text = String::from_utf8_lossy(line);
+            let delimiter = sniff_delimiter(line) as char;
+            let keys: Vec<String> = text.split(delimiter).map(column_key).collect();
+            let find = |names: &[&str]| names.iter().find_map(|name| {
+                let key = column_key(name);
+                keys.iter().position(|column| *column == key)
+            });
+            if let (Some(start), Some(originating), Some(terminating)) =
+                (find(&START_COLUMNS), find(&ORIGINATING_COLUMNS), find(&TERMINATING_COLUMNS))
+            {
+                return Some(Self {
+                    header_offset: offset,
+                    target,
+                    start,
+                    utc: keys[start].ends_with("utc"),
+                    elapsed: find(&ELAPSED_COLUMNS),
+                    originating,
+                    terminating,
+                    call_type: find(&CALL_TYPE_COLUMNS),
+                    feature: find(&FEATURE_COLUMNS),
+                });
+            }
+            target = target.or_else(|| preamble_target(&text));
+            offset += line.len();
+        }
+        None
+    }
+}
+
+/// Reads AT&T call detail returns: a block of case details, then one row per call with
+/// the connection date and time in one column (in UTC when the header says so), the
+/// elapsed time as `H:MM:SS`, and both parties' numbers rather than a remote number.
+/// Which party is the target comes from the call type column when there is one, otherwise
+/// from the target named in the case details or, failing that, the number on the most calls.
+pub struct AttParser;
+
+impl AttParser {
+    pub fn parse_file(path: &Path, timezone: Tz, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing AT&T file: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, timezone, duplicates)
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let layout = AttLayout::find(bytes)
+            .with_context(|| format!("{} has no AT&T call detail header row", source_file))?;
+        let rows = Self::rows(bytes, &layout);
+        let target = layout.target.clone().unwrap_or_else(|| busiest_number(&rows, &layout));
+        let calls = rows.into_iter().map(|row| row.map(|row| Self::call_record(&row, &layout, &target, timezone)));
+        let (records, report) = DelimitedParser::collect_records(calls, source_file, duplicates);
+        info!("Parsed {} AT&T records for target {}", records.len(), target);
+        Ok((records, report))
+    }
+
+    /// Whether `sample` is an AT&T return, from its header row.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        AttLayout::find(sample).is_some()
+    }
+
+    /// The rows after the header, leaving out blank and footer lines with fewer than two
+    /// fields filled in.
+    fn rows(bytes: &[u8], layout: &AttLayout) -> Vec<Result<StringRecord, String>> {
+        let mut reader = DelimitedParser::reader(&bytes[layout.header_offset..]);
+        reader.records()
+            .filter(|row| row.as_ref().map_or(true, |row| row.iter().filter(|field| !field.is_empty()).count() >= 2))
+            .map(|row| row.map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    fn call_record(row: &StringRecord, layout: &AttLayout, target: &str, timezone: Tz) -> (String, CallRecord) {
+        let field = |index: Option<usize>| index.and_then(|i| row.get(i)).unwrap_or("").trim();
+        let originating = field(Some(layout.originating));
+        let terminating = field(Some(layout.terminating));
+
+        let direction = match normalize_direction(field(layout.call_type)).as_str() {
+            known @ ("Incoming" | "Outgoing") => known.to_string(),
+            _ if same_number(terminating, target) => "Incoming".to_string(),
+            _ if same_number(originating, target) => "Outgoing".to_string(),
+            _ => String::new(),
+        };
+        let remote_number = if direction == "Incoming" { originating } else { terminating };
+
+        let start_text = field(Some(layout.start));
+        let start = parse_time(start_text, false, if layout.utc { Tz::UTC } else { timezone });
+        let length_of_call = parse_duration(field(layout.elapsed)).unwrap_or(0);
+        let end = start.map(|start| start + Duration::seconds(length_of_call as i64));
+
+        let call = CallRecord {
+            message_direction: direction,
+            remote_number: remote_number.to_string(),
+            start_time: start.map(|time| time.to_rfc3339()).unwrap_or_else(|| start_text.to_string()),
+            end_time: end.map(|time| time.to_rfc3339()).unwrap_or_default(),
+            length_of_call,
+            message_content: None,
+            record_type: None,
+            cell_site_id: None,
+            sector: None,
+            latitude: None,
+            longitude: None,
+            imei: None,
+            imsi: None,
+            disposition: Some(field(layout.feature).to_string()).filter(|feature| !feature.is_empty()),
+        };
+        (target.to_string(), call)
+    }
+}
+
+/// The number on either side of the most calls, which in a single target's return is the target.
+fn busiest_number(rows: &[Result<StringRecord, String>], layout: &AttLayout) -> String {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for row in rows.iter().flatten() {
+        for index in [layout.originating, layout.terminating] {
+            let number = normalize_phone_number(row.get(index).unwrap_or(""));
+            if number.is_valid() {
+                *counts.entry(number.into_string()).or_default() += 1;
+            }
+        }
+    }
+    counts.into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(number, _)| number)
+        .unwrap_or_default()
+}
+
+fn same_number(value: &str, target: &str) -> bool {
+    !target.is_empty() && normalize_phone_number(value).as_str() == normalize_phone_number(target).as_str()
+}
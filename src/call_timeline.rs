@@ -0,0 +1,139 @@
+use crate::data_models::ProcessedCallRecord;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Bucket lengths tried for the Excel layout, shortest first, in minutes.
+const BUCKET_MINUTES: &[i64] = &[15, 30, 60, 120, 180, 360, 720, 1440, 2880, 10080];
+
+/// One call drawn as a bar from its start to its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallSpan {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub target_number: String,
+    /// Another call on the same target was still going when this one started, or started
+    /// before this one ended.
+    pub overlaps: bool,
+}
+
+/// A contact's calls, in start order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineRow {
+    pub number: String,
+    pub calls: Vec<CallSpan>,
+}
+
+/// Gantt-style layout of the top contacts' calls: one row per contact, one bar per call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallTimeline {
+    /// In the order the contacts were given.
+    pub rows: Vec<TimelineRow>,
+    /// Earliest start to latest end over all rows.
+    pub range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+impl CallTimeline {
+    /// Lays out the calls with each of `contacts` (normalized numbers), normally the most
+    /// frequent numbers. Overlaps are checked among the calls shown, per target.
+    pub fn build(records: &[ProcessedCallRecord], contacts: &[String]) -> Self {
+        let row_of: HashMap<&str, usize> = contacts.iter().enumerate().map(|(i, c)| (c.as_str(), i)).collect();
+        let mut rows: Vec<TimelineRow> = contacts.iter()
+            .map(|number| TimelineRow { number: number.clone(), calls: Vec::new() })
+            .collect();
+        for record in records {
+            if let Some(&row) = row_of.get(record.normalized_number.as_str()) {
+                rows[row].calls.push(CallSpan {
+                    start: record.start_time,
+                    // A malformed end time still gets a visible sliver rather than a negative bar
+                    end: record.end_time.max(record.start_time),
+                    target_number: record.target_number.clone(),
+                    overlaps: false,
+                });
+            }
+        }
+        for row in &mut rows {
+            row.calls.sort_by_key(|call| call.start);
+        }
+        mark_overlaps(&mut rows);
+
+        let range = rows.iter().flat_map(|row| &row.calls).fold(None, |range, call| {
+            Some(match range {
+                Some((first, last)) => (call.start.min(first), call.end.max(last)),
+                None => (call.start, call.end),
+            })
+        });
+        Self { rows, range }
+    }
+
+    pub fn overlapping_calls(&self) -> usize {
+        self.rows.iter().flat_map(|row| &row.calls).filter(|call| call.overlaps).count()
+    }
+
+    /// Splits the range into equal buckets for a grid layout: the shortest bucket length
+    /// that needs at most `max_buckets` columns, and the start of each bucket.
+    pub fn buckets(&self, max_buckets: usize) -> Option<(Duration, Vec<DateTime<Utc>>)> {
+        let (first, last) = self.range?;
+        let span = (last - first).num_minutes() + 1;
+        let minutes = BUCKET_MINUTES.iter().copied()
+            .find(|minutes| (span + minutes - 1) / minutes <= max_buckets as i64)
+            .unwrap_or_else(|| (span + max_buckets as i64 - 1) / max_buckets as i64);
+        let length = Duration::minutes(minutes);
+        // Align to whole buckets from midnight so column headers read cleanly
+        let midnight = first.date_naive().and_hms_opt(0, 0, 0).expect("midnight exists").and_utc();
+        let mut start = midnight + Duration::minutes((first - midnight).num_minutes() / minutes * minutes);
+        let mut starts = Vec::new();
+        while start <= last {
+            starts.push(start);
+            start += length;
+        }
+        Some((length, starts))
+    }
+}
+
+impl TimelineRow {
+    /// Minutes on a call with this contact between `start` and `start + length`, and whether
+    /// any of those calls overlaps another.
+    pub fn busy_minutes(&self, start: DateTime<Utc>, length: Duration) -> (f64, bool) {
+        let end = start + length;
+        let mut overlaps = false;
+        let seconds: i64 = self.calls.iter()
+            .take_while(|call| call.start < end)
+            .filter(|call| call.end > start)
+            .map(|call| {
+                overlaps |= call.overlaps;
+                (call.end.min(end) - call.start.max(start)).num_seconds()
+            })
+            .sum();
+        (seconds as f64 / 60.0, overlaps)
+    }
+}
+
+/// Flags calls on the same target that overlap in time, checking the calls of all rows
+/// together since a target on two calls at once is what shows up as an overlap.
+fn mark_overlaps(rows: &mut [TimelineRow]) {
+    let mut spans: Vec<(usize, usize)> = rows.iter().enumerate()
+        .flat_map(|(row, r)| (0..r.calls.len()).map(move |call| (row, call)))
+        .collect();
+    spans.sort_by(|&(ra, ca), &(rb, cb)| {
+        let (a, b) = (&rows[ra].calls[ca], &rows[rb].calls[cb]);
+        a.target_number.cmp(&b.target_number).then(a.start.cmp(&b.start))
+    });
+
+    // The call reaching furthest so far on the current target overlaps anything starting before it ends
+    let mut latest: Option<(usize, usize)> = None;
+    for (row, call) in spans {
+        let span = &rows[row].calls[call];
+        let (start, end) = (span.start, span.end);
+        if let Some((latest_row, latest_call)) = latest {
+            let previous = &rows[latest_row].calls[latest_call];
+            if previous.target_number == span.target_number && start < previous.end {
+                rows[latest_row].calls[latest_call].overlaps = true;
+                rows[row].calls[call].overlaps = true;
+                if end <= rows[latest_row].calls[latest_call].end {
+                    continue;
+                }
+            }
+        }
+        latest = Some((row, call));
+    }
+}
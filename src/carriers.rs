@@ -0,0 +1,218 @@
+use crate::area_codes::{number_region, region_label};
+use crate::data_models::{normalize_phone_number, NormalizedNumber};
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Header names, compared case-insensitively, for the columns of the carrier datasets.
+const NPA_HEADERS: &[&str] = &["npa", "area code"];
+const NXX_HEADERS: &[&str] = &["nxx", "exchange", "prefix"];
+const NPA_NXX_HEADERS: &[&str] = &["npa-nxx", "npanxx", "npa nxx"];
+const NUMBER_HEADERS: &[&str] = &["number", "tn", "telephone number", "phone number", "ported number", "dn"];
+const LRN_HEADERS: &[&str] = &["lrn", "location routing number"];
+const CARRIER_HEADERS: &[&str] = &["carrier", "company", "ocn name", "operating company", "spid name", "service provider"];
+const RATE_CENTER_HEADERS: &[&str] = &["rate center", "ratecenter", "city", "locality"];
+const STATE_HEADERS: &[&str] = &["state", "province"];
+
+/// Where a number's carrier came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CarrierSource {
+    /// The number was ported; this is the carrier now serving it.
+    Ported,
+    /// The carrier the number's NPA-NXX block was assigned to. May be out of date if the
+    /// number was ported and no portability data covers it.
+    Assigned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CarrierInfo<'a> {
+    pub carrier: &'a str,
+    pub source: CarrierSource,
+    /// Location routing number of a ported number.
+    pub lrn: Option<&'a str>,
+}
+
+/// Who an NPA-NXX block was assigned to, and where, when the dataset says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlockAssignment {
+    carrier: String,
+    rate_center: String,
+    state: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PortedNumber {
+    lrn: String,
+    /// Empty when the dataset only gives the LRN; the LRN's own block is looked up instead.
+    carrier: String,
+}
+
+/// NPA-NXX block assignments and ported-number (LRN) records, for telling which carrier
+/// serves a number and so where to send the next subpoena.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CarrierDirectory {
+    /// Carrier and location per six-digit NPA-NXX block.
+    assignments: HashMap<String, BlockAssignment>,
+    /// Ported numbers by ten-digit number.
+    ported: HashMap<String, PortedNumber>,
+}
+
+impl CarrierDirectory {
+    /// Reads NPA-NXX assignments from a CSV with `NPA` and `NXX` columns (or one `NPA-NXX`
+    /// column), a `Carrier` column, and optional `Rate Center` and `State` columns.
+    pub fn import_assignments(&mut self, path: &Path) -> Result<usize> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open NPA-NXX assignments: {:?}", path))?;
+        let count = self.read_assignments(file)
+            .with_context(|| format!("Failed to read NPA-NXX assignments: {:?}", path))?;
+        info!("Loaded {} NPA-NXX assignments from {:?}", count, path);
+        Ok(count)
+    }
+
+    /// Reads ported numbers from a CSV with `Number` and `LRN` columns and an optional
+    /// `Carrier` column naming the new serving carrier.
+    pub fn import_ported(&mut self, path: &Path) -> Result<usize> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open ported-number data: {:?}", path))?;
+        let count = self.read_ported(file)
+            .with_context(|| format!("Failed to read ported-number data: {:?}", path))?;
+        info!("Loaded {} ported numbers from {:?}", count, path);
+        Ok(count)
+    }
+
+    pub fn read_assignments<R: Read>(&mut self, reader: R) -> Result<usize> {
+        let mut reader = csv_reader(reader);
+        let headers = reader.headers()?.clone();
+        let block_columns = match (find_column(&headers, NPA_NXX_HEADERS), find_column(&headers, NPA_HEADERS), find_column(&headers, NXX_HEADERS)) {
+            (Some(col), _, _) => BlockColumns::Combined(col),
+            (None, Some(npa), Some(nxx)) => BlockColumns::Split(npa, nxx),
+            _ => bail!("No NPA-NXX columns; expected \"NPA\" and \"NXX\" headers or one \"NPA-NXX\" header"),
+        };
+        let Some(carrier_col) = find_column(&headers, CARRIER_HEADERS) else {
+            bail!("No carrier column; expected a header such as \"Carrier\" or \"OCN Name\"");
+        };
+        let rate_center_col = find_column(&headers, RATE_CENTER_HEADERS);
+        let state_col = find_column(&headers, STATE_HEADERS);
+
+        let mut count = 0;
+        for row in reader.records() {
+            let row = row?;
+            let block: String = match block_columns {
+                BlockColumns::Combined(col) => row.get(col).unwrap_or_default().chars().filter(char::is_ascii_digit).collect(),
+                BlockColumns::Split(npa, nxx) => format!("{}{}", row.get(npa).unwrap_or_default(), row.get(nxx).unwrap_or_default()),
+            };
+            let carrier = row.get(carrier_col).unwrap_or_default();
+            if block.len() != 6 || !block.chars().all(|c| c.is_ascii_digit()) || carrier.is_empty() {
+                continue;
+            }
+            let optional = |col: Option<usize>| col.and_then(|col| row.get(col)).unwrap_or_default().to_string();
+            self.assignments.insert(block, BlockAssignment {
+                carrier: carrier.to_string(),
+                rate_center: optional(rate_center_col),
+                state: optional(state_col),
+            });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub fn read_ported<R: Read>(&mut self, reader: R) -> Result<usize> {
+        let mut reader = csv_reader(reader);
+        let headers = reader.headers()?.clone();
+        let Some(number_col) = find_column(&headers, NUMBER_HEADERS) else {
+            bail!("No number column; expected a header such as \"Number\" or \"TN\"");
+        };
+        let Some(lrn_col) = find_column(&headers, LRN_HEADERS) else {
+            bail!("No LRN column; expected a header such as \"LRN\"");
+        };
+        let carrier_col = find_column(&headers, CARRIER_HEADERS);
+
+        let mut count = 0;
+        for (index, row) in reader.records().enumerate() {
+            let row = row?;
+            let (NormalizedNumber::Nanp(number), NormalizedNumber::Nanp(lrn)) = (
+                normalize_phone_number(row.get(number_col).unwrap_or_default()),
+                normalize_phone_number(row.get(lrn_col).unwrap_or_default()),
+            ) else {
+                // Row 1 is the header
+                warn!("Skipping ported-number row {}: number and LRN must be ten-digit NANP numbers", index + 2);
+                continue;
+            };
+            let carrier = carrier_col.and_then(|col| row.get(col)).unwrap_or_default().to_string();
+            self.ported.insert(number, PortedNumber { lrn, carrier });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The carrier serving a normalized number: from portability data when the number was
+    /// ported, else from its NPA-NXX assignment.
+    pub fn lookup(&self, normalized_number: &str) -> Option<CarrierInfo<'_>> {
+        if let Some(ported) = self.ported.get(normalized_number) {
+            let carrier = if ported.carrier.is_empty() {
+                self.assigned_carrier(&ported.lrn)?
+            } else {
+                ported.carrier.as_str()
+            };
+            return Some(CarrierInfo { carrier, source: CarrierSource::Ported, lrn: Some(&ported.lrn) });
+        }
+        self.assigned_carrier(normalized_number)
+            .map(|carrier| CarrierInfo { carrier, source: CarrierSource::Assigned, lrn: None })
+    }
+
+    /// Where a normalized number likely is: its block's rate center and state when the
+    /// assignments give them, else the state, province, or country of its area code. A
+    /// ported number keeps its rate center, so this holds for ported numbers too.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::carriers::CarrierDirectory;
+    ///
+    /// let mut directory = CarrierDirectory::default();
+    /// directory.read_assignments("NPA,NXX,Carrier,Rate Center,State\n256,555,Example Wireless,Huntsville,AL\n".as_bytes()).unwrap();
+    /// assert_eq!(directory.region("2565550100").as_deref(), Some("Huntsville, AL"));
+    /// assert_eq!(directory.region("2055550100").as_deref(), Some("AL"));
+    /// assert_eq!(directory.region("8005550199"), None);
+    /// ```
+    pub fn region(&self, normalized_number: &str) -> Option<String> {
+        let located = self.assignment(normalized_number)
+            .map(|block| [block.rate_center.as_str(), block.state.as_str()].into_iter().filter(|part| !part.is_empty()).collect::<Vec<_>>())
+            .filter(|parts| !parts.is_empty());
+        match located {
+            Some(parts) => Some(parts.join(", ")),
+            None => number_region(normalized_number).map(region_label),
+        }
+    }
+
+    fn assigned_carrier(&self, number: &str) -> Option<&str> {
+        self.assignment(number).map(|block| block.carrier.as_str())
+    }
+
+    fn assignment(&self, number: &str) -> Option<&BlockAssignment> {
+        if number.len() != 10 || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        self.assignments.get(&number[..6])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty() && self.ported.is_empty()
+    }
+}
+
+/// How an assignments file spells the NPA-NXX block.
+#[derive(Clone, Copy)]
+enum BlockColumns {
+    Combined(usize),
+    Split(usize, usize),
+}
+
+fn csv_reader<R: Read>(reader: R) -> csv::Reader<R> {
+    csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader)
+}
+
+fn find_column(headers: &csv::StringRecord, names: &[&str]) -> Option<usize> {
+    headers.iter().position(|header| names.iter().any(|name| header.eq_ignore_ascii_case(name)))
+}
@@ -0,0 +1,151 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// How one number shows up within a single case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberActivity {
+    /// Calls the number took part in, as remote party or as target.
+    pub calls: usize,
+    pub first: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+    /// Whether the number is one of the case's targets.
+    pub is_target: bool,
+    /// Targets that called or were called by the number.
+    pub targets: HashSet<String>,
+}
+
+impl NumberActivity {
+    fn new(time: DateTime<Utc>) -> Self {
+        Self {
+            calls: 0,
+            first: time,
+            last: time,
+            is_target: false,
+            targets: HashSet::new(),
+        }
+    }
+
+    fn record(&mut self, time: DateTime<Utc>) {
+        self.calls += 1;
+        self.first = self.first.min(time);
+        self.last = self.last.max(time);
+    }
+
+    /// Reached by more than one target, same as a common contact in the case's analytics.
+    pub fn is_common_contact(&self) -> bool {
+        self.targets.len() > 1
+    }
+}
+
+/// A number seen in both the current case and the one compared against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedNumber {
+    pub number: String,
+    pub current: NumberActivity,
+    pub other: NumberActivity,
+}
+
+impl SharedNumber {
+    /// When the number was active in both cases, if those periods overlap.
+    pub fn overlapping_period(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        overlap((self.current.first, self.current.last), (self.other.first, self.other.last))
+    }
+}
+
+/// Overlaps between two cases, for linking investigations that share contacts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseComparison {
+    /// What the other case is called in the UI, normally its file name.
+    pub other_label: String,
+    pub current_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    pub other_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Sorted by combined call count, busiest first.
+    pub shared_numbers: Vec<SharedNumber>,
+}
+
+impl CaseComparison {
+    pub fn compare(current: &[ProcessedCallRecord], other: &[ProcessedCallRecord], other_label: &str) -> Self {
+        let current_numbers = number_activity(current);
+        let mut other_numbers = number_activity(other);
+
+        let mut shared_numbers: Vec<SharedNumber> = current_numbers.into_iter()
+            .filter_map(|(number, activity)| {
+                let other = other_numbers.remove(&number)?;
+                Some(SharedNumber { number, current: activity, other })
+            })
+            .collect();
+        shared_numbers.sort_by(|a, b| {
+            (b.current.calls + b.other.calls).cmp(&(a.current.calls + a.other.calls))
+                .then_with(|| a.number.cmp(&b.number))
+        });
+
+        Self {
+            other_label: other_label.to_string(),
+            current_range: date_range(current),
+            other_range: date_range(other),
+            shared_numbers,
+        }
+    }
+
+    /// The stretch of time both cases cover.
+    pub fn overlapping_period(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        overlap(self.current_range?, self.other_range?)
+    }
+
+    /// Shared numbers that are a common contact in both cases.
+    pub fn shared_common_contacts(&self) -> impl Iterator<Item = &SharedNumber> {
+        self.shared_numbers.iter()
+            .filter(|shared| shared.current.is_common_contact() && shared.other.is_common_contact())
+    }
+}
+
+/// Activity per normalized number, counting both remote parties and targets so a target in
+/// one case that is a contact in the other is still found. Withheld and invalid remote
+/// numbers are left out.
+fn number_activity(records: &[ProcessedCallRecord]) -> HashMap<String, NumberActivity> {
+    let mut activity: HashMap<String, NumberActivity> = HashMap::new();
+    let mut normalized_targets: HashMap<&str, Option<String>> = HashMap::new();
+
+    for record in records {
+        let target = normalized_targets.entry(record.target_number.as_str())
+            .or_insert_with(|| {
+                let normalized = normalize_phone_number(&record.target_number);
+                normalized.is_valid().then(|| normalized.into_string())
+            })
+            .clone();
+
+        if record.number_kind.identifies_party() {
+            let remote = activity.entry(record.normalized_number.clone())
+                .or_insert_with(|| NumberActivity::new(record.start_time));
+            remote.record(record.start_time);
+            if let Some(target) = &target {
+                remote.targets.insert(target.clone());
+            }
+        }
+        if let Some(target) = target {
+            let target = activity.entry(target).or_insert_with(|| NumberActivity::new(record.start_time));
+            target.record(record.start_time);
+            target.is_target = true;
+        }
+    }
+    activity
+}
+
+fn date_range(records: &[ProcessedCallRecord]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    records.iter().fold(None, |range, record| {
+        Some(match range {
+            Some((first, last)) => (record.start_time.min(first), record.start_time.max(last)),
+            None => (record.start_time, record.start_time),
+        })
+    })
+}
+
+fn overlap(
+    (a_start, a_end): (DateTime<Utc>, DateTime<Utc>),
+    (b_start, b_end): (DateTime<Utc>, DateTime<Utc>),
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = a_start.max(b_start);
+    let end = a_end.min(b_end);
+    (start <= end).then_some((start, end))
+}
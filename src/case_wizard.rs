@@ -0,0 +1,102 @@
+use chrono_tz::Tz;
+use esubpoena_tolls_tool::annotations::CaseDetails;
+use esubpoena_tolls_tool::format_detection::is_production_file;
+use esubpoena_tolls_tool::pipeline::DuplicatePolicy;
+use esubpoena_tolls_tool::settings::AppSettings;
+use esubpoena_tolls_tool::tr;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Details,
+    Defaults,
+    Productions,
+    Duplicates,
+}
+
+impl WizardStep {
+    pub const ALL: [WizardStep; 4] = [WizardStep::Details, WizardStep::Defaults, WizardStep::Productions, WizardStep::Duplicates];
+
+    pub fn title(&self) -> String {
+        match self {
+            WizardStep::Details => tr!("wizard-details-title"),
+            WizardStep::Defaults => tr!("wizard-defaults-title"),
+            WizardStep::Productions => tr!("wizard-productions-title"),
+            WizardStep::Duplicates => tr!("wizard-duplicates-title"),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            WizardStep::Details => tr!("wizard-details-body"),
+            WizardStep::Defaults => tr!("wizard-defaults-body"),
+            WizardStep::Productions => tr!("wizard-productions-body"),
+            WizardStep::Duplicates => tr!("wizard-duplicates-body"),
+        }
+    }
+
+    /// Position among the steps, counting from 1.
+    pub fn number(&self) -> usize {
+        Self::ALL.iter().position(|step| step == self).unwrap_or(0) + 1
+    }
+
+    pub fn next(&self) -> Option<WizardStep> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    pub fn previous(&self) -> Option<WizardStep> {
+        self.number().checked_sub(2).map(|index| Self::ALL[index])
+    }
+}
+
+/// The answers given in the new-case wizard, applied together once it finishes so a case
+/// abandoned halfway changes nothing.
+#[derive(Debug, Clone)]
+pub struct NewCaseWizard {
+    pub step: WizardStep,
+    pub case: CaseDetails,
+    pub timezone: Tz,
+    pub night_hours: (u32, u32),
+    pub productions: Vec<PathBuf>,
+    /// Path typed on the productions step, until it is added.
+    pub new_production: String,
+    pub duplicates: DuplicatePolicy,
+}
+
+impl NewCaseWizard {
+    /// Starts from the saved settings, so an analyst's usual timezone and night hours are
+    /// already chosen.
+    pub fn new(settings: &AppSettings) -> Self {
+        Self {
+            step: WizardStep::Details,
+            case: CaseDetails::default(),
+            timezone: settings.case_timezone,
+            night_hours: settings.night_hours,
+            productions: Vec::new(),
+            new_production: String::new(),
+            duplicates: DuplicatePolicy::default(),
+        }
+    }
+
+    /// Adds a production to load, or says why it cannot be. Adding one twice does nothing.
+    pub fn add_production(&mut self, path: PathBuf) -> Result<(), String> {
+        if !is_production_file(&path.to_string_lossy()) {
+            return Err(tr!("msg-xml-only"));
+        }
+        if !path.is_file() {
+            return Err(tr!("msg-wizard-file-missing", path = path.display().to_string()));
+        }
+        if !self.productions.contains(&path) {
+            self.productions.push(path);
+        }
+        Ok(())
+    }
+
+    /// Whether the current step is filled in enough to move on; a case needs a name.
+    pub fn can_advance(&self) -> bool {
+        match self.step {
+            WizardStep::Details => !self.case.name.trim().is_empty(),
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::data_models::{normalize_phone_number, NormalizedNumber, ProcessedCallRecord};
+use anyhow::{Context, Result};
+use log::info;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Columns of a UFED call log report. They stay in English whatever the interface language,
+/// since that is what Physical Analyzer and Pathfinder map on import.
+const HEADERS: [&str; 9] = ["#", "Type", "From", "To", "Account", "Timestamp", "Duration", "Source", "Source File"];
+/// Source of every row, so merged rows can be told apart from the device's own call log.
+const SOURCE: &str = "Carrier Toll Records";
+
+/// Call records laid out like a Cellebrite UFED call log, so carrier records can be imported
+/// into Physical Analyzer or Pathfinder next to a device extraction. Numbers are written in
+/// E.164 (`+12565550100`), as extractions store them, so the same party lines up in both.
+pub struct CellebriteExporter;
+
+impl CellebriteExporter {
+    pub fn export(records: &[ProcessedCallRecord], output_path: &Path) -> Result<()> {
+        info!("Exporting call records as a UFED call log: {:?}", output_path);
+
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create file: {:?}", output_path))?;
+        Self::write_call_log(file, records)?;
+
+        info!("Successfully exported {} records as a UFED call log", records.len());
+        Ok(())
+    }
+
+    /// Same as [`CellebriteExporter::export`] but into memory, for the browser build's downloads.
+    pub fn to_bytes(records: &[ProcessedCallRecord]) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        Self::write_call_log(&mut buffer, records)?;
+        Ok(buffer)
+    }
+
+    /// Rows are in call order. The account is the target, the party the production is about;
+    /// the remote party is "From" on incoming calls and "To" on outgoing ones.
+    pub fn write_call_log<W: Write>(writer: W, records: &[ProcessedCallRecord]) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(HEADERS)?;
+
+        let mut ordered: Vec<&ProcessedCallRecord> = records.iter().collect();
+        ordered.sort_by_key(|record| record.start_time);
+        for (index, record) in ordered.into_iter().enumerate() {
+            let target = e164(&record.target_number);
+            let remote = e164(&record.remote_number);
+            let incoming = record.message_direction.eq_ignore_ascii_case("incoming");
+            let (kind, from, to) = if incoming {
+                ("Incoming", remote, target.clone())
+            } else {
+                ("Outgoing", target.clone(), remote)
+            };
+            writer.write_record([
+                (index + 1).to_string(),
+                kind.to_string(),
+                from,
+                to,
+                target,
+                record.start_time.format("%m/%d/%Y %H:%M:%S(UTC+0)").to_string(),
+                format_duration(record.length_of_call),
+                SOURCE.to_string(),
+                record.source_file.clone(),
+            ])?;
+        }
+
+        writer.flush().context("Failed to write UFED call log")?;
+        Ok(())
+    }
+}
+
+/// NANP numbers get `+1`; international numbers already carry their `+`. Short codes and
+/// withheld caller IDs are written as produced.
+fn e164(number: &str) -> String {
+    match normalize_phone_number(number) {
+        NormalizedNumber::Nanp(digits) => format!("+1{}", digits),
+        NormalizedNumber::International(number) => number,
+        other => other.into_string(),
+    }
+}
+
+/// `HH:MM:SS`, as UFED reports durations.
+fn format_duration(seconds: u32) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
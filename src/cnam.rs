@@ -0,0 +1,180 @@
+use crate::data_models::{normalize_phone_number, normalize_phone_number_in, NormalizedNumber};
+use crate::phone_region::PhoneRegion;
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const CACHE_DIR_NAME: &str = "esubpoena-tolls-tool";
+const CACHE_FILE_NAME: &str = "cnam-cache.json";
+
+/// Header names, compared case-insensitively, for the columns of a CNAM file.
+const NUMBER_HEADERS: &[&str] = &["number", "phone", "phone number", "telephone number", "tn", "dn"];
+const NAME_HEADERS: &[&str] = &["name", "cnam", "caller name", "caller id name", "listing name"];
+
+/// Fields a JSON answer from the lookup service may carry the name in.
+const RESPONSE_NAME_FIELDS: &[&str] = &["name", "cnam", "caller_name", "callerName"];
+
+/// Most numbers looked up in one run, busiest contacts first.
+pub const CNAM_LOOKUP_LIMIT: usize = 200;
+
+/// Caller-ID (CNAM) names of contacted numbers, from an offline CNAM file and from earlier
+/// answers of the lookup service.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallerNames {
+    /// Names from the offline file, by normalized number.
+    offline: HashMap<String, String>,
+    /// Lookup service answers; `None` where it had no name, so the number is not sent again.
+    looked_up: HashMap<String, Option<String>>,
+}
+
+impl CallerNames {
+    /// Reads a CSV with a number column and a name column; other columns are ignored.
+    pub fn import_csv(&mut self, path: &Path) -> Result<usize> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open CNAM file: {:?}", path))?;
+        let count = self.read_csv(file)
+            .with_context(|| format!("Failed to read CNAM file: {:?}", path))?;
+        info!("Loaded {} caller names from {:?}", count, path);
+        Ok(count)
+    }
+
+    pub fn read_csv<R: Read>(&mut self, reader: R) -> Result<usize> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let find = |names: &[&str]| headers.iter().position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)));
+        let Some(number_col) = find(NUMBER_HEADERS) else {
+            bail!("No phone number column; expected a header such as \"Number\" or \"TN\"");
+        };
+        let Some(name_col) = find(NAME_HEADERS) else {
+            bail!("No caller name column; expected a header such as \"Name\" or \"CNAM\"");
+        };
+
+        let mut count = 0;
+        for (index, row) in reader.records().enumerate() {
+            let row = row?;
+            let raw_number = row.get(number_col).unwrap_or_default();
+            let number = normalize_phone_number(raw_number);
+            let name = row.get(name_col).unwrap_or_default();
+            if !number.is_valid() || name.is_empty() {
+                // Row 1 is the header
+                warn!("Skipping CNAM row {}: needs a phone number and a name", index + 2);
+                continue;
+            }
+            self.offline.insert(number.into_string(), name.to_string());
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The offline file wins over the lookup service, since it is the one the user chose.
+    pub fn lookup(&self, normalized_number: &str) -> Option<&str> {
+        self.offline.get(normalized_number)
+            .or_else(|| self.looked_up.get(normalized_number)?.as_ref())
+            .map(String::as_str)
+    }
+
+    /// NANP numbers without a name that the lookup service has not been asked about yet.
+    pub fn needs_lookup(&self, normalized_number: &str) -> bool {
+        // Normalized numbers outside the NANP carry their `+`, so they read the same in any region
+        matches!(normalize_phone_number_in(normalized_number, PhoneRegion::NorthAmerica), NormalizedNumber::Nanp(_))
+            && !self.offline.contains_key(normalized_number)
+            && !self.looked_up.contains_key(normalized_number)
+    }
+
+    pub fn add_lookups(&mut self, answers: HashMap<String, Option<String>>) {
+        self.looked_up.extend(answers);
+    }
+
+    /// Lookup answers are kept under the platform local data directory, next to the analytics cache.
+    pub fn cache_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join(CACHE_DIR_NAME).join(CACHE_FILE_NAME))
+    }
+
+    /// A missing or unreadable cache starts empty, never an error.
+    pub fn load_cache(&mut self, path: &Path) {
+        let Ok(content) = fs::read_to_string(path) else {
+            return;
+        };
+        match serde_json::from_str::<HashMap<String, Option<String>>>(&content) {
+            Ok(answers) => {
+                info!("Loaded {} cached caller-name lookups from {:?}", answers.len(), path);
+                self.looked_up.extend(answers);
+            }
+            Err(e) => warn!("Ignoring unreadable CNAM cache {:?}: {}", path, e),
+        }
+    }
+
+    pub fn save_cache(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create CNAM cache directory: {:?}", parent))?;
+        }
+        let content = serde_json::to_string(&self.looked_up).context("Failed to serialize CNAM cache")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write CNAM cache: {:?}", path))
+    }
+
+    /// Forgets the lookup service's answers, keeping the offline file.
+    pub fn clear_lookups(&mut self) -> usize {
+        let count = self.looked_up.len();
+        self.looked_up.clear();
+        count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offline.is_empty() && self.looked_up.values().all(Option::is_none)
+    }
+}
+
+/// Reads a caller name out of a lookup service answer: a JSON object with a `name` (or
+/// `cnam`, `caller_name`) field, or the name as plain text.
+pub fn parse_lookup_response(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        return RESPONSE_NAME_FIELDS.iter()
+            .find_map(|field| value.get(field)?.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+    }
+    (!body.is_empty() && !body.contains('\n')).then(|| body.to_string())
+}
+
+/// The caller-name lookup service configured in settings. Every number looked up is sent to
+/// it, which is why lookups are off until turned on and only run when asked for.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct CnamLookup {
+    url_template: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CnamLookup {
+    /// `url_template` holds a `{number}` placeholder for the ten-digit number.
+    pub fn new(url_template: &str) -> Result<Self> {
+        if !url_template.contains("{number}") {
+            bail!("The caller-name lookup URL needs a {{number}} placeholder");
+        }
+        let agent = ureq::AgentBuilder::new()
+            .timeout(std::time::Duration::from_secs(10))
+            .build();
+        Ok(Self { url_template: url_template.to_string(), agent })
+    }
+
+    /// `None` when the service has no name for the number.
+    pub fn fetch(&self, normalized_number: &str) -> Result<Option<String>> {
+        let url = self.url_template.replace("{number}", normalized_number);
+        match self.agent.get(&url).call() {
+            Ok(response) => {
+                let body = response.into_string().context("Failed to read caller-name lookup response")?;
+                Ok(parse_lookup_response(&body))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(e).context("Caller-name lookup failed"),
+        }
+    }
+}
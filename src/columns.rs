@@ -0,0 +1,162 @@
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const MIN_COLUMN_WIDTH: f32 = 50.0;
+
+/// Columns of the call records table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RecordColumn {
+    Direction,
+    RemoteNumber,
+    NormalizedNumber,
+    Date,
+    Time,
+    LocalTime,
+    Duration,
+    /// Subscriber name, from imported subscriber results; hidden until some are imported.
+    Subscriber,
+    /// Serving carrier, from the configured carrier datasets; hidden when none are loaded.
+    Carrier,
+    /// Rate center and state from the carrier datasets, else the area code's state or country.
+    Region,
+    /// Caller-ID name, from the CNAM file or lookups; hidden when there are none.
+    CallerName,
+    /// Name from an imported contact list; hidden until one is imported.
+    Alias,
+    /// Categories of the known-number lists the contact is on; hidden when none are loaded.
+    Category,
+    /// Message text with keyword hits highlighted; hidden when the production has none.
+    Message,
+    /// Serving cell ID and sector; hidden when no record has cell site fields.
+    CellSite,
+    /// The serving tower's latitude and longitude; hidden along with the cell site.
+    TowerLocation,
+    /// The target's IMEI and IMSI; hidden when no record has them.
+    Device,
+    /// Tags and note attached to the record; hidden until a record is tagged or noted.
+    Tags,
+}
+
+impl RecordColumn {
+    pub const ALL: [RecordColumn; 18] = [
+        RecordColumn::Direction,
+        RecordColumn::RemoteNumber,
+        RecordColumn::NormalizedNumber,
+        RecordColumn::Date,
+        RecordColumn::Time,
+        RecordColumn::LocalTime,
+        RecordColumn::Duration,
+        RecordColumn::Subscriber,
+        RecordColumn::Carrier,
+        RecordColumn::Region,
+        RecordColumn::CallerName,
+        RecordColumn::Alias,
+        RecordColumn::Category,
+        RecordColumn::Message,
+        RecordColumn::CellSite,
+        RecordColumn::TowerLocation,
+        RecordColumn::Device,
+        RecordColumn::Tags,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            RecordColumn::Direction => tr!("column-direction"),
+            RecordColumn::RemoteNumber => tr!("column-remote-number"),
+            RecordColumn::NormalizedNumber => tr!("column-normalized"),
+            RecordColumn::Date => tr!("column-date"),
+            RecordColumn::Time => tr!("column-time"),
+            RecordColumn::LocalTime => tr!("column-local-time"),
+            RecordColumn::Duration => tr!("column-duration"),
+            RecordColumn::Subscriber => tr!("column-subscriber"),
+            RecordColumn::Carrier => tr!("column-carrier"),
+            RecordColumn::Region => tr!("column-region"),
+            RecordColumn::CallerName => tr!("column-caller-name"),
+            RecordColumn::Alias => tr!("column-alias"),
+            RecordColumn::Category => tr!("column-category"),
+            RecordColumn::Message => tr!("column-message"),
+            RecordColumn::CellSite => tr!("column-cell-site"),
+            RecordColumn::TowerLocation => tr!("column-tower-location"),
+            RecordColumn::Device => tr!("column-device"),
+            RecordColumn::Tags => tr!("column-tags"),
+        }
+    }
+
+    pub fn default_width(&self) -> f32 {
+        match self {
+            RecordColumn::Direction => 80.0,
+            RecordColumn::RemoteNumber | RecordColumn::NormalizedNumber => 130.0,
+            RecordColumn::Date | RecordColumn::Time => 90.0,
+            RecordColumn::LocalTime => 190.0,
+            RecordColumn::Duration => 100.0,
+            RecordColumn::Subscriber => 180.0,
+            RecordColumn::Carrier => 180.0,
+            RecordColumn::Region => 150.0,
+            RecordColumn::CallerName => 180.0,
+            RecordColumn::Alias => 180.0,
+            RecordColumn::Category => 150.0,
+            RecordColumn::Message => 320.0,
+            RecordColumn::CellSite => 130.0,
+            RecordColumn::TowerLocation => 190.0,
+            RecordColumn::Device => 260.0,
+            RecordColumn::Tags => 220.0,
+        }
+    }
+}
+
+/// User-arranged order and widths of the call records table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub order: Vec<RecordColumn>,
+    pub widths: HashMap<RecordColumn, f32>,
+}
+
+impl Default for ColumnLayout {
+    fn default() -> Self {
+        Self {
+            order: RecordColumn::ALL.to_vec(),
+            widths: HashMap::new(),
+        }
+    }
+}
+
+impl ColumnLayout {
+    pub fn width(&self, column: RecordColumn) -> f32 {
+        self.widths.get(&column).copied().unwrap_or_else(|| column.default_width())
+    }
+
+    pub fn resize(&mut self, column: RecordColumn, delta: f32) {
+        let width = (self.width(column) + delta).max(MIN_COLUMN_WIDTH);
+        self.widths.insert(column, width);
+    }
+
+    /// Moves `column` into the position currently held by `target`.
+    pub fn move_column(&mut self, column: RecordColumn, target: RecordColumn) {
+        self.ensure_all_columns();
+        let (Some(from), Some(to)) = (self.position(column), self.position(target)) else {
+            return;
+        };
+        let column = self.order.remove(from);
+        self.order.insert(to, column);
+    }
+
+    /// Columns in display order. Layouts saved by older versions get any new columns appended.
+    pub fn ordered(&self) -> Vec<RecordColumn> {
+        let mut order = self.order.clone();
+        for column in RecordColumn::ALL {
+            if !order.contains(&column) {
+                order.push(column);
+            }
+        }
+        order
+    }
+
+    fn ensure_all_columns(&mut self) {
+        self.order = self.ordered();
+    }
+
+    fn position(&self, column: RecordColumn) -> Option<usize> {
+        self.order.iter().position(|c| *c == column)
+    }
+}
@@ -0,0 +1,71 @@
+/// State for the Ctrl+Shift+P command palette.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    pub query: String,
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Returns the indices of `labels` matching the query, best match first.
+    pub fn matches<S: AsRef<str>>(&self, labels: &[S]) -> Vec<usize> {
+        let mut scored: Vec<(usize, i32)> = labels.iter()
+            .enumerate()
+            .filter_map(|(i, label)| fuzzy_score(&self.query, label.as_ref()).map(|score| (i, score)))
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.into_iter().map(|(i, _)| i).collect()
+    }
+}
+
+/// Scores `candidate` against `query` as a case-insensitive subsequence match.
+/// Consecutive characters and matches at word starts score higher; `None` means no match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (i, c) in candidate.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if *c != query[query_index] {
+            continue;
+        }
+
+        score += 1;
+        if previous_match == Some(i.wrapping_sub(1)) {
+            score += 5;
+        }
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(i);
+        query_index += 1;
+    }
+
+    if query_index == query.len() {
+        // Prefer shorter labels when scores tie
+        Some(score * 100 - candidate.len() as i32)
+    } else {
+        None
+    }
+}
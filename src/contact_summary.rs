@@ -0,0 +1,65 @@
+use crate::data_models::ProcessedCallRecord;
+use crate::filters::is_night_hour;
+use chrono::{DateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+/// One contact's calls across the loaded records, totalled for the contact summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactSummary {
+    pub number: String,
+    pub first_contact: DateTime<Utc>,
+    pub last_contact: DateTime<Utc>,
+    pub total_calls: usize,
+    pub incoming: usize,
+    pub outgoing: usize,
+    /// Seconds on calls with the contact.
+    pub total_seconds: u64,
+    /// Calls starting within the night hours, in the timezone summarized with.
+    pub night_calls: usize,
+}
+
+impl ContactSummary {
+    fn new(record: &ProcessedCallRecord) -> Self {
+        Self {
+            number: record.normalized_number.clone(),
+            first_contact: record.start_time,
+            last_contact: record.start_time,
+            total_calls: 0,
+            incoming: 0,
+            outgoing: 0,
+            total_seconds: 0,
+            night_calls: 0,
+        }
+    }
+
+    /// Average seconds per call.
+    pub fn average_seconds(&self) -> f64 {
+        if self.total_calls == 0 { 0.0 } else { self.total_seconds as f64 / self.total_calls as f64 }
+    }
+
+    /// Summarizes every contact identifying a party, busiest first, with short codes and
+    /// service numbers only if `include_short_codes`.
+    pub fn summarize(records: &[ProcessedCallRecord], timezone: Tz, night_hours: (u32, u32), include_short_codes: bool) -> Vec<Self> {
+        let mut contacts: HashMap<&str, ContactSummary> = HashMap::new();
+        for record in records.iter().filter(|r| r.number_kind.is_contact(include_short_codes)) {
+            let contact = contacts.entry(record.normalized_number.as_str())
+                .or_insert_with(|| Self::new(record));
+            contact.first_contact = contact.first_contact.min(record.start_time);
+            contact.last_contact = contact.last_contact.max(record.start_time);
+            contact.total_calls += 1;
+            if record.message_direction.eq_ignore_ascii_case("incoming") {
+                contact.incoming += 1;
+            } else {
+                contact.outgoing += 1;
+            }
+            contact.total_seconds += record.length_of_call as u64;
+            if is_night_hour(record.start_time.with_timezone(&timezone).hour(), night_hours) {
+                contact.night_calls += 1;
+            }
+        }
+        let mut contacts: Vec<Self> = contacts.into_values().collect();
+        contacts.sort_by(|a, b| b.total_calls.cmp(&a.total_calls).then_with(|| a.number.cmp(&b.number)));
+        contacts
+    }
+}
@@ -0,0 +1,238 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Header names, compared case-insensitively, for the name of a contact in a CSV.
+const NAME_HEADERS: &[&str] = &["name", "full name", "display name", "contact", "contact name", "alias", "associate"];
+const FIRST_NAME_HEADERS: &[&str] = &["first name", "given name"];
+const LAST_NAME_HEADERS: &[&str] = &["last name", "family name", "surname"];
+/// Optional column for who a contact is to the case, added to the name in parentheses.
+const ROLE_HEADERS: &[&str] = &["role", "relationship", "relation", "title"];
+/// Headers containing any of these, compared case-insensitively, hold phone numbers. Phone
+/// exports spread numbers over several columns ("Phone 1 - Value", "Mobile Phone", ...).
+const PHONE_HEADER_WORDS: &[&str] = &["phone", "mobile", "number", "tel", "cell"];
+/// Headers that mention a phone but hold its label rather than the number.
+const PHONE_LABEL_WORDS: &[&str] = &["type", "label"];
+
+/// Names from a phone's contact export or a list of known associates, by normalized number.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactList {
+    names: HashMap<String, String>,
+}
+
+impl ContactList {
+    /// Reads a vCard file (`.vcf`) or a CSV, by extension.
+    pub fn import(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to open contact list: {:?}", path))?;
+        let is_vcard = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("vcf") || ext.eq_ignore_ascii_case("vcard"));
+        let list = if is_vcard {
+            Self::from_vcard(&content)
+        } else {
+            Self::from_csv(content.as_bytes())
+        }
+        .with_context(|| format!("Failed to read contact list: {:?}", path))?;
+        info!("Imported {} contact numbers from {:?}", list.len(), path);
+        Ok(list)
+    }
+
+    /// The first row must name a name column (or first and last name columns) and at least
+    /// one phone column, and may name a role column. A cell may hold several numbers
+    /// separated by `:::`, `;`, or `,`.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::contacts::ContactList;
+    ///
+    /// let csv = "Name,Role,Phone\nPat Doe,Supplier,(256) 555-0100\nSam Roe,,256-555-0111\n";
+    /// let contacts = ContactList::from_csv(csv.as_bytes()).unwrap();
+    /// let mut names: Vec<_> = contacts.names().collect();
+    /// names.sort();
+    /// assert_eq!(names, [("2565550100", "Pat Doe (Supplier)"), ("2565550111", "Sam Roe")]);
+    /// ```
+    pub fn from_csv<R: std::io::Read>(reader: R) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let find = |names: &[&str]| headers.iter().position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)));
+        let name_columns = match (find(NAME_HEADERS), find(FIRST_NAME_HEADERS), find(LAST_NAME_HEADERS)) {
+            (Some(col), _, _) => vec![col],
+            (None, first, last) if first.is_some() || last.is_some() => first.into_iter().chain(last).collect(),
+            _ => bail!("No contact name column; expected a header such as \"Name\" or \"First Name\""),
+        };
+        let role_column = find(ROLE_HEADERS);
+        let phone_columns: Vec<usize> = headers.iter().enumerate()
+            .filter(|(col, _)| !name_columns.contains(col) && role_column != Some(*col))
+            .filter(|(_, header)| {
+                let header = header.to_ascii_lowercase();
+                PHONE_HEADER_WORDS.iter().any(|word| header.contains(word))
+                    && !PHONE_LABEL_WORDS.iter().any(|word| header.contains(word))
+            })
+            .map(|(col, _)| col)
+            .collect();
+        if phone_columns.is_empty() {
+            bail!("No phone number column; expected a header such as \"Phone\" or \"Mobile\"");
+        }
+
+        let mut list = Self::default();
+        for (index, row) in reader.records().enumerate() {
+            let row = row?;
+            let name = name_columns.iter()
+                .filter_map(|&col| row.get(col))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if name.is_empty() {
+                // Row 1 is the header
+                warn!("Skipping contact row {}: no name", index + 2);
+                continue;
+            }
+            let name = match role_column.and_then(|col| row.get(col)).filter(|role| !role.is_empty()) {
+                Some(role) => format!("{} ({})", name, role),
+                None => name,
+            };
+            for &col in &phone_columns {
+                for number in row.get(col).unwrap_or_default().split([':', ';', ',']) {
+                    list.insert(number, &name);
+                }
+            }
+        }
+        Ok(list)
+    }
+
+    /// Reads every `BEGIN:VCARD` ... `END:VCARD` card, naming each number after the card's
+    /// `FN` (or, failing that, its `N`) property.
+    pub fn from_vcard(content: &str) -> Result<Self> {
+        let mut list = Self::default();
+        let mut cards = 0;
+        let mut card: Option<VCard> = None;
+        for line in unfold_lines(content) {
+            let Some((property, value)) = line.split_once(':') else {
+                continue;
+            };
+            // Drop parameters (`TEL;TYPE=CELL`) and group prefixes (`item1.TEL`)
+            let name = property.split(';').next().unwrap_or_default();
+            let name = name.rsplit('.').next().unwrap_or_default().to_ascii_uppercase();
+            match (name.as_str(), card.as_mut()) {
+                ("BEGIN", _) if value.trim().eq_ignore_ascii_case("VCARD") => card = Some(VCard::default()),
+                ("END", Some(_)) if value.trim().eq_ignore_ascii_case("VCARD") => {
+                    let finished = card.take().unwrap_or_default();
+                    cards += 1;
+                    let Some(name) = finished.name() else {
+                        warn!("Skipping vCard {}: no name", cards);
+                        continue;
+                    };
+                    for number in &finished.numbers {
+                        list.insert(number, &name);
+                    }
+                }
+                ("FN", Some(card)) => card.formatted_name = Some(unescape_vcard(value)),
+                ("N", Some(card)) => card.structured_name = Some(value.to_string()),
+                // vCard 4 may write the number as a `tel:` URI
+                ("TEL", Some(card)) => card.numbers.push(value.trim().trim_start_matches("tel:").to_string()),
+                _ => {}
+            }
+        }
+        if cards == 0 {
+            bail!("No vCards found; expected BEGIN:VCARD ... END:VCARD entries");
+        }
+        Ok(list)
+    }
+
+    /// Skips values that are not phone numbers, such as empty cells.
+    fn insert(&mut self, raw_number: &str, name: &str) {
+        let number = normalize_phone_number(raw_number.trim());
+        if number.is_valid() {
+            self.names.insert(number.into_string(), name.to_string());
+        }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.names.iter().map(|(number, name)| (number.as_str(), name.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// How the loaded numbers line up with the known contacts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContactMatches {
+    /// Loaded contacts that have an alias, busiest first: number, alias, calls.
+    pub matched: Vec<(String, String, usize)>,
+    /// Most frequent numbers with no alias, subscriber, or caller name: number, calls.
+    pub unidentified: Vec<(String, usize)>,
+}
+
+impl ContactMatches {
+    /// `frequent` is the most-frequent-numbers ranking; `identified` says whether a number is
+    /// known some other way (subscriber results, caller ID).
+    pub fn build(
+        records: &[ProcessedCallRecord],
+        aliases: &HashMap<String, String>,
+        frequent: &[(String, usize)],
+        identified: impl Fn(&str) -> bool,
+    ) -> Self {
+        let mut calls: HashMap<&str, usize> = HashMap::new();
+        for record in records.iter().filter(|r| aliases.contains_key(&r.normalized_number)) {
+            *calls.entry(record.normalized_number.as_str()).or_insert(0) += 1;
+        }
+        let mut matched: Vec<(String, String, usize)> = calls.into_iter()
+            .map(|(number, count)| (number.to_string(), aliases[number].clone(), count))
+            .collect();
+        matched.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+
+        let unidentified = frequent.iter()
+            .filter(|(number, _)| !aliases.contains_key(number) && !identified(number.as_str()))
+            .cloned()
+            .collect();
+        Self { matched, unidentified }
+    }
+}
+
+#[derive(Debug, Default)]
+struct VCard {
+    formatted_name: Option<String>,
+    /// `Family;Given;Additional;Prefix;Suffix`.
+    structured_name: Option<String>,
+    numbers: Vec<String>,
+}
+
+impl VCard {
+    fn name(&self) -> Option<String> {
+        if let Some(name) = self.formatted_name.as_ref().filter(|name| !name.trim().is_empty()) {
+            return Some(name.trim().to_string());
+        }
+        let parts: Vec<String> = self.structured_name.as_deref()?.split(';').map(unescape_vcard).collect();
+        // Given name first, then family name
+        let name = [parts.get(1), parts.first()].into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// Joins folded vCard lines: a line starting with a space or tab continues the one before.
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape_vcard(value: &str) -> String {
+    value.trim().replace("\\n", " ").replace("\\N", " ").replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\")
+}
@@ -0,0 +1,134 @@
+use crate::annotations::Annotations;
+use crate::cnam::CallerNames;
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::settings::AppSettings;
+use crate::tr;
+use anyhow::{Context, Result};
+use chrono_tz::Tz;
+use log::info;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Plain CSV export of the call records, with the same columns as the Excel "Call Records" sheet.
+pub struct CsvExporter;
+
+impl CsvExporter {
+    pub fn export_call_records(records: &[ProcessedCallRecord], annotations: &Annotations, caller_names: &CallerNames, settings: &AppSettings, output_path: &Path) -> Result<()> {
+        info!("Exporting call records to CSV: {:?}", output_path);
+
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create file: {:?}", output_path))?;
+        Self::write_call_records(file, records, annotations, caller_names, settings)?;
+
+        info!("Successfully exported {} records to CSV", records.len());
+        Ok(())
+    }
+
+    /// Same as [`CsvExporter::export_call_records`] but into memory, for the browser build's downloads.
+    pub fn call_records_to_bytes(records: &[ProcessedCallRecord], annotations: &Annotations, caller_names: &CallerNames, settings: &AppSettings) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        Self::write_call_records(&mut buffer, records, annotations, caller_names, settings)?;
+        Ok(buffer)
+    }
+
+    pub fn write_call_records<W: Write>(writer: W, records: &[ProcessedCallRecord], annotations: &Annotations, caller_names: &CallerNames, settings: &AppSettings) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+
+        writer.write_record(call_record_headers(settings.case_timezone))?;
+
+        for record in records {
+            writer.write_record(call_record_row(record, annotations, caller_names, settings))?;
+        }
+
+        writer.flush().context("Failed to write CSV")?;
+        Ok(())
+    }
+
+    /// Writes the records each production lost, one row apiece: `<results>` blocks passed over
+    /// as malformed XML, with the line and element where they break off, then records that
+    /// could not be normalized.
+    pub fn export_parse_errors(reports: &[ParseReport], output_path: &Path) -> Result<()> {
+        info!("Exporting parse errors to CSV: {:?}", output_path);
+
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create file: {:?}", output_path))?;
+        Self::write_parse_errors(file, reports)
+    }
+
+    /// Same as [`CsvExporter::export_parse_errors`] but into memory, for the browser build's downloads.
+    pub fn parse_errors_to_bytes(reports: &[ParseReport]) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        Self::write_parse_errors(&mut buffer, reports)?;
+        Ok(buffer)
+    }
+
+    pub fn write_parse_errors<W: Write>(writer: W, reports: &[ParseReport]) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+
+        writer.write_record([
+            tr!("header-source-file"), tr!("header-problem"), tr!("header-record-position"), tr!("header-line"),
+            tr!("header-element"), tr!("header-remote-number"), tr!("header-raw-start"), tr!("header-reason"),
+        ])?;
+
+        for report in reports {
+            for block in &report.malformed {
+                writer.write_record([
+                    report.source_file.clone(),
+                    tr!("problem-malformed-xml"),
+                    block.position.to_string(),
+                    block.line.to_string(),
+                    block.element.clone(),
+                    String::new(),
+                    String::new(),
+                    block.reason.clone(),
+                ])?;
+            }
+            for skipped in &report.skipped {
+                writer.write_record([
+                    report.source_file.clone(),
+                    tr!("problem-unreadable-record"),
+                    skipped.position.to_string(),
+                    String::new(),
+                    String::new(),
+                    skipped.remote_number.clone(),
+                    skipped.start_time.clone(),
+                    skipped.reason.clone(),
+                ])?;
+            }
+        }
+
+        writer.flush().context("Failed to write CSV")?;
+        Ok(())
+    }
+}
+
+/// One record as text, in the columns of [`call_record_headers`], with times and the day in
+/// the case timezone written in the export locale.
+pub fn call_record_row(record: &ProcessedCallRecord, annotations: &Annotations, caller_names: &CallerNames, settings: &AppSettings) -> [String; 12] {
+    let locale = settings.export_locale();
+    [
+        record.message_direction.clone(),
+        settings.format_number(&record.target_number),
+        record.remote_number.clone(),
+        settings.format_number(&record.normalized_number),
+        locale.format_date_time(&record.start_time.with_timezone(&settings.case_timezone)),
+        locale.format_date_time(&record.end_time.with_timezone(&settings.case_timezone)),
+        record.length_of_call.to_string(),
+        locale.format_decimal(record.duration_minutes, 2),
+        record.local_day_of_week(settings.case_timezone).to_string(),
+        record.source_file.clone(),
+        caller_names.lookup(&record.normalized_number).unwrap_or_default().to_string(),
+        annotations.alias(&record.normalized_number).unwrap_or_default().to_string(),
+    ]
+}
+
+/// Column headers of the call records export, shared with the Excel "Call Records" sheet.
+pub fn call_record_headers(timezone: Tz) -> [String; 12] {
+    let timezone = timezone.name();
+    [
+        tr!("header-direction"), tr!("header-target-number"), tr!("header-remote-number"), tr!("header-normalized-number"),
+        tr!("header-start-time-zone", timezone = timezone), tr!("header-end-time-zone", timezone = timezone), tr!("header-duration-sec"), tr!("header-duration-min"),
+        tr!("header-day-of-week"), tr!("header-source-file"), tr!("header-caller-name"), tr!("header-alias"),
+    ]
+}
@@ -0,0 +1,167 @@
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{ColumnMap, DelimitedParser, MappingProfile};
+use crate::format_detection::{FormatDetection, ParserKind, DETECTION_SAMPLE_BYTES};
+use crate::pipeline::DuplicatePolicy;
+use anyhow::{bail, Context, Result};
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+/// Rows of a sample shown while the columns are being mapped.
+const PREVIEW_ROWS: usize = 5;
+
+/// Columns picked by hand for a CSV production, for layouts no [`MappingProfile`] knows
+/// or ones a profile reads wrongly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CsvMapping {
+    pub columns: ColumnMap,
+    /// Whether slash dates are day first, e.g. 03/04 is the 3rd of April.
+    pub day_first: bool,
+}
+
+impl CsvMapping {
+    /// The columns a preset finds in the header row, as a starting point to adjust.
+    pub fn from_profile(headers: &StringRecord, profile: MappingProfile) -> Self {
+        Self {
+            columns: ColumnMap::resolve(headers, profile.columns()),
+            day_first: profile.day_first(),
+        }
+    }
+
+    /// Whether the other party, the start, and the length of each call have columns.
+    pub fn is_complete(&self) -> bool {
+        self.columns.is_complete()
+    }
+}
+
+/// Where a production's rows are in a workbook, and its columns picked by hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SheetMapping {
+    /// Position of the worksheet in the workbook, counting from 0.
+    pub sheet: usize,
+    /// Row with the column names, counting from 0 as the sheet does; the rows above it are
+    /// passed over.
+    pub header_row: usize,
+    pub mapping: CsvMapping,
+}
+
+/// Reads call detail from CSV, TSV, or other delimited text with the columns mapped by the
+/// user rather than found by name. Times without an offset are taken to be in the timezone
+/// given, as with [`DelimitedParser`].
+pub struct CsvParser;
+
+impl CsvParser {
+    pub fn parse_file(
+        path: &Path,
+        mapping: &CsvMapping,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing CSV file with mapped columns: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, mapping, timezone, duplicates)
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        mapping: &CsvMapping,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        if !mapping.is_complete() {
+            bail!("{} needs columns for the remote number, start time, and duration", source_file);
+        }
+        let mut reader = DelimitedParser::reader(bytes);
+        reader.headers().context("Failed to read the header row")?;
+        DelimitedParser::parse_rows(reader, source_file, &mapping.columns, mapping.day_first, timezone, duplicates)
+    }
+}
+
+/// The header row and first rows of a delimited production, for mapping its columns before
+/// the whole file is parsed.
+#[derive(Debug, Clone)]
+pub struct CsvSample {
+    pub source_file: String,
+    pub headers: StringRecord,
+    pub rows: Vec<StringRecord>,
+    pub detection: FormatDetection,
+}
+
+impl CsvSample {
+    /// Reads the first [`DETECTION_SAMPLE_BYTES`] of the file.
+    pub fn read_file(path: &Path) -> Result<Self> {
+        let mut sample = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(DETECTION_SAMPLE_BYTES).read_to_end(&mut sample))
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::from_bytes(source_file, &sample)
+    }
+
+    /// XML, JSON, PDFs, and carrier layouts have no columns to map, so a sample of them keeps only the
+    /// detection.
+    pub fn from_bytes(source_file: &str, bytes: &[u8]) -> Result<Self> {
+        Self::with_detection(source_file, bytes, FormatDetection::detect(bytes))
+    }
+
+    /// A sample of text known to be delimited, e.g. rows of a worksheet written out as CSV,
+    /// so no other layout is considered.
+    pub(crate) fn from_delimited(source_file: &str, bytes: &[u8]) -> Result<Self> {
+        Self::with_detection(source_file, bytes, FormatDetection::detect_delimited(bytes))
+    }
+
+    fn with_detection(source_file: &str, bytes: &[u8], detection: FormatDetection) -> Result<Self> {
+        let mut sample = Self {
+            source_file: source_file.to_string(),
+            headers: StringRecord::new(),
+            rows: Vec::new(),
+            detection,
+        };
+        if sample.fixed_parser().is_some() {
+            return Ok(sample);
+        }
+        let mut reader = DelimitedParser::reader(bytes);
+        sample.headers = reader.headers().context("Failed to read the header row")?.clone();
+        sample.rows = reader.records().take(PREVIEW_ROWS).filter_map(|row| row.ok()).collect();
+        Ok(sample)
+    }
+
+    /// The parser detection picked when it reads the file without columns mapped: XML, JSON,
+    /// a PDF, or a carrier's own layout.
+    pub fn fixed_parser(&self) -> Option<ParserKind> {
+        self.detection.parser.filter(|parser| {
+            !matches!(parser, ParserKind::Delimited(_) | ParserKind::Csv(_) | ParserKind::Spreadsheet(_))
+        })
+    }
+
+    /// The preset detection picked, with the columns it finds; the generic names when none
+    /// fits, so whatever columns they do match are filled in.
+    pub fn suggested_mapping(&self) -> (MappingProfile, CsvMapping) {
+        let profile = match self.detection.parser {
+            Some(ParserKind::Delimited(profile)) => profile,
+            _ => MappingProfile::Generic,
+        };
+        (profile, CsvMapping::from_profile(&self.headers, profile))
+    }
+
+    /// The sampled rows as they would be read with `mapping`, or why each would be skipped.
+    pub fn preview(&self, mapping: &CsvMapping, timezone: Tz) -> Vec<Result<ProcessedCallRecord, String>> {
+        self.rows.iter()
+            .map(|row| {
+                let (target, call) = DelimitedParser::call_record(row, &mapping.columns, mapping.day_first, timezone);
+                ProcessedCallRecord::from_call_record(&call, &target, &self.source_file).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+}
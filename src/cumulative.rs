@@ -0,0 +1,55 @@
+use crate::data_models::ProcessedCallRecord;
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+/// Contacts given their own series on the chart.
+pub const CUMULATIVE_TOP_CONTACTS: usize = 5;
+
+/// Running call totals per day, for spotting when communication speeds up or stops.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CumulativeCalls {
+    /// Every day from the first call to the last, including days without calls so a stop
+    /// shows as a flat line.
+    pub days: Vec<NaiveDate>,
+    /// Calls up to and including each day.
+    pub total: Vec<usize>,
+    /// The same running totals for each contact, in the order the contacts were given.
+    pub contacts: Vec<(String, Vec<usize>)>,
+}
+
+impl CumulativeCalls {
+    /// Days are taken in `timezone`, matching the calls-by-day figures.
+    pub fn build(records: &[ProcessedCallRecord], contacts: &[String], timezone: Tz) -> Self {
+        let mut per_day: HashMap<NaiveDate, usize> = HashMap::new();
+        let mut per_contact_day: HashMap<(&str, NaiveDate), usize> = HashMap::new();
+        for record in records {
+            let day = record.start_time.with_timezone(&timezone).date_naive();
+            *per_day.entry(day).or_insert(0) += 1;
+            if contacts.contains(&record.normalized_number) {
+                *per_contact_day.entry((record.normalized_number.as_str(), day)).or_insert(0) += 1;
+            }
+        }
+        let (Some(&first), Some(&last)) = (per_day.keys().min(), per_day.keys().max()) else {
+            return Self::default();
+        };
+
+        let days: Vec<NaiveDate> = (0..=(last - first).num_days()).map(|offset| first + Duration::days(offset)).collect();
+        let running = |count: &dyn Fn(NaiveDate) -> usize| {
+            days.iter()
+                .scan(0, |sum, day| {
+                    *sum += count(*day);
+                    Some(*sum)
+                })
+                .collect::<Vec<usize>>()
+        };
+        let total = running(&|day| per_day.get(&day).copied().unwrap_or(0));
+        let contacts = contacts.iter()
+            .map(|number| {
+                let series = running(&|day| per_contact_day.get(&(number.as_str(), day)).copied().unwrap_or(0));
+                (number.clone(), series)
+            })
+            .collect();
+        Self { days, total, contacts }
+    }
+}
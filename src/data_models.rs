@@ -1,4 +1,9 @@
-use chrono::{DateTime, Utc};
+use crate::format_detection::{ParserKind, ReadFormat};
+use crate::pdf_parser::PdfPattern;
+use crate::phone_region::{self, PhoneRegion};
+use crate::tr;
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Offset, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -18,10 +23,58 @@ pub struct Lds101Results {
     pub from_date: String,
     #[serde(rename = "toDate")]
     pub to_date: String,
+    /// Number of `<results>` the carrier says the production holds, when it says.
+    #[serde(rename = "recordCount", alias = "totalRecords", default)]
+    pub record_count: Option<usize>,
     pub results: Vec<CallRecord>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Lds101Results {
+    pub fn header(&self) -> ProductionHeader {
+        ProductionHeader {
+            target_value: self.target_value.clone(),
+            from_date: parse_declared_date(&self.from_date, false),
+            to_date: parse_declared_date(&self.to_date, true),
+            expected_records: self.record_count,
+        }
+    }
+}
+
+/// What a production states about itself ahead of its `<results>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductionHeader {
+    pub target_value: String,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+    pub expected_records: Option<usize>,
+}
+
+/// Hours a date-only bound is widened by, since the production does not say which zone the
+/// date is in and records are compared in UTC. Covers UTC-12 through UTC+14.
+const DATE_ONLY_SLACK_HOURS: i64 = 14;
+
+/// Reads a declared `fromDate`/`toDate`, either a full RFC 3339 timestamp or a bare date.
+/// Bare dates cover the whole day in any zone: `end_of_day` picks which end of it to return.
+pub fn parse_declared_date(value: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    let date = ["%Y-%m-%d", "%m/%d/%Y", "%Y%m%d"].iter()
+        .find_map(|format| chrono::NaiveDate::parse_from_str(value, format).ok())?;
+    let slack = chrono::Duration::hours(DATE_ONLY_SLACK_HOURS);
+    let bound = if end_of_day {
+        date.and_hms_opt(23, 59, 59)?.and_utc() + slack
+    } else {
+        date.and_hms_opt(0, 0, 0)?.and_utc() - slack
+    };
+    Some(bound)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CallRecord {
     #[serde(rename = "messageDirection")]
     pub message_direction: String,
@@ -33,13 +86,48 @@ pub struct CallRecord {
     pub end_time: String,
     #[serde(rename = "lengthOfCall")]
     pub length_of_call: u32,
+    /// Text of the message, for the providers that return it with text records.
+    #[serde(rename = "messageContent", alias = "messageBody", default)]
+    pub message_content: Option<String>,
+    /// The production's own word for the kind of record ("Voice", "SMS", ...), when it has one.
+    #[serde(rename = "messageType", alias = "recordType", default)]
+    pub record_type: Option<String>,
+    /// Serving cell site and sector, and the tower's coordinates, for the productions that
+    /// give them.
+    #[serde(rename = "cellSiteId", alias = "cellId", alias = "cellSite", default)]
+    pub cell_site_id: Option<String>,
+    #[serde(rename = "sector", alias = "sectorId", default)]
+    pub sector: Option<String>,
+    #[serde(rename = "latitude", alias = "lat", default)]
+    pub latitude: Option<String>,
+    #[serde(rename = "longitude", alias = "lon", alias = "long", default)]
+    pub longitude: Option<String>,
+    /// The target's handset (IMEI) and SIM (IMSI), for the productions that give them.
+    #[serde(rename = "imei", alias = "IMEI", default)]
+    pub imei: Option<String>,
+    #[serde(rename = "imsi", alias = "IMSI", default)]
+    pub imsi: Option<String>,
+    /// The production's disposition or call feature code ("CFW", "VM", "Roaming", ...),
+    /// when it has one.
+    #[serde(rename = "callDisposition", alias = "disposition", alias = "featureCode", alias = "callFeature", default)]
+    pub disposition: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessedCallRecord {
     pub message_direction: String,
     pub remote_number: String,
+    /// [`NormalizedNumber::as_str`] of `remote_number`.
     pub normalized_number: String,
+    /// Snapshots saved before numbers were classified load as [`NumberKind::Nanp`].
+    #[serde(default)]
+    pub number_kind: NumberKind,
+    /// Snapshots saved before record types were read load as [`RecordType::Voice`].
+    #[serde(default)]
+    pub record_type: RecordType,
+    /// Snapshots saved before dispositions were read load as [`CallDisposition::Completed`].
+    #[serde(default)]
+    pub disposition: CallDisposition,
     pub target_number: String,
     pub source_file: String,
     pub start_time: DateTime<Utc>,
@@ -50,6 +138,15 @@ pub struct ProcessedCallRecord {
     pub time: String,
     pub date_time: String,
     pub day_of_week: String,
+    /// Text of the message, when the production includes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_content: Option<String>,
+    /// The tower that served the call, when the production includes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell_site: Option<CellSite>,
+    /// The target's handset and SIM on the call, when the production includes them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<DeviceIds>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +157,25 @@ pub struct Analytics {
     pub total_duration_minutes: f64,
     pub average_call_duration: f64,
     pub unique_numbers: usize,
+    /// Calls whose caller ID was withheld or unavailable; they count toward the totals but
+    /// not toward `unique_numbers` or the rankings.
+    #[serde(default)]
+    pub unknown_number_calls: usize,
+    /// Calls with short codes and service numbers left out of the rankings and the other
+    /// contact analytics; zero when they are included.
+    #[serde(default)]
+    pub short_code_calls: usize,
+    /// Text and multimedia messages among the records; `total_calls` counts them too, but
+    /// the average duration and the answer rates do not.
+    #[serde(default)]
+    pub sms_count: usize,
+    #[serde(default)]
+    pub mms_count: usize,
+    /// Records with each disposition other than [`CallDisposition::Completed`], in
+    /// [`CallDisposition::ALL`] order, leaving out those with none; empty when the
+    /// productions give no disposition codes.
+    #[serde(default)]
+    pub calls_by_disposition: Vec<(CallDisposition, usize)>,
     pub most_frequent_numbers: Vec<(String, usize)>,
     pub calls_by_day: HashMap<String, usize>,
     pub calls_by_hour: HashMap<u32, usize>,
@@ -67,8 +183,47 @@ pub struct Analytics {
     pub shortest_call: Option<ProcessedCallRecord>,
     pub target_numbers: std::collections::HashSet<String>,
     pub common_contacts: Vec<CommonContact>,
+    /// NPA-NXX prefixes shared by several contacted numbers, largest first.
+    #[serde(default)]
+    pub exchange_clusters: Vec<ExchangeCluster>,
+    /// Calls with NANP numbers per region of their area code (`US-AL`, `CA-ON`, ...; see
+    /// [`area_codes`](crate::area_codes)), busiest first.
+    #[serde(default)]
+    pub calls_by_region: Vec<(String, usize)>,
+    /// Contacted NANP numbers grouped by area code, busiest first.
+    #[serde(default)]
+    pub area_code_activity: Vec<AreaCodeActivity>,
+    /// Contacts the target called often enough to judge, those that stopped answering first.
+    #[serde(default)]
+    pub answer_rates: Vec<ContactAnswerRate>,
+    /// Contacts with any night-hours calls, highest odd-hours score first.
+    #[serde(default)]
+    pub odd_hours_contacts: Vec<ContactNightActivity>,
+    /// Contacts the targets exchanged messages with, most messages first.
+    #[serde(default)]
+    pub messages_per_contact: Vec<ContactMessages>,
+    /// Every contact's first and last call or message, earliest first contact first.
+    #[serde(default)]
+    pub contact_spans: Vec<ContactSpan>,
+    /// Target numbers on several devices and devices with several target numbers.
+    #[serde(default)]
+    pub device_changes: Vec<DeviceChange>,
     pub files_processed: std::collections::HashSet<String>,
     pub date_range: (DateTime<Utc>, DateTime<Utc>),
+    /// Targets whose records were loaded but left out of these analytics.
+    #[serde(default)]
+    pub excluded_targets: std::collections::HashSet<String>,
+    /// Records the validation pass flagged, left out of these analytics by the setting.
+    #[serde(default)]
+    pub flagged_records_excluded: usize,
+    /// Zone `calls_by_day` and `calls_by_hour` were bucketed in.
+    #[serde(default = "utc_timezone")]
+    pub timezone: chrono_tz::Tz,
+}
+
+/// Bucketing zone of analytics cached before it was configurable.
+fn utc_timezone() -> chrono_tz::Tz {
+    chrono_tz::Tz::UTC
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,57 +233,1001 @@ pub struct CommonContact {
     pub count: usize,
 }
 
+/// Contacted numbers from one NPA-NXX exchange, which are often prepaid phones bought
+/// together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeCluster {
+    /// Six-digit NPA-NXX.
+    pub prefix: String,
+    /// Numbers in the exchange with their call counts, busiest first.
+    pub numbers: Vec<(String, usize)>,
+    pub total_calls: usize,
+}
+
+/// How close to the end of a target's records a contact's last call or message with the
+/// target must be for the contact to count as ongoing.
+pub const ONGOING_CONTACT_DAYS: i64 = 7;
+
+/// When a contact was first and last in touch with the targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactSpan {
+    pub number: String,
+    /// Calls and messages with the contact.
+    pub calls: usize,
+    pub first_contact: DateTime<Utc>,
+    pub last_contact: DateTime<Utc>,
+    /// Whether the last contact with some target was within [`ONGOING_CONTACT_DAYS`] of the
+    /// end of that target's records, so it likely went on past the production.
+    pub ongoing: bool,
+}
+
+impl ContactSpan {
+    /// Time from the first contact to the last.
+    pub fn span(&self) -> chrono::Duration {
+        self.last_contact - self.first_contact
+    }
+
+    /// [`span`](Self::span) in days, with the fraction of a day.
+    pub fn span_days(&self) -> f64 {
+        self.span().num_seconds() as f64 / 86_400.0
+    }
+}
+
+/// Contacted numbers sharing a North American area code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AreaCodeActivity {
+    /// Three-digit NPA.
+    pub area_code: String,
+    /// Where the area code is (`US-AL`, ...); none for toll-free and other non-geographic codes.
+    pub region: Option<String>,
+    /// Distinct numbers contacted.
+    pub numbers: usize,
+    pub calls: usize,
+}
+
+/// Unanswered calls in a row, after the contact had picked up before, that count as the
+/// contact having stopped answering.
+pub const STOPPED_ANSWERING_STREAK: usize = 3;
+
+/// How often a contact picked up when the target called. A call counts as unanswered when it
+/// lasted no longer than the configured short-call threshold (zero-duration calls always do).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactAnswerRate {
+    pub number: String,
+    /// Calls the target placed to the contact.
+    pub attempts: usize,
+    pub answered: usize,
+    /// Most unanswered attempts in a row.
+    pub longest_unanswered_streak: usize,
+    /// Unanswered attempts after the last answered one, up to the end of the records.
+    pub trailing_unanswered: usize,
+    pub last_answered: Option<DateTime<Utc>>,
+    /// Calls from the contact, and how many of them the target did not pick up.
+    pub incoming: usize,
+    pub incoming_unanswered: usize,
+}
+
+impl ContactAnswerRate {
+    /// Share of attempts answered, as a percentage.
+    pub fn answer_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.answered as f64 * 100.0 / self.attempts as f64
+        }
+    }
+
+    /// Whether the contact used to pick up but has let the latest calls go unanswered.
+    pub fn stopped_answering(&self) -> bool {
+        self.answered > 0 && self.trailing_unanswered >= STOPPED_ANSWERING_STREAK
+    }
+}
+
+/// How much of a contact's activity with the targets falls in the night hours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactNightActivity {
+    pub number: String,
+    pub calls: usize,
+    /// Calls starting within the night hours, in the analytics timezone.
+    pub night_calls: usize,
+}
+
+impl ContactNightActivity {
+    /// Share of the contact's calls made at night, as a percentage.
+    pub fn night_share(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.night_calls as f64 * 100.0 / self.calls as f64
+        }
+    }
+
+    /// The night share (as a fraction) weighted by `ln(1 + night calls)`, so a contact
+    /// reached mostly at night over many calls outranks one whose only call was at 2 AM, and
+    /// a busy daytime contact with a few late calls does not rank high on volume alone.
+    pub fn odd_hours_score(&self) -> f64 {
+        self.night_share() / 100.0 * (self.night_calls as f64).ln_1p()
+    }
+}
+
+/// Which way a device and a target number came apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceChangeKind {
+    /// One target number used on more than one device.
+    NumberOnDevices,
+    /// One device used with more than one target number.
+    DeviceOnNumbers,
+}
+
+/// A target number seen on more than one handset or SIM in the productions, or a handset or
+/// SIM seen with more than one target number, a sign of a phone or SIM swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceChange {
+    pub change: DeviceChangeKind,
+    pub id_kind: DeviceIdKind,
+    /// The number, or the device's identifier.
+    pub subject: String,
+    /// The devices the number was on, or the numbers the device carried, earliest first.
+    pub spans: Vec<DeviceSpan>,
+}
+
+/// The calls of one target number on one device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceSpan {
+    /// The device's identifier, or the number, whichever the change is not about.
+    pub value: String,
+    pub calls: usize,
+    pub first: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+}
+
+/// The messages exchanged with one contact.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactMessages {
+    pub number: String,
+    pub sms: usize,
+    pub mms: usize,
+    /// Messages the targets sent to the contact.
+    pub sent: usize,
+    pub received: usize,
+}
+
+impl ContactMessages {
+    pub fn total(&self) -> usize {
+        self.sms + self.mms
+    }
+}
+
+/// Most skipped records kept per production for the details view; the count covers all of them.
+pub const MAX_SKIPPED_DETAILS: usize = 1_000;
+
+/// A production record that could not be normalized and so is missing from the case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedRecord {
+    /// 1-based position among the production's `<results>` entries.
+    pub position: usize,
+    pub target_number: String,
+    pub remote_number: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub reason: String,
+}
+
+/// A `<results>` block that was not well-formed XML, passed over when reading in recovery mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MalformedBlock {
+    /// 1-based position among the production's `<results>` entries.
+    pub position: usize,
+    /// 1-based line of the file where the XML breaks off, or where the block starts when it
+    /// is never closed.
+    pub line: usize,
+    /// The element being read when the XML broke off, e.g. `startTime`.
+    pub element: String,
+    pub reason: String,
+}
+
+/// Whose name a number's account is in, from an LDS-102 style subscriber record delivered
+/// with a production. Fields the record leaves out are empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberInfo {
+    /// Normalized; a [`ProcessedCallRecord::target_number`] is normalized before it is compared.
+    pub number: String,
+    pub name: String,
+    pub address: String,
+    pub account_number: String,
+    pub email: String,
+    /// When the account or service began and ended, as the record gives them.
+    pub account_start: String,
+    pub account_end: String,
+}
+
+/// Where a production's contents disagree with what it declares, a common sign of truncation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Discrepancy {
+    /// Records dated before `fromDate` or after `toDate`.
+    OutsideDeclaredRange {
+        count: usize,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    /// The production holds fewer `<results>` than its declared record count.
+    FewerThanDeclared { declared: usize, found: usize },
+}
+
+/// How many of one production's records made it into the case, and why the rest did not.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParseReport {
+    pub source_file: String,
+    pub total_records: usize,
+    pub skipped_count: usize,
+    /// The first [`MAX_SKIPPED_DETAILS`] skipped records.
+    pub skipped: Vec<SkippedRecord>,
+    /// Coverage the production declared, if any; reports saved before reconciliation have none.
+    #[serde(default)]
+    pub declared: ProductionHeader,
+    /// Parsed records whose start time falls outside the declared range.
+    #[serde(default)]
+    pub out_of_range_count: usize,
+    /// Records left out as repeats of an earlier one, when loaded with duplicates dropped.
+    #[serde(default)]
+    pub duplicates_dropped: usize,
+    /// Records left out as repeats of calls in other productions of the case, by the
+    /// production that kept them, when cross-file duplicates are collapsed.
+    #[serde(default)]
+    pub cross_file_duplicates: Vec<(String, usize)>,
+    /// The parser the production was read with; reports saved before detection have none.
+    #[serde(default)]
+    pub format: Option<ReadFormat>,
+    /// `<results>` blocks passed over as malformed XML, counted among the skipped records.
+    #[serde(default)]
+    pub malformed_count: usize,
+    /// The first [`MAX_SKIPPED_DETAILS`] malformed blocks.
+    #[serde(default)]
+    pub malformed: Vec<MalformedBlock>,
+    /// Subscriber records, for a companion file of them rather than call records.
+    #[serde(default)]
+    pub subscribers: Vec<SubscriberInfo>,
+}
+
+impl ParseReport {
+    pub fn new(source_file: &str) -> Self {
+        Self {
+            source_file: source_file.to_string(),
+            ..Self::default()
+        }
+    }
+    
+    /// Takes the declared coverage of the production being read; call before its records.
+    pub fn declare(&mut self, header: &ProductionHeader) {
+        if self.declared != *header {
+            self.declared = header.clone();
+        }
+    }
+    
+    pub fn record_parsed(&mut self, record: &ProcessedCallRecord) {
+        self.total_records += 1;
+        let before = self.declared.from_date.is_some_and(|from| record.start_time < from);
+        let after = self.declared.to_date.is_some_and(|to| record.start_time > to);
+        if before || after {
+            self.out_of_range_count += 1;
+        }
+    }
+    
+    pub fn discrepancies(&self) -> Vec<Discrepancy> {
+        let mut discrepancies = Vec::new();
+        if self.out_of_range_count > 0 {
+            discrepancies.push(Discrepancy::OutsideDeclaredRange {
+                count: self.out_of_range_count,
+                from: self.declared.from_date,
+                to: self.declared.to_date,
+            });
+        }
+        if let Some(declared) = self.declared.expected_records {
+            if self.total_records < declared {
+                discrepancies.push(Discrepancy::FewerThanDeclared { declared, found: self.total_records });
+            }
+        }
+        discrepancies
+    }
+    
+    /// Records left out as repeats of calls in other productions.
+    pub fn cross_file_duplicates_dropped(&self) -> usize {
+        self.cross_file_duplicates.iter().map(|(_, count)| count).sum()
+    }
+    
+    /// Whether the user should be told about this production: skipped records or discrepancies.
+    pub fn has_issues(&self) -> bool {
+        self.skipped_count > 0 || !self.discrepancies().is_empty()
+    }
+    
+    pub fn record_skipped(&mut self, call: &CallRecord, target_number: &str, reason: String) {
+        self.total_records += 1;
+        self.skipped_count += 1;
+        if self.skipped.len() < MAX_SKIPPED_DETAILS {
+            self.skipped.push(SkippedRecord {
+                position: self.total_records,
+                target_number: target_number.to_string(),
+                remote_number: call.remote_number.clone(),
+                start_time: call.start_time.clone(),
+                end_time: call.end_time.clone(),
+                reason,
+            });
+        }
+    }
+    
+    pub fn record_malformed(&mut self, block: MalformedBlock) {
+        self.total_records += 1;
+        self.skipped_count += 1;
+        self.malformed_count += 1;
+        if self.malformed.len() < MAX_SKIPPED_DETAILS {
+            self.malformed.push(block);
+        }
+    }
+}
+
 impl ProcessedCallRecord {
     pub fn from_call_record(call: &CallRecord, target_number: &str, source_file: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let start_time = DateTime::parse_from_rfc3339(&call.start_time)?.with_timezone(&Utc);
-        let end_time = DateTime::parse_from_rfc3339(&call.end_time)?.with_timezone(&Utc);
+        let start_time = DateTime::parse_from_rfc3339(&call.start_time)
+            .map_err(|e| format!("invalid startTime {:?}: {}", call.start_time, e))?
+            .with_timezone(&Utc);
+        let end_time = DateTime::parse_from_rfc3339(&call.end_time)
+            .map_err(|e| format!("invalid endTime {:?}: {}", call.end_time, e))?
+            .with_timezone(&Utc);
         
-        let normalized_number = normalize_phone_number(&call.remote_number);
+        let normalized = normalize_phone_number(&call.remote_number);
+        let number_kind = normalized.kind();
+        let record_type = RecordType::from_production(call.record_type.as_deref(), call.message_content.is_some());
+        let disposition = CallDisposition::from_production(call.disposition.as_deref());
         let duration_minutes = call.length_of_call as f64 / 60.0;
         
+        // Formatted by hand: chrono's strftime parsing dominates normalization otherwise
+        let date = format!("{:04}-{:02}-{:02}", start_time.year(), start_time.month(), start_time.day());
+        let time = format!("{:02}:{:02}:{:02}", start_time.hour(), start_time.minute(), start_time.second());
+        let date_time = format!("{} {}", date, time);
+        
         Ok(Self {
             message_direction: call.message_direction.clone(),
             remote_number: call.remote_number.clone(),
-            normalized_number,
+            normalized_number: normalized.into_string(),
+            number_kind,
+            record_type,
+            disposition,
             target_number: target_number.to_string(),
             source_file: source_file.to_string(),
             start_time,
             end_time,
             length_of_call: call.length_of_call,
             duration_minutes,
-            date: start_time.format("%Y-%m-%d").to_string(),
-            time: start_time.format("%H:%M:%S").to_string(),
-            date_time: start_time.format("%Y-%m-%d %H:%M:%S").to_string(),
-            day_of_week: start_time.format("%A").to_string(),
+            date,
+            time,
+            date_time,
+            day_of_week: weekday_name(start_time.weekday()).to_string(),
+            message_content: call.message_content.clone(),
+            cell_site: CellSite::from_call_record(call),
+            device: DeviceIds::from_call_record(call),
         })
     }
+
+    /// The start in `timezone`, for display; `start_time` keeps the UTC instant.
+    pub fn local_start(&self, timezone: Tz) -> DateTime<Tz> {
+        self.start_time.with_timezone(&timezone)
+    }
+
+    /// The day the call started on in `timezone`, which near midnight is not the UTC
+    /// `day_of_week`.
+    pub fn local_day_of_week(&self, timezone: Tz) -> &'static str {
+        weekday_name(self.local_start(timezone).weekday())
+    }
+
+    /// The remote number in E.164 form, for the kinds of number that have one.
+    pub fn e164_number(&self) -> Option<String> {
+        match self.number_kind {
+            NumberKind::Nanp => Some(format!("+1{}", self.normalized_number)),
+            NumberKind::International => Some(self.normalized_number.clone()),
+            _ => None,
+        }
+    }
+
+    /// Stable identifier for a record, used to attach tags and other annotations.
+    pub fn record_key(&self) -> String {
+        format!("{}|{}|{}|{}", self.target_number, self.normalized_number, self.start_time.to_rfc3339(), self.length_of_call)
+    }
+}
+
+/// A production that records were ingested from, identified by its content rather than its path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceFile {
+    /// File name as stored in [`ProcessedCallRecord::source_file`].
+    pub name: String,
+    /// Lowercase hex SHA-256 of the file contents.
+    pub sha256: String,
+    /// Where the file was read from, so it can be re-hashed later; none when it was loaded
+    /// from memory (the browser, the sample dataset) or imported before paths were kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<std::path::PathBuf>,
+    /// Digest of how the file was read: its parser (with any columns mapped by hand), the
+    /// duplicate policy, the PDF row patterns, and the default phone region. Reading the
+    /// same file another way gives other records, so cached analytics are keyed on it too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_config: Option<String>,
 }
 
-pub fn normalize_phone_number(number: &str) -> String {
-    use regex::Regex;
-    use lazy_static::lazy_static;
+impl SourceFile {
+    pub fn from_bytes(name: &str, bytes: &[u8]) -> Self {
+        use sha2::{Digest, Sha256};
+        
+        Self {
+            name: name.to_string(),
+            sha256: hex_digest(&Sha256::digest(bytes)),
+            path: None,
+            parse_config: None,
+        }
+    }
     
-    lazy_static! {
-        static ref PHONE_REGEX: Regex = Regex::new(r"[^\d]").unwrap();
+    /// Hashes the file in chunks so large productions are never read into memory at once.
+    pub fn from_path(path: &std::path::Path) -> std::io::Result<Self> {
+        use sha2::{Digest, Sha256};
+        use std::io::Read;
+        
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        
+        let name = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Ok(Self {
+            name: name.to_string(),
+            sha256: hex_digest(&hasher.finalize()),
+            path: Some(path.to_path_buf()),
+            parse_config: None,
+        })
     }
     
-    let digits_only = PHONE_REGEX.replace_all(number, "");
+    /// Notes how the file was read in [`SourceFile::parse_config`]; the PDF row patterns
+    /// only count for PDFs, so editing them leaves other productions' cached analytics alone.
+    pub fn read_with(mut self, format: Option<ReadFormat>, pdf_patterns: &[PdfPattern]) -> Self {
+        use sha2::{Digest, Sha256};
+        
+        let mut hasher = Sha256::new();
+        if let Some(format) = format {
+            hasher.update(format!("parser={:?}\nduplicates={:?}\n", format.parser, format.duplicates));
+            if format.parser == ParserKind::Pdf {
+                hasher.update(format!("pdf_patterns={:?}\n", pdf_patterns));
+            }
+        }
+        hasher.update(format!("region={:?}", phone_region::default_region()));
+        self.parse_config = Some(hex_digest(&hasher.finalize()));
+        self
+    }
+}
+
+/// A wall-clock time in `timezone` as a UTC instant. The hour repeated when clocks fall
+/// back is taken the first time; a time in the hour skipped when they spring forward, which
+/// carriers still write for calls that straddle the change, is read with the offset in
+/// force before it, the way the clock on the wall would have shown it.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use esubpoena_tolls_tool::data_models::local_to_utc;
+///
+/// let chicago = chrono_tz::America::Chicago;
+/// let at = |day, hour, minute| NaiveDate::from_ymd_opt(2024, 3, day).unwrap().and_hms_opt(hour, minute, 0).unwrap();
+/// // 02:30 never happened on 10 March; it is 03:30 CDT
+/// assert_eq!(local_to_utc(&at(10, 2, 30), chicago).unwrap().to_rfc3339(), "2024-03-10T08:30:00+00:00");
+/// assert_eq!(local_to_utc(&at(10, 3, 30), chicago).unwrap().to_rfc3339(), "2024-03-10T08:30:00+00:00");
+/// assert_eq!(local_to_utc(&at(9, 2, 30), chicago).unwrap().to_rfc3339(), "2024-03-09T08:30:00+00:00");
+/// ```
+pub fn local_to_utc(local: &NaiveDateTime, timezone: Tz) -> Option<DateTime<Utc>> {
+    if let Some(time) = timezone.from_local_datetime(local).earliest() {
+        return Some(time.with_timezone(&Utc));
+    }
+    // No change skips more than a few hours, so three before is on the near side of it
+    let before = timezone.from_local_datetime(&(*local - Duration::hours(3))).earliest()?;
+    let offset = before.offset().fix().local_minus_utc();
+    Some((*local - Duration::seconds(offset as i64)).and_utc())
+}
+
+/// English day name, as `%A` formats it.
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+pub(crate) fn hex_digest(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// What kind of number a remote party's value turned out to be.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NumberKind {
+    #[default]
+    Nanp,
+    International,
+    ShortCode,
+    Unknown,
+    Invalid,
+}
+
+impl NumberKind {
+    pub const ALL: [NumberKind; 5] = [
+        NumberKind::Nanp,
+        NumberKind::International,
+        NumberKind::ShortCode,
+        NumberKind::Unknown,
+        NumberKind::Invalid,
+    ];
+
+    /// Whether numbers of this kind are analyzed as contacts: parties identified by their
+    /// number, with short codes and service numbers only when `include_short_codes` is set,
+    /// since a bank's alerts or the voicemail system would otherwise top the rankings.
+    pub fn is_contact(self, include_short_codes: bool) -> bool {
+        self.identifies_party() && (include_short_codes || self != NumberKind::ShortCode)
+    }
+
+    /// Whether numbers of this kind can be counted and ranked as one contact.
+    pub fn identifies_party(self) -> bool {
+        !matches!(self, NumberKind::Unknown | NumberKind::Invalid)
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            NumberKind::Nanp => tr!("number-kind-nanp"),
+            NumberKind::International => tr!("number-kind-international"),
+            NumberKind::ShortCode => tr!("number-kind-short-code"),
+            NumberKind::Unknown => tr!("number-kind-unknown"),
+            NumberKind::Invalid => tr!("number-kind-invalid"),
+        }
+    }
+}
+
+/// Whether a record is a voice call or a text message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RecordType {
+    #[default]
+    Voice,
+    Sms,
+    /// Picture and other multimedia messages.
+    Mms,
+}
+
+impl RecordType {
+    pub const ALL: [RecordType; 3] = [RecordType::Voice, RecordType::Sms, RecordType::Mms];
+
+    pub fn label(self) -> String {
+        match self {
+            RecordType::Voice => tr!("record-type-voice"),
+            RecordType::Sms => tr!("record-type-sms"),
+            RecordType::Mms => tr!("record-type-mms"),
+        }
+    }
+
+    pub fn is_message(self) -> bool {
+        self != RecordType::Voice
+    }
+
+    /// Reads the type a production gives, e.g. "SMS", "Text", "MMS", or "Voice". Records
+    /// that give none are messages when they carry message text and calls otherwise.
+    pub fn from_production(value: Option<&str>, has_content: bool) -> Self {
+        let value = value.map(|value| value.trim().to_ascii_lowercase()).unwrap_or_default();
+        if value.contains("mms") || value.contains("multimedia") || value.contains("picture") {
+            RecordType::Mms
+        } else if value.contains("sms") || value.contains("text") || value.contains("message") || (value.is_empty() && has_content) {
+            RecordType::Sms
+        } else {
+            RecordType::Voice
+        }
+    }
+}
+
+/// How a call ended up, going by the disposition or feature code a production gives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CallDisposition {
+    /// Connected normally, or no code given.
+    #[default]
+    Completed,
+    /// Rang without being answered.
+    Missed,
+    Busy,
+    /// Sent to voicemail.
+    Voicemail,
+    /// Forwarded to another number by the target's call forwarding.
+    Forwarded,
+    /// Handled while the target was roaming on another network.
+    Roaming,
+}
+
+impl CallDisposition {
+    pub const ALL: [CallDisposition; 6] = [
+        CallDisposition::Completed,
+        CallDisposition::Missed,
+        CallDisposition::Busy,
+        CallDisposition::Voicemail,
+        CallDisposition::Forwarded,
+        CallDisposition::Roaming,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            CallDisposition::Completed => tr!("call-disposition-completed"),
+            CallDisposition::Missed => tr!("call-disposition-missed"),
+            CallDisposition::Busy => tr!("call-disposition-busy"),
+            CallDisposition::Voicemail => tr!("call-disposition-voicemail"),
+            CallDisposition::Forwarded => tr!("call-disposition-forwarded"),
+            CallDisposition::Roaming => tr!("call-disposition-roaming"),
+        }
+    }
+
+    /// Reads the code or word a production gives, e.g. "CFW", "CFNR", "VM", "No Answer",
+    /// or "Roaming". Codes it does not know, like call waiting, read as completed calls.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::data_models::CallDisposition;
+    ///
+    /// assert_eq!(CallDisposition::from_production(Some("CFNR")), CallDisposition::Forwarded);
+    /// assert_eq!(CallDisposition::from_production(Some("[VM]")), CallDisposition::Voicemail);
+    /// assert_eq!(CallDisposition::from_production(Some("No Answer")), CallDisposition::Missed);
+    /// assert_eq!(CallDisposition::from_production(Some("CW")), CallDisposition::Completed);
+    /// assert_eq!(CallDisposition::from_production(None), CallDisposition::Completed);
+    /// ```
+    pub fn from_production(value: Option<&str>) -> Self {
+        let value = value.map(|value| value.trim().to_ascii_lowercase()).unwrap_or_default();
+        let is_code = |codes: &[&str]| {
+            value.split(|c: char| !c.is_ascii_alphanumeric()).any(|token| codes.contains(&token))
+        };
+        if value.contains("forward") || is_code(&["cf", "cfw", "cfu", "cfb", "cfnr", "cfnrc", "cfo", "fwd"]) {
+            CallDisposition::Forwarded
+        } else if value.contains("voicemail") || value.contains("voice mail") || is_code(&["vm", "vmail"]) {
+            CallDisposition::Voicemail
+        } else if value.contains("roam") {
+            CallDisposition::Roaming
+        } else if value.contains("busy") {
+            CallDisposition::Busy
+        } else if value.contains("missed") || value.contains("no answer") || value.contains("unanswered") {
+            CallDisposition::Missed
+        } else {
+            CallDisposition::Completed
+        }
+    }
+
+    /// Whether the call reached the target's phone and was answered there, as far as the
+    /// production says.
+    pub fn is_completed(self) -> bool {
+        self == CallDisposition::Completed
+    }
+}
+
+/// The cell site that served a call: the tower's ID and sector, and where it stands.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CellSite {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+}
+
+impl CellSite {
+    /// The tower fields of a record, or none when it has none. Coordinates that are not
+    /// decimal degrees in range are left out rather than failing the record.
+    pub fn from_call_record(call: &CallRecord) -> Option<Self> {
+        let text = |value: &Option<String>| value.as_deref().map(str::trim).filter(|value| !value.is_empty()).map(str::to_string);
+        let degrees = |value: &Option<String>, limit: f64| {
+            value.as_deref()
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .filter(|degrees| degrees.is_finite() && degrees.abs() <= limit)
+        };
+        let site = Self {
+            cell_id: text(&call.cell_site_id),
+            sector: text(&call.sector),
+            latitude: degrees(&call.latitude, 90.0),
+            longitude: degrees(&call.longitude, 180.0),
+        };
+        (site != Self::default()).then_some(site)
+    }
+
+    /// Latitude and longitude, when both are known.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.latitude.zip(self.longitude)
+    }
+
+    /// The cell ID and sector as `1234 / 2`, or whichever of them is known.
+    pub fn label(&self) -> String {
+        match (&self.cell_id, &self.sector) {
+            (Some(cell_id), Some(sector)) => format!("{} / {}", cell_id, sector),
+            (Some(cell_id), None) => cell_id.clone(),
+            (None, Some(sector)) => sector.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// The coordinates as `34.73040, -86.58610`, or empty when either is missing.
+    pub fn location_label(&self) -> String {
+        self.coordinates()
+            .map(|(latitude, longitude)| format!("{:.5}, {:.5}", latitude, longitude))
+            .unwrap_or_default()
+    }
+}
+
+/// Whether any record carries cell site fields; most productions have none.
+pub fn has_cell_sites(records: &[ProcessedCallRecord]) -> bool {
+    records.iter().any(|r| r.cell_site.is_some())
+}
+
+/// The identifiers a device is known by in a production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DeviceIdKind {
+    /// The handset.
+    Imei,
+    /// The SIM card in it.
+    Imsi,
+}
+
+impl DeviceIdKind {
+    pub const ALL: [DeviceIdKind; 2] = [DeviceIdKind::Imei, DeviceIdKind::Imsi];
+
+    pub fn label(&self) -> String {
+        match self {
+            DeviceIdKind::Imei => tr!("device-imei"),
+            DeviceIdKind::Imsi => tr!("device-imsi"),
+        }
+    }
+}
+
+/// The target's handset and SIM on a call, as their IMEI and IMSI digits.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceIds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imei: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imsi: Option<String>,
+}
+
+impl DeviceIds {
+    /// The identifiers of a record, or none when it has none. Spaces and dashes are dropped,
+    /// and a value without digits is left out.
+    pub fn from_call_record(call: &CallRecord) -> Option<Self> {
+        let digits = |value: &Option<String>| {
+            value.as_deref()
+                .map(|value| value.chars().filter(char::is_ascii_digit).collect::<String>())
+                .filter(|digits| !digits.is_empty())
+        };
+        let ids = Self { imei: digits(&call.imei), imsi: digits(&call.imsi) };
+        (ids != Self::default()).then_some(ids)
+    }
+
+    /// The identifier of `kind` as devices are compared by: an IMEI's first 14 digits, which
+    /// name the handset whether or not the production adds the check digit or software version.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::data_models::{DeviceIdKind, DeviceIds};
+    ///
+    /// let with_check_digit = DeviceIds { imei: Some("353456789012347".to_string()), imsi: None };
+    /// let with_version = DeviceIds { imei: Some("3534567890123401".to_string()), imsi: None };
+    /// assert_eq!(with_check_digit.id(DeviceIdKind::Imei), with_version.id(DeviceIdKind::Imei));
+    /// assert_eq!(with_check_digit.id(DeviceIdKind::Imsi), None);
+    /// ```
+    pub fn id(&self, kind: DeviceIdKind) -> Option<&str> {
+        match kind {
+            DeviceIdKind::Imei => self.imei.as_deref().map(|imei| imei.get(..14).unwrap_or(imei)),
+            DeviceIdKind::Imsi => self.imsi.as_deref(),
+        }
+    }
+
+    /// `IMEI 353456789012347 / IMSI 310260123456789`, or whichever is known.
+    pub fn label(&self) -> String {
+        let ids = [(DeviceIdKind::Imei, &self.imei), (DeviceIdKind::Imsi, &self.imsi)];
+        ids.iter()
+            .filter_map(|(kind, id)| id.as_ref().map(|id| format!("{} {}", kind.label(), id)))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// Whether any record carries an IMEI or IMSI; most productions have none.
+pub fn has_device_ids(records: &[ProcessedCallRecord]) -> bool {
+    records.iter().any(|r| r.device.is_some())
+}
+
+/// A remote number reduced to a comparable form, or flagged as unusable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NormalizedNumber {
+    /// Ten-digit North American number, without the leading 1.
+    Nanp(String),
+    /// Number outside the NANP, as `+` and its digits without any international prefix.
+    International(String),
+    /// Three- to six-digit short code or service number, e.g. 411 or 72345, a feature code
+    /// dialed on the handset, e.g. `*86` for voicemail, or voicemail named as such.
+    ShortCode(String),
+    /// Caller ID the carrier did not have or was not allowed to give: blank, "Restricted",
+    /// "Unavailable" and the like; kept as produced (trimmed).
+    Unknown(String),
+    /// Too short or otherwise not a dialable number; kept as produced (trimmed).
+    Invalid(String),
+}
+
+/// Placeholders carriers put in place of a withheld caller ID, compared case-insensitively.
+const WITHHELD_CALLER_IDS: &[&str] = &[
+    "restricted",
+    "unavailable",
+    "unknown",
+    "private",
+    "anonymous",
+    "blocked",
+    "withheld",
+    "no caller id",
+    "out of area",
+];
+
+/// What carriers write for calls to or from the voicemail system, compared case-insensitively.
+const VOICEMAIL_LABELS: &[&str] = &["voicemail", "voice mail", "vm", "vmail"];
+
+impl NormalizedNumber {
+    pub fn kind(&self) -> NumberKind {
+        match self {
+            NormalizedNumber::Nanp(_) => NumberKind::Nanp,
+            NormalizedNumber::International(_) => NumberKind::International,
+            NormalizedNumber::ShortCode(_) => NumberKind::ShortCode,
+            NormalizedNumber::Unknown(_) => NumberKind::Unknown,
+            NormalizedNumber::Invalid(_) => NumberKind::Invalid,
+        }
+    }
     
-    // If it's a 10-digit number, return as is
-    if digits_only.len() == 10 {
-        return digits_only.to_string();
+    pub fn as_str(&self) -> &str {
+        match self {
+            NormalizedNumber::Nanp(s)
+            | NormalizedNumber::International(s)
+            | NormalizedNumber::ShortCode(s)
+            | NormalizedNumber::Unknown(s)
+            | NormalizedNumber::Invalid(s) => s,
+        }
     }
     
-    // If it's 11 digits and starts with 1, remove the 1
-    if digits_only.len() == 11 && digits_only.starts_with('1') {
-        return digits_only[1..].to_string();
+    pub fn into_string(self) -> String {
+        match self {
+            NormalizedNumber::Nanp(s)
+            | NormalizedNumber::International(s)
+            | NormalizedNumber::ShortCode(s)
+            | NormalizedNumber::Unknown(s)
+            | NormalizedNumber::Invalid(s) => s,
+        }
     }
     
-    // If it's longer than 10 digits, take the last 10
-    if digits_only.len() > 10 {
-        return digits_only[digits_only.len() - 10..].to_string();
+    /// Whether this identifies one particular party, i.e. is neither unknown nor invalid.
+    pub fn is_valid(&self) -> bool {
+        self.kind().identifies_party()
+    }
+}
+
+impl std::fmt::Display for NormalizedNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classifies and normalizes a number as produced by the carrier, reading numbers without a
+/// country code as the [default region](crate::phone_region::default_region)'s. Values that
+/// are not a usable number come back as [`NormalizedNumber::Invalid`] instead of being padded
+/// into something that looks like one.
+pub fn normalize_phone_number(number: &str) -> NormalizedNumber {
+    normalize_phone_number_in(number, phone_region::default_region())
+}
+
+/// Like [`normalize_phone_number`], with numbers without a country code read as `region`'s.
+/// NANP numbers stay 10 digits; every other country's are written in E.164, `+` and all.
+///
+/// ```
+/// use esubpoena_tolls_tool::data_models::{normalize_phone_number_in, NormalizedNumber};
+/// use esubpoena_tolls_tool::phone_region::PhoneRegion;
+///
+/// let mexico = PhoneRegion::Mexico;
+/// assert_eq!(normalize_phone_number_in("55 1234 5678", mexico), NormalizedNumber::International("+525512345678".into()));
+/// assert_eq!(normalize_phone_number_in("+1 (256) 555-0100", mexico), NormalizedNumber::Nanp("2565550100".into()));
+/// assert_eq!(normalize_phone_number_in("00 44 7911 123456", mexico), NormalizedNumber::International("+447911123456".into()));
+/// assert_eq!(normalize_phone_number_in("07911 123456", PhoneRegion::UnitedKingdom), NormalizedNumber::International("+447911123456".into()));
+/// assert_eq!(normalize_phone_number_in("1-256-555-0100", PhoneRegion::NorthAmerica), NormalizedNumber::Nanp("2565550100".into()));
+/// assert_eq!(normalize_phone_number_in("*86", PhoneRegion::NorthAmerica), NormalizedNumber::ShortCode("*86".into()));
+/// assert_eq!(normalize_phone_number_in("Voicemail", PhoneRegion::NorthAmerica), NormalizedNumber::ShortCode("VOICEMAIL".into()));
+/// ```
+pub fn normalize_phone_number_in(number: &str, region: PhoneRegion) -> NormalizedNumber {
+    let trimmed = number.trim();
+    if trimmed.is_empty() || WITHHELD_CALLER_IDS.iter().any(|label| trimmed.eq_ignore_ascii_case(label)) {
+        return NormalizedNumber::Unknown(trimmed.to_string());
+    }
+    if VOICEMAIL_LABELS.iter().any(|label| trimmed.eq_ignore_ascii_case(label)) {
+        return NormalizedNumber::ShortCode(trimmed.to_uppercase());
+    }
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+    if is_feature_code(trimmed, &digits) {
+        return NormalizedNumber::ShortCode(trimmed.chars().filter(|c| !c.is_whitespace()).collect());
+    }
+    
+    // An explicit country code, after a `+` or the region's international prefix
+    let international = if trimmed.starts_with('+') {
+        Some(digits.as_str())
+    } else {
+        digits.strip_prefix(region.international_prefix())
+    };
+    if let Some(rest) = international {
+        return match rest.len() {
+            11 if rest.starts_with('1') => NormalizedNumber::Nanp(rest[1..].to_string()),
+            7..=15 => NormalizedNumber::International(format!("+{}", rest)),
+            _ => NormalizedNumber::Invalid(trimmed.to_string()),
+        };
     }
     
-    // Otherwise, pad with zeros to make it 10 digits
-    format!("{:0>10}", digits_only)
-} 
\ No newline at end of file
+    if let Some(national) = region.national_number(&digits) {
+        return match region {
+            PhoneRegion::NorthAmerica => NormalizedNumber::Nanp(national.to_string()),
+            _ => NormalizedNumber::International(format!("+{}{}", region.calling_code(), national)),
+        };
+    }
+    match digits.len() {
+        3..=6 => NormalizedNumber::ShortCode(digits),
+        11 if digits.starts_with('1') => NormalizedNumber::Nanp(digits[1..].to_string()),
+        // E.164 allows up to 15 digits; longer than a national number means another country
+        11..=15 => NormalizedNumber::International(format!("+{}", digits)),
+        _ => NormalizedNumber::Invalid(trimmed.to_string()),
+    }
+}
+
+/// Whether `trimmed` is a feature code such as `*86` or `#225#`: a `*` or `#` and at most a
+/// few digits, which no dialable number has.
+fn is_feature_code(trimmed: &str, digits: &str) -> bool {
+    trimmed.starts_with(['*', '#'])
+        && (1..=6).contains(&digits.len())
+        && trimmed.chars().all(|c| c.is_ascii_digit() || matches!(c, '*' | '#') || c.is_whitespace())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhoneFormat {
+    /// (256) 555-0100
+    Parenthesized,
+    /// 256-555-0100
+    Dashed,
+    /// 2565550100
+    Raw,
+}
+
+impl PhoneFormat {
+    pub const ALL: [PhoneFormat; 3] = [PhoneFormat::Parenthesized, PhoneFormat::Dashed, PhoneFormat::Raw];
+
+    pub fn label(&self) -> String {
+        match self {
+            PhoneFormat::Parenthesized => "(XXX) XXX-XXXX".to_string(),
+            PhoneFormat::Dashed => "XXX-XXX-XXXX".to_string(),
+            PhoneFormat::Raw => tr!("phone-format-raw"),
+        }
+    }
+}
+
+/// Renders a 10-digit number in the chosen display format. Anything else is returned unchanged.
+pub fn format_phone_number(number: &str, format: PhoneFormat) -> String {
+    if number.len() != 10 || !number.chars().all(|c| c.is_ascii_digit()) {
+        return number.to_string();
+    }
+
+    let (npa, nxx, line) = (&number[0..3], &number[3..6], &number[6..]);
+    match format {
+        PhoneFormat::Parenthesized => format!("({}) {}-{}", npa, nxx, line),
+        PhoneFormat::Dashed => format!("{}-{}-{}", npa, nxx, line),
+        PhoneFormat::Raw => number.to_string(),
+    }
+}
@@ -0,0 +1,186 @@
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::location::{implausible_moves, TowerTransition, IMPLAUSIBLE_SPEED_KMH};
+use crate::number_warnings::NumberWarning;
+use crate::record_warnings::{RecordIssue, RecordValidation};
+use crate::tr;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Completeness figures for one production, or for the whole case.
+///
+/// Percentages are of the records in the production, parsed or not, so a file that lost half
+/// its records to parse failures cannot look complete.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileQuality {
+    pub source_file: String,
+    /// `<results>` entries in the production.
+    pub total_records: usize,
+    pub parse_failures: usize,
+    /// Parsed records whose end time is not before their start time.
+    pub valid_timestamps: usize,
+    pub with_direction: usize,
+    pub zero_duration: usize,
+    /// Records identical to an earlier one in the case (same target, remote, start, and length).
+    pub duplicates: usize,
+    /// Records left out as repeats of calls in another production.
+    pub collapsed_duplicates: usize,
+    /// Remote numbers that are withheld or not a dialable number.
+    pub unusable_numbers: usize,
+}
+
+impl FileQuality {
+    fn new(source_file: &str) -> Self {
+        Self {
+            source_file: source_file.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn loaded_records(&self) -> usize {
+        self.total_records.saturating_sub(self.parse_failures + self.collapsed_duplicates)
+    }
+
+    /// `count` as a percentage of the production's records; 0 for an empty production.
+    pub fn percent(&self, count: usize) -> f64 {
+        if self.total_records == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / self.total_records as f64
+        }
+    }
+
+    fn add(&mut self, other: &FileQuality) {
+        self.total_records += other.total_records;
+        self.parse_failures += other.parse_failures;
+        self.valid_timestamps += other.valid_timestamps;
+        self.with_direction += other.with_direction;
+        self.zero_duration += other.zero_duration;
+        self.duplicates += other.duplicates;
+        self.collapsed_duplicates += other.collapsed_duplicates;
+        self.unusable_numbers += other.unusable_numbers;
+    }
+}
+
+/// What the Data Quality tab shows: completeness per production and for the case overall.
+#[derive(Debug, Clone, Default)]
+pub struct DataQualityReport {
+    pub files: Vec<FileQuality>,
+    pub overall: FileQuality,
+    /// Remote numbers that look like junk identifiers, to exclude or reclassify.
+    pub number_warnings: Vec<NumberWarning>,
+    /// Records that read but look wrong, e.g. ending before they start.
+    pub record_validation: RecordValidation,
+    /// Moves between consecutive calls' towers faster than a phone could travel.
+    pub implausible_moves: Vec<TowerTransition>,
+}
+
+impl DataQualityReport {
+    /// Productions without a parse report (e.g. a session saved before reports existed) are
+    /// counted from their loaded records alone.
+    pub fn build(records: &[ProcessedCallRecord], reports: &[ParseReport]) -> Self {
+        let mut files: HashMap<&str, FileQuality> = HashMap::new();
+        for report in reports {
+            let file = files.entry(report.source_file.as_str()).or_insert_with(|| FileQuality::new(&report.source_file));
+            file.total_records += report.total_records;
+            file.parse_failures += report.skipped_count;
+            file.collapsed_duplicates += report.cross_file_duplicates_dropped();
+        }
+        let reported: HashSet<&str> = reports.iter().map(|r| r.source_file.as_str()).collect();
+
+        let mut seen: HashSet<(&str, &str, DateTime<Utc>, u32)> = HashSet::with_capacity(records.len());
+        for record in records {
+            let file = files.entry(record.source_file.as_str()).or_insert_with(|| FileQuality::new(&record.source_file));
+            if !reported.contains(record.source_file.as_str()) {
+                file.total_records += 1;
+            }
+            if record.end_time >= record.start_time {
+                file.valid_timestamps += 1;
+            }
+            if !record.message_direction.trim().is_empty() {
+                file.with_direction += 1;
+            }
+            if record.length_of_call == 0 {
+                file.zero_duration += 1;
+            }
+            if !record.number_kind.identifies_party() {
+                file.unusable_numbers += 1;
+            }
+            let key = (record.target_number.as_str(), record.normalized_number.as_str(), record.start_time, record.length_of_call);
+            if !seen.insert(key) {
+                file.duplicates += 1;
+            }
+        }
+
+        let mut files: Vec<FileQuality> = files.into_values().collect();
+        files.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+        let mut overall = FileQuality::new(&tr!("quality-overall"));
+        for file in &files {
+            overall.add(file);
+        }
+        Self {
+            files,
+            overall,
+            number_warnings: NumberWarning::scan(records),
+            record_validation: RecordValidation::validate(records, Utc::now()),
+            implausible_moves: implausible_moves(records),
+        }
+    }
+
+    /// Plain-text statement of the dataset's limitations, for pasting into a report.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("=== {} ===\n\n", tr!("quality-title"));
+        for line in self.body_lines() {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The figures for each production and then the case, one line each, with a blank line
+    /// between productions.
+    pub fn body_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for file in self.files.iter().chain(std::iter::once(&self.overall)) {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(file.source_file.clone());
+            lines.push(tr!("quality-line-records",
+                total = file.total_records, loaded = file.loaded_records(), failed = file.parse_failures));
+            for (label, count) in [
+                (tr!("quality-valid-timestamps"), file.valid_timestamps),
+                (tr!("quality-with-direction"), file.with_direction),
+                (tr!("quality-zero-duration"), file.zero_duration),
+                (tr!("quality-unusable-numbers"), file.unusable_numbers),
+                (tr!("quality-duplicates"), file.duplicates),
+                (tr!("quality-collapsed-duplicates"), file.collapsed_duplicates),
+            ] {
+                lines.push(format!("  {}: {} ({:.1}%)", label, count, file.percent(count)));
+            }
+        }
+        if !self.record_validation.is_empty() {
+            lines.push(String::new());
+            lines.push(tr!("quality-line-flagged", count = self.record_validation.flagged));
+            for issue in RecordIssue::ALL {
+                lines.push(format!("  {}: {}", issue.label(), self.record_validation.count(issue)));
+            }
+        }
+        if !self.implausible_moves.is_empty() {
+            lines.push(String::new());
+            lines.push(tr!("quality-line-implausible-moves", count = self.implausible_moves.len(), speed = IMPLAUSIBLE_SPEED_KMH));
+            let time = |time: DateTime<Utc>| time.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+            for transition in &self.implausible_moves {
+                lines.push(format!("  {}", tr!(
+                    "quality-line-implausible-move",
+                    target = transition.target_number.as_str(),
+                    left = time(transition.left),
+                    from = transition.from.label(),
+                    arrived = time(transition.arrived),
+                    to = transition.to.label(),
+                    distance = format!("{:.1}", transition.distance_km.unwrap_or_default())
+                )));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,82 @@
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use chrono::{DateTime, Utc};
+use log::info;
+use std::collections::HashMap;
+
+/// Same target, remote number, start, and duration: the key a repeated call is found by,
+/// as for [`DuplicatePolicy`](crate::pipeline::DuplicatePolicy) within one production.
+type CallKey<'a> = (&'a str, &'a str, DateTime<Utc>, u32);
+
+/// Records that repeat a call an earlier production already had, e.g. when a supplemental
+/// return overlaps the original's dates: each record's index with the production it is
+/// a repeat of. Repeats within one production are left to the duplicate policy it was
+/// loaded with.
+pub fn find_cross_file_duplicates(records: &[ProcessedCallRecord]) -> Vec<(usize, &str)> {
+    let mut first_seen: HashMap<CallKey, &str> = HashMap::with_capacity(records.len());
+    let mut duplicates = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        let key = (record.target_number.as_str(), record.normalized_number.as_str(), record.start_time, record.length_of_call);
+        match first_seen.get(&key) {
+            Some(&source_file) if source_file != record.source_file => duplicates.push((index, source_file)),
+            Some(_) => {}
+            None => {
+                first_seen.insert(key, record.source_file.as_str());
+            }
+        }
+    }
+    duplicates
+}
+
+/// Drops the records [`find_cross_file_duplicates`] finds, so each call is counted once,
+/// noting in each production's report how many of its records were dropped and which
+/// production kept them. Productions earlier in `records` keep their records. Returns how
+/// many were dropped.
+///
+/// ```
+/// use esubpoena_tolls_tool::data_models::{CallRecord, ParseReport, ProcessedCallRecord};
+/// use esubpoena_tolls_tool::dedup::collapse_cross_file_duplicates;
+///
+/// let call = |start: &str| CallRecord {
+///     message_direction: "outgoing".into(),
+///     remote_number: "2565550100".into(),
+///     start_time: start.into(),
+///     end_time: start.into(),
+///     ..CallRecord::default()
+/// };
+/// let record = |start, file| ProcessedCallRecord::from_call_record(&call(start), "2565550111", file).unwrap();
+/// let mut records = vec![
+///     record("2024-01-01T10:00:00Z", "january.xml"),
+///     record("2024-01-31T10:00:00Z", "january.xml"),
+///     record("2024-01-31T10:00:00Z", "supplemental.xml"),
+///     record("2024-02-01T10:00:00Z", "supplemental.xml"),
+/// ];
+/// let mut reports = vec![ParseReport::new("january.xml"), ParseReport::new("supplemental.xml")];
+/// assert_eq!(collapse_cross_file_duplicates(&mut records, &mut reports), 1);
+/// assert_eq!(records.len(), 3);
+/// assert_eq!(reports[1].cross_file_duplicates, [("january.xml".to_string(), 1)]);
+/// ```
+pub fn collapse_cross_file_duplicates(records: &mut Vec<ProcessedCallRecord>, reports: &mut [ParseReport]) -> usize {
+    let duplicates = find_cross_file_duplicates(records);
+    if duplicates.is_empty() {
+        return 0;
+    }
+    let mut dropped = vec![false; records.len()];
+    for &(index, kept_in) in &duplicates {
+        dropped[index] = true;
+        let source_file = records[index].source_file.as_str();
+        if let Some(report) = reports.iter_mut().find(|report| report.source_file == source_file) {
+            match report.cross_file_duplicates.iter_mut().find(|(file, _)| file == kept_in) {
+                Some((_, count)) => *count += 1,
+                None => report.cross_file_duplicates.push((kept_in.to_string(), 1)),
+            }
+        }
+    }
+    let count = duplicates.len();
+    let mut index = 0;
+    records.retain(|_| {
+        index += 1;
+        !dropped[index - 1]
+    });
+    info!("Collapsed {} calls repeated across productions", count);
+    count
+}
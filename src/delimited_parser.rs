@@ -0,0 +1,529 @@
+use crate::data_models::{local_to_utc, normalize_phone_number, CallRecord, ParseReport, ProcessedCallRecord};
+use crate::pipeline::DuplicatePolicy;
+use crate::tr;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Delimiters tried on the header line, the most common first.
+pub(crate) const DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// Direction values read as incoming and outgoing, by how they start once lowercased.
+const INCOMING_PREFIXES: [&str; 4] = ["in", "mt", "term", "rec"];
+const OUTGOING_PREFIXES: [&str; 5] = ["out", "mo", "orig", "dial", "placed"];
+
+/// Date-time layouts tried after RFC 3339, month first; the day-first profile swaps them.
+const MONTH_FIRST_FORMATS: [&str; 8] = [
+    "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M",
+    "%m/%d/%Y %H:%M:%S", "%m/%d/%Y %H:%M", "%m/%d/%Y %I:%M:%S %p", "%m/%d/%Y %I:%M %p", "%m/%d/%y %H:%M",
+];
+const DAY_FIRST_FORMATS: [&str; 8] = [
+    "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M",
+    "%d/%m/%Y %H:%M:%S", "%d/%m/%Y %H:%M", "%d/%m/%Y %I:%M:%S %p", "%d/%m/%Y %I:%M %p", "%d/%m/%y %H:%M",
+];
+
+/// Local times followed by their UTC offset, e.g. `03/14/2024 09:05:09 -05:00`, tried
+/// before the layouts without one.
+const MONTH_FIRST_OFFSET_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S %z", "%m/%d/%Y %H:%M:%S %z", "%m/%d/%Y %I:%M:%S %p %z", "%m/%d/%Y %H:%M %z",
+];
+const DAY_FIRST_OFFSET_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S %z", "%d/%m/%Y %H:%M:%S %z", "%d/%m/%Y %I:%M:%S %p %z", "%d/%m/%Y %H:%M %z",
+];
+
+/// How the columns of a delimited production are mapped onto call record fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MappingProfile {
+    /// Common carrier and spreadsheet column names, with US month-first dates.
+    Generic,
+    /// The same column names with day-first dates, as most carriers outside the US write them.
+    GenericDayFirst,
+    /// PenLink's generic call import, as this tool exports it.
+    PenLink,
+}
+
+impl MappingProfile {
+    pub const ALL: [MappingProfile; 3] = [MappingProfile::Generic, MappingProfile::GenericDayFirst, MappingProfile::PenLink];
+
+    pub fn label(&self) -> String {
+        match self {
+            MappingProfile::Generic => tr!("mapping-generic"),
+            MappingProfile::GenericDayFirst => tr!("mapping-generic-day-first"),
+            MappingProfile::PenLink => tr!("mapping-penlink"),
+        }
+    }
+
+    pub fn columns(&self) -> &'static ColumnAliases {
+        match self {
+            MappingProfile::Generic | MappingProfile::GenericDayFirst => &GENERIC_COLUMNS,
+            MappingProfile::PenLink => &PENLINK_COLUMNS,
+        }
+    }
+
+    pub fn day_first(&self) -> bool {
+        *self == MappingProfile::GenericDayFirst
+    }
+}
+
+/// Column names each field is recognized by. Names are compared ignoring case, spaces, and
+/// punctuation, so "Remote Number", "remote_number", and "REMOTENUMBER" are the same.
+#[derive(Debug)]
+pub struct ColumnAliases {
+    pub target: &'static [&'static str],
+    pub direction: &'static [&'static str],
+    pub remote: &'static [&'static str],
+    /// Calling and called parties, for layouts with no remote column; the remote number is
+    /// the one that is not the target.
+    pub originating: &'static [&'static str],
+    pub terminating: &'static [&'static str],
+    /// A combined date and time.
+    pub start: &'static [&'static str],
+    /// Date and time in columns of their own.
+    pub date: &'static [&'static str],
+    pub time: &'static [&'static str],
+    pub end: &'static [&'static str],
+    /// Seconds, or `H:MM:SS`.
+    pub duration: &'static [&'static str],
+    pub content: &'static [&'static str],
+    /// Disposition or call feature codes, e.g. `CFW` or `VM`.
+    pub disposition: &'static [&'static str],
+}
+
+const GENERIC_COLUMNS: ColumnAliases = ColumnAliases {
+    target: &["target number", "target", "target value", "subscriber number", "account number"],
+    direction: &["direction", "message direction", "call direction", "in out"],
+    remote: &["remote number", "remote", "other party number", "other party", "other number", "counterparty"],
+    originating: &["originating number", "calling number", "calling party", "from number", "from", "a number"],
+    terminating: &["terminating number", "called number", "called party", "dialed digits", "dialed number", "to number", "to", "b number"],
+    start: &["start time", "date time", "date & time", "datetime", "timestamp", "call start", "seizure time", "connect time"],
+    date: &["date", "call date", "start date"],
+    time: &["time", "call time"],
+    end: &["end time", "call end", "release time", "disconnect time"],
+    duration: &["duration sec", "duration seconds", "length of call", "call duration", "duration", "seconds", "elapsed time"],
+    content: &["message content", "message body", "message", "content", "body"],
+    disposition: &["disposition", "call disposition", "call result", "release cause", "feature", "features", "call feature", "feature code"],
+};
+
+const PENLINK_COLUMNS: ColumnAliases = ColumnAliases {
+    target: &["target number"],
+    direction: &["direction"],
+    remote: &[],
+    originating: &["originating number"],
+    terminating: &["terminating number"],
+    start: &[],
+    date: &["date"],
+    time: &["time"],
+    end: &[],
+    duration: &["duration"],
+    content: &[],
+    disposition: &["disposition", "feature"],
+};
+
+/// A call record field a column can be mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MappedField {
+    Target,
+    Direction,
+    Remote,
+    Originating,
+    Terminating,
+    Start,
+    Date,
+    Time,
+    End,
+    Duration,
+    Content,
+    Disposition,
+}
+
+impl MappedField {
+    pub const ALL: [MappedField; 12] = [
+        MappedField::Target, MappedField::Direction, MappedField::Remote, MappedField::Originating,
+        MappedField::Terminating, MappedField::Start, MappedField::Date, MappedField::Time,
+        MappedField::End, MappedField::Duration, MappedField::Content, MappedField::Disposition,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            MappedField::Target => tr!("mapped-field-target"),
+            MappedField::Direction => tr!("mapped-field-direction"),
+            MappedField::Remote => tr!("mapped-field-remote"),
+            MappedField::Originating => tr!("mapped-field-originating"),
+            MappedField::Terminating => tr!("mapped-field-terminating"),
+            MappedField::Start => tr!("mapped-field-start"),
+            MappedField::Date => tr!("mapped-field-date"),
+            MappedField::Time => tr!("mapped-field-time"),
+            MappedField::End => tr!("mapped-field-end"),
+            MappedField::Duration => tr!("mapped-field-duration"),
+            MappedField::Content => tr!("mapped-field-content"),
+            MappedField::Disposition => tr!("mapped-field-disposition"),
+        }
+    }
+}
+
+/// Where each field is found in one file, resolved from its header row or picked by hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ColumnMap {
+    target: Option<usize>,
+    direction: Option<usize>,
+    remote: Option<usize>,
+    originating: Option<usize>,
+    terminating: Option<usize>,
+    start: Option<usize>,
+    date: Option<usize>,
+    time: Option<usize>,
+    end: Option<usize>,
+    duration: Option<usize>,
+    content: Option<usize>,
+    /// Mappings saved before dispositions were read have none.
+    #[serde(default)]
+    disposition: Option<usize>,
+}
+
+impl ColumnMap {
+    /// Each field's column is the first of its aliases the header row has.
+    pub fn resolve(headers: &StringRecord, aliases: &ColumnAliases) -> Self {
+        let headers: Vec<String> = headers.iter().map(column_key).collect();
+        let find = |names: &[&str]| names.iter().find_map(|name| {
+            let key = column_key(name);
+            headers.iter().position(|header| *header == key)
+        });
+        Self {
+            target: find(aliases.target),
+            direction: find(aliases.direction),
+            remote: find(aliases.remote),
+            originating: find(aliases.originating),
+            terminating: find(aliases.terminating),
+            start: find(aliases.start),
+            date: find(aliases.date),
+            time: find(aliases.time),
+            end: find(aliases.end),
+            duration: find(aliases.duration),
+            content: find(aliases.content),
+            disposition: find(aliases.disposition),
+        }
+    }
+
+    pub fn column(&self, field: MappedField) -> Option<usize> {
+        match field {
+            MappedField::Target => self.target,
+            MappedField::Direction => self.direction,
+            MappedField::Remote => self.remote,
+            MappedField::Originating => self.originating,
+            MappedField::Terminating => self.terminating,
+            MappedField::Start => self.start,
+            MappedField::Date => self.date,
+            MappedField::Time => self.time,
+            MappedField::End => self.end,
+            MappedField::Duration => self.duration,
+            MappedField::Content => self.content,
+            MappedField::Disposition => self.disposition,
+        }
+    }
+
+    pub fn column_mut(&mut self, field: MappedField) -> &mut Option<usize> {
+        match field {
+            MappedField::Target => &mut self.target,
+            MappedField::Direction => &mut self.direction,
+            MappedField::Remote => &mut self.remote,
+            MappedField::Originating => &mut self.originating,
+            MappedField::Terminating => &mut self.terminating,
+            MappedField::Start => &mut self.start,
+            MappedField::Date => &mut self.date,
+            MappedField::Time => &mut self.time,
+            MappedField::End => &mut self.end,
+            MappedField::Duration => &mut self.duration,
+            MappedField::Content => &mut self.content,
+            MappedField::Disposition => &mut self.disposition,
+        }
+    }
+
+    /// How many of the three things every call record needs were found: the other party,
+    /// when the call started, and how long it lasted.
+    pub fn required_found(&self) -> usize {
+        [
+            self.remote.is_some() || (self.originating.is_some() && self.terminating.is_some()),
+            self.start.is_some() || self.date.is_some(),
+            self.duration.is_some() || self.end.is_some(),
+        ]
+        .into_iter()
+        .filter(|found| *found)
+        .count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.required_found() == 3
+    }
+
+    /// The start time column's text, or the date and time columns' joined, for telling which
+    /// way round a file's dates are.
+    pub fn start_text(&self, row: &StringRecord) -> String {
+        let field = |index: Option<usize>| index.and_then(|i| row.get(i)).unwrap_or("").trim();
+        match self.start {
+            Some(index) => field(Some(index)).to_string(),
+            None => format!("{} {}", field(self.date), field(self.time)).trim().to_string(),
+        }
+    }
+}
+
+/// Reads call detail exported as CSV, TSV, or another delimited text layout, using a
+/// [`MappingProfile`] to find the fields. Times without an offset are taken to be in the
+/// timezone given, since delimited productions rarely say which zone they are in.
+pub struct DelimitedParser;
+
+impl DelimitedParser {
+    pub fn parse_file(
+        path: &Path,
+        profile: MappingProfile,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing delimited file: {:?} ({:?})", path, profile);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, profile, timezone, duplicates)
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        profile: MappingProfile,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let mut reader = Self::reader(bytes);
+        let headers = reader.headers().context("Failed to read the header row")?.clone();
+        let columns = ColumnMap::resolve(&headers, profile.columns());
+        if !columns.is_complete() {
+            bail!("{} has no columns for the remote number, start time, and duration under the {:?} mapping", source_file, profile);
+        }
+        Self::parse_rows(reader, source_file, &columns, profile.day_first(), timezone, duplicates)
+    }
+
+    /// Reads every row after the header with the columns given.
+    pub(crate) fn parse_rows(
+        mut reader: csv::Reader<&[u8]>,
+        source_file: &str,
+        columns: &ColumnMap,
+        day_first: bool,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let rows = reader.records().map(|row| {
+            row.map(|row| Self::call_record(&row, columns, day_first, timezone)).map_err(|e| e.to_string())
+        });
+        Ok(Self::collect_records(rows, source_file, duplicates))
+    }
+
+    /// Processes rows already turned into call records, each with its target, reporting the
+    /// rows that could not be read and those that do not process.
+    pub(crate) fn collect_records(
+        rows: impl Iterator<Item = Result<(String, CallRecord), String>>,
+        source_file: &str,
+        duplicates: DuplicatePolicy,
+    ) -> (Vec<ProcessedCallRecord>, ParseReport) {
+        let mut report = ParseReport::new(source_file);
+        let mut records = Vec::new();
+        // Only filled when dropping duplicates, as in the XML pipeline
+        let mut seen: HashSet<(String, String, DateTime<Utc>, u32)> = HashSet::new();
+        for row in rows {
+            let (target, call) = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    report.record_skipped(&empty_call_record(), "", e);
+                    continue;
+                }
+            };
+            match ProcessedCallRecord::from_call_record(&call, &target, source_file) {
+                Ok(record) => {
+                    report.record_parsed(&record);
+                    if duplicates == DuplicatePolicy::DropExact {
+                        let key = (record.target_number.clone(), record.normalized_number.clone(), record.start_time, record.length_of_call);
+                        if !seen.insert(key) {
+                            report.duplicates_dropped += 1;
+                            continue;
+                        }
+                    }
+                    records.push(record);
+                }
+                Err(e) => {
+                    warn!("Failed to process delimited row: {}", e);
+                    report.record_skipped(&call, &target, e.to_string());
+                }
+            }
+        }
+        info!("Parsed {} delimited records, skipped {}", records.len(), report.skipped_count);
+        (records, report)
+    }
+
+    /// A CSV reader over `bytes` with the delimiter its header line uses.
+    pub fn reader(bytes: &[u8]) -> csv::Reader<&[u8]> {
+        csv::ReaderBuilder::new()
+            .delimiter(sniff_delimiter(bytes))
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(bytes)
+    }
+
+    /// The row as the XML parser would have produced it, with the target it belongs to.
+    /// Start and end times become RFC 3339; ones that cannot be read are passed on as
+    /// written, so the record is skipped with the value in the report.
+    pub fn call_record(row: &StringRecord, columns: &ColumnMap, day_first: bool, timezone: Tz) -> (String, CallRecord) {
+        let field = |index: Option<usize>| index.and_then(|i| row.get(i)).unwrap_or("").trim();
+
+        let raw_direction = field(columns.direction);
+        let message_direction = normalize_direction(raw_direction);
+        let incoming = message_direction.eq_ignore_ascii_case("incoming");
+        let (remote_number, target) = match columns.remote {
+            Some(_) => (field(columns.remote).to_string(), field(columns.target).to_string()),
+            None if incoming => (field(columns.originating).to_string(), field(columns.terminating).to_string()),
+            None => (field(columns.terminating).to_string(), field(columns.originating).to_string()),
+        };
+        // An explicit target column wins over the side of the call the target was on
+        let target = if columns.target.is_some() { field(columns.target).to_string() } else { target };
+        let target = target_number(&target);
+
+        let start_text = columns.start_text(row);
+        let start = parse_time(&start_text, day_first, timezone);
+        let end = columns.end.and_then(|_| parse_time(field(columns.end), day_first, timezone));
+        let length_of_call = match columns.duration {
+            Some(_) => parse_duration(field(columns.duration)).unwrap_or(0),
+            None => match (start, end) {
+                (Some(start), Some(end)) => (end - start).num_seconds().max(0) as u32,
+                _ => 0,
+            },
+        };
+        let end = end.or_else(|| start.map(|start| start + Duration::seconds(length_of_call as i64)));
+        let content = field(columns.content);
+        let disposition = field(columns.disposition);
+
+        let call = CallRecord {
+            message_direction,
+            remote_number,
+            start_time: start.map(|time| time.to_rfc3339()).unwrap_or(start_text),
+            end_time: end.map(|time| time.to_rfc3339()).unwrap_or_default(),
+            length_of_call,
+            message_content: (!content.is_empty()).then(|| content.to_string()),
+            record_type: None,
+            cell_site_id: None,
+            sector: None,
+            latitude: None,
+            longitude: None,
+            imei: None,
+            imsi: None,
+            disposition: (!disposition.is_empty()).then(|| disposition.to_string()),
+        };
+        (target, call)
+    }
+}
+
+/// The most frequent of [`DELIMITERS`] on the first line; a comma when none occurs.
+pub fn sniff_delimiter(bytes: &[u8]) -> u8 {
+    let first_line = bytes.split(|&b| b == b'\n').next().unwrap_or(&[]);
+    DELIMITERS.into_iter()
+        .map(|delimiter| (delimiter, first_line.iter().filter(|&&b| b == delimiter).count()))
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map_or(b',', |(delimiter, _)| delimiter)
+}
+
+/// A column name reduced to lowercase letters and digits.
+pub(crate) fn column_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The number after a "Target" or "MDN" label in the case details some carriers put above
+/// the columns, e.g. `Target Number: (256) 555-0100`.
+pub(crate) fn preamble_target(line: &str) -> Option<String> {
+    let (label, value) = line.split_once([':', ',', '\t'])?;
+    let label = label.to_ascii_lowercase();
+    if !label.contains("target") && !label.contains("mdn") {
+        return None;
+    }
+    let number = normalize_phone_number(value);
+    number.is_valid().then(|| number.into_string())
+}
+
+/// A target column's number as the digits [`preamble_target`] gives, so a production that
+/// writes `(256) 555-0100` in some rows has one target; anything else is kept as written.
+pub(crate) fn target_number(value: &str) -> String {
+    let number = normalize_phone_number(value);
+    if number.is_valid() {
+        number.into_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// "Incoming" or "Outgoing" for the common ways of writing them, otherwise as written.
+pub(crate) fn normalize_direction(value: &str) -> String {
+    let lower = value.to_ascii_lowercase();
+    if INCOMING_PREFIXES.iter().any(|prefix| lower.starts_with(prefix)) {
+        "Incoming".to_string()
+    } else if OUTGOING_PREFIXES.iter().any(|prefix| lower.starts_with(prefix)) {
+        "Outgoing".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// A timestamp with an offset as given, or a local one in `timezone`, read across
+/// daylight-saving changes as by [`local_to_utc`].
+pub fn parse_time(value: &str, day_first: bool, timezone: Tz) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let offset_formats = if day_first { &DAY_FIRST_OFFSET_FORMATS } else { &MONTH_FIRST_OFFSET_FORMATS };
+    if let Some(time) = offset_formats.iter().find_map(|format| DateTime::parse_from_str(value, format).ok()) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let formats = if day_first { &DAY_FIRST_FORMATS } else { &MONTH_FIRST_FORMATS };
+    formats.iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .and_then(|local| local_to_utc(&local, timezone))
+}
+
+/// Seconds from `123`, `123.4`, `2:03`, or `0:02:03`.
+pub(crate) fn parse_duration(value: &str) -> Option<u32> {
+    if value.contains(':') {
+        return value.split(':')
+            .map(|part| part.trim().parse::<u32>().ok())
+            .try_fold(0u32, |total, part| part.map(|part| total * 60 + part));
+    }
+    value.parse::<f64>().ok().filter(|seconds| *seconds >= 0.0).map(|seconds| seconds.round() as u32)
+}
+
+fn empty_call_record() -> CallRecord {
+    CallRecord {
+        message_direction: String::new(),
+        remote_number: String::new(),
+        start_time: String::new(),
+        end_time: String::new(),
+        length_of_call: 0,
+        message_content: None,
+        record_type: None,
+        cell_site_id: None,
+        sector: None,
+        latitude: None,
+        longitude: None,
+        imei: None,
+        imsi: None,
+        disposition: None,
+    }
+}
@@ -0,0 +1,126 @@
+use crate::annotations::Annotations;
+use crate::cnam::CallerNames;
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord, SourceFile};
+use crate::settings::AppSettings;
+use anyhow::{Context, Result};
+use chrono::{Datelike, Timelike, Utc};
+use log::info;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Call records as Elasticsearch bulk-ingest NDJSON: an `index` action line, then the
+/// document, for every record. Document IDs are the records' stable keys, so indexing the
+/// same production twice updates documents instead of duplicating them.
+///
+/// Each document carries the normalized fields (normalized numbers, number kind, UTC and
+/// case-local times), any alias, subscriber, or caller name, and case metadata (the case's
+/// targets, its timezone, the export time, and the source file with its SHA-256) so the
+/// index can be filtered per case.
+pub struct ElasticExporter;
+
+impl ElasticExporter {
+    pub fn export(
+        records: &[ProcessedCallRecord],
+        sources: &[SourceFile],
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+        output_path: &Path,
+    ) -> Result<()> {
+        info!("Exporting call records as Elasticsearch bulk NDJSON: {:?}", output_path);
+
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create file: {:?}", output_path))?;
+        Self::write_bulk(BufWriter::new(file), records, sources, annotations, caller_names, settings)?;
+
+        info!("Successfully exported {} records for Elasticsearch", records.len());
+        Ok(())
+    }
+
+    /// Same as [`ElasticExporter::export`] but into memory, for the browser build's downloads.
+    pub fn to_bytes(
+        records: &[ProcessedCallRecord],
+        sources: &[SourceFile],
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+    ) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        Self::write_bulk(&mut buffer, records, sources, annotations, caller_names, settings)?;
+        Ok(buffer)
+    }
+
+    /// The bulk API requires a newline after the last line too.
+    pub fn write_bulk<W: Write>(
+        mut writer: W,
+        records: &[ProcessedCallRecord],
+        sources: &[SourceFile],
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+    ) -> Result<()> {
+        let hashes: HashMap<&str, &str> = sources.iter().map(|s| (s.name.as_str(), s.sha256.as_str())).collect();
+        let targets: BTreeSet<&str> = records.iter()
+            .map(|r| r.target_number.as_str())
+            .filter(|t| !t.is_empty())
+            .collect();
+        let case = json!({
+            // Same case ID as the log file uses
+            "id": targets.iter().copied().collect::<Vec<_>>().join("+"),
+            "targets": targets,
+            "timezone": settings.case_timezone.name(),
+            "exported_at": Utc::now().to_rfc3339(),
+        });
+
+        for record in records {
+            let action = json!({ "index": { "_index": settings.elasticsearch_index, "_id": record.record_key() } });
+            let document = Self::document(record, &case, &hashes, annotations, caller_names, settings);
+            serde_json::to_writer(&mut writer, &action)?;
+            writer.write_all(b"\n")?;
+            serde_json::to_writer(&mut writer, &document)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush().context("Failed to write Elasticsearch bulk file")?;
+        Ok(())
+    }
+
+    fn document(
+        record: &ProcessedCallRecord,
+        case: &Value,
+        hashes: &HashMap<&str, &str>,
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+    ) -> Value {
+        let local = record.start_time.with_timezone(&settings.case_timezone);
+        let number = record.normalized_number.as_str();
+        json!({
+            "@timestamp": record.start_time.to_rfc3339(),
+            "end_time": record.end_time.to_rfc3339(),
+            "local_time": local.to_rfc3339(),
+            "local_hour": local.hour(),
+            "local_day_of_week": local.weekday().to_string(),
+            "direction": record.message_direction.to_lowercase(),
+            "target_number": normalize_phone_number(&record.target_number).into_string(),
+            "remote_number": record.remote_number,
+            "normalized_number": number,
+            "e164_number": record.e164_number(),
+            "number_kind": record.number_kind,
+            "duration_seconds": record.length_of_call,
+            "duration_minutes": record.duration_minutes,
+            "alias": annotations.alias(number),
+            "subscriber": annotations.subscribers.lookup(number).map(|s| s.name.as_str()),
+            "caller_name": caller_names.lookup(number),
+            "bookmarked": annotations.is_bookmarked(record),
+            "case": case,
+            "source": {
+                "file": record.source_file,
+                "sha256": hashes.get(record.source_file.as_str()),
+            },
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Calls with all the numbers of one entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntitySummary {
+    pub name: String,
+    /// The entity's numbers, busiest first, with their call counts. Numbers with no calls in
+    /// the records are listed with zero.
+    pub numbers: Vec<(String, usize)>,
+    pub calls: usize,
+    pub duration_minutes: f64,
+    pub first_contact: Option<DateTime<Utc>>,
+    pub last_contact: Option<DateTime<Utc>>,
+}
+
+/// Numbers the analyst has declared to belong to one person (an old and a new phone, a
+/// work and a personal line, ...), by entity name. A number belongs to at most one entity.
+/// Records keep their own numbers; entities only group them for aggregate views.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entities {
+    entities: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Entities {
+    /// Adds the numbers to the named entity, creating it if needed and moving any number
+    /// that was in another entity. Entries that are not phone numbers are skipped. Returns
+    /// how many numbers were added.
+    pub fn merge<'a>(&mut self, name: &str, numbers: impl IntoIterator<Item = &'a str>) -> usize {
+        let name = name.trim();
+        if name.is_empty() {
+            return 0;
+        }
+        let mut added = 0;
+        for raw in numbers {
+            let number = normalize_phone_number(raw);
+            if !number.is_valid() {
+                continue;
+            }
+            let number = number.into_string();
+            if self.entity_of(&number) == Some(name) {
+                continue;
+            }
+            self.remove_number(&number);
+            self.entities.entry(name.to_string()).or_default().insert(number);
+            added += 1;
+        }
+        added
+    }
+
+    /// Takes a number out of its entity, dropping the entity once it is empty.
+    pub fn remove_number(&mut self, normalized_number: &str) {
+        self.entities.retain(|_, numbers| {
+            numbers.remove(normalized_number);
+            !numbers.is_empty()
+        });
+    }
+
+    pub fn dissolve(&mut self, name: &str) {
+        self.entities.remove(name);
+    }
+
+    pub fn entity_of(&self, normalized_number: &str) -> Option<&str> {
+        self.entities.iter()
+            .find(|(_, numbers)| numbers.contains(normalized_number))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Entity names with their numbers, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BTreeSet<String>)> {
+        self.entities.iter().map(|(name, numbers)| (name.as_str(), numbers))
+    }
+
+    /// Calls per entity over `records`, busiest entity first.
+    pub fn summaries(&self, records: &[ProcessedCallRecord]) -> Vec<EntitySummary> {
+        let mut calls: HashMap<&str, Vec<&ProcessedCallRecord>> = HashMap::new();
+        for record in records {
+            calls.entry(record.normalized_number.as_str()).or_default().push(record);
+        }
+        let mut summaries: Vec<EntitySummary> = self.entities.iter()
+            .map(|(name, members)| {
+                let entity_calls: Vec<&ProcessedCallRecord> = members.iter()
+                    .flat_map(|number| calls.get(number.as_str()).into_iter().flatten().copied())
+                    .collect();
+                let mut numbers: Vec<(String, usize)> = members.iter()
+                    .map(|number| (number.clone(), calls.get(number.as_str()).map_or(0, Vec::len)))
+                    .collect();
+                numbers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                EntitySummary {
+                    name: name.clone(),
+                    numbers,
+                    calls: entity_calls.len(),
+                    duration_minutes: entity_calls.iter().map(|r| r.duration_minutes).sum(),
+                    first_contact: entity_calls.iter().map(|r| r.start_time).min(),
+                    last_contact: entity_calls.iter().map(|r| r.start_time).max(),
+                }
+            })
+            .collect();
+        summaries.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.name.cmp(&b.name)));
+        summaries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
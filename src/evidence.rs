@@ -0,0 +1,104 @@
+use crate::data_models::SourceFile;
+use crate::tr;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+/// How a source file compared with the hash recorded when it was imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvidenceStatus {
+    /// The file hashes the same as at import.
+    Verified,
+    /// The file was found but its contents changed; holds the hash it has now.
+    Altered(String),
+    /// The file is at neither its recorded path nor next to the case file.
+    Missing,
+    /// The file was found but could not be read.
+    Unreadable(String),
+}
+
+/// One source file of a case and what re-hashing it showed.
+#[derive(Debug, Clone)]
+pub struct EvidenceCheck {
+    pub source: SourceFile,
+    /// Where the file was found, if anywhere.
+    pub found_at: Option<PathBuf>,
+    pub status: EvidenceStatus,
+}
+
+/// The result of re-hashing every production a saved case was built from, to confirm before
+/// testimony that the originals are unchanged since import.
+///
+/// Each file is looked for at the path it was imported from and, failing that (it was moved
+/// with the case, or the case predates recorded paths), by name in the case file's folder.
+#[derive(Debug, Clone)]
+pub struct EvidenceVerification {
+    pub case_file: PathBuf,
+    pub verified_at: DateTime<Utc>,
+    pub checks: Vec<EvidenceCheck>,
+}
+
+impl EvidenceVerification {
+    pub fn verify(case_file: &Path, sources: &[SourceFile]) -> Self {
+        let case_dir = case_file.parent().unwrap_or(Path::new("."));
+        let checks = sources.iter()
+            .map(|source| check_source(source, case_dir))
+            .collect();
+        Self {
+            case_file: case_file.to_path_buf(),
+            verified_at: Utc::now(),
+            checks,
+        }
+    }
+
+    pub fn verified_count(&self) -> usize {
+        self.checks.iter().filter(|check| check.status == EvidenceStatus::Verified).count()
+    }
+
+    /// Whether every source was found unchanged.
+    pub fn all_verified(&self) -> bool {
+        self.verified_count() == self.checks.len()
+    }
+
+    /// A plain-text record of the check, for the case notes.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("=== {} ===\n", tr!("evidence-title"));
+        text.push_str(&format!("{}\n", tr!("evidence-case-file", path = self.case_file.display().to_string())));
+        text.push_str(&format!("{}\n", tr!("evidence-verified-at", time = self.verified_at.format("%Y-%m-%d %H:%M:%S UTC").to_string())));
+        text.push_str(&format!("{}\n\n", tr!("evidence-counts", verified = self.verified_count(), total = self.checks.len())));
+        for check in &self.checks {
+            text.push_str(&format!("{}: {}\n", check.source.name, check.status_label()));
+            text.push_str(&format!("  {}\n", tr!("evidence-recorded-hash", hash = check.source.sha256.as_str())));
+            if let EvidenceStatus::Altered(actual) = &check.status {
+                text.push_str(&format!("  {}\n", tr!("evidence-current-hash", hash = actual.as_str())));
+            }
+            if let Some(path) = &check.found_at {
+                text.push_str(&format!("  {}\n", tr!("evidence-found-at", path = path.display().to_string())));
+            }
+        }
+        text
+    }
+}
+
+impl EvidenceCheck {
+    pub fn status_label(&self) -> String {
+        match &self.status {
+            EvidenceStatus::Verified => tr!("evidence-status-verified"),
+            EvidenceStatus::Altered(_) => tr!("evidence-status-altered"),
+            EvidenceStatus::Missing => tr!("evidence-status-missing"),
+            EvidenceStatus::Unreadable(error) => tr!("evidence-status-unreadable", error = error.as_str()),
+        }
+    }
+}
+
+fn check_source(source: &SourceFile, case_dir: &Path) -> EvidenceCheck {
+    let mut candidates = source.path.iter().cloned().chain(std::iter::once(case_dir.join(&source.name)));
+    let Some(path) = candidates.find(|path| path.is_file()) else {
+        return EvidenceCheck { source: source.clone(), found_at: None, status: EvidenceStatus::Missing };
+    };
+    let status = match SourceFile::from_path(&path) {
+        Ok(actual) if actual.sha256 == source.sha256 => EvidenceStatus::Verified,
+        Ok(actual) => EvidenceStatus::Altered(actual.sha256),
+        Err(e) => EvidenceStatus::Unreadable(e.to_string()),
+    };
+    EvidenceCheck { source: source.clone(), found_at: Some(path), status }
+}
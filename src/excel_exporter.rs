@@ -1,55 +1,163 @@
-use crate::data_models::{Analytics, ProcessedCallRecord};
+use crate::annotations::Annotations;
+use crate::analytics::{format_prefix, geographic_concentration};
+use crate::area_codes::region_label;
+use crate::call_timeline::CallTimeline;
+use crate::carriers::{CarrierDirectory, CarrierSource};
+use crate::cnam::CallerNames;
+use crate::contact_summary::ContactSummary;
+use crate::keywords::{has_message_content, KeywordHits};
+use crate::known_numbers::KnownNumbers;
+use crate::location::{Geofence, GeofenceArea, LocationSummary, DWELL_BREAK_HOURS, IMPLAUSIBLE_SPEED_KMH, TOWER_RANGE_KM};
+use crate::data_models::{has_cell_sites, has_device_ids, Analytics, ParseReport, ProcessedCallRecord, ONGOING_CONTACT_DAYS};
+use crate::report::{Report, ReportInput};
+use crate::scripting::ScriptOutput;
+use crate::settings::AppSettings;
+use crate::subscriber_parser::match_subscribers;
+use crate::tr;
 use anyhow::{Context, Result};
 use log::info;
+use std::collections::HashMap;
 use std::path::Path;
-use xlsxwriter::{Format, Workbook, Worksheet};
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+use xlsxwriter::{Format, FormatBorder, RowColOptions, Workbook};
+
+/// Columns at most in the Call Durations sheet; longer productions get longer buckets.
+const TIMELINE_MAX_BUCKETS: usize = 1000;
+
+/// Call records written between progress reports.
+const PROGRESS_INTERVAL: usize = 5_000;
+
+/// Excel's default row height in points, restated because outlined rows need a height set.
+const GROUPED_ROW_HEIGHT: f64 = 15.0;
 
 pub struct ExcelExporter;
 
 impl ExcelExporter {
+    #[allow(clippy::too_many_arguments)]
     pub fn export_data(
         records: &[ProcessedCallRecord],
+        parse_reports: &[ParseReport],
         analytics: &Analytics,
+        annotations: &Annotations,
+        carriers: &CarrierDirectory,
+        caller_names: &CallerNames,
+        known_numbers: &KnownNumbers,
+        settings: &AppSettings,
+        script_outputs: &[ScriptOutput],
         output_path: &Path,
+    ) -> Result<()> {
+        Self::export_data_with_progress(records, parse_reports, analytics, annotations, carriers, caller_names, known_numbers, settings, script_outputs, output_path, &|_| {})
+    }
+    
+    /// Like [`ExcelExporter::export_data`], calling `progress` with the share of call records
+    /// written so far, then with `None` once the remaining, smaller sheets are being written.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_data_with_progress(
+        records: &[ProcessedCallRecord],
+        parse_reports: &[ParseReport],
+        analytics: &Analytics,
+        annotations: &Annotations,
+        carriers: &CarrierDirectory,
+        caller_names: &CallerNames,
+        known_numbers: &KnownNumbers,
+        settings: &AppSettings,
+        script_outputs: &[ScriptOutput],
+        output_path: &Path,
+        progress: &dyn Fn(Option<f32>),
     ) -> Result<()> {
         info!("Exporting data to Excel: {:?}", output_path);
         
-        let workbook = Workbook::new(output_path.to_str().unwrap())
-            .with_context(|| format!("Failed to create workbook at {:?}", output_path))?;
+        let path_str = output_path.to_str()
+            .with_context(|| format!("Output path is not valid UTF-8: {:?}", output_path))?;
+        let workbook = Workbook::new(path_str);
         
         // Create formats
         let header_format = workbook.add_format()
             .set_bold()
             .set_bg_color(xlsxwriter::FormatColor::Gray)
-            .set_border()
+            .set_border(FormatBorder::Thin)
             .set_align(xlsxwriter::FormatAlignment::Center);
         
         let date_format = workbook.add_format()
-            .set_num_format("yyyy-mm-dd hh:mm:ss")
-            .set_border();
+            .set_num_format(settings.excel_datetime_format())
+            .set_border(FormatBorder::Thin);
         
         let number_format = workbook.add_format()
             .set_num_format("0")
-            .set_border();
+            .set_border(FormatBorder::Thin);
         
         let duration_format = workbook.add_format()
-            .set_num_format("0.00")
-            .set_border();
+            .set_num_format(settings.excel_duration_format())
+            .set_border(FormatBorder::Thin);
         
         let text_format = workbook.add_format()
-            .set_border();
+            .set_border(FormatBorder::Thin);
         
         // Export call records
-        Self::export_call_records(&workbook, records, &header_format, &date_format, &number_format, &duration_format, &text_format)?;
+        Self::export_call_records(&workbook, records, annotations, caller_names, settings, &header_format, &date_format, &number_format, &duration_format, &text_format, progress)?;
+        progress(None);
         
         // Export analytics
-        Self::export_analytics(&workbook, analytics, &header_format, &text_format, &number_format)?;
+        Self::export_analytics(&workbook, analytics, settings, &header_format, &text_format, &number_format)?;
         
         // Export summary report
-        Self::export_summary_report(&workbook, analytics, records, &header_format, &text_format)?;
+        Self::export_summary_report(&workbook, analytics, records, parse_reports, annotations, settings, &header_format, &text_format)?;
         
         // Export common contacts
-        Self::export_common_contacts(&workbook, analytics, &header_format, &text_format)?;
+        Self::export_common_contacts(&workbook, analytics, settings, &header_format, &text_format)?;
+        
+        // One row per contact with their totals
+        Self::export_contact_summary(&workbook, records, annotations, known_numbers, settings, &header_format, &date_format, &number_format, &duration_format, &text_format)?;
+        
+        // When each contact was first and last in touch, and whether contact was ongoing
+        Self::export_contact_spans(&workbook, analytics, annotations, settings, &header_format, &date_format, &number_format, &text_format)?;
+        
+        // Export contacted numbers sharing an exchange
+        Self::export_exchange_clusters(&workbook, analytics, settings, &header_format, &text_format, &number_format)?;
+        
+        // Contacted numbers by area code and where it is
+        Self::export_area_codes(&workbook, analytics, &header_format, &text_format, &number_format)?;
+        
+        // Export the top contacts' calls as a Gantt-style grid
+        Self::export_call_durations(&workbook, records, analytics, settings, &header_format, &text_format)?;
+        
+        // Join contacts to imported subscriber results, aliases, serving carriers, and caller names
+        if !annotations.subscribers.is_empty() || !annotations.aliases.is_empty() || !carriers.is_empty() || !caller_names.is_empty() {
+            Self::export_subscriber_resolution(&workbook, records, annotations, carriers, caller_names, settings, &header_format, &number_format, &text_format)?;
+        }
+        
+        // Subscriber records delivered with the productions
+        if parse_reports.iter().any(|report| !report.subscribers.is_empty()) {
+            Self::export_subscriber_info(&workbook, records, parse_reports, settings, &header_format, &number_format, &text_format)?;
+        }
+        
+        // Contacts found on the known-number lists, by category
+        if !known_numbers.is_empty() {
+            Self::export_known_numbers(&workbook, records, known_numbers, settings, &header_format, &text_format, &number_format)?;
+        }
+        
+        // Export bookmarked records
+        Self::export_bookmarks(&workbook, records, annotations, settings, &header_format, &date_format, &number_format, &duration_format, &text_format)?;
+        
+        // Messages mentioning the case's keywords, where the production has message content
+        if !annotations.keywords.is_empty() && has_message_content(records) {
+            Self::export_keyword_hits(&workbook, records, annotations, settings, &header_format, &date_format, &text_format)?;
+        }
+        
+        // Tower usage by day, dwell periods, and tower changes, where the production has tower locations
+        if has_cell_sites(records) {
+            Self::export_location_analysis(&workbook, records, settings, &header_format, &date_format, &number_format, &text_format)?;
+        }
+        
+        // Calls on towers inside the case's geofence, where the production has tower locations
+        if let (Some(geofence), true) = (&annotations.geofence, has_cell_sites(records)) {
+            Self::export_geofence_hits(&workbook, records, geofence, settings, &header_format, &date_format, &text_format)?;
+        }
+        
+        // Export the output of any scripts that were run
+        for output in script_outputs {
+            Self::export_script_output(&workbook, output, &header_format, &text_format)?;
+        }
         
         workbook.close()
             .with_context(|| "Failed to close workbook")?;
@@ -58,16 +166,168 @@ impl ExcelExporter {
         Ok(())
     }
     
+    /// Writes the call records grouped by contact, the layout many prosecutors ask for: each
+    /// contact's calls in chronological order, collapsible with Excel's outline buttons, and
+    /// followed by a subtotal row with the number of calls and their total duration. The
+    /// subtotals are `SUBTOTAL` formulas, so they follow any filter applied in Excel and are
+    /// left out of the grand total at the bottom.
+    pub fn export_grouped(
+        records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        settings: &AppSettings,
+        output_path: &Path,
+    ) -> Result<()> {
+        info!("Exporting records grouped by contact to Excel: {:?}", output_path);
+        
+        let path_str = output_path.to_str()
+            .with_context(|| format!("Output path is not valid UTF-8: {:?}", output_path))?;
+        let workbook = Workbook::new(path_str);
+        
+        let header_format = workbook.add_format()
+            .set_bold()
+            .set_bg_color(xlsxwriter::FormatColor::Gray)
+            .set_border(FormatBorder::Thin)
+            .set_align(xlsxwriter::FormatAlignment::Center);
+        let date_format = workbook.add_format()
+            .set_num_format(settings.excel_datetime_format())
+            .set_border(FormatBorder::Thin);
+        let number_format = workbook.add_format()
+            .set_num_format("0")
+            .set_border(FormatBorder::Thin);
+        let duration_format = workbook.add_format()
+            .set_num_format(settings.excel_duration_format())
+            .set_border(FormatBorder::Thin);
+        let text_format = workbook.add_format()
+            .set_border(FormatBorder::Thin);
+        let total_format = workbook.add_format()
+            .set_bold()
+            .set_bg_color(xlsxwriter::FormatColor::Silver)
+            .set_border(FormatBorder::Thin);
+        let total_number_format = workbook.add_format()
+            .set_bold()
+            .set_bg_color(xlsxwriter::FormatColor::Silver)
+            .set_num_format("0")
+            .set_border(FormatBorder::Thin);
+        let total_duration_format = workbook.add_format()
+            .set_bold()
+            .set_bg_color(xlsxwriter::FormatColor::Silver)
+            .set_num_format(settings.excel_duration_format())
+            .set_border(FormatBorder::Thin);
+        
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-calls-by-contact")))?;
+        worksheet.set_column(0, 0, 28.0, None)?; // Contact
+        worksheet.set_column(1, 1, 25.0, None)?; // Alias
+        worksheet.set_column(2, 2, 20.0, None)?; // Date & Time
+        worksheet.set_column(3, 3, 12.0, None)?; // Direction
+        worksheet.set_column(4, 4, 15.0, None)?; // Target Number
+        worksheet.set_column(5, 5, 12.0, None)?; // Duration (seconds)
+        worksheet.set_column(6, 6, 15.0, None)?; // Duration (h:mm:ss)
+        worksheet.set_column(7, 7, 15.0, None)?; // Source File
+        // Summary rows sit below their calls, as Excel's own Subtotal command puts them
+        worksheet.outline_settings(true, true, false, false);
+        worksheet.freeze_panes(1, 0);
+        
+        let timezone = settings.case_timezone;
+        let headers = [
+            tr!("header-phone-number"), tr!("header-alias"),
+            tr!("header-start-time-zone", timezone = timezone.name()),
+            tr!("header-direction"), tr!("header-target-number"),
+            tr!("header-duration-sec"), tr!("header-duration"), tr!("header-source-file"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(&header_format))?;
+        }
+        
+        // Busiest contact first, each one's calls in order
+        let mut groups: HashMap<&str, Vec<&ProcessedCallRecord>> = HashMap::new();
+        for record in records {
+            groups.entry(record.normalized_number.as_str()).or_default().push(record);
+        }
+        let mut groups: Vec<(&str, Vec<&ProcessedCallRecord>)> = groups.into_iter().collect();
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+        
+        let mut row_num: u32 = 1;
+        for (number, mut calls) in groups {
+            calls.sort_by_key(|record| record.start_time);
+            let contact = settings.format_number(number);
+            let alias = annotations.alias(number).unwrap_or_default();
+            let first_row = row_num;
+            for record in &calls {
+                let mut outline = RowColOptions { hidden: 0, level: 1, collapsed: 0 };
+                worksheet.set_row_opt(row_num, GROUPED_ROW_HEIGHT, None, &mut outline)?;
+                worksheet.write_string(row_num, 0, &contact, Some(&text_format))?;
+                worksheet.write_string(row_num, 1, alias, Some(&text_format))?;
+                worksheet.write_datetime(row_num, 2, &Self::excel_datetime(&record.start_time.with_timezone(&timezone)), Some(&date_format))?;
+                worksheet.write_string(row_num, 3, &record.message_direction, Some(&text_format))?;
+                worksheet.write_string(row_num, 4, &settings.format_number(&record.target_number), Some(&text_format))?;
+                worksheet.write_number(row_num, 5, record.length_of_call as f64, Some(&number_format))?;
+                worksheet.write_number(row_num, 6, Self::excel_duration(record.length_of_call), Some(&duration_format))?;
+                worksheet.write_string(row_num, 7, &record.source_file, Some(&text_format))?;
+                row_num += 1;
+            }
+            // Formula rows are one-based; `row_num` is now the subtotal row itself
+            let seconds: u64 = calls.iter().map(|record| record.length_of_call as u64).sum();
+            worksheet.write_string(row_num, 0, &tr!("grouped-subtotal", number = contact.as_str()), Some(&total_format))?;
+            worksheet.write_string(row_num, 1, alias, Some(&total_format))?;
+            worksheet.write_formula_num(row_num, 2, &format!("=SUBTOTAL(3,C{}:C{})", first_row + 1, row_num), Some(&total_number_format), calls.len() as f64)?;
+            worksheet.write_string(row_num, 3, "", Some(&total_format))?;
+            worksheet.write_string(row_num, 4, "", Some(&total_format))?;
+            worksheet.write_formula_num(row_num, 5, &format!("=SUBTOTAL(9,F{}:F{})", first_row + 1, row_num), Some(&total_number_format), seconds as f64)?;
+            worksheet.write_formula_num(row_num, 6, &format!("=SUBTOTAL(9,G{}:G{})", first_row + 1, row_num), Some(&total_duration_format), seconds as f64 / 86_400.0)?;
+            worksheet.write_string(row_num, 7, "", Some(&total_format))?;
+            row_num += 1;
+        }
+        
+        // SUBTOTAL skips the other SUBTOTAL cells in its range, so this counts each call once
+        let seconds: u64 = records.iter().map(|record| record.length_of_call as u64).sum();
+        worksheet.write_string(row_num, 0, &tr!("grouped-grand-total"), Some(&total_format))?;
+        worksheet.write_string(row_num, 1, "", Some(&total_format))?;
+        worksheet.write_formula_num(row_num, 2, &format!("=SUBTOTAL(3,C2:C{})", row_num), Some(&total_number_format), records.len() as f64)?;
+        worksheet.write_string(row_num, 3, "", Some(&total_format))?;
+        worksheet.write_string(row_num, 4, "", Some(&total_format))?;
+        worksheet.write_formula_num(row_num, 5, &format!("=SUBTOTAL(9,F2:F{})", row_num), Some(&total_number_format), seconds as f64)?;
+        worksheet.write_formula_num(row_num, 6, &format!("=SUBTOTAL(9,G2:G{})", row_num), Some(&total_duration_format), seconds as f64 / 86_400.0)?;
+        worksheet.write_string(row_num, 7, "", Some(&total_format))?;
+        
+        workbook.close()
+            .with_context(|| "Failed to close workbook")?;
+        
+        info!("Successfully exported grouped records to Excel");
+        Ok(())
+    }
+    
+    /// Excel has no timezones, so callers convert to the zone the cell should show first.
+    fn excel_datetime<Tz: TimeZone>(time: &DateTime<Tz>) -> xlsxwriter::DateTime {
+        xlsxwriter::DateTime::new(
+            time.year() as i16,
+            time.month() as i8,
+            time.day() as i8,
+            time.hour() as i8,
+            time.minute() as i8,
+            time.second() as f64,
+        )
+    }
+    
+    /// A call length as an Excel time value (fraction of a day), so durations sum with `=SUM`.
+    fn excel_duration(seconds: u32) -> f64 {
+        seconds as f64 / 86_400.0
+    }
+    
+    #[allow(clippy::too_many_arguments)]
     fn export_call_records(
         workbook: &Workbook,
         records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
         header_format: &Format,
         date_format: &Format,
         number_format: &Format,
         duration_format: &Format,
         text_format: &Format,
+        progress: &dyn Fn(Option<f32>),
     ) -> Result<()> {
-        let worksheet = workbook.add_worksheet(Some("Call Records"))?;
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-call-records")))?;
         
         // Set column widths
         worksheet.set_column(0, 0, 15.0, None)?; // Direction
@@ -77,16 +337,40 @@ impl ExcelExporter {
         worksheet.set_column(4, 4, 20.0, None)?; // Date & Time
         worksheet.set_column(5, 5, 20.0, None)?; // End Time
         worksheet.set_column(6, 6, 12.0, None)?; // Duration (seconds)
-        worksheet.set_column(7, 7, 15.0, None)?; // Duration (minutes)
+        worksheet.set_column(7, 7, 15.0, None)?; // Duration (h:mm:ss)
         worksheet.set_column(8, 8, 12.0, None)?; // Day of Week
         worksheet.set_column(9, 9, 15.0, None)?; // Source File
+        worksheet.set_column(10, 10, 25.0, None)?; // Caller Name
+        worksheet.set_column(11, 11, 25.0, None)?; // Alias
         
-        // Write headers
-        let headers = [
-            "Direction", "Target Number", "Remote Number", "Normalized Number", "Date & Time", "End Time",
-            "Duration (sec)", "Duration (min)", "Day of Week", "Source File"
-        ];
-        
+        // Write headers; the time columns hold real cells in the case timezone rather than text
+        let mut headers = crate::csv_exporter::call_record_headers(settings.case_timezone).to_vec();
+        headers[7] = tr!("header-duration");
+        // Tower columns only for productions that have them, so the usual sheet is unchanged
+        let cell_sites = has_cell_sites(records);
+        let coordinate_format = workbook.add_format()
+            .set_num_format("0.00000")
+            .set_border(FormatBorder::Thin);
+        if cell_sites {
+            worksheet.set_column(12, 13, 12.0, None)?; // Cell Site ID, Sector
+            worksheet.set_column(14, 15, 12.0, None)?; // Latitude, Longitude
+            headers.extend([tr!("header-cell-site-id"), tr!("header-sector"), tr!("header-latitude"), tr!("header-longitude")]);
+        }
+        // The IMEI and IMSI columns follow the tower columns, when there are any
+        let devices = has_device_ids(records);
+        let device_col = headers.len() as u16;
+        if devices {
+            worksheet.set_column(device_col, device_col + 1, 18.0, None)?; // IMEI, IMSI
+            headers.extend([tr!("device-imei"), tr!("device-imsi")]);
+        }
+        // Then the analyst's tags and notes, once any record has them
+        let record_annotations = annotations.has_record_annotations();
+        let tag_col = headers.len() as u16;
+        if record_annotations {
+            worksheet.set_column(tag_col, tag_col, 25.0, None)?; // Tags
+            worksheet.set_column(tag_col + 1, tag_col + 1, 40.0, None)?; // Notes
+            headers.extend([tr!("header-tags"), tr!("header-notes")]);
+        }
         for (col, header) in headers.iter().enumerate() {
             worksheet.write_string(0, col as u16, header, Some(header_format))?;
         }
@@ -94,17 +378,47 @@ impl ExcelExporter {
         // Write data
         for (row, record) in records.iter().enumerate() {
             let row_num = (row + 1) as u32;
+            if row % PROGRESS_INTERVAL == 0 {
+                progress(Some(row as f32 / records.len() as f32));
+            }
             
             worksheet.write_string(row_num, 0, &record.message_direction, Some(text_format))?;
-            worksheet.write_string(row_num, 1, &record.target_number, Some(text_format))?;
+            worksheet.write_string(row_num, 1, &settings.format_number(&record.target_number), Some(text_format))?;
             worksheet.write_string(row_num, 2, &record.remote_number, Some(text_format))?;
-            worksheet.write_string(row_num, 3, &record.normalized_number, Some(text_format))?;
-            worksheet.write_string(row_num, 4, &record.date_time, Some(text_format))?;
-            worksheet.write_datetime(row_num, 5, &record.end_time, Some(date_format))?;
+            worksheet.write_string(row_num, 3, &settings.format_number(&record.normalized_number), Some(text_format))?;
+            worksheet.write_datetime(row_num, 4, &Self::excel_datetime(&record.start_time.with_timezone(&settings.case_timezone)), Some(date_format))?;
+            worksheet.write_datetime(row_num, 5, &Self::excel_datetime(&record.end_time.with_timezone(&settings.case_timezone)), Some(date_format))?;
             worksheet.write_number(row_num, 6, record.length_of_call as f64, Some(number_format))?;
-            worksheet.write_number(row_num, 7, record.duration_minutes, Some(duration_format))?;
-            worksheet.write_string(row_num, 8, &record.day_of_week, Some(text_format))?;
+            worksheet.write_number(row_num, 7, Self::excel_duration(record.length_of_call), Some(duration_format))?;
+            worksheet.write_string(row_num, 8, record.local_day_of_week(settings.case_timezone), Some(text_format))?;
             worksheet.write_string(row_num, 9, &record.source_file, Some(text_format))?;
+            worksheet.write_string(row_num, 10, caller_names.lookup(&record.normalized_number).unwrap_or_default(), Some(text_format))?;
+            worksheet.write_string(row_num, 11, annotations.alias(&record.normalized_number).unwrap_or_default(), Some(text_format))?;
+            if let (true, Some(site)) = (cell_sites, &record.cell_site) {
+                worksheet.write_string(row_num, 12, site.cell_id.as_deref().unwrap_or_default(), Some(text_format))?;
+                worksheet.write_string(row_num, 13, site.sector.as_deref().unwrap_or_default(), Some(text_format))?;
+                if let Some(latitude) = site.latitude {
+                    worksheet.write_number(row_num, 14, latitude, Some(&coordinate_format))?;
+                }
+                if let Some(longitude) = site.longitude {
+                    worksheet.write_number(row_num, 15, longitude, Some(&coordinate_format))?;
+                }
+            }
+            if let (true, Some(device)) = (devices, &record.device) {
+                worksheet.write_string(row_num, device_col, device.imei.as_deref().unwrap_or_default(), Some(text_format))?;
+                worksheet.write_string(row_num, device_col + 1, device.imsi.as_deref().unwrap_or_default(), Some(text_format))?;
+            }
+            if record_annotations {
+                let record_key = record.record_key();
+                let tags: Vec<&str> = annotations.tags(&record_key).collect();
+                let tags = if tags.is_empty() && annotations.tagged_records.contains(&record_key) {
+                    tr!("value-yes")
+                } else {
+                    tags.join(", ")
+                };
+                worksheet.write_string(row_num, tag_col, &tags, Some(text_format))?;
+                worksheet.write_string(row_num, tag_col + 1, annotations.note(&record_key).unwrap_or_default(), Some(text_format))?;
+            }
         }
         
         Ok(())
@@ -113,28 +427,32 @@ impl ExcelExporter {
     fn export_analytics(
         workbook: &Workbook,
         analytics: &Analytics,
+        settings: &AppSettings,
         header_format: &Format,
         text_format: &Format,
         number_format: &Format,
     ) -> Result<()> {
-        let worksheet = workbook.add_worksheet(Some("Analytics"))?;
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-analytics")))?;
         
         // Set column widths
         worksheet.set_column(0, 0, 25.0, None)?;
         worksheet.set_column(1, 1, 15.0, None)?;
         
+        let locale = settings.export_locale();
+        
         // Summary statistics
         let summary_data = [
-            ("Total Calls", analytics.total_calls.to_string()),
-            ("Incoming Calls", analytics.incoming_calls.to_string()),
-            ("Outgoing Calls", analytics.outgoing_calls.to_string()),
-            ("Unique Phone Numbers", analytics.unique_numbers.to_string()),
-            ("Total Duration (minutes)", format!("{:.2}", analytics.total_duration_minutes)),
-            ("Average Call Duration (minutes)", format!("{:.2}", analytics.average_call_duration)),
+            (tr!("metric-total-calls"), analytics.total_calls.to_string()),
+            (tr!("metric-incoming-calls"), analytics.incoming_calls.to_string()),
+            (tr!("metric-outgoing-calls"), analytics.outgoing_calls.to_string()),
+            (tr!("metric-unique-numbers"), analytics.unique_numbers.to_string()),
+            (tr!("metric-unknown-numbers"), analytics.unknown_number_calls.to_string()),
+            (tr!("metric-total-duration"), locale.format_decimal(analytics.total_duration_minutes, 2)),
+            (tr!("metric-average-duration"), locale.format_decimal(analytics.average_call_duration, 2)),
         ];
         
-        worksheet.write_string(0, 0, "Metric", Some(header_format))?;
-        worksheet.write_string(0, 1, "Value", Some(header_format))?;
+        worksheet.write_string(0, 0, &tr!("header-metric"), Some(header_format))?;
+        worksheet.write_string(0, 1, &tr!("header-value"), Some(header_format))?;
         
         for (row, (metric, value)) in summary_data.iter().enumerate() {
             let row_num = (row + 1) as u32;
@@ -144,38 +462,38 @@ impl ExcelExporter {
         
         // Most frequent numbers
         let start_row = (summary_data.len() + 3) as u32;
-        worksheet.write_string(start_row, 0, "Most Frequent Numbers", Some(header_format))?;
-        worksheet.write_string(start_row, 1, "Call Count", Some(header_format))?;
+        worksheet.write_string(start_row, 0, &tr!("heading-most-frequent"), Some(header_format))?;
+        worksheet.write_string(start_row, 1, &tr!("header-call-count"), Some(header_format))?;
         
         for (i, (number, count)) in analytics.most_frequent_numbers.iter().enumerate() {
             let row_num = start_row + 1 + i as u32;
-            worksheet.write_string(row_num, 0, number, Some(text_format))?;
+            worksheet.write_string(row_num, 0, &settings.format_number(number), Some(text_format))?;
             worksheet.write_number(row_num, 1, *count as f64, Some(number_format))?;
         }
         
         // Calls by day
         let day_start_row = start_row + analytics.most_frequent_numbers.len() as u32 + 3;
-        worksheet.write_string(day_start_row, 0, "Calls by Day", Some(header_format))?;
-        worksheet.write_string(day_start_row, 1, "Call Count", Some(header_format))?;
+        worksheet.write_string(day_start_row, 0, &tr!("heading-calls-by-day", timezone = analytics.timezone.name()), Some(header_format))?;
+        worksheet.write_string(day_start_row, 1, &tr!("header-call-count"), Some(header_format))?;
         
         let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
         sorted_days.sort_by(|a, b| a.0.cmp(b.0));
         
         for (i, (day, count)) in sorted_days.iter().enumerate() {
             let row_num = day_start_row + 1 + i as u32;
-            worksheet.write_string(row_num, 0, day, Some(text_format))?;
+            worksheet.write_string(row_num, 0, &locale.format_day_key(day), Some(text_format))?;
             worksheet.write_number(row_num, 1, **count as f64, Some(number_format))?;
         }
         
         // Calls by hour
         let hour_start_row = day_start_row + sorted_days.len() as u32 + 3;
-        worksheet.write_string(hour_start_row, 0, "Calls by Hour", Some(header_format))?;
-        worksheet.write_string(hour_start_row, 1, "Call Count", Some(header_format))?;
+        worksheet.write_string(hour_start_row, 0, &tr!("heading-calls-by-hour", timezone = analytics.timezone.name()), Some(header_format))?;
+        worksheet.write_string(hour_start_row, 1, &tr!("header-call-count"), Some(header_format))?;
         
         for hour in 0..24 {
             if let Some(count) = analytics.calls_by_hour.get(&hour) {
                 let row_num = hour_start_row + 1 + hour as u32;
-                worksheet.write_string(row_num, 0, &format!("{:02}:00", hour), Some(text_format))?;
+                worksheet.write_string(row_num, 0, &locale.format_hour(hour), Some(text_format))?;
                 worksheet.write_number(row_num, 1, *count as f64, Some(number_format))?;
             }
         }
@@ -183,28 +501,35 @@ impl ExcelExporter {
         Ok(())
     }
     
+    #[allow(clippy::too_many_arguments)]
     fn export_summary_report(
         workbook: &Workbook,
         analytics: &Analytics,
         records: &[ProcessedCallRecord],
+        parse_reports: &[ParseReport],
+        annotations: &Annotations,
+        settings: &AppSettings,
         header_format: &Format,
         text_format: &Format,
     ) -> Result<()> {
-        let worksheet = workbook.add_worksheet(Some("Summary Report"))?;
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-summary-report")))?;
         
         // Set column width
         worksheet.set_column(0, 0, 80.0, None)?;
         
-        let report = crate::analytics::AnalyticsEngine::generate_summary_report(analytics, records);
-        let lines: Vec<&str> = report.lines().collect();
+        let input = ReportInput { analytics, records, annotations, settings, parse_reports };
+        let report = Report::compose(&settings.report_sections, &input);
         
-        for (row, line) in lines.iter().enumerate() {
-            let row_num = row as u32;
-            if line.starts_with("===") {
-                worksheet.write_string(row_num, 0, line, Some(header_format))?;
-            } else {
+        worksheet.write_string(0, 0, &report.title, Some(header_format))?;
+        let mut row_num = 2;
+        for block in &report.blocks {
+            worksheet.write_string(row_num, 0, &block.heading, Some(header_format))?;
+            row_num += 1;
+            for line in &block.lines {
                 worksheet.write_string(row_num, 0, line, Some(text_format))?;
+                row_num += 1;
             }
+            row_num += 1;
         }
         
         Ok(())
@@ -213,10 +538,11 @@ impl ExcelExporter {
     fn export_common_contacts(
         workbook: &Workbook,
         analytics: &Analytics,
+        settings: &AppSettings,
         header_format: &Format,
         text_format: &Format,
     ) -> Result<()> {
-        let worksheet = workbook.add_worksheet(Some("Common Contacts"))?;
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-common-contacts")))?;
         
         // Set column widths
         worksheet.set_column(0, 0, 15.0, None)?; // Phone Number
@@ -224,26 +550,793 @@ impl ExcelExporter {
         worksheet.set_column(2, 2, 10.0, None)?; // Count
         
         // Write headers
-        let headers = ["Phone Number", "Target Numbers", "Count"];
+        let headers = [tr!("header-phone-number"), tr!("header-target-numbers"), tr!("header-count")];
         for (col, header) in headers.iter().enumerate() {
             worksheet.write_string(0, col as u16, header, Some(header_format))?;
         }
         
         if analytics.common_contacts.is_empty() {
-            worksheet.write_string(1, 0, "No common contacts found across target numbers", Some(text_format))?;
+            worksheet.write_string(1, 0, &tr!("sheet-no-common-contacts"), Some(text_format))?;
             return Ok(());
         }
         
         // Write data
         for (row, contact) in analytics.common_contacts.iter().enumerate() {
             let row_num = (row + 1) as u32;
-            let target_nums = contact.target_numbers.join(", ");
+            let target_nums = contact.target_numbers.iter()
+                .map(|t| settings.format_number(t))
+                .collect::<Vec<_>>()
+                .join(", ");
             
-            worksheet.write_string(row_num, 0, &contact.number, Some(text_format))?;
+            worksheet.write_string(row_num, 0, &settings.format_number(&contact.number), Some(text_format))?;
             worksheet.write_string(row_num, 1, &target_nums, Some(text_format))?;
             worksheet.write_number(row_num, 2, contact.count as f64, Some(text_format))?;
         }
         
         Ok(())
     }
-} 
\ No newline at end of file
+    
+    #[allow(clippy::too_many_arguments)]
+    fn export_contact_summary(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        known_numbers: &KnownNumbers,
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        number_format: &Format,
+        duration_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-contact-summary")))?;
+        
+        worksheet.set_column(0, 0, 18.0, None)?; // Phone Number
+        worksheet.set_column(1, 1, 25.0, None)?; // Alias
+        worksheet.set_column(2, 3, 20.0, None)?; // First / Last Contact
+        worksheet.set_column(4, 6, 12.0, None)?; // Call counts
+        worksheet.set_column(7, 8, 14.0, None)?; // Durations
+        worksheet.set_column(9, 9, 12.0, None)?; // Night Calls
+        worksheet.set_column(10, 10, 10.0, None)?; // Watchlist
+        worksheet.set_column(11, 11, 25.0, None)?; // Category
+        
+        let timezone = settings.case_timezone;
+        let headers = [
+            tr!("header-phone-number"), tr!("header-alias"),
+            tr!("header-first-contact-zone", timezone = timezone.name()),
+            tr!("header-last-contact-zone", timezone = timezone.name()),
+            tr!("header-total-calls"), tr!("header-incoming"), tr!("header-outgoing"),
+            tr!("header-total-duration"), tr!("header-average-duration"),
+            tr!("header-night-calls"), tr!("header-watchlist"), tr!("header-category"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        let contacts = ContactSummary::summarize(records, timezone, settings.night_hours, settings.include_short_codes);
+        for (row, contact) in contacts.iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            let watched = annotations.watchlist.contains(&contact.number);
+            worksheet.write_string(row_num, 0, &settings.format_number(&contact.number), Some(text_format))?;
+            worksheet.write_string(row_num, 1, annotations.alias(&contact.number).unwrap_or_default(), Some(text_format))?;
+            worksheet.write_datetime(row_num, 2, &Self::excel_datetime(&contact.first_contact.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_datetime(row_num, 3, &Self::excel_datetime(&contact.last_contact.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_number(row_num, 4, contact.total_calls as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 5, contact.incoming as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 6, contact.outgoing as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 7, contact.total_seconds as f64 / 86_400.0, Some(duration_format))?;
+            worksheet.write_number(row_num, 8, contact.average_seconds() / 86_400.0, Some(duration_format))?;
+            worksheet.write_number(row_num, 9, contact.night_calls as f64, Some(number_format))?;
+            worksheet.write_string(row_num, 10, &if watched { tr!("value-yes") } else { String::new() }, Some(text_format))?;
+            worksheet.write_string(row_num, 11, &known_numbers.label(&contact.number).unwrap_or_default(), Some(text_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn export_contact_spans(
+        workbook: &Workbook,
+        analytics: &Analytics,
+        annotations: &Annotations,
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        number_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-contact-spans")))?;
+        let days_format = workbook.add_format()
+            .set_num_format("0.0")
+            .set_border(FormatBorder::Thin);
+        
+        worksheet.set_column(0, 0, 18.0, None)?; // Phone Number
+        worksheet.set_column(1, 1, 25.0, None)?; // Alias
+        worksheet.set_column(2, 3, 20.0, None)?; // First / Last Contact
+        worksheet.set_column(4, 5, 12.0, None)?; // Span, Call Count
+        worksheet.set_column(6, 6, 10.0, None)?; // Ongoing
+        
+        let timezone = settings.case_timezone;
+        let headers = [
+            tr!("header-phone-number"), tr!("header-alias"),
+            tr!("header-first-contact-zone", timezone = timezone.name()),
+            tr!("header-last-contact-zone", timezone = timezone.name()),
+            tr!("header-span-days"), tr!("header-call-count"), tr!("header-ongoing"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        if analytics.contact_spans.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-contact-spans"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        for (row, span) in analytics.contact_spans.iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            worksheet.write_string(row_num, 0, &settings.format_number(&span.number), Some(text_format))?;
+            worksheet.write_string(row_num, 1, annotations.alias(&span.number).unwrap_or_default(), Some(text_format))?;
+            worksheet.write_datetime(row_num, 2, &Self::excel_datetime(&span.first_contact.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_datetime(row_num, 3, &Self::excel_datetime(&span.last_contact.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_number(row_num, 4, span.span_days(), Some(&days_format))?;
+            worksheet.write_number(row_num, 5, span.calls as f64, Some(number_format))?;
+            worksheet.write_string(row_num, 6, &if span.ongoing { tr!("value-yes") } else { String::new() }, Some(text_format))?;
+        }
+        
+        // What ongoing means, under the table
+        let note_row = analytics.contact_spans.len() as u32 + 2;
+        worksheet.write_string(note_row, 0, &tr!("sheet-contact-spans-note", days = ONGOING_CONTACT_DAYS), None)?;
+        
+        Ok(())
+    }
+    
+    fn export_exchange_clusters(
+        workbook: &Workbook,
+        analytics: &Analytics,
+        settings: &AppSettings,
+        header_format: &Format,
+        text_format: &Format,
+        number_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-exchange-clusters")))?;
+        
+        worksheet.set_column(0, 0, 12.0, None)?; // Prefix
+        worksheet.set_column(1, 1, 15.0, None)?; // Phone Number
+        worksheet.set_column(2, 2, 10.0, None)?; // Call Count
+        
+        let headers = [tr!("header-prefix"), tr!("header-phone-number"), tr!("header-call-count")];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        if analytics.exchange_clusters.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-exchange-clusters"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        // One row per number, grouped under its exchange
+        let mut row_num = 1;
+        for cluster in &analytics.exchange_clusters {
+            for (number, count) in &cluster.numbers {
+                worksheet.write_string(row_num, 0, &format_prefix(&cluster.prefix), Some(text_format))?;
+                worksheet.write_string(row_num, 1, &settings.format_number(number), Some(text_format))?;
+                worksheet.write_number(row_num, 2, *count as f64, Some(number_format))?;
+                row_num += 1;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn export_area_codes(
+        workbook: &Workbook,
+        analytics: &Analytics,
+        header_format: &Format,
+        text_format: &Format,
+        number_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-area-codes")))?;
+        let share_format = workbook.add_format()
+            .set_num_format("0.0%")
+            .set_border(FormatBorder::Thin);
+        
+        worksheet.set_column(0, 0, 10.0, None)?; // Area Code
+        worksheet.set_column(1, 1, 25.0, None)?; // Region
+        worksheet.set_column(2, 4, 10.0, None)?; // Numbers, Call Count, Share
+        
+        let headers = [tr!("header-area-code"), tr!("header-region"), tr!("header-numbers"), tr!("header-call-count"), tr!("header-share")];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        if analytics.area_code_activity.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-area-codes"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        let nanp_calls: usize = analytics.area_code_activity.iter().map(|activity| activity.calls).sum();
+        let mut row_num = 1;
+        for activity in &analytics.area_code_activity {
+            let region = activity.region.as_deref().map_or_else(|| tr!("area-code-non-geographic"), region_label);
+            worksheet.write_string(row_num, 0, &activity.area_code, Some(text_format))?;
+            worksheet.write_string(row_num, 1, &region, Some(text_format))?;
+            worksheet.write_number(row_num, 2, activity.numbers as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 3, activity.calls as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 4, activity.calls as f64 / nanp_calls as f64, Some(&share_format))?;
+            row_num += 1;
+        }
+        
+        // Where the calls are concentrated, under the table
+        if let Some(concentration) = geographic_concentration(analytics) {
+            worksheet.write_string(row_num + 1, 0, &concentration, None)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// One row per top contact and one column per time bucket, with minutes on a call in each
+    /// cell. Cells are shaded directly, since the xlsxwriter bindings have no conditional
+    /// formats; calls that overlap another call on the same target are shaded orange.
+    fn export_call_durations(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        analytics: &Analytics,
+        settings: &AppSettings,
+        header_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-call-durations")))?;
+        let contacts: Vec<String> = analytics.most_frequent_numbers.iter().map(|(number, _)| number.clone()).collect();
+        let timeline = CallTimeline::build(records, &contacts);
+        
+        worksheet.set_column(0, 0, 15.0, None)?; // Phone Number
+        worksheet.set_column(1, 1, 10.0, None)?; // Calls
+        worksheet.write_string(0, 0, &tr!("header-phone-number"), Some(header_format))?;
+        worksheet.write_string(0, 1, &tr!("header-call-count"), Some(header_format))?;
+        
+        let Some((length, buckets)) = timeline.buckets(TIMELINE_MAX_BUCKETS) else {
+            worksheet.write_string(1, 0, &tr!("sheet-no-calls"), Some(text_format))?;
+            return Ok(());
+        };
+        
+        let bucket_header_format = workbook.add_format()
+            .set_bold()
+            .set_bg_color(xlsxwriter::FormatColor::Gray)
+            .set_border(FormatBorder::Thin)
+            .set_rotation(90)
+            .set_num_format(settings.export_locale().excel_short_datetime_format());
+        let bar_format = workbook.add_format()
+            .set_num_format("0")
+            .set_bg_color(xlsxwriter::FormatColor::Custom(0x6496FF));
+        let overlap_format = workbook.add_format()
+            .set_num_format("0")
+            .set_bg_color(xlsxwriter::FormatColor::Orange);
+        
+        worksheet.set_column(2, (buckets.len() + 1) as u16, 4.0, None)?;
+        worksheet.set_row(0, 75.0, None)?;
+        for (i, start) in buckets.iter().enumerate() {
+            worksheet.write_datetime(0, (i + 2) as u16, &Self::excel_datetime(start), Some(&bucket_header_format))?;
+        }
+        
+        for (row, contact) in timeline.rows.iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            worksheet.write_string(row_num, 0, &settings.format_number(&contact.number), Some(text_format))?;
+            worksheet.write_number(row_num, 1, contact.calls.len() as f64, Some(text_format))?;
+            for (i, start) in buckets.iter().enumerate() {
+                let (minutes, overlaps) = contact.busy_minutes(*start, length);
+                if minutes > 0.0 || overlaps {
+                    let format = if overlaps { &overlap_format } else { &bar_format };
+                    worksheet.write_number(row_num, (i + 2) as u16, minutes.ceil(), Some(format))?;
+                }
+            }
+        }
+        
+        let note_row = (timeline.rows.len() + 2) as u32;
+        worksheet.write_string(note_row, 0, &tr!("sheet-call-durations-note",
+            minutes = length.num_minutes(), overlapping = timeline.overlapping_calls()), None)?;
+        worksheet.freeze_panes(1, 2);
+        
+        Ok(())
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn export_subscriber_resolution(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        carriers: &CarrierDirectory,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+        header_format: &Format,
+        number_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-subscribers")))?;
+        
+        worksheet.set_column(0, 0, 18.0, None)?; // Phone Number
+        worksheet.set_column(1, 1, 12.0, None)?; // Call Count
+        worksheet.set_column(2, 2, 30.0, None)?; // Subscriber
+        worksheet.set_column(3, 3, 45.0, None)?; // Address
+        worksheet.set_column(4, 4, 30.0, None)?; // Carrier
+        worksheet.set_column(5, 5, 18.0, None)?; // LRN
+        worksheet.set_column(6, 6, 30.0, None)?; // Caller Name
+        worksheet.set_column(7, 7, 30.0, None)?; // Alias
+        
+        let headers = [
+            tr!("header-phone-number"), tr!("header-call-count"), tr!("header-subscriber"), tr!("header-address"),
+            tr!("header-carrier"), tr!("header-lrn"), tr!("header-caller-name"), tr!("header-alias"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        for (row, resolution) in annotations.subscribers.resolve(records).iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            worksheet.write_string(row_num, 0, &settings.format_number(resolution.number), Some(text_format))?;
+            worksheet.write_number(row_num, 1, resolution.calls as f64, Some(number_format))?;
+            match resolution.subscriber {
+                Some(subscriber) => {
+                    worksheet.write_string(row_num, 2, &subscriber.name, Some(text_format))?;
+                    worksheet.write_string(row_num, 3, &subscriber.address, Some(text_format))?;
+                }
+                None => {
+                    worksheet.write_string(row_num, 2, &tr!("sheet-unresolved"), Some(text_format))?;
+                    worksheet.write_string(row_num, 3, "", Some(text_format))?;
+                }
+            }
+            let carrier = carriers.lookup(resolution.number);
+            let carrier_name = carrier.as_ref().map(|info| match info.source {
+                CarrierSource::Ported => tr!("carrier-ported", carrier = info.carrier),
+                CarrierSource::Assigned => info.carrier.to_string(),
+            });
+            let lrn = carrier.as_ref().and_then(|info| info.lrn).map(|lrn| settings.format_number(lrn));
+            worksheet.write_string(row_num, 4, carrier_name.as_deref().unwrap_or_default(), Some(text_format))?;
+            worksheet.write_string(row_num, 5, lrn.as_deref().unwrap_or_default(), Some(text_format))?;
+            worksheet.write_string(row_num, 6, caller_names.lookup(resolution.number).unwrap_or_default(), Some(text_format))?;
+            worksheet.write_string(row_num, 7, annotations.alias(resolution.number).unwrap_or_default(), Some(text_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn export_subscriber_info(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        parse_reports: &[ParseReport],
+        settings: &AppSettings,
+        header_format: &Format,
+        number_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-subscriber-info")))?;
+        
+        worksheet.set_column(0, 0, 18.0, None)?; // Phone Number
+        worksheet.set_column(1, 2, 12.0, None)?; // As Target, As Contact
+        worksheet.set_column(3, 3, 30.0, None)?; // Name
+        worksheet.set_column(4, 4, 45.0, None)?; // Address
+        worksheet.set_column(5, 5, 18.0, None)?; // Account Number
+        worksheet.set_column(6, 6, 30.0, None)?; // Email
+        worksheet.set_column(7, 8, 15.0, None)?; // Account Start, Account End
+        worksheet.set_column(9, 9, 30.0, None)?; // Source File
+        
+        let headers = [
+            tr!("header-phone-number"), tr!("subscribers-as-target"), tr!("subscribers-as-contact"), tr!("subscribers-name"),
+            tr!("subscribers-address"), tr!("subscribers-account"), tr!("subscribers-email"), tr!("subscribers-account-start"),
+            tr!("subscribers-account-end"), tr!("header-source-file"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        for (row, subscriber) in match_subscribers(parse_reports, records).iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            let info = subscriber.info;
+            worksheet.write_string(row_num, 0, &settings.format_number(&info.number), Some(text_format))?;
+            worksheet.write_number(row_num, 1, subscriber.as_target as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 2, subscriber.as_contact as f64, Some(number_format))?;
+            let fields = [&info.name, &info.address, &info.account_number, &info.email, &info.account_start, &info.account_end];
+            for (col, field) in fields.into_iter().enumerate() {
+                worksheet.write_string(row_num, (col + 3) as u16, field, Some(text_format))?;
+            }
+            worksheet.write_string(row_num, 9, subscriber.source_file, Some(text_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    fn export_known_numbers(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        known_numbers: &KnownNumbers,
+        settings: &AppSettings,
+        header_format: &Format,
+        text_format: &Format,
+        number_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-known-numbers")))?;
+        
+        worksheet.set_column(0, 0, 25.0, None)?; // Category
+        worksheet.set_column(1, 1, 18.0, None)?; // Phone Number
+        worksheet.set_column(2, 2, 12.0, None)?; // Call Count
+        
+        let headers = [tr!("header-category"), tr!("header-phone-number"), tr!("header-call-count")];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        let counts = known_numbers.category_counts(records);
+        if counts.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-known-numbers"), Some(text_format))?;
+        }
+        let rows = counts.iter().flat_map(|count| count.numbers.iter().map(move |number| (&count.category, number)));
+        for (row, (category, (number, calls))) in rows.enumerate() {
+            let row_num = (row + 1) as u32;
+            worksheet.write_string(row_num, 0, category, Some(text_format))?;
+            worksheet.write_string(row_num, 1, &settings.format_number(number), Some(text_format))?;
+            worksheet.write_number(row_num, 2, *calls as f64, Some(number_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn export_bookmarks(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        number_format: &Format,
+        duration_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-bookmarks")))?;
+        
+        worksheet.set_column(0, 0, 20.0, None)?; // Date & Time
+        worksheet.set_column(1, 1, 12.0, None)?; // Direction
+        worksheet.set_column(2, 3, 16.0, None)?; // Target / Remote Number
+        worksheet.set_column(4, 5, 14.0, None)?; // Durations
+        worksheet.set_column(6, 6, 30.0, None)?; // Source File
+        
+        let headers = [
+            tr!("header-start-time-zone", timezone = settings.case_timezone.name()), tr!("header-direction"),
+            tr!("header-target-number"), tr!("header-remote-number"),
+            tr!("header-duration-sec"), tr!("header-duration"), tr!("header-source-file"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        let bookmarked = annotations.bookmarked_records(records);
+        if bookmarked.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-bookmarks"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        for (row, record) in bookmarked.iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            worksheet.write_datetime(row_num, 0, &Self::excel_datetime(&record.start_time.with_timezone(&settings.case_timezone)), Some(date_format))?;
+            worksheet.write_string(row_num, 1, &record.message_direction, Some(text_format))?;
+            worksheet.write_string(row_num, 2, &settings.format_number(&record.target_number), Some(text_format))?;
+            worksheet.write_string(row_num, 3, &settings.format_number(&record.normalized_number), Some(text_format))?;
+            worksheet.write_number(row_num, 4, record.length_of_call as f64, Some(number_format))?;
+            worksheet.write_number(row_num, 5, Self::excel_duration(record.length_of_call), Some(duration_format))?;
+            worksheet.write_string(row_num, 6, &record.source_file, Some(text_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    fn export_keyword_hits(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        annotations: &Annotations,
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-keyword-hits")))?;
+        
+        worksheet.set_column(0, 0, 20.0, None)?; // Date & Time
+        worksheet.set_column(1, 1, 12.0, None)?; // Direction
+        worksheet.set_column(2, 3, 16.0, None)?; // Target / Remote Number
+        worksheet.set_column(4, 4, 20.0, None)?; // Keywords
+        worksheet.set_column(5, 5, 8.0, None)?; // Tagged
+        worksheet.set_column(6, 6, 80.0, None)?; // Message
+        
+        let headers = [
+            tr!("header-start-time-zone", timezone = settings.case_timezone.name()), tr!("header-direction"),
+            tr!("header-target-number"), tr!("header-remote-number"),
+            tr!("header-keywords"), tr!("header-tagged"), tr!("header-message"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header, Some(header_format))?;
+        }
+        
+        let hits = KeywordHits::search(records, &annotations.keywords, settings.case_timezone);
+        if hits.hits.is_empty() {
+            worksheet.write_string(1, 0, &tr!("sheet-no-keyword-hits"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        for (row, hit) in hits.hits.iter().enumerate() {
+            let row_num = (row + 1) as u32;
+            let record = hit.record;
+            let tagged = annotations.tagged_records.contains(&record.record_key());
+            worksheet.write_datetime(row_num, 0, &Self::excel_datetime(&record.start_time.with_timezone(&settings.case_timezone)), Some(date_format))?;
+            worksheet.write_string(row_num, 1, &record.message_direction, Some(text_format))?;
+            worksheet.write_string(row_num, 2, &settings.format_number(&record.target_number), Some(text_format))?;
+            worksheet.write_string(row_num, 3, &settings.format_number(&record.normalized_number), Some(text_format))?;
+            worksheet.write_string(row_num, 4, &hits.matched_text(hit), Some(text_format))?;
+            worksheet.write_string(row_num, 5, &if tagged { tr!("value-yes") } else { String::new() }, Some(text_format))?;
+            worksheet.write_string(row_num, 6, record.message_content.as_deref().unwrap_or_default(), Some(text_format))?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Tables one above the other: the towers each target used by day, the dwell periods, the
+    /// tower changes, and any moves no phone could have made.
+    fn export_location_analysis(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        number_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-location-analysis")))?;
+        let distance_format = workbook.add_format()
+            .set_num_format("0.00")
+            .set_border(FormatBorder::Thin);
+        let timezone = settings.case_timezone;
+        let locale = settings.export_locale();
+        let summary = LocationSummary::build(records, timezone);
+        
+        worksheet.set_column(0, 0, 20.0, None)?; // Date / Target
+        worksheet.set_column(1, 1, 20.0, None)?; // Target / Tower / Left
+        worksheet.set_column(2, 2, 22.0, None)?; // Tower
+        worksheet.set_column(3, 4, 20.0, None)?; // Cell Site ID / times
+        worksheet.set_column(5, 7, 14.0, None)?; // Counts / Distance / Speed
+        
+        worksheet.write_string(0, 0, &tr!(
+            "location-summary",
+            towers = summary.tower_count(),
+            dwells = summary.dwells.len(),
+            transitions = summary.transitions.len(),
+            days = summary.days.len()
+        ), None)?;
+        worksheet.write_string(1, 0, &tr!("location-hint", hours = DWELL_BREAK_HOURS), None)?;
+        
+        // Every tower each target used, by day
+        let mut row = 3;
+        worksheet.write_string(row, 0, &tr!("heading-tower-usage", timezone = timezone.name()), None)?;
+        row += 1;
+        let headers = [
+            tr!("header-date"), tr!("header-target-number"), tr!("column-tower-location"),
+            tr!("header-cell-site-id"), tr!("header-call-count"), tr!("header-tower-changes"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(row, col as u16, header, Some(header_format))?;
+        }
+        for day in &summary.days {
+            for (i, (tower, calls)) in day.towers.iter().enumerate() {
+                row += 1;
+                worksheet.write_string(row, 0, &locale.format_date(&day.date), Some(text_format))?;
+                worksheet.write_string(row, 1, &settings.format_number(&day.target_number), Some(text_format))?;
+                worksheet.write_string(row, 2, &tower.label(), Some(text_format))?;
+                worksheet.write_string(row, 3, tower.cell_id.as_deref().unwrap_or_default(), Some(text_format))?;
+                worksheet.write_number(row, 4, *calls as f64, Some(number_format))?;
+                // The day's changes once, beside its busiest tower
+                if i == 0 {
+                    worksheet.write_number(row, 5, day.transitions as f64, Some(number_format))?;
+                }
+            }
+        }
+        
+        row += 3;
+        worksheet.write_string(row, 0, &tr!("heading-dwell-periods"), None)?;
+        row += 1;
+        let headers = [
+            tr!("header-target-number"), tr!("column-tower-location"), tr!("header-cell-site-id"),
+            tr!("header-first-call-zone", timezone = timezone.name()), tr!("header-last-call-zone", timezone = timezone.name()),
+            tr!("header-duration-min"), tr!("header-call-count"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(row, col as u16, header, Some(header_format))?;
+        }
+        for dwell in &summary.dwells {
+            row += 1;
+            worksheet.write_string(row, 0, &settings.format_number(&dwell.target_number), Some(text_format))?;
+            worksheet.write_string(row, 1, &dwell.tower.label(), Some(text_format))?;
+            worksheet.write_string(row, 2, dwell.tower.cell_id.as_deref().unwrap_or_default(), Some(text_format))?;
+            worksheet.write_datetime(row, 3, &Self::excel_datetime(&dwell.first.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_datetime(row, 4, &Self::excel_datetime(&dwell.last.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_number(row, 5, dwell.duration().num_minutes() as f64, Some(number_format))?;
+            worksheet.write_number(row, 6, dwell.calls as f64, Some(number_format))?;
+        }
+        
+        row += 3;
+        worksheet.write_string(row, 0, &tr!("heading-tower-changes"), None)?;
+        row += 1;
+        let headers = [
+            tr!("header-target-number"), tr!("header-left-zone", timezone = timezone.name()), tr!("header-from-tower"),
+            tr!("header-arrived-zone", timezone = timezone.name()), tr!("header-to-tower"), tr!("header-distance-km"),
+            tr!("header-speed-kmh"), tr!("header-implausible"),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(row, col as u16, header, Some(header_format))?;
+        }
+        if summary.transitions.is_empty() {
+            worksheet.write_string(row + 1, 0, &tr!("sheet-no-tower-changes"), Some(text_format))?;
+        }
+        for transition in &summary.transitions {
+            row += 1;
+            worksheet.write_string(row, 0, &settings.format_number(&transition.target_number), Some(text_format))?;
+            worksheet.write_datetime(row, 1, &Self::excel_datetime(&transition.left.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_string(row, 2, &transition.from.label(), Some(text_format))?;
+            worksheet.write_datetime(row, 3, &Self::excel_datetime(&transition.arrived.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_string(row, 4, &transition.to.label(), Some(text_format))?;
+            if let Some(distance) = transition.distance_km {
+                worksheet.write_number(row, 5, distance, Some(&distance_format))?;
+            }
+            if let Some(speed) = transition.speed_kmh() {
+                worksheet.write_number(row, 6, speed, Some(number_format))?;
+            }
+            worksheet.write_string(row, 7, &if transition.implausible() { tr!("value-yes") } else { String::new() }, Some(text_format))?;
+        }
+        
+        // Checked between consecutive calls with coordinates, so a call on a tower without
+        // them cannot hide a jump
+        if !summary.implausible.is_empty() {
+            row += 3;
+            worksheet.write_string(row, 0, &tr!("heading-implausible-moves"), None)?;
+            worksheet.write_string(row + 1, 0, &tr!("implausible-moves-hint", speed = IMPLAUSIBLE_SPEED_KMH, range = TOWER_RANGE_KM), None)?;
+            row += 2;
+            let headers = [
+                tr!("header-target-number"), tr!("header-left-zone", timezone = timezone.name()), tr!("header-from-tower"),
+                tr!("header-arrived-zone", timezone = timezone.name()), tr!("header-to-tower"), tr!("header-distance-km"),
+                tr!("header-speed-kmh"),
+            ];
+            for (col, header) in headers.iter().enumerate() {
+                worksheet.write_string(row, col as u16, header, Some(header_format))?;
+            }
+            for transition in &summary.implausible {
+                row += 1;
+                worksheet.write_string(row, 0, &settings.format_number(&transition.target_number), Some(text_format))?;
+                worksheet.write_datetime(row, 1, &Self::excel_datetime(&transition.left.with_timezone(&timezone)), Some(date_format))?;
+                worksheet.write_string(row, 2, &transition.from.label(), Some(text_format))?;
+                worksheet.write_datetime(row, 3, &Self::excel_datetime(&transition.arrived.with_timezone(&timezone)), Some(date_format))?;
+                worksheet.write_string(row, 4, &transition.to.label(), Some(text_format))?;
+                if let Some(distance) = transition.distance_km {
+                    worksheet.write_number(row, 5, distance, Some(&distance_format))?;
+                }
+                // Both calls in the same second: no finite speed
+                if let Some(speed) = transition.speed_kmh() {
+                    worksheet.write_number(row, 6, speed, Some(number_format))?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn export_geofence_hits(
+        workbook: &Workbook,
+        records: &[ProcessedCallRecord],
+        geofence: &Geofence,
+        settings: &AppSettings,
+        header_format: &Format,
+        date_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&tr!("sheet-geofence-hits")))?;
+        let coordinate_format = workbook.add_format()
+            .set_num_format("0.00000")
+            .set_border(FormatBorder::Thin);
+        let distance_format = workbook.add_format()
+            .set_num_format("0.00")
+            .set_border(FormatBorder::Thin);
+        
+        worksheet.set_column(0, 0, 24.0, None)?; // Local Time
+        worksheet.set_column(1, 1, 12.0, None)?; // Direction
+        worksheet.set_column(2, 3, 16.0, None)?; // Target / Remote Number
+        worksheet.set_column(4, 5, 12.0, None)?; // Cell Site ID, Sector
+        worksheet.set_column(6, 7, 12.0, None)?; // Latitude, Longitude
+        worksheet.set_column(8, 8, 14.0, None)?; // Distance
+        
+        // What was searched, above the table
+        let timezone = settings.case_timezone;
+        let hits = geofence.hits(records);
+        worksheet.write_string(0, 0, &tr!("geofence-summary", count = hits.len(), geofence = geofence.label(timezone)), None)?;
+        let radius = matches!(geofence.area, GeofenceArea::Radius { .. });
+        let mut headers = vec![
+            tr!("header-start-time-zone", timezone = timezone.name()), tr!("header-direction"),
+            tr!("header-target-number"), tr!("header-remote-number"),
+            tr!("header-cell-site-id"), tr!("header-sector"), tr!("header-latitude"), tr!("header-longitude"),
+        ];
+        if radius {
+            headers.push(tr!("header-distance-km"));
+        }
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(2, col as u16, header, Some(header_format))?;
+        }
+        
+        if hits.is_empty() {
+            worksheet.write_string(3, 0, &tr!("sheet-no-geofence-hits"), Some(text_format))?;
+            return Ok(());
+        }
+        
+        for (row, record) in hits.iter().enumerate() {
+            let row_num = (row + 3) as u32;
+            worksheet.write_datetime(row_num, 0, &Self::excel_datetime(&record.start_time.with_timezone(&timezone)), Some(date_format))?;
+            worksheet.write_string(row_num, 1, &record.message_direction, Some(text_format))?;
+            worksheet.write_string(row_num, 2, &settings.format_number(&record.target_number), Some(text_format))?;
+            worksheet.write_string(row_num, 3, &settings.format_number(&record.normalized_number), Some(text_format))?;
+            if let Some(site) = &record.cell_site {
+                worksheet.write_string(row_num, 4, site.cell_id.as_deref().unwrap_or_default(), Some(text_format))?;
+                worksheet.write_string(row_num, 5, site.sector.as_deref().unwrap_or_default(), Some(text_format))?;
+                if let Some((latitude, longitude)) = site.coordinates() {
+                    worksheet.write_number(row_num, 6, latitude, Some(&coordinate_format))?;
+                    worksheet.write_number(row_num, 7, longitude, Some(&coordinate_format))?;
+                }
+            }
+            if let Some(distance) = geofence.distance_km(record) {
+                worksheet.write_number(row_num, 8, distance, Some(&distance_format))?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Sheet names are capped at 31 characters and cannot contain `[]:*?/\`.
+    fn script_sheet_name(script_name: &str) -> String {
+        let cleaned: String = script_name.chars()
+            .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+            .collect();
+        tr!("sheet-script", name = cleaned).chars().take(31).collect()
+    }
+    
+    fn export_script_output(
+        workbook: &Workbook,
+        output: &ScriptOutput,
+        header_format: &Format,
+        text_format: &Format,
+    ) -> Result<()> {
+        let mut worksheet = workbook.add_worksheet(Some(&Self::script_sheet_name(&output.script_name)))?;
+        
+        let mut row_num = 0u32;
+        for (name, value) in &output.metrics {
+            worksheet.write_string(row_num, 0, name, Some(header_format))?;
+            worksheet.write_string(row_num, 1, value, Some(text_format))?;
+            row_num += 1;
+        }
+        if !output.metrics.is_empty() {
+            row_num += 1;
+        }
+        
+        if output.columns.is_empty() {
+            if output.metrics.is_empty() {
+                worksheet.write_string(row_num, 0, &tr!("sheet-script-no-output"), Some(text_format))?;
+            }
+            return Ok(());
+        }
+        
+        worksheet.set_column(0, output.columns.len() as u16 - 1, 18.0, None)?;
+        for (col, column) in output.columns.iter().enumerate() {
+            worksheet.write_string(row_num, col as u16, column, Some(header_format))?;
+        }
+        for row in output.padded_rows() {
+            row_num += 1;
+            for (col, value) in row.iter().enumerate() {
+                worksheet.write_string(row_num, col as u16, value, Some(text_format))?;
+            }
+        }
+        
+        Ok(())
+    }
+}
\ No newline at end of file
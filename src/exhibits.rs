@@ -0,0 +1,261 @@
+use crate::export_locale::ClockFormat;
+use crate::pdf::{self, PdfPages, PdfStyle, MARGIN, PAGE_HEIGHT, PAGE_WIDTH};
+use crate::report::{Report, ReportInput};
+use crate::tr;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use log::info;
+use std::path::Path;
+
+/// Height of a chart's plot area in points.
+const CHART_HEIGHT: f32 = 260.0;
+/// Most day labels under the calls-by-day chart; the rest are skipped so they stay legible.
+const CHART_DAY_LABELS: usize = 12;
+/// Source file names are cut to this many characters to keep rows on one line.
+const SOURCE_CHARS: usize = 28;
+
+/// One exhibit: its index description and its laid-out pages.
+struct Exhibit {
+    description: String,
+    pages: Vec<String>,
+}
+
+/// The case as a court-exhibit package: one PDF with a cover index, then individually numbered
+/// exhibits (the summary report, the calls-by-hour and calls-by-day charts, and a call list for
+/// each of the most frequent contacts). Every exhibit starts on a new page, carries an
+/// "EXHIBIT n" label in its corner, and is paginated on its own ("Exhibit 4 — Page 2 of 3"),
+/// the way toll analysis is usually handed up at trial.
+pub struct ExhibitPackage;
+
+impl ExhibitPackage {
+    pub fn export(report: &Report, input: &ReportInput, output_path: &Path) -> Result<()> {
+        info!("Exporting exhibit package: {:?}", output_path);
+        std::fs::write(output_path, Self::to_bytes(report, input))
+            .with_context(|| format!("Failed to write exhibit package: {:?}", output_path))?;
+        info!("Successfully exported exhibit package");
+        Ok(())
+    }
+
+    pub fn to_bytes(report: &Report, input: &ReportInput) -> Vec<u8> {
+        let mut exhibits = vec![
+            summary_exhibit(report),
+            hour_chart_exhibit(input),
+            day_chart_exhibit(input),
+        ];
+        exhibits.extend(input.analytics.most_frequent_numbers.iter().map(|(number, _)| contact_exhibit(number, input)));
+
+        let cover = cover_index(&exhibits, input);
+        let mut pages = cover;
+        for (index, exhibit) in exhibits.into_iter().enumerate() {
+            let number = index + 1;
+            let count = exhibit.pages.len();
+            for (page, mut content) in exhibit.pages.into_iter().enumerate() {
+                content.push_str(&exhibit_label(number));
+                let footer = tr!("exhibit-footer", number = number, page = page + 1, pages = count);
+                let x = (PAGE_WIDTH - pdf::text_width(PdfStyle::Small, &footer)) / 2.0;
+                content.push_str(&pdf::text_at(PdfStyle::Small, x, MARGIN / 2.0, &footer));
+                pages.push(content);
+            }
+        }
+        pdf::write_pdf(&pages)
+    }
+}
+
+/// The boxed "EXHIBIT n" label in the top right corner, inside the margin.
+fn exhibit_label(number: usize) -> String {
+    let label = tr!("exhibit-label", number = number);
+    let width = pdf::text_width(PdfStyle::Heading, &label) + 16.0;
+    let x = PAGE_WIDTH - MARGIN - width;
+    let y = PAGE_HEIGHT - MARGIN + 8.0;
+    format!(
+        "0 0 0 RG 1 w {:.1} {:.1} {:.1} 24 re S\n{}",
+        x, y, width, pdf::text_at(PdfStyle::Heading, x + 8.0, y + 7.0, &label)
+    )
+}
+
+/// The index page(s): the case, then every exhibit with the package page it starts on.
+/// Exhibits follow the index, so their page numbers depend on its length; it is laid out once
+/// to count its pages and again with the numbers filled in.
+fn cover_index(exhibits: &[Exhibit], input: &ReportInput) -> Vec<String> {
+    let lay_out = |first_page: usize| {
+        let ReportInput { analytics, settings, .. } = *input;
+        let mut pages = PdfPages::new();
+        pages.line(PdfStyle::Title, &tr!("exhibit-index-title"));
+        let targets = analytics.target_numbers.iter().map(|t| settings.format_number(t)).collect::<Vec<_>>().join(", ");
+        let locale = settings.export_locale();
+        let day = |time: &chrono::DateTime<Utc>| locale.format_date(&time.with_timezone(&analytics.timezone));
+        pages.line(PdfStyle::Body, &tr!("exhibit-index-targets", targets = targets));
+        pages.line(PdfStyle::Body, &tr!("exhibit-index-period",
+            from = day(&analytics.date_range.0), to = day(&analytics.date_range.1), timezone = analytics.timezone.name()));
+        pages.line(PdfStyle::Body, &tr!("exhibit-index-prepared", date = locale.format_date(&Utc::now())));
+        pages.line(PdfStyle::Body, "");
+        let columns = [MARGIN, MARGIN + 80.0, PAGE_WIDTH - MARGIN - 60.0];
+        let headings = [tr!("exhibit-index-exhibit"), tr!("exhibit-index-description"), tr!("exhibit-index-page")];
+        pages.row(PdfStyle::Heading, &[
+            (columns[0], headings[0].as_str()),
+            (columns[1], headings[1].as_str()),
+            (columns[2], headings[2].as_str()),
+        ]);
+        let mut page = first_page;
+        for (index, exhibit) in exhibits.iter().enumerate() {
+            pages.row(PdfStyle::Body, &[
+                (columns[0], (index + 1).to_string().as_str()),
+                (columns[1], exhibit.description.as_str()),
+                (columns[2], page.to_string().as_str()),
+            ]);
+            page += exhibit.pages.len();
+        }
+        pages.finish()
+    };
+    let cover_pages = lay_out(0).len();
+    lay_out(cover_pages + 1)
+}
+
+fn summary_exhibit(report: &Report) -> Exhibit {
+    let mut pages = PdfPages::new();
+    report.lay_out(&mut pages);
+    Exhibit { description: tr!("exhibit-summary"), pages: pages.finish() }
+}
+
+fn hour_chart_exhibit(input: &ReportInput) -> Exhibit {
+    let analytics = input.analytics;
+    let locale = input.settings.export_locale();
+    let label = |hour: u32| match locale.clock {
+        ClockFormat::TwentyFourHour => format!("{:02}", hour),
+        ClockFormat::TwelveHour => locale.format_hour(hour),
+    };
+    let bars: Vec<(String, usize)> = (0..24)
+        .map(|hour| (label(hour), analytics.calls_by_hour.get(&hour).copied().unwrap_or(0)))
+        .collect();
+    let title = tr!("exhibit-hour-chart", timezone = analytics.timezone.name());
+    let mut pages = PdfPages::new();
+    pages.line(PdfStyle::Title, &title);
+    pages.line(PdfStyle::Body, &tr!("exhibit-chart-caption", calls = analytics.total_calls));
+    pages.line(PdfStyle::Body, "");
+    bar_chart(&mut pages, &bars, 1);
+    Exhibit { description: title, pages: pages.finish() }
+}
+
+/// Days without calls are drawn as empty slots, so gaps in activity show.
+fn day_chart_exhibit(input: &ReportInput) -> Exhibit {
+    let analytics = input.analytics;
+    let locale = input.settings.export_locale();
+    let mut days: Vec<NaiveDate> = analytics.calls_by_day.keys()
+        .filter_map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+        .collect();
+    days.sort();
+    let mut bars = Vec::new();
+    if let (Some(&first), Some(&last)) = (days.first(), days.last()) {
+        let mut day = first;
+        while day <= last {
+            let key = day.format("%Y-%m-%d").to_string();
+            bars.push((locale.format_day_month(&day), analytics.calls_by_day.get(&key).copied().unwrap_or(0)));
+            let Some(next) = day.succ_opt() else {
+                break;
+            };
+            day = next;
+        }
+    }
+    let title = tr!("exhibit-day-chart", timezone = analytics.timezone.name());
+    let mut pages = PdfPages::new();
+    pages.line(PdfStyle::Title, &title);
+    pages.line(PdfStyle::Body, &tr!("exhibit-chart-caption", calls = analytics.total_calls));
+    pages.line(PdfStyle::Body, "");
+    let label_every = bars.len().div_ceil(CHART_DAY_LABELS).max(1);
+    bar_chart(&mut pages, &bars, label_every);
+    Exhibit { description: title, pages: pages.finish() }
+}
+
+/// A bar per value, scaled to the largest, with every `label_every`-th label under its bar.
+fn bar_chart(pages: &mut PdfPages, bars: &[(String, usize)], label_every: usize) {
+    let (_, _, label_height) = PdfStyle::Small.font();
+    let top = pages.reserve(CHART_HEIGHT + 2.0 * label_height);
+    let bottom = top - CHART_HEIGHT;
+    let left = MARGIN + 24.0;
+    let right = PAGE_WIDTH - MARGIN;
+    let max = bars.iter().map(|(_, value)| *value).max().unwrap_or(0).max(1);
+
+    let mut ops = String::from("0.0 0.0 0.0 RG 0.5 w\n");
+    ops.push_str(&format!("{:.1} {:.1} m {:.1} {:.1} l S\n", left, bottom, right, bottom));
+    ops.push_str(&format!("{:.1} {:.1} m {:.1} {:.1} l S\n", left, bottom, left, top));
+    ops.push_str(&pdf::text_at(PdfStyle::Small, MARGIN, bottom, "0"));
+    ops.push_str(&pdf::text_at(PdfStyle::Small, MARGIN, top - label_height, &max.to_string()));
+    if !bars.is_empty() {
+        let slot = (right - left) / bars.len() as f32;
+        ops.push_str("0.25 0.42 0.68 rg\n");
+        for (i, (_, value)) in bars.iter().enumerate() {
+            let height = CHART_HEIGHT * (*value as f32 / max as f32);
+            if height > 0.0 {
+                ops.push_str(&format!("{:.1} {:.1} {:.1} {:.1} re f\n", left + slot * (i as f32 + 0.1), bottom, slot * 0.8, height));
+            }
+        }
+        ops.push_str("0 0 0 rg\n");
+        for (i, (label, _)) in bars.iter().enumerate().step_by(label_every) {
+            let x = left + slot * (i as f32 + 0.5) - pdf::text_width(PdfStyle::Small, label) / 2.0;
+            ops.push_str(&pdf::text_at(PdfStyle::Small, x, bottom - label_height, label));
+        }
+    }
+    pages.draw(&ops);
+}
+
+/// Every call with one contact in call order, under a line of totals and any known name.
+fn contact_exhibit(number: &str, input: &ReportInput) -> Exhibit {
+    let ReportInput { analytics, records, annotations, settings, .. } = *input;
+    let mut calls: Vec<_> = records.iter().filter(|r| r.normalized_number == number).collect();
+    calls.sort_by_key(|r| r.start_time);
+    let incoming = calls.iter().filter(|r| r.message_direction.eq_ignore_ascii_case("incoming")).count();
+    let minutes: f64 = calls.iter().map(|r| r.duration_minutes).sum();
+    let locale = settings.export_locale();
+
+    let formatted = settings.format_number(number);
+    let description = tr!("exhibit-contact", number = formatted.clone());
+    let mut pages = PdfPages::new();
+    pages.line(PdfStyle::Title, &description);
+    let name = annotations.alias(number)
+        .or_else(|| annotations.subscribers.lookup(number).map(|s| s.name.as_str()));
+    if let Some(name) = name {
+        pages.line(PdfStyle::Body, &tr!("exhibit-contact-name", name = name));
+    }
+    if let Some(entity) = annotations.entities.entity_of(number) {
+        pages.line(PdfStyle::Body, &tr!("exhibit-contact-entity", entity = entity));
+    }
+    pages.line(PdfStyle::Body, &tr!("exhibit-contact-totals",
+        total = calls.len(), incoming = incoming, outgoing = calls.len() - incoming,
+        minutes = locale.format_decimal(minutes, 1), timezone = analytics.timezone.name()));
+    pages.line(PdfStyle::Body, "");
+
+    let header = [
+        tr!("exhibit-column-time"),
+        tr!("exhibit-column-direction"),
+        tr!("exhibit-column-target"),
+        tr!("exhibit-column-duration"),
+        tr!("exhibit-column-source"),
+    ];
+    let columns = [MARGIN, MARGIN + 130.0, MARGIN + 200.0, MARGIN + 300.0, MARGIN + 360.0];
+    let header_row = |pages: &mut PdfPages| {
+        let cells: Vec<(f32, &str)> = columns.iter().copied().zip(header.iter().map(String::as_str)).collect();
+        pages.row(PdfStyle::Heading, &cells);
+    };
+    header_row(&mut pages);
+    let (_, _, leading) = PdfStyle::Body.font();
+    for record in calls {
+        // Continuation pages repeat the column headings
+        if !pages.fits(leading) {
+            pages.new_page();
+            header_row(&mut pages);
+        }
+        let time = locale.format_date_time(&record.start_time.with_timezone(&analytics.timezone));
+        let seconds = record.length_of_call;
+        let duration = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+        let source: String = record.source_file.chars().take(SOURCE_CHARS).collect();
+        let target = settings.format_number(&record.target_number);
+        pages.row(PdfStyle::Body, &[
+            (columns[0], time.as_str()),
+            (columns[1], record.message_direction.as_str()),
+            (columns[2], target.as_str()),
+            (columns[3], duration.as_str()),
+            (columns[4], source.as_str()),
+        ]);
+    }
+    Exhibit { description, pages: pages.finish() }
+}
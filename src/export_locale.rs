@@ -0,0 +1,172 @@
+use crate::tr;
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// Order of the day, month, and year in exported dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateOrder {
+    /// `2024-03-14`, which sorts as text.
+    YearMonthDay,
+    /// `03/14/2024`, as in the US.
+    MonthDayYear,
+    /// `14/03/2024`, as in most other countries.
+    DayMonthYear,
+}
+
+impl DateOrder {
+    pub const ALL: [DateOrder; 3] = [DateOrder::YearMonthDay, DateOrder::MonthDayYear, DateOrder::DayMonthYear];
+
+    pub fn label(&self) -> String {
+        match self {
+            DateOrder::YearMonthDay => tr!("date-order-ymd"),
+            DateOrder::MonthDayYear => tr!("date-order-mdy"),
+            DateOrder::DayMonthYear => tr!("date-order-dmy"),
+        }
+    }
+}
+
+/// Character between the whole and fractional parts of exported decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimalSeparator {
+    Point,
+    Comma,
+}
+
+impl DecimalSeparator {
+    pub const ALL: [DecimalSeparator; 2] = [DecimalSeparator::Point, DecimalSeparator::Comma];
+
+    pub fn label(&self) -> String {
+        match self {
+            DecimalSeparator::Point => tr!("decimal-separator-point"),
+            DecimalSeparator::Comma => tr!("decimal-separator-comma"),
+        }
+    }
+
+    fn char(&self) -> char {
+        match self {
+            DecimalSeparator::Point => '.',
+            DecimalSeparator::Comma => ',',
+        }
+    }
+}
+
+/// Whether exported times run 00–23 or 12 AM–11 PM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockFormat {
+    TwentyFourHour,
+    TwelveHour,
+}
+
+impl ClockFormat {
+    pub const ALL: [ClockFormat; 2] = [ClockFormat::TwentyFourHour, ClockFormat::TwelveHour];
+
+    pub fn label(&self) -> String {
+        match self {
+            ClockFormat::TwentyFourHour => tr!("clock-24-hour"),
+            ClockFormat::TwelveHour => tr!("clock-12-hour"),
+        }
+    }
+}
+
+/// How dates, times, and decimals are written in Excel, CSV, and PDF output, for agencies
+/// whose standards differ from the language's conventions.
+///
+/// Numbers Excel stores as numbers are shown with the viewer's own decimal separator; the
+/// separator here applies to figures written out as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExportLocale {
+    pub date_order: DateOrder,
+    pub decimal_separator: DecimalSeparator,
+    pub clock: ClockFormat,
+}
+
+impl ExportLocale {
+    pub fn format_date(&self, date: &impl Datelike) -> String {
+        let (year, month, day) = (date.year(), date.month(), date.day());
+        match self.date_order {
+            DateOrder::YearMonthDay => format!("{:04}-{:02}-{:02}", year, month, day),
+            DateOrder::MonthDayYear => format!("{:02}/{:02}/{:04}", month, day, year),
+            DateOrder::DayMonthYear => format!("{:02}/{:02}/{:04}", day, month, year),
+        }
+    }
+
+    /// Day and month only, for chart labels.
+    pub fn format_day_month(&self, date: &impl Datelike) -> String {
+        match self.date_order {
+            DateOrder::YearMonthDay => format!("{:02}-{:02}", date.month(), date.day()),
+            DateOrder::MonthDayYear => format!("{:02}/{:02}", date.month(), date.day()),
+            DateOrder::DayMonthYear => format!("{:02}/{:02}", date.day(), date.month()),
+        }
+    }
+
+    /// A `YYYY-MM-DD` day key, as the analytics count calls by, in this locale's order.
+    /// Keys that do not parse are returned as they are.
+    pub fn format_day_key(&self, key: &str) -> String {
+        match NaiveDate::parse_from_str(key, "%Y-%m-%d") {
+            Ok(date) => self.format_date(&date),
+            Err(_) => key.to_string(),
+        }
+    }
+
+    pub fn format_time(&self, time: &impl Timelike) -> String {
+        match self.clock {
+            ClockFormat::TwentyFourHour => format!("{:02}:{:02}:{:02}", time.hour(), time.minute(), time.second()),
+            ClockFormat::TwelveHour => {
+                let (pm, hour) = time.hour12();
+                format!("{:02}:{:02}:{:02} {}", hour, time.minute(), time.second(), if pm { "PM" } else { "AM" })
+            }
+        }
+    }
+
+    /// The start of an hour of the day, e.g. `22:00` or `10 PM`.
+    pub fn format_hour(&self, hour: u32) -> String {
+        match self.clock {
+            ClockFormat::TwentyFourHour => format!("{:02}:00", hour),
+            ClockFormat::TwelveHour => {
+                let twelve = if hour.is_multiple_of(12) { 12 } else { hour % 12 };
+                format!("{} {}", twelve, if hour % 24 >= 12 { "PM" } else { "AM" })
+            }
+        }
+    }
+
+    /// Date and time in the timestamp's own zone.
+    pub fn format_date_time<Z: TimeZone>(&self, time: &DateTime<Z>) -> String {
+        format!("{} {}", self.format_date(time), self.format_time(time))
+    }
+
+    /// A figure with `places` decimals and this locale's separator.
+    pub fn format_decimal(&self, value: f64, places: usize) -> String {
+        let text = format!("{:.*}", places, value);
+        match self.decimal_separator {
+            DecimalSeparator::Point => text,
+            separator => text.replace('.', &separator.char().to_string()),
+        }
+    }
+
+    /// Excel number format for date-time cells.
+    pub fn excel_datetime_format(&self) -> &'static str {
+        match (self.date_order, self.clock) {
+            (DateOrder::YearMonthDay, ClockFormat::TwentyFourHour) => "yyyy-mm-dd hh:mm:ss",
+            (DateOrder::YearMonthDay, ClockFormat::TwelveHour) => "yyyy-mm-dd hh:mm:ss AM/PM",
+            (DateOrder::MonthDayYear, ClockFormat::TwentyFourHour) => "mm/dd/yyyy hh:mm:ss",
+            (DateOrder::MonthDayYear, ClockFormat::TwelveHour) => "mm/dd/yyyy hh:mm:ss AM/PM",
+            (DateOrder::DayMonthYear, ClockFormat::TwentyFourHour) => "dd/mm/yyyy hh:mm:ss",
+            (DateOrder::DayMonthYear, ClockFormat::TwelveHour) => "dd/mm/yyyy hh:mm:ss AM/PM",
+        }
+    }
+
+    /// Excel number format for the narrow, rotated time headers of the call durations sheet.
+    pub fn excel_short_datetime_format(&self) -> &'static str {
+        match (self.date_order, self.clock) {
+            (DateOrder::YearMonthDay, ClockFormat::TwentyFourHour) => "mm-dd hh:mm",
+            (DateOrder::YearMonthDay, ClockFormat::TwelveHour) => "mm-dd h:mm AM/PM",
+            (DateOrder::MonthDayYear, ClockFormat::TwentyFourHour) => "mm/dd hh:mm",
+            (DateOrder::MonthDayYear, ClockFormat::TwelveHour) => "mm/dd h:mm AM/PM",
+            (DateOrder::DayMonthYear, ClockFormat::TwentyFourHour) => "dd/mm hh:mm",
+            (DateOrder::DayMonthYear, ClockFormat::TwelveHour) => "dd/mm h:mm AM/PM",
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::annotations::Annotations;
+use crate::data_models::ProcessedCallRecord;
+use crate::keywords::matched_keywords;
+use crate::tr;
+use chrono::{NaiveDate, Timelike};
+use chrono_tz::Tz;
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Calls longer than this many seconds match the ">5 min" chip.
+pub const LONG_CALL_SECONDS: u32 = 5 * 60;
+
+/// Default night window (start hour inclusive, end hour exclusive).
+pub const DEFAULT_NIGHT_HOURS: (u32, u32) = (22, 5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuickFilter {
+    IncomingOnly,
+    OutgoingOnly,
+    LongCalls,
+    NightHours,
+    WatchlistHits,
+    Tagged,
+    /// Messages whose content mentions one of the case's keywords.
+    KeywordHits,
+}
+
+impl QuickFilter {
+    pub const ALL: [QuickFilter; 7] = [
+        QuickFilter::IncomingOnly,
+        QuickFilter::OutgoingOnly,
+        QuickFilter::LongCalls,
+        QuickFilter::NightHours,
+        QuickFilter::WatchlistHits,
+        QuickFilter::Tagged,
+        QuickFilter::KeywordHits,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            QuickFilter::IncomingOnly => tr!("chip-incoming-only"),
+            QuickFilter::OutgoingOnly => tr!("chip-outgoing-only"),
+            QuickFilter::LongCalls => tr!("chip-long-calls"),
+            QuickFilter::NightHours => tr!("chip-night-hours"),
+            QuickFilter::WatchlistHits => tr!("chip-watchlist-hits"),
+            QuickFilter::Tagged => tr!("chip-tagged"),
+            QuickFilter::KeywordHits => tr!("chip-keyword-hits"),
+        }
+    }
+
+    /// Chips that cannot be active at the same time as this one.
+    pub fn conflicts_with(&self) -> Option<QuickFilter> {
+        match self {
+            QuickFilter::IncomingOnly => Some(QuickFilter::OutgoingOnly),
+            QuickFilter::OutgoingOnly => Some(QuickFilter::IncomingOnly),
+            _ => None,
+        }
+    }
+}
+
+/// A day or an hour of the day picked by clicking a chart, in the filter context's timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartSelection {
+    Day(NaiveDate),
+    Hour(u32),
+}
+
+impl ChartSelection {
+    pub fn label(&self) -> String {
+        match self {
+            ChartSelection::Day(date) => tr!("chip-chart-day", date = date.format("%Y-%m-%d").to_string()),
+            ChartSelection::Hour(hour) => tr!("chip-chart-hour", hour = format!("{:02}:00", hour)),
+        }
+    }
+
+    fn matches(&self, record: &ProcessedCallRecord, timezone: Tz) -> bool {
+        let local = record.start_time.with_timezone(&timezone);
+        match self {
+            ChartSelection::Day(date) => local.date_naive() == *date,
+            ChartSelection::Hour(hour) => local.hour() == *hour,
+        }
+    }
+}
+
+/// Session state the chips are evaluated against.
+pub struct FilterContext<'a> {
+    pub annotations: &'a Annotations,
+    pub night_hours: (u32, u32),
+    /// Zone the date range, chart selection, and night-hours window are measured in.
+    pub timezone: Tz,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecordFilter {
+    pub text: String,
+    pub date_from: String,
+    pub date_to: String,
+    pub chips: HashSet<QuickFilter>,
+    pub chart_selection: Option<ChartSelection>,
+    /// Only records carrying this tag label.
+    pub tag: Option<String>,
+    /// Interpret `text` as a case-insensitive regex over every column.
+    pub regex_mode: bool,
+    regex: Option<Regex>,
+    regex_error: Option<String>,
+}
+
+impl RecordFilter {
+    pub fn is_active(&self) -> bool {
+        !self.text.trim().is_empty()
+            || self.parsed_date_from().is_some()
+            || self.parsed_date_to().is_some()
+            || !self.chips.is_empty()
+            || self.chart_selection.is_some()
+            || self.tag.is_some()
+    }
+
+    pub fn toggle_chip(&mut self, chip: QuickFilter) {
+        if !self.chips.remove(&chip) {
+            if let Some(other) = chip.conflicts_with() {
+                self.chips.remove(&other);
+            }
+            self.chips.insert(chip);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        let regex_mode = self.regex_mode;
+        *self = Self::default();
+        self.regex_mode = regex_mode;
+    }
+
+    /// Recompiles the search pattern; call after `text` or `regex_mode` changes.
+    pub fn refresh_regex(&mut self) {
+        self.regex = None;
+        self.regex_error = None;
+
+        let pattern = self.text.trim();
+        if !self.regex_mode || pattern.is_empty() {
+            return;
+        }
+
+        match RegexBuilder::new(pattern).case_insensitive(true).build() {
+            Ok(regex) => self.regex = Some(regex),
+            Err(e) => self.regex_error = Some(e.to_string()),
+        }
+    }
+
+    pub fn regex_error(&self) -> Option<&str> {
+        self.regex_error.as_deref()
+    }
+
+    pub fn matches(&self, record: &ProcessedCallRecord, ctx: &FilterContext) -> bool {
+        self.matches_text(record)
+            && self.matches_date_range(record, ctx)
+            && self.matches_chips(record, ctx)
+            && self.chart_selection.is_none_or(|selection| selection.matches(record, ctx.timezone))
+            && self.tag.as_ref().is_none_or(|tag| ctx.annotations.has_tag(&record.record_key(), tag))
+    }
+
+    /// If the filter text looks like a number fragment, returns just its digits.
+    pub fn number_fragment(&self) -> Option<String> {
+        if self.regex_mode {
+            return None;
+        }
+        number_fragment(&self.text)
+    }
+
+    fn matches_text(&self, record: &ProcessedCallRecord) -> bool {
+        let needle = self.text.trim().to_lowercase();
+        if needle.is_empty() {
+            return true;
+        }
+
+        if self.regex_mode {
+            // An invalid pattern leaves the table unfiltered while the user fixes it
+            return match &self.regex {
+                Some(regex) => Self::matches_regex(regex, record),
+                None => true,
+            };
+        }
+
+        if let Some(digits) = self.number_fragment() {
+            if !digit_match_ranges(&record.remote_number, &digits).is_empty()
+                || record.normalized_number.contains(&digits)
+            {
+                return true;
+            }
+        }
+
+        [
+            &record.message_direction,
+            &record.remote_number,
+            &record.normalized_number,
+            &record.target_number,
+            &record.date_time,
+            &record.source_file,
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&needle))
+            || record.message_content.as_ref().is_some_and(|content| content.to_lowercase().contains(&needle))
+    }
+
+    fn matches_regex(regex: &Regex, record: &ProcessedCallRecord) -> bool {
+        let duration = record.length_of_call.to_string();
+        [
+            record.message_direction.as_str(),
+            record.remote_number.as_str(),
+            record.normalized_number.as_str(),
+            record.target_number.as_str(),
+            record.date_time.as_str(),
+            record.date.as_str(),
+            record.time.as_str(),
+            record.day_of_week.as_str(),
+            record.source_file.as_str(),
+            duration.as_str(),
+            record.message_content.as_deref().unwrap_or_default(),
+        ]
+        .iter()
+        .any(|field| regex.is_match(field))
+    }
+
+    fn matches_date_range(&self, record: &ProcessedCallRecord, ctx: &FilterContext) -> bool {
+        let date = record.start_time.with_timezone(&ctx.timezone).date_naive();
+        if let Some(from) = self.parsed_date_from() {
+            if date < from {
+                return false;
+            }
+        }
+        if let Some(to) = self.parsed_date_to() {
+            if date > to {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_chips(&self, record: &ProcessedCallRecord, ctx: &FilterContext) -> bool {
+        self.chips.iter().all(|chip| match chip {
+            QuickFilter::IncomingOnly => record.message_direction.eq_ignore_ascii_case("incoming"),
+            QuickFilter::OutgoingOnly => !record.message_direction.eq_ignore_ascii_case("incoming"),
+            QuickFilter::LongCalls => record.length_of_call > LONG_CALL_SECONDS,
+            QuickFilter::NightHours => is_night_hour(record.start_time.with_timezone(&ctx.timezone).hour(), ctx.night_hours),
+            QuickFilter::WatchlistHits => ctx.annotations.watchlist.contains(&record.normalized_number),
+            QuickFilter::Tagged => ctx.annotations.tagged_records.contains(&record.record_key()),
+            QuickFilter::KeywordHits => !matched_keywords(record, &ctx.annotations.keywords).is_empty(),
+        })
+    }
+
+    fn parsed_date_from(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.date_from.trim(), "%Y-%m-%d").ok()
+    }
+
+    fn parsed_date_to(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.date_to.trim(), "%Y-%m-%d").ok()
+    }
+}
+
+/// Returns true if `hour` falls in the night window, which may wrap past midnight.
+pub fn is_night_hour(hour: u32, night_hours: (u32, u32)) -> bool {
+    let (start, end) = night_hours;
+    if start <= end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+/// Returns the digits of `text` if it consists only of digits and phone punctuation.
+pub fn number_fragment(text: &str) -> Option<String> {
+    let text = text.trim();
+    let is_number_like = text.chars().all(|c| c.is_ascii_digit() || "()-. +".contains(c));
+    let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+    if is_number_like && !digits.is_empty() {
+        Some(digits)
+    } else {
+        None
+    }
+}
+
+/// Finds `digits` within the digits of `haystack`, ignoring formatting characters
+/// between them, and returns the matching byte ranges of `haystack`.
+pub fn digit_match_ranges(haystack: &str, digits: &str) -> Vec<Range<usize>> {
+    if digits.is_empty() {
+        return Vec::new();
+    }
+
+    let positions: Vec<(usize, char)> = haystack.char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .collect();
+    let haystack_digits: String = positions.iter().map(|(_, c)| *c).collect();
+
+    let mut ranges = Vec::new();
+    let mut search_from = 0;
+    while let Some(offset) = haystack_digits[search_from..].find(digits) {
+        let first = search_from + offset;
+        let last = first + digits.len() - 1;
+        ranges.push(positions[first].0..positions[last].0 + 1);
+        search_from = last + 1;
+    }
+    ranges
+}
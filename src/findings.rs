@@ -0,0 +1,176 @@
+use crate::data_models::{DeviceChangeKind, ProcessedCallRecord};
+use crate::filters::is_night_hour;
+use crate::report::{direct_target_contacts, ReportInput};
+use crate::tr;
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use std::collections::BTreeSet;
+
+/// Most-frequent contacts that get a finding of their own.
+const FINDING_CONTACTS: usize = 5;
+
+/// Common contacts named in the findings.
+const FINDING_COMMON_CONTACTS: usize = 3;
+
+/// A contact's night share is mentioned from this percentage of its calls up.
+const NOTABLE_NIGHT_PCT: f64 = 25.0;
+
+/// Days between a contact's first or last call and the edge of the records before it counts
+/// as having begun or ceased within the period.
+const NOTABLE_EDGE_DAYS: i64 = 7;
+
+/// Days without any calls reported as a gap in activity.
+const NOTABLE_GAP_DAYS: i64 = 7;
+
+/// Plain-language findings drawn from the analytics, one sentence each, as a starting point
+/// for the analyst's narrative: the overall picture, night activity, gaps, calls between
+/// targets, common contacts, phone and SIM swaps, and a sentence per top contact such as "2565551234 had 41 calls
+/// with 5558675309 (38 outgoing, 3 incoming); 90% between 23:00 and 03:00; contact ceased
+/// after 2023-04-02." Nothing is interpreted beyond what the figures show.
+pub fn key_findings(input: &ReportInput) -> Vec<String> {
+    let ReportInput { analytics, records, settings, .. } = *input;
+    if analytics.total_calls == 0 {
+        return Vec::new();
+    }
+    let timezone = analytics.timezone;
+    let locale = settings.export_locale();
+    let day = |time: &DateTime<Utc>| locale.format_date(&time.with_timezone(&timezone));
+    let night_from = locale.format_hour(settings.night_hours.0);
+    let night_to = locale.format_hour(settings.night_hours.1);
+    let is_night = |record: &ProcessedCallRecord| is_night_hour(record.start_time.with_timezone(&timezone).hour(), settings.night_hours);
+    let analyzed: Vec<&ProcessedCallRecord> = records.iter()
+        .filter(|r| !analytics.excluded_targets.contains(&r.target_number))
+        .collect();
+    let mut findings = Vec::new();
+
+    findings.push(tr!("finding-overview",
+        calls = analytics.total_calls,
+        numbers = analytics.unique_numbers,
+        from = day(&analytics.date_range.0),
+        to = day(&analytics.date_range.1)));
+
+    let night = analyzed.iter().filter(|r| is_night(r)).count();
+    if !analyzed.is_empty() {
+        findings.push(tr!("finding-night-share",
+            pct = format!("{:.0}", percent(night, analyzed.len())),
+            from = night_from.as_str(),
+            to = night_to.as_str()));
+    }
+
+    if let Some((date, count)) = analytics.calls_by_day.iter().max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0))) {
+        findings.push(tr!("finding-busiest-day", date = locale.format_day_key(date), count = *count));
+    }
+
+    let days: BTreeSet<NaiveDate> = analytics.calls_by_day.keys()
+        .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .collect();
+    for (before, after) in days.iter().zip(days.iter().skip(1)) {
+        let silent = (*after - *before).num_days() - 1;
+        if silent >= NOTABLE_GAP_DAYS {
+            findings.push(tr!("finding-gap",
+                days = silent,
+                from = locale.format_date(before),
+                to = locale.format_date(after)));
+        }
+    }
+
+    for ((a, b), times) in direct_target_contacts(records) {
+        let first = times.iter().next().map(&day).unwrap_or_default();
+        let last = times.iter().next_back().map(&day).unwrap_or_default();
+        findings.push(tr!("finding-direct-contact",
+            a = settings.format_number(&a), b = settings.format_number(&b),
+            count = times.len(), first = first, last = last));
+    }
+
+    for rate in analytics.answer_rates.iter().filter(|rate| rate.stopped_answering()) {
+        findings.push(tr!("finding-stopped-answering",
+            number = contact_label(input, &rate.number),
+            count = rate.trailing_unanswered,
+            date = rate.last_answered.as_ref().map(&day).unwrap_or_default()));
+    }
+
+    for contact in analytics.common_contacts.iter().take(FINDING_COMMON_CONTACTS) {
+        findings.push(tr!("finding-common-contact",
+            number = contact_label(input, &contact.number),
+            count = contact.count));
+    }
+
+    for change in &analytics.device_changes {
+        let span_label = |value: &str| match change.change {
+            DeviceChangeKind::NumberOnDevices => value.to_string(),
+            DeviceChangeKind::DeviceOnNumbers => settings.format_number(value),
+        };
+        let spans = change.spans.iter()
+            .map(|span| tr!("finding-device-span",
+                value = span_label(&span.value),
+                from = day(&span.first),
+                to = day(&span.last),
+                count = span.calls))
+            .collect::<Vec<_>>()
+            .join(", ");
+        findings.push(match change.change {
+            DeviceChangeKind::NumberOnDevices => tr!("finding-number-on-devices",
+                number = settings.format_number(&change.subject),
+                kind = change.id_kind.label(),
+                count = change.spans.len(),
+                spans = spans),
+            DeviceChangeKind::DeviceOnNumbers => tr!("finding-device-on-numbers",
+                device = change.subject.as_str(),
+                kind = change.id_kind.label(),
+                count = change.spans.len(),
+                spans = spans),
+        });
+    }
+
+    for (number, count) in analytics.most_frequent_numbers.iter().take(FINDING_CONTACTS) {
+        let calls: Vec<&ProcessedCallRecord> = analyzed.iter().copied().filter(|r| &r.normalized_number == number).collect();
+        let (Some(first), Some(last)) = (calls.iter().map(|r| r.start_time).min(), calls.iter().map(|r| r.start_time).max()) else {
+            continue;
+        };
+        let incoming = calls.iter().filter(|r| r.message_direction.eq_ignore_ascii_case("incoming")).count();
+        let targets: BTreeSet<String> = calls.iter().map(|r| settings.format_number(&r.target_number)).collect();
+        let mut clauses = vec![tr!("finding-contact",
+            targets = targets.into_iter().collect::<Vec<_>>().join(", "),
+            number = contact_label(input, number),
+            count = *count,
+            outgoing = calls.len() - incoming,
+            incoming = incoming)];
+
+        let night_pct = percent(calls.iter().filter(|r| is_night(r)).count(), calls.len());
+        if night_pct >= NOTABLE_NIGHT_PCT {
+            clauses.push(tr!("finding-contact-night",
+                pct = format!("{:.0}", night_pct),
+                from = night_from.as_str(),
+                to = night_to.as_str()));
+        }
+        if (first - analytics.date_range.0).num_days() >= NOTABLE_EDGE_DAYS {
+            clauses.push(tr!("finding-contact-began", date = day(&first)));
+        }
+        if (analytics.date_range.1 - last).num_days() >= NOTABLE_EDGE_DAYS {
+            clauses.push(tr!("finding-contact-ceased", date = day(&last)));
+        }
+        findings.push(format!("{}.", clauses.join("; ")));
+    }
+    findings
+}
+
+/// The findings as a bulleted block of text, for pasting into the narrative template.
+pub fn key_findings_text(input: &ReportInput) -> String {
+    key_findings(input).into_iter()
+        .map(|finding| format!("• {}\n", finding))
+        .collect()
+}
+
+/// A number with the alias or subscriber name it is known by, if any.
+fn contact_label(input: &ReportInput, number: &str) -> String {
+    let name = input.annotations.alias(number)
+        .or_else(|| input.annotations.subscribers.lookup(number).map(|s| s.name.as_str()));
+    let formatted = input.settings.format_number(number);
+    match name {
+        Some(name) => tr!("finding-named-number", number = formatted, name = name),
+        None => formatted,
+    }
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 * 100.0 / whole as f64 }
+}
@@ -0,0 +1,314 @@
+use crate::att_parser::AttParser;
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::csv_parser::{CsvMapping, SheetMapping};
+use crate::delimited_parser::{ColumnMap, DelimitedParser, MappingProfile};
+use crate::json_parser::JsonParser;
+use crate::pdf_parser::PdfParser;
+use crate::pipeline::DuplicatePolicy;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::spreadsheet_parser::WorkbookSample;
+use crate::subscriber_parser::SubscriberParser;
+use crate::preview::{ProductionFormat, ProductionPreview};
+use crate::tmobile_parser::TMobileParser;
+use crate::tr;
+use crate::verizon_parser::VerizonParser;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a file to tell which parser it needs.
+pub const DETECTION_SAMPLE_BYTES: u64 = 256 * 1024;
+
+/// Data rows of a delimited sample that are test-parsed.
+const SAMPLE_ROWS: usize = 50;
+
+/// File extensions offered to the parsers, or for ZIP archives unpacked for them; anything
+/// else is refused when dropped.
+pub const PRODUCTION_EXTENSIONS: [&str; 9] = ["xml", "json", "csv", "tsv", "txt", "pdf", "xlsx", "xls", "zip"];
+
+/// Extensions of the Excel workbooks read by [`ParserKind::Spreadsheet`].
+const SPREADSHEET_EXTENSIONS: [&str; 2] = ["xlsx", "xls"];
+
+/// A parser a production can be read with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParserKind {
+    /// LDS-101 XML, with or without the eSubpoena `<dataProduct>` wrapper.
+    Lds101Xml,
+    /// LDS-101 delivered as JSON, with the same fields as the XML.
+    Json,
+    /// LDS-102 subscriber records, delivered beside a production rather than call records.
+    Lds102Xml,
+    /// Delimited text, with the columns mapped by a profile.
+    Delimited(MappingProfile),
+    /// AT&T's call detail return, with its case details above the columns.
+    Att,
+    /// Verizon's subpoena compliance report, with voice and SMS in sections of their own.
+    Verizon,
+    /// T-Mobile's call detail, bare or in its XML envelope, with epoch times and switch codes.
+    TMobile,
+    /// A PDF return, its rows read from the text with the configured patterns.
+    Pdf,
+    /// Delimited text, with the columns mapped by hand. Never detected, only chosen.
+    Csv(CsvMapping),
+    /// One worksheet of an Excel workbook, from its header row down, with the columns mapped
+    /// as for delimited text.
+    Spreadsheet(SheetMapping),
+}
+
+impl ParserKind {
+    /// Every parser that needs no columns mapped, in the order they are offered for
+    /// overriding the detection.
+    pub fn all() -> Vec<ParserKind> {
+        [ParserKind::Lds101Xml, ParserKind::Json, ParserKind::Lds102Xml].into_iter()
+            .chain(MappingProfile::ALL.into_iter().map(ParserKind::Delimited))
+            .chain([ParserKind::Att, ParserKind::Verizon, ParserKind::TMobile, ParserKind::Pdf])
+            .collect()
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ParserKind::Lds101Xml => tr!("parser-lds101-xml"),
+            ParserKind::Json => tr!("parser-json"),
+            ParserKind::Lds102Xml => tr!("parser-lds102-xml"),
+            ParserKind::Delimited(profile) => tr!("parser-delimited", profile = profile.label()),
+            ParserKind::Att => tr!("parser-att"),
+            ParserKind::Verizon => tr!("parser-verizon"),
+            ParserKind::TMobile => tr!("parser-tmobile"),
+            ParserKind::Pdf => tr!("parser-pdf"),
+            ParserKind::Csv(_) => tr!("parser-csv-mapped"),
+            ParserKind::Spreadsheet(_) => tr!("parser-spreadsheet"),
+        }
+    }
+}
+
+/// How sure detection is that it picked the right parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DetectionConfidence {
+    /// Nothing in the sample matched a parser, or what matched did not read.
+    Low,
+    /// The parser fits, but the sample leaves a choice open, e.g. whether 03/04 is March or April.
+    Medium,
+    /// The layout is unmistakable and the sampled records read.
+    High,
+}
+
+impl DetectionConfidence {
+    pub fn label(&self) -> String {
+        match self {
+            DetectionConfidence::Low => tr!("confidence-low"),
+            DetectionConfidence::Medium => tr!("confidence-medium"),
+            DetectionConfidence::High => tr!("confidence-high"),
+        }
+    }
+}
+
+/// Which parser the start of a file calls for, and how sure that is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatDetection {
+    /// None when no parser fits.
+    pub parser: Option<ParserKind>,
+    pub confidence: DetectionConfidence,
+}
+
+impl FormatDetection {
+    /// Reads the first [`DETECTION_SAMPLE_BYTES`] of the file, or all of a workbook, which
+    /// cannot be read in part.
+    pub fn detect_file(path: &Path) -> Result<Self> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if is_spreadsheet_file(&path.to_string_lossy()) {
+            return Ok(WorkbookSample::read_file(path)?.detection());
+        }
+        let mut sample = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(DETECTION_SAMPLE_BYTES).read_to_end(&mut sample))
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        Ok(Self::detect(&sample))
+    }
+
+    /// Like [`FormatDetection::detect`] for the whole of a file held in memory, with its name
+    /// so a workbook is known.
+    pub fn detect_named(name: &str, bytes: &[u8]) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        if is_spreadsheet_file(name) {
+            return WorkbookSample::from_bytes(name, bytes)
+                .map_or(Self { parser: None, confidence: DetectionConfidence::Low }, |sample| sample.detection());
+        }
+        Self::detect(&bytes[..bytes.len().min(DETECTION_SAMPLE_BYTES as usize)])
+    }
+
+    /// PDFs go to the PDF parser at medium confidence, since how well their rows read is only
+    /// known once the whole file's text is extracted. A JSON object with `results` goes to
+    /// the JSON parser, confidently when the sample holds the whole file and its records read.
+    /// T-Mobile call detail is known by its
+    /// switch code column, even inside its XML envelope, and goes to its own parser. XML with
+    /// subscriber blocks and no results goes to the LDS-102 parser. Other XML
+    /// goes to the LDS-101 parser, confidently once records are found. AT&T and Verizon
+    /// returns are known by their layout and go to their own parsers. Other delimited text
+    /// goes to the mapping profile whose columns it has, preferring the more specific PenLink
+    /// layout, and is trusted once the sampled rows read; for the generic columns, slash dates
+    /// decide between month-first and day-first, and dates that could be either leave the
+    /// confidence at medium.
+    pub fn detect(sample: &[u8]) -> Self {
+        let text = sample.strip_prefix(b"\xEF\xBB\xBF".as_slice()).unwrap_or(sample);
+        if PdfParser::recognizes(text) {
+            return Self { parser: Some(ParserKind::Pdf), confidence: DetectionConfidence::Medium };
+        }
+        if JsonParser::recognizes(text) {
+            return Self::detect_json(text);
+        }
+        if TMobileParser::recognizes(text) {
+            let parsed = TMobileParser::parse_bytes(text, "", DuplicatePolicy::Keep);
+            return Self::detect_carrier(ParserKind::TMobile, parsed);
+        }
+        if SubscriberParser::recognizes(text) {
+            return Self::detect_subscribers(text);
+        }
+        if text.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'<') {
+            return Self::detect_xml(text);
+        }
+        if AttParser::recognizes(text) {
+            let parsed = AttParser::parse_bytes(text, "", chrono_tz::Tz::UTC, DuplicatePolicy::Keep);
+            return Self::detect_carrier(ParserKind::Att, parsed);
+        }
+        if VerizonParser::recognizes(text) {
+            let parsed = VerizonParser::parse_bytes(text, "", chrono_tz::Tz::UTC, DuplicatePolicy::Keep);
+            return Self::detect_carrier(ParserKind::Verizon, parsed);
+        }
+        Self::detect_delimited(text)
+    }
+
+    fn detect_xml(sample: &[u8]) -> Self {
+        let confidence = match ProductionPreview::from_sample("", sample, u64::MAX) {
+            Ok(preview) if preview.format != ProductionFormat::Unrecognized => DetectionConfidence::High,
+            _ => DetectionConfidence::Low,
+        };
+        Self { parser: Some(ParserKind::Lds101Xml), confidence }
+    }
+
+    /// Subscriber files are small, so the sample is usually the whole file.
+    fn detect_subscribers(sample: &[u8]) -> Self {
+        let confidence = match SubscriberParser::read_subscribers(sample) {
+            Ok(subscribers) if !subscribers.is_empty() => DetectionConfidence::High,
+            _ => DetectionConfidence::Medium,
+        };
+        Self { parser: Some(ParserKind::Lds102Xml), confidence }
+    }
+
+    /// A sample of a large file is cut off mid-document and does not parse, so it only
+    /// narrows the choice.
+    fn detect_json(sample: &[u8]) -> Self {
+        let confidence = match JsonParser::parse_bytes(sample, "", DuplicatePolicy::Keep) {
+            Ok((records, _)) if !records.is_empty() => DetectionConfidence::High,
+            _ => DetectionConfidence::Medium,
+        };
+        Self { parser: Some(ParserKind::Json), confidence }
+    }
+
+    /// A carrier's layout is trusted once the sampled rows read; the last row of a sample may
+    /// be cut off, so a sample with one unreadable record still counts.
+    fn detect_carrier(parser: ParserKind, parsed: Result<(Vec<ProcessedCallRecord>, ParseReport)>) -> Self {
+        let confidence = match parsed {
+            Ok((records, report)) if !records.is_empty() && report.skipped_count <= 1 => DetectionConfidence::High,
+            Ok((records, _)) if !records.is_empty() => DetectionConfidence::Medium,
+            _ => DetectionConfidence::Low,
+        };
+        Self { parser: Some(parser), confidence }
+    }
+
+    pub(crate) fn detect_delimited(sample: &[u8]) -> Self {
+        let unrecognized = Self { parser: None, confidence: DetectionConfidence::Low };
+        let mut reader = DelimitedParser::reader(sample);
+        let Ok(headers) = reader.headers().cloned() else {
+            return unrecognized;
+        };
+        // The last row of a sample may be cut off, so it is never judged
+        let mut rows: Vec<csv::StringRecord> = reader.records().take(SAMPLE_ROWS + 1).filter_map(|row| row.ok()).collect();
+        if rows.len() > SAMPLE_ROWS {
+            rows.pop();
+        }
+
+        let profile = if ColumnMap::resolve(&headers, MappingProfile::PenLink.columns()).is_complete() {
+            MappingProfile::PenLink
+        } else if ColumnMap::resolve(&headers, MappingProfile::Generic.columns()).is_complete() {
+            MappingProfile::Generic
+        } else {
+            return unrecognized;
+        };
+        let columns = ColumnMap::resolve(&headers, profile.columns());
+
+        let (profile, order_known) = match profile {
+            MappingProfile::PenLink => (profile, true),
+            _ => match date_order(rows.iter().map(|row| columns.start_text(row))) {
+                Some(true) => (MappingProfile::GenericDayFirst, true),
+                Some(false) => (MappingProfile::Generic, true),
+                None => (MappingProfile::Generic, rows.iter().all(|row| !columns.start_text(row).contains('/'))),
+            },
+        };
+
+        let timezone = chrono_tz::Tz::UTC;
+        let reads = !rows.is_empty() && rows.iter().all(|row| {
+            let (target, call) = DelimitedParser::call_record(row, &columns, profile.day_first(), timezone);
+            crate::data_models::ProcessedCallRecord::from_call_record(&call, &target, "").is_ok()
+        });
+        let confidence = match (reads, order_known) {
+            (true, true) => DetectionConfidence::High,
+            (true, false) => DetectionConfidence::Medium,
+            (false, _) => DetectionConfidence::Low,
+        };
+        Self { parser: Some(ParserKind::Delimited(profile)), confidence }
+    }
+}
+
+/// How a production was read, kept with its parse report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadFormat {
+    pub parser: ParserKind,
+    /// How sure detection was; none when the parser was chosen by hand.
+    pub confidence: Option<DetectionConfidence>,
+    pub duplicates: DuplicatePolicy,
+}
+
+impl ReadFormat {
+    pub fn overridden(&self) -> bool {
+        self.confidence.is_none()
+    }
+}
+
+/// Whether the file has one of the extensions the parsers take.
+pub fn is_production_file(name: &str) -> bool {
+    PRODUCTION_EXTENSIONS.iter().any(|ext| has_extension(name, ext))
+}
+
+/// Whether the file is a ZIP archive of productions rather than a production itself.
+pub fn is_archive_file(name: &str) -> bool {
+    has_extension(name, "zip")
+}
+
+/// Whether the file is an Excel workbook, whose sheet and columns are picked before it is read.
+pub fn is_spreadsheet_file(name: &str) -> bool {
+    SPREADSHEET_EXTENSIONS.iter().any(|ext| has_extension(name, ext))
+}
+
+fn has_extension(name: &str, ext: &str) -> bool {
+    name.rsplit_once('.').is_some_and(|(_, found)| found.eq_ignore_ascii_case(ext))
+}
+
+/// Whether slash dates are day-first (`Some(true)`), month-first, or cannot be told apart,
+/// from the first value over 12 in either position.
+fn date_order(values: impl Iterator<Item = String>) -> Option<bool> {
+    for value in values {
+        let date = value.split_whitespace().next().unwrap_or("");
+        let mut parts = date.split('/').map(|part| part.parse::<u32>().ok());
+        if let (Some(Some(first)), Some(Some(second))) = (parts.next(), parts.next()) {
+            if first > 12 {
+                return Some(true);
+            }
+            if second > 12 {
+                return Some(false);
+            }
+        }
+    }
+    None
+}
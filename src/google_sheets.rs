@@ -0,0 +1,225 @@
+use crate::annotations::Annotations;
+use crate::cnam::CallerNames;
+use crate::csv_exporter::{call_record_headers, call_record_row};
+use crate::data_models::{Analytics, ProcessedCallRecord};
+use crate::report::Report;
+use crate::settings::AppSettings;
+use crate::tr;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const SHEETS_API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+/// Lifetime of the signed assertion exchanged for an access token; Google allows at most an hour.
+const ASSERTION_LIFETIME_SECS: i64 = 3600;
+
+/// The fields of a service-account key file (the JSON downloaded from the Cloud console)
+/// needed to sign in.
+#[derive(Debug, Clone, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+/// Pushes the records, analytics, and summary report to a shared Google Sheet with the Sheets
+/// API, signing in as a service account. The sheet must be shared with the service account's
+/// email address. Each push replaces the tabs it writes and leaves any others alone, so
+/// collaborators' own tabs survive.
+pub struct GoogleSheetsExporter {
+    spreadsheet_id: String,
+    access_token: String,
+    agent: ureq::Agent,
+}
+
+impl GoogleSheetsExporter {
+    /// Signs in with the service-account key at `credentials_path`.
+    pub fn connect(credentials_path: &Path, spreadsheet_id: &str) -> Result<Self> {
+        let content = fs::read_to_string(credentials_path)
+            .with_context(|| format!("Failed to read service-account key: {:?}", credentials_path))?;
+        let key: ServiceAccountKey = serde_json::from_str(&content)
+            .with_context(|| format!("Not a service-account key file: {:?}", credentials_path))?;
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            iss: &key.client_email,
+            scope: SHEETS_SCOPE,
+            aud: &key.token_uri,
+            iat: now,
+            exp: now + ASSERTION_LIFETIME_SECS,
+        };
+        let signing_key = EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+            .context("The service-account key file has an unusable private key")?;
+        let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &signing_key)
+            .context("Failed to sign the service-account assertion")?;
+        let response: Value = agent.post(&key.token_uri)
+            .send_form(&[("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"), ("assertion", &assertion)])
+            .map_err(api_error)
+            .context("Google sign-in failed")?
+            .into_json()
+            .context("Failed to read Google sign-in response")?;
+        let Some(access_token) = response.get("access_token").and_then(Value::as_str) else {
+            bail!("Google sign-in returned no access token");
+        };
+
+        info!("Signed in to Google Sheets as {}", key.client_email);
+        Ok(Self { spreadsheet_id: spreadsheet_id.to_string(), access_token: access_token.to_string(), agent })
+    }
+
+    /// Writes the Call Records, Analytics, and Summary Report tabs, creating any that are
+    /// missing. Returns the sheet's URL.
+    pub fn push(
+        &self,
+        records: &[ProcessedCallRecord],
+        analytics: &Analytics,
+        report: &Report,
+        annotations: &Annotations,
+        caller_names: &CallerNames,
+        settings: &AppSettings,
+    ) -> Result<String> {
+        let tabs = [
+            (tr!("sheet-call-records"), call_record_rows(records, annotations, caller_names, settings)),
+            (tr!("sheet-analytics"), analytics_rows(analytics, settings)),
+            (tr!("sheet-summary-report"), report_rows(report)),
+        ];
+        let existing = self.sheet_titles()?;
+        let missing: Vec<Value> = tabs.iter()
+            .filter(|(title, _)| !existing.contains(title))
+            .map(|(title, _)| json!({ "addSheet": { "properties": { "title": title } } }))
+            .collect();
+        if !missing.is_empty() {
+            self.post(&format!("{}:batchUpdate", self.spreadsheet_url()), &json!({ "requests": missing }))?;
+        }
+
+        let ranges: Vec<String> = tabs.iter().map(|(title, _)| quote_sheet(title)).collect();
+        self.post(&format!("{}/values:batchClear", self.spreadsheet_url()), &json!({ "ranges": ranges }))?;
+        let data: Vec<Value> = tabs.iter()
+            .map(|(title, rows)| json!({ "range": format!("{}!A1", quote_sheet(title)), "values": rows }))
+            .collect();
+        // RAW keeps numbers such as "2565550100" and dates as written instead of reinterpreting them
+        self.post(&format!("{}/values:batchUpdate", self.spreadsheet_url()), &json!({ "valueInputOption": "RAW", "data": data }))?;
+
+        info!("Pushed {} records to Google Sheet {}", records.len(), self.spreadsheet_id);
+        Ok(format!("https://docs.google.com/spreadsheets/d/{}/edit", self.spreadsheet_id))
+    }
+
+    fn spreadsheet_url(&self) -> String {
+        format!("{}/{}", SHEETS_API, self.spreadsheet_id)
+    }
+
+    fn sheet_titles(&self) -> Result<Vec<String>> {
+        let response: Value = self.agent.get(&self.spreadsheet_url())
+            .query("fields", "sheets.properties.title")
+            .set("Authorization", &format!("Bearer {}", self.access_token))
+            .call()
+            .map_err(api_error)
+            .context("Failed to open the Google Sheet; is it shared with the service account?")?
+            .into_json()
+            .context("Failed to read the Google Sheet")?;
+        Ok(response["sheets"].as_array().into_iter().flatten()
+            .filter_map(|sheet| sheet["properties"]["title"].as_str())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn post(&self, url: &str, body: &Value) -> Result<()> {
+        self.agent.post(url)
+            .set("Authorization", &format!("Bearer {}", self.access_token))
+            .send_json(body)
+            .map_err(api_error)
+            .context("Google Sheets update failed")?;
+        Ok(())
+    }
+}
+
+/// Keeps the message Google sends with a failed request, which says what is wrong (sheet not
+/// shared, API not enabled, ...).
+fn api_error(error: ureq::Error) -> anyhow::Error {
+    match error {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            let message = serde_json::from_str::<Value>(&body).ok()
+                .and_then(|value| value["error"]["message"].as_str().map(str::to_string))
+                .unwrap_or(body);
+            anyhow::anyhow!("HTTP {}: {}", code, message)
+        }
+        other => other.into(),
+    }
+}
+
+/// A sheet name quoted for A1 notation, e.g. `'Call Records'`.
+fn quote_sheet(title: &str) -> String {
+    format!("'{}'", title.replace('\'', "''"))
+}
+
+fn call_record_rows(records: &[ProcessedCallRecord], annotations: &Annotations, caller_names: &CallerNames, settings: &AppSettings) -> Vec<Vec<Value>> {
+    let mut rows = vec![call_record_headers(settings.case_timezone).into_iter().map(Value::from).collect()];
+    rows.extend(records.iter().map(|record| call_record_row(record, annotations, caller_names, settings).into_iter().map(Value::from).collect()));
+    rows
+}
+
+/// The same tables as the Excel Analytics sheet, one after the other.
+fn analytics_rows(analytics: &Analytics, settings: &AppSettings) -> Vec<Vec<Value>> {
+    let mut rows: Vec<Vec<Value>> = vec![
+        vec![tr!("header-metric").into(), tr!("header-value").into()],
+        vec![tr!("metric-total-calls").into(), analytics.total_calls.into()],
+        vec![tr!("metric-incoming-calls").into(), analytics.incoming_calls.into()],
+        vec![tr!("metric-outgoing-calls").into(), analytics.outgoing_calls.into()],
+        vec![tr!("metric-unique-numbers").into(), analytics.unique_numbers.into()],
+        vec![tr!("metric-unknown-numbers").into(), analytics.unknown_number_calls.into()],
+        vec![tr!("metric-total-duration").into(), round2(analytics.total_duration_minutes).into()],
+        vec![tr!("metric-average-duration").into(), round2(analytics.average_call_duration).into()],
+        vec![],
+        vec![tr!("heading-most-frequent").into(), tr!("header-call-count").into()],
+    ];
+    rows.extend(analytics.most_frequent_numbers.iter()
+        .map(|(number, count)| vec![settings.format_number(number).into(), (*count).into()]));
+
+    rows.push(vec![]);
+    rows.push(vec![tr!("heading-calls-by-day", timezone = analytics.timezone.name()).into(), tr!("header-call-count").into()]);
+    let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
+    sorted_days.sort_by(|a, b| a.0.cmp(b.0));
+    rows.extend(sorted_days.into_iter().map(|(day, count)| vec![day.as_str().into(), (*count).into()]));
+
+    rows.push(vec![]);
+    rows.push(vec![tr!("heading-calls-by-hour", timezone = analytics.timezone.name()).into(), tr!("header-call-count").into()]);
+    for hour in 0..24 {
+        if let Some(count) = analytics.calls_by_hour.get(&hour) {
+            rows.push(vec![format!("{:02}:00", hour).into(), (*count).into()]);
+        }
+    }
+    rows
+}
+
+fn report_rows(report: &Report) -> Vec<Vec<Value>> {
+    let mut rows = vec![vec![Value::from(report.title.as_str())]];
+    for block in &report.blocks {
+        rows.push(vec![]);
+        rows.push(vec![block.heading.as_str().into()]);
+        rows.extend(block.lines.iter().map(|line| vec![line.as_str().into()]));
+    }
+    rows
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,177 @@
+//! UI and report text, looked up by message id from Fluent resources in `locales/`.
+//!
+//! Use the [`tr!`](crate::tr) macro rather than calling [`translate`] directly:
+//!
+//! ```
+//! use esubpoena_tolls_tool::tr;
+//!
+//! let heading = tr!("report-title");
+//! let line = tr!("report-total-calls", count = 42);
+//! ```
+
+use crate::export_locale::{ClockFormat, DateOrder, DecimalSeparator, ExportLocale};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentResource;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+pub use fluent_bundle::FluentArgs;
+
+/// Languages the UI and reports can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's name in that language, as shown in the picker.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// How exports write dates, times, and decimals unless the settings override it.
+    pub fn export_locale(&self) -> ExportLocale {
+        match self {
+            Language::English => ExportLocale {
+                date_order: DateOrder::YearMonthDay,
+                decimal_separator: DecimalSeparator::Point,
+                clock: ClockFormat::TwentyFourHour,
+            },
+            Language::Spanish => ExportLocale {
+                date_order: DateOrder::DayMonthYear,
+                decimal_separator: DecimalSeparator::Comma,
+                clock: ClockFormat::TwentyFourHour,
+            },
+        }
+    }
+
+    /// Excel number format for call durations unless the settings override it. `[h]` keeps
+    /// summed durations past 24 hours from wrapping.
+    pub fn excel_duration_format(&self) -> &'static str {
+        match self {
+            Language::English | Language::Spanish => "[h]:mm:ss",
+        }
+    }
+
+    fn locale(&self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Spanish => "es",
+        }
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::Spanish => include_str!("../locales/es.ftl"),
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Language::English as u8);
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+/// Switches the language used by subsequent [`tr!`](crate::tr) lookups.
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == Language::Spanish as u8 => Language::Spanish,
+        _ => Language::English,
+    }
+}
+
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    BUNDLES.get_or_init(|| {
+        Language::ALL.iter().map(|language| {
+            let locale = language.locale().parse().expect("hard-coded locale is valid");
+            let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+            // Isolation marks around placeables show up as stray characters in egui and Excel
+            bundle.set_use_isolating(false);
+            let resource = FluentResource::try_new(language.resource().to_string()).unwrap_or_else(|(resource, errors)| {
+                warn!("Errors in the {:?} translations: {:?}", language, errors);
+                resource
+            });
+            if let Err(errors) = bundle.add_resource(resource) {
+                warn!("Duplicate messages in the {:?} translations: {:?}", language, errors);
+            }
+            bundle
+        }).collect()
+    })
+}
+
+/// A count with the current language's digit grouping, e.g. `9,841` or `9.841`.
+pub fn format_count(count: usize) -> String {
+    let separator = match language() {
+        Language::English => ',',
+        Language::Spanish => '.',
+    };
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Formats message `id` in the current language, falling back to English and then to the id
+/// itself when a translation is missing.
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    let bundles = bundles();
+    for language in [language(), Language::English] {
+        let bundle = &bundles[language as usize];
+        if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                warn!("Errors formatting message {:?}: {:?}", id, errors);
+            }
+            return text.into_owned();
+        }
+    }
+    warn!("Missing translation for {:?}", id);
+    id.to_string()
+}
+
+/// Message `id` in every language, for recognizing localized text read back from an export.
+pub fn translations(id: &str) -> Vec<String> {
+    let bundles = bundles();
+    Language::ALL.iter()
+        .filter_map(|language| {
+            let bundle = &bundles[*language as usize];
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, None, &mut errors).into_owned())
+        })
+        .collect()
+}
+
+/// Looks up a localized message, optionally with named arguments:
+/// `tr!("msg-processed", count = records.len())`.
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
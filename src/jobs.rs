@@ -0,0 +1,243 @@
+use esubpoena_tolls_tool::tr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use std::time::Duration;
+use web_time::Instant;
+
+pub type JobId = u64;
+
+/// Progress has to have been measured for this long before the time left is estimated.
+const MIN_ETA_SAMPLE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Parse,
+    Analytics,
+    Export,
+    Script,
+    Compare,
+    Lookup,
+}
+
+impl JobKind {
+    pub fn label(&self) -> String {
+        match self {
+            JobKind::Parse => tr!("job-kind-parse"),
+            JobKind::Analytics => tr!("job-kind-analytics"),
+            JobKind::Export => tr!("job-kind-export"),
+            JobKind::Script => tr!("job-kind-script"),
+            JobKind::Compare => tr!("job-kind-compare"),
+            JobKind::Lookup => tr!("job-kind-lookup"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+/// Handed to a running job so it can report progress and notice cancellation.
+#[derive(Clone)]
+pub struct JobContext {
+    cancelled: Arc<AtomicBool>,
+    progress: Arc<Mutex<JobProgress>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct JobProgress {
+    fraction: Option<f32>,
+    message: String,
+    /// When the current run of fractional progress started, and at what fraction. The rate
+    /// since then estimates the time left, leaving out setup before progress was measurable.
+    measured_from: Option<(Instant, f32)>,
+}
+
+impl JobContext {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails with a "cancelled" error once the user has cancelled the job.
+    pub fn check_cancelled(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            anyhow::bail!("Cancelled");
+        }
+        Ok(())
+    }
+
+    /// `fraction` is `None` when the total amount of work is unknown.
+    pub fn set_progress(&self, fraction: Option<f32>, message: impl Into<String>) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress.fraction = fraction.map(|f| f.clamp(0.0, 1.0));
+            progress.message = message.into();
+            match progress.fraction {
+                Some(fraction) if progress.measured_from.is_none() => progress.measured_from = Some((Instant::now(), fraction)),
+                Some(_) => {}
+                None => progress.measured_from = None,
+            }
+        }
+    }
+}
+
+/// One background task as shown in the Jobs panel.
+pub struct Job {
+    pub id: JobId,
+    pub kind: JobKind,
+    pub label: String,
+    pub status: JobStatus,
+    pub started: Instant,
+    pub finished: Option<Instant>,
+    context: JobContext,
+}
+
+impl Job {
+    pub fn progress(&self) -> (Option<f32>, String) {
+        self.context.progress.lock()
+            .map(|p| (p.fraction, p.message.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Time left at the rate progress has been made so far, once there is enough of it to go on.
+    pub fn eta(&self) -> Option<Duration> {
+        let progress = self.context.progress.lock().ok()?;
+        let fraction = progress.fraction?;
+        let (since, start) = progress.measured_from?;
+        let measured = since.elapsed();
+        if measured < MIN_ETA_SAMPLE || fraction <= start {
+            return None;
+        }
+        Some(measured.mul_f32((1.0 - fraction) / (fraction - start)))
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.finished.unwrap_or_else(Instant::now) - self.started
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == JobStatus::Running
+    }
+}
+
+/// Completion event for a job that was not cancelled.
+pub enum JobEvent<T> {
+    Completed(T),
+    Failed { kind: JobKind, error: String },
+}
+
+/// Runs background jobs on worker threads and collects their results on the UI thread.
+///
+/// Every job runs its closure on its own thread (inline in the browser build) and reports back
+/// through a shared channel; [`JobManager::poll`] drains it once per frame and returns the
+/// completion events.
+pub struct JobManager<T> {
+    jobs: Vec<Job>,
+    next_id: JobId,
+    sender: mpsc::Sender<(JobId, anyhow::Result<T>)>,
+    receiver: mpsc::Receiver<(JobId, anyhow::Result<T>)>,
+}
+
+impl<T: Send + 'static> Default for JobManager<T> {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            jobs: Vec::new(),
+            next_id: 1,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl<T: Send + 'static> JobManager<T> {
+    pub fn spawn<F>(&mut self, kind: JobKind, label: impl Into<String>, work: F) -> JobId
+    where
+        F: FnOnce(&JobContext) -> anyhow::Result<T> + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let context = JobContext {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            progress: Arc::new(Mutex::new(JobProgress::default())),
+        };
+        self.jobs.push(Job {
+            id,
+            kind,
+            label: label.into(),
+            status: JobStatus::Running,
+            started: Instant::now(),
+            finished: None,
+            context: context.clone(),
+        });
+
+        let sender = self.sender.clone();
+        let run = move || {
+            let result = work(&context);
+            let _ = sender.send((id, result));
+        };
+        // The browser build has no threads, so jobs run to completion within the frame there
+        #[cfg(not(target_arch = "wasm32"))]
+        thread::spawn(run);
+        #[cfg(target_arch = "wasm32")]
+        run();
+
+        id
+    }
+
+    /// Requests cancellation. The job stops at its next cancellation check and its result is discarded.
+    pub fn cancel(&mut self, id: JobId) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == id && j.is_running()) {
+            job.context.cancelled.store(true, Ordering::Relaxed);
+            job.status = JobStatus::Cancelled;
+            job.finished = Some(Instant::now());
+        }
+    }
+
+    /// Collects finished jobs and updates their status. Results of cancelled jobs are dropped.
+    pub fn poll(&mut self) -> Vec<JobEvent<T>> {
+        let mut events = Vec::new();
+        while let Ok((id, result)) = self.receiver.try_recv() {
+            let Some(job) = self.jobs.iter_mut().find(|j| j.id == id) else {
+                continue;
+            };
+            if job.status == JobStatus::Cancelled {
+                continue;
+            }
+            job.finished = Some(Instant::now());
+            match result {
+                Ok(output) => {
+                    job.status = JobStatus::Completed;
+                    events.push(JobEvent::Completed(output));
+                }
+                Err(e) => {
+                    job.status = JobStatus::Failed(e.to_string());
+                    events.push(JobEvent::Failed { kind: job.kind, error: e.to_string() });
+                }
+            }
+        }
+        events
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn has_running(&self) -> bool {
+        self.jobs.iter().any(Job::is_running)
+    }
+
+    pub fn is_running(&self, kind: JobKind) -> bool {
+        self.jobs.iter().any(|j| j.kind == kind && j.is_running())
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(Job::is_running);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::data_models::{CallRecord, Lds101Results, ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{target_number, DelimitedParser};
+use crate::pipeline::DuplicatePolicy;
+use anyhow::{bail, Context, Result};
+use log::info;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Reads LDS-101 productions delivered as JSON rather than XML: the same fields under the
+/// same names, either bare, inside `xmlResult`, or inside `dataProduct.xmlResult`, with the
+/// records in a `results` array. A record that does not deserialize is reported as skipped
+/// rather than failing the file.
+pub struct JsonParser;
+
+impl JsonParser {
+    pub fn parse_file(path: &Path, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing JSON file: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, duplicates)
+    }
+
+    /// ```
+    /// use esubpoena_tolls_tool::json_parser::JsonParser;
+    /// use esubpoena_tolls_tool::DuplicatePolicy;
+    ///
+    /// let json = br#"{"xmlResult": {"targetType": "phone", "targetValue": "2565550100",
+    ///     "fromDate": "2024-01-01", "toDate": "2024-01-31", "results": [
+    ///     {"messageDirection": "incoming", "remoteNumber": "2565550123", "startTime": "2024-01-01T10:00:00Z",
+    ///      "endTime": "2024-01-01T10:05:00Z", "lengthOfCall": 300},
+    ///     {"remoteNumber": "2565550123"}]}}"#;
+    /// let (records, report) = JsonParser::parse_bytes(json, "tolls.json", DuplicatePolicy::Keep).unwrap();
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].target_number, "2565550100");
+    /// assert_eq!(report.skipped_count, 1);
+    /// ```
+    pub fn parse_bytes(bytes: &[u8], source_file: &str, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF".as_slice()).unwrap_or(bytes);
+        let mut document: Value = serde_json::from_slice(bytes)
+            .with_context(|| format!("{} is not valid JSON", source_file))?;
+        let production = production_mut(&mut document)
+            .with_context(|| format!("{} has no LDS-101 results", source_file))?;
+        let Some(Value::Array(results)) = production.get_mut("results").map(Value::take) else {
+            bail!("{} has no results array", source_file);
+        };
+        production["results"] = Value::Array(Vec::new());
+        let header: Lds101Results = serde_json::from_value(production.take())
+            .with_context(|| format!("Failed to read the production header of {}", source_file))?;
+
+        let target = target_number(&header.target_value);
+        let rows = results.into_iter().map(|result| {
+            serde_json::from_value::<CallRecord>(result)
+                .map(|call| (target.clone(), call))
+                .map_err(|e| e.to_string())
+        });
+        let (records, mut report) = DelimitedParser::collect_records(rows, source_file, duplicates);
+        report.declare(&header.header());
+        info!("Parsed {} JSON records for target {}", records.len(), target);
+        Ok((records, report))
+    }
+
+    /// Whether `sample` looks like a JSON production: an object with a `results` key
+    /// somewhere in it. A sample of a large file is cut off, so it is not parsed.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        let sample = sample.strip_prefix(b"\xEF\xBB\xBF".as_slice()).unwrap_or(sample);
+        sample.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+            && sample.windows(RESULTS_KEY.len()).any(|window| window == RESULTS_KEY)
+    }
+}
+
+const RESULTS_KEY: &[u8] = b"\"results\"";
+
+/// The object holding `results`, unwrapping `dataProduct` and `xmlResult` as the XML does.
+fn production_mut(document: &mut Value) -> Option<&mut Value> {
+    let mut production = document;
+    for wrapper in ["dataProduct", "xmlResult"] {
+        if production.get(wrapper).is_some_and(Value::is_object) {
+            production = production.get_mut(wrapper)?;
+        }
+    }
+    production.get("results").is_some().then_some(production)
+}
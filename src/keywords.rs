@@ -0,0 +1,134 @@
+use crate::data_models::ProcessedCallRecord;
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Splits the comma-separated keywords typed by the analyst, dropping blanks and repeats.
+pub fn parse_keywords(text: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in text.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        if !keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+            keywords.push(keyword.to_string());
+        }
+    }
+    keywords
+}
+
+/// Byte ranges of `text` where any keyword occurs, sorted and with overlaps merged, for
+/// highlighting. Matching ignores ASCII case, so offsets into the lowercased text are valid
+/// in the original.
+pub fn keyword_ranges(text: &str, keywords: &[String]) -> Vec<Range<usize>> {
+    let haystack = text.to_ascii_lowercase();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for keyword in keywords {
+        let needle = keyword.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        ranges.extend(haystack.match_indices(&needle).map(|(start, _)| start..start + needle.len()));
+    }
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Indices into `keywords` of those occurring in the record's message content.
+pub fn matched_keywords(record: &ProcessedCallRecord, keywords: &[String]) -> Vec<usize> {
+    let Some(content) = record.message_content.as_deref() else {
+        return Vec::new();
+    };
+    let haystack = content.to_ascii_lowercase();
+    keywords.iter().enumerate()
+        .filter(|(_, keyword)| !keyword.is_empty() && haystack.contains(&keyword.to_ascii_lowercase()))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Whether any record carries message content; most productions are call detail only.
+pub fn has_message_content(records: &[ProcessedCallRecord]) -> bool {
+    records.iter().any(|r| r.message_content.is_some())
+}
+
+/// A message mentioning one or more of the keywords.
+#[derive(Debug, Clone)]
+pub struct KeywordHit<'a> {
+    pub record: &'a ProcessedCallRecord,
+    /// Indices into [`KeywordHits::keywords`].
+    pub keywords: Vec<usize>,
+}
+
+/// Messages whose content mentions the case's keywords, with how often each keyword came
+/// up and when.
+#[derive(Debug, Clone, Default)]
+pub struct KeywordHits<'a> {
+    pub keywords: Vec<String>,
+    /// In chronological order.
+    pub hits: Vec<KeywordHit<'a>>,
+    /// Messages mentioning each keyword, in keyword order.
+    pub counts: Vec<usize>,
+    /// Every day from the first hit to the last, in the timezone searched with.
+    pub days: Vec<NaiveDate>,
+    /// Running totals of each keyword's messages per day, in keyword order.
+    pub cumulative: Vec<Vec<usize>>,
+}
+
+impl<'a> KeywordHits<'a> {
+    pub fn search(records: &'a [ProcessedCallRecord], keywords: &[String], timezone: Tz) -> Self {
+        let mut hits: Vec<KeywordHit> = records.iter()
+            .filter_map(|record| {
+                let matched = matched_keywords(record, keywords);
+                (!matched.is_empty()).then_some(KeywordHit { record, keywords: matched })
+            })
+            .collect();
+        hits.sort_by_key(|hit| hit.record.start_time);
+
+        let mut counts = vec![0; keywords.len()];
+        let mut per_day: HashMap<(usize, NaiveDate), usize> = HashMap::new();
+        for hit in &hits {
+            let day = hit.record.start_time.with_timezone(&timezone).date_naive();
+            for &keyword in &hit.keywords {
+                counts[keyword] += 1;
+                *per_day.entry((keyword, day)).or_insert(0) += 1;
+            }
+        }
+        let first = hits.first().map(|hit| hit.record.start_time.with_timezone(&timezone).date_naive());
+        let last = hits.last().map(|hit| hit.record.start_time.with_timezone(&timezone).date_naive());
+        let days: Vec<NaiveDate> = match (first, last) {
+            (Some(first), Some(last)) => (0..=(last - first).num_days()).map(|offset| first + Duration::days(offset)).collect(),
+            _ => Vec::new(),
+        };
+        let cumulative = (0..keywords.len())
+            .map(|keyword| {
+                days.iter()
+                    .scan(0, |sum, day| {
+                        *sum += per_day.get(&(keyword, *day)).copied().unwrap_or(0);
+                        Some(*sum)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            keywords: keywords.to_vec(),
+            hits,
+            counts,
+            days,
+            cumulative,
+        }
+    }
+
+    /// The keywords a hit matched, comma-separated.
+    pub fn matched_text(&self, hit: &KeywordHit) -> String {
+        hit.keywords.iter()
+            .map(|&i| self.keywords[i].as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -0,0 +1,111 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Header names, compared case-insensitively, for the columns of a known-number list.
+const NUMBER_HEADERS: &[&str] = &["number", "phone", "phone number", "telephone number", "tn"];
+const CATEGORY_HEADERS: &[&str] = &["category", "list", "type", "group"];
+
+/// Calls with numbers of one category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryCount {
+    pub category: String,
+    /// Matching contacts, busiest first, with their call counts.
+    pub numbers: Vec<(String, usize)>,
+    pub calls: usize,
+}
+
+/// Reference lists of numbers the agency already knows (informant lines, agency numbers,
+/// bondsmen, ...), each number tagged with the categories of the lists it is on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownNumbers {
+    categories: HashMap<String, BTreeSet<String>>,
+}
+
+impl KnownNumbers {
+    /// Reads a CSV with a number column and an optional category column. Rows without a
+    /// category get the file's name, so a list of agency numbers saved as `Agency.csv` needs
+    /// no category column at all.
+    pub fn import_list(&mut self, path: &Path) -> Result<usize> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open known-number list: {:?}", path))?;
+        let default_category = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        let count = self.read_list(file, &default_category)
+            .with_context(|| format!("Failed to read known-number list: {:?}", path))?;
+        info!("Loaded {} known numbers from {:?}", count, path);
+        Ok(count)
+    }
+
+    pub fn read_list<R: Read>(&mut self, reader: R, default_category: &str) -> Result<usize> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let find = |names: &[&str]| headers.iter().position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)));
+        let Some(number_col) = find(NUMBER_HEADERS) else {
+            bail!("No phone number column; expected a header such as \"Number\" or \"Phone\"");
+        };
+        let category_col = find(CATEGORY_HEADERS);
+
+        let mut count = 0;
+        for (index, row) in reader.records().enumerate() {
+            let row = row?;
+            let raw_number = row.get(number_col).unwrap_or_default();
+            let number = normalize_phone_number(raw_number);
+            if !number.is_valid() {
+                // Row 1 is the header
+                warn!("Skipping known-number row {}: {:?} is not a phone number", index + 2, raw_number);
+                continue;
+            }
+            let category = category_col.and_then(|col| row.get(col)).filter(|c| !c.is_empty()).unwrap_or(default_category);
+            self.categories.entry(number.into_string()).or_default().insert(category.to_string());
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Categories of the lists a normalized number is on, sorted.
+    pub fn categories(&self, normalized_number: &str) -> Option<&BTreeSet<String>> {
+        self.categories.get(normalized_number)
+    }
+
+    /// Categories joined for display, e.g. "Agency, Informant".
+    pub fn label(&self, normalized_number: &str) -> Option<String> {
+        self.categories(normalized_number)
+            .map(|categories| categories.iter().map(String::as_str).collect::<Vec<_>>().join(", "))
+    }
+
+    /// Calls per category over `records`, busiest category first. A number on several lists
+    /// counts toward each.
+    pub fn category_counts(&self, records: &[ProcessedCallRecord]) -> Vec<CategoryCount> {
+        let mut calls: HashMap<&str, usize> = HashMap::new();
+        for record in records.iter().filter(|r| self.categories.contains_key(&r.normalized_number)) {
+            *calls.entry(record.normalized_number.as_str()).or_insert(0) += 1;
+        }
+        let mut by_category: HashMap<&str, Vec<(String, usize)>> = HashMap::new();
+        for (number, count) in calls {
+            for category in &self.categories[number] {
+                by_category.entry(category.as_str()).or_default().push((number.to_string(), count));
+            }
+        }
+        let mut counts: Vec<CategoryCount> = by_category.into_iter()
+            .map(|(category, mut numbers)| {
+                numbers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                let calls = numbers.iter().map(|(_, calls)| calls).sum();
+                CategoryCount { category: category.to_string(), numbers, calls }
+            })
+            .collect();
+        counts.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.category.cmp(&b.category)));
+        counts
+    }
+
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+}
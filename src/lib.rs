@@ -0,0 +1,146 @@
+//! Parsing, analytics, and export for eSubpoena telecommunication toll records.
+//!
+//! The GUI binary is a thin front-end over this crate; other tools can use it directly:
+//!
+//! ```no_run
+//! use esubpoena_tolls_tool::{AnalyticsEngine, XmlParser};
+//! use std::path::Path;
+//!
+//! let records = XmlParser::parse_file(Path::new("production.xml"))?;
+//! let analytics = AnalyticsEngine::generate_analytics(&records);
+//! println!("{} calls with {} unique numbers", analytics.total_calls, analytics.unique_numbers);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! For productions too large to hold in memory, stream them through a [`Pipeline`]
+//! into a [`RecordSink`] such as [`AnalyticsAccumulator`]:
+//!
+//! ```no_run
+//! use esubpoena_tolls_tool::{AnalyticsAccumulator, Pipeline, PipelineConfig};
+//! use std::path::Path;
+//!
+//! let mut accumulator = AnalyticsAccumulator::default();
+//! Pipeline::run_file(Path::new("production.xml"), &mut accumulator, PipelineConfig::default())?;
+//! let analytics = accumulator.finish();
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod analytics;
+pub mod analytics_cache;
+pub mod annotations;
+pub mod area_codes;
+pub mod archive;
+pub mod att_parser;
+pub mod call_timeline;
+pub mod carriers;
+pub mod cellebrite_export;
+pub mod case_comparison;
+pub mod cnam;
+pub mod columns;
+pub mod contact_summary;
+pub mod contacts;
+pub mod csv_exporter;
+pub mod csv_parser;
+pub mod cumulative;
+pub mod data_models;
+pub mod data_quality;
+pub mod dedup;
+pub mod delimited_parser;
+pub mod entities;
+pub mod evidence;
+pub mod exhibits;
+pub mod export_locale;
+pub mod elastic_export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod excel_exporter;
+pub mod filters;
+pub mod findings;
+pub mod format_detection;
+#[cfg(all(feature = "google-sheets", not(target_arch = "wasm32")))]
+pub mod google_sheets;
+pub mod i18n;
+pub mod json_parser;
+pub mod keywords;
+pub mod known_numbers;
+pub mod location;
+pub mod network_export;
+pub mod number_warnings;
+mod pdf;
+pub mod pdf_parser;
+pub mod phone_region;
+pub mod penlink_export;
+pub mod pipeline;
+pub mod preview;
+pub mod production_diff;
+pub mod record_warnings;
+pub mod report;
+pub mod scripting;
+pub mod session;
+pub mod settings;
+pub mod signing;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod spreadsheet_parser;
+pub mod subscriber_parser;
+pub mod subscribers;
+pub mod tmobile_parser;
+pub mod verizon_parser;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch_folder;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod workbook_import;
+pub mod xml_parser;
+
+pub use analytics::{AnalyticsAccumulator, AnalyticsEngine, AnalyticsOptions};
+pub use analytics_cache::{AnalyticsCache, AnalyticsCacheKey};
+pub use annotations::{AnnotationHistory, Annotations, CaseDetails};
+pub use archive::{ArchiveEntry, ProductionArchive};
+pub use att_parser::AttParser;
+pub use call_timeline::CallTimeline;
+pub use carriers::{CarrierDirectory, CarrierInfo, CarrierSource};
+pub use cellebrite_export::CellebriteExporter;
+pub use case_comparison::{CaseComparison, SharedNumber};
+pub use cnam::CallerNames;
+pub use contact_summary::ContactSummary;
+pub use contacts::{ContactList, ContactMatches};
+pub use data_models::{
+    Analytics, AreaCodeActivity, CallDisposition, CallRecord, CellSite, CommonContact, ContactMessages, ContactSpan, Discrepancy,
+    ExchangeCluster, NormalizedNumber, NumberKind, ParseReport, ProcessedCallRecord, ProductionHeader, RecordType, SkippedRecord,
+    SourceFile, SubscriberInfo,
+};
+pub use csv_exporter::CsvExporter;
+pub use csv_parser::{CsvMapping, CsvParser, CsvSample, SheetMapping};
+pub use data_quality::{DataQualityReport, FileQuality};
+pub use delimited_parser::{ColumnMap, DelimitedParser, MappedField, MappingProfile};
+pub use elastic_export::ElasticExporter;
+pub use entities::{Entities, EntitySummary};
+pub use evidence::{EvidenceCheck, EvidenceStatus, EvidenceVerification};
+pub use exhibits::ExhibitPackage;
+pub use export_locale::{ClockFormat, DateOrder, DecimalSeparator, ExportLocale};
+pub use format_detection::{DetectionConfidence, FormatDetection, ParserKind, ReadFormat};
+#[cfg(not(target_arch = "wasm32"))]
+pub use excel_exporter::ExcelExporter;
+pub use i18n::Language;
+pub use json_parser::JsonParser;
+pub use keywords::{KeywordHit, KeywordHits};
+pub use known_numbers::{CategoryCount, KnownNumbers};
+pub use location::{Geofence, GeofenceArea};
+pub use network_export::{Network, NetworkExporter};
+pub use number_warnings::{NumberIssue, NumberWarning};
+pub use pdf_parser::{PdfExtraction, PdfParser, PdfPattern, PdfRow};
+pub use phone_region::PhoneRegion;
+pub use penlink_export::PenLinkExporter;
+pub use pipeline::{DuplicatePolicy, ParseProgress, Pipeline, PipelineConfig, RecordSink};
+pub use preview::{ProductionFormat, ProductionPreview};
+pub use production_diff::{ProductionDiff, RecordChange};
+pub use scripting::{ScriptOutput, ScriptRunner, UserScript};
+pub use settings::AppSettings;
+pub use subscriber_parser::{SubscriberMatch, SubscriberParser};
+pub use subscribers::{Subscriber, SubscriberDirectory};
+pub use signing::{ExportSignature, SigningKey};
+#[cfg(not(target_arch = "wasm32"))]
+pub use spreadsheet_parser::{SpreadsheetParser, WorkbookSample};
+pub use tmobile_parser::TMobileParser;
+pub use verizon_parser::VerizonParser;
+#[cfg(not(target_arch = "wasm32"))]
+pub use watch_folder::{AutoExporter, FolderWatcher};
+pub use xml_parser::XmlParser;
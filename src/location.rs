@@ -0,0 +1,450 @@
+//! Where the targets' phones were, going by the cell sites that served their calls.
+//!
+//! A cell site's coordinates are where the tower stands, not where the phone was: a phone
+//! is usually within a few kilometers of its serving tower, farther out in rural areas.
+
+use crate::data_models::{local_to_utc, CellSite, ProcessedCallRecord};
+use crate::tr;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Mean radius of the Earth, for great-circle distances.
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Hours without a call after which the next call on the same tower starts a new dwell
+/// period rather than extending the last one.
+pub const DWELL_BREAK_HOURS: i64 = 4;
+
+/// Speed between two calls' towers above which the phone could not have made the trip by
+/// road.
+pub const IMPLAUSIBLE_SPEED_KMH: f64 = 200.0;
+
+/// How far a phone can be from the tower serving it. Two towers up to twice this apart can
+/// serve a phone that has not moved.
+pub const TOWER_RANGE_KM: f64 = 10.0;
+
+/// Great-circle distance in kilometers between two `(latitude, longitude)` points in
+/// decimal degrees.
+///
+/// ```
+/// use esubpoena_tolls_tool::location::distance_km;
+///
+/// // Huntsville to Birmingham
+/// let distance = distance_km((34.7304, -86.5861), (33.5186, -86.8104));
+/// assert!((distance - 136.4).abs() < 1.0);
+/// ```
+pub fn distance_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// A time typed as `2024-03-01 18:30` or `2024-03-01` in `timezone`. A date alone is the
+/// start of the day, or its last second when `end_of_day` is set, so a window ending on a
+/// date takes in all of it.
+pub fn parse_local_time(text: &str, timezone: Tz, end_of_day: bool) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    let local = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+            let time = if end_of_day { NaiveTime::from_hms_opt(23, 59, 59)? } else { NaiveTime::MIN };
+            Some(date.and_time(time))
+        })?;
+    local_to_utc(&local, timezone)
+}
+
+/// The area a geofence query covers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GeofenceArea {
+    /// Within `radius_km` of a point, e.g. a scene.
+    Radius { latitude: f64, longitude: f64, radius_km: f64 },
+    /// Between two latitudes and two longitudes. A box whose `west` is east of its `east`
+    /// spans the 180th meridian.
+    BoundingBox { south: f64, west: f64, north: f64, east: f64 },
+}
+
+impl GeofenceArea {
+    pub fn contains(&self, (latitude, longitude): (f64, f64)) -> bool {
+        match *self {
+            GeofenceArea::Radius { latitude: center_latitude, longitude: center_longitude, radius_km } => {
+                distance_km((center_latitude, center_longitude), (latitude, longitude)) <= radius_km
+            }
+            GeofenceArea::BoundingBox { south, west, north, east } => {
+                let within_longitudes = if west <= east {
+                    (west..=east).contains(&longitude)
+                } else {
+                    longitude >= west || longitude <= east
+                };
+                (south..=north).contains(&latitude) && within_longitudes
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match *self {
+            GeofenceArea::Radius { latitude, longitude, radius_km } => tr!(
+                "geofence-radius-label",
+                radius = format!("{:.2}", radius_km),
+                center = format!("{:.5}, {:.5}", latitude, longitude)
+            ),
+            GeofenceArea::BoundingBox { south, west, north, east } => tr!(
+                "geofence-box-label",
+                southwest = format!("{:.5}, {:.5}", south, west),
+                northeast = format!("{:.5}, {:.5}", north, east)
+            ),
+        }
+    }
+}
+
+/// Calls whose serving tower stood inside an area during a window of time: the question of
+/// whether the phone was near a scene.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geofence {
+    pub area: GeofenceArea,
+    /// Start of the window; none means from the first record.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// End of the window, included; none means to the last record.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl Geofence {
+    /// Whether the call started within the window on a tower inside the area. Records
+    /// without tower coordinates never match.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::data_models::{CallRecord, ProcessedCallRecord};
+    /// use esubpoena_tolls_tool::location::{Geofence, GeofenceArea};
+    ///
+    /// let call = CallRecord {
+    ///     message_direction: "outgoing".into(),
+    ///     remote_number: "2565550100".into(),
+    ///     start_time: "2024-03-01T23:30:00Z".into(),
+    ///     end_time: "2024-03-01T23:32:00Z".into(),
+    ///     latitude: Some("34.7304".into()),
+    ///     longitude: Some("-86.5861".into()),
+    ///     ..CallRecord::default()
+    /// };
+    /// let record = ProcessedCallRecord::from_call_record(&call, "2565550111", "march.xml").unwrap();
+    /// let scene = GeofenceArea::Radius { latitude: 34.7400, longitude: -86.5900, radius_km: 2.0 };
+    /// assert!(Geofence { area: scene, from: None, to: None }.matches(&record));
+    ///
+    /// let earlier = "2024-03-01T20:00:00Z".parse().ok();
+    /// assert!(!Geofence { area: scene, from: None, to: earlier }.matches(&record));
+    /// ```
+    pub fn matches(&self, record: &ProcessedCallRecord) -> bool {
+        self.from.is_none_or(|from| record.start_time >= from)
+            && self.to.is_none_or(|to| record.start_time <= to)
+            && record.cell_site.as_ref()
+                .and_then(|site| site.coordinates())
+                .is_some_and(|coordinates| self.area.contains(coordinates))
+    }
+
+    /// The matching records, earliest first.
+    pub fn hits<'a>(&self, records: &'a [ProcessedCallRecord]) -> Vec<&'a ProcessedCallRecord> {
+        let mut hits: Vec<&ProcessedCallRecord> = records.iter().filter(|record| self.matches(record)).collect();
+        hits.sort_by_key(|record| record.start_time);
+        hits
+    }
+
+    /// How far the record's tower stood from the center of a radius query.
+    pub fn distance_km(&self, record: &ProcessedCallRecord) -> Option<f64> {
+        let GeofenceArea::Radius { latitude, longitude, .. } = self.area else {
+            return None;
+        };
+        let coordinates = record.cell_site.as_ref()?.coordinates()?;
+        Some(distance_km((latitude, longitude), coordinates))
+    }
+
+    /// The area and the window, with times in `timezone`.
+    pub fn label(&self, timezone: Tz) -> String {
+        let time = |time: Option<DateTime<Utc>>| {
+            time.map(|time| time.with_timezone(&timezone).format("%Y-%m-%d %H:%M").to_string())
+        };
+        match (time(self.from), time(self.to)) {
+            (None, None) => self.area.label(),
+            (from, to) => tr!(
+                "geofence-window-label",
+                area = self.area.label(),
+                from = from.unwrap_or_else(|| tr!("geofence-window-open")),
+                to = to.unwrap_or_else(|| tr!("geofence-window-open")),
+                timezone = timezone.name()
+            ),
+        }
+    }
+}
+
+/// The tower that served a call. Sectors of one tower count as the same place, so a tower
+/// is known by where it stands or, without coordinates, by its cell ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tower {
+    pub cell_id: Option<String>,
+    pub coordinates: Option<(f64, f64)>,
+}
+
+impl Tower {
+    /// The record's tower, or none when the production gives neither coordinates nor a
+    /// cell ID for it.
+    pub fn of(site: &CellSite) -> Option<Self> {
+        (site.coordinates().is_some() || site.cell_id.is_some()).then(|| Self {
+            cell_id: site.cell_id.clone(),
+            coordinates: site.coordinates(),
+        })
+    }
+
+    /// Where the tower stands as `34.73040, -86.58610`, or its cell ID when that is unknown.
+    /// Towers with the same label are the same place.
+    pub fn label(&self) -> String {
+        match (self.coordinates, &self.cell_id) {
+            (Some((latitude, longitude)), _) => format!("{:.5}, {:.5}", latitude, longitude),
+            (None, Some(cell_id)) => cell_id.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// A stretch of a target's calls served by one tower, with no call elsewhere in between
+/// and no gap longer than [`DWELL_BREAK_HOURS`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DwellPeriod {
+    pub target_number: String,
+    pub tower: Tower,
+    pub first: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+    pub calls: usize,
+}
+
+impl DwellPeriod {
+    /// First call to last; zero for a single call.
+    pub fn duration(&self) -> Duration {
+        self.last - self.first
+    }
+}
+
+/// A target's move from one tower to another between consecutive calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TowerTransition {
+    pub target_number: String,
+    pub from: Tower,
+    pub to: Tower,
+    /// The last call on `from`.
+    pub left: DateTime<Utc>,
+    /// The first call on `to`.
+    pub arrived: DateTime<Utc>,
+    /// Between the towers, when both have coordinates.
+    pub distance_km: Option<f64>,
+}
+
+impl TowerTransition {
+    /// The distance over the time between the two calls; none without coordinates or when
+    /// both calls started the same second.
+    pub fn speed_kmh(&self) -> Option<f64> {
+        let seconds = (self.arrived - self.left).num_seconds();
+        self.distance_km.filter(|_| seconds > 0).map(|distance| distance * 3600.0 / seconds as f64)
+    }
+
+    /// Whether the phone would have had to travel faster than [`IMPLAUSIBLE_SPEED_KMH`],
+    /// after allowing [`TOWER_RANGE_KM`] around each tower: a data error, or the number in
+    /// use on more than one phone.
+    pub fn implausible(&self) -> bool {
+        let Some(distance) = self.distance_km else {
+            return false;
+        };
+        let beyond_range = distance - 2.0 * TOWER_RANGE_KM;
+        let hours = (self.arrived - self.left).num_seconds().max(0) as f64 / 3600.0;
+        beyond_range > 0.0 && beyond_range >= IMPLAUSIBLE_SPEED_KMH * hours
+    }
+}
+
+/// Moves between each target's consecutive calls on towers with coordinates that no phone
+/// could have made, by target and then time. Calls on towers without coordinates are
+/// skipped, so they cannot hide a jump.
+///
+/// ```
+/// use esubpoena_tolls_tool::data_models::{CallRecord, ProcessedCallRecord};
+/// use esubpoena_tolls_tool::location::implausible_moves;
+///
+/// let call = |time: &str, latitude: &str, longitude: &str| {
+///     let call = CallRecord {
+///         message_direction: "outgoing".into(),
+///         remote_number: "2565550100".into(),
+///         start_time: time.into(),
+///         end_time: time.into(),
+///         latitude: Some(latitude.into()),
+///         longitude: Some(longitude.into()),
+///         ..CallRecord::default()
+///     };
+///     ProcessedCallRecord::from_call_record(&call, "2565550111", "march.xml").unwrap()
+/// };
+/// // Huntsville, Birmingham two hours later, then Atlanta ten minutes after that
+/// let records = vec![
+///     call("2024-03-01T14:00:00Z", "34.7304", "-86.5861"),
+///     call("2024-03-01T16:00:00Z", "33.5186", "-86.8104"),
+///     call("2024-03-01T16:10:00Z", "33.7490", "-84.3880"),
+/// ];
+/// let moves = implausible_moves(&records);
+/// assert_eq!(moves.len(), 1);
+/// assert!(moves[0].speed_kmh().unwrap() > 1000.0);
+/// ```
+pub fn implausible_moves(records: &[ProcessedCallRecord]) -> Vec<TowerTransition> {
+    let mut by_target: BTreeMap<&str, Vec<(DateTime<Utc>, Tower)>> = BTreeMap::new();
+    for record in records {
+        if let Some(tower) = record.cell_site.as_ref().and_then(Tower::of).filter(|tower| tower.coordinates.is_some()) {
+            by_target.entry(record.target_number.as_str()).or_default().push((record.start_time, tower));
+        }
+    }
+
+    let mut moves = Vec::new();
+    for (target, mut calls) in by_target {
+        calls.sort_by_key(|(time, _)| *time);
+        for pair in calls.windows(2) {
+            let [(left, from), (arrived, to)] = pair else { continue };
+            let transition = TowerTransition {
+                target_number: target.to_string(),
+                from: from.clone(),
+                to: to.clone(),
+                left: *left,
+                arrived: *arrived,
+                distance_km: from.coordinates.zip(to.coordinates).map(|(from, to)| distance_km(from, to)),
+            };
+            if transition.implausible() {
+                moves.push(transition);
+            }
+        }
+    }
+    moves
+}
+
+/// The towers that served a target's calls on one day of the case timezone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TowerDay {
+    pub date: NaiveDate,
+    pub target_number: String,
+    /// Each tower with its number of calls, busiest first.
+    pub towers: Vec<(Tower, usize)>,
+    /// Moves that arrived on this day.
+    pub transitions: usize,
+}
+
+/// Per-day tower usage, dwell periods, and tower transitions for each target, from the
+/// records with a tower.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LocationSummary {
+    /// By target, then day.
+    pub days: Vec<TowerDay>,
+    /// By target, then time.
+    pub dwells: Vec<DwellPeriod>,
+    /// By target, then time.
+    pub transitions: Vec<TowerTransition>,
+    /// The moves no phone could have made, from [`implausible_moves`].
+    pub implausible: Vec<TowerTransition>,
+}
+
+impl LocationSummary {
+    /// Walks each target's calls in time order, grouping days in `timezone`.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::data_models::{CallRecord, ProcessedCallRecord};
+    /// use esubpoena_tolls_tool::location::LocationSummary;
+    ///
+    /// let call = |time: &str, latitude: &str| {
+    ///     let call = CallRecord {
+    ///         message_direction: "outgoing".into(),
+    ///         remote_number: "2565550100".into(),
+    ///         start_time: time.into(),
+    ///         end_time: time.into(),
+    ///         latitude: Some(latitude.into()),
+    ///         longitude: Some("-86.5861".into()),
+    ///         ..CallRecord::default()
+    ///     };
+    ///     ProcessedCallRecord::from_call_record(&call, "2565550111", "march.xml").unwrap()
+    /// };
+    /// let records = vec![
+    ///     call("2024-03-01T14:00:00Z", "34.7304"),
+    ///     call("2024-03-01T15:30:00Z", "34.7304"),
+    ///     call("2024-03-01T18:00:00Z", "33.5186"),
+    /// ];
+    /// let summary = LocationSummary::build(&records, chrono_tz::Tz::UTC);
+    /// assert_eq!(summary.dwells.len(), 2);
+    /// assert_eq!(summary.dwells[0].calls, 2);
+    /// assert_eq!(summary.transitions.len(), 1);
+    /// assert_eq!(summary.days[0].towers[0].1, 2);
+    /// ```
+    pub fn build(records: &[ProcessedCallRecord], timezone: Tz) -> Self {
+        let mut by_target: BTreeMap<&str, Vec<(DateTime<Utc>, Tower)>> = BTreeMap::new();
+        for record in records {
+            if let Some(tower) = record.cell_site.as_ref().and_then(Tower::of) {
+                by_target.entry(record.target_number.as_str()).or_default().push((record.start_time, tower));
+            }
+        }
+
+        let mut summary = Self::default();
+        let break_after = Duration::hours(DWELL_BREAK_HOURS);
+        for (target, mut calls) in by_target {
+            calls.sort_by_key(|(time, _)| *time);
+            let mut days: BTreeMap<NaiveDate, (HashMap<String, usize>, usize)> = BTreeMap::new();
+            let mut towers: HashMap<String, Tower> = HashMap::new();
+            let mut dwell: Option<DwellPeriod> = None;
+            for (time, tower) in calls {
+                let label = tower.label();
+                let day = days.entry(time.with_timezone(&timezone).date_naive()).or_default();
+                *day.0.entry(label.clone()).or_default() += 1;
+
+                match &mut dwell {
+                    Some(current) if current.tower.label() == label && time - current.last <= break_after => {
+                        current.last = time;
+                        current.calls += 1;
+                    }
+                    _ => {
+                        if let Some(previous) = dwell.take() {
+                            if previous.tower.label() != label {
+                                day.1 += 1;
+                                summary.transitions.push(TowerTransition {
+                                    target_number: target.to_string(),
+                                    distance_km: previous.tower.coordinates.zip(tower.coordinates)
+                                        .map(|(from, to)| distance_km(from, to)),
+                                    from: previous.tower.clone(),
+                                    to: tower.clone(),
+                                    left: previous.last,
+                                    arrived: time,
+                                });
+                            }
+                            summary.dwells.push(previous);
+                        }
+                        dwell = Some(DwellPeriod { target_number: target.to_string(), tower: tower.clone(), first: time, last: time, calls: 1 });
+                    }
+                }
+                towers.entry(label).or_insert(tower);
+            }
+            summary.dwells.extend(dwell);
+
+            for (date, (counts, transitions)) in days {
+                let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+                counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                summary.days.push(TowerDay {
+                    date,
+                    target_number: target.to_string(),
+                    towers: counts.into_iter().map(|(label, calls)| (towers[&label].clone(), calls)).collect(),
+                    transitions,
+                });
+            }
+        }
+        summary.implausible = implausible_moves(records);
+        summary
+    }
+
+    /// Distinct towers over all targets.
+    pub fn tower_count(&self) -> usize {
+        let mut labels: Vec<String> = self.days.iter()
+            .flat_map(|day| day.towers.iter().map(|(tower, _)| tower.label()))
+            .collect();
+        labels.sort();
+        labels.dedup();
+        labels.len()
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const LOG_DIR_NAME: &str = "esubpoena-tolls-tool";
+const LOG_FILE_NAME: &str = "esubpoena.log";
+/// A log file is rotated once it grows past this size.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the active one (`esubpoena.log.1` is the newest).
+const MAX_ROTATED_FILES: usize = 3;
+/// Entries kept in memory for the Logs tab.
+const RECENT_CAPACITY: usize = 2000;
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+static CASE_ID: Mutex<Option<String>> = Mutex::new(None);
+
+/// One line of the JSON-lines log file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    pub module: String,
+    pub case_id: Option<String>,
+    pub message: String,
+}
+
+/// Sends every record to the console logger (env_logger, or the browser console on the web
+/// build), and additionally appends the tool's own records (plus anything `RUST_LOG` enables)
+/// to a rotating JSON-lines file and an in-memory buffer shown in the Logs tab.
+struct AppLogger {
+    console: Box<dyn Log>,
+    file: Mutex<Option<RotatingFile>>,
+    recent: Mutex<VecDeque<LogEntry>>,
+}
+
+impl AppLogger {
+    fn captures(&self, record: &Record) -> bool {
+        let own_module = record.target().starts_with("esubpoena");
+        (own_module && record.level() <= Level::Info) || self.console.enabled(record.metadata())
+    }
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        (metadata.target().starts_with("esubpoena") && metadata.level() <= Level::Info)
+            || self.console.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.console.enabled(record.metadata()) {
+            self.console.log(record);
+        }
+        if !self.captures(record) {
+            return;
+        }
+
+        let entry = LogEntry {
+            timestamp: Utc::now(),
+            level: record.level(),
+            module: record.module_path().unwrap_or(record.target()).to_string(),
+            case_id: current_case_id(),
+            message: record.args().to_string(),
+        };
+
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                // Logging must never take the app down; a failed write is simply dropped
+                let _ = file.append(&entry);
+            }
+        }
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.file.flush();
+            }
+        }
+    }
+}
+
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    fn append(&mut self, entry: &LogEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        if self.size + line.len() as u64 > MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        self.file.write_all(&line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for i in (1..MAX_ROTATED_FILES).rev() {
+            let from = rotated_path(&self.path, i);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, i + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// `esubpoena.log` under the platform local data directory.
+pub fn log_file_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(LOG_DIR_NAME).join("logs").join(LOG_FILE_NAME))
+}
+
+/// Installs the logger. Call once, before anything logs.
+pub fn init() {
+    #[cfg(not(target_arch = "wasm32"))]
+    let (console, console_level): (Box<dyn Log>, LevelFilter) = {
+        let logger = env_logger::Builder::from_default_env().build();
+        let level = logger.filter();
+        (Box::new(logger), level)
+    };
+    #[cfg(target_arch = "wasm32")]
+    let (console, console_level): (Box<dyn Log>, LevelFilter) =
+        (Box::new(eframe::WebLogger::new(LevelFilter::Info)), LevelFilter::Info);
+
+    // No file in the browser build; the in-memory buffer still feeds the Logs tab
+    let file = log_file_path().and_then(|path| match RotatingFile::open(path.clone()) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("File logging disabled, cannot open {:?}: {}", path, e);
+            None
+        }
+    });
+
+    let max_level = console_level.max(LevelFilter::Info);
+    let logger = LOGGER.get_or_init(|| AppLogger {
+        console,
+        file: Mutex::new(file),
+        recent: Mutex::new(VecDeque::with_capacity(RECENT_CAPACITY)),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Tags subsequent log entries with the case currently loaded, or clears the tag.
+pub fn set_case_id(case_id: Option<String>) {
+    if let Ok(mut current) = CASE_ID.lock() {
+        *current = case_id;
+    }
+}
+
+fn current_case_id() -> Option<String> {
+    CASE_ID.lock().ok().and_then(|id| id.clone())
+}
+
+/// Entries logged during this session, oldest first.
+pub fn recent_entries() -> Vec<LogEntry> {
+    LOGGER.get()
+        .and_then(|logger| logger.recent.lock().ok().map(|recent| recent.iter().cloned().collect()))
+        .unwrap_or_default()
+}
@@ -1,35 +1,69 @@
+#[cfg(not(target_arch = "wasm32"))]
 use eframe::egui;
 use log::info;
-use std::path::PathBuf;
 
 mod app;
-mod data_models;
-mod excel_exporter;
-mod xml_parser;
-mod analytics;
+#[cfg(not(target_arch = "wasm32"))]
+mod case_wizard;
+mod command_palette;
+mod jobs;
+mod logging;
+mod tutorial;
+#[cfg(target_arch = "wasm32")]
+mod web;
 
 use app::EsubpoenaApp;
+#[cfg(not(target_arch = "wasm32"))]
+use esubpoena_tolls_tool::session::RecoveryFiles;
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), eframe::Error> {
     // Initialize logging
-    env_logger::init();
+    logging::init();
     info!("Starting eSubpoena Tolls Tool");
 
     let options = eframe::NativeOptions {
-        initial_window_size: Some(egui::vec2(1200.0, 800.0)),
-        min_window_size: Some(egui::vec2(800.0, 600.0)),
-        resizable: true,
-        transparent: false,
-        decorated: true,
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([1200.0, 800.0])
+            .with_min_inner_size([800.0, 600.0])
+            .with_resizable(true)
+            .with_transparent(false)
+            .with_decorations(true),
         centered: true,
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "eSubpoena Tolls Tool",
         options,
         Box::new(|_cc| {
             Box::new(EsubpoenaApp::new())
         }),
-    )
-} 
\ No newline at end of file
+    );
+
+    // Getting here means the window was closed normally, so there is nothing to recover next time
+    if let Some(recovery) = RecoveryFiles::locate() {
+        recovery.end_session();
+    }
+    result
+}
+
+/// Browser entry point, mounted on the canvas in `index.html` (build with `trunk`).
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    // Initialize logging (browser console plus the Logs tab)
+    logging::init();
+    info!("Starting eSubpoena Tolls Tool (web)");
+
+    let web_options = eframe::WebOptions::default();
+    wasm_bindgen_futures::spawn_local(async {
+        eframe::WebRunner::new()
+            .start(
+                "esubpoena_canvas",
+                web_options,
+                Box::new(|_cc| Box::new(EsubpoenaApp::new())),
+            )
+            .await
+            .expect("failed to start eframe");
+    });
+}
\ No newline at end of file
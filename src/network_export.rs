@@ -0,0 +1,110 @@
+use crate::annotations::Annotations;
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use crate::settings::AppSettings;
+use anyhow::{Context, Result};
+use log::info;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Column headers stay in English whatever the interface language, since graph tools such
+/// as Gephi recognize `source`, `target`, and `weight` by name.
+const NODE_HEADERS: [&str; 4] = ["number", "alias", "calls", "duration_minutes"];
+const EDGE_HEADERS: [&str; 3] = ["source", "target", "weight"];
+
+/// A number in the call graph: a target or a contact of one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkNode {
+    pub calls: usize,
+    pub duration_minutes: f64,
+}
+
+/// The call graph as a node list and a weighted edge list from each target to its contacts,
+/// a lighter-weight alternative to GraphML that any graph tool can import.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Network {
+    /// By normalized number.
+    pub nodes: BTreeMap<String, NetworkNode>,
+    /// Calls per (target, contact) pair, both normalized.
+    pub edges: BTreeMap<(String, String), usize>,
+}
+
+impl Network {
+    /// Withheld and invalid remote numbers are left out, since they do not name one party.
+    pub fn build(records: &[ProcessedCallRecord]) -> Self {
+        let mut normalized_targets: HashMap<&str, String> = HashMap::new();
+        let mut network = Self::default();
+        for record in records.iter().filter(|r| r.number_kind.identifies_party()) {
+            let target = normalized_targets.entry(record.target_number.as_str())
+                .or_insert_with(|| {
+                    let normalized = normalize_phone_number(&record.target_number);
+                    if normalized.is_valid() { normalized.into_string() } else { record.target_number.clone() }
+                })
+                .clone();
+            for number in [&target, &record.normalized_number] {
+                let node = network.nodes.entry(number.clone()).or_default();
+                node.calls += 1;
+                node.duration_minutes += record.duration_minutes;
+            }
+            *network.edges.entry((target, record.normalized_number.clone())).or_insert(0) += 1;
+        }
+        network
+    }
+}
+
+/// Writes a [`Network`] as the `nodes.csv`/`edges.csv` pair.
+pub struct NetworkExporter;
+
+impl NetworkExporter {
+    pub fn export(records: &[ProcessedCallRecord], annotations: &Annotations, settings: &AppSettings, nodes_path: &Path, edges_path: &Path) -> Result<()> {
+        info!("Exporting call network to {:?} and {:?}", nodes_path, edges_path);
+        let network = Network::build(records);
+
+        let nodes = File::create(nodes_path)
+            .with_context(|| format!("Failed to create file: {:?}", nodes_path))?;
+        Self::write_nodes(nodes, &network, annotations, settings)?;
+        let edges = File::create(edges_path)
+            .with_context(|| format!("Failed to create file: {:?}", edges_path))?;
+        Self::write_edges(edges, &network, settings)?;
+
+        info!("Exported {} nodes and {} edges", network.nodes.len(), network.edges.len());
+        Ok(())
+    }
+
+    /// Same as [`NetworkExporter::export`] but into memory, for the browser build's downloads:
+    /// the nodes file, then the edges file.
+    pub fn to_bytes(records: &[ProcessedCallRecord], annotations: &Annotations, settings: &AppSettings) -> Result<(Vec<u8>, Vec<u8>)> {
+        let network = Network::build(records);
+        let mut nodes = Vec::new();
+        Self::write_nodes(&mut nodes, &network, annotations, settings)?;
+        let mut edges = Vec::new();
+        Self::write_edges(&mut edges, &network, settings)?;
+        Ok((nodes, edges))
+    }
+
+    pub fn write_nodes<W: Write>(writer: W, network: &Network, annotations: &Annotations, settings: &AppSettings) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(NODE_HEADERS)?;
+        for (number, node) in &network.nodes {
+            writer.write_record([
+                settings.format_number(number),
+                annotations.alias(number).unwrap_or_default().to_string(),
+                node.calls.to_string(),
+                format!("{:.2}", node.duration_minutes),
+            ])?;
+        }
+        writer.flush().context("Failed to write nodes CSV")?;
+        Ok(())
+    }
+
+    pub fn write_edges<W: Write>(writer: W, network: &Network, settings: &AppSettings) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(EDGE_HEADERS)?;
+        for ((source, target), weight) in &network.edges {
+            writer.write_record([settings.format_number(source), settings.format_number(target), weight.to_string()])?;
+        }
+        writer.flush().context("Failed to write edges CSV")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::data_models::{NumberKind, ProcessedCallRecord};
+use crate::tr;
+use std::collections::HashMap;
+
+/// Why a remote number looks like junk rather than a party to the call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NumberIssue {
+    /// Letters or feature-code symbols, e.g. "VOICEMAIL" or `*86`.
+    NotANumber,
+    /// Filler digits: one digit repeated, an area code starting with 0 or 1, or more digits
+    /// than any dialable number has.
+    Padded,
+    /// Too few digits to dial, e.g. a seven-digit number without its area code.
+    TooShort,
+}
+
+impl NumberIssue {
+    pub fn label(&self) -> String {
+        match self {
+            NumberIssue::NotANumber => tr!("number-issue-not-a-number"),
+            NumberIssue::Padded => tr!("number-issue-padded"),
+            NumberIssue::TooShort => tr!("number-issue-too-short"),
+        }
+    }
+
+    /// Judges one remote value as produced and as normalized. Withheld caller IDs are a
+    /// known gap, not junk, and are never flagged.
+    pub fn of(record: &ProcessedCallRecord) -> Option<Self> {
+        let raw = record.remote_number.trim();
+        let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
+        match record.number_kind {
+            NumberKind::Unknown => None,
+            _ if digits.is_empty() || raw.starts_with(['*', '#']) || raw.chars().any(|c| c.is_alphabetic()) => {
+                Some(NumberIssue::NotANumber)
+            }
+            NumberKind::Invalid if digits.len() > 15 => Some(NumberIssue::Padded),
+            NumberKind::Invalid if digits.len() < 10 => Some(NumberIssue::TooShort),
+            NumberKind::Nanp => {
+                let number = record.normalized_number.as_bytes();
+                let repeated = number.iter().all(|&digit| digit == number[0]);
+                (repeated || matches!(number.first(), Some(b'0' | b'1'))).then_some(NumberIssue::Padded)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A suspicious remote number and how much of the data it accounts for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberWarning {
+    /// As normalized in the records, which is what exclusions and reclassifications match.
+    pub number: String,
+    /// The kind the number was classified as when parsed.
+    pub kind: NumberKind,
+    pub issue: NumberIssue,
+    pub calls: usize,
+    /// The value as the carrier produced it, from the first record seen.
+    pub example: String,
+}
+
+impl NumberWarning {
+    /// Every suspicious number in the records, the most calls first.
+    pub fn scan(records: &[ProcessedCallRecord]) -> Vec<Self> {
+        let mut warnings: HashMap<&str, NumberWarning> = HashMap::new();
+        for record in records {
+            if warnings.contains_key(record.normalized_number.as_str()) {
+                continue;
+            }
+            if let Some(issue) = NumberIssue::of(record) {
+                warnings.insert(record.normalized_number.as_str(), NumberWarning {
+                    number: record.normalized_number.clone(),
+                    kind: record.number_kind,
+                    issue,
+                    calls: 0,
+                    example: record.remote_number.trim().to_string(),
+                });
+            }
+        }
+        if warnings.is_empty() {
+            return Vec::new();
+        }
+        for record in records {
+            if let Some(warning) = warnings.get_mut(record.normalized_number.as_str()) {
+                warning.calls += 1;
+            }
+        }
+        let mut warnings: Vec<Self> = warnings.into_values().collect();
+        warnings.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.number.cmp(&b.number)));
+        warnings
+    }
+}
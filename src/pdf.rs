@@ -0,0 +1,185 @@
+//! A minimal PDF writer for the text reports and exhibits: US Letter pages using the standard
+//! Helvetica fonts, so no fonts are embedded, plus filled rectangles for simple bar charts.
+
+/// Characters per body line, which fits 10pt Helvetica between the page margins.
+pub(crate) const LINE_CHARS: usize = 95;
+pub(crate) const PAGE_WIDTH: f32 = 612.0;
+pub(crate) const PAGE_HEIGHT: f32 = 792.0;
+pub(crate) const MARGIN: f32 = 54.0;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PdfStyle {
+    Title,
+    Heading,
+    Body,
+    /// Chart axis labels and page furniture.
+    Small,
+}
+
+impl PdfStyle {
+    /// Font resource name, size, and line height.
+    pub(crate) fn font(&self) -> (&'static str, f32, f32) {
+        match self {
+            PdfStyle::Title => ("F2", 16.0, 24.0),
+            PdfStyle::Heading => ("F2", 12.0, 18.0),
+            PdfStyle::Body => ("F1", 10.0, 13.0),
+            PdfStyle::Small => ("F1", 7.0, 9.0),
+        }
+    }
+}
+
+/// Lays content out top to bottom into page content streams, starting a new page whenever
+/// the next line or block would cross the bottom margin.
+#[derive(Debug)]
+pub(crate) struct PdfPages {
+    pages: Vec<String>,
+    content: String,
+    /// Bottom of the content laid out so far on the current page.
+    y: f32,
+}
+
+impl PdfPages {
+    pub(crate) fn new() -> Self {
+        Self { pages: Vec::new(), content: String::new(), y: PAGE_HEIGHT - MARGIN }
+    }
+
+    pub(crate) fn line(&mut self, style: PdfStyle, text: &str) {
+        self.row(style, &[(MARGIN, text)]);
+    }
+
+    /// One line with cells starting at the given x positions.
+    pub(crate) fn row(&mut self, style: PdfStyle, cells: &[(f32, &str)]) {
+        let (font, size, leading) = style.font();
+        self.reserve(leading);
+        for (x, text) in cells.iter().filter(|(_, text)| !text.is_empty()) {
+            self.content.push_str(&format!("BT /{} {} Tf {:.1} {:.1} Td {} Tj ET\n", font, size, x, self.y, pdf_string(text)));
+        }
+    }
+
+    /// Keeps `height` points free below the current line, moving to a new page if needed, and
+    /// returns the top of that space. The space is then taken, for drawing into with [`PdfPages::draw`].
+    pub(crate) fn reserve(&mut self, height: f32) -> f32 {
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+        let top = self.y;
+        self.y -= height;
+        top
+    }
+
+    /// Whether `height` more points fit on the current page.
+    pub(crate) fn fits(&self, height: f32) -> bool {
+        self.y - height >= MARGIN
+    }
+
+    /// Appends raw content-stream operators to the current page.
+    pub(crate) fn draw(&mut self, operators: &str) {
+        self.content.push_str(operators);
+    }
+
+    pub(crate) fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.content));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// The page content streams; an empty layout is one blank page.
+    pub(crate) fn finish(mut self) -> Vec<String> {
+        self.pages.push(self.content);
+        self.pages
+    }
+}
+
+/// Text drawn at an absolute position, for page furniture such as labels and footers.
+pub(crate) fn text_at(style: PdfStyle, x: f32, y: f32, text: &str) -> String {
+    let (font, size, _) = style.font();
+    format!("BT /{} {} Tf {:.1} {:.1} Td {} Tj ET\n", font, size, x, y, pdf_string(text))
+}
+
+/// Rough width of `text` in points, for right-aligning and centering. Helvetica averages a
+/// little over half the font size per character.
+pub(crate) fn text_width(style: PdfStyle, text: &str) -> f32 {
+    let (_, size, _) = style.font();
+    text.chars().count() as f32 * size * 0.55
+}
+
+/// Breaks a line at spaces so no piece is longer than `width` characters, except single
+/// words that are longer on their own.
+pub(crate) fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    pieces.push(current);
+    pieces
+}
+
+/// A PDF string literal in WinAnsiEncoding; characters outside it become `?`.
+fn pdf_string(text: &str) -> String {
+    let mut literal = String::from("(");
+    for c in text.chars() {
+        let byte = match c {
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u32 as u8,
+            _ => b'?',
+        };
+        match byte {
+            b'(' | b')' | b'\\' => {
+                literal.push('\\');
+                literal.push(byte as char);
+            }
+            0x20..=0x7E => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    literal.push(')');
+    literal
+}
+
+/// Assembles page content streams into a PDF file.
+pub(crate) fn write_pdf(pages: &[String]) -> Vec<u8> {
+    // Objects 1-4 are fixed; each page then takes a page object and a content stream
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        String::new(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    let mut kids = Vec::new();
+    for page in pages {
+        let page_id = objects.len() + 1;
+        kids.push(format!("{} 0 R", page_id));
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT, page_id + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", page.len(), page));
+    }
+    objects[1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len());
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref_offset).as_bytes());
+    pdf
+}
@@ -0,0 +1,365 @@
+use crate::data_models::{normalize_phone_number, CallRecord, ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{normalize_direction, parse_duration, parse_time, preamble_target, DelimitedParser};
+use crate::format_detection::DetectionConfidence;
+use crate::pipeline::DuplicatePolicy;
+use crate::tr;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Duration;
+use chrono_tz::Tz;
+use log::{info, warn};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Pieces the default patterns are built from.
+const DATE: &str = r"\d{1,2}/\d{1,2}/\d{2,4}";
+const TIME: &str = r"\d{1,2}:\d{2}(?::\d{2})?(?:\s?[AP]M)?";
+const NUMBER: &str = r"\+?1?[-. ]?\(?\d{3}\)?[-. ]?\d{3}[-. ]?\d{4}";
+const DIRECTION: &str = r"incoming|outgoing|orig\w*|term\w*|in|out|mo|mt";
+const DURATION: &str = r"\d+:\d{2}(?::\d{2})?|\d+";
+
+/// Fewest digits on a line that matched no pattern before it is offered for review as a
+/// row the patterns missed; a date and a phone number have at least this many.
+const CANDIDATE_DIGITS: usize = 10;
+
+/// A regex that picks one call's fields out of a line of a PDF's text.
+///
+/// The named groups `remote` and either `start` or `date` and `time` are required;
+/// `direction`, `duration`, `end`, `target`, `type`, and `disposition` are read when the
+/// pattern has them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PdfPattern {
+    pub name: String,
+    pub pattern: String,
+    /// Whether slash dates are day first, e.g. 03/04 is the 3rd of April.
+    #[serde(default)]
+    pub day_first: bool,
+}
+
+impl PdfPattern {
+    /// Patterns for the common layouts: date and time then the number, the number first,
+    /// and ISO timestamps. Each allows a direction column before the number.
+    pub fn defaults() -> Vec<PdfPattern> {
+        let direction = format!(r"(?:(?P<direction>{})\s+)?", DIRECTION);
+        vec![
+            PdfPattern {
+                name: "Date, time, number, duration".to_string(),
+                pattern: format!(
+                    r"(?P<date>{DATE})\s+(?P<time>{TIME})\s+{direction}(?P<remote>{NUMBER})\s+(?P<duration>{DURATION})\b"
+                ),
+                day_first: false,
+            },
+            PdfPattern {
+                name: "Number, date, time, duration".to_string(),
+                pattern: format!(
+                    r"(?P<remote>{NUMBER})\s+(?P<date>{DATE})\s+(?P<time>{TIME})\s+{direction}(?P<duration>{DURATION})\b"
+                ),
+                day_first: false,
+            },
+            PdfPattern {
+                name: "ISO timestamp, number, duration".to_string(),
+                pattern: format!(
+                    r"(?P<start>\d{{4}}-\d{{2}}-\d{{2}}[T ]\d{{2}}:\d{{2}}(?::\d{{2}})?)\s+{direction}(?P<remote>{NUMBER})\s+(?P<duration>{DURATION})\b"
+                ),
+                day_first: false,
+            },
+        ]
+    }
+
+    /// The pattern compiled case-insensitively, or why it cannot be used.
+    pub fn compile(&self) -> Result<Regex> {
+        let regex = RegexBuilder::new(&self.pattern).case_insensitive(true).build()?;
+        let has = |group: &str| regex.capture_names().flatten().any(|name| name == group);
+        if !has("remote") {
+            bail!("the pattern needs a (?P<remote>...) group");
+        }
+        let dated = has("date") && has("time");
+        if !has("start") && !dated {
+            bail!("the pattern needs a (?P<start>...) group, or (?P<date>...) and (?P<time>...)");
+        }
+        Ok(regex)
+    }
+}
+
+/// Why a row read from a PDF may be wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowProblem {
+    /// The start does not read as a date and time.
+    BadTime,
+    /// The remote number is not a dialable number.
+    BadNumber,
+    /// There is neither a duration nor an end time.
+    NoDuration,
+    /// The direction is written in a way that is neither incoming nor outgoing.
+    UnknownDirection,
+}
+
+impl RowProblem {
+    pub fn label(self) -> String {
+        match self {
+            RowProblem::BadTime => tr!("pdf-problem-time"),
+            RowProblem::BadNumber => tr!("pdf-problem-number"),
+            RowProblem::NoDuration => tr!("pdf-problem-duration"),
+            RowProblem::UnknownDirection => tr!("pdf-problem-direction"),
+        }
+    }
+}
+
+/// A line of a PDF read as a call, with its fields as text so they can be corrected.
+#[derive(Debug, Clone)]
+pub struct PdfRow {
+    /// 1-based line of the extracted text.
+    pub line_number: usize,
+    pub line: String,
+    /// Name of the pattern that matched, or none for a line that looks like a call but
+    /// matched no pattern.
+    pub pattern: Option<String>,
+    pub target: String,
+    pub direction: String,
+    pub remote_number: String,
+    pub start: String,
+    pub end: String,
+    pub duration: String,
+    pub record_type: Option<String>,
+    pub disposition: Option<String>,
+    pub day_first: bool,
+    pub problems: Vec<RowProblem>,
+    /// Whether the row is loaded; lines no pattern matched start out left out.
+    pub keep: bool,
+}
+
+impl PdfRow {
+    fn matched(line_number: usize, line: &str, pattern: &CompiledPattern, target: &str) -> Option<Self> {
+        let captures = pattern.regex.captures(line)?;
+        let group = |name: &str| captures.name(name).map_or("", |found| found.as_str().trim()).to_string();
+        let start = match captures.name("start") {
+            Some(start) => start.as_str().trim().to_string(),
+            None => format!("{} {}", group("date"), group("time")),
+        };
+        let row_target = normalize_phone_number(&group("target"));
+        let mut row = Self {
+            line_number,
+            line: line.to_string(),
+            pattern: Some(pattern.name.clone()),
+            target: if row_target.is_valid() { row_target.into_string() } else { target.to_string() },
+            direction: group("direction"),
+            remote_number: group("remote"),
+            start,
+            end: group("end"),
+            duration: group("duration"),
+            record_type: Some(group("type")).filter(|value| !value.is_empty()),
+            disposition: Some(group("disposition")).filter(|value| !value.is_empty()),
+            day_first: pattern.day_first,
+            problems: Vec::new(),
+            keep: true,
+        };
+        row.check();
+        Some(row)
+    }
+
+    fn unmatched(line_number: usize, line: &str, target: &str) -> Self {
+        let mut row = Self {
+            line_number,
+            line: line.to_string(),
+            pattern: None,
+            target: target.to_string(),
+            direction: String::new(),
+            remote_number: String::new(),
+            start: String::new(),
+            end: String::new(),
+            duration: String::new(),
+            record_type: None,
+            disposition: None,
+            day_first: false,
+            problems: Vec::new(),
+            keep: false,
+        };
+        row.check();
+        row
+    }
+
+    /// Whether the row should be looked at before it is loaded.
+    pub fn needs_review(&self) -> bool {
+        self.pattern.is_none() || !self.problems.is_empty()
+    }
+
+    /// Works out the problems again, e.g. after a field was corrected. Times are checked in
+    /// UTC; the zone they are read in does not change whether they read.
+    pub fn check(&mut self) {
+        self.problems.clear();
+        if parse_time(&self.start, self.day_first, Tz::UTC).is_none() {
+            self.problems.push(RowProblem::BadTime);
+        }
+        if !normalize_phone_number(&self.remote_number).is_valid() {
+            self.problems.push(RowProblem::BadNumber);
+        }
+        if parse_duration(self.duration.trim()).is_none() && parse_time(&self.end, self.day_first, Tz::UTC).is_none() {
+            self.problems.push(RowProblem::NoDuration);
+        }
+        let direction = normalize_direction(self.direction.trim());
+        if !self.direction.trim().is_empty() && direction != "Incoming" && direction != "Outgoing" {
+            self.problems.push(RowProblem::UnknownDirection);
+        }
+    }
+
+    /// The row as a call record, with local times in `timezone`. The length is the duration,
+    /// or the time from the start to the end when there is no duration.
+    pub fn call_record(&self, timezone: Tz) -> CallRecord {
+        let start = parse_time(&self.start, self.day_first, timezone);
+        let end = parse_time(&self.end, self.day_first, timezone);
+        let length_of_call = match (parse_duration(self.duration.trim()), start, end) {
+            (Some(seconds), _, _) => seconds,
+            (None, Some(start), Some(end)) => (end - start).num_seconds().max(0) as u32,
+            _ => 0,
+        };
+        let end = end.or_else(|| start.map(|start| start + Duration::seconds(length_of_call as i64)));
+        CallRecord {
+            message_direction: normalize_direction(self.direction.trim()),
+            remote_number: self.remote_number.trim().to_string(),
+            start_time: start.map(|time| time.to_rfc3339()).unwrap_or_else(|| self.start.clone()),
+            end_time: end.map(|time| time.to_rfc3339()).unwrap_or_default(),
+            length_of_call,
+            message_content: None,
+            record_type: self.record_type.clone(),
+            cell_site_id: None,
+            sector: None,
+            latitude: None,
+            longitude: None,
+            imei: None,
+            imsi: None,
+            disposition: self.disposition.clone(),
+        }
+    }
+}
+
+struct CompiledPattern {
+    name: String,
+    regex: Regex,
+    day_first: bool,
+}
+
+/// The rows read from a PDF's text, before they are loaded.
+#[derive(Debug, Clone)]
+pub struct PdfExtraction {
+    pub source_file: String,
+    /// The target named above the rows, e.g. on a `Target Number:` line.
+    pub target: Option<String>,
+    pub rows: Vec<PdfRow>,
+}
+
+impl PdfExtraction {
+    pub fn read_file(path: &Path, patterns: &[PdfPattern]) -> Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::from_bytes(&bytes, source_file, patterns)
+    }
+
+    pub fn from_bytes(bytes: &[u8], source_file: &str, patterns: &[PdfPattern]) -> Result<Self> {
+        // The extractor panics on some malformed files rather than returning an error
+        let text = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(bytes))
+            .map_err(|_| anyhow!("Failed to read the text of {}", source_file))?
+            .map_err(|e| anyhow!("Failed to read the text of {}: {}", source_file, e))?;
+        Ok(Self::from_text(&text, source_file, patterns))
+    }
+
+    /// Reads rows from text already extracted from a PDF. Each line goes to the first
+    /// pattern that matches it; lines no pattern matches that have the digits of a date and
+    /// a phone number are kept for review. Patterns that do not compile are skipped.
+    pub fn from_text(text: &str, source_file: &str, patterns: &[PdfPattern]) -> Self {
+        let compiled: Vec<CompiledPattern> = patterns.iter()
+            .filter_map(|pattern| match pattern.compile() {
+                Ok(regex) => Some(CompiledPattern { name: pattern.name.clone(), regex, day_first: pattern.day_first }),
+                Err(e) => {
+                    warn!("Skipping PDF pattern {:?}: {:#}", pattern.name, e);
+                    None
+                }
+            })
+            .collect();
+
+        let mut extraction = Self { source_file: source_file.to_string(), target: None, rows: Vec::new() };
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if extraction.rows.is_empty() && extraction.target.is_none() {
+                extraction.target = preamble_target(line);
+                if extraction.target.is_some() {
+                    continue;
+                }
+            }
+            let target = extraction.target.as_deref().unwrap_or_default();
+            if let Some(row) = compiled.iter().find_map(|pattern| PdfRow::matched(index + 1, line, pattern, target)) {
+                extraction.rows.push(row);
+            } else if line.chars().filter(char::is_ascii_digit).count() >= CANDIDATE_DIGITS && line.contains([':', '/']) {
+                extraction.rows.push(PdfRow::unmatched(index + 1, line, target));
+            }
+        }
+        info!("Read {} rows from {}, {} to review", extraction.rows.len(), source_file, extraction.review_count());
+        extraction
+    }
+
+    /// Rows that matched no pattern or have a problem.
+    pub fn review_count(&self) -> usize {
+        self.rows.iter().filter(|row| row.needs_review()).count()
+    }
+
+    /// High when every row matched cleanly, medium when some need review, and low when no
+    /// row matched at all.
+    pub fn confidence(&self) -> DetectionConfidence {
+        if !self.rows.iter().any(|row| row.pattern.is_some()) {
+            DetectionConfidence::Low
+        } else if self.review_count() > 0 {
+            DetectionConfidence::Medium
+        } else {
+            DetectionConfidence::High
+        }
+    }
+
+    /// The rows kept, processed like any other production's; rows that still do not read
+    /// are reported as skipped.
+    pub fn into_records(self, timezone: Tz, duplicates: DuplicatePolicy) -> (Vec<ProcessedCallRecord>, ParseReport) {
+        let rows = self.rows.iter()
+            .filter(|row| row.keep)
+            .map(|row| Ok((row.target.clone(), row.call_record(timezone))));
+        DelimitedParser::collect_records(rows, &self.source_file, duplicates)
+    }
+}
+
+/// Reads toll records from PDF returns by extracting their text and matching each line
+/// against [`PdfPattern`]s. Rows that need review are loaded as they read; on the desktop, a
+/// single PDF opened on its own is shown for review first.
+pub struct PdfParser;
+
+impl PdfParser {
+    pub fn parse_file(
+        path: &Path,
+        patterns: &[PdfPattern],
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing PDF file: {:?}", path);
+        let (records, report) = PdfExtraction::read_file(path, patterns)?.into_records(timezone, duplicates);
+        info!("Parsed {} PDF records", records.len());
+        Ok((records, report))
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        patterns: &[PdfPattern],
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        Ok(PdfExtraction::from_bytes(bytes, source_file, patterns)?.into_records(timezone, duplicates))
+    }
+
+    /// Whether `sample` is the start of a PDF.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        sample.starts_with(b"%PDF-")
+    }
+}
@@ -0,0 +1,87 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use anyhow::{Context, Result};
+use chrono_tz::Tz;
+use log::info;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Columns of PenLink's generic call import. They stay in English whatever the interface
+/// language, since the import template maps them by name.
+const HEADERS: [&str; 11] = [
+    "Target Number", "Date", "Time", "Time Zone", "Duration", "Direction",
+    "Originating Number", "Terminating Number", "Call Type", "Record ID", "Source File",
+];
+
+/// Call records in the layout of PenLink's generic call import, so this tool can normalize
+/// productions before they are charted in PenLink. Numbers are digits only, dates are
+/// `MM/DD/YYYY` and times `HH:MM:SS` in the case timezone, and durations are in seconds.
+pub struct PenLinkExporter;
+
+impl PenLinkExporter {
+    pub fn export(records: &[ProcessedCallRecord], timezone: Tz, output_path: &Path) -> Result<()> {
+        info!("Exporting call records for PenLink: {:?}", output_path);
+
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create file: {:?}", output_path))?;
+        Self::write_calls(file, records, timezone)?;
+
+        info!("Successfully exported {} records for PenLink", records.len());
+        Ok(())
+    }
+
+    /// Same as [`PenLinkExporter::export`] but into memory, for the browser build's downloads.
+    pub fn to_bytes(records: &[ProcessedCallRecord], timezone: Tz) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        Self::write_calls(&mut buffer, records, timezone)?;
+        Ok(buffer)
+    }
+
+    /// Rows are in call order. The target is the originating number of outgoing calls and
+    /// the terminating number of incoming ones.
+    pub fn write_calls<W: Write>(writer: W, records: &[ProcessedCallRecord], timezone: Tz) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(HEADERS)?;
+
+        let mut ordered: Vec<&ProcessedCallRecord> = records.iter().collect();
+        ordered.sort_by_key(|record| record.start_time);
+        for record in ordered {
+            let target = digits(&record.target_number);
+            let remote = digits(&record.remote_number);
+            let incoming = record.message_direction.eq_ignore_ascii_case("incoming");
+            let (direction, originating, terminating) = if incoming {
+                ("Incoming", remote, target.clone())
+            } else {
+                ("Outgoing", target.clone(), remote)
+            };
+            let start = record.start_time.with_timezone(&timezone);
+            writer.write_record([
+                target,
+                start.format("%m/%d/%Y").to_string(),
+                start.format("%H:%M:%S").to_string(),
+                start.format("%Z").to_string(),
+                record.length_of_call.to_string(),
+                direction.to_string(),
+                originating,
+                terminating,
+                "Voice".to_string(),
+                record.record_key(),
+                record.source_file.clone(),
+            ])?;
+        }
+
+        writer.flush().context("Failed to write PenLink import")?;
+        Ok(())
+    }
+}
+
+/// Normalized digits (international numbers without their `+`); withheld caller IDs and
+/// other non-numbers are written as produced.
+fn digits(number: &str) -> String {
+    let normalized = normalize_phone_number(number);
+    if normalized.is_valid() {
+        normalized.as_str().trim_start_matches('+').to_string()
+    } else {
+        normalized.into_string()
+    }
+}
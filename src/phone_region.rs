@@ -0,0 +1,152 @@
+//! The country numbers without a country code are read in, and its dialing conventions.
+//!
+//! Carriers write most numbers the way they are dialed at home: a US return has `2565550100`,
+//! a Mexican one `5512345678`, a British one `07911 123456`. [`normalize_phone_number`]
+//! reads such numbers as belonging to the default region set here, the way the language is
+//! set for [`tr!`](crate::tr), while numbers with a `+` or an international prefix keep the
+//! country they name.
+//!
+//! [`normalize_phone_number`]: crate::data_models::normalize_phone_number
+
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Countries whose national numbers can be read without a country code. The United States
+/// stands for the whole North American Numbering Plan, whose numbers stay 10 digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum PhoneRegion {
+    #[default]
+    #[serde(rename = "US")]
+    NorthAmerica,
+    #[serde(rename = "MX")]
+    Mexico,
+    #[serde(rename = "GT")]
+    Guatemala,
+    #[serde(rename = "SV")]
+    ElSalvador,
+    #[serde(rename = "HN")]
+    Honduras,
+    #[serde(rename = "CO")]
+    Colombia,
+    #[serde(rename = "BR")]
+    Brazil,
+    #[serde(rename = "GB")]
+    UnitedKingdom,
+    #[serde(rename = "ES")]
+    Spain,
+    #[serde(rename = "FR")]
+    France,
+    #[serde(rename = "DE")]
+    Germany,
+    #[serde(rename = "IN")]
+    India,
+    #[serde(rename = "CN")]
+    China,
+}
+
+impl PhoneRegion {
+    /// In declaration order, which is how [`set_default_region`] stores them.
+    pub const ALL: [PhoneRegion; 13] = [
+        PhoneRegion::NorthAmerica, PhoneRegion::Mexico, PhoneRegion::Guatemala, PhoneRegion::ElSalvador,
+        PhoneRegion::Honduras, PhoneRegion::Colombia, PhoneRegion::Brazil, PhoneRegion::UnitedKingdom,
+        PhoneRegion::Spain, PhoneRegion::France, PhoneRegion::Germany, PhoneRegion::India, PhoneRegion::China,
+    ];
+
+    /// The country's name with its calling code, e.g. `Mexico (+52)`.
+    pub fn label(&self) -> String {
+        let name = match self {
+            PhoneRegion::NorthAmerica => tr!("region-north-america"),
+            PhoneRegion::Mexico => tr!("region-mexico"),
+            PhoneRegion::Guatemala => tr!("region-guatemala"),
+            PhoneRegion::ElSalvador => tr!("region-el-salvador"),
+            PhoneRegion::Honduras => tr!("region-honduras"),
+            PhoneRegion::Colombia => tr!("region-colombia"),
+            PhoneRegion::Brazil => tr!("region-brazil"),
+            PhoneRegion::UnitedKingdom => tr!("region-united-kingdom"),
+            PhoneRegion::Spain => tr!("region-spain"),
+            PhoneRegion::France => tr!("region-france"),
+            PhoneRegion::Germany => tr!("region-germany"),
+            PhoneRegion::India => tr!("region-india"),
+            PhoneRegion::China => tr!("region-china"),
+        };
+        format!("{} (+{})", name, self.calling_code())
+    }
+
+    pub fn calling_code(&self) -> &'static str {
+        match self {
+            PhoneRegion::NorthAmerica => "1",
+            PhoneRegion::Mexico => "52",
+            PhoneRegion::Guatemala => "502",
+            PhoneRegion::ElSalvador => "503",
+            PhoneRegion::Honduras => "504",
+            PhoneRegion::Colombia => "57",
+            PhoneRegion::Brazil => "55",
+            PhoneRegion::UnitedKingdom => "44",
+            PhoneRegion::Spain => "34",
+            PhoneRegion::France => "33",
+            PhoneRegion::Germany => "49",
+            PhoneRegion::India => "91",
+            PhoneRegion::China => "86",
+        }
+    }
+
+    /// What is dialed before a number with a country code from inside the country.
+    pub fn international_prefix(&self) -> &'static str {
+        match self {
+            PhoneRegion::NorthAmerica => "011",
+            _ => "00",
+        }
+    }
+
+    /// What is dialed before a national number from another area of the country, e.g. the
+    /// `0` of `07911 123456`, or the `1` of `1-256-555-0100`.
+    pub fn trunk_prefix(&self) -> Option<&'static str> {
+        match self {
+            PhoneRegion::NorthAmerica => Some("1"),
+            PhoneRegion::Brazil
+            | PhoneRegion::UnitedKingdom
+            | PhoneRegion::France
+            | PhoneRegion::Germany
+            | PhoneRegion::India
+            | PhoneRegion::China => Some("0"),
+            _ => None,
+        }
+    }
+
+    /// Digits of a national number without the trunk prefix.
+    pub fn national_lengths(&self) -> RangeInclusive<usize> {
+        match self {
+            PhoneRegion::NorthAmerica | PhoneRegion::Mexico | PhoneRegion::Colombia | PhoneRegion::India => 10..=10,
+            PhoneRegion::Guatemala | PhoneRegion::ElSalvador | PhoneRegion::Honduras => 8..=8,
+            PhoneRegion::Spain | PhoneRegion::France => 9..=9,
+            PhoneRegion::UnitedKingdom => 9..=10,
+            PhoneRegion::Brazil | PhoneRegion::China => 10..=11,
+            // Short area codes leave small towns with short numbers
+            PhoneRegion::Germany => 7..=13,
+        }
+    }
+
+    /// The national number in `digits`, with or without its trunk prefix, if it has the
+    /// length of one.
+    pub fn national_number<'a>(&self, digits: &'a str) -> Option<&'a str> {
+        let lengths = self.national_lengths();
+        self.trunk_prefix()
+            .and_then(|prefix| digits.strip_prefix(prefix))
+            .filter(|national| lengths.contains(&national.len()))
+            .or_else(|| lengths.contains(&digits.len()).then_some(digits))
+    }
+}
+
+static DEFAULT_REGION: AtomicU8 = AtomicU8::new(PhoneRegion::NorthAmerica as u8);
+
+/// Switches the region subsequent normalization reads numbers without a country code in.
+/// Numbers already loaded keep the form they were given.
+pub fn set_default_region(region: PhoneRegion) {
+    DEFAULT_REGION.store(region as u8, Ordering::Relaxed);
+}
+
+pub fn default_region() -> PhoneRegion {
+    PhoneRegion::ALL.get(DEFAULT_REGION.load(Ordering::Relaxed) as usize).copied().unwrap_or_default()
+}
@@ -0,0 +1,349 @@
+use crate::analytics::AnalyticsAccumulator;
+use crate::data_models::{CallRecord, MalformedBlock, ParseReport, ProcessedCallRecord, ProductionHeader};
+use crate::delimited_parser::target_number;
+use crate::tr;
+use crate::xml_parser::{log_discrepancies, XmlParser};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread;
+
+pub const DEFAULT_BATCH_SIZE: usize = 5_000;
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 4;
+
+/// Final stage of the ingestion pipeline. Receives normalized records in batches.
+pub trait RecordSink {
+    fn accept(&mut self, batch: Vec<ProcessedCallRecord>) -> Result<()>;
+}
+
+/// Keeps every record, for callers that need the full table (e.g. the records grid).
+impl RecordSink for Vec<ProcessedCallRecord> {
+    fn accept(&mut self, batch: Vec<ProcessedCallRecord>) -> Result<()> {
+        self.extend(batch);
+        Ok(())
+    }
+}
+
+/// Folds records into running analytics and drops them.
+impl RecordSink for AnalyticsAccumulator {
+    fn accept(&mut self, batch: Vec<ProcessedCallRecord>) -> Result<()> {
+        self.add_all(&batch);
+        Ok(())
+    }
+}
+
+/// What to do with a record repeated within one production.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DuplicatePolicy {
+    /// Keep every record as produced; the Data Quality tab still counts the repeats.
+    #[default]
+    Keep,
+    /// Drop records with the same target, number, start, and duration as an earlier one.
+    DropExact,
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 2] = [DuplicatePolicy::Keep, DuplicatePolicy::DropExact];
+
+    pub fn label(&self) -> String {
+        match self {
+            DuplicatePolicy::Keep => tr!("duplicates-keep"),
+            DuplicatePolicy::DropExact => tr!("duplicates-drop-exact"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineConfig {
+    /// Records per batch handed between stages.
+    pub batch_size: usize,
+    /// Batches each channel may hold before the upstream stage blocks.
+    pub channel_capacity: usize,
+    pub duplicates: DuplicatePolicy,
+    /// Pass over `<results>` blocks that are not well-formed XML, reporting them, instead of
+    /// failing the whole production at the first one.
+    pub recover_malformed: bool,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            duplicates: DuplicatePolicy::default(),
+            recover_malformed: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PipelineStats {
+    pub parsed: usize,
+    pub normalized: usize,
+    pub failed: usize,
+    /// Which records failed normalization, for surfacing to the user.
+    pub report: ParseReport,
+}
+
+/// Counts the bytes read through it, so how far into a file parsing is can be watched from
+/// another thread.
+pub struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R> CountingReader<R> {
+    pub fn new(inner: R, count: Arc<AtomicU64>) -> Self {
+        Self { inner, count }
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+/// How far through a file a streamed parse is, reported each time a batch reaches the sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseProgress {
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+    /// Records handed to the sink so far.
+    pub records: usize,
+}
+
+impl ParseProgress {
+    /// Share of the file read, when its size is known.
+    pub fn fraction(&self) -> Option<f32> {
+        (self.total_bytes > 0).then(|| (self.bytes_read as f32 / self.total_bytes as f32).min(1.0))
+    }
+}
+
+/// Passes batches on to the wrapped sink, reporting progress after each.
+struct ProgressReporting<'a, S, P> {
+    sink: &'a mut S,
+    on_progress: P,
+    bytes_read: Arc<AtomicU64>,
+    total_bytes: u64,
+    records: usize,
+}
+
+impl<S: RecordSink, P: FnMut(ParseProgress)> RecordSink for ProgressReporting<'_, S, P> {
+    fn accept(&mut self, batch: Vec<ProcessedCallRecord>) -> Result<()> {
+        self.records += batch.len();
+        self.sink.accept(batch)?;
+        (self.on_progress)(ParseProgress {
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            total_bytes: self.total_bytes,
+            records: self.records,
+        });
+        Ok(())
+    }
+}
+
+struct RawBatch {
+    header: ProductionHeader,
+    records: Vec<CallRecord>,
+    /// Blocks passed over since the last batch, in recovery mode.
+    malformed: Vec<MalformedBlock>,
+}
+
+/// Ingestion as three stages — parse, normalize, sink — joined by bounded channels.
+///
+/// Parsing and normalization run on their own threads; the sink runs on the calling
+/// thread. Because each channel holds at most `channel_capacity` batches, a slow sink
+/// back-pressures the parser and at most a few batches are in flight at once, no matter
+/// how large the production is.
+pub struct Pipeline;
+
+impl Pipeline {
+    pub fn run_file<S: RecordSink>(file_path: &Path, sink: &mut S, config: PipelineConfig) -> Result<PipelineStats> {
+        Self::run_file_counting(file_path, sink, config, Arc::new(AtomicU64::new(0)))
+    }
+
+    /// Like [`Pipeline::run_file`], adding the bytes read to `bytes_read` as parsing goes.
+    pub fn run_file_counting<S: RecordSink>(
+        file_path: &Path,
+        sink: &mut S,
+        config: PipelineConfig,
+        bytes_read: Arc<AtomicU64>,
+    ) -> Result<PipelineStats> {
+        info!("Streaming XML file: {:?}", file_path);
+
+        let file = File::open(file_path)
+            .with_context(|| format!("Failed to open file: {:?}", file_path))?;
+
+        let source_file = file_path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        Self::run(BufReader::new(CountingReader::new(file, bytes_read)), source_file, sink, config)
+    }
+
+    /// Like [`Pipeline::run_file`], calling `on_progress` on the calling thread each time a
+    /// batch has reached the sink, e.g. to drive a progress bar or a log line.
+    pub fn run_file_with_progress<S, P>(file_path: &Path, sink: &mut S, config: PipelineConfig, on_progress: P) -> Result<PipelineStats>
+    where
+        S: RecordSink,
+        P: FnMut(ParseProgress),
+    {
+        let total_bytes = std::fs::metadata(file_path).map(|metadata| metadata.len()).unwrap_or(0);
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let mut reporting = ProgressReporting { sink, on_progress, bytes_read: Arc::clone(&bytes_read), total_bytes, records: 0 };
+        Self::run_file_counting(file_path, &mut reporting, config, bytes_read)
+    }
+
+    pub fn run<R, S>(reader: R, source_file: String, sink: &mut S, config: PipelineConfig) -> Result<PipelineStats>
+    where
+        R: BufRead + Send + 'static,
+        S: RecordSink,
+    {
+        let batch_size = config.batch_size.max(1);
+        let (raw_tx, raw_rx) = sync_channel::<RawBatch>(config.channel_capacity);
+        let (processed_tx, processed_rx) = sync_channel::<Vec<ProcessedCallRecord>>(config.channel_capacity);
+
+        let recover = config.recover_malformed;
+        let parser = thread::spawn(move || Self::parse_stage(reader, raw_tx, batch_size, recover));
+        let duplicates = config.duplicates;
+        let normalizer = thread::spawn(move || Self::normalize_stage(raw_rx, processed_tx, &source_file, duplicates));
+
+        // Dropping the receiver on a sink error unblocks and stops the upstream stages
+        let sink_result = processed_rx.into_iter().try_for_each(|batch| sink.accept(batch));
+
+        let parsed = parser.join().map_err(|_| anyhow!("Parse stage panicked"))??;
+        let report = normalizer.join().map_err(|_| anyhow!("Normalize stage panicked"))?;
+        sink_result?;
+
+        let failed = report.skipped_count;
+        let normalized = report.total_records - failed;
+        info!("Pipeline finished: {} parsed, {} normalized, {} failed, {} duplicates dropped",
+            parsed, normalized, failed, report.duplicates_dropped);
+        log_discrepancies(&report);
+        Ok(PipelineStats { parsed, normalized, failed, report })
+    }
+
+    fn parse_stage<R: BufRead>(reader: R, tx: SyncSender<RawBatch>, batch_size: usize, recover: bool) -> Result<usize> {
+        let mut parsed = 0;
+        let mut sink_closed = false;
+        let mut batch = RawBatch {
+            header: ProductionHeader::default(),
+            records: Vec::with_capacity(batch_size),
+            malformed: Vec::new(),
+        };
+        // Malformed blocks wait here until the next batch goes, as the record callback has the batch
+        let malformed = RefCell::new(Vec::new());
+
+        let on_record = |header: &ProductionHeader, record: CallRecord| {
+            parsed += 1;
+
+            // A batch never mixes targets, so the header travels with the batch
+            let header_changed = batch.header != *header && !batch.records.is_empty();
+            if header_changed && !Self::send_batch(&tx, &mut batch, batch_size) {
+                sink_closed = true;
+            }
+            if batch.header != *header {
+                batch.header = header.clone();
+            }
+            batch.records.push(record);
+            batch.malformed.append(&mut malformed.borrow_mut());
+
+            if batch.records.len() >= batch_size && !Self::send_batch(&tx, &mut batch, batch_size) {
+                sink_closed = true;
+            }
+
+            if sink_closed {
+                Err(anyhow!("Pipeline closed before parsing finished"))
+            } else {
+                Ok(())
+            }
+        };
+        let result = if recover {
+            XmlParser::recover_call_records(reader, on_record, |block| malformed.borrow_mut().push(block))
+        } else {
+            XmlParser::stream_call_records(reader, on_record)
+        };
+
+        match result {
+            Ok(()) => {
+                batch.malformed.append(&mut malformed.borrow_mut());
+                if !batch.records.is_empty() || !batch.malformed.is_empty() {
+                    Self::send_batch(&tx, &mut batch, batch_size);
+                }
+                Ok(parsed)
+            }
+            // The sink stopped early; its own error is what gets reported
+            Err(_) if sink_closed => Ok(parsed),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sends the pending batch downstream. Returns false once the receiver has hung up.
+    fn send_batch(tx: &SyncSender<RawBatch>, batch: &mut RawBatch, batch_size: usize) -> bool {
+        let full = RawBatch {
+            header: batch.header.clone(),
+            records: std::mem::replace(&mut batch.records, Vec::with_capacity(batch_size)),
+            malformed: std::mem::take(&mut batch.malformed),
+        };
+        tx.send(full).is_ok()
+    }
+
+    fn normalize_stage(
+        rx: Receiver<RawBatch>,
+        tx: SyncSender<Vec<ProcessedCallRecord>>,
+        source_file: &str,
+        duplicates: DuplicatePolicy,
+    ) -> ParseReport {
+        let mut report = ParseReport::new(source_file);
+        // Only filled when dropping duplicates, so keeping them costs no memory
+        let mut seen: HashSet<(String, String, DateTime<Utc>, u32)> = HashSet::new();
+
+        for batch in rx {
+            report.declare(&batch.header);
+            for block in batch.malformed {
+                warn!("Skipped malformed record at line {}: {}", block.line, block.reason);
+                report.record_malformed(block);
+            }
+            // Normalized like the other parsers' targets, so one target matches across formats
+            let target = target_number(&batch.header.target_value);
+            let mut processed = Vec::with_capacity(batch.records.len());
+            for call_record in &batch.records {
+                match ProcessedCallRecord::from_call_record(call_record, &target, source_file) {
+                    Ok(record) => {
+                        report.record_parsed(&record);
+                        if duplicates == DuplicatePolicy::DropExact {
+                            let key = (record.target_number.clone(), record.normalized_number.clone(), record.start_time, record.length_of_call);
+                            if !seen.insert(key) {
+                                report.duplicates_dropped += 1;
+                                continue;
+                            }
+                        }
+                        processed.push(record);
+                    }
+                    Err(e) => {
+                        warn!("Failed to process call record: {}", e);
+                        report.record_skipped(call_record, &target, e.to_string());
+                    }
+                }
+            }
+
+            if tx.send(processed).is_err() {
+                break;
+            }
+        }
+
+        report
+    }
+}
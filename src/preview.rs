@@ -0,0 +1,123 @@
+use crate::data_models::ProductionHeader;
+use crate::tr;
+use crate::xml_parser::XmlParser;
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a production to preview it.
+pub const PREVIEW_SAMPLE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Productions at least this large are previewed before parsing unless configured otherwise.
+pub const DEFAULT_PREVIEW_THRESHOLD_MB: u64 = 100;
+
+/// How a production's XML is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductionFormat {
+    /// LDS-101 results wrapped in a `<dataProduct>`, as eSubpoena returns them.
+    DataProduct,
+    /// LDS-101 results without the wrapper.
+    Lds101,
+    /// No call records were found in the sample.
+    Unrecognized,
+}
+
+impl ProductionFormat {
+    pub fn label(&self) -> String {
+        match self {
+            ProductionFormat::DataProduct => tr!("format-data-product"),
+            ProductionFormat::Lds101 => tr!("format-lds101"),
+            ProductionFormat::Unrecognized => tr!("format-unrecognized"),
+        }
+    }
+}
+
+/// What a quick look at the start of a production shows, so a very large file can be
+/// checked (right target? right period?) before the full parse is started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductionPreview {
+    pub source_file: String,
+    pub file_size: u64,
+    pub format: ProductionFormat,
+    /// The target and coverage declared ahead of the first record in the sample.
+    pub header: ProductionHeader,
+    pub sampled_records: usize,
+    /// Records in the whole file, extrapolated from the sample's bytes per record; exact
+    /// when the whole file fit in the sample.
+    pub estimated_records: usize,
+    pub complete: bool,
+}
+
+impl ProductionPreview {
+    /// Reads the first [`PREVIEW_SAMPLE_BYTES`] of the file.
+    pub fn read_file(path: &Path) -> Result<Self> {
+        let file_size = fs::metadata(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?
+            .len();
+        let mut sample = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(PREVIEW_SAMPLE_BYTES).read_to_end(&mut sample))
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::from_sample(source_file, &sample, file_size)
+    }
+
+    /// Previews a production from its first bytes and its total size.
+    pub fn from_sample(source_file: &str, sample: &[u8], file_size: u64) -> Result<Self> {
+        let complete = sample.len() as u64 >= file_size;
+        let mut header = ProductionHeader::default();
+        let mut sampled_records = 0;
+        let result = XmlParser::stream_call_records(sample, |record_header, _| {
+            if sampled_records == 0 {
+                header = record_header.clone();
+            }
+            sampled_records += 1;
+            Ok(())
+        });
+        // A sample cut off mid-document fails at the cut; the records before it still count
+        if complete {
+            result?;
+        }
+
+        let format = if sampled_records == 0 {
+            ProductionFormat::Unrecognized
+        } else if root_element(sample).as_deref() == Some("dataProduct") {
+            ProductionFormat::DataProduct
+        } else {
+            ProductionFormat::Lds101
+        };
+        let estimated_records = if complete || sample.is_empty() {
+            sampled_records
+        } else {
+            (sampled_records as f64 * file_size as f64 / sample.len() as f64).round() as usize
+        };
+        Ok(Self {
+            source_file: source_file.to_string(),
+            file_size,
+            format,
+            header,
+            sampled_records,
+            estimated_records,
+            complete,
+        })
+    }
+}
+
+/// Name of the document's first element.
+fn root_element(sample: &[u8]) -> Option<String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(sample);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf).ok()? {
+            Event::Start(e) | Event::Empty(e) => return Some(String::from_utf8_lossy(e.name().as_ref()).into_owned()),
+            Event::Eof => return None,
+            _ => buf.clear(),
+        }
+    }
+}
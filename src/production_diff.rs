@@ -0,0 +1,172 @@
+use crate::data_models::ProcessedCallRecord;
+use crate::settings::AppSettings;
+use crate::tr;
+use std::collections::HashMap;
+
+/// A corrected record whose start moved by more than this is treated as a removal plus an
+/// addition rather than a time correction.
+const MAX_TIME_CORRECTION_SECS: i64 = 15 * 60;
+
+/// A record both productions have, with its time or duration corrected.
+#[derive(Debug, Clone)]
+pub struct RecordChange {
+    pub original: ProcessedCallRecord,
+    pub corrected: ProcessedCallRecord,
+}
+
+impl RecordChange {
+    /// How far the start time moved, in seconds; positive when the correction is later.
+    pub fn time_shift_secs(&self) -> i64 {
+        (self.corrected.start_time - self.original.start_time).num_seconds()
+    }
+
+    /// How much the duration changed, in seconds.
+    pub fn duration_change_secs(&self) -> i64 {
+        i64::from(self.corrected.length_of_call) - i64::from(self.original.length_of_call)
+    }
+}
+
+/// What changed between a production and the carrier's re-production of it (a corrected or
+/// supplemental return) covering the same period.
+///
+/// Records with the same target, number, direction, start, and duration are unchanged. Of
+/// the rest, a record is changed when the other production has one with the same target,
+/// number, and direction starting within [`MAX_TIME_CORRECTION_SECS`]; the nearest is paired.
+/// Anything left is removed (only in the original) or added (only in the corrected one).
+#[derive(Debug, Clone)]
+pub struct ProductionDiff {
+    pub original: String,
+    pub corrected: String,
+    pub unchanged: usize,
+    /// Sorted by start time, as are `removed` and `changed` (by original start).
+    pub added: Vec<ProcessedCallRecord>,
+    pub removed: Vec<ProcessedCallRecord>,
+    pub changed: Vec<RecordChange>,
+}
+
+impl ProductionDiff {
+    /// Compares the records loaded from source file `original` with those from `corrected`.
+    pub fn compare(records: &[ProcessedCallRecord], original: &str, corrected: &str) -> Self {
+        let mut originals: Vec<&ProcessedCallRecord> = records.iter().filter(|r| r.source_file == original).collect();
+        let mut corrections: Vec<&ProcessedCallRecord> = records.iter().filter(|r| r.source_file == corrected).collect();
+        originals.sort_by_key(|r| r.start_time);
+        corrections.sort_by_key(|r| r.start_time);
+
+        // Exact matches first, counting duplicates so each record pairs at most once
+        let mut available: HashMap<(String, String), usize> = HashMap::new();
+        for record in &corrections {
+            *available.entry(identity(record)).or_insert(0) += 1;
+        }
+        let mut matched: HashMap<(String, String), usize> = HashMap::new();
+        let mut unmatched_originals = Vec::new();
+        for record in originals {
+            let key = identity(record);
+            match available.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    *matched.entry(key).or_insert(0) += 1;
+                }
+                _ => unmatched_originals.push(record),
+            }
+        }
+        let unchanged = matched.values().sum();
+        let mut unmatched_corrections: Vec<&ProcessedCallRecord> = Vec::new();
+        for record in corrections {
+            match matched.get_mut(&identity(record)) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => unmatched_corrections.push(record),
+            }
+        }
+
+        // Then corrections: the nearest same-party record within the window
+        let mut by_party: HashMap<(&str, &str, String), Vec<&ProcessedCallRecord>> = HashMap::new();
+        for record in unmatched_corrections {
+            by_party.entry(party(record)).or_default().push(record);
+        }
+        let mut changed = Vec::new();
+        let mut removed = Vec::new();
+        for record in unmatched_originals {
+            let candidates = by_party.get_mut(&party(record));
+            let nearest = candidates.as_ref().and_then(|candidates| {
+                candidates.iter().enumerate()
+                    .map(|(i, c)| (i, (c.start_time - record.start_time).num_seconds().abs()))
+                    .filter(|(_, shift)| *shift <= MAX_TIME_CORRECTION_SECS)
+                    .min_by_key(|(_, shift)| *shift)
+                    .map(|(i, _)| i)
+            });
+            match (candidates, nearest) {
+                (Some(candidates), Some(i)) => {
+                    let corrected = candidates.remove(i);
+                    changed.push(RecordChange { original: record.clone(), corrected: corrected.clone() });
+                }
+                _ => removed.push(record.clone()),
+            }
+        }
+        let mut added: Vec<ProcessedCallRecord> = by_party.into_values().flatten().cloned().collect();
+        added.sort_by_key(|r| r.start_time);
+
+        Self { original: original.to_string(), corrected: corrected.to_string(), unchanged, added, removed, changed }
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// A plain-text report documenting the supplemental production: the counts, then every
+    /// added, removed, and corrected record.
+    pub fn to_text(&self, settings: &AppSettings) -> String {
+        let timezone = settings.case_timezone;
+        let describe = |record: &ProcessedCallRecord| {
+            tr!("diff-record",
+                time = record.start_time.with_timezone(&timezone).format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+                direction = record.message_direction.as_str(),
+                number = settings.format_number(&record.normalized_number),
+                target = settings.format_number(&record.target_number),
+                seconds = record.length_of_call)
+        };
+
+        let mut text = format!("=== {} ===\n", tr!("diff-title"));
+        text.push_str(&format!("{}\n", tr!("diff-original", name = self.original.as_str())));
+        text.push_str(&format!("{}\n", tr!("diff-corrected", name = self.corrected.as_str())));
+        text.push_str(&format!("{}\n", tr!("diff-counts",
+            unchanged = self.unchanged, added = self.added.len(), removed = self.removed.len(), changed = self.changed.len())));
+        if self.is_identical() {
+            text.push_str(&format!("\n{}\n", tr!("diff-identical")));
+            return text;
+        }
+
+        if !self.added.is_empty() {
+            text.push_str(&format!("\n=== {} ===\n", tr!("diff-added-heading", count = self.added.len())));
+            for record in &self.added {
+                text.push_str(&format!("+ {}\n", describe(record)));
+            }
+        }
+        if !self.removed.is_empty() {
+            text.push_str(&format!("\n=== {} ===\n", tr!("diff-removed-heading", count = self.removed.len())));
+            for record in &self.removed {
+                text.push_str(&format!("- {}\n", describe(record)));
+            }
+        }
+        if !self.changed.is_empty() {
+            text.push_str(&format!("\n=== {} ===\n", tr!("diff-changed-heading", count = self.changed.len())));
+            for change in &self.changed {
+                text.push_str(&format!("~ {}\n", describe(&change.original)));
+                text.push_str(&format!("  {}\n", tr!("diff-correction",
+                    record = describe(&change.corrected),
+                    shift = change.time_shift_secs(),
+                    duration = change.duration_change_secs())));
+            }
+        }
+        text
+    }
+}
+
+/// The fields that must all match for a record to count as unchanged.
+fn identity(record: &ProcessedCallRecord) -> (String, String) {
+    (record.record_key(), record.message_direction.to_lowercase())
+}
+
+/// The fields a time or duration correction leaves alone.
+fn party(record: &ProcessedCallRecord) -> (&str, &str, String) {
+    (record.target_number.as_str(), record.normalized_number.as_str(), record.message_direction.to_lowercase())
+}
@@ -0,0 +1,120 @@
+use crate::data_models::ProcessedCallRecord;
+use crate::tr;
+use chrono::{DateTime, Utc};
+
+/// How far a call's stated duration may be from its end minus its start before it is
+/// flagged. Carriers round billed durations up to the minute.
+pub const DURATION_TOLERANCE_SECONDS: i64 = 60;
+
+/// Flagged records kept with their issues for the Data Quality tab; the rest are counted.
+pub const MAX_FLAGGED_DETAILS: usize = 1000;
+
+/// Why a parsed record looks wrong, though it read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RecordIssue {
+    EndBeforeStart,
+    /// The duration disagrees with the start and end times by more than
+    /// [`DURATION_TOLERANCE_SECONDS`]. Records whose end is their start, as for messages or
+    /// productions without end times, are not checked.
+    DurationMismatch,
+    /// No remote number at all, as opposed to a withheld one.
+    MissingRemoteNumber,
+    /// Starts after the records were checked, usually a mistyped year or a clock error.
+    FutureDate,
+}
+
+impl RecordIssue {
+    pub const ALL: [RecordIssue; 4] = [
+        RecordIssue::EndBeforeStart,
+        RecordIssue::DurationMismatch,
+        RecordIssue::MissingRemoteNumber,
+        RecordIssue::FutureDate,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            RecordIssue::EndBeforeStart => tr!("record-issue-end-before-start"),
+            RecordIssue::DurationMismatch => tr!("record-issue-duration-mismatch"),
+            RecordIssue::MissingRemoteNumber => tr!("record-issue-missing-remote"),
+            RecordIssue::FutureDate => tr!("record-issue-future-date"),
+        }
+    }
+
+    fn applies(&self, record: &ProcessedCallRecord, now: DateTime<Utc>) -> bool {
+        match self {
+            RecordIssue::EndBeforeStart => record.end_time < record.start_time,
+            RecordIssue::DurationMismatch => {
+                let elapsed = (record.end_time - record.start_time).num_seconds();
+                record.end_time > record.start_time
+                    && (elapsed - record.length_of_call as i64).abs() > DURATION_TOLERANCE_SECONDS
+            }
+            RecordIssue::MissingRemoteNumber => record.remote_number.trim().is_empty(),
+            RecordIssue::FutureDate => record.start_time > now,
+        }
+    }
+
+    /// Every issue with `record`, judged as of `now`.
+    ///
+    /// ```
+    /// use esubpoena_tolls_tool::data_models::{CallRecord, ProcessedCallRecord};
+    /// use esubpoena_tolls_tool::record_warnings::RecordIssue;
+    ///
+    /// let call = CallRecord {
+    ///     message_direction: "outgoing".into(),
+    ///     remote_number: "".into(),
+    ///     start_time: "2024-01-01T10:00:00Z".into(),
+    ///     end_time: "2024-01-01T10:05:00Z".into(),
+    ///     length_of_call: 30,
+    ///     ..CallRecord::default()
+    /// };
+    /// let record = ProcessedCallRecord::from_call_record(&call, "2565550111", "january.xml").unwrap();
+    /// assert_eq!(RecordIssue::of(&record, chrono::Utc::now()), [RecordIssue::DurationMismatch, RecordIssue::MissingRemoteNumber]);
+    /// ```
+    pub fn of(record: &ProcessedCallRecord, now: DateTime<Utc>) -> Vec<Self> {
+        Self::ALL.into_iter().filter(|issue| issue.applies(record, now)).collect()
+    }
+
+    /// Whether `record` has any issue, without collecting them.
+    pub fn any(record: &ProcessedCallRecord, now: DateTime<Utc>) -> bool {
+        Self::ALL.iter().any(|issue| issue.applies(record, now))
+    }
+}
+
+/// What the validation pass found in the loaded records.
+#[derive(Debug, Clone, Default)]
+pub struct RecordValidation {
+    /// Records with at least one issue.
+    pub flagged: usize,
+    /// Records with each issue, in [`RecordIssue::ALL`] order.
+    pub counts: [usize; 4],
+    /// The first [`MAX_FLAGGED_DETAILS`] flagged records, with their issues.
+    pub records: Vec<(ProcessedCallRecord, Vec<RecordIssue>)>,
+}
+
+impl RecordValidation {
+    pub fn validate(records: &[ProcessedCallRecord], now: DateTime<Utc>) -> Self {
+        let mut validation = Self::default();
+        for record in records {
+            let issues = RecordIssue::of(record, now);
+            if issues.is_empty() {
+                continue;
+            }
+            validation.flagged += 1;
+            for issue in &issues {
+                validation.counts[*issue as usize] += 1;
+            }
+            if validation.records.len() < MAX_FLAGGED_DETAILS {
+                validation.records.push((record.clone(), issues));
+            }
+        }
+        validation
+    }
+
+    pub fn count(&self, issue: RecordIssue) -> usize {
+        self.counts[issue as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flagged == 0
+    }
+}
@@ -0,0 +1,569 @@
+use crate::analytics::format_prefix;
+use crate::annotations::Annotations;
+use crate::data_models::{normalize_phone_number, Analytics, ParseReport, ProcessedCallRecord};
+use crate::data_quality::DataQualityReport;
+use crate::filters::is_night_hour;
+use crate::findings::key_findings;
+use crate::pdf::{self, PdfPages, PdfStyle};
+use crate::settings::AppSettings;
+use crate::tr;
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Contacts and busiest days listed in the email digest.
+const DIGEST_TOP_CONTACTS: usize = 5;
+const DIGEST_BUSIEST_DAYS: usize = 3;
+
+/// Parts a report can be composed of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSection {
+    /// The narrative template from settings with its placeholders filled in.
+    Narrative,
+    /// Plain-language findings generated from the analytics.
+    KeyFindings,
+    SummaryStats,
+    TopContacts,
+    Targets,
+    CommonContacts,
+    ExchangeClusters,
+    NightActivity,
+    Bookmarks,
+    CallsByDay,
+    CallsByHour,
+    DataQuality,
+}
+
+impl ReportSection {
+    pub const ALL: [ReportSection; 12] = [
+        ReportSection::Narrative,
+        ReportSection::KeyFindings,
+        ReportSection::SummaryStats,
+        ReportSection::TopContacts,
+        ReportSection::Targets,
+        ReportSection::CommonContacts,
+        ReportSection::ExchangeClusters,
+        ReportSection::NightActivity,
+        ReportSection::Bookmarks,
+        ReportSection::CallsByDay,
+        ReportSection::CallsByHour,
+        ReportSection::DataQuality,
+    ];
+
+    /// The summary report as it was before reports could be composed.
+    pub const DEFAULT: [ReportSection; 8] = [
+        ReportSection::SummaryStats,
+        ReportSection::TopContacts,
+        ReportSection::Targets,
+        ReportSection::CommonContacts,
+        ReportSection::ExchangeClusters,
+        ReportSection::Bookmarks,
+        ReportSection::CallsByDay,
+        ReportSection::CallsByHour,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            ReportSection::Narrative => tr!("section-narrative"),
+            ReportSection::KeyFindings => tr!("section-key-findings"),
+            ReportSection::SummaryStats => tr!("section-summary-stats"),
+            ReportSection::TopContacts => tr!("section-top-contacts"),
+            ReportSection::Targets => tr!("section-targets"),
+            ReportSection::CommonContacts => tr!("section-common-contacts"),
+            ReportSection::ExchangeClusters => tr!("section-exchange-clusters"),
+            ReportSection::NightActivity => tr!("section-night-activity"),
+            ReportSection::Bookmarks => tr!("section-bookmarks"),
+            ReportSection::CallsByDay => tr!("section-calls-by-day"),
+            ReportSection::CallsByHour => tr!("section-calls-by-hour"),
+            ReportSection::DataQuality => tr!("section-data-quality"),
+        }
+    }
+}
+
+/// File formats a report can be saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Pdf,
+    /// RTF, which Word opens natively.
+    Word,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [ReportFormat::Text, ReportFormat::Pdf, ReportFormat::Word];
+
+    pub fn label(&self) -> String {
+        match self {
+            ReportFormat::Text => tr!("report-format-text"),
+            ReportFormat::Pdf => tr!("report-format-pdf"),
+            ReportFormat::Word => tr!("report-format-word"),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Word => "rtf",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "text/plain",
+            ReportFormat::Pdf => "application/pdf",
+            ReportFormat::Word => "application/rtf",
+        }
+    }
+}
+
+/// Everything a report section may draw on.
+pub struct ReportInput<'a> {
+    pub analytics: &'a Analytics,
+    pub records: &'a [ProcessedCallRecord],
+    pub annotations: &'a Annotations,
+    pub settings: &'a AppSettings,
+    /// Parse reports for the data-quality section; without them it counts loaded records only.
+    pub parse_reports: &'a [ParseReport],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportBlock {
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+/// A report rendered once and then written out as text, RTF, PDF, or an Excel sheet, so
+/// every format carries the same content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub title: String,
+    /// Sections with nothing to show (no bookmarks, no common contacts, ...) are left out.
+    pub blocks: Vec<ReportBlock>,
+}
+
+impl Report {
+    pub fn compose(sections: &[ReportSection], input: &ReportInput) -> Self {
+        let blocks = sections.iter()
+            .filter_map(|section| {
+                let lines = section_lines(*section, input);
+                (!lines.is_empty()).then(|| ReportBlock { heading: section_heading(*section, input.analytics), lines })
+            })
+            .collect();
+        Self { title: tr!("report-title"), blocks }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> Vec<u8> {
+        match format {
+            ReportFormat::Text => self.to_text().into_bytes(),
+            ReportFormat::Pdf => self.to_pdf(),
+            ReportFormat::Word => self.to_rtf().into_bytes(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("=== {} ===\n", self.title);
+        for block in &self.blocks {
+            text.push_str(&format!("\n=== {} ===\n", block.heading));
+            for line in &block.lines {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Rich Text Format, which Word opens as a document.
+    pub fn to_rtf(&self) -> String {
+        let mut rtf = String::from("{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\fswiss Calibri;}}\\fs22\n");
+        rtf.push_str(&format!("{{\\b\\fs32 {}\\par}}\n", rtf_escape(&self.title)));
+        for block in &self.blocks {
+            rtf.push_str(&format!("\\par{{\\b\\fs26 {}\\par}}\n", rtf_escape(&block.heading)));
+            for line in &block.lines {
+                rtf.push_str(&format!("{}\\par\n", rtf_escape(line)));
+            }
+        }
+        rtf.push('}');
+        rtf
+    }
+
+    /// A plain US Letter PDF using the standard Helvetica fonts, so no fonts are embedded.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut pages = PdfPages::new();
+        self.lay_out(&mut pages);
+        pdf::write_pdf(&pages.finish())
+    }
+
+    /// Lays the title and blocks out as PDF lines, wrapping long ones.
+    pub(crate) fn lay_out(&self, pages: &mut PdfPages) {
+        pages.line(PdfStyle::Title, &self.title);
+        for block in &self.blocks {
+            pages.line(PdfStyle::Body, "");
+            pages.line(PdfStyle::Heading, &block.heading);
+            for line in &block.lines {
+                for wrapped in pdf::wrap(line, pdf::LINE_CHARS) {
+                    pages.line(PdfStyle::Body, &wrapped);
+                }
+            }
+        }
+    }
+}
+
+/// A short plain-text digest for pasting into an update email to the case agent: the top
+/// contacts with any known names, key dates, calls between targets, and the files to attach.
+pub fn email_digest(input: &ReportInput, attachments: &[String]) -> String {
+    let ReportInput { analytics, records, annotations, settings, .. } = *input;
+    let locale = settings.export_locale();
+    let day = |time: &DateTime<Utc>| locale.format_date(&time.with_timezone(&analytics.timezone));
+    let mut text = tr!("digest-overview",
+        calls = analytics.total_calls,
+        targets = analytics.target_numbers.len(),
+        from = day(&analytics.date_range.0),
+        to = day(&analytics.date_range.1),
+        timezone = analytics.timezone.name());
+    text.push('\n');
+    let mut push_block = |heading: String, lines: Vec<String>| {
+        text.push_str(&format!("\n{}\n", heading));
+        for line in lines {
+            text.push_str(&format!("{}\n", line));
+        }
+    };
+
+    let contacts = analytics.most_frequent_numbers.iter().take(DIGEST_TOP_CONTACTS).enumerate()
+        .map(|(i, (number, count))| {
+            let name = annotations.alias(number)
+                .or_else(|| annotations.subscribers.lookup(number).map(|s| s.name.as_str()));
+            let contact = match name {
+                Some(name) => tr!("digest-named-contact", number = settings.format_number(number), name = name, count = *count),
+                None => tr!("report-number-calls", number = settings.format_number(number), count = *count),
+            };
+            format!("{}. {}", i + 1, contact)
+        })
+        .collect();
+    push_block(tr!("digest-top-contacts"), contacts);
+
+    let mut busiest: Vec<(&String, &usize)> = analytics.calls_by_day.iter().collect();
+    busiest.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let mut dates = vec![
+        format!("• {}", tr!("digest-first-call", date = day(&analytics.date_range.0))),
+        format!("• {}", tr!("digest-last-call", date = day(&analytics.date_range.1))),
+    ];
+    dates.extend(busiest.into_iter().take(DIGEST_BUSIEST_DAYS)
+        .map(|(date, count)| format!("• {}", tr!("digest-busiest-day", date = locale.format_day_key(date), count = *count))));
+    push_block(tr!("digest-key-dates"), dates);
+
+    let direct: Vec<String> = direct_target_contacts(records).into_iter()
+        .map(|((a, b), times)| {
+            let first = times.iter().next().map(&day).unwrap_or_default();
+            let last = times.iter().next_back().map(&day).unwrap_or_default();
+            format!("• {}", tr!("digest-direct-contact",
+                a = settings.format_number(&a), b = settings.format_number(&b),
+                count = times.len(), first = first, last = last))
+        })
+        .collect();
+    if direct.is_empty() {
+        push_block(tr!("digest-direct-contacts"), vec![tr!("digest-no-direct-contacts")]);
+    } else {
+        push_block(tr!("digest-direct-contacts"), direct);
+    }
+
+    let mut files: Vec<String> = attachments.iter().map(|name| format!("• {}", name)).collect();
+    let mut sources: Vec<&str> = analytics.files_processed.iter().map(String::as_str).collect();
+    sources.sort();
+    if !sources.is_empty() {
+        files.push(tr!("digest-sources", files = sources.join(", ")));
+    }
+    push_block(tr!("digest-attachments"), files);
+    text
+}
+
+/// Calls between two loaded targets, by pair, with their start times. A call in both targets'
+/// records is counted once.
+pub(crate) fn direct_target_contacts(records: &[ProcessedCallRecord]) -> BTreeMap<(String, String), BTreeSet<DateTime<Utc>>> {
+    let mut normalized_targets: HashMap<&str, String> = HashMap::new();
+    for record in records {
+        normalized_targets.entry(record.target_number.as_str())
+            .or_insert_with(|| normalize_phone_number(&record.target_number).into_string());
+    }
+    let targets: BTreeSet<&str> = normalized_targets.values().map(String::as_str).collect();
+    let mut pairs: BTreeMap<(String, String), BTreeSet<DateTime<Utc>>> = BTreeMap::new();
+    for record in records {
+        let target = normalized_targets[record.target_number.as_str()].as_str();
+        let remote = record.normalized_number.as_str();
+        if remote != target && targets.contains(remote) {
+            let pair = if target < remote { (target, remote) } else { (remote, target) };
+            pairs.entry((pair.0.to_string(), pair.1.to_string())).or_default().insert(record.start_time);
+        }
+    }
+    pairs
+}
+
+/// Placeholders a narrative template can use, for the template editor's help text. Top
+/// contacts are numbered from 1 up to the number of most-frequent numbers.
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "total_calls", "incoming_calls", "outgoing_calls", "unique_numbers", "total_minutes",
+    "date_range", "first_date", "last_date", "timezone", "targets", "files",
+    "top_contact_1", "top_contact_1_calls", "night_calls", "night_call_pct",
+];
+
+/// Fills every `{{placeholder}}` in `template` from the case, e.g. `{{total_calls}}` or
+/// `{{top_contact_2}}`, so agencies can keep their standard affidavit language and have the
+/// figures written in. A top contact beyond the ranking is left blank; unknown placeholders
+/// are left as written so a typo shows in the report.
+pub fn fill_template(template: &str, input: &ReportInput) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + length].trim();
+        filled.push_str(&rest[..start]);
+        match placeholder_value(name, input) {
+            Some(value) => filled.push_str(&value),
+            None => filled.push_str(&rest[start..start + length + 4]),
+        }
+        rest = &rest[start + length + 4..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Placeholders in `template` that [`fill_template`] does not know, in order of appearance.
+pub fn unknown_placeholders(template: &str, input: &ReportInput) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + length].trim();
+        if placeholder_value(name, input).is_none() && !unknown.iter().any(|n| n == name) {
+            unknown.push(name.to_string());
+        }
+        rest = &rest[start + length + 4..];
+    }
+    unknown
+}
+
+fn placeholder_value(name: &str, input: &ReportInput) -> Option<String> {
+    let ReportInput { analytics, records, settings, .. } = *input;
+    let locale = settings.export_locale();
+    let day = |time: &DateTime<Utc>| locale.format_date(&time.with_timezone(&analytics.timezone));
+    let value = match name {
+        "total_calls" => analytics.total_calls.to_string(),
+        "incoming_calls" => analytics.incoming_calls.to_string(),
+        "outgoing_calls" => analytics.outgoing_calls.to_string(),
+        "unique_numbers" => analytics.unique_numbers.to_string(),
+        "total_minutes" => locale.format_decimal(analytics.total_duration_minutes, 1),
+        "date_range" => tr!("template-date-range", from = day(&analytics.date_range.0), to = day(&analytics.date_range.1)),
+        "first_date" => day(&analytics.date_range.0),
+        "last_date" => day(&analytics.date_range.1),
+        "timezone" => analytics.timezone.name().to_string(),
+        "targets" => analytics.target_numbers.iter().map(|t| settings.format_number(t)).collect::<Vec<_>>().join(", "),
+        "files" => analytics.files_processed.len().to_string(),
+        "night_calls" => night_call_count(input).to_string(),
+        "night_call_pct" => {
+            let pct = if records.is_empty() { 0.0 } else { night_call_count(input) as f64 * 100.0 / records.len() as f64 };
+            format!("{}%", locale.format_decimal(pct, 1))
+        }
+        _ => {
+            let rank = name.strip_prefix("top_contact_")?;
+            let (rank, calls) = match rank.strip_suffix("_calls") {
+                Some(rank) => (rank, true),
+                None => (rank, false),
+            };
+            let rank: usize = rank.parse().ok().filter(|&rank| rank > 0)?;
+            match analytics.most_frequent_numbers.get(rank - 1) {
+                Some((_, count)) if calls => count.to_string(),
+                Some((number, _)) => settings.format_number(number),
+                None => String::new(),
+            }
+        }
+    };
+    Some(value)
+}
+
+/// Calls starting within the night hours in the analytics timezone.
+fn night_call_count(input: &ReportInput) -> usize {
+    input.records.iter()
+        .filter(|r| is_night_hour(r.start_time.with_timezone(&input.analytics.timezone).hour(), input.settings.night_hours))
+        .count()
+}
+
+fn section_heading(section: ReportSection, analytics: &Analytics) -> String {
+    let timezone = analytics.timezone.name();
+    match section {
+        ReportSection::Narrative => tr!("report-narrative"),
+        ReportSection::KeyFindings => tr!("report-key-findings"),
+        ReportSection::SummaryStats => tr!("report-summary-stats"),
+        ReportSection::TopContacts => tr!("report-most-frequent"),
+        ReportSection::Targets => tr!("report-targets"),
+        ReportSection::CommonContacts => tr!("report-common-contacts"),
+        ReportSection::ExchangeClusters => tr!("report-exchange-clusters"),
+        ReportSection::NightActivity => tr!("report-night-activity", timezone = timezone),
+        ReportSection::Bookmarks => tr!("report-bookmarks"),
+        ReportSection::CallsByDay => tr!("report-calls-by-day", timezone = timezone),
+        ReportSection::CallsByHour => tr!("report-calls-by-hour", timezone = timezone),
+        ReportSection::DataQuality => tr!("quality-title"),
+    }
+}
+
+fn section_lines(section: ReportSection, input: &ReportInput) -> Vec<String> {
+    let ReportInput { analytics, records, annotations, settings, parse_reports } = *input;
+    let locale = settings.export_locale();
+    let mut lines = Vec::new();
+    match section {
+        ReportSection::Narrative => {
+            if !settings.report_template.trim().is_empty() {
+                lines = fill_template(&settings.report_template, input).lines().map(str::to_string).collect();
+            }
+        }
+        ReportSection::KeyFindings => {
+            lines = key_findings(input).into_iter().map(|finding| format!("• {}", finding)).collect();
+        }
+        ReportSection::SummaryStats => {
+            lines.push(tr!("report-total-calls", count = analytics.total_calls));
+            lines.push(tr!("report-incoming-calls", count = analytics.incoming_calls));
+            lines.push(tr!("report-outgoing-calls", count = analytics.outgoing_calls));
+            lines.push(tr!("report-unique-numbers", count = analytics.unique_numbers));
+            lines.push(tr!("report-unknown-numbers", count = analytics.unknown_number_calls));
+            if analytics.short_code_calls > 0 {
+                lines.push(tr!("report-short-code-calls", count = analytics.short_code_calls));
+            }
+            for (disposition, count) in &analytics.calls_by_disposition {
+                lines.push(tr!("report-disposition-calls", disposition = disposition.label(), count = *count));
+            }
+            lines.push(tr!("report-target-numbers", count = analytics.target_numbers.len()));
+            lines.push(tr!("report-files-processed", count = analytics.files_processed.len()));
+            lines.push(tr!("report-total-duration", minutes = locale.format_decimal(analytics.total_duration_minutes, 2)));
+            lines.push(tr!("report-average-duration", minutes = locale.format_decimal(analytics.average_call_duration, 2)));
+            if let Some(longest) = &analytics.longest_call {
+                lines.push(tr!("report-longest-call",
+                    seconds = longest.length_of_call,
+                    minutes = locale.format_decimal(longest.duration_minutes, 2),
+                    number = settings.format_number(&longest.normalized_number),
+                    date = locale.format_date(&longest.start_time)));
+            }
+            if let Some(shortest) = &analytics.shortest_call {
+                lines.push(tr!("report-shortest-call",
+                    seconds = shortest.length_of_call,
+                    number = settings.format_number(&shortest.normalized_number),
+                    date = locale.format_date(&shortest.start_time)));
+            }
+            lines.push(tr!("report-date-range",
+                from = locale.format_date(&analytics.date_range.0),
+                to = locale.format_date(&analytics.date_range.1)));
+        }
+        ReportSection::TopContacts => {
+            for (i, (number, count)) in analytics.most_frequent_numbers.iter().enumerate() {
+                lines.push(format!("{}. {}", i + 1, tr!("report-number-calls", number = settings.format_number(number), count = *count)));
+            }
+        }
+        ReportSection::Targets => {
+            let mut calls_per_target: HashMap<&str, usize> = HashMap::new();
+            for record in records {
+                *calls_per_target.entry(record.target_number.as_str()).or_insert(0) += 1;
+            }
+            for target_num in &analytics.target_numbers {
+                let count = calls_per_target.get(target_num.as_str()).copied().unwrap_or(0);
+                lines.push(format!("• {}", tr!("report-number-calls", number = settings.format_number(target_num), count = count)));
+            }
+            if !analytics.excluded_targets.is_empty() {
+                let mut excluded: Vec<_> = analytics.excluded_targets.iter().map(|t| settings.format_number(t)).collect();
+                excluded.sort();
+                lines.push(tr!("report-excluded-targets", targets = excluded.join(", ")));
+            }
+        }
+        ReportSection::CommonContacts => {
+            for contact in &analytics.common_contacts {
+                let target_nums = contact.target_numbers.iter()
+                    .map(|t| settings.format_number(t))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!("• {}", tr!("report-common-contact",
+                    number = settings.format_number(&contact.number), count = contact.count, targets = target_nums)));
+            }
+        }
+        ReportSection::ExchangeClusters => {
+            for cluster in &analytics.exchange_clusters {
+                let numbers = cluster.numbers.iter()
+                    .map(|(number, _)| settings.format_number(number))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!("• {}", tr!("report-exchange-cluster",
+                    prefix = format_prefix(&cluster.prefix), count = cluster.numbers.len(),
+                    calls = cluster.total_calls, numbers = numbers)));
+            }
+        }
+        ReportSection::NightActivity => {
+            let mut night_calls: HashMap<&str, usize> = HashMap::new();
+            let mut total = 0;
+            for record in records {
+                if is_night_hour(record.start_time.with_timezone(&analytics.timezone).hour(), settings.night_hours) {
+                    total += 1;
+                    if record.number_kind.is_contact(settings.include_short_codes) {
+                        *night_calls.entry(record.normalized_number.as_str()).or_insert(0) += 1;
+                    }
+                }
+            }
+            lines.push(tr!("report-night-calls",
+                count = total,
+                from = locale.format_hour(settings.night_hours.0),
+                to = locale.format_hour(settings.night_hours.1)));
+            let mut ranked: Vec<(&str, usize)> = night_calls.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            for (i, (number, count)) in ranked.into_iter().take(settings.top_n).enumerate() {
+                lines.push(format!("{}. {}", i + 1, tr!("report-number-calls", number = settings.format_number(number), count = count)));
+            }
+        }
+        ReportSection::Bookmarks => {
+            for record in annotations.bookmarked_records(records) {
+                lines.push(format!("• {} {} {} ({})",
+                    locale.format_date_time(&record.local_start(settings.case_timezone)), record.message_direction,
+                    settings.format_number(&record.normalized_number),
+                    tr!("report-seconds", seconds = record.length_of_call)));
+            }
+        }
+        ReportSection::CallsByDay => {
+            let mut sorted_days: Vec<_> = analytics.calls_by_day.iter().collect();
+            sorted_days.sort_by(|a, b| a.0.cmp(b.0));
+            for (day, count) in sorted_days {
+                lines.push(format!("{}: {}", locale.format_day_key(day), tr!("report-calls", count = *count)));
+            }
+        }
+        ReportSection::CallsByHour => {
+            for hour in 0..24 {
+                if let Some(count) = analytics.calls_by_hour.get(&hour) {
+                    lines.push(format!("{}: {}", locale.format_hour(hour), tr!("report-calls", count = *count)));
+                }
+            }
+        }
+        ReportSection::DataQuality => {
+            lines = DataQualityReport::build(records, parse_reports).body_lines();
+        }
+    }
+    lines
+}
+
+fn rtf_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_ascii() => escaped.push(c),
+            // RTF takes non-ASCII as signed 16-bit code units, each followed by a fallback character
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+    escaped
+}
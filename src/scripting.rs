@@ -0,0 +1,150 @@
+use crate::data_models::ProcessedCallRecord;
+use anyhow::{anyhow, Result};
+use chrono::Timelike;
+use log::info;
+use rhai::{Array, Dynamic, Engine, Map, Scope};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Upper bound on Rhai operations per run, so a runaway loop cannot hang a job forever.
+const MAX_OPERATIONS: u64 = 500_000_000;
+
+/// A user-written Rhai script, saved with the settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserScript {
+    pub name: String,
+    pub source: String,
+}
+
+impl UserScript {
+    /// Starter script shown when a new script is created.
+    pub fn example() -> Self {
+        Self {
+            name: "Long night calls".to_string(),
+            source: r#"// `records` holds every processed call record. Each record has:
+//   direction, remote, number, target, start, date, time, day, hour,
+//   seconds, minutes, source, key
+// emit(#{ ... })      adds a row to the output table (and the export sheet)
+// metric(name, value) adds a named figure
+// print(...)          writes to the script log
+
+let count = 0;
+for r in records {
+    if r.minutes >= 10.0 && (r.hour >= 22 || r.hour < 5) {
+        count += 1;
+        emit(#{ number: r.number, start: r.start, minutes: r.minutes });
+    }
+}
+metric("Night calls over 10 minutes", count);
+"#.to_string(),
+        }
+    }
+}
+
+/// What a script produced: named metrics, a table of emitted rows, and printed lines.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScriptOutput {
+    pub script_name: String,
+    pub metrics: Vec<(String, String)>,
+    /// Column names in order of first appearance across emitted rows.
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub log: Vec<String>,
+}
+
+impl ScriptOutput {
+    fn emit(&mut self, row: Map) {
+        for key in row.keys() {
+            if !self.columns.iter().any(|c| c == key.as_str()) {
+                self.columns.push(key.to_string());
+            }
+        }
+        let values = self.columns.iter()
+            .map(|column| row.get(column.as_str()).map(Dynamic::to_string).unwrap_or_default())
+            .collect();
+        self.rows.push(values);
+    }
+
+    /// Rows padded to the final column count (earlier rows may predate later columns).
+    pub fn padded_rows(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+        self.rows.iter().map(|row| {
+            let mut row = row.clone();
+            row.resize(self.columns.len(), String::new());
+            row
+        })
+    }
+}
+
+/// Runs [`UserScript`]s over processed records with Rhai.
+pub struct ScriptRunner;
+
+impl ScriptRunner {
+    /// `should_stop` is polled while the script runs; returning true aborts it.
+    pub fn run<F>(script: &UserScript, records: &[ProcessedCallRecord], should_stop: F) -> Result<ScriptOutput>
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        info!("Running script {:?} over {} records", script.name, records.len());
+
+        let output = Arc::new(Mutex::new(ScriptOutput {
+            script_name: script.name.clone(),
+            ..ScriptOutput::default()
+        }));
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_progress(move |_| should_stop().then(|| Dynamic::from("Cancelled")));
+
+        let sink = Arc::clone(&output);
+        engine.on_print(move |text| {
+            if let Ok(mut output) = sink.lock() {
+                output.log.push(text.to_string());
+            }
+        });
+        let sink = Arc::clone(&output);
+        engine.register_fn("emit", move |row: Map| {
+            if let Ok(mut output) = sink.lock() {
+                output.emit(row);
+            }
+        });
+        let sink = Arc::clone(&output);
+        engine.register_fn("metric", move |name: &str, value: Dynamic| {
+            if let Ok(mut output) = sink.lock() {
+                output.metrics.push((name.to_string(), value.to_string()));
+            }
+        });
+
+        let mut scope = Scope::new();
+        scope.push("records", records.iter().map(Self::record_to_map).map(Dynamic::from).collect::<Array>());
+
+        engine.run_with_scope(&mut scope, &script.source)
+            .map_err(|e| anyhow!("Script {:?} failed: {}", script.name, e))?;
+
+        // The engine (and the closures holding the other references) is dropped here
+        drop(engine);
+        let output = Arc::try_unwrap(output)
+            .map_err(|_| anyhow!("Script output is still in use"))?
+            .into_inner()
+            .map_err(|_| anyhow!("Script output lock was poisoned"))?;
+        info!("Script {:?} emitted {} rows and {} metrics", script.name, output.rows.len(), output.metrics.len());
+        Ok(output)
+    }
+
+    fn record_to_map(record: &ProcessedCallRecord) -> Map {
+        let mut map = Map::new();
+        map.insert("direction".into(), record.message_direction.clone().into());
+        map.insert("remote".into(), record.remote_number.clone().into());
+        map.insert("number".into(), record.normalized_number.clone().into());
+        map.insert("target".into(), record.target_number.clone().into());
+        map.insert("start".into(), record.start_time.to_rfc3339().into());
+        map.insert("date".into(), record.date.clone().into());
+        map.insert("time".into(), record.time.clone().into());
+        map.insert("day".into(), record.day_of_week.clone().into());
+        map.insert("hour".into(), (record.start_time.hour() as i64).into());
+        map.insert("seconds".into(), (record.length_of_call as i64).into());
+        map.insert("minutes".into(), record.duration_minutes.into());
+        map.insert("source".into(), record.source_file.clone().into());
+        map.insert("key".into(), record.record_key().into());
+        map
+    }
+}
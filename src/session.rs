@@ -0,0 +1,123 @@
+use crate::annotations::Annotations;
+use crate::data_models::{ParseReport, ProcessedCallRecord, SourceFile};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const RECOVERY_DIR_NAME: &str = "esubpoena-tolls-tool";
+const SNAPSHOT_FILE_NAME: &str = "session.json";
+const RUNNING_MARKER_NAME: &str = "running.lock";
+
+/// Everything an analyst would lose in a crash: the loaded records and their review state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub saved_at: DateTime<Utc>,
+    /// Shared with the app, so taking a snapshot never copies the records.
+    pub records: Arc<Vec<ProcessedCallRecord>>,
+    /// Productions the records came from; lets a restored session reuse cached analytics.
+    #[serde(default)]
+    pub sources: Vec<SourceFile>,
+    /// Records each production had to skip, so the warning survives a restore.
+    #[serde(default)]
+    pub parse_reports: Vec<ParseReport>,
+    pub annotations: Annotations,
+}
+
+impl SessionSnapshot {
+    pub fn new(
+        records: Arc<Vec<ProcessedCallRecord>>,
+        sources: Vec<SourceFile>,
+        parse_reports: Vec<ParseReport>,
+        annotations: Annotations,
+    ) -> Self {
+        Self {
+            saved_at: Utc::now(),
+            records,
+            sources,
+            parse_reports,
+            annotations,
+        }
+    }
+
+    /// Writes to a temporary file first so a crash mid-write never leaves a truncated snapshot.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        let file = File::create(&tmp_path)
+            .with_context(|| format!("Failed to create snapshot: {:?}", tmp_path))?;
+        serde_json::to_writer(BufWriter::new(file), self).context("Failed to write snapshot")?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to move snapshot into place: {:?}", path))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open snapshot: {:?}", path))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("Failed to read snapshot: {:?}", path))
+    }
+}
+
+/// Tracks whether the previous run exited cleanly, using a marker file that exists only
+/// while the app is running.
+pub struct RecoveryFiles {
+    dir: PathBuf,
+}
+
+impl RecoveryFiles {
+    /// Recovery files live under the platform local data directory.
+    pub fn locate() -> Option<Self> {
+        dirs::data_local_dir().map(|dir| Self { dir: dir.join(RECOVERY_DIR_NAME).join("recovery") })
+    }
+
+    pub fn snapshot_path(&self) -> PathBuf {
+        self.dir.join(SNAPSHOT_FILE_NAME)
+    }
+
+    fn marker_path(&self) -> PathBuf {
+        self.dir.join(RUNNING_MARKER_NAME)
+    }
+
+    /// Marks the session as running. Returns the snapshot left behind if the previous run
+    /// never reached [`RecoveryFiles::end_session`].
+    pub fn begin_session(&self) -> Option<PathBuf> {
+        let unclean_exit = self.marker_path().exists();
+        let snapshot = self.snapshot_path();
+        let recoverable = (unclean_exit && snapshot.exists()).then_some(snapshot);
+        if recoverable.is_some() {
+            info!("Previous session did not exit cleanly; a recovery snapshot is available");
+        }
+
+        if let Err(e) = fs::create_dir_all(&self.dir).and_then(|_| fs::write(self.marker_path(), Utc::now().to_rfc3339())) {
+            warn!("Crash recovery disabled, cannot write {:?}: {}", self.marker_path(), e);
+        }
+        recoverable
+    }
+
+    /// Called on a clean exit: nothing needs recovering, so the snapshot is removed too.
+    pub fn end_session(&self) {
+        self.discard_snapshot();
+        remove_if_exists(&self.marker_path());
+    }
+
+    /// Drops a snapshot the user chose not to restore.
+    pub fn discard_snapshot(&self) {
+        remove_if_exists(&self.snapshot_path());
+    }
+}
+
+fn remove_if_exists(path: &Path) {
+    if path.exists() {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("Failed to remove {:?}: {}", path, e);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use crate::analytics::{DEFAULT_TOP_N, DEFAULT_UNANSWERED_SECONDS};
+use crate::carriers::CarrierDirectory;
+use crate::cnam::CallerNames;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cnam::CnamLookup;
+use crate::columns::ColumnLayout;
+use crate::data_models::{normalize_phone_number, Analytics, PhoneFormat};
+use crate::export_locale::ExportLocale;
+use crate::filters::DEFAULT_NIGHT_HOURS;
+use crate::known_numbers::KnownNumbers;
+use crate::pdf_parser::PdfPattern;
+use crate::phone_region::PhoneRegion;
+use crate::preview::DEFAULT_PREVIEW_THRESHOLD_MB;
+use crate::report::ReportSection;
+use crate::i18n::Language;
+use crate::scripting::UserScript;
+use crate::signing::SigningKey;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_DIR_NAME: &str = "esubpoena-tolls-tool";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Default Excel export file name. See [`AppSettings::export_file_name`] for placeholders.
+pub const DEFAULT_EXPORT_TEMPLATE: &str = "telecommunication_analysis.xlsx";
+
+/// Default index named in Elasticsearch bulk exports.
+pub const DEFAULT_ELASTICSEARCH_INDEX: &str = "toll-records";
+
+/// Timezones offered in the case timezone picker.
+pub const COMMON_TIMEZONES: [Tz; 9] = [
+    Tz::UTC,
+    Tz::America__New_York,
+    Tz::America__Chicago,
+    Tz::America__Denver,
+    Tz::America__Phoenix,
+    Tz::America__Los_Angeles,
+    Tz::America__Anchorage,
+    Tz::Pacific__Honolulu,
+    Tz::America__Puerto_Rico,
+];
+
+/// User preferences applied across the UI, summary report, and exports.
+///
+/// Persisted as TOML in the platform config directory; keys missing from the file fall back
+/// to their defaults, so configs written by older versions keep loading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Language of the UI, the summary report, and exports.
+    pub language: Language,
+    pub phone_format: PhoneFormat,
+    /// Country numbers written without a country code are read as belonging to.
+    pub default_region: PhoneRegion,
+    pub case_timezone: Tz,
+    /// Show a local-time column next to the UTC times in the records table.
+    pub show_local_time: bool,
+    /// Set once the first-run walkthrough has been finished or skipped.
+    pub tutorial_completed: bool,
+    /// Start hour (inclusive) and end hour (exclusive) of the "night hours" window; may wrap past midnight.
+    pub night_hours: (u32, u32),
+    /// How many entries the most-frequent-numbers ranking keeps.
+    pub top_n: usize,
+    /// Calls no longer than this many seconds count as unanswered in the answer rates.
+    pub unanswered_max_seconds: u32,
+    /// Rank short codes and service numbers (bank alerts, voicemail, ...) as contacts; by
+    /// default their calls are only counted.
+    pub include_short_codes: bool,
+    /// Excel export file name, with `{date}`, `{time}`, and `{target}` placeholders.
+    pub export_template: String,
+    /// Date order, decimal separator, and clock of Excel, CSV, and PDF output; the
+    /// language's conventions when unset.
+    pub export_locale: Option<ExportLocale>,
+    /// Excel number format for start and end times; the export locale's format when unset.
+    pub excel_datetime_format: Option<String>,
+    /// Excel number format for call durations; the language's format when unset.
+    pub excel_duration_format: Option<String>,
+    /// Normalized numbers left out of analytics (voicemail, carrier test lines, ...).
+    pub excluded_numbers: Vec<String>,
+    /// Opened productions of at least this many megabytes are previewed before they are
+    /// parsed; 0 turns the preview off. Watch folder productions are never previewed.
+    pub preview_threshold_mb: u64,
+    /// Pass over `<results>` blocks of XML productions that are not well-formed, listing
+    /// them with the skipped records, instead of failing the whole production.
+    pub recover_malformed_xml: bool,
+    /// Count a call that several productions of the case returned once, dropping the
+    /// repeats from the productions loaded later.
+    pub collapse_cross_file_duplicates: bool,
+    /// Leave records the validation pass flags out of the analytics; they stay loaded and
+    /// listed on the Data Quality tab either way.
+    pub exclude_flagged_records: bool,
+    /// Folder that is monitored for new productions.
+    pub watch_folder: Option<PathBuf>,
+    /// Export Excel and CSV automatically for every production picked up from the watch folder.
+    pub auto_export: bool,
+    /// Where auto-exports are written; defaults to an `exports` folder inside the watch folder.
+    pub auto_export_dir: Option<PathBuf>,
+    /// Write a detached signature next to every export.
+    pub sign_exports: bool,
+    /// Agency key file exports are signed with.
+    pub signing_key_path: Option<PathBuf>,
+    /// CSV of NPA-NXX block assignments used for carrier lookups.
+    pub carrier_assignments_path: Option<PathBuf>,
+    /// CSV of ported numbers and their LRNs, which take precedence over block assignments.
+    pub ported_numbers_path: Option<PathBuf>,
+    /// Offline CNAM file of caller-ID names.
+    pub cnam_path: Option<PathBuf>,
+    /// Allow looking names up with the service at `cnam_lookup_url`. Off by default since
+    /// every number looked up is sent to it.
+    pub cnam_lookup_enabled: bool,
+    /// Caller-name lookup URL with a `{number}` placeholder.
+    pub cnam_lookup_url: Option<String>,
+    /// CSVs of categorized known numbers (informant lines, agency numbers, ...).
+    pub known_number_lists: Vec<PathBuf>,
+    /// Service-account key file (JSON) for the Google Sheets export.
+    pub google_sheets_credentials: Option<PathBuf>,
+    /// ID of the shared Google Sheet exports are pushed to, from its URL.
+    pub google_sheet_id: Option<String>,
+    /// Index every document of an Elasticsearch bulk export is written to.
+    pub elasticsearch_index: String,
+    pub column_layout: ColumnLayout,
+    /// Sections of the summary report, in order, as arranged on the Summary tab.
+    pub report_sections: Vec<ReportSection>,
+    /// Narrative text with `{{placeholders}}` for the report's narrative section, e.g. an
+    /// agency's standard affidavit paragraph.
+    pub report_template: String,
+    /// Rhai scripts shown in the Scripts tab.
+    pub scripts: Vec<UserScript>,
+    /// Patterns tried, in order, on each line of a PDF return's text.
+    pub pdf_patterns: Vec<PdfPattern>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            phone_format: PhoneFormat::Parenthesized,
+            default_region: PhoneRegion::default(),
+            case_timezone: Tz::UTC,
+            show_local_time: false,
+            tutorial_completed: false,
+            night_hours: DEFAULT_NIGHT_HOURS,
+            top_n: DEFAULT_TOP_N,
+            unanswered_max_seconds: DEFAULT_UNANSWERED_SECONDS,
+            include_short_codes: false,
+            export_template: DEFAULT_EXPORT_TEMPLATE.to_string(),
+            export_locale: None,
+            excel_datetime_format: None,
+            excel_duration_format: None,
+            excluded_numbers: Vec::new(),
+            preview_threshold_mb: DEFAULT_PREVIEW_THRESHOLD_MB,
+            recover_malformed_xml: false,
+            collapse_cross_file_duplicates: false,
+            exclude_flagged_records: false,
+            watch_folder: None,
+            auto_export: false,
+            auto_export_dir: None,
+            sign_exports: false,
+            signing_key_path: None,
+            carrier_assignments_path: None,
+            ported_numbers_path: None,
+            cnam_path: None,
+            cnam_lookup_enabled: false,
+            cnam_lookup_url: None,
+            known_number_lists: Vec::new(),
+            google_sheets_credentials: None,
+            google_sheet_id: None,
+            elasticsearch_index: DEFAULT_ELASTICSEARCH_INDEX.to_string(),
+            column_layout: ColumnLayout::default(),
+            report_sections: ReportSection::DEFAULT.to_vec(),
+            report_template: String::new(),
+            scripts: Vec::new(),
+            pdf_patterns: PdfPattern::defaults(),
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn format_number(&self, number: &str) -> String {
+        crate::data_models::format_phone_number(number, self.phone_format)
+    }
+
+    pub fn export_locale(&self) -> ExportLocale {
+        self.export_locale.unwrap_or_else(|| self.language.export_locale())
+    }
+
+    pub fn excel_datetime_format(&self) -> &str {
+        self.excel_datetime_format.as_deref().unwrap_or_else(|| self.export_locale().excel_datetime_format())
+    }
+
+    pub fn excel_duration_format(&self) -> &str {
+        self.excel_duration_format.as_deref().unwrap_or_else(|| self.language.excel_duration_format())
+    }
+
+    /// Formats a UTC timestamp in the case timezone, including the zone abbreviation.
+    pub fn format_local_time(&self, time: &DateTime<Utc>) -> String {
+        time.with_timezone(&self.case_timezone).format("%Y-%m-%d %H:%M:%S %Z").to_string()
+    }
+
+    /// `config.toml` under the platform config directory (`$XDG_CONFIG_HOME` on Linux,
+    /// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS).
+    pub fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    /// Loads the config file, falling back to defaults when it is missing or unreadable.
+    pub fn load() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+        if !path.exists() {
+            return Self::default();
+        }
+        match Self::load_from(&path) {
+            Ok(settings) => {
+                info!("Loaded settings from {:?}", path);
+                settings
+            }
+            Err(e) => {
+                warn!("Ignoring unreadable settings file {:?}: {:#}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read settings: {:?}", path))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse settings: {:?}", path))
+    }
+
+    /// Writes the config file and returns where it was written.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::config_path().context("No configuration directory on this platform")?;
+        self.save_to(&path)?;
+        Ok(path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create settings directory: {:?}", parent))?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize settings")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write settings: {:?}", path))
+    }
+
+    /// The key exports should be signed with, or `None` when signing is off.
+    pub fn signing_key(&self) -> Result<Option<SigningKey>> {
+        if !self.sign_exports {
+            return Ok(None);
+        }
+        let Some(path) = &self.signing_key_path else {
+            bail!("Export signing is on but no signing key file is set");
+        };
+        SigningKey::load(path).map(Some)
+    }
+
+    /// Loads the configured carrier datasets; empty when none are set.
+    pub fn carrier_directory(&self) -> Result<CarrierDirectory> {
+        let mut directory = CarrierDirectory::default();
+        if let Some(path) = &self.carrier_assignments_path {
+            directory.import_assignments(path)?;
+        }
+        if let Some(path) = &self.ported_numbers_path {
+            directory.import_ported(path)?;
+        }
+        Ok(directory)
+    }
+
+    /// Loads the offline CNAM file, when set, and earlier lookup answers.
+    pub fn caller_names(&self) -> Result<CallerNames> {
+        let mut names = CallerNames::default();
+        if let Some(path) = CallerNames::cache_path() {
+            names.load_cache(&path);
+        }
+        if let Some(path) = &self.cnam_path {
+            names.import_csv(path)?;
+        }
+        Ok(names)
+    }
+
+    /// Loads every configured known-number list; empty when none are set.
+    pub fn known_numbers(&self) -> Result<KnownNumbers> {
+        let mut known = KnownNumbers::default();
+        for path in &self.known_number_lists {
+            known.import_list(path)?;
+        }
+        Ok(known)
+    }
+
+    /// The caller-name lookup service, or `None` while lookups are turned off.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cnam_lookup(&self) -> Result<Option<CnamLookup>> {
+        if !self.cnam_lookup_enabled {
+            return Ok(None);
+        }
+        let Some(url) = &self.cnam_lookup_url else {
+            bail!("Caller-name lookups are on but no lookup URL is set");
+        };
+        CnamLookup::new(url).map(Some)
+    }
+
+    /// Adds a number to the exclusion list in normalized form. Returns false if it was already there.
+    pub fn exclude_number(&mut self, number: &str) -> bool {
+        if !number.chars().any(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        let normalized = normalize_phone_number(number);
+        if !normalized.is_valid() {
+            return false;
+        }
+        let normalized = normalized.into_string();
+        if self.excluded_numbers.contains(&normalized) {
+            return false;
+        }
+        self.excluded_numbers.push(normalized);
+        true
+    }
+
+    /// Expands the export template. `{date}` and `{time}` use the case timezone.
+    pub fn export_file_name(&self, now: &DateTime<Utc>, target: &str) -> String {
+        let local = now.with_timezone(&self.case_timezone);
+        let name = self.export_template
+            .replace("{date}", &local.format("%Y%m%d").to_string())
+            .replace("{time}", &local.format("%H%M%S").to_string())
+            .replace("{target}", target);
+        if name.to_ascii_lowercase().ends_with(".xlsx") {
+            name
+        } else {
+            format!("{}.xlsx", name)
+        }
+    }
+
+    /// Export file name for a case, with `{target}` set to its target when there is only one.
+    pub fn case_export_file_name(&self, now: &DateTime<Utc>, analytics: &Analytics) -> String {
+        let target = match analytics.target_numbers.len() {
+            1 => analytics.target_numbers.iter().next().cloned().unwrap_or_default(),
+            _ => "multiple_targets".to_string(),
+        };
+        self.export_file_name(now, &target)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::data_models::{hex_digest, SourceFile};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension appended to an export's file name for its detached signature.
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+const SIGNATURE_FORMAT_VERSION: u32 = 1;
+const ALGORITHM: &str = "HMAC-SHA256";
+const HMAC_BLOCK_SIZE: usize = 64;
+
+/// A secret shared between the agency producing exports and the recipients who verify them.
+///
+/// Any file works as a key; its raw bytes are the secret. Keys shorter than 32 bytes are refused.
+pub struct SigningKey {
+    secret: Vec<u8>,
+}
+
+impl SigningKey {
+    pub fn load(path: &Path) -> Result<Self> {
+        let secret = fs::read(path)
+            .with_context(|| format!("Failed to read signing key: {:?}", path))?;
+        Self::from_bytes(secret)
+    }
+
+    pub fn from_bytes(secret: Vec<u8>) -> Result<Self> {
+        if secret.len() < 32 {
+            bail!("Signing key must be at least 32 bytes, got {}", secret.len());
+        }
+        Ok(Self { secret })
+    }
+
+    /// Short fingerprint recorded in signatures so a recipient can tell which key to verify with.
+    pub fn key_id(&self) -> String {
+        hex_digest(&Sha256::digest(&self.secret)[..8])
+    }
+
+    fn mac(&self, message: &[u8]) -> String {
+        hex_digest(&hmac_sha256(&self.secret, message))
+    }
+}
+
+/// Detached signature stored as JSON next to the export, e.g. `case.xlsx.sig`.
+///
+/// Covers the SHA-256 of the exported file, so any change to the file after generation, or to
+/// the signature's own fields, fails [`ExportSignature::verify`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSignature {
+    pub format_version: u32,
+    pub algorithm: String,
+    pub file_name: String,
+    pub sha256: String,
+    pub signed_at: DateTime<Utc>,
+    pub key_id: String,
+    pub signature: String,
+}
+
+impl ExportSignature {
+    /// Where the signature for `path` is written.
+    pub fn path_for(path: &Path) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".");
+        name.push(SIGNATURE_EXTENSION);
+        PathBuf::from(name)
+    }
+
+    /// Signs an export that has already been written and saves the signature next to it.
+    pub fn sign_file(path: &Path, key: &SigningKey) -> Result<PathBuf> {
+        let source = SourceFile::from_path(path)
+            .with_context(|| format!("Failed to hash export: {:?}", path))?;
+        let mut signature = Self {
+            format_version: SIGNATURE_FORMAT_VERSION,
+            algorithm: ALGORITHM.to_string(),
+            file_name: source.name,
+            sha256: source.sha256,
+            signed_at: Utc::now(),
+            key_id: key.key_id(),
+            signature: String::new(),
+        };
+        signature.signature = key.mac(signature.signed_payload().as_bytes());
+
+        let signature_path = Self::path_for(path);
+        let content = serde_json::to_string_pretty(&signature).context("Failed to serialize signature")?;
+        fs::write(&signature_path, content)
+            .with_context(|| format!("Failed to write signature: {:?}", signature_path))?;
+        info!("Signed {:?} with key {}", path, signature.key_id);
+        Ok(signature_path)
+    }
+
+    pub fn load_for(path: &Path) -> Result<Self> {
+        let signature_path = Self::path_for(path);
+        let content = fs::read_to_string(&signature_path)
+            .with_context(|| format!("No signature found at {:?}", signature_path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to read signature: {:?}", signature_path))
+    }
+
+    /// Checks `path` against its detached signature. Fails with the reason when the file was
+    /// altered, the signature was tampered with, or it was made with a different key.
+    pub fn verify_file(path: &Path, key: &SigningKey) -> Result<Self> {
+        let signature = Self::load_for(path)?;
+        signature.verify(path, key)?;
+        Ok(signature)
+    }
+
+    pub fn verify(&self, path: &Path, key: &SigningKey) -> Result<()> {
+        if self.format_version != SIGNATURE_FORMAT_VERSION || self.algorithm != ALGORITHM {
+            bail!("Unsupported signature: {} v{}", self.algorithm, self.format_version);
+        }
+        if self.key_id != key.key_id() {
+            bail!("Signed with key {}, not the loaded key {}", self.key_id, key.key_id());
+        }
+        if !constant_time_eq(key.mac(self.signed_payload().as_bytes()).as_bytes(), self.signature.as_bytes()) {
+            bail!("Signature does not match its contents; the signature file was altered");
+        }
+        let actual = SourceFile::from_path(path)
+            .with_context(|| format!("Failed to hash file: {:?}", path))?;
+        if actual.sha256 != self.sha256 {
+            bail!("{} was modified after it was signed", self.file_name);
+        }
+        Ok(())
+    }
+
+    /// Every field except the signature itself, one per line.
+    fn signed_payload(&self) -> String {
+        format!(
+            "esubpoena-export-signature v{}\nalgorithm={}\nfile={}\nsha256={}\nsigned_at={}\nkey_id={}",
+            self.format_version, self.algorithm, self.file_name, self.sha256, self.signed_at.to_rfc3339(), self.key_id
+        )
+    }
+}
+
+/// HMAC (RFC 2104) over SHA-256.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
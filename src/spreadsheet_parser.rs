@@ -0,0 +1,192 @@
+use crate::csv_parser::{CsvSample, SheetMapping};
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::DelimitedParser;
+use crate::format_detection::{DetectionConfidence, FormatDetection, ParserKind};
+use crate::pipeline::DuplicatePolicy;
+use crate::workbook_import::excel_serial_to_naive;
+use anyhow::{bail, Context, Result};
+use calamine::{open_workbook_auto_from_rs, Data, Range, Reader};
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::info;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Rows of each sheet kept for picking the sheet and header row and previewing the columns.
+const SAMPLE_ROWS: usize = 60;
+
+/// Rows searched for a header row whose columns a mapping preset knows.
+pub const HEADER_SEARCH_ROWS: usize = 20;
+
+/// Reads call detail from an Excel workbook, e.g. a return an agency converted before
+/// forwarding it: one worksheet, from its header row down, with the columns mapped as for
+/// delimited text. Date-time cells, like times without an offset in [`DelimitedParser`],
+/// are taken to be in the timezone given.
+pub struct SpreadsheetParser;
+
+impl SpreadsheetParser {
+    pub fn parse_file(
+        path: &Path,
+        sheet: &SheetMapping,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing workbook: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, sheet, timezone, duplicates)
+    }
+
+    /// Blank rows, such as the gaps between sections, are passed over.
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        sheet: &SheetMapping,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        if !sheet.mapping.is_complete() {
+            bail!("{} needs columns for the remote number, start time, and duration", source_file);
+        }
+        let mut workbook = open_workbook(bytes, source_file)?;
+        let range = workbook.worksheet_range_at(sheet.sheet)
+            .with_context(|| format!("{} has no sheet {}", source_file, sheet.sheet + 1))?
+            .with_context(|| format!("Failed to read sheet {} of {}", sheet.sheet + 1, source_file))?;
+        let columns = &sheet.mapping.columns;
+        let rows = sheet_rows(&range, usize::MAX).into_iter()
+            .skip(sheet.header_row + 1)
+            .filter(|row| !is_blank(row))
+            .map(|row| Ok(DelimitedParser::call_record(&row, columns, sheet.mapping.day_first, timezone)));
+        let (records, report) = DelimitedParser::collect_records(rows, source_file, duplicates);
+        info!("Parsed {} records from sheet {} of {}", records.len(), sheet.sheet + 1, source_file);
+        Ok((records, report))
+    }
+}
+
+/// The first rows of every worksheet in a workbook, for picking the sheet, header row, and
+/// columns a production is read with.
+#[derive(Debug, Clone)]
+pub struct WorkbookSample {
+    pub source_file: String,
+    /// Each sheet's name and first rows, as text.
+    pub sheets: Vec<(String, Vec<StringRecord>)>,
+}
+
+impl WorkbookSample {
+    pub fn read_file(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::from_bytes(source_file, &bytes)
+    }
+
+    pub fn from_bytes(source_file: &str, bytes: &[u8]) -> Result<Self> {
+        let mut workbook = open_workbook(bytes, source_file)?;
+        let mut sheets = Vec::new();
+        for name in workbook.sheet_names() {
+            let range = workbook.worksheet_range(&name)
+                .with_context(|| format!("Failed to read sheet {:?} of {}", name, source_file))?;
+            sheets.push((name, sheet_rows(&range, SAMPLE_ROWS)));
+        }
+        if sheets.is_empty() {
+            bail!("{} has no worksheets", source_file);
+        }
+        Ok(Self { source_file: source_file.to_string(), sheets })
+    }
+
+    /// The rows of `sheet` below `header_row` as a delimited sample, with that row's cells
+    /// as the column names.
+    pub fn csv_sample(&self, sheet: usize, header_row: usize) -> Result<CsvSample> {
+        let rows = self.sheets.get(sheet).map_or(&[][..], |(_, rows)| rows.as_slice());
+        let rows = rows.get(header_row..).unwrap_or_default();
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+        for row in rows.iter().filter(|row| !is_blank(row)) {
+            writer.write_record(row)?;
+        }
+        let bytes = writer.into_inner().context("Failed to sample the sheet")?;
+        CsvSample::from_delimited(&self.source_file, &bytes)
+    }
+
+    /// The first of the sheet's first [`HEADER_SEARCH_ROWS`] rows whose cells a preset maps
+    /// as column names.
+    pub fn header_row(&self, sheet: usize) -> Option<usize> {
+        let rows = &self.sheets.get(sheet)?.1;
+        (0..rows.len().min(HEADER_SEARCH_ROWS)).find(|&header_row| {
+            !is_blank(&rows[header_row])
+                && self.csv_sample(sheet, header_row)
+                    .is_ok_and(|sample| matches!(sample.detection.parser, Some(ParserKind::Delimited(_))))
+        })
+    }
+
+    /// The first sheet with a header row a preset maps, with the columns it finds and how
+    /// sure detection is of them.
+    pub fn suggested(&self) -> Option<(SheetMapping, DetectionConfidence)> {
+        let (sheet, header_row) = (0..self.sheets.len()).find_map(|sheet| Some((sheet, self.header_row(sheet)?)))?;
+        let sample = self.csv_sample(sheet, header_row).ok()?;
+        let (_, mapping) = sample.suggested_mapping();
+        Some((SheetMapping { sheet, header_row, mapping }, sample.detection.confidence))
+    }
+
+    /// How the workbook is read without its sheet and columns picked by hand.
+    pub fn detection(&self) -> FormatDetection {
+        match self.suggested() {
+            Some((mapping, confidence)) => FormatDetection { parser: Some(ParserKind::Spreadsheet(mapping)), confidence },
+            None => FormatDetection { parser: None, confidence: DetectionConfidence::Low },
+        }
+    }
+}
+
+fn open_workbook<'a>(bytes: &'a [u8], source_file: &str) -> Result<calamine::Sheets<Cursor<&'a [u8]>>> {
+    open_workbook_auto_from_rs(Cursor::new(bytes))
+        .with_context(|| format!("{} is not a workbook that can be read", source_file))
+}
+
+/// Up to `limit` rows of a sheet as text, counted from the sheet's first row even when its
+/// cells start further down.
+fn sheet_rows(range: &Range<Data>, limit: usize) -> Vec<StringRecord> {
+    let (first_row, _) = range.start().unwrap_or((0, 0));
+    std::iter::repeat_with(StringRecord::new)
+        .take(first_row as usize)
+        .chain(range.rows().map(|row| row.iter().map(cell_text).collect()))
+        .take(limit)
+        .collect()
+}
+
+/// Whether every cell of the row is empty.
+pub fn is_blank(row: &StringRecord) -> bool {
+    row.iter().all(str::is_empty)
+}
+
+/// A cell as the delimited parser reads it. Whole numbers, phone numbers among them, lose
+/// the `.0` they come back with; date-time cells are written out as text.
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 1e15 => (*value as i64).to_string(),
+        Data::DateTime(time) => excel_time_text(time.as_f64()),
+        cell => cell.to_string().trim().to_string(),
+    }
+}
+
+/// A date-time cell as `YYYY-MM-DD HH:MM:SS`, a date without a time of day as `YYYY-MM-DD`,
+/// and a time without a date, usually a call's length, as `H:MM:SS`.
+fn excel_time_text(serial: f64) -> String {
+    if (0.0..1.0).contains(&serial) {
+        let seconds = (serial * 86_400.0).round() as u32;
+        return format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    }
+    let Some(time) = excel_serial_to_naive(serial) else {
+        return String::new();
+    };
+    if serial.fract() == 0.0 {
+        time.format("%Y-%m-%d").to_string()
+    } else {
+        time.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
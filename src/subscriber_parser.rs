@@ -0,0 +1,195 @@
+use crate::data_models::{normalize_phone_number, ParseReport, ProcessedCallRecord, SubscriberInfo};
+use crate::delimited_parser::column_key;
+use anyhow::{Context, Result};
+use log::{info, warn};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Elements holding one subscriber's record, compared as by [`column_key`].
+const SUBSCRIBER_ELEMENTS: [&str; 4] = ["subscriber", "subscriberinfo", "subscriberrecord", "accountholder"];
+
+/// Element names read for each field, first found wins.
+const NUMBER_ELEMENTS: [&str; 6] = ["phonenumber", "msisdn", "mdn", "telephonenumber", "number", "targetvalue"];
+const NAME_ELEMENTS: [&str; 5] = ["name", "subscribername", "fullname", "accountname", "customername"];
+const FIRST_NAME_ELEMENTS: [&str; 2] = ["firstname", "givenname"];
+const LAST_NAME_ELEMENTS: [&str; 3] = ["lastname", "surname", "familyname"];
+const ADDRESS_ELEMENTS: [&str; 4] = ["address", "subscriberaddress", "serviceaddress", "billingaddress"];
+const STREET_ELEMENTS: [&str; 3] = ["street", "streetaddress", "address1"];
+const CITY_ELEMENTS: [&str; 1] = ["city"];
+const STATE_ELEMENTS: [&str; 2] = ["state", "province"];
+const ZIP_ELEMENTS: [&str; 3] = ["zip", "zipcode", "postalcode"];
+const ACCOUNT_ELEMENTS: [&str; 4] = ["accountnumber", "accountid", "account", "ban"];
+const EMAIL_ELEMENTS: [&str; 2] = ["email", "emailaddress"];
+const START_ELEMENTS: [&str; 5] = ["activationdate", "accountstartdate", "servicestartdate", "accountopened", "startdate"];
+const END_ELEMENTS: [&str; 5] = ["deactivationdate", "accountenddate", "serviceenddate", "accountclosed", "enddate"];
+
+/// Reads the subscriber records that come with some productions in a companion XML file,
+/// LDS-102 style: the production's header, then a `<subscriber>` block per account with the
+/// holder's name, address, account number, and account dates. Element names vary between
+/// carriers, so they are matched loosely, and a block without a number of its own is taken
+/// to be the target's.
+pub struct SubscriberParser;
+
+impl SubscriberParser {
+    pub fn parse_file(path: &Path) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing subscriber records: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file)
+    }
+
+    /// A subscriber file has no call records, so it loads as a production without any,
+    /// its subscribers kept in the report.
+    pub fn parse_bytes(bytes: &[u8], source_file: &str) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let mut report = ParseReport::new(source_file);
+        report.subscribers = Self::read_subscribers(bytes)
+            .with_context(|| format!("Failed to read the subscriber records in {}", source_file))?;
+        info!("Read {} subscriber records from {}", report.subscribers.len(), source_file);
+        Ok((Vec::new(), report))
+    }
+
+    /// Whether `sample` is XML with subscriber blocks and no call records.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(sample).to_lowercase();
+        !text.contains("<results")
+            && SUBSCRIBER_ELEMENTS.iter().any(|element| text.contains(&format!("<{}>", element)) || text.contains(&format!("<{} ", element)))
+    }
+
+    /// ```
+    /// use esubpoena_tolls_tool::subscriber_parser::SubscriberParser;
+    ///
+    /// let xml = br#"<dataProduct><xmlResult>
+    ///   <targetValue>(256) 555-0100</targetValue>
+    ///   <subscriber>
+    ///     <firstName>Pat</firstName><lastName>Doe</lastName>
+    ///     <address><street>1 Main St</street><city>Huntsville</city><state>AL</state><zip>35801</zip></address>
+    ///     <accountNumber>A-1001</accountNumber>
+    ///     <activationDate>2021-06-01</activationDate>
+    ///   </subscriber>
+    /// </xmlResult></dataProduct>"#;
+    /// let subscribers = SubscriberParser::read_subscribers(xml).unwrap();
+    /// assert_eq!(subscribers.len(), 1);
+    /// assert_eq!(subscribers[0].number, "2565550100");
+    /// assert_eq!(subscribers[0].name, "Pat Doe");
+    /// assert_eq!(subscribers[0].address, "1 Main St, Huntsville, AL 35801");
+    /// assert_eq!(subscribers[0].account_start, "2021-06-01");
+    /// assert_eq!(subscribers[0].account_end, "");
+    /// ```
+    pub fn read_subscribers(bytes: &[u8]) -> Result<Vec<SubscriberInfo>> {
+        let mut reader = Reader::from_reader(bytes);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+        let mut target = String::new();
+        let mut block: Option<HashMap<String, String>> = None;
+        let mut element = String::new();
+        let mut subscribers = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(e) => {
+                    element = column_key(&String::from_utf8_lossy(e.local_name().as_ref()));
+                    if SUBSCRIBER_ELEMENTS.contains(&element.as_str()) {
+                        // One block starting inside another closes it
+                        if let Some(fields) = block.replace(HashMap::new()) {
+                            subscribers.extend(subscriber(&fields, &target));
+                        }
+                    }
+                }
+                Event::Text(text) => {
+                    let text = text.unescape()?.trim().to_string();
+                    match &mut block {
+                        Some(fields) => {
+                            fields.entry(element.clone()).or_insert(text);
+                        }
+                        None if element == "targetvalue" => target = text,
+                        None => {}
+                    }
+                }
+                Event::End(e) => {
+                    let name = column_key(&String::from_utf8_lossy(e.local_name().as_ref()));
+                    if SUBSCRIBER_ELEMENTS.contains(&name.as_str()) {
+                        if let Some(fields) = block.take() {
+                            subscribers.extend(subscriber(&fields, &target));
+                        }
+                    }
+                    element.clear();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(subscribers)
+    }
+}
+
+/// One block's fields as a subscriber record, or none when no number is known for it.
+fn subscriber(fields: &HashMap<String, String>, target: &str) -> Option<SubscriberInfo> {
+    let field = |names: &[&str]| names.iter().find_map(|name| fields.get(*name).filter(|value| !value.is_empty())).cloned();
+    let join = |parts: &[Option<String>], separator: &str| {
+        parts.iter().flatten().map(String::as_str).collect::<Vec<_>>().join(separator)
+    };
+
+    let raw_number = field(&NUMBER_ELEMENTS).unwrap_or_else(|| target.to_string());
+    let number = normalize_phone_number(&raw_number);
+    if !number.is_valid() {
+        warn!("Skipping subscriber record without a phone number: {:?}", raw_number);
+        return None;
+    }
+    let name = field(&NAME_ELEMENTS)
+        .unwrap_or_else(|| join(&[field(&FIRST_NAME_ELEMENTS), field(&LAST_NAME_ELEMENTS)], " "));
+    let address = field(&ADDRESS_ELEMENTS).unwrap_or_else(|| {
+        let region = join(&[field(&STATE_ELEMENTS), field(&ZIP_ELEMENTS)], " ");
+        join(&[field(&STREET_ELEMENTS), field(&CITY_ELEMENTS), Some(region).filter(|region| !region.is_empty())], ", ")
+    });
+    Some(SubscriberInfo {
+        number: number.into_string(),
+        name,
+        address,
+        account_number: field(&ACCOUNT_ELEMENTS).unwrap_or_default(),
+        email: field(&EMAIL_ELEMENTS).unwrap_or_default(),
+        account_start: field(&START_ELEMENTS).unwrap_or_default(),
+        account_end: field(&END_ELEMENTS).unwrap_or_default(),
+    })
+}
+
+/// A subscriber record of the case, with the production it came from and how the case's
+/// calls involve its number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberMatch<'a> {
+    pub info: &'a SubscriberInfo,
+    pub source_file: &'a str,
+    /// Calls in which the number is the target.
+    pub as_target: usize,
+    /// Calls in which the number is the other party.
+    pub as_contact: usize,
+}
+
+/// Every subscriber record in `reports`, in the order they were read, joined to `records`.
+pub fn match_subscribers<'a>(reports: &'a [ParseReport], records: &[ProcessedCallRecord]) -> Vec<SubscriberMatch<'a>> {
+    let mut by_target: HashMap<&str, usize> = HashMap::new();
+    let mut as_contact: HashMap<&str, usize> = HashMap::new();
+    for record in records {
+        *by_target.entry(record.target_number.as_str()).or_insert(0) += 1;
+        *as_contact.entry(record.normalized_number.as_str()).or_insert(0) += 1;
+    }
+    // Targets are kept as the production wrote them, e.g. `(256) 555-0100`
+    let mut as_target: HashMap<String, usize> = HashMap::new();
+    for (target, count) in by_target {
+        *as_target.entry(normalize_phone_number(target).into_string()).or_insert(0) += count;
+    }
+    reports.iter()
+        .flat_map(|report| report.subscribers.iter().map(move |info| (info, report.source_file.as_str())))
+        .map(|(info, source_file)| SubscriberMatch {
+            info,
+            source_file,
+            as_target: as_target.get(info.number.as_str()).copied().unwrap_or(0),
+            as_contact: as_contact.get(info.number.as_str()).copied().unwrap_or(0),
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+use crate::data_models::{normalize_phone_number, ProcessedCallRecord};
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Header names, compared case-insensitively, that identify each column of a subscriber sheet.
+const NUMBER_HEADERS: &[&str] = &["number", "phone", "phone number", "telephone", "msisdn", "mdn", "target"];
+const NAME_HEADERS: &[&str] = &["name", "subscriber", "subscriber name", "account name", "customer"];
+const ADDRESS_HEADERS: &[&str] = &["address", "subscriber address", "billing address", "service address"];
+
+/// Who a number is registered to, per the carrier's subscriber response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscriber {
+    pub name: String,
+    pub address: String,
+}
+
+/// One contact of the case and who it belongs to, if the subscriber results say.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution<'a> {
+    pub number: &'a str,
+    pub calls: usize,
+    pub subscriber: Option<&'a Subscriber>,
+}
+
+/// Subscriber results keyed by normalized number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberDirectory {
+    by_number: HashMap<String, Subscriber>,
+}
+
+impl SubscriberDirectory {
+    /// Reads a CSV of subscriber results, e.g. a spreadsheet saved as CSV. The first row
+    /// must name a number column and a name column; an address column is optional and any
+    /// other columns are ignored.
+    pub fn import_csv(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open subscriber results: {:?}", path))?;
+        let directory = Self::from_csv_reader(file)
+            .with_context(|| format!("Failed to read subscriber results: {:?}", path))?;
+        info!("Imported {} subscribers from {:?}", directory.len(), path);
+        Ok(directory)
+    }
+
+    pub fn from_csv_reader<R: Read>(reader: R) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let find = |names: &[&str]| headers.iter().position(|h| names.iter().any(|name| h.eq_ignore_ascii_case(name)));
+        let Some(number_col) = find(NUMBER_HEADERS) else {
+            bail!("No phone number column; expected a header such as \"Number\" or \"Phone Number\"");
+        };
+        let Some(name_col) = find(NAME_HEADERS) else {
+            bail!("No subscriber name column; expected a header such as \"Name\" or \"Subscriber\"");
+        };
+        let address_col = find(ADDRESS_HEADERS);
+
+        let mut directory = Self::default();
+        for (index, row) in reader.records().enumerate() {
+            let row = row?;
+            let raw_number = row.get(number_col).unwrap_or_default();
+            let number = normalize_phone_number(raw_number);
+            if !number.is_valid() {
+                // Row 1 is the header
+                warn!("Skipping subscriber row {}: {:?} is not a phone number", index + 2, raw_number);
+                continue;
+            }
+            directory.insert(number.as_str(), Subscriber {
+                name: row.get(name_col).unwrap_or_default().to_string(),
+                address: address_col.and_then(|col| row.get(col)).unwrap_or_default().to_string(),
+            });
+        }
+        Ok(directory)
+    }
+
+    /// Later imports win, so re-importing a corrected sheet replaces earlier answers.
+    pub fn merge(&mut self, other: SubscriberDirectory) {
+        self.by_number.extend(other.by_number);
+    }
+
+    pub fn insert(&mut self, normalized_number: &str, subscriber: Subscriber) {
+        self.by_number.insert(normalized_number.to_string(), subscriber);
+    }
+
+    pub fn lookup(&self, normalized_number: &str) -> Option<&Subscriber> {
+        self.by_number.get(normalized_number)
+    }
+
+    /// Every identifiable contact in `records`, busiest first, joined to its subscriber.
+    pub fn resolve<'a>(&'a self, records: &'a [ProcessedCallRecord]) -> Vec<Resolution<'a>> {
+        let mut calls: HashMap<&str, usize> = HashMap::new();
+        for record in records.iter().filter(|r| r.number_kind.identifies_party()) {
+            *calls.entry(record.normalized_number.as_str()).or_insert(0) += 1;
+        }
+        let mut resolutions: Vec<Resolution> = calls.into_iter()
+            .map(|(number, calls)| Resolution { number, calls, subscriber: self.lookup(number) })
+            .collect();
+        resolutions.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.number.cmp(b.number)));
+        resolutions
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_number.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_number.is_empty()
+    }
+}
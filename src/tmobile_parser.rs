@@ -0,0 +1,229 @@
+use crate::data_models::{normalize_phone_number, CallRecord, ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{
+    column_key, normalize_direction, parse_duration, parse_time, ColumnAliases, ColumnMap, DelimitedParser, MappedField,
+};
+use crate::pipeline::DuplicatePolicy;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::info;
+use std::fs;
+use std::path::Path;
+
+/// Column names in T-Mobile call detail. The direction is a switch code column.
+const TMOBILE_COLUMNS: ColumnAliases = ColumnAliases {
+    target: &["served msisdn", "msisdn", "target"],
+    direction: &["switch code", "call type code", "call type"],
+    remote: &["other party", "other party number"],
+    originating: &["calling number", "calling party", "a number"],
+    terminating: &["called number", "called party", "b number"],
+    start: &["start time utc", "start time", "start epoch", "event time utc", "seizure time utc"],
+    date: &[],
+    time: &[],
+    end: &["end time utc", "end time", "end epoch", "release time utc"],
+    duration: &["duration", "duration sec", "call duration"],
+    content: &[],
+    disposition: &["feature", "call feature", "feature code"],
+};
+
+/// Header names that mark a switch code column, which only T-Mobile's layout has.
+const SWITCH_CODE_COLUMNS: [&str; 2] = ["switch code", "call type code"];
+
+/// Element names in the XML envelope that hold the target.
+const TARGET_ELEMENTS: [&str; 4] = ["msisdn", "served msisdn", "target", "target number"];
+
+const CDATA_START: &[u8] = b"<![CDATA[";
+const CDATA_END: &[u8] = b"]]>";
+
+/// Reads T-Mobile call detail exports, which come either as plain delimited text or as an
+/// XML envelope with the request details in elements and the records as delimited text in
+/// a CDATA section. Times are seconds since the Unix epoch in UTC, and the direction is a
+/// switch code such as `MOC` or `MTC`.
+pub struct TMobileParser;
+
+impl TMobileParser {
+    pub fn parse_file(path: &Path, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing T-Mobile file: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, duplicates)
+    }
+
+    pub fn parse_bytes(bytes: &[u8], source_file: &str, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let (payload, envelope_target) = split_envelope(bytes);
+        let mut reader = DelimitedParser::reader(payload);
+        let headers = reader.headers().context("Failed to read the header row")?.clone();
+        let columns = ColumnMap::resolve(&headers, &TMOBILE_COLUMNS);
+        let target = envelope_target.unwrap_or_default();
+        let rows = reader.records().map(|row| {
+            row.map(|row| Self::call_record(&row, &columns, &target)).map_err(|e| e.to_string())
+        });
+        let (records, report) = DelimitedParser::collect_records(rows, source_file, duplicates);
+        info!("Parsed {} T-Mobile records", records.len());
+        Ok((records, report))
+    }
+
+    /// Whether `sample` is T-Mobile call detail: a header with a switch code column and the
+    /// columns every call record needs, bare or inside the XML envelope.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        let (payload, _) = split_envelope(sample);
+        let mut reader = DelimitedParser::reader(payload);
+        let Ok(headers) = reader.headers() else {
+            return false;
+        };
+        let switch_code = SWITCH_CODE_COLUMNS.iter().any(|name| headers.iter().any(|header| column_key(header) == column_key(name)));
+        switch_code && ColumnMap::resolve(headers, &TMOBILE_COLUMNS).is_complete()
+    }
+
+    fn call_record(row: &StringRecord, columns: &ColumnMap, envelope_target: &str) -> (String, CallRecord) {
+        let field = |mapped: MappedField| columns.column(mapped).and_then(|i| row.get(i)).unwrap_or("").trim();
+
+        let code = field(MappedField::Direction);
+        let direction = switch_direction(code).map_or_else(|| normalize_direction(code), str::to_string);
+        let outgoing = direction == "Outgoing";
+        let (remote_number, other_side) = match columns.column(MappedField::Remote) {
+            Some(_) => (field(MappedField::Remote), ""),
+            None if outgoing => (field(MappedField::Terminating), field(MappedField::Originating)),
+            None => (field(MappedField::Originating), field(MappedField::Terminating)),
+        };
+        let target = [field(MappedField::Target), envelope_target, other_side]
+            .into_iter()
+            .find(|number| !number.is_empty())
+            .map(|number| normalize_phone_number(number).into_string())
+            .unwrap_or_default();
+
+        let time = |mapped: MappedField| {
+            let value = field(mapped);
+            epoch_time(value).or_else(|| parse_time(value, false, Tz::UTC))
+        };
+        let start_text = field(MappedField::Start);
+        let start = time(MappedField::Start);
+        let end = time(MappedField::End);
+        let length_of_call = match columns.column(MappedField::Duration) {
+            Some(_) => parse_duration(field(MappedField::Duration)).unwrap_or(0),
+            None => match (start, end) {
+                (Some(start), Some(end)) => (end - start).num_seconds().max(0) as u32,
+                _ => 0,
+            },
+        };
+        let end = end.or_else(|| start.map(|start| start + Duration::seconds(length_of_call as i64)));
+
+        let call = CallRecord {
+            message_direction: direction,
+            remote_number: remote_number.to_string(),
+            start_time: start.map(|time| time.to_rfc3339()).unwrap_or_else(|| start_text.to_string()),
+            end_time: end.map(|time| time.to_rfc3339()).unwrap_or_default(),
+            length_of_call,
+            message_content: None,
+            // MOSMS, MTSMS and the like are text messages
+            record_type: code.to_ascii_uppercase().contains("SMS").then(|| "SMS".to_string()),
+            cell_site_id: None,
+            sector: None,
+            latitude: None,
+            longitude: None,
+            imei: None,
+            imsi: None,
+            // Forwarded calls are marked by their switch code when there is no feature column
+            disposition: [field(MappedField::Disposition), code].into_iter()
+                .find(|value| !value.is_empty())
+                .map(str::to_string),
+        };
+        (target, call)
+    }
+}
+
+/// A time given as seconds since the Unix epoch, or milliseconds when it has 13 digits.
+///
+/// ```
+/// use esubpoena_tolls_tool::tmobile_parser::epoch_time;
+///
+/// assert_eq!(epoch_time("1710425109").unwrap().to_rfc3339(), "2024-03-14T14:05:09+00:00");
+/// assert_eq!(epoch_time("1710425109000"), epoch_time("1710425109"));
+/// assert_eq!(epoch_time(" 1710425109 "), epoch_time("1710425109"));
+/// assert_eq!(epoch_time("03/14/2024 14:05:09"), None);
+/// assert_eq!(epoch_time(""), None);
+/// ```
+pub fn epoch_time(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let number: i64 = value.parse().ok()?;
+    if value.len() >= 13 {
+        DateTime::from_timestamp_millis(number)
+    } else {
+        DateTime::from_timestamp(number, 0)
+    }
+}
+
+/// The direction a T-Mobile switch code stands for: mobile-originated calls and texts are
+/// outgoing, mobile-terminated ones incoming, and call-forwarding legs forwarded. Case and
+/// surrounding space are ignored; other codes are not known.
+///
+/// ```
+/// use esubpoena_tolls_tool::tmobile_parser::switch_direction;
+///
+/// assert_eq!(switch_direction("MOC"), Some("Outgoing"));
+/// assert_eq!(switch_direction("mosms"), Some("Outgoing"));
+/// assert_eq!(switch_direction("MTC"), Some("Incoming"));
+/// assert_eq!(switch_direction(" MTSMS "), Some("Incoming"));
+/// assert_eq!(switch_direction("CFW"), Some("Forwarded"));
+/// assert_eq!(switch_direction("ROAM"), None);
+/// ```
+pub fn switch_direction(code: &str) -> Option<&'static str> {
+    match code.trim().to_ascii_uppercase().as_str() {
+        "MOC" | "MO" | "MOSMS" | "SMSMO" => Some("Outgoing"),
+        "MTC" | "MT" | "MTSMS" | "SMSMT" => Some("Incoming"),
+        "CFW" | "CF" | "FWD" => Some("Forwarded"),
+        _ => None,
+    }
+}
+
+/// The delimited records and, for the XML envelope, the target it names. Records in a CDATA
+/// section run to its end, or to the end of `bytes` when a sample cuts it off.
+fn split_envelope(bytes: &[u8]) -> (&[u8], Option<String>) {
+    let Some(start) = find(bytes, CDATA_START) else {
+        return (bytes, None);
+    };
+    let records = &bytes[start + CDATA_START.len()..];
+    let end = find(records, CDATA_END).unwrap_or(records.len());
+    let records = &records[..end];
+    // The payload often starts on the line after the CDATA marker
+    let records = records.strip_prefix(b"\r").unwrap_or(records);
+    let records = records.strip_prefix(b"\n").unwrap_or(records);
+    (records, envelope_target(&bytes[..start]))
+}
+
+/// The text of the first target element in the envelope before the records.
+fn envelope_target(envelope: &[u8]) -> Option<String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(envelope);
+    let mut buf = Vec::new();
+    let mut in_target = false;
+    loop {
+        match reader.read_event_into(&mut buf).ok()? {
+            Event::Start(e) => {
+                let name = column_key(&String::from_utf8_lossy(e.local_name().as_ref()));
+                in_target = TARGET_ELEMENTS.iter().any(|element| column_key(element) == name);
+            }
+            Event::Text(text) if in_target => {
+                let number = normalize_phone_number(&text.unescape().ok()?);
+                return number.is_valid().then(|| number.into_string());
+            }
+            Event::End(_) => in_target = false,
+            Event::Eof => return None,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
@@ -0,0 +1,83 @@
+use eframe::egui;
+use esubpoena_tolls_tool::tr;
+
+/// Synthetic production loaded by the guided walkthrough.
+pub const SAMPLE_DATASET: &str = include_str!("../assets/sample_production.xml");
+pub const SAMPLE_DATASET_NAME: &str = "sample_production.xml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    Welcome,
+    DropZone,
+    Tabs,
+    Filters,
+    Export,
+    Finished,
+}
+
+impl TutorialStep {
+    pub fn title(&self) -> String {
+        match self {
+            TutorialStep::Welcome => tr!("tutorial-welcome-title"),
+            TutorialStep::DropZone => tr!("tutorial-drop-zone-title"),
+            TutorialStep::Tabs => tr!("tutorial-tabs-title"),
+            TutorialStep::Filters => tr!("tutorial-filters-title"),
+            TutorialStep::Export => tr!("tutorial-export-title"),
+            TutorialStep::Finished => tr!("tutorial-finished-title"),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            TutorialStep::Welcome => tr!("tutorial-welcome-body"),
+            TutorialStep::DropZone => tr!("tutorial-drop-zone-body"),
+            TutorialStep::Tabs => tr!("tutorial-tabs-body"),
+            TutorialStep::Filters => tr!("tutorial-filters-body"),
+            TutorialStep::Export => tr!("tutorial-export-body"),
+            TutorialStep::Finished => tr!("tutorial-finished-body"),
+        }
+    }
+
+    pub fn next(&self) -> TutorialStep {
+        match self {
+            TutorialStep::Welcome => TutorialStep::DropZone,
+            TutorialStep::DropZone => TutorialStep::Tabs,
+            TutorialStep::Tabs => TutorialStep::Filters,
+            TutorialStep::Filters => TutorialStep::Export,
+            TutorialStep::Export | TutorialStep::Finished => TutorialStep::Finished,
+        }
+    }
+
+    /// The screen region this step points at, if it has been rendered this frame.
+    pub fn target(&self, anchors: &TutorialAnchors) -> Option<egui::Rect> {
+        match self {
+            TutorialStep::DropZone => anchors.drop_zone,
+            TutorialStep::Tabs => anchors.tab_bar,
+            TutorialStep::Filters => anchors.filter_bar,
+            TutorialStep::Export => anchors.export_button,
+            TutorialStep::Welcome | TutorialStep::Finished => None,
+        }
+    }
+}
+
+/// Screen rects of the widgets the walkthrough highlights, refreshed every frame.
+#[derive(Debug, Clone, Default)]
+pub struct TutorialAnchors {
+    pub drop_zone: Option<egui::Rect>,
+    pub tab_bar: Option<egui::Rect>,
+    pub filter_bar: Option<egui::Rect>,
+    pub export_button: Option<egui::Rect>,
+}
+
+/// Draws a pulsing outline around `rect` above all other UI.
+pub fn paint_highlight(ctx: &egui::Context, rect: egui::Rect) {
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("tutorial_highlight")));
+    let t = ctx.input(|i| i.time) as f32;
+    let alpha = (160.0 + 95.0 * (t * 3.0).sin()) as u8;
+    painter.rect_stroke(
+        rect.expand(4.0),
+        6.0,
+        egui::Stroke::new(3.0, egui::Color32::from_rgba_unmultiplied(255, 200, 0, alpha)),
+    );
+    ctx.request_repaint();
+}
@@ -0,0 +1,172 @@
+use crate::data_models::{ParseReport, ProcessedCallRecord};
+use crate::delimited_parser::{
+    column_key, preamble_target, sniff_delimiter, ColumnAliases, ColumnMap, DelimitedParser, MappedField, DELIMITERS,
+};
+use crate::pipeline::DuplicatePolicy;
+use anyhow::{bail, Context, Result};
+use chrono_tz::Tz;
+use csv::StringRecord;
+use log::info;
+use std::fs;
+use std::path::Path;
+
+/// Column names in Verizon's compliance reports. "Originating" and "Terminating" in the
+/// direction column are read as outgoing and incoming.
+const VERIZON_COLUMNS: ColumnAliases = ColumnAliases {
+    target: &["target", "target mdn", "target number", "mdn"],
+    direction: &["direction", "call direction", "orig term", "originating terminating"],
+    remote: &["other party", "other party number", "remote number"],
+    originating: &["originating number", "calling number", "from number"],
+    terminating: &["terminating number", "called number", "to number"],
+    start: &["date time", "start date time", "call date time", "message date time", "connection date time"],
+    date: &["date", "call date", "message date"],
+    time: &["time", "call time", "message time"],
+    end: &["end date time", "end time"],
+    duration: &["duration", "duration sec", "duration seconds", "call duration", "seconds"],
+    content: &["message", "message content", "message text"],
+    disposition: &["disposition", "call disposition", "feature", "features"],
+};
+
+/// Which kind of usage a section of the report lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Voice,
+    Sms,
+}
+
+impl SectionKind {
+    /// A section title, e.g. "Voice Usage" or "SMS Detail", on a line of its own.
+    fn of_title(title: &str) -> Option<Self> {
+        let key = column_key(title);
+        if key.contains("voice") || key.contains("call") {
+            Some(SectionKind::Voice)
+        } else if key.contains("sms") || key.contains("text") || key.contains("message") {
+            Some(SectionKind::Sms)
+        } else {
+            None
+        }
+    }
+}
+
+/// One usage section: its columns and the rows under them.
+struct Section {
+    kind: SectionKind,
+    columns: ColumnMap,
+    rows: Vec<StringRecord>,
+}
+
+impl Section {
+    /// Voice rows need the other party, a start, and a duration or end time; text messages
+    /// have no duration, so the first two do for them.
+    fn readable(&self) -> bool {
+        match self.kind {
+            SectionKind::Voice => self.columns.is_complete(),
+            SectionKind::Sms => {
+                let timed = self.columns.column(MappedField::Duration).is_some() || self.columns.column(MappedField::End).is_some();
+                self.columns.required_found() == if timed { 3 } else { 2 }
+            }
+        }
+    }
+}
+
+/// The report split into its sections, with the target named above them, if any.
+struct VerizonReport {
+    target: Option<String>,
+    sections: Vec<Section>,
+}
+
+impl VerizonReport {
+    /// Titles and headers are told apart from data by how many fields they fill: a title
+    /// line has one, and the first line after it with more is the section's header. Lines
+    /// with one field that is not a title, such as totals, are left out.
+    fn read(bytes: &[u8]) -> Self {
+        // Title lines have no delimiter to sniff
+        let header_line = bytes.split(|&b| b == b'\n')
+            .find(|line| line.iter().any(|b| DELIMITERS.contains(b)))
+            .unwrap_or(bytes);
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(sniff_delimiter(header_line))
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(bytes);
+
+        let mut report = Self { target: None, sections: Vec::new() };
+        let mut pending: Option<SectionKind> = None;
+        for row in reader.records().filter_map(|row| row.ok()) {
+            let filled: Vec<&str> = row.iter().filter(|field| !field.is_empty()).collect();
+            match filled.as_slice() {
+                [] => {}
+                [single] => {
+                    if let Some(kind) = SectionKind::of_title(single) {
+                        pending = Some(kind);
+                    } else if report.sections.is_empty() {
+                        report.target = report.target.take().or_else(|| preamble_target(single));
+                    }
+                }
+                _ => match pending.take() {
+                    Some(kind) => report.sections.push(Section {
+                        kind,
+                        columns: ColumnMap::resolve(&row, &VERIZON_COLUMNS),
+                        rows: Vec::new(),
+                    }),
+                    None => match report.sections.last_mut() {
+                        Some(section) => section.rows.push(row),
+                        None => report.target = report.target.take().or_else(|| preamble_target(&filled.join(","))),
+                    },
+                },
+            }
+        }
+        report.sections.retain(Section::readable);
+        report
+    }
+}
+
+/// Reads Verizon's subpoena compliance reports, which list voice calls and text messages
+/// in sections of their own, each under a title line and its own header row. Directions
+/// are given as originating and terminating, and times usually carry their UTC offset;
+/// ones that do not are taken to be in the timezone given.
+pub struct VerizonParser;
+
+impl VerizonParser {
+    pub fn parse_file(path: &Path, timezone: Tz, duplicates: DuplicatePolicy) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        info!("Parsing Verizon file: {:?}", path);
+        let bytes = fs::read(path)
+            .with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source_file = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+        Self::parse_bytes(&bytes, source_file, timezone, duplicates)
+    }
+
+    pub fn parse_bytes(
+        bytes: &[u8],
+        source_file: &str,
+        timezone: Tz,
+        duplicates: DuplicatePolicy,
+    ) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let report = VerizonReport::read(bytes);
+        if report.sections.is_empty() {
+            bail!("{} has no Verizon voice or SMS section", source_file);
+        }
+        let target = report.target.as_deref().unwrap_or_default();
+        let calls = report.sections.iter().flat_map(move |section| {
+            section.rows.iter().map(move |row| {
+                let (row_target, mut call) = DelimitedParser::call_record(row, &section.columns, false, timezone);
+                if section.kind == SectionKind::Sms {
+                    call.record_type = Some("SMS".to_string());
+                }
+                Ok((if row_target.is_empty() { target.to_string() } else { row_target }, call))
+            })
+        });
+        let (records, report) = DelimitedParser::collect_records(calls, source_file, duplicates);
+        info!("Parsed {} Verizon records", records.len());
+        Ok((records, report))
+    }
+
+    /// Whether `sample` is a Verizon compliance report: a voice or SMS section title followed
+    /// by a header whose columns can be read.
+    pub fn recognizes(sample: &[u8]) -> bool {
+        !VerizonReport::read(sample).sections.is_empty()
+    }
+}
@@ -0,0 +1,136 @@
+use crate::annotations::Annotations;
+use crate::carriers::CarrierDirectory;
+use crate::cnam::CallerNames;
+use crate::known_numbers::KnownNumbers;
+use crate::csv_exporter::CsvExporter;
+use crate::data_models::{Analytics, ParseReport, ProcessedCallRecord};
+use crate::excel_exporter::ExcelExporter;
+use crate::format_detection::is_production_file;
+use crate::scripting::ScriptOutput;
+use crate::settings::AppSettings;
+use crate::signing::ExportSignature;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Subfolder of the watch folder that auto-exports go to when no output directory is set.
+pub const DEFAULT_EXPORT_SUBFOLDER: &str = "exports";
+
+/// Polls a folder for productions that appear after watching started.
+///
+/// Files already in the folder are left alone, so restarting the app never re-ingests them.
+/// A new file is reported once its size has stayed the same across two polls, so a
+/// production still being copied in is not picked up half-written.
+pub struct FolderWatcher {
+    folder: PathBuf,
+    seen: HashSet<PathBuf>,
+    /// New files and their size at the previous poll.
+    pending: HashMap<PathBuf, u64>,
+}
+
+impl FolderWatcher {
+    pub fn new(folder: PathBuf) -> Self {
+        let seen = match productions_in(&folder) {
+            Ok(files) => files.into_iter().map(|(path, _)| path).collect(),
+            Err(e) => {
+                warn!("Cannot list watch folder {:?}: {:#}", folder, e);
+                HashSet::new()
+            }
+        };
+        info!("Watching {:?} for new productions", folder);
+        Self { folder, seen, pending: HashMap::new() }
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Productions that finished arriving since the last poll, in name order.
+    pub fn poll(&mut self) -> Result<Vec<PathBuf>> {
+        let mut ready = Vec::new();
+        for (path, size) in productions_in(&self.folder)? {
+            if self.seen.contains(&path) {
+                continue;
+            }
+            if size > 0 && self.pending.get(&path) == Some(&size) {
+                self.pending.remove(&path);
+                self.seen.insert(path.clone());
+                ready.push(path);
+            } else {
+                self.pending.insert(path, size);
+            }
+        }
+        ready.sort();
+        Ok(ready)
+    }
+}
+
+/// Production files directly inside `folder`, with their sizes.
+fn productions_in(folder: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let entries = fs::read_dir(folder)
+        .with_context(|| format!("Failed to read watch folder: {:?}", folder))?;
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_production = is_production_file(&path.to_string_lossy());
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if is_production && metadata.is_file() {
+            files.push((path, metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+/// Writes the Excel workbook and a CSV of the call records for a watched production.
+pub struct AutoExporter;
+
+impl AutoExporter {
+    /// Where auto-exports go: the configured directory, else [`DEFAULT_EXPORT_SUBFOLDER`]
+    /// under the watch folder. `None` when neither is set.
+    pub fn output_dir(settings: &AppSettings) -> Option<PathBuf> {
+        settings.auto_export_dir.clone()
+            .or_else(|| settings.watch_folder.as_ref().map(|folder| folder.join(DEFAULT_EXPORT_SUBFOLDER)))
+    }
+
+    /// Both files are named from the export template for the case's target, e.g.
+    /// `2565550100_20240301.xlsx` and `2565550100_20240301.csv`, and signed when signing is on.
+    /// Returns the written paths.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export(
+        records: &[ProcessedCallRecord],
+        parse_reports: &[ParseReport],
+        analytics: &Analytics,
+        annotations: &Annotations,
+        carriers: &CarrierDirectory,
+        caller_names: &CallerNames,
+        known_numbers: &KnownNumbers,
+        settings: &AppSettings,
+        script_outputs: &[ScriptOutput],
+        output_dir: &Path,
+    ) -> Result<Vec<PathBuf>> {
+        // Fail before writing anything if signing is on but the key is unusable
+        let signing_key = settings.signing_key()?;
+        fs::create_dir_all(output_dir)
+            .with_context(|| format!("Failed to create export directory: {:?}", output_dir))?;
+
+        let excel_path = output_dir.join(settings.case_export_file_name(&Utc::now(), analytics));
+        let csv_path = excel_path.with_extension("csv");
+        ExcelExporter::export_data(records, parse_reports, analytics, annotations, carriers, caller_names, known_numbers, settings, script_outputs, &excel_path)?;
+        CsvExporter::export_call_records(records, annotations, caller_names, settings, &csv_path)?;
+        let mut paths = vec![excel_path, csv_path];
+        if let Some(key) = &signing_key {
+            let signatures = paths.iter()
+                .map(|path| ExportSignature::sign_file(path, key))
+                .collect::<Result<Vec<_>>>()?;
+            paths.extend(signatures);
+        }
+
+        info!("Auto-exported {} records to {:?}", records.len(), output_dir);
+        Ok(paths)
+    }
+}
@@ -0,0 +1,54 @@
+//! Browser-only glue: the file picker and client-side downloads. Files are read and written
+//! entirely inside the page, so no case data leaves the machine.
+
+use esubpoena_tolls_tool::format_detection::PRODUCTION_EXTENSIONS;
+use esubpoena_tolls_tool::tr;
+use std::sync::mpsc::Sender;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// A file chosen in the browser picker, read fully into memory.
+pub struct PickedFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Opens the browser file picker. Sends the chosen files once the picker closes; an empty
+/// list means the user cancelled.
+pub fn pick_files(sender: Sender<Vec<PickedFile>>) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let handles = rfd::AsyncFileDialog::new()
+            .add_filter(tr!("filter-productions"), &PRODUCTION_EXTENSIONS)
+            .add_filter(tr!("filter-xml"), &["xml"])
+            .pick_files()
+            .await
+            .unwrap_or_default();
+
+        let mut files = Vec::with_capacity(handles.len());
+        for handle in handles {
+            files.push(PickedFile {
+                name: handle.file_name(),
+                bytes: handle.read().await,
+            });
+        }
+        let _ = sender.send(files);
+    });
+}
+
+/// Hands `bytes` to the browser as a download named `file_name`.
+pub fn download(file_name: &str, mime_type: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document available"))?;
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
@@ -0,0 +1,221 @@
+use crate::annotations::Annotations;
+use crate::data_models::{local_to_utc, normalize_phone_number, CallRecord, ProcessedCallRecord};
+use crate::i18n;
+use crate::session::SessionSnapshot;
+use crate::subscribers::Subscriber;
+use anyhow::{bail, Context, Result};
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use log::{info, warn};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Columns of the Call Records sheet, as written by the Excel exporter.
+const COL_DIRECTION: usize = 0;
+const COL_TARGET: usize = 1;
+const COL_REMOTE: usize = 2;
+const COL_START: usize = 4;
+const COL_END: usize = 5;
+const COL_SECONDS: usize = 6;
+const COL_SOURCE: usize = 9;
+
+/// Columns of the Bookmarks sheet.
+const BOOKMARK_COL_START: usize = 0;
+const BOOKMARK_COL_TARGET: usize = 2;
+const BOOKMARK_COL_REMOTE: usize = 3;
+const BOOKMARK_COL_SECONDS: usize = 4;
+
+/// Columns of the Subscriber Resolution sheet.
+const SUBSCRIBER_COL_NUMBER: usize = 0;
+const SUBSCRIBER_COL_NAME: usize = 2;
+const SUBSCRIBER_COL_ADDRESS: usize = 3;
+const SUBSCRIBER_COL_ALIAS: usize = 7;
+
+/// Rebuilds a session from a workbook written by [`ExcelExporter`](crate::excel_exporter::ExcelExporter),
+/// for when the case file is gone but an export survives.
+///
+/// Records, with their tags and notes, come from the Call Records sheet, bookmarks from the
+/// Bookmarks sheet, and subscriber results and aliases from the Subscriber Resolution sheet.
+/// Sheets and the Tags and Notes columns are found by their name in any language. The
+/// watchlist is not exported, so it cannot be recovered, and neither can source hashes, so
+/// analytics are recomputed rather than taken from the cache.
+pub fn import_workbook(path: &Path) -> Result<SessionSnapshot> {
+    let mut workbook: Xlsx<_> = open_workbook(path)
+        .with_context(|| format!("Failed to open workbook: {:?}", path))?;
+    let sheet_names = workbook.sheet_names();
+    let find_sheet = |id: &str| {
+        let names = i18n::translations(id);
+        sheet_names.iter().find(|sheet| names.contains(sheet)).cloned()
+    };
+
+    let Some(records_sheet) = find_sheet("sheet-call-records") else {
+        bail!("No Call Records sheet; only workbooks exported by this tool can be opened");
+    };
+    let range = workbook.worksheet_range(&records_sheet)
+        .with_context(|| format!("Failed to read sheet {:?}", records_sheet))?;
+    let mut rows = range.rows();
+    let header = rows.next().context("The Call Records sheet is empty")?;
+    // Exports with native time cells name the case timezone in the start time header;
+    // older ones wrote UTC text
+    let timezone = header.get(COL_START).and_then(|cell| header_timezone(&cell.to_string())).unwrap_or(Tz::UTC);
+    // The Tags and Notes columns follow the optional tower and device columns, when written
+    let column = |id: &str| {
+        let names = i18n::translations(id);
+        header.iter().position(|cell| names.contains(&cell.to_string()))
+    };
+    let (tag_col, note_col) = (column("header-tags"), column("header-notes"));
+    let tagged = i18n::translations("value-yes");
+
+    let mut records = Vec::new();
+    let mut annotations = Annotations::default();
+    for (index, row) in rows.enumerate() {
+        // Row 1 is the header
+        match record_from_row(row, timezone) {
+            Ok(record) => {
+                let record_key = record.record_key();
+                if let Some(tags) = tag_col.map(|col| cell_text(row, col)).filter(|tags| !tags.is_empty()) {
+                    // A record tagged without labels is exported as "Yes"
+                    if tagged.contains(&tags) {
+                        annotations.add_tag(&record_key, "");
+                    } else {
+                        tags.split(", ").for_each(|label| annotations.add_tag(&record_key, label));
+                    }
+                }
+                if let Some(col) = note_col {
+                    annotations.set_note(&record_key, &cell_text(row, col));
+                }
+                records.push(record);
+            }
+            Err(e) => warn!("Skipping Call Records row {}: {:#}", index + 2, e),
+        }
+    }
+    if records.is_empty() {
+        bail!("No call records could be read from {:?}", records_sheet);
+    }
+
+    if let Some(sheet) = find_sheet("sheet-bookmarks") {
+        let range = workbook.worksheet_range(&sheet)
+            .with_context(|| format!("Failed to read sheet {:?}", sheet))?;
+        let bookmarked: HashSet<(String, String, DateTime<Utc>, u32)> = range.rows().skip(1)
+            .filter_map(|row| bookmark_key(row, timezone))
+            .collect();
+        for record in &records {
+            let key = (record.target_number.clone(), record.normalized_number.clone(), record.start_time, record.length_of_call);
+            if bookmarked.contains(&key) {
+                annotations.bookmarks.insert(record.record_key());
+            }
+        }
+    }
+    if let Some(sheet) = find_sheet("sheet-subscribers") {
+        let range = workbook.worksheet_range(&sheet)
+            .with_context(|| format!("Failed to read sheet {:?}", sheet))?;
+        let unresolved = i18n::translations("sheet-unresolved");
+        for row in range.rows().skip(1) {
+            let number = normalize_phone_number(&cell_text(row, SUBSCRIBER_COL_NUMBER));
+            if !number.is_valid() {
+                continue;
+            }
+            let name = cell_text(row, SUBSCRIBER_COL_NAME);
+            if !name.is_empty() && !unresolved.contains(&name) {
+                annotations.subscribers.insert(number.as_str(), Subscriber { name, address: cell_text(row, SUBSCRIBER_COL_ADDRESS) });
+            }
+            let alias = cell_text(row, SUBSCRIBER_COL_ALIAS);
+            if !alias.is_empty() {
+                annotations.aliases.insert(number.into_string(), alias);
+            }
+        }
+    }
+
+    info!(
+        "Rebuilt {} records, {} tagged, {} bookmarks, and {} subscribers from {:?}",
+        records.len(), annotations.tagged_records.len(), annotations.bookmarks.len(), annotations.subscribers.len(), path
+    );
+    Ok(SessionSnapshot::new(Arc::new(records), Vec::new(), Vec::new(), annotations))
+}
+
+fn record_from_row(row: &[Data], timezone: Tz) -> Result<ProcessedCallRecord> {
+    let start = cell_time(row, COL_START, timezone).context("no start time")?;
+    let end = cell_time(row, COL_END, timezone).context("no end time")?;
+    let length_of_call = cell_seconds(row, COL_SECONDS).context("no duration")?;
+    let call = CallRecord {
+        message_direction: cell_text(row, COL_DIRECTION),
+        remote_number: cell_text(row, COL_REMOTE),
+        start_time: start.to_rfc3339(),
+        end_time: end.to_rfc3339(),
+        length_of_call,
+        message_content: None,
+        record_type: None,
+        cell_site_id: None,
+        sector: None,
+        latitude: None,
+        longitude: None,
+        imei: None,
+        imsi: None,
+        disposition: None,
+    };
+    ProcessedCallRecord::from_call_record(&call, &target_number(&cell_text(row, COL_TARGET)), &cell_text(row, COL_SOURCE))
+        .map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Target, remote number, start, and length of a bookmarked call, compared against the
+/// rebuilt records.
+fn bookmark_key(row: &[Data], timezone: Tz) -> Option<(String, String, DateTime<Utc>, u32)> {
+    let start = cell_time(row, BOOKMARK_COL_START, timezone)?;
+    let seconds = cell_seconds(row, BOOKMARK_COL_SECONDS)?;
+    let remote = normalize_phone_number(&cell_text(row, BOOKMARK_COL_REMOTE)).into_string();
+    Some((target_number(&cell_text(row, BOOKMARK_COL_TARGET)), remote, start, seconds))
+}
+
+/// Targets are exported in the display format; digits are what the parser stores.
+fn target_number(cell: &str) -> String {
+    let number = normalize_phone_number(cell);
+    if number.is_valid() {
+        number.into_string()
+    } else {
+        cell.to_string()
+    }
+}
+
+fn cell_text(row: &[Data], col: usize) -> String {
+    match row.get(col) {
+        Some(Data::Empty) | None => String::new(),
+        Some(cell) => cell.to_string().trim().to_string(),
+    }
+}
+
+fn cell_seconds(row: &[Data], col: usize) -> Option<u32> {
+    match row.get(col)? {
+        Data::Int(seconds) => u32::try_from(*seconds).ok(),
+        Data::Float(seconds) if *seconds >= 0.0 => Some(seconds.round() as u32),
+        Data::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A date-time cell in `timezone`, or a `YYYY-MM-DD HH:MM:SS` UTC text cell from older exports.
+fn cell_time(row: &[Data], col: usize, timezone: Tz) -> Option<DateTime<Utc>> {
+    let local = match row.get(col)? {
+        Data::DateTime(time) => excel_serial_to_naive(time.as_f64())?,
+        Data::Float(serial) => excel_serial_to_naive(*serial)?,
+        Data::String(text) => {
+            let naive = NaiveDateTime::parse_from_str(text.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+            return Some(naive.and_utc());
+        }
+        _ => return None,
+    };
+    local_to_utc(&local, timezone)
+}
+
+/// Excel serial dates count days from 1899-12-30, with the time of day as the fraction.
+pub(crate) fn excel_serial_to_naive(serial: f64) -> Option<NaiveDateTime> {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    epoch.checked_add_signed(Duration::milliseconds((serial * 86_400_000.0).round() as i64))
+}
+
+/// The timezone named in parentheses at the end of a header such as "Start Time (America/Chicago)".
+fn header_timezone(header: &str) -> Option<Tz> {
+    let (_, rest) = header.rsplit_once('(')?;
+    rest.strip_suffix(')')?.trim().parse().ok()
+}
@@ -1,25 +1,32 @@
-use crate::data_models::{CallRecord, DataProduct, Lds101Results, ProcessedCallRecord};
-use anyhow::{Context, Result};
+use crate::data_models::{
+    parse_declared_date, CallRecord, DataProduct, Lds101Results, MalformedBlock, ParseReport, ProcessedCallRecord,
+    ProductionHeader,
+};
+use crate::delimited_parser::target_number;
+use crate::pipeline::{Pipeline, PipelineConfig};
+use anyhow::Result;
 use log::{info, warn};
 use quick_xml::de::from_str;
-use std::fs;
+use quick_xml::events::{BytesText, Event};
+use quick_xml::Reader;
+use std::io::BufRead;
 use std::path::Path;
 
 pub struct XmlParser;
 
 impl XmlParser {
+    /// Streams the file through the [`Pipeline`], so it is never read into memory whole;
+    /// only the records it yields are kept. To keep memory bounded for any size of
+    /// production, or to follow progress, run the pipeline with a [`RecordSink`] of your own
+    /// instead, e.g. [`Pipeline::run_file_with_progress`].
+    ///
+    /// [`RecordSink`]: crate::pipeline::RecordSink
     pub fn parse_file(file_path: &Path) -> Result<Vec<ProcessedCallRecord>> {
         info!("Parsing XML file: {:?}", file_path);
         
-        let content = fs::read_to_string(file_path)
-            .with_context(|| format!("Failed to read file: {:?}", file_path))?;
-        
-        let source_file = file_path.file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-        
-        Self::parse_content_with_source(&content, &source_file)
+        let mut records = Vec::new();
+        Pipeline::run_file(file_path, &mut records, PipelineConfig::default())?;
+        Ok(records)
     }
     
     pub fn parse_content(content: &str) -> Result<Vec<ProcessedCallRecord>> {
@@ -27,6 +34,21 @@ impl XmlParser {
     }
     
     pub fn parse_content_with_source(content: &str, source_file: &str) -> Result<Vec<ProcessedCallRecord>> {
+        Self::parse_content_with_report(content, source_file).map(|(records, _)| records)
+    }
+    
+    /// Like [`XmlParser::parse_content_with_source`], also reporting the records that had to be skipped.
+    pub fn parse_content_with_report(content: &str, source_file: &str) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        Self::parse_content_with(content, source_file, false)
+    }
+    
+    /// Like [`XmlParser::parse_content_with_report`], but `<results>` blocks that are not
+    /// well-formed XML are passed over and reported rather than failing the whole parse.
+    pub fn parse_content_recovering(content: &str, source_file: &str) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        Self::parse_content_with(content, source_file, true)
+    }
+    
+    fn parse_content_with(content: &str, source_file: &str, recover: bool) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
         // Try to parse as DataProduct first
         if let Ok(data_product) = from_str::<DataProduct>(content) {
             return Self::process_data_product_with_source(data_product, source_file);
@@ -38,100 +60,114 @@ impl XmlParser {
         }
         
         // If both fail, try to extract results from the XML manually
-        Self::parse_manual_with_source(content, source_file)
+        Self::parse_manual_with_source(content, source_file, recover)
     }
     
-    fn process_data_product(data_product: DataProduct) -> Result<Vec<ProcessedCallRecord>> {
-        Self::process_lds_results_with_source(data_product.xml_result, "unknown")
-    }
-    
-    fn process_data_product_with_source(data_product: DataProduct, source_file: &str) -> Result<Vec<ProcessedCallRecord>> {
+    fn process_data_product_with_source(data_product: DataProduct, source_file: &str) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
         Self::process_lds_results_with_source(data_product.xml_result, source_file)
     }
     
-    fn process_lds_results(lds_results: Lds101Results) -> Result<Vec<ProcessedCallRecord>> {
-        Self::process_lds_results_with_source(lds_results, "unknown")
-    }
-    
-    fn process_lds_results_with_source(lds_results: Lds101Results, source_file: &str) -> Result<Vec<ProcessedCallRecord>> {
+    fn process_lds_results_with_source(lds_results: Lds101Results, source_file: &str) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
         info!("Processing {} call records", lds_results.results.len());
         
-        let mut processed_records = Vec::new();
+        let mut processed_records = Vec::with_capacity(lds_results.results.len());
+        let mut report = ParseReport::new(source_file);
+        report.declare(&lds_results.header());
+        let target = target_number(&lds_results.target_value);
         
         for (index, call_record) in lds_results.results.iter().enumerate() {
-            match ProcessedCallRecord::from_call_record(call_record, &lds_results.target_value, source_file) {
+            match ProcessedCallRecord::from_call_record(call_record, &target, source_file) {
                 Ok(processed) => {
+                    report.record_parsed(&processed);
                     processed_records.push(processed);
                 }
                 Err(e) => {
                     warn!("Failed to process call record {}: {}", index, e);
+                    report.record_skipped(call_record, &target, e.to_string());
                 }
             }
         }
         
-        info!("Successfully processed {} call records", processed_records.len());
-        Ok(processed_records)
+        info!("Successfully processed {} call records, skipped {}", processed_records.len(), report.skipped_count);
+        log_discrepancies(&report);
+        Ok((processed_records, report))
     }
     
-    fn parse_manual(content: &str) -> Result<Vec<ProcessedCallRecord>> {
-        Self::parse_manual_with_source(content, "unknown")
+    fn parse_manual_with_source(content: &str, source_file: &str, recover: bool) -> Result<(Vec<ProcessedCallRecord>, ParseReport)> {
+        let mut processed_records = Vec::new();
+        let mut report = ParseReport::new(source_file);
+        let mut malformed = Vec::new();
+        // The header as written and its normalized target, so the target is normalized once
+        let mut target = (String::new(), String::new());
+        
+        let on_record = |header: &ProductionHeader, call_record: CallRecord| {
+            report.declare(header);
+            if target.0 != header.target_value {
+                target = (header.target_value.clone(), target_number(&header.target_value));
+            }
+            match ProcessedCallRecord::from_call_record(&call_record, &target.1, source_file) {
+                Ok(processed) => {
+                    report.record_parsed(&processed);
+                    processed_records.push(processed);
+                }
+                Err(e) => {
+                    warn!("Failed to process manually parsed record: {}", e);
+                    report.record_skipped(&call_record, &target.1, e.to_string());
+                }
+            }
+            Ok(())
+        };
+        if recover {
+            Self::recover_call_records(content.as_bytes(), on_record, |block| malformed.push(block))?;
+        } else {
+            Self::stream_call_records(content.as_bytes(), on_record)?;
+        }
+        for block in malformed {
+            warn!("Skipped malformed record at line {}: {}", block.line, block.reason);
+            report.record_malformed(block);
+        }
+        
+        info!("Manually parsed {} call records, skipped {}", report.total_records, report.skipped_count);
+        log_discrepancies(&report);
+        Ok((processed_records, report))
     }
     
-    fn parse_manual_with_source(content: &str, source_file: &str) -> Result<Vec<ProcessedCallRecord>> {
-        use quick_xml::events::Event;
-        use quick_xml::Reader;
-        
-        let mut reader = Reader::from_str(content);
+    /// Walks an LDS-101 document event by event, handing each `<results>` entry to
+    /// `on_record` together with the header fields (`<targetValue>`, `<fromDate>`, ...) read
+    /// so far. Nothing beyond the current record is kept in memory, so this is safe for
+    /// arbitrarily large productions.
+    pub fn stream_call_records<R, F>(source: R, mut on_record: F) -> Result<()>
+    where
+        R: BufRead,
+        F: FnMut(&ProductionHeader, CallRecord) -> Result<()>,
+    {
+        let mut reader = Reader::from_reader(source);
         reader.trim_text(true);
         
-        let mut buf = Vec::new();
-        let mut call_records = Vec::new();
+        // Element names are compared as bytes so no event allocates except the text we keep
+        let mut buf = Vec::with_capacity(1024);
         let mut current_record: Option<CallRecord> = None;
-        let mut current_element = String::new();
-        let mut target_value = String::new();
+        let mut current_element = Element::Other;
+        let mut header = ProductionHeader::default();
         
         loop {
             match reader.read_event_into(&mut buf)? {
                 Event::Start(ref e) => {
-                    current_element = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    current_element = Element::from_name(e.name().as_ref());
                     
-                    if current_element == "results" {
-                        current_record = Some(CallRecord {
-                            message_direction: String::new(),
-                            remote_number: String::new(),
-                            start_time: String::new(),
-                            end_time: String::new(),
-                            length_of_call: 0,
-                        });
+                    if current_element == Element::Results {
+                        current_record = Some(CallRecord::default());
                     }
                 }
                 Event::Text(e) => {
-                    let text = String::from_utf8_lossy(&e).to_string();
-                    
-                    if let Some(ref mut record) = current_record {
-                        match current_element.as_str() {
-                            "messageDirection" => record.message_direction = text,
-                            "remoteNumber" => record.remote_number = text,
-                            "startTime" => record.start_time = text,
-                            "endTime" => record.end_time = text,
-                            "lengthOfCall" => {
-                                if let Ok(length) = text.parse::<u32>() {
-                                    record.length_of_call = length;
-                                }
-                            }
-                            _ => {}
-                        }
-                    } else if current_element == "targetValue" {
-                        target_value = text;
+                    match current_record {
+                        Some(ref mut record) => read_record_text(record, current_element, &e),
+                        None => read_header_text(&mut header, current_element, &e),
                     }
                 }
-                Event::End(ref e) => {
-                    let end_element = String::from_utf8_lossy(e.name().as_ref()).to_string();
-                    
-                    if end_element == "results" {
-                        if let Some(record) = current_record.take() {
-                            call_records.push(record);
-                        }
+                Event::End(ref e) if e.name().as_ref() == b"results" => {
+                    if let Some(record) = current_record.take() {
+                        on_record(&header, record)?;
                     }
                 }
                 Event::Eof => break,
@@ -141,16 +177,244 @@ impl XmlParser {
             buf.clear();
         }
         
-        info!("Manually parsed {} call records", call_records.len());
+        Ok(())
+    }
+    
+    /// Like [`XmlParser::stream_call_records`], but for productions with corrupt records: each
+    /// `<results>` block is read on its own, and one that is not well-formed XML, or is never
+    /// closed, goes to `on_malformed` while reading carries on with the next. Only the current
+    /// block is kept in memory.
+    pub fn recover_call_records<R, F, M>(mut source: R, mut on_record: F, mut on_malformed: M) -> Result<()>
+    where
+        R: BufRead,
+        F: FnMut(&ProductionHeader, CallRecord) -> Result<()>,
+        M: FnMut(MalformedBlock),
+    {
+        let mut header = ProductionHeader::default();
+        // Text between blocks, where the header elements are
+        let mut outside = Vec::new();
+        // The open block: its first line and its bytes so far
+        let mut block: Option<(usize, Vec<u8>)> = None;
+        let mut position = 0;
+        let mut line = 1;
+        let mut piece = Vec::with_capacity(1024);
         
-        let mut processed_records = Vec::new();
-        for call_record in call_records {
-            match ProcessedCallRecord::from_call_record(&call_record, &target_value, source_file) {
-                Ok(processed) => processed_records.push(processed),
-                Err(e) => warn!("Failed to process manually parsed record: {}", e),
+        // Each piece runs up to and including the next `>`, so it ends with at most one tag
+        loop {
+            piece.clear();
+            if source.read_until(b'>', &mut piece)? == 0 {
+                break;
+            }
+            let piece_line = line;
+            line += count_lines(&piece);
+            let tag_start = piece.iter().rposition(|&b| b == b'<').unwrap_or(piece.len());
+            let (before, tag) = piece.split_at(tag_start);
+            
+            if is_results_tag(tag, false) {
+                match block.take() {
+                    Some((start, _)) => {
+                        position += 1;
+                        on_malformed(MalformedBlock {
+                            position,
+                            line: start,
+                            element: "results".to_string(),
+                            reason: "<results> is not closed before the next one starts".to_string(),
+                        });
+                    }
+                    None => {
+                        outside.extend_from_slice(before);
+                        read_header(&outside, &mut header);
+                        outside.clear();
+                    }
+                }
+                block = Some((piece_line + count_lines(before), tag.to_vec()));
+            } else if let Some((start, mut bytes)) = block.take() {
+                bytes.extend_from_slice(&piece);
+                if !is_results_tag(tag, true) {
+                    block = Some((start, bytes));
+                    continue;
+                }
+                position += 1;
+                match read_block(&bytes, start) {
+                    Ok(record) => on_record(&header, record)?,
+                    Err((element, line, reason)) => on_malformed(MalformedBlock { position, line, element, reason }),
+                }
+            } else {
+                outside.extend_from_slice(&piece);
             }
         }
         
-        Ok(processed_records)
+        if let Some((start, _)) = block {
+            on_malformed(MalformedBlock {
+                position: position + 1,
+                line: start,
+                element: "results".to_string(),
+                reason: "the file ends inside <results>".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Reads `text` of `element` into the record being built.
+fn read_record_text(record: &mut CallRecord, element: Element, text: &BytesText) {
+    let lossy = || String::from_utf8_lossy(text).into_owned();
+    match element {
+        Element::MessageDirection => record.message_direction = lossy(),
+        Element::RemoteNumber => record.remote_number = lossy(),
+        Element::StartTime => record.start_time = lossy(),
+        Element::EndTime => record.end_time = lossy(),
+        Element::MessageContent => {
+            let text = text.unescape().map(|text| text.into_owned()).unwrap_or_else(|_| lossy());
+            record.message_content = Some(text);
+        }
+        Element::MessageType => record.record_type = Some(lossy()),
+        Element::CellSiteId => record.cell_site_id = Some(lossy()),
+        Element::Sector => record.sector = Some(lossy()),
+        Element::Latitude => record.latitude = Some(lossy()),
+        Element::Longitude => record.longitude = Some(lossy()),
+        Element::Imei => record.imei = Some(lossy()),
+        Element::Imsi => record.imsi = Some(lossy()),
+        Element::Disposition => record.disposition = Some(lossy()),
+        Element::LengthOfCall => {
+            if let Some(length) = std::str::from_utf8(text).ok().and_then(|text| text.parse::<u32>().ok()) {
+                record.length_of_call = length;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Reads `text` of `element`, outside any record, into the production's header.
+fn read_header_text(header: &mut ProductionHeader, element: Element, text: &BytesText) {
+    match element {
+        Element::TargetValue => header.target_value = String::from_utf8_lossy(text).into_owned(),
+        Element::FromDate => header.from_date = parse_declared_date(&String::from_utf8_lossy(text), false),
+        Element::ToDate => header.to_date = parse_declared_date(&String::from_utf8_lossy(text), true),
+        Element::RecordCount => {
+            header.expected_records = std::str::from_utf8(text).ok().and_then(|text| text.trim().parse().ok());
+        }
+        _ => {}
+    }
+}
+
+/// Reads the header elements in the text between two blocks. The text starts and ends
+/// part-way through the document, so unmatched tags are expected; reading stops at anything
+/// worse.
+fn read_header(outside: &[u8], header: &mut ProductionHeader) {
+    let mut reader = Reader::from_reader(outside);
+    reader.trim_text(true);
+    reader.check_end_names(false);
+    let mut element = Element::Other;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => element = Element::from_name(e.name().as_ref()),
+            Ok(Event::Text(e)) => read_header_text(header, element, &e),
+            Ok(Event::End(_)) => element = Element::Other,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+}
+
+/// Reads one `<results>` block starting on line `start`, or says where and why it is not
+/// well-formed: the innermost open element, the line, and the XML error.
+fn read_block(bytes: &[u8], start: usize) -> Result<CallRecord, (String, usize, String)> {
+    let mut reader = Reader::from_reader(bytes);
+    reader.trim_text(true);
+    let mut record = CallRecord::default();
+    let mut open: Vec<String> = Vec::new();
+    loop {
+        let event = reader.read_event();
+        let line = || start + count_lines(&bytes[..reader.buffer_position().min(bytes.len())]);
+        match event {
+            Ok(Event::Start(e)) => open.push(String::from_utf8_lossy(e.name().as_ref()).into_owned()),
+            Ok(Event::Text(e)) => {
+                let element = open.last().map_or(Element::Other, |name| Element::from_name(name.as_bytes()));
+                read_record_text(&mut record, element, &e);
+            }
+            Ok(Event::End(_)) => {
+                open.pop();
+            }
+            Ok(Event::Eof) => return Ok(record),
+            Err(e) => {
+                let element = open.last().cloned().unwrap_or_else(|| "results".to_string());
+                return Err((element, line(), e.to_string()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `tag` is a `<results>` start tag or, with `end`, a `</results>` end tag. An
+/// empty `<results/>` holds no record, so it is neither.
+fn is_results_tag(tag: &[u8], end: bool) -> bool {
+    let name = if end { tag.strip_prefix(b"</") } else { tag.strip_prefix(b"<") };
+    let named = name.and_then(|name| name.strip_prefix(b"results"))
+        .and_then(|rest| rest.first())
+        .is_some_and(|&next| next == b'>' || next.is_ascii_whitespace());
+    named && !tag.ends_with(b"/>")
+}
+
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b == b'\n').count()
+}
+
+/// Elements [`XmlParser::stream_call_records`] reads text from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Element {
+    Results,
+    TargetValue,
+    FromDate,
+    ToDate,
+    RecordCount,
+    MessageDirection,
+    RemoteNumber,
+    StartTime,
+    EndTime,
+    LengthOfCall,
+    MessageContent,
+    MessageType,
+    CellSiteId,
+    Sector,
+    Latitude,
+    Longitude,
+    Imei,
+    Imsi,
+    Disposition,
+    Other,
+}
+
+impl Element {
+    fn from_name(name: &[u8]) -> Self {
+        match name {
+            b"results" => Element::Results,
+            b"targetValue" => Element::TargetValue,
+            b"fromDate" => Element::FromDate,
+            b"toDate" => Element::ToDate,
+            b"recordCount" | b"totalRecords" => Element::RecordCount,
+            b"messageDirection" => Element::MessageDirection,
+            b"remoteNumber" => Element::RemoteNumber,
+            b"startTime" => Element::StartTime,
+            b"endTime" => Element::EndTime,
+            b"lengthOfCall" => Element::LengthOfCall,
+            b"messageContent" | b"messageBody" => Element::MessageContent,
+            b"messageType" | b"recordType" => Element::MessageType,
+            b"cellSiteId" | b"cellId" | b"cellSite" => Element::CellSiteId,
+            b"sector" | b"sectorId" => Element::Sector,
+            b"latitude" | b"lat" => Element::Latitude,
+            b"longitude" | b"lon" | b"long" => Element::Longitude,
+            b"imei" | b"IMEI" => Element::Imei,
+            b"imsi" | b"IMSI" => Element::Imsi,
+            b"callDisposition" | b"disposition" | b"featureCode" | b"callFeature" => Element::Disposition,
+            _ => Element::Other,
+        }
+    }
+}
+
+/// Warns about a production that disagrees with its own declared coverage.
+pub(crate) fn log_discrepancies(report: &ParseReport) {
+    for discrepancy in report.discrepancies() {
+        warn!("{}: {:?}", report.source_file, discrepancy);
     }
-} 
\ No newline at end of file
+}